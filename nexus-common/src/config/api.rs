@@ -2,7 +2,7 @@ use std::net::{IpAddr, Ipv4Addr};
 use std::{fmt::Debug, net::SocketAddr};
 
 use super::file::ConfigLoader;
-use super::{default_stack, DaemonConfig, StackConfig};
+use super::{default_stack, ConfigValidationError, DaemonConfig, StackConfig};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -10,6 +10,132 @@ use serde::{Deserialize, Serialize};
 pub const DEFAULT_LOCAL_IP: [u8; 4] = [127, 0, 0, 1];
 pub const DEFAULT_ICANN_LOCAL_PORT: u16 = 8080;
 pub const DEFAULT_PUBKY_LOCAL_PORT: u16 = 8081;
+/// Default for [ApiConfig::min_engagement]
+pub const DEFAULT_MIN_ENGAGEMENT: u64 = 0;
+/// Default for [ApiConfig::max_stream_tags]
+pub const DEFAULT_MAX_STREAM_TAGS: usize = 5;
+
+/// Cross-origin requests accepted from browser clients. An empty list allows any value, so the
+/// default config is permissive, e.g. for local development
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, e.g. `https://app.example.com`
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// HTTP methods allowed in cross-origin requests, e.g. `GET`
+    #[serde(default)]
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed in cross-origin requests
+    #[serde(default)]
+    pub allowed_headers: Vec<String>,
+    /// Allow cross-origin requests with credentials (cookies or authorization headers). The
+    /// `allowed_origins` must then be listed, and any method or header is only allowed by
+    /// mirroring the request
+    #[serde(default)]
+    pub allow_credentials: bool,
+}
+
+impl CorsConfig {
+    pub(crate) fn validate(&self) -> Result<(), ConfigValidationError> {
+        for origin in &self.allowed_origins {
+            let host = origin
+                .strip_prefix("https://")
+                .or_else(|| origin.strip_prefix("http://"));
+            if !host.is_some_and(|host| !host.is_empty() && !host.contains('/')) {
+                return Err(ConfigValidationError::new(
+                    "allowed_origins",
+                    format!("`{origin}` is not an origin like `https://app.example.com`"),
+                ));
+            }
+        }
+        // Mirroring any origin would let every site send credentialed requests
+        if self.allow_credentials && self.allowed_origins.is_empty() {
+            return Err(ConfigValidationError::new(
+                "allow_credentials",
+                "credentials can only be allowed for the listed `allowed_origins`",
+            ));
+        }
+        let is_token = |value: &String| {
+            !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        };
+        if let Some(method) = self.allowed_methods.iter().find(|m| !is_token(m)) {
+            return Err(ConfigValidationError::new(
+                "allowed_methods",
+                format!("`{method}` is not an HTTP method"),
+            ));
+        }
+        if let Some(header) = self.allowed_headers.iter().find(|h| !is_token(h)) {
+            return Err(ConfigValidationError::new(
+                "allowed_headers",
+                format!("`{header}` is not a header name"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Request budget of the API endpoints whose path starts with `path_prefix`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RateLimitRule {
+    /// Start of the limited paths, e.g. `/v0/search`
+    pub path_prefix: String,
+    /// Number of requests a client can send in a burst
+    pub requests: u32,
+    /// Seconds for an exhausted budget to be fully restored
+    pub window_secs: u64,
+}
+
+/// Rate limits of the API, keyed by client IP. Each request is limited by the first rule
+/// matching its path, unlimited if none matches. The budgets are kept in Redis, so they are
+/// shared by every API instance. Exhausted budgets are answered with `429 Too Many Requests`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    #[serde(default)]
+    pub rules: Vec<RateLimitRule>,
+    /// Read the client IP from the first address of the `X-Forwarded-For` header. Only enable it
+    /// behind a reverse proxy that sets the header, as clients could otherwise forge it
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+}
+
+impl RateLimitConfig {
+    /// Returns the rule limiting the requests to `path`, if any
+    pub fn rule_for(&self, path: &str) -> Option<&RateLimitRule> {
+        self.rules
+            .iter()
+            .find(|rule| path.starts_with(&rule.path_prefix))
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ConfigValidationError> {
+        for (i, rule) in self.rules.iter().enumerate() {
+            let within = format!("rules[{i}]");
+            if !rule.path_prefix.starts_with('/') {
+                return Err(ConfigValidationError::new(
+                    "path_prefix",
+                    format!("`{}` is not a path like `/v0/search`", rule.path_prefix),
+                )
+                .within(&within));
+            }
+            if rule.requests == 0 {
+                return Err(ConfigValidationError::new(
+                    "requests",
+                    "must allow at least one request",
+                )
+                .within(&within));
+            }
+            if rule.window_secs == 0 {
+                return Err(
+                    ConfigValidationError::new("window_secs", "must be at least 1 second")
+                        .within(&within),
+                );
+            }
+        }
+        Ok(())
+    }
+}
 
 /// Configuration settings for the Nexus API service
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +143,27 @@ pub struct ApiConfig {
     pub public_ip: IpAddr,
     pub public_addr: SocketAddr,
     pub pubky_listen_socket: SocketAddr,
+    /// Serve reads only, e.g. during a maintenance window. Requests to the endpoints that write
+    /// (PUT, DELETE and PATCH) are rejected with `503 Service Unavailable`
+    #[serde(default)]
+    pub read_only: bool,
+    /// Bearer token with which an operator can export the data of any user. Users can always export
+    /// their own data with a signed request, and no token is accepted when unset
+    #[serde(default)]
+    pub export_token: Option<String>,
+    /// Minimum number of interactions (tags, replies and reposts) of a post to be ranked in the
+    /// streams sorted by engagement. Requests can override it with `min_engagement`
+    #[serde(default = "default_min_engagement")]
+    pub min_engagement: u64,
+    /// Maximum number of tags a post stream can be filtered by. Larger lists are rejected
+    #[serde(default = "default_max_stream_tags")]
+    pub max_stream_tags: usize,
+    /// Cross-origin requests accepted from browser clients, see [CorsConfig]
+    #[serde(default)]
+    pub cors: CorsConfig,
+    /// Per route request limits, see [RateLimitConfig]. Requests are not limited by default
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
     #[serde(default = "default_stack")]
     pub stack: StackConfig,
 }
@@ -27,6 +174,12 @@ impl Default for ApiConfig {
             public_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
             public_addr: SocketAddr::from((DEFAULT_LOCAL_IP, DEFAULT_ICANN_LOCAL_PORT)),
             pubky_listen_socket: SocketAddr::from((DEFAULT_LOCAL_IP, DEFAULT_PUBKY_LOCAL_PORT)),
+            read_only: false,
+            export_token: None,
+            min_engagement: DEFAULT_MIN_ENGAGEMENT,
+            max_stream_tags: DEFAULT_MAX_STREAM_TAGS,
+            cors: CorsConfig::default(),
+            rate_limit: RateLimitConfig::default(),
             stack: StackConfig::default(),
         }
     }
@@ -44,4 +197,21 @@ impl From<DaemonConfig> for ApiConfig {
 }
 
 #[async_trait]
-impl ConfigLoader<ApiConfig> for ApiConfig {}
+impl ConfigLoader<ApiConfig> for ApiConfig {
+    fn validate(config: &ApiConfig) -> Result<(), ConfigValidationError> {
+        config.cors.validate().map_err(|e| e.within("cors"))?;
+        config
+            .rate_limit
+            .validate()
+            .map_err(|e| e.within("rate_limit"))?;
+        config.stack.validate().map_err(|e| e.within("stack"))
+    }
+}
+
+fn default_min_engagement() -> u64 {
+    DEFAULT_MIN_ENGAGEMENT
+}
+
+fn default_max_stream_tags() -> usize {
+    DEFAULT_MAX_STREAM_TAGS
+}
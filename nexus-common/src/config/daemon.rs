@@ -5,7 +5,7 @@ use tracing::error;
 
 use crate::{file::CONFIG_FILE_NAME, types::DynError};
 
-use super::{file::ConfigLoader, ApiConfig, StackConfig, WatcherConfig};
+use super::{file::ConfigLoader, ApiConfig, ConfigValidationError, StackConfig, WatcherConfig};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonConfig {
@@ -56,7 +56,21 @@ impl DaemonConfig {
 }
 
 #[async_trait]
-impl ConfigLoader<DaemonConfig> for DaemonConfig {}
+impl ConfigLoader<DaemonConfig> for DaemonConfig {
+    fn validate(config: &DaemonConfig) -> Result<(), ConfigValidationError> {
+        config
+            .api
+            .cors
+            .validate()
+            .map_err(|e| e.within("cors").within("api"))?;
+        config
+            .api
+            .rate_limit
+            .validate()
+            .map_err(|e| e.within("rate_limit").within("api"))?;
+        config.stack.validate().map_err(|e| e.within("stack"))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -64,7 +78,11 @@ mod tests {
 
     use pubky_app_specs::PubkyId;
 
-    use crate::{file::validate_and_expand_path, DaemonConfig, Level};
+    use crate::{
+        file::{validate_and_expand_path, ConfigLoader, CONFIG_FILE_NAME},
+        CorsConfig, CursorMode, DaemonConfig, DeletedRepostMode, Level, RateLimitConfig,
+        DEFAULT_ALLOWED_CONTENT_TYPES, DEFAULT_MAX_STREAM_TAGS, DEFAULT_MIN_ENGAGEMENT,
+    };
 
     #[tokio_shared_rt::test(shared)]
     async fn test_toml_parsing() {
@@ -75,6 +93,12 @@ mod tests {
         .unwrap();
 
         assert_eq!(c.api.public_addr, SocketAddr::from(([127, 0, 0, 1], 8080)));
+        assert!(!c.api.read_only);
+        assert!(c.api.export_token.is_none());
+        assert_eq!(c.api.min_engagement, DEFAULT_MIN_ENGAGEMENT);
+        assert_eq!(c.api.max_stream_tags, DEFAULT_MAX_STREAM_TAGS);
+        assert_eq!(c.api.cors, CorsConfig::default());
+        assert_eq!(c.api.rate_limit, RateLimitConfig::default());
 
         assert!(!c.watcher.testnet);
         assert_eq!(
@@ -83,6 +107,21 @@ mod tests {
         );
         assert_eq!(c.watcher.events_limit, 50);
         assert_eq!(c.watcher.watcher_sleep, 5_000);
+        assert!(!c.watcher.log_run_durations);
+        assert_eq!(c.watcher.follower_snapshot_interval_secs, 3_600);
+        assert!(c.watcher.parallel_default_homeserver);
+        assert_eq!(c.watcher.max_tags_per_target, 1_000);
+        assert!(c.watcher.tag_autosuggest_cleanup);
+        assert!(!c.watcher.allow_self_follows);
+        assert!(!c.watcher.record_tombstones);
+        assert_eq!(c.watcher.tombstone_ttl_secs, 2_592_000);
+        assert_eq!(c.watcher.retry_max_attempts, 10);
+        assert_eq!(c.watcher.retry_initial_backoff_secs, 60);
+        assert_eq!(c.watcher.retry_max_backoff_secs, 3_600);
+        assert_eq!(c.watcher.cursor_mode, CursorMode::RetryQueue);
+        assert!(c.watcher.event_metrics);
+        assert!(c.watcher.homeserver_overrides.is_empty());
+        assert_eq!(c.watcher.notification_webhook, None);
         assert_eq!(
             c.watcher.moderation_id,
             PubkyId::try_from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa").unwrap()
@@ -98,6 +137,8 @@ mod tests {
                 "il_adult_nu_sex_act",
             ]
         );
+        assert_eq!(c.watcher.moderation_log_max_entries, 10_000);
+        assert_eq!(c.watcher.moderation_log_max_age_secs, 2_592_000);
 
         assert_eq!(c.stack.log_level, Level::Info);
         assert_eq!(
@@ -105,9 +146,94 @@ mod tests {
             validate_and_expand_path(PathBuf::from_str("~/.pubky-nexus/static/files").unwrap())
                 .unwrap()
         );
+        assert!(!c.stack.case_sensitive_tags);
+        assert!(!c.stack.descendant_counts);
+        assert_eq!(c.stack.descendants_cache_ttl_secs, 3_600);
+        assert!(!c.stack.index_embeds);
+        assert!(!c.stack.single_flight_on_read_miss);
+        assert_eq!(c.stack.tags_cache_ttl_secs, 10_800);
+        assert_eq!(c.stack.deleted_repost_mode, DeletedRepostMode::Tombstone);
         assert_eq!(c.stack.otlp.name, "nexusd");
         assert!(c.stack.otlp.endpoint.is_none());
         assert_eq!(c.stack.db.redis, "redis://127.0.0.1:6379");
         assert_eq!(c.stack.db.neo4j.uri, "bolt://localhost:7687");
+        assert_eq!(c.stack.db.neo4j.database, None);
+        assert_eq!(c.stack.db.neo4j.batch_size, 1000);
+        assert_eq!(c.stack.db.neo4j.max_retries, 3);
+        assert_eq!(c.stack.db.neo4j.retry_base_delay_ms, 100);
+        assert_eq!(
+            c.stack.media.allowed_content_types,
+            DEFAULT_ALLOWED_CONTENT_TYPES
+        );
+        assert_eq!(c.stack.media.max_file_size_bytes, 104_857_600);
+        assert!(c.stack.media.strip_metadata);
+        assert_eq!(c.stack.media.limits.max_width, 16_384);
+        assert_eq!(c.stack.media.limits.max_height, 16_384);
+        assert_eq!(c.stack.media.limits.max_pixels, 50_000_000);
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_load_rejects_invalid_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_file_path = dir.path().join(CONFIG_FILE_NAME);
+        let config_toml = crate::file::reader::DEFAULT_CONFIG_TOML
+            .replace("redis://127.0.0.1:6379", "127.0.0.1:6379");
+        std::fs::write(&config_file_path, config_toml).unwrap();
+
+        let err = DaemonConfig::load(&config_file_path).await.unwrap_err();
+        assert!(
+            err.to_string().contains("`stack.db.redis`"),
+            "Error should name the offending field: {err}"
+        );
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_load_rejects_credentials_for_any_origin() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_file_path = dir.path().join(CONFIG_FILE_NAME);
+        let config_toml = crate::file::reader::DEFAULT_CONFIG_TOML
+            .replace("allow_credentials = false", "allow_credentials = true");
+        std::fs::write(&config_file_path, config_toml).unwrap();
+
+        let err = DaemonConfig::load(&config_file_path).await.unwrap_err();
+        assert!(
+            err.to_string().contains("`api.cors.allow_credentials`"),
+            "Error should name the offending field: {err}"
+        );
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_load_rejects_invalid_cors_origin() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_file_path = dir.path().join(CONFIG_FILE_NAME);
+        let config_toml = crate::file::reader::DEFAULT_CONFIG_TOML.replace(
+            "allowed_origins = []",
+            r#"allowed_origins = ["app.example.com/path"]"#,
+        );
+        std::fs::write(&config_file_path, config_toml).unwrap();
+
+        let err = DaemonConfig::load(&config_file_path).await.unwrap_err();
+        assert!(
+            err.to_string().contains("`api.cors.allowed_origins`"),
+            "Error should name the offending field: {err}"
+        );
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_load_rejects_rate_limit_without_requests() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_file_path = dir.path().join(CONFIG_FILE_NAME);
+        let config_toml = crate::file::reader::DEFAULT_CONFIG_TOML.replace(
+            "rules = []",
+            r#"rules = [{ path_prefix = "/v0/search", requests = 0, window_secs = 60 }]"#,
+        );
+        std::fs::write(&config_file_path, config_toml).unwrap();
+
+        let err = DaemonConfig::load(&config_file_path).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`api.rate_limit.rules[0].requests`"),
+            "Error should name the offending field: {err}"
+        );
     }
 }
@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+/// A config value rejected by the validation run after loading a config file
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid config field `{field}`: {reason}")]
+pub struct ConfigValidationError {
+    /// Dotted path of the offending field, e.g. `stack.db.neo4j.uri`
+    pub field: String,
+    pub reason: String,
+}
+
+impl ConfigValidationError {
+    pub fn new(field: &str, reason: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Prefixes the offending field with the name of the `parent` table it belongs to
+    pub fn within(mut self, parent: &str) -> Self {
+        self.field = format!("{parent}.{}", self.field);
+        self
+    }
+}
@@ -0,0 +1,175 @@
+use toml::{Table, Value};
+
+/// Prefix of the environment variables overriding config file values
+pub const ENV_PREFIX: &str = "NEXUS_";
+/// Separator between the nested keys in the name of an overriding environment variable
+pub const ENV_NESTING_SEPARATOR: &str = "__";
+
+/// Applies the environment variables starting with `prefix` over the parsed config `table`.
+///
+/// After the prefix, the variable name is the path of the overridden key, with the nested keys
+/// joined by [ENV_NESTING_SEPARATOR] and matched case-insensitively. For example, with the
+/// [ENV_PREFIX] prefix, `NEXUS_STACK__DB__NEO4J__PASSWORD` overrides `password` in the
+/// `[stack.db.neo4j]` table. Tables missing from the file are created, so that the keys unset
+/// in both keep their default.
+///
+/// The value replacing a string keeps the raw variable value. Any other value is parsed as a
+/// TOML value (e.g. `8080`, `true` or `["a", "b"]`), falling back to a string if it is not one.
+pub fn apply_env_overrides(
+    table: &mut Table,
+    prefix: &str,
+    vars: impl IntoIterator<Item = (String, String)>,
+) {
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(prefix) else {
+            continue;
+        };
+        let keys: Vec<String> = path
+            .split(ENV_NESTING_SEPARATOR)
+            .map(str::to_lowercase)
+            .collect();
+        if keys.iter().any(String::is_empty) {
+            continue;
+        }
+        set_value(table, &keys, &raw);
+    }
+}
+
+/// Sets the value at the `keys` path, creating the missing tables along the way
+fn set_value(table: &mut Table, keys: &[String], raw: &str) {
+    let (key, parents) = match keys.split_last() {
+        Some(split) => split,
+        None => return,
+    };
+
+    let mut current = table;
+    for parent in parents {
+        let entry = current
+            .entry(parent.clone())
+            .or_insert_with(|| Value::Table(Table::new()));
+        if !entry.is_table() {
+            *entry = Value::Table(Table::new());
+        }
+        current = match entry {
+            Value::Table(table) => table,
+            _ => return,
+        };
+    }
+
+    let value = match current.get(key) {
+        Some(Value::String(_)) => Value::String(raw.to_string()),
+        _ => parse_value(raw),
+    };
+    current.insert(key.clone(), value);
+}
+
+/// Parses a raw environment variable value as a TOML value, or as a string if it is not one
+fn parse_value(raw: &str) -> Value {
+    toml::from_str::<Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut parsed| parsed.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::reader::DEFAULT_CONFIG_TOML;
+    use crate::types::DynError;
+    use crate::DaemonConfig;
+
+    const TEST_PREFIX: &str = "NEXUS_ENV_OVERRIDE_TEST_";
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_env_overrides_database_config() -> Result<(), DynError> {
+        // A dedicated prefix, to not affect the configs loaded by the other tests
+        std::env::set_var(
+            format!("{TEST_PREFIX}STACK__DB__NEO4J__PASSWORD"),
+            "env-password",
+        );
+        std::env::set_var(
+            format!("{TEST_PREFIX}STACK__DB__REDIS"),
+            "redis://redis.internal:6379",
+        );
+
+        let mut table: Table = toml::from_str(DEFAULT_CONFIG_TOML)?;
+        apply_env_overrides(&mut table, TEST_PREFIX, std::env::vars());
+        let config: DaemonConfig = Value::Table(table).try_into()?;
+
+        assert_eq!(config.stack.db.neo4j.password, "env-password");
+        assert_eq!(config.stack.db.redis, "redis://redis.internal:6379");
+        // Values not overridden are kept from the file
+        assert_eq!(config.stack.db.neo4j.uri, "bolt://localhost:7687");
+        assert_eq!(config.stack.db.neo4j.user, "neo4j");
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_overrides_typed_and_missing_values() -> Result<(), DynError> {
+        let mut table: Table = toml::from_str(DEFAULT_CONFIG_TOML)?;
+        apply_env_overrides(
+            &mut table,
+            TEST_PREFIX,
+            vars(&[
+                (&format!("{TEST_PREFIX}WATCHER__EVENTS_LIMIT"), "42"),
+                (&format!("{TEST_PREFIX}API__READ_ONLY"), "true"),
+                // Commented out in the file
+                (&format!("{TEST_PREFIX}STACK__DB__NEO4J__DATABASE"), "nexus"),
+                // A string in the file, not parsed as a number
+                (&format!("{TEST_PREFIX}STACK__DB__NEO4J__PASSWORD"), "1234"),
+                ("OTHER_STACK__DB__REDIS", "ignored"),
+            ]),
+        );
+        let config: DaemonConfig = Value::Table(table).try_into()?;
+
+        assert_eq!(config.watcher.events_limit, 42);
+        assert!(config.api.read_only);
+        assert_eq!(config.stack.db.neo4j.database.as_deref(), Some("nexus"));
+        assert_eq!(config.stack.db.neo4j.password, "1234");
+        assert_eq!(config.stack.db.redis, "redis://127.0.0.1:6379");
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_overrides_keep_defaults() -> Result<(), DynError> {
+        // A file without the database settings, nor the api and watcher sections
+        let mut table: Table =
+            toml::from_str("[stack]\nlog_level = \"info\"\nfiles_path = \"/tmp\"\n")?;
+        apply_env_overrides(
+            &mut table,
+            TEST_PREFIX,
+            vars(&[
+                (
+                    &format!("{TEST_PREFIX}STACK__DB__REDIS"),
+                    "redis://env:6379",
+                ),
+                (
+                    &format!("{TEST_PREFIX}STACK__DB__NEO4J__URI"),
+                    "bolt://env:7687",
+                ),
+                (
+                    &format!("{TEST_PREFIX}STACK__DB__NEO4J__PASSWORD"),
+                    "secret",
+                ),
+            ]),
+        );
+        let config: DaemonConfig = Value::Table(table).try_into()?;
+
+        assert_eq!(config.stack.db.redis, "redis://env:6379");
+        assert_eq!(config.stack.db.neo4j.uri, "bolt://env:7687");
+        assert_eq!(config.stack.db.neo4j.user, "neo4j");
+        assert_eq!(
+            config.watcher.events_limit,
+            crate::WatcherConfig::default().events_limit
+        );
+        assert!(!config.api.read_only);
+        Ok(())
+    }
+}
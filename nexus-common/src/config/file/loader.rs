@@ -1,3 +1,5 @@
+use super::env::{apply_env_overrides, ENV_PREFIX};
+use crate::config::ConfigValidationError;
 use crate::types::DynError;
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
@@ -16,7 +18,25 @@ where
         Ok(config_toml)
     }
 
-    /// Loads the struct from a TOML file
+    /// Parses the struct from a TOML string, overriding its values with the `NEXUS_`-prefixed
+    /// environment variables. See [apply_env_overrides] for the variable names
+    fn try_from_str_with_env(value: &str) -> Result<T, DynError> {
+        let mut table: toml::Table = toml::from_str(value)?;
+        let vars = std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+        apply_env_overrides(&mut table, ENV_PREFIX, vars);
+        Ok(toml::Value::Table(table).try_into()?)
+    }
+
+    /// Checks the loaded values, naming the offending field on failure. Accepts any value by
+    /// default
+    fn validate(_config: &T) -> Result<(), ConfigValidationError> {
+        Ok(())
+    }
+
+    /// Loads the struct from a TOML file, with the values set by environment variables taking
+    /// precedence over the file ones
     async fn load(path: impl AsRef<Path> + Send) -> Result<T, DynError> {
         let config_file_path = path.as_ref();
 
@@ -26,9 +46,12 @@ where
             .map_err(|e| format!("!Failed to read config file {config_file_path:?}: {e}"))?;
 
         // Convert TOML to struct with error handling
-        let config = Self::try_from_str(&s)
+        let config = Self::try_from_str_with_env(&s)
             .map_err(|e| format!("Failed to parse config file {config_file_path:?}: {e}"))?;
 
+        Self::validate(&config)
+            .map_err(|e| format!("Invalid config file {config_file_path:?}: {e}"))?;
+
         Ok(config)
     }
 }
@@ -1,5 +1,7 @@
+mod env;
 mod loader;
 pub(super) mod reader;
 
+pub use env::{apply_env_overrides, ENV_NESTING_SEPARATOR, ENV_PREFIX};
 pub use loader::ConfigLoader;
 pub use reader::{default_config_dir_path, validate_and_expand_path, CONFIG_FILE_NAME};
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Default for [MediaConfig::allowed_content_types]
+pub const DEFAULT_ALLOWED_CONTENT_TYPES: [&str; 7] = [
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "video/mp4",
+    "video/webm",
+    "video/quicktime",
+];
+
+/// Default for [MediaConfig::max_file_size_bytes], 100 MiB
+pub const DEFAULT_MAX_FILE_SIZE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Default for [MediaConfig::strip_metadata]
+pub const DEFAULT_STRIP_METADATA: bool = true;
+
+fn default_allowed_content_types() -> Vec<String> {
+    DEFAULT_ALLOWED_CONTENT_TYPES
+        .iter()
+        .map(|content_type| content_type.to_string())
+        .collect()
+}
+
+/// Default for [MediaLimits::max_width]
+pub const DEFAULT_MAX_IMAGE_WIDTH: u32 = 16_384;
+/// Default for [MediaLimits::max_height]
+pub const DEFAULT_MAX_IMAGE_HEIGHT: u32 = 16_384;
+/// Default for [MediaLimits::max_pixels], 50 megapixels
+pub const DEFAULT_MAX_IMAGE_PIXELS: u64 = 50_000_000;
+
+/// Dimension limits of processed images, checked from the image header before decoding it,
+/// so that decompression bombs are rejected before they can exhaust memory
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct MediaLimits {
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Maximum width × height
+    pub max_pixels: u64,
+}
+
+impl Default for MediaLimits {
+    fn default() -> Self {
+        Self {
+            max_width: DEFAULT_MAX_IMAGE_WIDTH,
+            max_height: DEFAULT_MAX_IMAGE_HEIGHT,
+            max_pixels: DEFAULT_MAX_IMAGE_PIXELS,
+        }
+    }
+}
+
+impl MediaLimits {
+    /// Returns `true` if an image of this size is within the limits
+    pub fn allows(&self, width: u32, height: u32) -> bool {
+        width <= self.max_width
+            && height <= self.max_height
+            && width as u64 * height as u64 <= self.max_pixels
+    }
+}
+
+/// Media processing configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct MediaConfig {
+    /// Content types accepted for variant processing. Files of any other type are rejected
+    /// before reaching the image or video processors
+    #[serde(default = "default_allowed_content_types")]
+    pub allowed_content_types: Vec<String>,
+    /// Maximum size of an ingested file. Larger files are rejected while they are being
+    /// streamed to disk, without ever being held in memory
+    #[serde(default = "default_max_file_size_bytes")]
+    pub max_file_size_bytes: u64,
+    /// Strip EXIF, XMP and IPTC metadata (e.g. GPS coordinates) from ingested images and
+    /// their variants, after rotating the pixels to match the EXIF orientation
+    #[serde(default = "default_strip_metadata")]
+    pub strip_metadata: bool,
+    #[serde(default)]
+    pub limits: MediaLimits,
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            allowed_content_types: default_allowed_content_types(),
+            max_file_size_bytes: DEFAULT_MAX_FILE_SIZE_BYTES,
+            strip_metadata: DEFAULT_STRIP_METADATA,
+            limits: MediaLimits::default(),
+        }
+    }
+}
+
+fn default_max_file_size_bytes() -> u64 {
+    DEFAULT_MAX_FILE_SIZE_BYTES
+}
+
+fn default_strip_metadata() -> bool {
+    DEFAULT_STRIP_METADATA
+}
@@ -30,15 +30,36 @@ pub fn get_files_dir_test_pathbuf() -> PathBuf {
 
 mod api;
 mod daemon;
+mod error;
 pub mod file;
+mod media;
 mod stack;
 mod watcher;
 
-pub use api::ApiConfig;
+pub use api::{
+    ApiConfig, CorsConfig, RateLimitConfig, RateLimitRule, DEFAULT_MAX_STREAM_TAGS,
+    DEFAULT_MIN_ENGAGEMENT,
+};
 pub use daemon::DaemonConfig;
-pub use stack::{default_stack, OtlpConfig, StackConfig};
-pub use watcher::WatcherConfig;
-pub use watcher::{DEFAULT_INITIAL_BACKOFF_SECS, DEFAULT_MAX_BACKOFF_SECS};
+pub use error::ConfigValidationError;
+pub use media::{
+    MediaConfig, MediaLimits, DEFAULT_ALLOWED_CONTENT_TYPES, DEFAULT_MAX_FILE_SIZE_BYTES,
+    DEFAULT_MAX_IMAGE_HEIGHT, DEFAULT_MAX_IMAGE_PIXELS, DEFAULT_MAX_IMAGE_WIDTH,
+    DEFAULT_STRIP_METADATA,
+};
+pub use stack::{
+    default_stack, DeletedRepostMode, OtlpConfig, StackConfig, DEFAULT_DESCENDANTS_CACHE_TTL_SECS,
+    DEFAULT_TAGS_CACHE_TTL_SECS,
+};
+pub use watcher::{CursorMode, HomeserverOverride, NotificationWebhookConfig, WatcherConfig};
+pub use watcher::{
+    DEFAULT_EVENT_METRICS, DEFAULT_FOLLOWER_SNAPSHOT_INTERVAL_SECS, DEFAULT_INITIAL_BACKOFF_SECS,
+    DEFAULT_MAX_BACKOFF_SECS, DEFAULT_MAX_TAGS_PER_TARGET, DEFAULT_MODERATION_LOG_MAX_AGE_SECS,
+    DEFAULT_MODERATION_LOG_MAX_ENTRIES, DEFAULT_RETRY_INITIAL_BACKOFF_SECS,
+    DEFAULT_RETRY_MAX_ATTEMPTS, DEFAULT_RETRY_MAX_BACKOFF_SECS, DEFAULT_TAG_AUTOSUGGEST_CLEANUP,
+    DEFAULT_TOMBSTONE_TTL_SECS, DEFAULT_WEBHOOK_INITIAL_BACKOFF_MS, DEFAULT_WEBHOOK_MAX_ATTEMPTS,
+    DEFAULT_WEBHOOK_QUEUE_SIZE,
+};
 
 use crate::file::validate_and_expand_path;
 
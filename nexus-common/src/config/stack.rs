@@ -2,7 +2,24 @@ use crate::{db::DatabaseConfig, get_files_dir_pathbuf};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{fmt::Debug, path::PathBuf};
 
-use super::{file::validate_and_expand_path, Level, LOG_LEVEL};
+use super::{file::validate_and_expand_path, ConfigValidationError, Level, MediaConfig, LOG_LEVEL};
+
+/// Default for [StackConfig::descendants_cache_ttl_secs]
+pub const DEFAULT_DESCENDANTS_CACHE_TTL_SECS: u64 = 60 * 60;
+/// Default for [StackConfig::tags_cache_ttl_secs]
+pub const DEFAULT_TAGS_CACHE_TTL_SECS: u64 = 3 * 60 * 60;
+
+/// How the reposts of a deleted post are returned, since they embed a post without content
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletedRepostMode {
+    /// Return the reposts flagged with [PostView::original_deleted](crate::models::post::PostView::original_deleted),
+    /// so clients can render a tombstone in place of the original post
+    #[default]
+    Tombstone,
+    /// Leave the reposts out of the returned posts
+    Hide,
+}
 
 fn deserialize_and_expand<'de, D>(deserializer: D) -> Result<PathBuf, D::Error>
 where
@@ -38,6 +55,34 @@ pub struct StackConfig {
     #[serde(default)]
     pub otlp: OtlpConfig,
     pub db: DatabaseConfig,
+    #[serde(default)]
+    pub media: MediaConfig,
+    /// Keep the case of tag labels instead of lowercasing them, so that e.g. "React" and "react"
+    /// are distinct tags. Must be the same for the watcher and the API
+    #[serde(default)]
+    pub case_sensitive_tags: bool,
+    /// Include the number of posts anywhere in the reply thread below a post in its counts.
+    /// Expensive to compute, so disabled by default
+    #[serde(default)]
+    pub descendant_counts: bool,
+    /// Expiry (in seconds) of the cached descendant counts. Set to 0 to keep them without expiry
+    #[serde(default = "default_descendants_cache_ttl_secs")]
+    pub descendants_cache_ttl_secs: u64,
+    /// Index new posts by the URI they embed, so that the posts embedding a resource can be
+    /// listed. Must be the same for the watcher and the API
+    #[serde(default)]
+    pub index_embeds: bool,
+    /// When a view is missing from the cache, let only one request per key rebuild it from
+    /// the graph while concurrent requests for the same key wait for the warmed cache
+    #[serde(default)]
+    pub single_flight_on_read_miss: bool,
+    /// Expiry (in seconds) of the viewer specific tag caches, like the WoT tags of a post or user.
+    /// Set to 0 to keep them without expiry
+    #[serde(default = "default_tags_cache_ttl_secs")]
+    pub tags_cache_ttl_secs: u64,
+    /// How the reposts of a deleted post are returned, see [DeletedRepostMode]
+    #[serde(default)]
+    pub deleted_repost_mode: DeletedRepostMode,
 }
 
 /// Utility function
@@ -52,6 +97,30 @@ impl Default for StackConfig {
             files_path: get_files_dir_pathbuf(),
             otlp: OtlpConfig::default(),
             db: DatabaseConfig::default(),
+            media: MediaConfig::default(),
+            case_sensitive_tags: false,
+            descendant_counts: false,
+            descendants_cache_ttl_secs: DEFAULT_DESCENDANTS_CACHE_TTL_SECS,
+            index_embeds: false,
+            single_flight_on_read_miss: false,
+            tags_cache_ttl_secs: DEFAULT_TAGS_CACHE_TTL_SECS,
+            deleted_repost_mode: DeletedRepostMode::default(),
         }
     }
 }
+
+impl StackConfig {
+    /// Checks the settings that would otherwise only fail once the stack connects, see
+    /// [DatabaseConfig::validate]
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        self.db.validate().map_err(|e| e.within("db"))
+    }
+}
+
+fn default_descendants_cache_ttl_secs() -> u64 {
+    DEFAULT_DESCENDANTS_CACHE_TTL_SECS
+}
+
+fn default_tags_cache_ttl_secs() -> u64 {
+    DEFAULT_TAGS_CACHE_TTL_SECS
+}
@@ -1,8 +1,9 @@
 use super::file::ConfigLoader;
-use super::{default_stack, DaemonConfig, StackConfig};
+use super::{default_stack, ConfigValidationError, DaemonConfig, StackConfig};
 use async_trait::async_trait;
 use pubky_app_specs::PubkyId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 pub const TESTNET: bool = false;
@@ -19,6 +20,36 @@ pub const DEFAULT_WATCHER_SLEEP: u64 = 5_000;
 pub const DEFAULT_INITIAL_BACKOFF_SECS: u64 = 60;
 /// Default for [WatcherConfig::max_backoff_secs]
 pub const DEFAULT_MAX_BACKOFF_SECS: u64 = 3_600;
+/// Default for [WatcherConfig::follower_snapshot_interval_secs]
+pub const DEFAULT_FOLLOWER_SNAPSHOT_INTERVAL_SECS: u64 = 3_600;
+/// Default for [WatcherConfig::parallel_default_homeserver]
+pub const DEFAULT_PARALLEL_DEFAULT_HOMESERVER: bool = true;
+/// Default for [WatcherConfig::max_tags_per_target]
+pub const DEFAULT_MAX_TAGS_PER_TARGET: usize = 1_000;
+/// Default for [WatcherConfig::event_metrics]
+pub const DEFAULT_EVENT_METRICS: bool = true;
+/// Default for [WatcherConfig::tag_autosuggest_cleanup]
+pub const DEFAULT_TAG_AUTOSUGGEST_CLEANUP: bool = true;
+
+/// Default for [NotificationWebhookConfig::queue_size]
+pub const DEFAULT_WEBHOOK_QUEUE_SIZE: usize = 1_000;
+/// Default for [NotificationWebhookConfig::max_attempts]
+pub const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+/// Default for [NotificationWebhookConfig::initial_backoff_ms]
+pub const DEFAULT_WEBHOOK_INITIAL_BACKOFF_MS: u64 = 500;
+
+/// Default for [WatcherConfig::tombstone_ttl_secs]
+pub const DEFAULT_TOMBSTONE_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+/// Default for [WatcherConfig::retry_max_attempts]
+pub const DEFAULT_RETRY_MAX_ATTEMPTS: u32 = 10;
+/// Default for [WatcherConfig::retry_initial_backoff_secs]
+pub const DEFAULT_RETRY_INITIAL_BACKOFF_SECS: u64 = 60;
+/// Default for [WatcherConfig::retry_max_backoff_secs]
+pub const DEFAULT_RETRY_MAX_BACKOFF_SECS: u64 = 3_600;
+/// Default for [WatcherConfig::moderation_log_max_entries]
+pub const DEFAULT_MODERATION_LOG_MAX_ENTRIES: usize = 10_000;
+/// Default for [WatcherConfig::moderation_log_max_age_secs]
+pub const DEFAULT_MODERATION_LOG_MAX_AGE_SECS: u64 = 30 * 24 * 60 * 60;
 // Moderation service key
 pub const MODERATION_ID: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
 // Moderation service key
@@ -31,6 +62,67 @@ pub const MODERATED_TAGS: [&str; 6] = [
     "il_adult_nu_sex_act",
 ];
 
+/// Polling and processing settings of a single homeserver, overriding the global ones of [WatcherConfig]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct HomeserverOverride {
+    /// Maximum number of events to fetch per run from this homeserver, instead of
+    /// [WatcherConfig::events_limit]
+    #[serde(default)]
+    pub events_limit: Option<u32>,
+    /// Minimum time between two polls of this homeserver, in milliseconds. Runs in between skip it.
+    /// If unset, the homeserver is polled in every run
+    #[serde(default)]
+    pub poll_interval: Option<u64>,
+    /// Maximum duration of a run of this homeserver's event processor, in seconds. Allows a
+    /// homeserver with a large backlog to run longer than the global processing timeout
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Tags moderated on the content hosted by this homeserver when placed by the trusted
+    /// moderator, instead of [WatcherConfig::moderated_tags]. The moderator's own homeserver
+    /// does not matter
+    #[serde(default)]
+    pub moderated_tags: Option<Vec<String>>,
+}
+
+/// Outbound webhook the new notifications are POSTed to, as JSON signed with HMAC-SHA256
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NotificationWebhookConfig {
+    /// URL receiving the notifications of every user. Users can register their own URL with the
+    /// `webhook_url` of their notification preferences. If unset, only those users are notified.
+    /// Unlike the URLs of the users, it may target a local or private host
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Key of the HMAC-SHA256 signature of each body, sent in the `X-Nexus-Signature` header
+    pub secret: String,
+    /// Maximum number of notifications waiting to be delivered, overall and to each destination
+    /// host. New ones are dropped when it is full
+    #[serde(default = "default_webhook_queue_size")]
+    pub queue_size: usize,
+    /// Number of delivery attempts of a notification before it is dropped
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay (in milliseconds) before the second delivery attempt, doubled after each further failure
+    #[serde(default = "default_webhook_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+}
+
+/// How the cursor of a homeserver advances over a batch of events in which some failed
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CursorMode {
+    /// Advance the cursor past the whole batch. The following events are not delayed by a
+    /// failing one, but nothing processes a failed event again: it is only recorded in the retry
+    /// index, for operators to inspect, until a later event of the same resource succeeds or the
+    /// resource is moved to the dead-letter index
+    #[default]
+    RetryQueue,
+    /// At-least-once delivery: stop at the first failed event, and only advance the cursor to
+    /// the last event before it. The rest of the batch is polled again in the next run, so a
+    /// failing event delays all the following events of its homeserver until it succeeds or
+    /// is moved to the dead-letter index
+    Strict,
+}
+
 /// Configuration settings for the Nexus Watcher service
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct WatcherConfig {
@@ -50,11 +142,79 @@ pub struct WatcherConfig {
     /// Maximum backoff duration (in seconds) for a failing homeserver
     #[serde(default = "default_max_backoff_secs")]
     pub max_backoff_secs: u64,
+    /// Log the duration and processed event count of each homeserver run, and the total
+    /// duration of every full run, at info level
+    #[serde(default)]
+    pub log_run_durations: bool,
+    /// Interval (in seconds) between two follower count snapshots of the same user. Follower
+    /// changes within the same interval overwrite its snapshot. Set to 0 to disable snapshots
+    #[serde(default = "default_follower_snapshot_interval_secs")]
+    pub follower_snapshot_interval_secs: u64,
+    /// Process the default homeserver concurrently with the other monitored homeservers in every
+    /// run, instead of one after the other, so that slow homeservers don't delay it
+    #[serde(default = "default_parallel_default_homeserver")]
+    pub parallel_default_homeserver: bool,
+    /// Maximum number of distinct tag labels indexed per post or user. Tags with a new label are
+    /// rejected once a target reaches it, while its existing labels still accept more taggers.
+    /// Set to 0 to disable the cap
+    #[serde(default = "default_max_tags_per_target")]
+    pub max_tags_per_target: usize,
+    /// Remove a tag label from the autosuggest index once the last post tagged with it is untagged.
+    /// Disable it to keep a stable autosuggest vocabulary, where labels persist after their
+    /// occurrences drop to zero
+    #[serde(default = "default_tag_autosuggest_cleanup")]
+    pub tag_autosuggest_cleanup: bool,
+    /// Index the follows of a user to themselves. They are ignored by default, so that they don't
+    /// count as followers or reach
+    #[serde(default)]
+    pub allow_self_follows: bool,
+    /// Keep a tombstone of the deleted posts and users, so that the API answers `410 Gone` for
+    /// them instead of `404 Not Found`. The tombstones are kept until the ID is indexed again, or
+    /// until they expire after `tombstone_ttl_secs`
+    #[serde(default)]
+    pub record_tombstones: bool,
+    /// Seconds a tombstone is kept before the API answers `404 Not Found` again for its ID. Set to
+    /// 0 to keep the tombstones forever
+    #[serde(default = "default_tombstone_ttl_secs")]
+    pub tombstone_ttl_secs: u64,
+    /// Number of failed processing attempts after which an event is moved from the retry index to
+    /// the dead-letter index. With [CursorMode::RetryQueue], the attempts are the failed events of
+    /// the same URI
+    #[serde(default = "default_retry_max_attempts")]
+    pub retry_max_attempts: u32,
+    /// Delay (in seconds) before an event held by a [CursorMode::Strict] cursor can be processed
+    /// again, doubled after each further failure
+    #[serde(default = "default_retry_initial_backoff_secs")]
+    pub retry_initial_backoff_secs: u64,
+    /// Maximum delay (in seconds) before a failed event can be processed again
+    #[serde(default = "default_retry_max_backoff_secs")]
+    pub retry_max_backoff_secs: u64,
+    /// How the cursor of a homeserver advances past failed events, see [CursorMode]
+    #[serde(default)]
+    pub cursor_mode: CursorMode,
+    /// Record OpenTelemetry metrics of the processed, failed and retry-queued events, and of the
+    /// duration of every homeserver run. Can be disabled when no metrics collector is configured
+    #[serde(default = "default_event_metrics")]
+    pub event_metrics: bool,
+    /// Polling and moderation settings of specific homeservers, by homeserver ID
+    #[serde(default)]
+    pub homeserver_overrides: BTreeMap<String, HomeserverOverride>,
+    /// Deliver the new notifications to a webhook. Disabled when unset
+    #[serde(default)]
+    pub notification_webhook: Option<NotificationWebhookConfig>,
     #[serde(default = "default_stack")]
     pub stack: StackConfig,
     // Moderation
     pub moderation_id: PubkyId,
     pub moderated_tags: Vec<String>,
+    /// Maximum number of moderation actions kept in the audit log. The oldest ones are removed
+    /// beyond it. Set to 0 to keep them all
+    #[serde(default = "default_moderation_log_max_entries")]
+    pub moderation_log_max_entries: usize,
+    /// Maximum age (in seconds) of the moderation actions kept in the audit log. Set to 0 to keep
+    /// them regardless of their age
+    #[serde(default = "default_moderation_log_max_age_secs")]
+    pub moderation_log_max_age_secs: u64,
 }
 
 impl Default for WatcherConfig {
@@ -76,8 +236,25 @@ impl Default for WatcherConfig {
             watcher_sleep: DEFAULT_WATCHER_SLEEP,
             initial_backoff_secs: DEFAULT_INITIAL_BACKOFF_SECS,
             max_backoff_secs: DEFAULT_MAX_BACKOFF_SECS,
+            log_run_durations: false,
+            follower_snapshot_interval_secs: DEFAULT_FOLLOWER_SNAPSHOT_INTERVAL_SECS,
+            parallel_default_homeserver: DEFAULT_PARALLEL_DEFAULT_HOMESERVER,
+            max_tags_per_target: DEFAULT_MAX_TAGS_PER_TARGET,
+            tag_autosuggest_cleanup: DEFAULT_TAG_AUTOSUGGEST_CLEANUP,
+            allow_self_follows: false,
+            record_tombstones: false,
+            tombstone_ttl_secs: DEFAULT_TOMBSTONE_TTL_SECS,
+            retry_max_attempts: DEFAULT_RETRY_MAX_ATTEMPTS,
+            retry_initial_backoff_secs: DEFAULT_RETRY_INITIAL_BACKOFF_SECS,
+            retry_max_backoff_secs: DEFAULT_RETRY_MAX_BACKOFF_SECS,
+            cursor_mode: CursorMode::default(),
+            event_metrics: DEFAULT_EVENT_METRICS,
+            homeserver_overrides: BTreeMap::new(),
+            notification_webhook: None,
             moderation_id,
             moderated_tags: MODERATED_TAGS.iter().map(|s| s.to_string()).collect(),
+            moderation_log_max_entries: DEFAULT_MODERATION_LOG_MAX_ENTRIES,
+            moderation_log_max_age_secs: DEFAULT_MODERATION_LOG_MAX_AGE_SECS,
         }
     }
 }
@@ -94,7 +271,11 @@ impl From<DaemonConfig> for WatcherConfig {
 }
 
 #[async_trait]
-impl ConfigLoader<WatcherConfig> for WatcherConfig {}
+impl ConfigLoader<WatcherConfig> for WatcherConfig {
+    fn validate(config: &WatcherConfig) -> Result<(), ConfigValidationError> {
+        config.stack.validate().map_err(|e| e.within("stack"))
+    }
+}
 
 fn default_initial_backoff_secs() -> u64 {
     DEFAULT_INITIAL_BACKOFF_SECS
@@ -103,3 +284,59 @@ fn default_initial_backoff_secs() -> u64 {
 fn default_max_backoff_secs() -> u64 {
     DEFAULT_MAX_BACKOFF_SECS
 }
+
+fn default_follower_snapshot_interval_secs() -> u64 {
+    DEFAULT_FOLLOWER_SNAPSHOT_INTERVAL_SECS
+}
+
+fn default_parallel_default_homeserver() -> bool {
+    DEFAULT_PARALLEL_DEFAULT_HOMESERVER
+}
+
+fn default_max_tags_per_target() -> usize {
+    DEFAULT_MAX_TAGS_PER_TARGET
+}
+
+fn default_tag_autosuggest_cleanup() -> bool {
+    DEFAULT_TAG_AUTOSUGGEST_CLEANUP
+}
+
+fn default_tombstone_ttl_secs() -> u64 {
+    DEFAULT_TOMBSTONE_TTL_SECS
+}
+
+fn default_retry_max_attempts() -> u32 {
+    DEFAULT_RETRY_MAX_ATTEMPTS
+}
+
+fn default_retry_initial_backoff_secs() -> u64 {
+    DEFAULT_RETRY_INITIAL_BACKOFF_SECS
+}
+
+fn default_retry_max_backoff_secs() -> u64 {
+    DEFAULT_RETRY_MAX_BACKOFF_SECS
+}
+
+fn default_event_metrics() -> bool {
+    DEFAULT_EVENT_METRICS
+}
+
+fn default_webhook_queue_size() -> usize {
+    DEFAULT_WEBHOOK_QUEUE_SIZE
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    DEFAULT_WEBHOOK_MAX_ATTEMPTS
+}
+
+fn default_webhook_initial_backoff_ms() -> u64 {
+    DEFAULT_WEBHOOK_INITIAL_BACKOFF_MS
+}
+
+fn default_moderation_log_max_entries() -> usize {
+    DEFAULT_MODERATION_LOG_MAX_ENTRIES
+}
+
+fn default_moderation_log_max_age_secs() -> u64 {
+    DEFAULT_MODERATION_LOG_MAX_AGE_SECS
+}
@@ -1,8 +1,13 @@
+use crate::config::ConfigValidationError;
+use redis::IntoConnectionInfo;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 mod neo4j;
-pub use neo4j::Neo4JConfig;
+pub use neo4j::{
+    Neo4JConfig, DEFAULT_NEO4J_BATCH_SIZE, DEFAULT_NEO4J_MAX_RETRIES,
+    DEFAULT_NEO4J_RETRY_BASE_DELAY_MS,
+};
 
 pub const REDIS_URI: &str = "redis://localhost:6379";
 
@@ -20,3 +25,88 @@ impl Default for DatabaseConfig {
         }
     }
 }
+
+impl DatabaseConfig {
+    /// Checks that the Redis URI is a valid `redis://` (or `rediss://`) URL and that the Neo4j
+    /// settings are valid, see [Neo4JConfig::validate]
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        if !self.redis.starts_with("redis://") && !self.redis.starts_with("rediss://") {
+            return Err(ConfigValidationError::new(
+                "redis",
+                format!("expected a redis:// or rediss:// URL, got {:?}", self.redis),
+            ));
+        }
+        self.redis
+            .as_str()
+            .into_connection_info()
+            .map_err(|e| ConfigValidationError::new("redis", format!("malformed URL: {e}")))?;
+
+        self.neo4j.validate().map_err(|e| e.within("neo4j"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_invalid(config: &DatabaseConfig, field: &str) {
+        let err = config.validate().expect_err("Config should be invalid");
+        assert_eq!(err.field, field);
+        assert!(
+            err.to_string().contains(&format!("`{field}`")),
+            "Error should name {field}: {err}"
+        );
+    }
+
+    #[test]
+    fn test_validate_default() {
+        assert_eq!(DatabaseConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_redis_scheme() {
+        let mut config = DatabaseConfig::default();
+        config.redis = String::from("localhost:6379");
+        assert_invalid(&config, "redis");
+
+        config.redis = String::from("http://localhost:6379");
+        assert_invalid(&config, "redis");
+
+        config.redis = String::from("rediss://redis.example.com:6380");
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_malformed_redis_uri() {
+        let mut config = DatabaseConfig::default();
+        config.redis = String::from("redis://localhost:notaport");
+        assert_invalid(&config, "redis");
+    }
+
+    #[test]
+    fn test_validate_neo4j_scheme() {
+        let mut config = DatabaseConfig::default();
+        config.neo4j.uri = String::from("http://localhost:7474");
+        assert_invalid(&config, "neo4j.uri");
+
+        config.neo4j.uri = String::from("localhost:7687");
+        assert_invalid(&config, "neo4j.uri");
+
+        config.neo4j.uri = String::from("bolt://");
+        assert_invalid(&config, "neo4j.uri");
+
+        config.neo4j.uri = String::from("neo4j+s://graph.example.com:7687");
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_neo4j_password() {
+        let mut config = DatabaseConfig::default();
+        config.neo4j.password = String::new();
+        // Allowed for the default local server
+        assert_eq!(config.validate(), Ok(()));
+
+        config.neo4j.uri = String::from("bolt://graph.example.com:7687");
+        assert_invalid(&config, "neo4j.password");
+    }
+}
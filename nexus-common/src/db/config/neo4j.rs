@@ -1,8 +1,15 @@
+use crate::config::ConfigValidationError;
 use serde::{Deserialize, Serialize};
 
 pub const NEO4J_URI: &str = "bolt://localhost:7687";
 pub const NEO4J_USER: &str = "neo4j";
 pub const NEO4J_PASS: &str = "12345678";
+/// Default for [Neo4JConfig::batch_size]
+pub const DEFAULT_NEO4J_BATCH_SIZE: usize = 1000;
+/// Default for [Neo4JConfig::max_retries]
+pub const DEFAULT_NEO4J_MAX_RETRIES: u32 = 3;
+/// Default for [Neo4JConfig::retry_base_delay_ms]
+pub const DEFAULT_NEO4J_RETRY_BASE_DELAY_MS: u64 = 100;
 // Create temporal struct to wrap database config
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct Neo4JConfig {
@@ -13,6 +20,11 @@ pub struct Neo4JConfig {
 
     pub password: String,
 
+    /// Name of the database holding the Nexus graph, for servers hosting several databases
+    /// (Neo4j 4+). Defaults to `None`, the default database of the server.
+    #[serde(default)]
+    pub database: Option<String>,
+
     /// Slow-query warning threshold in milliseconds.
     /// `Some(ms)` — emit a warning for queries exceeding `ms` milliseconds.
     /// `Some(0)`  — warn on every query (useful for debugging).
@@ -25,6 +37,70 @@ pub struct Neo4JConfig {
     /// Useful for debugging but verbose. Defaults to false.
     #[serde(default)]
     pub slow_query_logging_include_cypher: bool,
+
+    /// Maximum number of rows written by a single `UNWIND` query of
+    /// [execute_batch](crate::db::execute_batch). Larger inputs are split in several queries.
+    #[serde(default = "default_neo4j_batch_size")]
+    pub batch_size: usize,
+
+    /// Number of times a query failing with a transient error (e.g. connection reset, failover)
+    /// is retried. `0` disables retries.
+    #[serde(default = "default_neo4j_max_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry of a query, doubled for each following retry
+    #[serde(default = "default_neo4j_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+}
+
+/// URI schemes supported by the Neo4j driver
+const NEO4J_URI_SCHEMES: [&str; 6] = [
+    "bolt",
+    "bolt+s",
+    "bolt+ssc",
+    "neo4j",
+    "neo4j+s",
+    "neo4j+ssc",
+];
+
+impl Neo4JConfig {
+    /// Checks that the URI uses a `bolt://` or `neo4j://` scheme (or one of their TLS variants)
+    /// and names a host, and that the password is set for any server other than the default
+    /// local one
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        let (scheme, host) = self.uri.split_once("://").unwrap_or_default();
+        if !NEO4J_URI_SCHEMES.contains(&scheme) {
+            return Err(ConfigValidationError::new(
+                "uri",
+                format!("expected a bolt:// or neo4j:// URI, got {:?}", self.uri),
+            ));
+        }
+        if host.is_empty() {
+            return Err(ConfigValidationError::new(
+                "uri",
+                format!("missing host in {:?}", self.uri),
+            ));
+        }
+        if self.password.is_empty() && self.uri != NEO4J_URI {
+            return Err(ConfigValidationError::new(
+                "password",
+                format!("cannot be empty when connecting to {}", self.uri),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_neo4j_batch_size() -> usize {
+    DEFAULT_NEO4J_BATCH_SIZE
+}
+
+fn default_neo4j_max_retries() -> u32 {
+    DEFAULT_NEO4J_MAX_RETRIES
+}
+
+fn default_neo4j_retry_base_delay_ms() -> u64 {
+    DEFAULT_NEO4J_RETRY_BASE_DELAY_MS
 }
 
 fn default_neo4j_user() -> String {
@@ -37,8 +113,12 @@ impl Default for Neo4JConfig {
             uri: String::from(NEO4J_URI),
             user: String::from(NEO4J_USER),
             password: String::from(NEO4J_PASS),
+            database: None,
             slow_query_logging_threshold_ms: None,
             slow_query_logging_include_cypher: false,
+            batch_size: DEFAULT_NEO4J_BATCH_SIZE,
+            max_retries: DEFAULT_NEO4J_MAX_RETRIES,
+            retry_base_delay_ms: DEFAULT_NEO4J_RETRY_BASE_DELAY_MS,
         }
     }
 }
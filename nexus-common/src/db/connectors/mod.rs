@@ -2,6 +2,8 @@ mod neo4j;
 mod pubky;
 mod redis;
 
-pub use neo4j::{get_neo4j_graph, Neo4jConnector, NEO4J_CONNECTOR};
+pub use neo4j::{
+    get_neo4j_batch_size, get_neo4j_graph, ping_neo4j, Neo4jConnector, NEO4J_CONNECTOR,
+};
 pub use pubky::{PubkyClientError, PubkyConnector};
-pub use redis::{get_redis_conn, RedisConnector, REDIS_CONNECTOR};
+pub use redis::{get_redis_conn, ping_redis, RedisConnector, REDIS_CONNECTOR};
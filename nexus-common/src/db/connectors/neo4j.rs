@@ -5,13 +5,15 @@ use std::time::Duration;
 use tracing::{debug, info};
 
 use crate::db::graph::error::{GraphError, GraphResult};
-use crate::db::graph::{Graph, GraphOps, InstrumentedGraph};
+use crate::db::graph::{Graph, GraphOps, InstrumentedGraph, RetryGraph, RetryPolicy};
 use crate::db::setup::setup_graph;
-use crate::db::Neo4JConfig;
+use crate::db::{Neo4JConfig, DEFAULT_NEO4J_BATCH_SIZE};
 use crate::types::DynError;
 
 pub struct Neo4jConnector {
     graph: Arc<dyn GraphOps>,
+    /// See [Neo4JConfig::batch_size]
+    batch_size: usize,
 }
 
 impl Neo4jConnector {
@@ -33,27 +35,44 @@ impl Neo4jConnector {
 
     /// Create and return a new connector after defining a database connection
     async fn new_connection(config: &Neo4JConfig) -> GraphResult<Self> {
-        let neo4j_graph = neo4rs::Graph::new(&config.uri, &config.user, &config.password).await?;
+        let mut neo4j_config = neo4rs::ConfigBuilder::default()
+            .uri(&config.uri)
+            .user(&config.user)
+            .password(&config.password);
+        if let Some(database) = &config.database {
+            neo4j_config = neo4j_config.db(database.as_str());
+        }
+        let neo4j_graph = neo4rs::Graph::connect(neo4j_config.build()?).await?;
         let graph = Graph::new(neo4j_graph);
 
         // Always wrap with InstrumentedGraph to collect OpenTelemetry metrics.
         // slow_query_threshold is None when slow-query logging is disabled.
-        let graph: Arc<dyn GraphOps> = Arc::new(
-            InstrumentedGraph::new(graph)
-                .with_slow_query_threshold(
-                    config
-                        .slow_query_logging_threshold_ms
-                        .map(Duration::from_millis),
-                )
-                .with_log_cypher(config.slow_query_logging_include_cypher),
-        );
+        let graph = InstrumentedGraph::new(graph)
+            .with_slow_query_threshold(
+                config
+                    .slow_query_logging_threshold_ms
+                    .map(Duration::from_millis),
+            )
+            .with_log_cypher(config.slow_query_logging_include_cypher);
+
+        // Retries wrap the instrumentation, so that every failed attempt is recorded
+        let retry_policy = RetryPolicy {
+            max_retries: config.max_retries,
+            base_delay: Duration::from_millis(config.retry_base_delay_ms),
+        };
+        let graph: Arc<dyn GraphOps> = Arc::new(RetryGraph::new(graph, retry_policy));
 
         info!(
+            database = config.database.as_deref().unwrap_or("default"),
             slow_query_logging_threshold_ms = ?config.slow_query_logging_threshold_ms,
             slow_query_logging_include_cypher = config.slow_query_logging_include_cypher,
+            max_retries = config.max_retries,
             "Created Neo4j connector"
         );
-        Ok(Neo4jConnector { graph })
+        Ok(Neo4jConnector {
+            graph,
+            batch_size: config.batch_size.max(1),
+        })
     }
 
     /// Perform a health-check PING over the Bolt protocol to the Neo4j server
@@ -71,6 +90,7 @@ impl fmt::Debug for Neo4jConnector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Neo4jConnector")
             .field("graph", &"GraphOps instance")
+            .field("batch_size", &self.batch_size)
             .finish()
     }
 }
@@ -83,4 +103,21 @@ pub fn get_neo4j_graph() -> GraphResult<Arc<dyn GraphOps>> {
         .map(|neo4j_connector| neo4j_connector.graph.clone())
 }
 
+/// Returns the maximum number of rows written by a single batched query, see [Neo4JConfig::batch_size]
+pub fn get_neo4j_batch_size() -> usize {
+    NEO4J_CONNECTOR
+        .get()
+        .map_or(DEFAULT_NEO4J_BATCH_SIZE, |neo4j_connector| {
+            neo4j_connector.batch_size
+        })
+}
+
+/// Checks that Neo4j answers a trivial query, e.g. for a readiness probe
+pub async fn ping_neo4j() -> GraphResult<()> {
+    get_neo4j_graph()?
+        .run(Query::new("ping", "RETURN 1"))
+        .await?;
+    Ok(())
+}
+
 pub static NEO4J_CONNECTOR: OnceLock<Neo4jConnector> = OnceLock::new();
@@ -66,6 +66,13 @@ impl fmt::Debug for RedisConnector {
 /// Make sure to initialize this once when your application starts.
 pub static REDIS_CONNECTOR: OnceLock<RedisConnector> = OnceLock::new();
 
+/// Checks that Redis answers a `PING`, e.g. for a readiness probe
+pub async fn ping_redis() -> RedisResult<()> {
+    let mut redis_conn = get_redis_conn().await?;
+    let _: String = redis::cmd("PING").query_async(&mut redis_conn).await?;
+    Ok(())
+}
+
 /// Retrieves a Redis connection from the pool.
 pub async fn get_redis_conn() -> RedisResult<Connection> {
     let connector = REDIS_CONNECTOR
@@ -30,6 +30,14 @@ pub enum GraphError {
     #[error("Invalid resource type: {0}")]
     InvalidResourceType(String),
 
+    /// A batched write failed. Rows before `row` were written, `row` and the following ones were not
+    #[error("Batch write failed at row {row}: {source}")]
+    BatchFailed {
+        row: usize,
+        #[source]
+        source: Box<GraphError>,
+    },
+
     #[error("Generic: {0}")]
     Generic(String),
 }
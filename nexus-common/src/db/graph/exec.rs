@@ -1,8 +1,10 @@
 use super::query::Query;
-use crate::db::{get_neo4j_graph, graph::error::GraphResult};
+use crate::db::graph::error::{GraphError, GraphResult};
+use crate::db::{get_neo4j_batch_size, get_neo4j_graph};
 use futures::TryStreamExt;
-use neo4rs::Row;
+use neo4rs::{BoltMap, Row};
 use serde::de::DeserializeOwned;
+use std::time::Duration;
 
 /// Represents the outcome of a mutation-like query in the graph database.
 #[derive(Debug)]
@@ -41,6 +43,52 @@ pub async fn exec_single_row(query: Query) -> GraphResult<()> {
     graph.run(query).await.map_err(Into::into)
 }
 
+/// Writes many rows with `UNWIND $batch AS row` queries instead of one query per row.
+///
+/// `query_template` is the cypher run for each row, reading its fields from `row`
+/// (e.g. `CREATE (u:User {id: row.id, name: row.name})`). The rows are split in batches of
+/// at most [Neo4JConfig::batch_size](crate::db::Neo4JConfig::batch_size) rows, written in order.
+///
+/// Each batch is written atomically. If one fails, its rows are written again one at a time to
+/// find the failing row, and [GraphError::BatchFailed] reports its index: all the rows before it
+/// were written, none of the following ones were.
+pub async fn execute_batch(query_template: Query, rows: Vec<BoltMap>) -> GraphResult<()> {
+    execute_batch_with_size(query_template, rows, get_neo4j_batch_size()).await
+}
+
+async fn execute_batch_with_size(
+    query_template: Query,
+    rows: Vec<BoltMap>,
+    batch_size: usize,
+) -> GraphResult<()> {
+    let graph = get_neo4j_graph()?;
+
+    let mut rows = rows.into_iter().peekable();
+    let mut row = 0;
+    while rows.peek().is_some() {
+        let batch: Vec<BoltMap> = rows.by_ref().take(batch_size).collect();
+        let batch_len = batch.len();
+        if graph
+            .run(query_template.unwind(batch.clone()))
+            .await
+            .is_err()
+        {
+            // The failed batch was rolled back, so its rows can be written one by one
+            for (offset, single_row) in batch.into_iter().enumerate() {
+                graph
+                    .run(query_template.unwind(vec![single_row]))
+                    .await
+                    .map_err(|e| GraphError::BatchFailed {
+                        row: row + offset,
+                        source: Box::new(e.into()),
+                    })?;
+            }
+        }
+        row += batch_len;
+    }
+    Ok(())
+}
+
 pub async fn fetch_row_from_graph(query: Query) -> GraphResult<Option<Row>> {
     let graph = get_neo4j_graph()?;
 
@@ -57,6 +105,20 @@ pub async fn fetch_all_rows_from_graph(query: Query) -> GraphResult<Vec<Row>> {
     result.try_collect().await.map_err(Into::into)
 }
 
+/// Like [fetch_all_rows_from_graph], but gives up with [GraphError::QueryTimeout] if the query
+/// does not complete within `timeout`, e.g. an unbounded `[:FOLLOWS*1..n]` traversal.
+///
+/// The query is only dropped on our side: the database may keep running it until its own
+/// transaction timeout.
+pub async fn fetch_all_rows_from_graph_with_timeout(
+    query: Query,
+    timeout: Duration,
+) -> GraphResult<Vec<Row>> {
+    tokio::time::timeout(timeout, fetch_all_rows_from_graph(query))
+        .await
+        .map_err(|_| GraphError::QueryTimeout)?
+}
+
 /// Fetch the value of type T mapped to a specific key from the first row of a graph query's result
 pub async fn fetch_key_from_graph<T>(query: Query, key: &str) -> GraphResult<Option<T>>
 where
@@ -74,3 +136,109 @@ where
         .map_err(Into::into)
         .inspect_err(|e| tracing::error!("Failed to get {key} from query result: {e}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+    use neo4rs::BoltType;
+
+    const BATCH_USER_PREFIX: &str = "test_execute_batch";
+
+    fn user_rows(test_name: &str, count: usize) -> Vec<BoltMap> {
+        (0..count)
+            .map(|i| {
+                let mut row = BoltMap::default();
+                row.put(
+                    "id".into(),
+                    BoltType::String(format!("{BATCH_USER_PREFIX}_{test_name}_{i}").into()),
+                );
+                row.put("name".into(), BoltType::String(format!("User {i}").into()));
+                row
+            })
+            .collect()
+    }
+
+    async fn count_users(test_name: &str) -> GraphResult<i64> {
+        let query = Query::new(
+            "test_count_batch_users",
+            "MATCH (u:User) WHERE u.id STARTS WITH $prefix RETURN count(u) AS count",
+        )
+        .param("prefix", format!("{BATCH_USER_PREFIX}_{test_name}_"));
+        Ok(fetch_key_from_graph(query, "count").await?.unwrap_or(0))
+    }
+
+    async fn delete_users(test_name: &str) -> GraphResult<()> {
+        let query = Query::new(
+            "test_delete_batch_users",
+            "MATCH (u:User) WHERE u.id STARTS WITH $prefix DETACH DELETE u",
+        )
+        .param("prefix", format!("{BATCH_USER_PREFIX}_{test_name}_"));
+        exec_single_row(query).await
+    }
+
+    fn create_user_query() -> Query {
+        Query::new(
+            "test_create_batch_users",
+            "CREATE (u:User {id: row.id, name: row.name})",
+        )
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_fetch_all_rows_with_timeout() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let query = Query::new("test_fast_query", "UNWIND range(1, 3) AS x RETURN x");
+        let rows = fetch_all_rows_from_graph_with_timeout(query, Duration::from_secs(10)).await?;
+        assert_eq!(rows.len(), 3);
+
+        // A cartesian product of 10^5 x 10^5 rows takes far longer than the timeout
+        let query = Query::new(
+            "test_slow_query",
+            "UNWIND range(1, 100000) AS a UNWIND range(1, 100000) AS b RETURN count(*) AS count",
+        );
+        let result =
+            fetch_all_rows_from_graph_with_timeout(query, Duration::from_millis(100)).await;
+        assert!(
+            matches!(result, Err(GraphError::QueryTimeout)),
+            "Expected the slow query to time out, got {:?}",
+            result.map(|rows| rows.len())
+        );
+
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_execute_batch_creates_all_rows() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+        delete_users("all").await?;
+
+        execute_batch(create_user_query(), user_rows("all", 1000)).await?;
+        assert_eq!(count_users("all").await?, 1000);
+
+        delete_users("all").await?;
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_execute_batch_reports_failed_row() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+        delete_users("failed").await?;
+
+        // The user ID is unique, so the duplicate of row 0 at row 250 fails the third batch
+        let mut rows = user_rows("failed", 300);
+        rows[250] = rows[0].clone();
+
+        let result = execute_batch_with_size(create_user_query(), rows, 100).await;
+        assert!(
+            matches!(result, Err(GraphError::BatchFailed { row: 250, .. })),
+            "Expected row 250 to fail, got {result:?}"
+        );
+        // The rows before the failed one are written
+        assert_eq!(count_users("failed").await?, 250);
+
+        delete_users("failed").await?;
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ mod instrumented;
 mod ops;
 pub mod queries;
 mod query;
+mod retry;
 pub mod setup;
 
 pub use error::{GraphError, GraphResult};
@@ -11,3 +12,5 @@ pub(crate) use instrumented::InstrumentedGraph;
 pub(crate) use ops::Graph;
 pub use ops::GraphOps;
 pub use query::Query;
+pub(crate) use retry::RetryGraph;
+pub use retry::{is_transient, with_retry, RetryPolicy};
@@ -56,6 +56,56 @@ pub fn post_counts(author_id: &str, post_id: &str) -> Query {
     .param("post_id", post_id)
 }
 
+// Count the posts anywhere in the reply thread below a post, up to `max_depth` replies deep
+pub fn post_descendants(author_id: &str, post_id: &str, max_depth: usize) -> Query {
+    Query::new(
+        "post_descendants",
+        format!(
+            "
+        MATCH (u:User {{id: $author_id}})-[:AUTHORED]->(p:Post {{id: $post_id}})
+        OPTIONAL MATCH (descendant:Post)-[:REPLIED*1..{max_depth}]->(p)
+        RETURN COUNT(DISTINCT descendant) AS descendants
+    "
+        ),
+    )
+    .param("author_id", author_id)
+    .param("post_id", post_id)
+}
+
+// Get the engagement breakdown of a post: the taggers with the most tags on it,
+// the distinct reply authors, the repost authors and the bookmark count
+pub fn post_engagement_breakdown(author_id: &str, post_id: &str, limit_taggers: usize) -> Query {
+    Query::new(
+        "post_engagement_breakdown",
+        "
+        MATCH (u:User {id: $author_id})-[:AUTHORED]->(p:Post {id: $post_id})
+        CALL {
+            WITH p
+            MATCH (tagger:User)-[t:TAGGED]->(p)
+            WITH tagger, COUNT(t) AS tags
+            ORDER BY tags DESC, tagger.id ASC
+            LIMIT $limit_taggers
+            RETURN COLLECT({user_id: tagger.id, tags: tags}) AS top_taggers
+        }
+        CALL {
+            WITH p
+            OPTIONAL MATCH (replier:User)-[:AUTHORED]->(:Post)-[:REPLIED]->(p)
+            RETURN COUNT(DISTINCT replier) AS unique_repliers
+        }
+        CALL {
+            WITH p
+            OPTIONAL MATCH (reposter:User)-[:AUTHORED]->(:Post)-[:REPOSTED]->(p)
+            RETURN COLLECT(DISTINCT reposter.id) AS repost_authors
+        }
+        RETURN top_taggers, unique_repliers, repost_authors,
+            COUNT { (:User)-[:BOOKMARKED]->(p) } AS bookmarks
+    ",
+    )
+    .param("author_id", author_id)
+    .param("post_id", post_id)
+    .param("limit_taggers", limit_taggers as i64)
+}
+
 // Check if the viewer_id has a bookmark in the post
 pub fn post_bookmark(author_id: &str, post_id: &str, viewer_id: &str) -> Query {
     Query::new(
@@ -135,6 +185,7 @@ pub fn post_relationships(author_id: &str, post_id: &str) -> Query {
           replied_author.id AS replied_author_id,
           reposted_post.id AS reposted_post_id,
           reposted_author.id AS reposted_author_id,
+          reposted_post IS NOT NULL AND trim(p.content) <> '' AS is_quote,
           COLLECT(mentioned_user.id) AS mentioned_user_ids",
     )
     .param("author_id", author_id)
@@ -161,6 +212,36 @@ pub fn get_users_details_by_ids(user_ids: &[&str]) -> Query {
     .param("ids", user_ids)
 }
 
+/// Returns the IDs among `user_ids` that have no user node
+pub fn get_missing_user_ids(user_ids: &[&str]) -> Query {
+    Query::new(
+        "get_missing_user_ids",
+        "
+        UNWIND $ids AS id
+        OPTIONAL MATCH (record:User {id: id})
+        WITH id, record
+        WHERE record IS NULL
+        RETURN COLLECT(id) AS missing
+        ",
+    )
+    .param("ids", user_ids)
+}
+
+/// Returns the `author_id:post_id` keys among the `[author_id, post_id]` pairs that have no post node
+pub fn get_missing_post_keys(key_pairs: &[&[&str]]) -> Query {
+    Query::new(
+        "get_missing_post_keys",
+        "
+        UNWIND $pairs AS pair
+        OPTIONAL MATCH (:User {id: pair[0]})-[:AUTHORED]->(record:Post {id: pair[1]})
+        WITH pair, record
+        WHERE record IS NULL
+        RETURN COLLECT(pair[0] + ':' + pair[1]) AS missing
+        ",
+    )
+    .param("pairs", key_pairs)
+}
+
 /// Retrieves unique global tags for posts, returning a list of `post_ids` and `timestamp` pairs for each tag label.
 pub fn global_tags_by_post() -> Query {
     Query::new(
@@ -376,6 +457,26 @@ pub fn get_user_followers(user_id: &str, skip: Option<usize>, limit: Option<usiz
     Query::new("get_user_followers", &query_string).param("user_id", user_id)
 }
 
+/// Counts the tags a user has applied to posts and users, by label
+pub fn get_tagger_label_counts(user_id: &str) -> Query {
+    Query::new(
+        "get_tagger_label_counts",
+        "MATCH (u:User {id: $user_id})-[t:TAGGED]->()
+         RETURN t.label AS label, COUNT(t) AS tags",
+    )
+    .param("user_id", user_id)
+}
+
+/// Retrieves the followers of a user along with the time they followed them
+pub fn get_user_followed_at(user_id: &str) -> Query {
+    Query::new(
+        "get_user_followed_at",
+        "MATCH (u:User {id: $user_id})<-[r:FOLLOWS]-(follower:User)
+         RETURN follower.id AS follower_id, r.indexed_at AS followed_at",
+    )
+    .param("user_id", user_id)
+}
+
 pub fn get_user_following(user_id: &str, skip: Option<usize>, limit: Option<usize>) -> Query {
     let mut query_string = String::from(
         "MATCH (u:User {id: $user_id}) 
@@ -392,6 +493,40 @@ pub fn get_user_following(user_id: &str, skip: Option<usize>, limit: Option<usiz
     Query::new("get_user_following", &query_string).param("user_id", user_id)
 }
 
+/// Retrieves a page of follower IDs of a user, sorted by ID and starting after the `after` ID
+pub fn get_user_followers_after(user_id: &str, after: Option<&str>, limit: usize) -> Query {
+    Query::new(
+        "get_user_followers_after",
+        "MATCH (u:User {id: $user_id})
+         OPTIONAL MATCH (u)<-[:FOLLOWS]-(follower:User)
+         WHERE follower.id > $after
+         WITH u, follower
+         ORDER BY follower.id
+         RETURN COUNT(u) > 0 AS user_exists,
+                COLLECT(follower.id)[..$limit] AS follower_ids",
+    )
+    .param("user_id", user_id)
+    .param("after", after.unwrap_or_default())
+    .param("limit", limit as i64)
+}
+
+/// Retrieves a page of IDs a user follows, sorted by ID and starting after the `after` ID
+pub fn get_user_following_after(user_id: &str, after: Option<&str>, limit: usize) -> Query {
+    Query::new(
+        "get_user_following_after",
+        "MATCH (u:User {id: $user_id})
+         OPTIONAL MATCH (u)-[:FOLLOWS]->(following:User)
+         WHERE following.id > $after
+         WITH u, following
+         ORDER BY following.id
+         RETURN COUNT(u) > 0 AS user_exists,
+                COLLECT(following.id)[..$limit] AS following_ids",
+    )
+    .param("user_id", user_id)
+    .param("after", after.unwrap_or_default())
+    .param("limit", limit as i64)
+}
+
 fn stream_reach_to_graph_subquery(reach: &StreamReach) -> String {
     match reach {
         StreamReach::Followers => "MATCH (user:User)<-[:FOLLOWS]-(reach:User)".to_string(),
@@ -427,6 +562,19 @@ pub fn get_tags() -> Query {
     )
 }
 
+/// Retrieves the IDs of all users within the given `reach` of `user_id`
+pub fn get_user_ids_by_reach(user_id: &str, reach: &StreamReach) -> Query {
+    let cypher = format!(
+        "
+            {}
+            WHERE user.id = $user_id AND reach.id <> $user_id
+            RETURN COLLECT(DISTINCT reach.id) AS user_ids
+            ",
+        stream_reach_to_graph_subquery(reach)
+    );
+    Query::new("get_user_ids_by_reach", &cypher).param("user_id", user_id)
+}
+
 pub fn get_tag_taggers_by_reach(
     label: &str,
     user_id: &str,
@@ -639,12 +787,16 @@ pub fn get_files_by_ids(key_pair: &[&[&str]]) -> Query {
 }
 
 // Build the graph query based on parameters
+// The tags are matched with OR semantics: the posts tagged with any of the labels are streamed.
+// The callers bound the number of labels, see `max_stream_tags`
 pub fn post_stream(
     source: StreamSource,
     sorting: StreamSorting,
     tags: &Option<Vec<String>>,
     pagination: Pagination,
     kind: Option<PubkyAppPostKind>,
+    quotes_only: bool,
+    min_engagement: u64,
 ) -> Query {
     // Initialize the cypher query
     let mut cypher = String::new();
@@ -668,13 +820,13 @@ pub fn post_stream(
         StreamSource::Friends { .. } => {
             Some("MATCH (observer)-[:FOLLOWS]->(author)-[:FOLLOWS]->(observer)\n")
         }
-        StreamSource::Bookmarks { .. } => Some("MATCH (observer)-[:BOOKMARKED]->(p)\n"),
+        StreamSource::Bookmarks { .. } => Some("MATCH (observer)-[bookmark:BOOKMARKED]->(p)\n"),
         _ => None,
     } {
         cypher.push_str(query);
     }
 
-    // Apply tags
+    // Apply tags, any of them is enough for a post to match
     if tags.is_some() {
         cypher.push_str("MATCH (User)-[tag:TAGGED]->(p)\n");
         append_condition(
@@ -694,18 +846,39 @@ pub fn post_stream(
         );
     }
 
+    // Only keep the posts bookmarked within the requested timeframe
+    if let StreamSource::Bookmarks {
+        timeframe: Some(_), ..
+    } = source
+    {
+        append_condition(
+            &mut cypher,
+            "bookmark.indexed_at >= $bookmarked_since",
+            &mut where_clause_applied,
+        );
+    }
+
     // If post kind is provided, add the corresponding condition
     if kind.is_some() {
         append_condition(&mut cypher, "p.kind = $kind", &mut where_clause_applied);
     }
 
-    // Filter just the parent posts: StreamSource:PostReplies and StreamSource:AuthorReplies do not reach that query
-    // so we do not need any condition to filter just parent nodes
-    append_condition(
-        &mut cypher,
-        "NOT ( (p)-[:REPLIED]->(:Post) )",
-        &mut where_clause_applied,
-    );
+    // Quote-posts are reposts with their own content
+    if quotes_only {
+        append_condition(
+            &mut cypher,
+            "(p)-[:REPOSTED]->(:Post) AND trim(p.content) <> ''",
+            &mut where_clause_applied,
+        );
+    }
+
+    // The replies of an author reach that query when they are filtered, e.g. by kind. Any other
+    // source only streams the parent posts. StreamSource:PostReplies does not reach that query
+    let replies_condition = match source {
+        StreamSource::AuthorReplies { .. } => "(p)-[:REPLIED]->(:Post)",
+        _ => "NOT ( (p)-[:REPLIED]->(:Post) )",
+    };
+    append_condition(&mut cypher, replies_condition, &mut where_clause_applied);
 
     // Apply time interval conditions. Only can be applied with timeline sorting
     // The engagament score has to be computed
@@ -732,6 +905,9 @@ pub fn post_stream(
 
     // Apply StreamSorting
     // Conditionally compute engagement counts only for TotalEngagement sorting
+    // The engagement components are then returned along with the keys
+    let mut return_clause =
+        String::from("RETURN author.id AS author_id, p.id AS post_id, p.indexed_at AS indexed_at");
     let order_clause = match sorting {
         StreamSorting::Timeline => "ORDER BY p.indexed_at DESC".to_string(),
         StreamSorting::TotalEngagement => {
@@ -773,14 +949,22 @@ pub fn post_stream(
                 );
             }
 
+            // Leave out the posts with too few interactions to be ranked
+            if min_engagement > 0 {
+                append_condition(
+                    &mut cypher,
+                    "total_engagement >= $min_engagement",
+                    &mut where_clause_applied,
+                );
+            }
+
+            return_clause.push_str(", tags_count, replies_count, reposts_count");
             "ORDER BY total_engagement DESC".to_string()
         }
     };
 
     // Final return statement
-    cypher.push_str(&format!(
-        "RETURN author.id AS author_id, p.id AS post_id, p.indexed_at AS indexed_at\n{order_clause}\n"
-    ));
+    cypher.push_str(&format!("{return_clause}\n{order_clause}\n"));
 
     // Apply skip and limit
     if let Some(skip) = pagination.skip {
@@ -804,7 +988,11 @@ pub fn post_stream(
         },
         &cypher,
     );
-    build_query_with_params(query, &source, tags, kind, &pagination)
+    let query = build_query_with_params(query, &source, tags, kind, &pagination);
+    match min_engagement > 0 && sorting == StreamSorting::TotalEngagement {
+        true => query.param("min_engagement", min_engagement as i64),
+        false => query,
+    }
 }
 
 /// Appends a condition to the Cypher query, using `WHERE` if no `WHERE` clause
@@ -844,6 +1032,14 @@ fn build_query_with_params(
     if let Some(observer_id) = source.get_observer() {
         query = query.param("observer_id", observer_id.to_string());
     }
+    if let StreamSource::Bookmarks {
+        timeframe: Some(timeframe),
+        ..
+    } = source
+    {
+        let (since, _) = timeframe.to_timestamp_range();
+        query = query.param("bookmarked_since", since);
+    }
     if let Some(labels) = tags.clone() {
         query = query.param("labels", labels);
     }
@@ -937,6 +1133,79 @@ pub fn recommend_users(user_id: &str, limit: usize) -> Query {
     .param("limit", limit as i64)
 }
 
+/// Ranks the users followed by the users the given user follows, by the number of them that
+/// follow each. The traversal is capped to the second degree. The users already followed, the
+/// excluded ones, deleted users and the user itself are left out
+pub fn who_to_follow(user_id: &str, excluded_ids: &[String], limit: usize) -> Query {
+    Query::new(
+        "who_to_follow",
+        "
+        MATCH (user:User {id: $user_id})-[:FOLLOWS]->(followee:User)-[:FOLLOWS]->(candidate:User)
+        WHERE candidate.id <> $user_id
+          AND candidate.name <> '[DELETED]'
+          AND NOT candidate.id IN $excluded_ids
+          AND NOT (user)-[:FOLLOWS]->(candidate)
+        WITH candidate.id AS candidate_id, COUNT(DISTINCT followee) AS shared_follows
+        WITH {
+            user_id: candidate_id,
+            shared_follows: shared_follows
+        } AS recommendation
+        ORDER BY recommendation.shared_follows DESC, recommendation.user_id ASC
+        LIMIT $limit
+        RETURN COLLECT(recommendation) AS recommendations
+    ",
+    )
+    .param("user_id", user_id.to_string())
+    .param("excluded_ids", excluded_ids.to_vec())
+    .param("limit", limit as i64)
+}
+
+/// Ranks the users the given user has interacted with the most: replies and reposts of their
+/// posts, and tags on them or on their posts. Deleted users and the user itself are excluded
+pub fn user_top_connections(user_id: &str, limit: usize) -> Query {
+    Query::new(
+        "user_top_connections",
+        "
+        MATCH (user:User {id: $user_id})
+        CALL {
+            WITH user
+            MATCH (user)-[:AUTHORED]->(:Post)-[:REPLIED]->(:Post)<-[:AUTHORED]-(target:User)
+            RETURN target, 'reply' AS kind
+            UNION ALL
+            WITH user
+            MATCH (user)-[:AUTHORED]->(:Post)-[:REPOSTED]->(:Post)<-[:AUTHORED]-(target:User)
+            RETURN target, 'repost' AS kind
+            UNION ALL
+            WITH user
+            MATCH (user)-[:TAGGED]->(target:User)
+            RETURN target, 'tag' AS kind
+            UNION ALL
+            WITH user
+            MATCH (user)-[:TAGGED]->(:Post)<-[:AUTHORED]-(target:User)
+            RETURN target, 'tag' AS kind
+        }
+        WITH target, kind
+        WHERE target.id <> $user_id AND target.name <> '[DELETED]'
+        WITH target.id AS target_id,
+             SUM(CASE kind WHEN 'reply' THEN 1 ELSE 0 END) AS replies,
+             SUM(CASE kind WHEN 'repost' THEN 1 ELSE 0 END) AS reposts,
+             SUM(CASE kind WHEN 'tag' THEN 1 ELSE 0 END) AS tags
+        WITH {
+            user_id: target_id,
+            replies: replies,
+            reposts: reposts,
+            tags: tags,
+            total: replies + reposts + tags
+        } AS connection
+        ORDER BY connection.total DESC, connection.user_id ASC
+        LIMIT $limit
+        RETURN COLLECT(connection) AS connections
+    ",
+    )
+    .param("user_id", user_id.to_string())
+    .param("limit", limit as i64)
+}
+
 /// Retrieve specific tag created by the user
 pub fn get_tag_by_tagger_and_id(tagger_id: &str, tag_id: &str) -> Query {
     Query::new(
@@ -956,3 +1225,164 @@ pub fn get_tag_by_tagger_and_id(tagger_id: &str, tag_id: &str) -> Query {
     .param("tagger_id", tagger_id)
     .param("tag_id", tag_id)
 }
+
+/// Retrieve a page of the tags created by the user, oldest first
+pub fn get_tags_by_tagger(tagger_id: &str, skip: usize, limit: usize) -> Query {
+    Query::new(
+        "get_tags_by_tagger",
+        "
+        MATCH (tagger:User { id: $tagger_id})-[tag:TAGGED]->(tagged)
+        OPTIONAL MATCH (author:User)-[:AUTHORED]->(tagged)
+        RETURN
+            labels(tagged) as tagged_labels,
+            tagged.id as tagged_id,
+            author.id as author_id,
+            tag.id as id,
+            tag.indexed_at as indexed_at,
+            tag.label as label
+        ORDER BY tag.indexed_at ASC, tag.id ASC
+        SKIP $skip
+        LIMIT $limit
+        ",
+    )
+    .param("tagger_id", tagger_id)
+    .param("skip", skip as i64)
+    .param("limit", limit as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTHOR_ID: &str = "pxnu33x7jtpx9ar1ytsi4yxbp6a5o36gwhffs8zoxmbuptici1jy";
+
+    #[test]
+    fn post_stream_of_author_replies_requires_reply() {
+        let source = StreamSource::AuthorReplies {
+            author_id: AUTHOR_ID.to_string(),
+        };
+        let query = post_stream(
+            source,
+            StreamSorting::Timeline,
+            &None,
+            Pagination::default(),
+            Some(PubkyAppPostKind::Short),
+            false,
+            0,
+        );
+        let cypher = query.to_cypher_populated();
+
+        assert!(cypher.contains("(p)-[:REPLIED]->(:Post)"));
+        assert!(!cypher.contains("NOT ( (p)-[:REPLIED]->(:Post) )"));
+        assert!(cypher.contains(&format!("author.id = '{AUTHOR_ID}'")));
+    }
+
+    #[test]
+    fn post_stream_of_author_excludes_replies() {
+        let source = StreamSource::Author {
+            author_id: AUTHOR_ID.to_string(),
+            collapse_self_threads: false,
+        };
+        let query = post_stream(
+            source,
+            StreamSorting::Timeline,
+            &None,
+            Pagination::default(),
+            Some(PubkyAppPostKind::Short),
+            false,
+            0,
+        );
+        let cypher = query.to_cypher_populated();
+
+        assert!(cypher.contains("NOT ( (p)-[:REPLIED]->(:Post) )"));
+    }
+
+    #[test]
+    fn post_stream_by_engagement_filters_kind() {
+        let pagination = Pagination {
+            start: Some(10.0),
+            ..Default::default()
+        };
+        let query = post_stream(
+            StreamSource::All,
+            StreamSorting::TotalEngagement,
+            &None,
+            pagination,
+            Some(PubkyAppPostKind::Short),
+            false,
+            0,
+        );
+        let cypher = query.to_cypher_populated();
+
+        // The kind is filtered before the posts are aggregated by engagement
+        let kind_condition = cypher
+            .find("p.kind = 'short'")
+            .expect("The kind condition should be applied");
+        let aggregation = cypher
+            .find("WITH DISTINCT p, author")
+            .expect("The posts should be aggregated");
+        assert!(kind_condition < aggregation);
+
+        // The engagement conditions start a new WHERE clause after the aggregation
+        assert!(cypher.contains("WHERE total_engagement <= 10"));
+        assert!(cypher.contains("ORDER BY total_engagement DESC"));
+    }
+
+    #[test]
+    fn post_stream_returns_engagement_components_only_by_engagement() {
+        let components = "tags_count, replies_count, reposts_count\n";
+
+        let by_engagement = post_stream(
+            StreamSource::All,
+            StreamSorting::TotalEngagement,
+            &None,
+            Pagination::default(),
+            None,
+            false,
+            0,
+        );
+        let cypher = by_engagement.to_cypher_populated();
+        assert!(cypher.contains(&format!(
+            "RETURN author.id AS author_id, p.id AS post_id, p.indexed_at AS indexed_at, {components}"
+        )));
+
+        let by_timeline = post_stream(
+            StreamSource::All,
+            StreamSorting::Timeline,
+            &None,
+            Pagination::default(),
+            None,
+            false,
+            0,
+        );
+        assert!(!by_timeline.to_cypher_populated().contains(components));
+    }
+
+    #[test]
+    fn post_stream_by_engagement_requires_min_engagement() {
+        let query = post_stream(
+            StreamSource::All,
+            StreamSorting::TotalEngagement,
+            &None,
+            Pagination::default(),
+            None,
+            false,
+            3,
+        );
+        let cypher = query.to_cypher_populated();
+        assert!(cypher.contains("WHERE total_engagement >= 3"));
+
+        // The timeline is not ranked by engagement
+        let query = post_stream(
+            StreamSource::All,
+            StreamSorting::Timeline,
+            &None,
+            Pagination::default(),
+            None,
+            false,
+            3,
+        );
+        let cypher = query.to_cypher_populated();
+        assert!(!cypher.contains("total_engagement >="));
+    }
+}
@@ -284,6 +284,31 @@ pub fn create_user_tag(
     .param("indexed_at", indexed_at)
 }
 
+/// Relabels the `TAGGED` relationships using the `alias` label to `label`. A tagger that already
+/// tagged the same target with `label` keeps that tag, and its `alias` tag is deleted.
+/// Returns one row per affected target, with the IDs of the taggers that used `alias`
+/// # Arguments
+/// * `alias` - The label being folded.
+/// * `label` - The canonical label the `alias` tags are moved to.
+pub fn fold_tag_label(alias: &str, label: &str) -> Query {
+    Query::new(
+        "fold_tag_label",
+        "MATCH (tagger:User)-[tag:TAGGED {label: $alias}]->(target)
+         OPTIONAL MATCH (tagger)-[existing:TAGGED {label: $label}]->(target)
+         OPTIONAL MATCH (target)<-[:AUTHORED]-(author:User)
+         WITH tagger, tag, existing, target, author
+         FOREACH (_ IN CASE WHEN existing IS NULL THEN [1] ELSE [] END | SET tag.label = $label)
+         FOREACH (_ IN CASE WHEN existing IS NOT NULL THEN [1] ELSE [] END | DELETE tag)
+         WITH CASE WHEN target:User THEN target.id ELSE null END AS user_id,
+              CASE WHEN target:Post THEN target.id ELSE null END AS post_id,
+              CASE WHEN target:Post THEN author.id ELSE null END AS author_id,
+              COLLECT(DISTINCT tagger.id) AS taggers
+         RETURN user_id, post_id, author_id, taggers",
+    )
+    .param("alias", alias)
+    .param("label", label)
+}
+
 /// Create a file node
 pub fn create_file(file: &FileDetails) -> GraphResult<Query> {
     let urls = serde_json::to_string(&file.urls)
@@ -293,7 +318,8 @@ pub fn create_file(file: &FileDetails) -> GraphResult<Query> {
         "create_file",
         "MERGE (f:File {id: $id, owner_id: $owner_id})
          SET f.uri = $uri, f.indexed_at = $indexed_at, f.created_at = $created_at, f.size = $size,
-            f.src = $src, f.name = $name, f.content_type = $content_type, f.urls = $urls;",
+            f.src = $src, f.name = $name, f.content_type = $content_type, f.urls = $urls,
+            f.blurhash = $blurhash;",
     )
     .param("id", file.id.to_string())
     .param("owner_id", file.owner_id.to_string())
@@ -304,7 +330,8 @@ pub fn create_file(file: &FileDetails) -> GraphResult<Query> {
     .param("src", file.src.to_string())
     .param("name", file.name.to_string())
     .param("content_type", file.content_type.to_string())
-    .param("urls", urls);
+    .param("urls", urls)
+    .param("blurhash", file.blurhash.clone());
 
     Ok(query)
 }
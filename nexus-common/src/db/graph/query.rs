@@ -9,14 +9,19 @@ pub struct Query {
     label: Option<&'static str>,
     cypher: String,
     params: BoltMap,
+    idempotent: bool,
 }
 
+/// Cypher clauses that write to the graph
+const WRITE_CLAUSES: [&str; 6] = ["CREATE", "MERGE", "SET", "DELETE", "REMOVE", "FOREACH"];
+
 impl Query {
     pub fn new(label: &'static str, cypher: impl Into<String>) -> Self {
         Self {
             label: Some(label),
             cypher: cypher.into(),
             params: BoltMap::default(),
+            idempotent: false,
         }
     }
 
@@ -24,6 +29,30 @@ impl Query {
         self.label
     }
 
+    /// Marks a write as idempotent: running it again after an error that left its outcome unknown
+    /// gives the same graph, e.g. a query made of `MERGE`s and constant `SET`s
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    /// Whether the query can safely be run again after a failed attempt: it only reads from the
+    /// graph, or it was marked [idempotent](Self::idempotent)
+    pub fn is_retryable(&self) -> bool {
+        self.idempotent || !self.writes()
+    }
+
+    /// Whether the cypher contains a clause that writes to the graph
+    fn writes(&self) -> bool {
+        self.cypher
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .any(|word| {
+                WRITE_CLAUSES
+                    .iter()
+                    .any(|clause| word.eq_ignore_ascii_case(clause))
+            })
+    }
+
     pub fn param<T: Into<BoltType>>(mut self, key: &str, value: T) -> Self {
         self.params.put(key.into(), value.into());
         self
@@ -40,6 +69,19 @@ impl Query {
         self
     }
 
+    /// Returns a copy of this query run once per row of `batch`, which the cypher
+    /// reads as `row` (e.g. `CREATE (u:User {id: row.id})`).
+    pub fn unwind(&self, batch: Vec<BoltMap>) -> Self {
+        let batch: Vec<BoltType> = batch.into_iter().map(BoltType::Map).collect();
+        Self {
+            label: self.label,
+            cypher: format!("UNWIND $batch AS row\n{}", self.cypher),
+            params: self.params.clone(),
+            idempotent: self.idempotent,
+        }
+        .param("batch", BoltType::List(BoltList::from(batch)))
+    }
+
     /// Returns the cypher string with `$param` placeholders replaced by their
     /// literal values, ready to copy-paste into a Neo4j browser.
     pub fn to_cypher_populated(&self) -> String {
@@ -130,6 +172,7 @@ fn query(cypher: impl Into<String>) -> Query {
         label: None,
         cypher: cypher.into(),
         params: BoltMap::default(),
+        idempotent: false,
     }
 }
 
@@ -137,6 +180,27 @@ fn query(cypher: impl Into<String>) -> Query {
 mod tests {
     use super::*;
 
+    // ── is_retryable ────────────────────────────────────────────────
+
+    #[test]
+    fn reads_are_retryable() {
+        assert!(query("MATCH (u:User {id: $id}) RETURN u.settings").is_retryable());
+    }
+
+    #[test]
+    fn writes_are_not_retryable() {
+        assert!(!query("MATCH (u:User {id: $id}) SET u.name = $name").is_retryable());
+        assert!(!query("MATCH (p:Post)\nDETACH DELETE p").is_retryable());
+        assert!(!query("create (u:User {id: $id})").is_retryable());
+    }
+
+    #[test]
+    fn idempotent_writes_are_retryable() {
+        assert!(query("MERGE (u:User {id: $id})")
+            .idempotent()
+            .is_retryable());
+    }
+
     // ── bolt_to_cypher_literal ──────────────────────────────────────
 
     #[test]
@@ -303,6 +367,17 @@ mod tests {
 
     // ── Query builder ───────────────────────────────────────────────
 
+    #[test]
+    fn query_unwind_batch() {
+        let mut row = BoltMap::default();
+        row.put("id".into(), BoltType::String("abc".into()));
+        let q = query("CREATE (u:User {id: row.id})").unwind(vec![row]);
+        assert_eq!(
+            q.to_cypher_populated(),
+            "UNWIND [{id: 'abc'}] AS row\nCREATE (u:User {id: row.id})"
+        );
+    }
+
     #[test]
     fn query_builder_params_batch() {
         let q = query("MATCH (u {id: $id, name: $name})").params(vec![
@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use neo4rs::{Neo4jErrorKind, Row};
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+use super::ops::GraphOps;
+use super::query::Query;
+
+/// How often and how fast failed graph queries are retried by [with_retry]
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt. `0` disables retries
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each following one
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before the retry number `retry` (starting at 0)
+    fn delay(&self, retry: u32) -> Duration {
+        self.base_delay.saturating_mul(1 << retry.min(16))
+    }
+}
+
+/// Whether an error may go away by running the same query again, e.g. a dropped connection or a
+/// cluster failover. Deterministic errors (syntax, constraint violations, ...) are not transient.
+pub fn is_transient(error: &neo4rs::Error) -> bool {
+    match error {
+        neo4rs::Error::IOError { .. } | neo4rs::Error::ConnectionError => true,
+        // Server side errors are classified by the class of their Neo4j status code
+        neo4rs::Error::Neo4j(error) => matches!(error.kind(), Neo4jErrorKind::Transient),
+        _ => false,
+    }
+}
+
+/// Runs `operation` until it succeeds, fails with an error that is not [transient](is_transient),
+/// or `policy.max_retries` retries were made, waiting with exponential backoff between attempts.
+pub async fn with_retry<T, F, Fut>(
+    policy: RetryPolicy,
+    label: Option<&str>,
+    mut operation: F,
+) -> neo4rs::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = neo4rs::Result<T>>,
+{
+    let mut retry = 0;
+    loop {
+        match operation().await {
+            Err(e) if retry < policy.max_retries && is_transient(&e) => {
+                let delay = policy.delay(retry);
+                retry += 1;
+                warn!(
+                    query = label.unwrap_or("unknown"),
+                    retry,
+                    delay_ms = delay.as_millis(),
+                    "Transient Neo4j error, retrying: {e}"
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Decorator around [`GraphOps`] that retries transient errors with [with_retry].
+///
+/// Only [retryable](Query::is_retryable) queries are retried: a failed write may have been applied
+/// before the error, so running it again could apply it twice. Only starting a query is retried: an
+/// error while reading the rows of a stream is returned as-is, since some rows may have been
+/// consumed already.
+#[derive(Clone)]
+pub struct RetryGraph<G> {
+    inner: G,
+    policy: RetryPolicy,
+}
+
+impl<G: GraphOps> RetryGraph<G> {
+    pub fn new(graph: G, policy: RetryPolicy) -> Self {
+        Self {
+            inner: graph,
+            policy,
+        }
+    }
+}
+
+#[async_trait]
+impl<G: GraphOps> GraphOps for RetryGraph<G> {
+    async fn execute(
+        &self,
+        query: Query,
+    ) -> neo4rs::Result<BoxStream<'static, Result<Row, neo4rs::Error>>> {
+        if !query.is_retryable() {
+            return self.inner.execute(query).await;
+        }
+        with_retry(self.policy, query.label(), || {
+            self.inner.execute(query.clone())
+        })
+        .await
+    }
+
+    async fn run(&self, query: Query) -> neo4rs::Result<()> {
+        if !query.is_retryable() {
+            return self.inner.run(query).await;
+        }
+        with_retry(self.policy, query.label(), || self.inner.run(query.clone())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{stream, StreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Mock `GraphOps` failing its first `failures` calls with `error`
+    struct FlakyGraph {
+        failures: usize,
+        error: fn() -> neo4rs::Error,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl FlakyGraph {
+        fn attempt(&self) -> neo4rs::Result<()> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            match call < self.failures {
+                true => Err((self.error)()),
+                false => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl GraphOps for FlakyGraph {
+        async fn execute(
+            &self,
+            _query: Query,
+        ) -> neo4rs::Result<BoxStream<'static, Result<Row, neo4rs::Error>>> {
+            self.attempt()?;
+            Ok(stream::empty().boxed())
+        }
+
+        async fn run(&self, _query: Query) -> neo4rs::Result<()> {
+            self.attempt()
+        }
+    }
+
+    fn retry_graph(
+        failures: usize,
+        error: fn() -> neo4rs::Error,
+        max_retries: u32,
+    ) -> (RetryGraph<FlakyGraph>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let graph = FlakyGraph {
+            failures,
+            error,
+            calls: calls.clone(),
+        };
+        let policy = RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+        };
+        (RetryGraph::new(graph, policy), calls)
+    }
+
+    fn test_query() -> Query {
+        Query::new("test_retry", "RETURN 1")
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_success() {
+        let (graph, calls) = retry_graph(2, || neo4rs::Error::ConnectionError, 3);
+
+        assert!(graph.run(test_query()).await.is_ok());
+        // One failed attempt, two retries and the last one succeeds
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_execute() {
+        let (graph, calls) = retry_graph(2, || neo4rs::Error::ConnectionError, 3);
+
+        let rows: Vec<_> = graph.execute(test_query()).await.unwrap().collect().await;
+        assert!(rows.is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let (graph, calls) = retry_graph(usize::MAX, || neo4rs::Error::ConnectionError, 2);
+
+        assert!(graph.run(test_query()).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_writes() {
+        let (graph, calls) = retry_graph(1, || neo4rs::Error::ConnectionError, 3);
+
+        let write = Query::new("test_retry_write", "CREATE (u:User {id: 'retry'})");
+        assert!(graph.run(write).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_idempotent_writes() {
+        let (graph, calls) = retry_graph(1, || neo4rs::Error::ConnectionError, 3);
+
+        let write = Query::new("test_retry_merge", "MERGE (u:User {id: 'retry'})").idempotent();
+        assert!(graph.run(write).await.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_deterministic_errors() {
+        let (graph, calls) = retry_graph(
+            1,
+            || neo4rs::Error::UnexpectedMessage("Neo.ClientError.Statement.SyntaxError".into()),
+            3,
+        );
+
+        assert!(graph.run(test_query()).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+    }
+}
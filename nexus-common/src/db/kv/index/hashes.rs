@@ -0,0 +1,92 @@
+use crate::db::get_redis_conn;
+use crate::db::kv::RedisResult;
+use deadpool_redis::redis::AsyncCommands;
+use std::collections::HashMap;
+
+/// Sets a field of a Redis hash, creating the hash if it doesn't exist.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the hash is stored.
+/// * `field` - A string slice representing the field to set.
+/// * `value` - A string slice representing the value of the field.
+///
+/// # Errors
+///
+/// Returns an error if the operation fails.
+pub async fn put(prefix: &str, key: &str, field: &str, value: &str) -> RedisResult<()> {
+    let index_key = format!("{prefix}:{key}");
+    let mut redis_conn = get_redis_conn().await?;
+
+    let _: () = redis_conn.hset(index_key, field, value).await?;
+    Ok(())
+}
+
+/// Retrieves the value of a field of a Redis hash.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the hash is stored.
+/// * `field` - A string slice representing the field to read.
+///
+/// # Returns
+///
+/// Returns `Ok(Some(value))` if the field exists, or `Ok(None)` if the hash or the field does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the operation fails.
+pub async fn get(prefix: &str, key: &str, field: &str) -> RedisResult<Option<String>> {
+    let index_key = format!("{prefix}:{key}");
+    let mut redis_conn = get_redis_conn().await?;
+
+    let value: Option<String> = redis_conn.hget(index_key, field).await?;
+    Ok(value)
+}
+
+/// Retrieves all the fields and values of a Redis hash.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the hash is stored.
+///
+/// # Returns
+///
+/// Returns the fields of the hash mapped to their values, empty if the hash does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the operation fails.
+pub async fn get_all(prefix: &str, key: &str) -> RedisResult<HashMap<String, String>> {
+    let index_key = format!("{prefix}:{key}");
+    let mut redis_conn = get_redis_conn().await?;
+
+    let fields: HashMap<String, String> = redis_conn.hgetall(index_key).await?;
+    Ok(fields)
+}
+
+/// Removes a field from a Redis hash.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the hash is stored.
+/// * `field` - A string slice representing the field to remove.
+///
+/// # Returns
+///
+/// Returns `true` if the field existed and was removed.
+///
+/// # Errors
+///
+/// Returns an error if the operation fails.
+pub async fn del(prefix: &str, key: &str, field: &str) -> RedisResult<bool> {
+    let index_key = format!("{prefix}:{key}");
+    let mut redis_conn = get_redis_conn().await?;
+
+    let removed: usize = redis_conn.hdel(index_key, field).await?;
+    Ok(removed > 0)
+}
@@ -161,6 +161,120 @@ pub async fn modify_json_field(
     Ok(())
 }
 
+/// Maximum number of attempts of [modify_json_field_with_score] when the watched keys keep
+/// changing under it
+pub const MAX_TRANSACTION_ATTEMPTS: usize = 50;
+
+/// A member of a sorted set whose score follows a JSON numeric field, see [modify_json_field_with_score]
+pub struct ScoreTarget<'a> {
+    pub prefix: &'a str,
+    pub key: &'a str,
+    pub member: &'a str,
+}
+
+/// Modifies a numeric field in a Redis JSON object, like [modify_json_field], and applies the
+/// same change to the score of a sorted set member, in a single transaction.
+///
+/// Both keys are `WATCH`ed while the current value is read, and the updates are committed with
+/// `MULTI`/`EXEC`. If another client changed one of the keys in between, the transaction is
+/// aborted and retried, up to [MAX_TRANSACTION_ATTEMPTS] times. The score changes by the
+/// actual change of the field after clamping it in `range`, so that the count and the score
+/// never diverge.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis key.
+/// * `key` - A string slice representing the Redis key where the JSON object is stored.
+/// * `field` - A string slice representing the field to be modified in the JSON object.
+/// * `action` - A `JsonAction` enum that specifies whether to increment or decrement the field.
+/// * `score` - The sorted set member to update with the field, if any.
+///
+/// # Errors
+///
+/// Returns an error if the JSON object does not exist, if the operation fails or if the
+/// transaction still conflicts after [MAX_TRANSACTION_ATTEMPTS] attempts.
+pub async fn modify_json_field_with_score(
+    prefix: &str,
+    key: &str,
+    field: &str,
+    action: JsonAction,
+    range: Option<ValueRange>,
+    score: Option<ScoreTarget<'_>>,
+) -> RedisResult<()> {
+    let mut redis_conn = get_redis_conn().await?;
+    let index_key = format!("{prefix}:{key}");
+    let json_path = format!("$.{field}");
+    let sorted_key = score
+        .as_ref()
+        .map(|target| format!("{}:{}", target.prefix, target.key));
+
+    let amount = match action {
+        JsonAction::Increment(value) => value,
+        JsonAction::Decrement(value) => -value,
+    };
+    let range = range.unwrap_or_default();
+
+    for attempt in 1..=MAX_TRANSACTION_ATTEMPTS {
+        let mut watch = redis::cmd("WATCH");
+        watch.arg(&index_key);
+        if let Some(sorted_key) = &sorted_key {
+            watch.arg(sorted_key);
+        }
+        let _: () = watch.query_async(&mut redis_conn).await?;
+
+        let current_value: Option<String> = redis_conn.json_get(&index_key, &json_path).await?;
+        let Some(current_value) = current_value else {
+            let _: () = redis::cmd("UNWATCH").query_async(&mut redis_conn).await?;
+            return Err(RedisError::InvalidInput(format!(
+                "Cannot modify field {field}, {index_key} does not exist"
+            )));
+        };
+        let current = serde_json::from_str::<serde_json::Value>(&current_value)
+            .ok()
+            .and_then(|decoded| match decoded {
+                serde_json::Value::Array(values) => values.first().and_then(|v| v.as_i64()),
+                other => other.as_i64(),
+            })
+            .unwrap_or(0);
+
+        let new_value = (current + amount).clamp(range.min, range.max);
+        let change = new_value - current;
+        if change == 0 {
+            let _: () = redis::cmd("UNWATCH").query_async(&mut redis_conn).await?;
+            return Ok(());
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        pipe.json_set(&index_key, &json_path, &new_value)?;
+        if let (Some(target), Some(sorted_key)) = (&score, &sorted_key) {
+            pipe.zincr(sorted_key, target.member, change as f64);
+        }
+
+        // EXEC replies with nil when a watched key changed, aborting the transaction
+        let committed: Option<redis::Value> = pipe.query_async(&mut redis_conn).await?;
+        if committed.is_some() {
+            debug!(
+                "Modified field: {} in key: {} by {} (attempt {})",
+                field, index_key, change, attempt
+            );
+            return Ok(());
+        }
+        trace!(
+            "Transaction on {} conflicted, retrying (attempt {})",
+            index_key,
+            attempt
+        );
+    }
+
+    Err(RedisError::CommandFailed(
+        format!(
+            "Transaction on {index_key} still conflicted after {MAX_TRANSACTION_ATTEMPTS} attempts"
+        )
+        .into(),
+    ))
+}
+
 /// Handles storing a boolean value in Redis with an optional expiration.
 ///
 /// This function sets a key in Redis to either `1` or `0`, depending on the boolean value provided.
@@ -1,6 +1,7 @@
 use crate::db::get_redis_conn;
 use crate::db::kv::error::RedisResult;
-use deadpool_redis::redis::AsyncCommands;
+use crate::db::kv::SortOrder;
+use deadpool_redis::redis::{AsyncCommands, Script};
 
 /// Adds elements to a Redis list.
 ///
@@ -12,24 +13,59 @@ use deadpool_redis::redis::AsyncCommands;
 /// * `prefix` - A string slice representing the prefix for the Redis keys.
 /// * `key` - A string slice representing the key under which the list is stored.
 /// * `values` - A slice of string slices representing the elements to be added to the list.
+/// * `max_len` - If `Some`, the list is trimmed to its `max_len` most recent elements after the
+///   push, in the same atomic operation. If `None`, the list grows unbounded.
 ///
 /// # Errors
 ///
 /// Returns an error if the operation fails.
-pub async fn put(prefix: &str, key: &str, values: &[&str]) -> RedisResult<()> {
+pub async fn put(
+    prefix: &str,
+    key: &str,
+    values: &[&str],
+    max_len: Option<usize>,
+) -> RedisResult<()> {
     if values.is_empty() {
         return Ok(());
     }
     let index_key = format!("{prefix}:{key}");
     let mut redis_conn = get_redis_conn().await?;
-    let _: () = redis_conn.rpush(index_key, values).await?;
+
+    let Some(max_len) = max_len else {
+        let _: () = redis_conn.rpush(index_key, values).await?;
+        return Ok(());
+    };
+
+    // Push and trim in a single script, so that readers never see the list above its max length
+    let script = Script::new(
+        r#"
+        local max_len = tonumber(ARGV[1])
+        for i = 2, #ARGV do
+            redis.call('RPUSH', KEYS[1], ARGV[i])
+        end
+        if max_len == 0 then
+            redis.call('DEL', KEYS[1])
+        else
+            redis.call('LTRIM', KEYS[1], -max_len, -1)
+        end
+        return 1
+    "#,
+    );
+
+    let mut invocation = script.key(index_key);
+    invocation.arg(max_len);
+    for value in values {
+        invocation.arg(*value);
+    }
+    let _: i64 = invocation.invoke_async(&mut redis_conn).await?;
     Ok(())
 }
 
 /// Retrieves a range of elements from a Redis list.
 ///
 /// This function retrieves elements from a specified Redis list within a given range.
-/// The range is defined by `skip` and `limit` parameters.
+/// The range is defined by `skip` and `limit` parameters, counted from the head of the list
+/// in ascending order and from its tail in descending order.
 ///
 /// # Arguments
 ///
@@ -37,6 +73,7 @@ pub async fn put(prefix: &str, key: &str, values: &[&str]) -> RedisResult<()> {
 /// * `key` - A string slice representing the key under which the list is stored.
 /// * `skip` - The number of elements to skip from the beginning of the list.
 /// * `limit` - The number of elements to retrieve from the list after the skip.
+/// * `order` - `Ascending` returns the elements in insertion order, `Descending` newest first.
 ///
 /// # Returns
 ///
@@ -50,30 +87,134 @@ pub async fn get_range(
     key: &str,
     skip: Option<usize>,
     limit: Option<usize>,
+    order: SortOrder,
 ) -> RedisResult<Option<Vec<String>>> {
     let mut redis_conn = get_redis_conn().await?;
 
     let index_key = format!("{prefix}:{key}");
     let start = skip.unwrap_or(0);
 
-    // Calculate end index
-    let end: isize = match limit {
+    // Clamp the indexes to isize::MAX: Redis lists cannot realistically reach this size,
+    // and an out-of-range index simply yields an empty result.
+    let first = isize::try_from(start).unwrap_or(isize::MAX);
+    let last = match limit {
         Some(0) => return Ok(None),
-        Some(lim) => {
-            let end_usize = start.saturating_add(lim - 1);
-            // Clamp to isize::MAX: Redis lists cannot realistically reach this size,
-            // and an out-of-range index simply yields an empty result.
-            isize::try_from(end_usize).unwrap_or(isize::MAX)
-        }
-        // Redis LRANGE uses -1 to mean "to the end of the list"
-        None => -1,
+        Some(lim) => Some(isize::try_from(start.saturating_add(lim - 1)).unwrap_or(isize::MAX)),
+        None => None,
     };
 
-    // Clamp start to isize::MAX for the same reason as end above.
-    let start_isize = isize::try_from(start).unwrap_or(isize::MAX);
-    let result: Vec<String> = redis_conn.lrange(index_key, start_isize, end).await?;
+    let result: Vec<String> = match order {
+        // Redis LRANGE uses -1 to mean "to the end of the list"
+        SortOrder::Ascending => {
+            redis_conn
+                .lrange(index_key, first, last.unwrap_or(-1))
+                .await?
+        }
+        // Mirror the range against the tail, where the i-th element is at index -(i + 1),
+        // then reverse it so that the last inserted element comes first
+        SortOrder::Descending => {
+            let tail_first = last.map_or(0, |last| -last - 1);
+            let mut result: Vec<String> =
+                redis_conn.lrange(index_key, tail_first, -first - 1).await?;
+            result.reverse();
+            result
+        }
+    };
     match result.len() {
         0 => Ok(None),
         _ => Ok(Some(result)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::DynError, StackConfig, StackManager};
+
+    const PREFIX: &str = "Test:Lists";
+
+    async fn put_letters(key: &str) -> Result<(), DynError> {
+        let mut redis_conn = get_redis_conn().await?;
+        let _: () = redis_conn.del(format!("{PREFIX}:{key}")).await?;
+        put(PREFIX, key, &["a", "b", "c", "d", "e", "f"], None).await?;
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_get_range_ascending() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+        put_letters("ascending").await?;
+
+        let result = get_range(PREFIX, "ascending", Some(1), Some(3), SortOrder::Ascending).await?;
+        assert_eq!(result, Some(vec!["b".into(), "c".into(), "d".into()]));
+
+        let result = get_range(PREFIX, "ascending", Some(4), None, SortOrder::Ascending).await?;
+        assert_eq!(result, Some(vec!["e".into(), "f".into()]));
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_get_range_descending() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+        put_letters("descending").await?;
+
+        let result = get_range(
+            PREFIX,
+            "descending",
+            Some(1),
+            Some(3),
+            SortOrder::Descending,
+        )
+        .await?;
+        assert_eq!(result, Some(vec!["e".into(), "d".into(), "c".into()]));
+
+        let result = get_range(PREFIX, "descending", Some(4), None, SortOrder::Descending).await?;
+        assert_eq!(result, Some(vec!["b".into(), "a".into()]));
+
+        // The limit is clamped to the head of the list
+        let result = get_range(
+            PREFIX,
+            "descending",
+            Some(5),
+            Some(3),
+            SortOrder::Descending,
+        )
+        .await?;
+        assert_eq!(result, Some(vec!["a".into()]));
+
+        let result = get_range(PREFIX, "descending", Some(6), None, SortOrder::Descending).await?;
+        assert_eq!(result, None);
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_put_with_max_len() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+        let mut redis_conn = get_redis_conn().await?;
+        let _: () = redis_conn.del(format!("{PREFIX}:max_len")).await?;
+
+        let values = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        for value in values {
+            put(PREFIX, "max_len", &[value], Some(5)).await?;
+        }
+
+        // Only the last five values survive, in insertion order
+        let result = get_range(PREFIX, "max_len", None, None, SortOrder::Ascending).await?;
+        assert_eq!(
+            result,
+            Some(vec![
+                "5".into(),
+                "6".into(),
+                "7".into(),
+                "8".into(),
+                "9".into()
+            ])
+        );
+
+        // A single push above the max length is trimmed as well
+        put(PREFIX, "max_len", &values, Some(3)).await?;
+        let result = get_range(PREFIX, "max_len", None, None, SortOrder::Ascending).await?;
+        assert_eq!(result, Some(vec!["7".into(), "8".into(), "9".into()]));
+        Ok(())
+    }
+}
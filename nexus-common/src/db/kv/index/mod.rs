@@ -1,4 +1,5 @@
 /// Module for redis Indexing operations split into modules by Redis types
+pub mod hashes;
 pub mod json;
 pub mod lists;
 pub mod sets;
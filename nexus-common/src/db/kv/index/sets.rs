@@ -1,4 +1,5 @@
 use crate::db::get_redis_conn;
+use crate::db::kv::key::build_key;
 use crate::db::kv::RedisResult;
 use deadpool_redis::redis::AsyncCommands;
 
@@ -148,6 +149,94 @@ pub async fn check_member(prefix: &str, key: &str, member: &str) -> RedisResult<
     }
 }
 
+/// Checks which of the given members exist in a Redis set.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the set is stored.
+/// * `members` - A slice of string slices representing the members to check.
+///
+/// # Returns
+///
+/// Returns `Ok(Some(flags))` with one flag per member, in the same order, if the set exists,
+/// or `Ok(None)` if the set does not exist.
+///
+/// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+pub async fn check_members(
+    prefix: &str,
+    key: &str,
+    members: &[&str],
+) -> RedisResult<Option<Vec<bool>>> {
+    let mut redis_conn = get_redis_conn().await?;
+    let index_key = format!("{prefix}:{key}");
+
+    if !redis_conn.exists(&index_key).await? {
+        return Ok(None);
+    }
+    if members.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+
+    let flags: Vec<bool> = redis_conn.smismember(&index_key, members).await?;
+    Ok(Some(flags))
+}
+
+/// Checks whether a member exists in each of several Redis sets, in a single call using a pipeline.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `keys` - A slice of string slices representing the keys under which the sets are stored.
+/// * `member` - A string slice representing the member to check for existence in each set.
+///
+/// # Returns
+///
+/// Returns one flag per key, in the same order, `false` for the sets that do not exist.
+///
+/// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+pub async fn check_member_in_sets(
+    prefix: &str,
+    keys: &[&str],
+    member: &str,
+) -> RedisResult<Vec<bool>> {
+    if keys.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut redis_conn = get_redis_conn().await?;
+
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.sismember(format!("{prefix}:{key}"), member);
+    }
+
+    let flags: Vec<bool> = pipe.query_async(&mut redis_conn).await?;
+    Ok(flags)
+}
+
+/// Retrieves the members common to several Redis sets, with a single `SINTER`.
+///
+/// # Arguments
+///
+/// * `keys` - A slice of the full Redis keys of the sets, prefix included, as they may belong to
+///   different types.
+///
+/// # Returns
+///
+/// Returns the members found in all the sets, in no particular order. A set that does not exist
+/// is empty, so the intersection is empty too.
+///
+/// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+pub async fn intersect(keys: &[String]) -> RedisResult<Vec<String>> {
+    if keys.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut redis_conn = get_redis_conn().await?;
+    let members: Vec<String> = redis_conn.sinter(keys).await?;
+    Ok(members)
+}
+
 /// Retrieves the size of a Redis set.
 ///
 /// This function returns the number of elements in the set identified by the combined `prefix` and `key`.
@@ -280,7 +369,7 @@ pub async fn put_multiple_sets(
     let mut has_commands = false;
 
     for (i, key) in index.iter().enumerate() {
-        let full_index = format!("{}:{}:{}", &prefix, common_key.join(":"), key);
+        let full_index = format!("{prefix}:{}", build_key(&[common_key, &[*key]].concat()));
         if !collections[i].is_empty() {
             pipe.sadd(&full_index, collections[i]); // Add expiration to the pipeline if specified
             if let Some(ttl) = expiration {
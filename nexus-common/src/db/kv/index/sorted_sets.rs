@@ -205,6 +205,204 @@ pub async fn get_lex_range(
     }
 }
 
+/// Retrieves a page of the members of a Redis sorted set whose members all have the same score,
+/// in lexicographic order.
+///
+/// The page starts right after the `after` member instead of at an offset, so members added or
+/// removed between two pages do not shift the following pages.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the sorted set is stored.
+/// * `after` - The last member of the previous page (exclusive), `None` to start from the first member.
+/// * `limit` - The number of elements to retrieve.
+///
+/// # Returns
+///
+/// Returns `None` if the sorted set does not exist, otherwise the members of the page, which is
+/// empty past the last member.
+pub async fn get_lex_range_after(
+    prefix: &str,
+    key: &str,
+    after: Option<&str>,
+    limit: usize,
+) -> RedisResult<Option<Vec<String>>> {
+    let mut redis_conn = get_redis_conn().await?;
+    let index_key = format!("{prefix}:{key}");
+
+    if !redis_conn.exists(&index_key).await? {
+        return Ok(None);
+    }
+
+    let min = match after {
+        Some(after) => format!("({after}"),
+        None => "-".to_string(),
+    };
+    let elements: Vec<String> = redis_conn
+        .zrangebylex_limit(&index_key, min, "+", 0, limit as isize)
+        .await?;
+    Ok(Some(elements))
+}
+
+/// Counts the elements of a Redis sorted set within a lexicographical range, without retrieving them.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the sorted set is stored.
+/// * `min` - The minimum lexicographical bound (inclusive).
+/// * `max` - The maximum lexicographical bound (exclusive).
+pub async fn count_lex_range(prefix: &str, key: &str, min: &str, max: &str) -> RedisResult<usize> {
+    let mut redis_conn = get_redis_conn().await?;
+    let index_key = format!("{prefix}:{key}");
+
+    let count: usize = redis_conn.zlexcount(index_key, min, max).await?;
+    Ok(count)
+}
+
+/// Incrementally iterates over the members of a Redis sorted set with `ZSCAN`.
+///
+/// Members present during the whole iteration are returned at least once, even if the set is
+/// modified in between, so a scan can be resumed later from the returned cursor.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the sorted set is stored.
+/// * `cursor` - The cursor returned by the previous call, `0` to start a new scan.
+/// * `count` - A hint of the number of members to return. Small sets are returned at once.
+///
+/// # Returns
+///
+/// The cursor for the next call, `0` once the scan is complete, and the members of this step.
+pub async fn scan(
+    prefix: &str,
+    key: &str,
+    cursor: u64,
+    count: usize,
+) -> RedisResult<(u64, Vec<String>)> {
+    let mut redis_conn = get_redis_conn().await?;
+    let index_key = format!("{prefix}:{key}");
+
+    // ZSCAN replies with the next cursor and a flat list of members and scores
+    let (next_cursor, members_with_scores): (u64, Vec<String>) = redis::cmd("ZSCAN")
+        .arg(index_key)
+        .arg(cursor)
+        .arg("COUNT")
+        .arg(count)
+        .query_async(&mut redis_conn)
+        .await?;
+    let members = members_with_scores.into_iter().step_by(2).collect();
+
+    Ok((next_cursor, members))
+}
+
+/// Counts the elements of a Redis sorted set within a score range, without retrieving them.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the sorted set is stored.
+/// * `min_score` - The minimum score for the range (inclusive).
+/// * `max_score` - The maximum score for the range (inclusive).
+pub async fn count_range(
+    prefix: &str,
+    key: &str,
+    min_score: Option<f64>,
+    max_score: Option<f64>,
+) -> RedisResult<usize> {
+    let mut redis_conn = get_redis_conn().await?;
+    let index_key = format!("{prefix}:{key}");
+
+    let min_score = min_score.unwrap_or(f64::MIN);
+    let max_score = max_score.unwrap_or(f64::MAX);
+    let count: usize = redis_conn.zcount(index_key, min_score, max_score).await?;
+    Ok(count)
+}
+
+/// Bounds a Redis sorted set, removing the elements scored below `min_score` and then the lowest
+/// scored elements beyond the `max_len` highest ones.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the sorted set is stored.
+/// * `max_len` - The number of elements to keep at most. If `None`, the length is not bounded.
+/// * `min_score` - The lowest score to keep (inclusive). If `None`, no element is removed by score.
+pub async fn trim(
+    prefix: &str,
+    key: &str,
+    max_len: Option<usize>,
+    min_score: Option<f64>,
+) -> RedisResult<()> {
+    let index_key = format!("{prefix}:{key}");
+    let mut redis_conn = get_redis_conn().await?;
+
+    let mut pipe = redis::pipe();
+    if let Some(min_score) = min_score {
+        pipe.cmd("ZREMRANGEBYSCORE")
+            .arg(&index_key)
+            .arg("-inf")
+            .arg(format!("({min_score}"))
+            .ignore();
+    }
+    if let Some(max_len) = max_len {
+        // Ranks are ascending by score, so this keeps the `max_len` highest scores
+        pipe.cmd("ZREMRANGEBYRANK")
+            .arg(&index_key)
+            .arg(0)
+            .arg(-(max_len as isize) - 1)
+            .ignore();
+    }
+    let _: () = pipe.query_async(&mut redis_conn).await?;
+    Ok(())
+}
+
+/// Counts the elements of a Redis sorted set that are not members of another sorted set, reading
+/// both cardinalities atomically.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key of the sorted set whose elements are counted.
+/// * `other_key` - A string slice representing the key of the sorted set whose members are left out.
+pub async fn count_difference(prefix: &str, key: &str, other_key: &str) -> RedisResult<usize> {
+    let index_key = format!("{prefix}:{key}");
+    let other_index_key = format!("{prefix}:{other_key}");
+    let mut redis_conn = get_redis_conn().await?;
+
+    let (total, common): (usize, usize) = redis::pipe()
+        .atomic()
+        .zcard(&index_key)
+        .cmd("ZINTERCARD")
+        .arg(2)
+        .arg(&index_key)
+        .arg(&other_index_key)
+        .query_async(&mut redis_conn)
+        .await?;
+    Ok(total.saturating_sub(common))
+}
+
+/// Stores the union of Redis sorted sets in a destination sorted set, replacing its elements.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `destination` - A string slice representing the key of the sorted set to replace.
+/// * `keys` - A slice of string slices representing the keys of the sorted sets to unite.
+///
+/// # Returns
+///
+/// Returns the number of elements of the destination sorted set.
+pub async fn union_store(prefix: &str, destination: &str, keys: &[&str]) -> RedisResult<usize> {
+    let destination = format!("{prefix}:{destination}");
+    let keys: Vec<String> = keys.iter().map(|key| format!("{prefix}:{key}")).collect();
+    let mut redis_conn = get_redis_conn().await?;
+    let count: usize = redis_conn.zunionstore(destination, &keys).await?;
+    Ok(count)
+}
+
 /// Removes elements from the Redis sorted set.
 ///
 /// # Arguments
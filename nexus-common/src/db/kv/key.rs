@@ -0,0 +1,80 @@
+use std::borrow::Cow;
+
+/// Separator between the parts of a Redis key
+pub const KEY_DELIMITER: char = ':';
+
+/// Escape character used by [escape_part]
+const ESCAPE_CHAR: char = '%';
+
+/// Escapes the [KEY_DELIMITER] inside a single key part, so that a part can never be read back as
+/// several parts. The escape character itself is escaped too, which keeps the encoding injective.
+///
+/// Parts without any of these characters (e.g. user and post IDs) are returned unchanged.
+pub fn escape_part(part: &str) -> Cow<'_, str> {
+    if !part.contains([KEY_DELIMITER, ESCAPE_CHAR]) {
+        return Cow::Borrowed(part);
+    }
+    let mut escaped = String::with_capacity(part.len() + 4);
+    for c in part.chars() {
+        match c {
+            ESCAPE_CHAR => escaped.push_str("%25"),
+            KEY_DELIMITER => escaped.push_str("%3A"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Builds a Redis key from its parts, escaping each of them with [escape_part].
+///
+/// All keys of the [RedisOps](super::RedisOps) indexes are built here, so that a part containing
+/// the delimiter (e.g. a tag label like `a:b`) cannot collide with the key of another index.
+pub fn build_key(key_parts: &[&str]) -> String {
+    let mut key = String::new();
+    for (i, part) in key_parts.iter().enumerate() {
+        if i > 0 {
+            key.push(KEY_DELIMITER);
+        }
+        key.push_str(&escape_part(part));
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_key_plain_parts() {
+        assert_eq!(
+            build_key(&["Posts", "Tag", "user", "post"]),
+            "Posts:Tag:user:post"
+        );
+        assert_eq!(build_key(&["user"]), "user");
+        assert_eq!(build_key(&[]), "");
+    }
+
+    #[test]
+    fn test_build_key_escapes_delimiter() {
+        assert_eq!(build_key(&["user", "a:b"]), "user:a%3Ab");
+        assert_eq!(build_key(&["user", "100%"]), "user:100%25");
+        // Already escaped looking input is escaped again
+        assert_eq!(build_key(&["user", "a%3Ab"]), "user:a%253Ab");
+    }
+
+    #[test]
+    fn test_build_key_no_collisions() {
+        // Each of these would be `user:a:b` if parts were joined as-is
+        let keys = [
+            build_key(&["user", "a", "b"]),
+            build_key(&["user", "a:b"]),
+            build_key(&["user:a", "b"]),
+            build_key(&["user", "a%3Ab"]),
+        ];
+        for (i, key) in keys.iter().enumerate() {
+            for other in keys.iter().skip(i + 1) {
+                assert_ne!(key, other);
+            }
+        }
+    }
+}
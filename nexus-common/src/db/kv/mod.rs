@@ -1,11 +1,16 @@
 mod error;
 mod flush;
 mod index;
+pub mod key;
 mod last_save;
+pub mod nonce;
+pub mod rate_limit;
+pub mod single_flight;
 mod traits;
 
 pub use error::{RedisError, RedisResult};
 pub use flush::clear_redis;
+pub use index::hashes;
 pub use index::json::JsonAction;
 pub use index::sets;
 pub use index::sorted_sets::{ScoreAction, SortOrder};
@@ -0,0 +1,48 @@
+use crate::db::get_redis_conn;
+use crate::db::kv::RedisResult;
+
+/// Prefix of the recorded nonces in Redis
+const NONCE_PREFIX: &str = "Nonce";
+
+/// Records the nonce `key` for `ttl_ms` milliseconds, so that it can only be used once meanwhile.
+///
+/// Returns `false` if the nonce was already recorded and has not expired yet.
+pub async fn record(key: &str, ttl_ms: u64) -> RedisResult<bool> {
+    let mut redis_conn = get_redis_conn().await?;
+    // SET NX answers nothing when the key exists, so recording and checking are a single step
+    let recorded: Option<String> = redis::cmd("SET")
+        .arg(format!("{NONCE_PREFIX}:{key}"))
+        .arg(1)
+        .arg("NX")
+        .arg("PX")
+        .arg(ttl_ms.max(1))
+        .query_async(&mut redis_conn)
+        .await?;
+    Ok(recorded.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_record_accepts_a_nonce_once_until_expired() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let key = format!(
+            "test:record:{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+        );
+
+        assert!(record(&key, 500).await?);
+        assert!(!record(&key, 500).await?);
+
+        // Once expired, the nonce is accepted again
+        tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+        assert!(record(&key, 500).await?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::db::get_redis_conn;
+use crate::db::kv::RedisResult;
+use redis::Script;
+use std::sync::OnceLock;
+
+/// Prefix of the token buckets in Redis
+const RATE_LIMIT_PREFIX: &str = "RateLimit";
+
+/// Takes one token of a bucket holding up to `capacity` tokens, refilled at a steady rate so that
+/// an empty bucket is full again after `window_ms`. Redis' own clock is used, so that every API
+/// instance sharing the Redis sees the same buckets. Returns whether a token was taken and, if
+/// not, the milliseconds until the next one is available.
+const TAKE_TOKEN_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local window_ms = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1]) or capacity
+local ts = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * capacity / window_ms)
+
+local allowed = 0
+local retry_ms = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_ms = math.ceil((1 - tokens) * window_ms / capacity)
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', tostring(now))
+redis.call('PEXPIRE', KEYS[1], window_ms)
+return {allowed, retry_ms}
+"#;
+
+fn take_token_script() -> &'static Script {
+    static SCRIPT: OnceLock<Script> = OnceLock::new();
+    SCRIPT.get_or_init(|| Script::new(TAKE_TOKEN_SCRIPT))
+}
+
+/// Takes a token from the bucket of `key`, which allows `capacity` requests per `window_secs`.
+///
+/// Returns `None` if the request is allowed, or the number of seconds to wait before retrying.
+pub async fn take_token(key: &str, capacity: u32, window_secs: u64) -> RedisResult<Option<u64>> {
+    let mut redis_conn = get_redis_conn().await?;
+    let (allowed, retry_ms): (u8, u64) = take_token_script()
+        .key(format!("{RATE_LIMIT_PREFIX}:{key}"))
+        .arg(capacity.max(1))
+        .arg(window_secs.max(1) * 1000)
+        .invoke_async(&mut redis_conn)
+        .await?;
+
+    match allowed {
+        1 => Ok(None),
+        // Round up, so that a client retrying on time finds a token
+        _ => Ok(Some(retry_ms.div_ceil(1000).max(1))),
+    }
+}
+
+/// Removes the bucket of `key`, e.g. to start from a full bucket
+pub async fn reset(key: &str) -> RedisResult<()> {
+    let mut redis_conn = get_redis_conn().await?;
+    let _: () = redis::cmd("DEL")
+        .arg(format!("{RATE_LIMIT_PREFIX}:{key}"))
+        .query_async(&mut redis_conn)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_take_token_refills_after_window() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let key = "test:take_token_refills_after_window";
+        reset(key).await?;
+
+        // The bucket starts full, so the first requests of the window are allowed
+        for _ in 0..3 {
+            assert_eq!(take_token(key, 3, 1).await?, None);
+        }
+        // Until it is empty
+        assert_eq!(take_token(key, 3, 1).await?, Some(1));
+
+        // After the window, the bucket is full again
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        for _ in 0..3 {
+            assert_eq!(take_token(key, 3, 1).await?, None);
+        }
+
+        reset(key).await?;
+        Ok(())
+    }
+}
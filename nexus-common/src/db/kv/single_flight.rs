@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// Per-key locks of the recomputations currently in flight
+static IN_FLIGHT: OnceLock<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+
+fn in_flight() -> &'static Mutex<HashMap<String, Arc<AsyncMutex<()>>>> {
+    IN_FLIGHT.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Held by the only caller allowed to recompute a missing cache entry for a given key.
+///
+/// Other callers of [acquire] with the same key wait until this guard is dropped, by which time
+/// the cache is expected to be warm again, so they read it instead of hitting the graph.
+pub struct SingleFlightGuard {
+    key: String,
+    _lock: OwnedMutexGuard<()>,
+}
+
+impl Drop for SingleFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = in_flight().lock().unwrap_or_else(|e| e.into_inner());
+        // The map and this guard each hold one reference. Anything above that is a waiter,
+        // which still needs the entry to find the lock
+        if let Some(lock) = in_flight.get(&self.key) {
+            if Arc::strong_count(lock) <= 2 {
+                in_flight.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Waits until no other caller is recomputing `key` and returns a guard for it.
+///
+/// Only called on the cache misses of the views when
+/// [StackConfig::single_flight_on_read_miss](crate::StackConfig::single_flight_on_read_miss) is set.
+pub async fn acquire(key: impl Into<String>) -> SingleFlightGuard {
+    let key = key.into();
+    let lock = {
+        let mut in_flight = in_flight().lock().unwrap_or_else(|e| e.into_inner());
+        in_flight
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+            .clone()
+    };
+
+    SingleFlightGuard {
+        key,
+        _lock: lock.lock_owned().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_acquire_serializes_same_key() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let max_active = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let active = active.clone();
+                let max_active = max_active.clone();
+                tokio::spawn(async move {
+                    let _guard = acquire("test:single_flight:same_key").await;
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_active.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(max_active.load(Ordering::SeqCst), 1);
+        // The entry is cleaned up once the last guard is dropped
+        assert!(!in_flight()
+            .lock()
+            .unwrap()
+            .contains_key("test:single_flight:same_key"));
+    }
+}
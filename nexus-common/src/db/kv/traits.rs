@@ -1,4 +1,5 @@
 use super::index::*;
+use super::key::build_key;
 use crate::db::kv::{RedisError, RedisResult};
 use async_trait::async_trait;
 use json::JsonAction;
@@ -60,7 +61,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         expiration: Option<i64>,
     ) -> RedisResult<()> {
         let prefix = prefix.unwrap_or(Self::prefix().await);
-        json::put(&prefix, &key_parts.join(":"), self, None, expiration).await
+        json::put(&prefix, &build_key(key_parts), self, None, expiration).await
     }
 
     /// Retrieves data from Redis using the provided key parts.
@@ -85,7 +86,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         prefix: Option<String>,
     ) -> RedisResult<Option<Self>> {
         let prefix = prefix.unwrap_or(Self::prefix().await);
-        json::get(&prefix, &key_parts.join(":"), None).await
+        json::get(&prefix, &build_key(key_parts), None).await
     }
 
     /// Retrieves multiple JSON objects from Redis using the provided key parts.
@@ -107,7 +108,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         let prefix = Self::prefix().await;
         let keys: Vec<String> = key_parts_list
             .iter()
-            .map(|key_parts| key_parts.join(":"))
+            .map(|key_parts| build_key(key_parts))
             .collect();
 
         json::get_multiple(&prefix, &keys, None).await
@@ -157,7 +158,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
     {
         let mut data = Vec::with_capacity(key_parts_list.len());
         for (i, key_parts) in key_parts_list.iter().enumerate() {
-            let key = key_parts.join(":");
+            let key = build_key(key_parts);
             data.push((key, &collection[i]));
         }
 
@@ -185,7 +186,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         let prefix = Self::prefix().await;
         let keys: Vec<String> = key_parts_list
             .iter()
-            .map(|key_parts| key_parts.join(":"))
+            .map(|key_parts| build_key(key_parts))
             .collect();
 
         json::del_multiple(&prefix, &keys).await
@@ -215,10 +216,46 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         action: JsonAction,
     ) -> RedisResult<()> {
         let prefix = Self::prefix().await;
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         json::modify_json_field(&prefix, &key, field, action, None).await
     }
 
+    /// Modifies a numeric field in a Redis JSON object and the score of a sorted set member by
+    /// the same amount, in a single transaction retried on conflict.
+    ///
+    /// See [json::modify_json_field_with_score] for how the count and the score are kept in sync.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices representing the parts used to form the key under which the JSON object is stored.
+    /// * `field` - A string slice representing the field to be modified in the JSON object.
+    /// * `action` - A `JsonAction` enum that specifies whether to increment or decrement the field.
+    /// * `score` - The key parts of the sorted set and of its member to update with the field, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON object does not exist, if the operation fails, or if the
+    /// transaction keeps conflicting with concurrent updates.
+    async fn modify_json_field_with_score(
+        key_parts: &[&str],
+        field: &str,
+        action: JsonAction,
+        score: Option<(&[&str], &[&str])>,
+    ) -> RedisResult<()> {
+        let prefix = Self::prefix().await;
+        let key = build_key(key_parts);
+        let score_keys =
+            score.map(|(set_key_parts, member)| (build_key(set_key_parts), member.join(":")));
+        let score_target = score_keys
+            .as_ref()
+            .map(|(set_key, member)| json::ScoreTarget {
+                prefix: SORTED_PREFIX,
+                key: set_key,
+                member,
+            });
+        json::modify_json_field_with_score(&prefix, &key, field, action, None, score_target).await
+    }
+
     // ############################################################
     // ################# List related functions ###################
     // ############################################################
@@ -231,18 +268,19 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
     /// # Arguments
     ///
     /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the list is stored.
+    /// * `max_len` - If `Some`, the list is trimmed to its `max_len` most recent elements after the push.
     ///
     /// # Errors
     ///
     /// Returns an error if the operation fails, such as if the Redis connection is unavailable or
     /// if there is an issue with serialization.
-    async fn put_index_list<T>(&self, key_parts: &[&str]) -> RedisResult<()>
+    async fn put_index_list<T>(&self, key_parts: &[&str], max_len: Option<usize>) -> RedisResult<()>
     where
         Self: AsRef<[T]>,            // Self can be dereferenced into a slice of T
         T: AsRef<str> + Send + Sync, // The items must be convertible to &str
     {
         let prefix = Self::prefix().await;
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
 
         // TODO: Unsafe. If re-indexed it will duplicate follower/following list entries.
         // Need reading, matching out the duplicates then storing. Inneficient.
@@ -253,7 +291,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         let values: Vec<&str> = collection.iter().map(|item| item.as_ref()).collect();
 
         // Store the values in the Redis list
-        lists::put(&prefix, &key, &values).await
+        lists::put(&prefix, &key, &values, max_len).await
     }
 
     /// Retrieves a range of elements from a Redis list using the provided key parts.
@@ -266,6 +304,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
     /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the list is stored.
     /// * `skip` - An optional number of elements to skip (useful for pagination).
     /// * `limit` - An optional number of elements to return (useful for pagination).
+    /// * `order` - `Ascending` reads the list in insertion order, `Descending` from its tail.
     ///
     /// # Returns
     ///
@@ -278,10 +317,11 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         key_parts: &[&str],
         skip: Option<usize>,
         limit: Option<usize>,
+        order: SortOrder,
     ) -> RedisResult<Option<Vec<String>>> {
         let prefix = Self::prefix().await;
-        let key = key_parts.join(":");
-        lists::get_range(&prefix, &key, skip, limit).await
+        let key = build_key(key_parts);
+        lists::get_range(&prefix, &key, skip, limit, order).await
     }
 
     // ############################################################
@@ -310,7 +350,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         prefix: Option<String>,
     ) -> RedisResult<()> {
         let prefix = prefix.unwrap_or(Self::prefix().await);
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         // Store the values in the Redis set
         sets::put(&prefix, &key, values, expiration).await
     }
@@ -333,7 +373,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         T: AsRef<str> + Send + Sync, // The items must be convertible to &str
     {
         let prefix = Self::prefix().await;
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
 
         // Directly use the string representations of items without additional serialization
         let collection = self.as_ref();
@@ -371,7 +411,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
             Some(p) => format!("{}:{}", p, Self::prefix().await),
             None => Self::prefix().await,
         };
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         sets::get_range(&combined_prefix, &key, skip, limit).await
     }
 
@@ -396,10 +436,55 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
     /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
     async fn check_set_member(key_parts: &[&str], member: &str) -> RedisResult<(bool, bool)> {
         let prefix = Self::prefix().await;
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         sets::check_member(&prefix, &key, member).await
     }
 
+    /// Checks which of the given members exist in a Redis set using the provided key parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the set is stored.
+    /// * `members` - A slice of string slices representing the members to check for existence in the set.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(flags))` with one flag per member, in the same order, if the set exists,
+    /// or `Ok(None)` if the set does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn check_set_members(
+        key_parts: &[&str],
+        members: &[&str],
+    ) -> RedisResult<Option<Vec<bool>>> {
+        let prefix = Self::prefix().await;
+        let key = build_key(key_parts);
+        sets::check_members(&prefix, &key, members).await
+    }
+
+    /// Checks whether a member exists in each of several Redis sets, in a single pipelined call.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A slice of keys, each one being the single key part of a set.
+    /// * `member` - A string slice representing the member to check for existence in each set.
+    ///
+    /// # Returns
+    ///
+    /// Returns one flag per key, in the same order, `false` for the sets that do not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn check_member_in_sets(keys: &[&str], member: &str) -> RedisResult<Vec<bool>> {
+        let prefix = Self::prefix().await;
+        let keys: Vec<String> = keys.iter().map(|key| build_key(&[key])).collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        sets::check_member_in_sets(&prefix, &keys, member).await
+    }
+
     /// Retrieves the size of a Redis set using the provided key parts.
     ///
     /// This method retrieves the number of elements in a Redis set stored under the key generated from the provided `key_parts`.
@@ -418,7 +503,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
     /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
     async fn get_set_size(key_parts: &[&str]) -> RedisResult<Option<usize>> {
         let prefix = Self::prefix().await;
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         sets::get_size(&prefix, &key).await
     }
 
@@ -518,7 +603,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         prefix: Option<String>,
     ) -> RedisResult<Option<Vec<String>>> {
         let prefix = prefix.unwrap_or(Self::prefix().await);
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         sets::get_random_members(&prefix, &key, count).await
     }
 
@@ -541,7 +626,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         member: &[&str],
     ) -> RedisResult<Option<isize>> {
         let prefix = prefix.unwrap_or(SORTED_PREFIX);
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         let member_key = member.join(":");
         sorted_sets::check_member(prefix, &key, &member_key).await
     }
@@ -569,7 +654,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         expiration: Option<i64>,
     ) -> RedisResult<()> {
         let prefix = prefix.unwrap_or(SORTED_PREFIX);
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         // Store the elements in the Redis sorted set
         sorted_sets::put(prefix, &key, elements, expiration).await
     }
@@ -589,7 +674,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         member: &[&str],
         score_mutation: ScoreAction,
     ) -> RedisResult<()> {
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         let member_key = member.join(":");
         sorted_sets::put_score(SORTED_PREFIX, &key, &member_key, score_mutation).await
     }
@@ -635,11 +720,78 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
 
         let prefix = prefix.unwrap_or(SORTED_PREFIX);
         // Create the key by joining the key parts
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         // Call the sorted_sets::del function to remove the items from the sorted set
         sorted_sets::del(prefix, &key, items).await
     }
 
+    /// Bounds a Redis sorted set by length and by score, see [sorted_sets::trim].
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `max_len` - The number of highest scored elements to keep. If `None`, the length is not bounded.
+    /// * `min_score` - The lowest score to keep (inclusive). If `None`, no element is removed by score.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn trim_index_sorted_set(
+        key_parts: &[&str],
+        max_len: Option<usize>,
+        min_score: Option<f64>,
+    ) -> RedisResult<()> {
+        let key = build_key(key_parts);
+        sorted_sets::trim(SORTED_PREFIX, &key, max_len, min_score).await
+    }
+
+    /// Counts the elements of a Redis sorted set that are not members of another one, see
+    /// [sorted_sets::count_difference].
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key of the sorted set to count.
+    /// * `other_key_parts` - A slice of string slices that represent the parts used to form the key of the sorted set whose members are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn count_index_sorted_set_difference(
+        key_parts: &[&str],
+        other_key_parts: &[&str],
+    ) -> RedisResult<usize> {
+        let key = build_key(key_parts);
+        let other_key = build_key(other_key_parts);
+        sorted_sets::count_difference(SORTED_PREFIX, &key, &other_key).await
+    }
+
+    /// Replaces a Redis sorted set with the union of other sorted sets, see [sorted_sets::union_store].
+    ///
+    /// # Arguments
+    ///
+    /// * `destination_key_parts` - A slice of string slices that represent the parts used to form the key of the sorted set to replace.
+    /// * `key_parts_list` - A slice of key parts, each forming the key of a sorted set to unite.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of elements of the destination sorted set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn union_index_sorted_sets(
+        destination_key_parts: &[&str],
+        key_parts_list: &[&[&str]],
+    ) -> RedisResult<usize> {
+        let destination = build_key(destination_key_parts);
+        let keys: Vec<String> = key_parts_list
+            .iter()
+            .map(|key_parts| build_key(key_parts))
+            .collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        sorted_sets::union_store(SORTED_PREFIX, &destination, &keys).await
+    }
+
     /// Retrieves a range of elements from a Redis sorted set using the provided key parts.
     ///
     /// This method fetches elements from a Redis sorted set stored under the key generated from the provided `key_parts`.
@@ -670,7 +822,7 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         sorting: SortOrder,
         prefix: Option<&str>,
     ) -> RedisResult<Option<Vec<(String, f64)>>> {
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         let prefix = prefix.unwrap_or("Sorted");
 
         sorted_sets::get_range(prefix, &key, end, start, skip, limit, sorting).await
@@ -703,7 +855,94 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         skip: Option<usize>,
         limit: Option<usize>,
     ) -> RedisResult<Option<Vec<String>>> {
-        let key = key_parts.join(":");
+        let key = build_key(key_parts);
         sorted_sets::get_lex_range("Sorted", &key, min, max, skip, limit).await
     }
+
+    /// Retrieves a page of a Redis sorted set whose members all have the same score, in lexicographic order.
+    ///
+    /// The page starts right after the `after` member, so it is stable when the sorted set changes between pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `after` - The last member of the previous page, `None` to start from the first member.
+    /// * `limit` - The number of elements to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the sorted set does not exist, otherwise the members of the page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn try_from_index_sorted_set_after(
+        key_parts: &[&str],
+        after: Option<&str>,
+        limit: usize,
+    ) -> RedisResult<Option<Vec<String>>> {
+        let key = build_key(key_parts);
+        sorted_sets::get_lex_range_after(SORTED_PREFIX, &key, after, limit).await
+    }
+
+    /// Counts the elements of a Redis sorted set within a score range.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `min_score` - The minimum score (inclusive). If `None`, no lower bound is applied.
+    /// * `max_score` - The maximum score (inclusive). If `None`, no upper bound is applied.
+    ///
+    /// # Returns
+    ///
+    /// The number of matching elements, `0` if the sorted set does not exist.
+    async fn count_index_sorted_set(
+        key_parts: &[&str],
+        min_score: Option<f64>,
+        max_score: Option<f64>,
+    ) -> RedisResult<usize> {
+        let key = build_key(key_parts);
+        sorted_sets::count_range(SORTED_PREFIX, &key, min_score, max_score).await
+    }
+
+    /// Counts the elements of a Redis sorted set within a lexicographical range, using the same
+    /// bounds as [Self::try_from_index_sorted_set_lex].
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `min` - The minimum lexicographical bound (inclusive).
+    /// * `max` - The maximum lexicographical bound (exclusive).
+    ///
+    /// # Returns
+    ///
+    /// The number of matching elements, `0` if the sorted set does not exist.
+    async fn count_index_sorted_set_lex(
+        key_parts: &[&str],
+        min: &str,
+        max: &str,
+    ) -> RedisResult<usize> {
+        let key = build_key(key_parts);
+        sorted_sets::count_lex_range("Sorted", &key, min, max).await
+    }
+
+    /// Incrementally iterates over the members of a Redis sorted set, see [sorted_sets::scan].
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `cursor` - The cursor returned by the previous call, `0` to start a new scan.
+    /// * `count` - A hint of the number of members to return.
+    ///
+    /// # Returns
+    ///
+    /// The cursor for the next call, `0` once the scan is complete, and the members of this step.
+    async fn scan_index_sorted_set(
+        key_parts: &[&str],
+        cursor: u64,
+        count: usize,
+    ) -> RedisResult<(u64, Vec<String>)> {
+        let key = build_key(key_parts);
+        sorted_sets::scan(SORTED_PREFIX, &key, cursor, count).await
+    }
 }
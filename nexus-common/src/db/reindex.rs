@@ -1,6 +1,6 @@
 use crate::db::graph::exec::fetch_all_rows_from_graph;
 use crate::db::graph::Query;
-use crate::models::follow::{Followers, Following, UserFollows};
+use crate::models::follow::{FollowStats, Followers, Following, UserFollows};
 use crate::models::post::search::PostsByTagSearch;
 use crate::models::post::Bookmark;
 use crate::models::tag::post::TagPost;
@@ -18,73 +18,97 @@ use crate::{
 use tokio::task::JoinSet;
 use tracing::{info, Instrument};
 
+/// Default number of users or posts reindexed per batch by [sync]
+pub const DEFAULT_REINDEX_BATCH_SIZE: usize = 1000;
+
 #[tracing::instrument(name = "reindex.sync", skip_all)]
 pub async fn sync() {
-    let mut user_tasks = JoinSet::new();
-    let mut post_tasks = JoinSet::new();
-
-    let user_ids: Vec<String> = get_all_user_ids().await.expect("Failed to get user IDs");
-    let user_ids_refs: Vec<&str> = user_ids.iter().map(|id| id.as_str()).collect();
-
-    UserDetails::reindex(&user_ids_refs)
+    sync_in_batches(DEFAULT_REINDEX_BATCH_SIZE)
         .await
-        .expect("Failed indexing User Details");
-    //TODO use collections for every other model
-
-    for user_id in user_ids {
-        let span = tracing::info_span!("reindex.user", user_id = %user_id);
-        user_tasks.spawn(
-            async move {
-                if let Err(e) = reindex_user(&user_id).await {
-                    tracing::error!("Failed to reindex user {}: {:?}", user_id, e);
+        .expect("Failed to reindex");
+}
+
+/// Rebuilds the indexes from the graph, paging through users and posts in batches of
+/// `batch_size`. Each batch is fully reindexed before the next one is read, so memory stays
+/// bounded on large graphs.
+#[tracing::instrument(name = "reindex.sync_in_batches", skip_all, fields(batch_size = batch_size))]
+pub async fn sync_in_batches(batch_size: usize) -> Result<(), DynError> {
+    let batch_size = batch_size.max(1);
+
+    let mut last_user_id: Option<String> = None;
+    let mut users_done = 0;
+    loop {
+        let user_ids = get_user_ids_page(last_user_id.as_deref(), batch_size).await?;
+        let Some(last) = user_ids.last() else {
+            break;
+        };
+        last_user_id = Some(last.clone());
+
+        let user_ids_refs: Vec<&str> = user_ids.iter().map(|id| id.as_str()).collect();
+        UserDetails::reindex(&user_ids_refs).await?;
+        //TODO use collections for every other model
+
+        let mut user_tasks = JoinSet::new();
+        for user_id in user_ids.iter().cloned() {
+            let span = tracing::info_span!("reindex.user", user_id = %user_id);
+            user_tasks.spawn(
+                async move {
+                    if let Err(e) = reindex_user(&user_id).await {
+                        tracing::error!("Failed to reindex user {}: {:?}", user_id, e);
+                    }
                 }
+                .instrument(span),
+            );
+        }
+        while let Some(res) = user_tasks.join_next().await {
+            if let Err(e) = res {
+                tracing::error!("User reindexing task failed: {:?}", e);
             }
-            .instrument(span),
-        );
+        }
+
+        users_done += user_ids.len();
+        info!("Reindexed {users_done} users");
     }
 
-    let post_ids = get_all_post_ids().await.expect("Failed to get post IDs");
-    for (author_id, post_id) in post_ids {
-        let span = tracing::info_span!("reindex.post", author_id = %author_id, post_id = %post_id);
-        post_tasks.spawn(
-            async move {
-                if let Err(e) = reindex_post(&author_id, &post_id).await {
-                    tracing::error!("Failed to reindex post {}: {:?}", post_id, e);
+    let mut last_post: Option<(String, String)> = None;
+    let mut posts_done = 0;
+    loop {
+        let post_ids = get_post_ids_page(last_post.as_ref(), batch_size).await?;
+        let Some(last) = post_ids.last() else {
+            break;
+        };
+        last_post = Some(last.clone());
+
+        let mut post_tasks = JoinSet::new();
+        for (author_id, post_id) in post_ids.iter().cloned() {
+            let span =
+                tracing::info_span!("reindex.post", author_id = %author_id, post_id = %post_id);
+            post_tasks.spawn(
+                async move {
+                    if let Err(e) = reindex_post(&author_id, &post_id).await {
+                        tracing::error!("Failed to reindex post {}: {:?}", post_id, e);
+                    }
                 }
+                .instrument(span),
+            );
+        }
+        while let Some(res) = post_tasks.join_next().await {
+            if let Err(e) = res {
+                tracing::error!("Post reindexing task failed: {:?}", e);
             }
-            .instrument(span),
-        );
-    }
-
-    while let Some(res) = user_tasks.join_next().await {
-        if let Err(e) = res {
-            tracing::error!("User reindexing task failed: {:?}", e);
         }
-    }
 
-    while let Some(res) = post_tasks.join_next().await {
-        if let Err(e) = res {
-            tracing::error!("Post reindexing task failed: {:?}", e);
-        }
+        posts_done += post_ids.len();
+        info!("Reindexed {posts_done} posts");
     }
 
-    HotTags::reindex()
-        .await
-        .expect("Failed to store the global hot tags");
-
-    Influencers::reindex()
-        .await
-        .expect("Failed to reindex influencers");
-
-    PostsByTagSearch::reindex()
-        .await
-        .expect("Failed to store the global post tags");
-
-    TagSearch::reindex()
-        .await
-        .expect("Failed to store the global tags");
+    HotTags::reindex().await?;
+    Influencers::reindex().await?;
+    PostsByTagSearch::reindex().await?;
+    TagSearch::reindex().await?;
 
     info!("Reindexing completed successfully.");
+    Ok(())
 }
 
 pub async fn reindex_user(user_id: &str) -> Result<(), DynError> {
@@ -93,7 +117,9 @@ pub async fn reindex_user(user_id: &str) -> Result<(), DynError> {
         UserCounts::reindex(user_id),
         Followers::reindex(user_id),
         Following::reindex(user_id),
-        TagUser::reindex(user_id, None)
+        FollowStats::reindex(user_id),
+        TagUser::reindex(user_id, None),
+        TagUser::reindex_tagger_labels(user_id)
     )?;
     Ok(())
 }
@@ -122,11 +148,51 @@ pub async fn get_all_user_ids() -> Result<Vec<String>, DynError> {
     Ok(user_ids)
 }
 
-async fn get_all_post_ids() -> Result<Vec<(String, String)>, DynError> {
-    let query = Query::new(
-        "get_all_post_ids",
-        "MATCH (u:User)-[:AUTHORED]->(p:Post) RETURN u.id AS author_id, p.id AS post_id",
+/// Reads up to `limit` user IDs in ascending order, starting after `after`
+async fn get_user_ids_page(after: Option<&str>, limit: usize) -> Result<Vec<String>, DynError> {
+    let mut cypher = String::from("MATCH (u:User)");
+    if after.is_some() {
+        cypher.push_str(" WHERE u.id > $after");
+    }
+    cypher.push_str(" RETURN u.id AS id ORDER BY id LIMIT $limit");
+
+    let mut query = Query::new("get_user_ids_page", cypher).param("limit", limit as i64);
+    if let Some(after) = after {
+        query = query.param("after", after);
+    }
+    let rows = fetch_all_rows_from_graph(query).await?;
+
+    let mut user_ids = Vec::new();
+    for row in rows {
+        if let Some(id) = row.get("id")? {
+            user_ids.push(id);
+        }
+    }
+
+    Ok(user_ids)
+}
+
+/// Reads up to `limit` `(author_id, post_id)` pairs in ascending order, starting after `after`
+async fn get_post_ids_page(
+    after: Option<&(String, String)>,
+    limit: usize,
+) -> Result<Vec<(String, String)>, DynError> {
+    let mut cypher = String::from("MATCH (u:User)-[:AUTHORED]->(p:Post)");
+    if after.is_some() {
+        cypher.push_str(
+            " WHERE u.id > $after_author_id OR (u.id = $after_author_id AND p.id > $after_post_id)",
+        );
+    }
+    cypher.push_str(
+        " RETURN u.id AS author_id, p.id AS post_id ORDER BY author_id, post_id LIMIT $limit",
     );
+
+    let mut query = Query::new("get_post_ids_page", cypher).param("limit", limit as i64);
+    if let Some((after_author_id, after_post_id)) = after {
+        query = query
+            .param("after_author_id", after_author_id.as_str())
+            .param("after_post_id", after_post_id.as_str());
+    }
     let rows = fetch_all_rows_from_graph(query).await?;
 
     let mut post_ids = Vec::new();
@@ -1,7 +1,9 @@
 use crate::{
+    config::{MediaConfig, MediaLimits},
     media::processors::MediaProcessorError,
     models::file::{FileDetails, FileUrls},
     types::DynError,
+    StackManager,
 };
 use processors::{ImageProcessor, VariantProcessor, VideoProcessor};
 use serde::{Deserialize, Serialize};
@@ -15,12 +17,32 @@ use utoipa::ToSchema;
 
 pub mod processors;
 
+/// Returns the maximum size of an ingested file, see [MediaConfig::max_file_size_bytes]
+pub fn max_file_size_bytes() -> u64 {
+    StackManager::config().media.max_file_size_bytes
+}
+
+/// Returns `true` if EXIF, XMP and IPTC metadata is stripped from images, see
+/// [MediaConfig::strip_metadata]
+pub fn strip_metadata_enabled() -> bool {
+    StackManager::config().media.strip_metadata
+}
+
+/// Returns the dimension limits of processed images, see [MediaConfig::limits]
+pub fn media_limits() -> MediaLimits {
+    StackManager::config().media.limits
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, ToSchema, Clone)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum FileVariant {
     Main,
     Feed,
     Small,
+    /// Same size as [FileVariant::Feed], encoded as AVIF
+    FeedAvif,
+    /// Same size as [FileVariant::Small], encoded as AVIF
+    SmallAvif,
 }
 
 impl FromStr for FileVariant {
@@ -31,6 +53,8 @@ impl FromStr for FileVariant {
             "main" => Ok(FileVariant::Main),
             "feed" => Ok(FileVariant::Feed),
             "small" => Ok(FileVariant::Small),
+            "feed_avif" => Ok(FileVariant::FeedAvif),
+            "small_avif" => Ok(FileVariant::SmallAvif),
             _ => Err("Invalid file version".into()),
         }
     }
@@ -42,6 +66,8 @@ impl Display for FileVariant {
             FileVariant::Main => "main",
             FileVariant::Feed => "feed",
             FileVariant::Small => "small",
+            FileVariant::FeedAvif => "feed_avif",
+            FileVariant::SmallAvif => "small_avif",
         };
         write!(f, "{version_string}")
     }
@@ -55,6 +81,12 @@ impl VariantController {
         variant: &FileVariant,
         file_path: PathBuf,
     ) -> Result<String, MediaProcessorError> {
+        if !Self::is_content_type_allowed(&file.content_type) {
+            return Err(MediaProcessorError::UnsupportedContentType(
+                file.content_type.clone(),
+            ));
+        }
+
         match &file.content_type {
             content_type if content_type.starts_with("image/") => {
                 ImageProcessor::create_variant(file, variant, file_path).await
@@ -68,6 +100,54 @@ impl VariantController {
         }
     }
 
+    /// Rewrites an original image in place without its EXIF, XMP and IPTC metadata, once its
+    /// pixels are rotated to match the EXIF orientation.
+    ///
+    /// The original is served as the [FileVariant::Main] variant as is, so this has to run on
+    /// ingestion. Does nothing for non-image content types or when stripping is disabled.
+    pub async fn strip_metadata(
+        content_type: &str,
+        main_file: &Path,
+    ) -> Result<(), MediaProcessorError> {
+        if !strip_metadata_enabled()
+            || !content_type.starts_with("image/")
+            || !Self::is_content_type_allowed(content_type)
+        {
+            return Ok(());
+        }
+        let Some(main_file_path) = main_file.to_str() else {
+            return Err(MediaProcessorError::InvalidFilePath(
+                "Original file".to_string(),
+            ));
+        };
+
+        ImageProcessor::strip_metadata(main_file_path).await
+    }
+
+    /// Computes the blurhash placeholder of an image from its original file.
+    ///
+    /// Returns `None` for non-image content types, or if the blurhash could not be computed.
+    pub async fn compute_blurhash(content_type: &str, main_file: &Path) -> Option<String> {
+        if !content_type.starts_with("image/") || !Self::is_content_type_allowed(content_type) {
+            return None;
+        }
+        let main_file_path = main_file.to_str()?;
+
+        ImageProcessor::compute_blurhash(main_file_path)
+            .await
+            .inspect_err(|e| tracing::warn!("Blurhash failed for {main_file_path}: {e}"))
+            .ok()
+    }
+
+    /// Returns `true` if files of this content type may be processed into variants
+    pub fn is_content_type_allowed(content_type: &str) -> bool {
+        StackManager::config()
+            .media
+            .allowed_content_types
+            .iter()
+            .any(|allowed| allowed == content_type)
+    }
+
     pub async fn check_variant_exists(
         file: &FileDetails,
         variant: FileVariant,
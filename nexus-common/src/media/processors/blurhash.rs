@@ -0,0 +1,153 @@
+//! Minimal [BlurHash](https://blurha.sh) encoder, following the reference implementation
+
+use std::f32::consts::PI;
+
+const BASE83_CHARS: &[u8; 83] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// Encodes a raw RGB image (3 bytes per pixel, row by row) into a BlurHash string.
+///
+/// Returns `None` if the component counts are outside of `1..=9` or if `rgb` does not
+/// hold exactly `width * height` pixels.
+pub fn encode(
+    components_x: u32,
+    components_y: u32,
+    width: u32,
+    height: u32,
+    rgb: &[u8],
+) -> Option<String> {
+    if !(1..=9).contains(&components_x) || !(1..=9).contains(&components_y) {
+        return None;
+    }
+    if width == 0 || height == 0 || rgb.len() != (width * height * 3) as usize {
+        return None;
+    }
+
+    let mut factors = Vec::with_capacity((components_x * components_y) as usize);
+    for j in 0..components_y {
+        for i in 0..components_x {
+            factors.push(multiply_basis(i, j, width, height, rgb));
+        }
+    }
+    let (dc, ac) = factors.split_first()?;
+
+    let mut hash = String::with_capacity(4 + 2 * factors.len());
+    encode_base83((components_x - 1) + (components_y - 1) * 9, 1, &mut hash);
+
+    let maximum_value = if ac.is_empty() {
+        encode_base83(0, 1, &mut hash);
+        1.0
+    } else {
+        let actual_maximum = ac
+            .iter()
+            .flat_map(|factor| factor.iter())
+            .fold(0.0_f32, |max, value| max.max(value.abs()));
+        let quantised_maximum = (actual_maximum * 166.0 - 0.5).floor().clamp(0.0, 82.0) as u32;
+        encode_base83(quantised_maximum, 1, &mut hash);
+        (quantised_maximum + 1) as f32 / 166.0
+    };
+
+    encode_base83(encode_dc(dc), 4, &mut hash);
+    for factor in ac {
+        encode_base83(encode_ac(factor, maximum_value), 2, &mut hash);
+    }
+
+    Some(hash)
+}
+
+fn multiply_basis(i: u32, j: u32, width: u32, height: u32, rgb: &[u8]) -> [f32; 3] {
+    let normalisation = if i == 0 && j == 0 { 1.0 } else { 2.0 };
+    let mut factor = [0.0_f32; 3];
+
+    for y in 0..height {
+        for x in 0..width {
+            let basis = (PI * i as f32 * x as f32 / width as f32).cos()
+                * (PI * j as f32 * y as f32 / height as f32).cos();
+            let offset = 3 * (x + y * width) as usize;
+            for (channel, value) in factor.iter_mut().enumerate() {
+                *value += basis * srgb_to_linear(rgb[offset + channel]);
+            }
+        }
+    }
+
+    let scale = normalisation / (width * height) as f32;
+    factor.map(|value| value * scale)
+}
+
+fn encode_dc([r, g, b]: &[f32; 3]) -> u32 {
+    (linear_to_srgb(*r) << 16) + (linear_to_srgb(*g) << 8) + linear_to_srgb(*b)
+}
+
+fn encode_ac(factor: &[f32; 3], maximum_value: f32) -> u32 {
+    let quantise = |value: f32| {
+        (sign_pow(value / maximum_value, 0.5) * 9.0 + 9.5)
+            .floor()
+            .clamp(0.0, 18.0) as u32
+    };
+    quantise(factor[0]) * 19 * 19 + quantise(factor[1]) * 19 + quantise(factor[2])
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    let v = value as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u32 {
+    let v = value.clamp(0.0, 1.0);
+    if v <= 0.003_130_8 {
+        (v * 12.92 * 255.0 + 0.5) as u32
+    } else {
+        ((1.055 * v.powf(1.0 / 2.4) - 0.055) * 255.0 + 0.5) as u32
+    }
+}
+
+fn sign_pow(value: f32, exp: f32) -> f32 {
+    value.abs().powf(exp).copysign(value)
+}
+
+fn encode_base83(value: u32, length: u32, hash: &mut String) {
+    for i in 1..=length {
+        let digit = (value / 83_u32.pow(length - i)) % 83;
+        hash.push(BASE83_CHARS[digit as usize] as char);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 4x3 image fading from blue on the left to red on the right
+    fn gradient_fixture() -> Vec<u8> {
+        (0..3)
+            .flat_map(|_| (0..4).flat_map(|x| [x * 85, 0, 255 - x * 85]))
+            .collect()
+    }
+
+    #[test]
+    fn test_encode_known_fixture() {
+        let hash = encode(4, 3, 4, 3, &gradient_fixture()).unwrap();
+
+        // Component counts, maximum AC value and the average (DC) color
+        assert!(hash.starts_with("L~I+E+A}"), "unexpected blurhash {hash}");
+        // 1 + 1 + 4 + 2 * (4 * 3 - 1) characters
+        assert_eq!(hash.len(), 28);
+    }
+
+    #[test]
+    fn test_encode_single_component() {
+        let hash = encode(1, 1, 4, 3, &gradient_fixture()).unwrap();
+        assert_eq!(hash.len(), 6);
+        assert!(hash.starts_with("00"));
+    }
+
+    #[test]
+    fn test_encode_rejects_invalid_input() {
+        assert!(encode(0, 3, 4, 3, &gradient_fixture()).is_none());
+        assert!(encode(4, 10, 4, 3, &gradient_fixture()).is_none());
+        assert!(encode(4, 3, 4, 4, &gradient_fixture()).is_none());
+    }
+}
@@ -1,16 +1,29 @@
 use async_trait::async_trait;
+use std::sync::OnceLock;
 use tokio::process::Command;
+use tracing::{info, warn};
 
 use crate::{
-    media::{processors::MediaProcessorError, FileVariant},
+    media::{media_limits, processors::MediaProcessorError, strip_metadata_enabled, FileVariant},
     models::file::FileDetails,
 };
 
-use super::{BaseProcessingOptions, VariantProcessor};
+use super::{blurhash, BaseProcessingOptions, VariantProcessor};
 
 const SMALL_IMAGE_WIDTH: &str = "320";
 const FEED_IMAGE_WIDTH: &str = "720";
 const IMAGE_FORMAT: &str = "webp";
+const AVIF_FORMAT: &str = "avif";
+/// Still image types for which AVIF variants are offered
+const AVIF_SOURCE_CONTENT_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+/// Side, in pixels, of the thumbnail the blurhash is computed from
+const BLURHASH_SAMPLE_SIZE: u32 = 32;
+/// Horizontal and vertical blurhash components
+const BLURHASH_COMPONENTS: (u32, u32) = (4, 3);
+
+/// Whether the installed ImageMagick can encode AVIF, see [ImageProcessor::detect_avif_support]
+static AVIF_SUPPORTED: OnceLock<bool> = OnceLock::new();
 
 pub struct ImageOptions {
     width: String,
@@ -30,30 +43,37 @@ pub struct ImageProcessor;
 impl VariantProcessor for ImageProcessor {
     type ProcessingOptions = ImageOptions;
 
-    fn get_valid_variants_for_content_type(_content_type: &str) -> Vec<FileVariant> {
-        vec![FileVariant::Main, FileVariant::Small, FileVariant::Feed]
+    fn get_valid_variants_for_content_type(content_type: &str) -> Vec<FileVariant> {
+        let mut variants = vec![FileVariant::Main, FileVariant::Small, FileVariant::Feed];
+        if Self::is_avif_supported() && AVIF_SOURCE_CONTENT_TYPES.contains(&content_type) {
+            variants.extend([FileVariant::SmallAvif, FileVariant::FeedAvif]);
+        }
+        variants
     }
 
     fn get_content_type_for_variant(file: &FileDetails, variant: &FileVariant) -> String {
-        if variant.eq(&FileVariant::Main) {
-            return file.content_type.clone();
+        match variant {
+            FileVariant::Main => file.content_type.clone(),
+            FileVariant::FeedAvif | FileVariant::SmallAvif => String::from("image/avif"),
+            _ => String::from("image/webp"),
         }
-        String::from("image/webp")
     }
 
     fn get_options_for_variant(
         file: &FileDetails,
         variant: &FileVariant,
     ) -> Result<ImageOptions, MediaProcessorError> {
-        let width = match variant {
-            FileVariant::Small => String::from(SMALL_IMAGE_WIDTH),
-            FileVariant::Feed => String::from(FEED_IMAGE_WIDTH),
+        let (width, format) = match variant {
+            FileVariant::Small => (SMALL_IMAGE_WIDTH, IMAGE_FORMAT),
+            FileVariant::Feed => (FEED_IMAGE_WIDTH, IMAGE_FORMAT),
+            FileVariant::SmallAvif => (SMALL_IMAGE_WIDTH, AVIF_FORMAT),
+            FileVariant::FeedAvif => (FEED_IMAGE_WIDTH, AVIF_FORMAT),
             _ => return Err(MediaProcessorError::UnsupportedFileVariant),
         };
         let content_type = Self::get_content_type_for_variant(file, variant);
         Ok(ImageOptions {
-            format: IMAGE_FORMAT.to_string(),
-            width,
+            format: format.to_string(),
+            width: width.to_string(),
             content_type,
         })
     }
@@ -63,6 +83,14 @@ impl VariantProcessor for ImageProcessor {
         output_file_path: &str,
         options: &ImageOptions,
     ) -> Result<String, MediaProcessorError> {
+        // Without an AVIF encoder only this variant is skipped, the WebP ones are still served
+        if options.format == AVIF_FORMAT && !Self::is_avif_supported() {
+            warn!("AVIF encoding is not available, skipping variant for {origin_file_path}");
+            return Err(MediaProcessorError::UnsupportedFileVariant);
+        }
+
+        Self::check_dimensions(origin_file_path).await?;
+
         let origin_file_format = ImageProcessor::get_format(origin_file_path)
             .await?
             .to_lowercase();
@@ -72,11 +100,18 @@ impl VariantProcessor for ImageProcessor {
             false => format!("{}:{}", options.format, output_file_path),
         };
 
-        let child_output = Command::new("convert")
+        let mut command = Command::new("convert");
+        command
             .arg(origin_file_path)
             .arg("-resize")
             .arg(format!("{}x", options.width))
-            .arg("-auto-orient") // https://github.com/ImageMagick/ImageMagick/issues/6396
+            .arg("-auto-orient"); // https://github.com/ImageMagick/ImageMagick/issues/6396
+
+        // Only after -auto-orient, which relies on the EXIF orientation tag
+        if strip_metadata_enabled() {
+            command.arg("-strip");
+        }
+        let child_output = command
             .arg(output)
             .output() // Automatically pipes stdout and stderr
             .await
@@ -94,6 +129,153 @@ impl VariantProcessor for ImageProcessor {
 }
 
 impl ImageProcessor {
+    /// Checks once whether the installed ImageMagick can write AVIF images.
+    ///
+    /// Until this has run, AVIF variants are neither offered nor created.
+    pub async fn detect_avif_support() -> bool {
+        if let Some(supported) = AVIF_SUPPORTED.get() {
+            return *supported;
+        }
+
+        let supported = match Command::new("convert")
+            .arg("-list")
+            .arg("format")
+            .output()
+            .await
+        {
+            Ok(output) if output.status.success() => {
+                Self::lists_avif_encoder(&String::from_utf8_lossy(&output.stdout))
+            }
+            _ => false,
+        };
+        info!("AVIF image variants enabled: {supported}");
+
+        *AVIF_SUPPORTED.get_or_init(|| supported)
+    }
+
+    fn is_avif_supported() -> bool {
+        AVIF_SUPPORTED.get().copied().unwrap_or(false)
+    }
+
+    /// Looks for a writable AVIF entry in the output of `convert -list format`, where each
+    /// format is listed as `<name>[*] <module> <mode> <description>`
+    fn lists_avif_encoder(format_list: &str) -> bool {
+        format_list.lines().any(|line| {
+            let mut columns = line.split_whitespace();
+            let is_avif = columns
+                .next()
+                .is_some_and(|name| name.trim_end_matches('*') == "AVIF");
+            is_avif && columns.nth(1).is_some_and(|mode| mode.contains('w'))
+        })
+    }
+
+    /// Rewrites an image in place, rotated to match its EXIF orientation and without any
+    /// EXIF, XMP or IPTC metadata
+    pub async fn strip_metadata(file_path: &str) -> Result<(), MediaProcessorError> {
+        Self::check_dimensions(file_path).await?;
+
+        let child_output = Command::new("convert")
+            .arg(file_path)
+            .arg("-auto-orient")
+            .arg("-strip")
+            .arg(file_path)
+            .output()
+            .await
+            .map_err(MediaProcessorError::command_failed)?;
+
+        if child_output.status.success() {
+            Ok(())
+        } else {
+            Err(MediaProcessorError::command_failed(format!(
+                "ImageMagick metadata stripping failed: {}",
+                String::from_utf8_lossy(&child_output.stderr)
+            )))
+        }
+    }
+
+    /// Computes the blurhash placeholder of an image.
+    ///
+    /// The image is first scaled down to a tiny RGB thumbnail, smaller than any variant, so the
+    /// encoding stays cheap regardless of the original size. Only the first frame of animated
+    /// images is used.
+    pub async fn compute_blurhash(file_path: &str) -> Result<String, MediaProcessorError> {
+        Self::check_dimensions(file_path).await?;
+
+        let child_output = Command::new("convert")
+            .arg(format!("{file_path}[0]"))
+            .arg("-auto-orient")
+            .arg("-resize")
+            .arg(format!("{BLURHASH_SAMPLE_SIZE}x{BLURHASH_SAMPLE_SIZE}!"))
+            .arg("-depth")
+            .arg("8")
+            .arg("rgb:-")
+            .output()
+            .await
+            .map_err(MediaProcessorError::command_failed)?;
+
+        if !child_output.status.success() {
+            return Err(MediaProcessorError::command_failed(format!(
+                "ImageMagick blurhash sampling failed: {}",
+                String::from_utf8_lossy(&child_output.stderr)
+            )));
+        }
+
+        let (components_x, components_y) = BLURHASH_COMPONENTS;
+        blurhash::encode(
+            components_x,
+            components_y,
+            BLURHASH_SAMPLE_SIZE,
+            BLURHASH_SAMPLE_SIZE,
+            &child_output.stdout,
+        )
+        .ok_or_else(|| {
+            MediaProcessorError::command_failed(format!(
+                "Unexpected thumbnail size for blurhash: {} bytes",
+                child_output.stdout.len()
+            ))
+        })
+    }
+
+    /// Rejects images above the configured [crate::MediaLimits].
+    ///
+    /// The dimensions are read from the image header only (`identify -ping`), so oversized images
+    /// are rejected before any pixel data is decoded.
+    pub async fn check_dimensions(file_path: &str) -> Result<(), MediaProcessorError> {
+        let child_output = Command::new("identify")
+            .arg("-ping")
+            .arg("-format")
+            .arg("%w %h")
+            .arg(format!("{file_path}[0]"))
+            .output()
+            .await
+            .map_err(MediaProcessorError::command_failed)?;
+
+        if !child_output.status.success() {
+            return Err(MediaProcessorError::command_failed(format!(
+                "ImageMagick dimensions extraction failed: {}",
+                String::from_utf8_lossy(&child_output.stderr)
+            )));
+        }
+
+        let dimensions = String::from_utf8_lossy(&child_output.stdout);
+        let (width, height) = Self::parse_dimensions(&dimensions).ok_or_else(|| {
+            MediaProcessorError::command_failed(format!(
+                "Unexpected image dimensions: {dimensions}"
+            ))
+        })?;
+
+        match media_limits().allows(width, height) {
+            true => Ok(()),
+            false => Err(MediaProcessorError::DimensionsExceeded { width, height }),
+        }
+    }
+
+    /// Parses the `<width> <height>` output of `identify -format "%w %h"`
+    fn parse_dimensions(output: &str) -> Option<(u32, u32)> {
+        let (width, height) = output.trim().split_once(' ')?;
+        Some((width.parse().ok()?, height.parse().ok()?))
+    }
+
     // function to get image format
     async fn get_format(file_path: &str) -> Result<String, MediaProcessorError> {
         let child_output = Command::new("identify")
@@ -114,3 +296,65 @@ impl ImageProcessor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaLimits;
+
+    #[test]
+    fn test_parse_dimensions() {
+        assert_eq!(
+            ImageProcessor::parse_dimensions("30000 30000"),
+            Some((30000, 30000))
+        );
+        assert_eq!(
+            ImageProcessor::parse_dimensions("640 480\n"),
+            Some((640, 480))
+        );
+        assert_eq!(ImageProcessor::parse_dimensions("640x480"), None);
+    }
+
+    #[test]
+    fn test_media_limits() {
+        let limits = MediaLimits::default();
+        assert!(limits.allows(4000, 3000));
+        // Within the width and height limits, but above 50 megapixels
+        assert!(!limits.allows(10_000, 10_000));
+        assert!(!limits.allows(30_000, 100));
+    }
+
+    #[test]
+    fn test_lists_avif_encoder() {
+        let with_avif = "   Format  Module    Mode  Description
+-------------------------------------------------------------------------------
+     AVIF  HEIC      rw+   AV1 Image File Format (1.17.6)
+      PNG* PNG       rw-   Portable Network Graphics (libpng 1.6.43)";
+        assert!(ImageProcessor::lists_avif_encoder(with_avif));
+
+        let read_only = "     AVIF  HEIC      r--   AV1 Image File Format (1.17.6)";
+        assert!(!ImageProcessor::lists_avif_encoder(read_only));
+
+        let without_avif = "      PNG* PNG       rw-   Portable Network Graphics (libpng 1.6.43)";
+        assert!(!ImageProcessor::lists_avif_encoder(without_avif));
+    }
+
+    #[test]
+    fn test_avif_variant_options() {
+        let file = FileDetails {
+            content_type: "image/png".to_string(),
+            ..Default::default()
+        };
+
+        let options =
+            ImageProcessor::get_options_for_variant(&file, &FileVariant::FeedAvif).unwrap();
+        assert_eq!(options.width, FEED_IMAGE_WIDTH);
+        assert_eq!(options.format, "avif");
+        assert_eq!(options.content_type, "image/avif");
+
+        let options = ImageProcessor::get_options_for_variant(&file, &FileVariant::Small).unwrap();
+        assert_eq!(options.width, SMALL_IMAGE_WIDTH);
+        assert_eq!(options.format, "webp");
+        assert_eq!(options.content_type, "image/webp");
+    }
+}
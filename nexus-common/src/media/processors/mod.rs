@@ -1,8 +1,10 @@
 use crate::{media::FileVariant, models::file::FileDetails};
 use async_trait::async_trait;
+use futures::future::join_all;
 use std::path::PathBuf;
 use thiserror::Error;
 
+mod blurhash;
 mod image;
 mod video;
 
@@ -28,6 +30,8 @@ pub enum MediaProcessorError {
     UnsupportedFileVariant,
     #[error("InvalidFilePath: {0}")]
     InvalidFilePath(String),
+    #[error("DimensionsExceeded: {width}x{height} is above the configured media limits")]
+    DimensionsExceeded { width: u32, height: u32 },
 }
 
 impl MediaProcessorError {
@@ -38,6 +42,15 @@ impl MediaProcessorError {
     }
 }
 
+/// Outcome of [VariantProcessor::process_all_variants]
+#[derive(Debug, Default)]
+pub struct ProcessedVariants {
+    /// Successfully created variants with their content type, in the requested order
+    pub created: Vec<(FileVariant, String)>,
+    /// Variants that could not be created, in the requested order
+    pub errors: Vec<(FileVariant, MediaProcessorError)>,
+}
+
 #[async_trait]
 pub trait VariantProcessor {
     type ProcessingOptions: BaseProcessingOptions;
@@ -100,4 +113,30 @@ pub trait VariantProcessor {
 
         Ok(options.content_type())
     }
+
+    /// Creates several variants of the given file concurrently.
+    ///
+    /// The encoding runs in external processes, so the variants are created in parallel without
+    /// blocking the runtime. A failing variant does not abort the others.
+    async fn process_all_variants(
+        file: &FileDetails,
+        variants: &[FileVariant],
+        file_path: PathBuf,
+    ) -> ProcessedVariants {
+        let results = join_all(
+            variants
+                .iter()
+                .map(|variant| Self::create_variant(file, variant, file_path.clone())),
+        )
+        .await;
+
+        let mut processed = ProcessedVariants::default();
+        for (variant, result) in variants.iter().zip(results) {
+            match result {
+                Ok(content_type) => processed.created.push((variant.clone(), content_type)),
+                Err(e) => processed.errors.push((variant.clone(), e)),
+            }
+        }
+        processed
+    }
 }
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
-use tokio::process::Command;
+use serde::Deserialize;
+use tokio::{fs, process::Command};
 
 use crate::{
     media::{processors::MediaProcessorError, FileVariant},
@@ -8,6 +9,21 @@ use crate::{
 
 use super::{BaseProcessingOptions, VariantProcessor};
 
+/// Width of the 480p variant
+const SMALL_VIDEO_WIDTH: &str = "854";
+/// Width of the 720p variant
+const FEED_VIDEO_WIDTH: &str = "1280";
+const VIDEO_FORMAT: &str = "mp4";
+const VIDEO_CONTENT_TYPE: &str = "video/mp4";
+/// Video codec that can be served as is inside the target container
+const VIDEO_CODEC: &str = "h264";
+/// Major brands of the ISO base media files that are MP4, as opposed to e.g. QuickTime (`qt  `),
+/// which ffprobe reports under the same format names
+const MP4_BRANDS: [&str; 10] = [
+    "isom", "iso2", "iso4", "iso5", "iso6", "mp41", "mp42", "avc1", "dash", "M4V ",
+];
+const PROCESSABLE_CONTENT_TYPES: [&str; 3] = ["video/mp4", "video/webm", "video/quicktime"];
+
 pub struct VideoOptions {
     width: String,
     format: String,
@@ -20,32 +36,124 @@ impl BaseProcessingOptions for VideoOptions {
     }
 }
 
-/// VideoProcessor is just a prototype and not a real implementation
-/// when we decide to actual start video processing we will need to implement this.
+/// Output of `ffprobe -show_format -show_streams -print_format json`, limited to the fields we use
+#[derive(Deserialize, Debug, Default)]
+struct ProbeOutput {
+    #[serde(default)]
+    format: ProbeFormat,
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct ProbeFormat {
+    /// Comma separated list of the names the container is known by, e.g. `mov,mp4,m4a,3gp,3g2,mj2`
+    #[serde(default)]
+    format_name: String,
+    #[serde(default)]
+    tags: ProbeFormatTags,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct ProbeFormatTags {
+    /// Brand of an ISO base media file, e.g. `isom` or `qt  `
+    major_brand: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ProbeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    width: Option<u64>,
+}
+
+impl ProbeOutput {
+    fn video_stream(&self) -> Option<&ProbeStream> {
+        self.streams
+            .iter()
+            .find(|stream| stream.codec_type.as_deref() == Some("video"))
+    }
+
+    fn has_audio(&self) -> bool {
+        self.streams
+            .iter()
+            .any(|stream| stream.codec_type.as_deref() == Some("audio"))
+    }
+
+    fn is_format(&self, format: &str) -> bool {
+        self.format
+            .format_name
+            .split(',')
+            .any(|name| name == format)
+    }
+
+    /// Whether the container is `format`. MP4 and QuickTime files share their format names, so an
+    /// MP4 container is told apart by its major brand
+    fn is_container(&self, format: &str) -> bool {
+        if format != VIDEO_FORMAT {
+            return self.is_format(format);
+        }
+        self.is_format(format)
+            && self
+                .format
+                .tags
+                .major_brand
+                .as_deref()
+                .is_some_and(|brand| MP4_BRANDS.contains(&brand))
+    }
+
+    /// Whether the video already is in the target container and codec, and no wider than the
+    /// target width, in which case it can be used for the variant without re-encoding or re-muxing
+    fn fits(&self, options: &VideoOptions) -> bool {
+        let max_width = options.width.parse::<u64>().unwrap_or(0);
+        self.is_container(&options.format)
+            && self.video_stream().is_some_and(|stream| {
+                stream.codec_name.as_deref() == Some(VIDEO_CODEC)
+                    && stream.width.is_some_and(|width| width <= max_width)
+            })
+    }
+}
+
 pub struct VideoProcessor;
 
 #[async_trait]
 impl VariantProcessor for VideoProcessor {
     type ProcessingOptions = VideoOptions;
 
-    fn get_valid_variants_for_content_type(_content_type: &str) -> Vec<FileVariant> {
-        vec![FileVariant::Main]
+    fn get_valid_variants_for_content_type(content_type: &str) -> Vec<FileVariant> {
+        match PROCESSABLE_CONTENT_TYPES.contains(&content_type) {
+            true => vec![FileVariant::Main, FileVariant::Feed, FileVariant::Small],
+            false => vec![FileVariant::Main],
+        }
     }
 
     fn get_content_type_for_variant(file: &FileDetails, variant: &FileVariant) -> String {
         if variant.eq(&FileVariant::Main) {
             return file.content_type.clone();
         }
-        String::from("video/mp4")
+        String::from(VIDEO_CONTENT_TYPE)
     }
 
     fn get_options_for_variant(
-        _file: &FileDetails,
-        _variant: &FileVariant,
+        file: &FileDetails,
+        variant: &FileVariant,
     ) -> Result<VideoOptions, MediaProcessorError> {
-        // Return Err until we have a real implementation
-        // TODO: Add real implementation for videos
-        Err(MediaProcessorError::NotImplemented)
+        if !PROCESSABLE_CONTENT_TYPES.contains(&file.content_type.as_str()) {
+            return Err(MediaProcessorError::UnsupportedContentType(
+                file.content_type.clone(),
+            ));
+        }
+        let width = match variant {
+            FileVariant::Small => String::from(SMALL_VIDEO_WIDTH),
+            FileVariant::Feed => String::from(FEED_VIDEO_WIDTH),
+            _ => return Err(MediaProcessorError::UnsupportedFileVariant),
+        };
+        let content_type = Self::get_content_type_for_variant(file, variant);
+        Ok(VideoOptions {
+            format: VIDEO_FORMAT.to_string(),
+            width,
+            content_type,
+        })
     }
 
     async fn process(
@@ -53,21 +161,42 @@ impl VariantProcessor for VideoProcessor {
         output_file_path: &str,
         options: &VideoOptions,
     ) -> Result<String, MediaProcessorError> {
-        let origin_file_format = VideoProcessor::get_format(origin_file_path).await?;
+        let probe = VideoProcessor::probe(origin_file_path).await?;
 
-        let output = match origin_file_format == options.format {
-            true => output_file_path.to_string(),
-            false => format!("{}.{}", output_file_path, options.format),
-        };
+        // Nothing to scale or convert, serve a copy of the original
+        if probe.fits(options) {
+            fs::copy(origin_file_path, output_file_path)
+                .await
+                .map_err(MediaProcessorError::command_failed)?;
+            return Ok(String::new());
+        }
 
-        let child_output = Command::new("ffmpeg")
+        let mut command = Command::new("ffmpeg");
+        command
+            .arg("-y")
             .arg("-i")
             .arg(origin_file_path)
+            // Only downscale, keeping the aspect ratio with an even height as required by H.264
             .arg("-vf")
-            .arg(format!("scale={}:-1", options.width))
-            .arg("-c:a")
-            .arg("copy")
-            .arg(output)
+            .arg(format!("scale='min({},iw)':-2", options.width))
+            .arg("-c:v")
+            .arg("libx264")
+            .arg("-pix_fmt")
+            .arg("yuv420p")
+            .arg("-movflags")
+            .arg("+faststart");
+
+        // WebM audio (Opus/Vorbis) cannot be copied into MP4, and copying fails without audio
+        match probe.has_audio() {
+            true => command.arg("-c:a").arg("aac"),
+            false => command.arg("-an"),
+        };
+
+        // The variant path has no extension, so the container is set explicitly
+        let child_output = command
+            .arg("-f")
+            .arg(&options.format)
+            .arg(output_file_path)
             .output() // Automatically pipes stdout and stderr
             .await
             .map_err(MediaProcessorError::command_failed)?;
@@ -84,22 +213,26 @@ impl VariantProcessor for VideoProcessor {
 }
 
 impl VideoProcessor {
-    /// Returns the format of the video
-    async fn get_format(input: &str) -> Result<String, MediaProcessorError> {
-        let child_output = Command::new("ffmpeg")
-            .arg("-i")
+    /// Returns the container format and streams of the video
+    async fn probe(input: &str) -> Result<ProbeOutput, MediaProcessorError> {
+        let child_output = Command::new("ffprobe")
+            .arg("-v")
+            .arg("error")
+            .arg("-show_format")
+            .arg("-show_streams")
+            .arg("-print_format")
+            .arg("json")
             .arg(input)
-            .arg("-f")
-            .arg("null")
             .output() // Automatically pipes stdout and stderr
             .await
             .map_err(MediaProcessorError::command_failed)?;
 
         if child_output.status.success() {
-            Ok(String::from_utf8_lossy(&child_output.stdout).to_string())
+            serde_json::from_slice(&child_output.stdout)
+                .map_err(MediaProcessorError::command_failed)
         } else {
             Err(MediaProcessorError::command_failed(format!(
-                "FFmpeg metadata extraction failed: {}",
+                "FFprobe metadata extraction failed: {}",
                 String::from_utf8_lossy(&child_output.stderr)
             )))
         }
@@ -130,4 +263,114 @@ mod tests {
         let result = VideoProcessor::get_content_type_for_variant(&file, &FileVariant::Main);
         assert_eq!(result, "video/mp4");
     }
+
+    fn make_options(variant: &FileVariant) -> VideoOptions {
+        VideoProcessor::get_options_for_variant(&make_file("video/mp4"), variant).unwrap()
+    }
+
+    fn make_probe(
+        format_name: &str,
+        major_brand: &str,
+        codec_name: &str,
+        width: u64,
+        audio: bool,
+    ) -> ProbeOutput {
+        let mut streams = vec![ProbeStream {
+            codec_type: Some("video".to_string()),
+            codec_name: Some(codec_name.to_string()),
+            width: Some(width),
+        }];
+        if audio {
+            streams.push(ProbeStream {
+                codec_type: Some("audio".to_string()),
+                codec_name: Some("aac".to_string()),
+                width: None,
+            });
+        }
+        ProbeOutput {
+            format: ProbeFormat {
+                format_name: format_name.to_string(),
+                tags: ProbeFormatTags {
+                    major_brand: Some(major_brand.to_string()),
+                },
+            },
+            streams,
+        }
+    }
+
+    #[test]
+    fn test_valid_variants_for_processable_content_types() {
+        for content_type in ["video/mp4", "video/webm", "video/quicktime"] {
+            let variants = VideoProcessor::get_valid_variants_for_content_type(content_type);
+            assert_eq!(
+                variants,
+                vec![FileVariant::Main, FileVariant::Feed, FileVariant::Small]
+            );
+        }
+        let variants = VideoProcessor::get_valid_variants_for_content_type("video/x-msvideo");
+        assert_eq!(variants, vec![FileVariant::Main]);
+    }
+
+    #[test]
+    fn test_options_for_variants() {
+        let feed = make_options(&FileVariant::Feed);
+        assert_eq!(feed.width, FEED_VIDEO_WIDTH);
+        assert_eq!(feed.format, "mp4");
+        assert_eq!(feed.content_type, "video/mp4");
+
+        let small = make_options(&FileVariant::Small);
+        assert_eq!(small.width, SMALL_VIDEO_WIDTH);
+
+        let file = make_file("video/webm");
+        assert!(matches!(
+            VideoProcessor::get_options_for_variant(&file, &FileVariant::Main),
+            Err(MediaProcessorError::UnsupportedFileVariant)
+        ));
+    }
+
+    #[test]
+    fn test_parse_probe_output() {
+        let json = r#"{
+            "streams": [
+                {"index": 0, "codec_name": "vp9", "codec_type": "video", "width": 1920, "height": 1080},
+                {"index": 1, "codec_name": "opus", "codec_type": "audio"}
+            ],
+            "format": {"filename": "main", "format_name": "matroska,webm", "duration": "4.000000"}
+        }"#;
+        let probe: ProbeOutput = serde_json::from_str(json).unwrap();
+
+        assert!(probe.is_format("webm"));
+        assert!(!probe.is_format("mp4"));
+        assert!(probe.has_audio());
+        assert_eq!(probe.video_stream().unwrap().width, Some(1920));
+    }
+
+    #[test]
+    fn test_probe_fits_only_matching_container_codec_and_width() {
+        let options = make_options(&FileVariant::Feed);
+
+        let mp4 = "mov,mp4,m4a,3gp,3g2,mj2";
+        assert!(make_probe(mp4, "isom", "h264", 1280, false).fits(&options));
+        assert!(make_probe(mp4, "mp42", "h264", 640, true).fits(&options));
+        // Too wide, needs downscaling
+        assert!(!make_probe(mp4, "isom", "h264", 1920, true).fits(&options));
+        // Other container or codec, needs re-encoding
+        assert!(!make_probe("matroska,webm", "webm", "h264", 640, true).fits(&options));
+        assert!(!make_probe(mp4, "isom", "prores", 640, true).fits(&options));
+        // QuickTime shares the format names of MP4, but needs re-muxing
+        assert!(!make_probe(mp4, "qt  ", "h264", 640, true).fits(&options));
+    }
+
+    #[test]
+    fn test_parse_probe_output_of_quicktime() {
+        let json = r#"{
+            "streams": [{"index": 0, "codec_name": "h264", "codec_type": "video", "width": 640}],
+            "format": {"format_name": "mov,mp4,m4a,3gp,3g2,mj2", "tags": {"major_brand": "qt  "}}
+        }"#;
+        let probe: ProbeOutput = serde_json::from_str(json).unwrap();
+
+        assert!(probe.is_format("mp4"));
+        assert!(!probe.is_container("mp4"));
+        assert!(!probe.fits(&make_options(&FileVariant::Feed)));
+    }
 }
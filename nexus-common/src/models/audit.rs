@@ -0,0 +1,23 @@
+/// Result of one step of an audit of index entries without a backing graph node.
+///
+/// Audits are read-only: the orphaned entries are reported, never deleted.
+#[derive(Debug, Default)]
+pub struct OrphanReport {
+    /// Number of index members checked against the graph
+    pub scanned: usize,
+    /// Members without a backing graph node
+    pub orphans: Vec<String>,
+    /// Cursor to resume the audit from, `None` once the whole index was scanned
+    pub next_cursor: Option<u64>,
+}
+
+impl OrphanReport {
+    pub(crate) fn new(scanned: usize, orphans: Vec<String>, next_cursor: u64) -> Self {
+        Self {
+            scanned,
+            orphans,
+            // Redis returns cursor 0 once a scan is complete
+            next_cursor: (next_cursor != 0).then_some(next_cursor),
+        }
+    }
+}
@@ -10,7 +10,7 @@ use super::error::ModelResult;
 
 use crate::models::{
     file::FileDetails,
-    post::{PostStream, StreamSource},
+    post::{PostStream, PostStreamParams, StreamSource},
     traits::Collection,
     user::{Influencers, UserStream},
 };
@@ -250,13 +250,14 @@ impl Bootstrap {
             end: None,
         };
         Ok(PostStream::get_posts(
-            source,
-            pagination,
-            SortOrder::default(),
-            StreamSorting::Timeline,
+            PostStreamParams {
+                source,
+                pagination,
+                order: SortOrder::default(),
+                sorting: StreamSorting::Timeline,
+                ..Default::default()
+            },
             maybe_viewer_id.map(|id| id.to_string()),
-            None,
-            None,
         )
         .await?
         .unwrap_or_default())
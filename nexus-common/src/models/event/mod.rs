@@ -1,6 +1,9 @@
 mod errors;
 
-use crate::db::{kv::RedisResult, RedisOps};
+use crate::db::{
+    kv::{RedisResult, SortOrder},
+    RedisOps,
+};
 use pubky_app_specs::{ParsedUri, Resource};
 use serde::{Deserialize, Serialize};
 use std::{fmt, path::PathBuf};
@@ -97,7 +100,8 @@ impl Event {
     /// Stores event line in Redis as part of the events list.
     #[tracing::instrument(name = "event.index.write", skip_all)]
     pub async fn store_event(&self) -> RedisResult<()> {
-        self.put_index_list(&["Events"]).await
+        // Never trimmed, the event cursors are indexes into this list
+        self.put_index_list(&["Events"], None).await
     }
 
     pub async fn get_events_from_redis(
@@ -108,7 +112,13 @@ impl Event {
         // Clamp to usize::MAX: on 32-bit targets u64 can exceed usize; the LRANGE
         // would return empty results for such a large index either way.
         let start_u = usize::try_from(start).unwrap_or(usize::MAX);
-        let result = Event::try_from_index_list(&["Events"], Some(start_u), Some(limit)).await;
+        let result = Event::try_from_index_list(
+            &["Events"],
+            Some(start_u),
+            Some(limit),
+            SortOrder::Ascending,
+        )
+        .await;
 
         let events = match result {
             Ok(r) => r.unwrap_or_default(),
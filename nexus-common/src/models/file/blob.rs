@@ -1,12 +1,9 @@
 use crate::{
-    media::{
-        processors::{ImageProcessor, VariantProcessor, VideoProcessor},
-        FileVariant, VariantController,
-    },
+    media::{FileVariant, VariantController},
     models::error::{ModelError, ModelResult},
 };
 use pubky_app_specs::PubkyAppBlob;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::{
     fs::{self, File},
     io::AsyncWriteExt,
@@ -62,21 +59,73 @@ impl Blob {
         variant: &FileVariant,
         file_path: PathBuf,
     ) -> ModelResult<String> {
-        match &file.content_type {
-            content_type if content_type.starts_with("image/") => {
-                ImageProcessor::create_variant(file, variant, file_path)
-                    .await
-                    .map_err(Into::into)
-            }
-            content_type if content_type.starts_with("video/") => {
-                VideoProcessor::create_variant(file, variant, file_path)
-                    .await
-                    .map_err(Into::into)
-            }
-            _ => Err(ModelError::from_generic(format!(
-                "Unsupported content type: {}",
-                file.content_type
-            ))),
+        VariantController::create_file_variant(file, variant, file_path)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+/// Writes a file to the static files directory chunk by chunk, so that large uploads are never
+/// held in memory as a whole.
+///
+/// Chunks go to a `.part` file next to the target, which is only moved into place by
+/// [StaticFileWriter::finish]. Exceeding the size cap removes the partial file.
+pub struct StaticFileWriter {
+    file: File,
+    part_path: PathBuf,
+    target_path: PathBuf,
+    written: u64,
+    max_size: u64,
+}
+
+impl StaticFileWriter {
+    pub async fn create(files_path: &Path, name: &str, max_size: u64) -> ModelResult<Self> {
+        fs::create_dir_all(files_path).await?;
+
+        let target_path = files_path.join(name);
+        let part_path = files_path.join(format!("{name}.part"));
+        let file = File::create(&part_path).await?;
+
+        Ok(Self {
+            file,
+            part_path,
+            target_path,
+            written: 0,
+            max_size,
+        })
+    }
+
+    /// Appends a chunk to the file, failing once the total size exceeds the cap
+    pub async fn write_chunk(&mut self, chunk: &[u8]) -> ModelResult<()> {
+        self.written += chunk.len() as u64;
+        if self.written > self.max_size {
+            self.abort().await;
+            return Err(ModelError::from_generic(format!(
+                "File exceeds the maximum size of {} bytes",
+                self.max_size
+            )));
+        }
+        if let Err(e) = self.file.write_all(chunk).await {
+            self.abort().await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Flushes the file and moves it into place. Returns the number of bytes written
+    pub async fn finish(mut self) -> ModelResult<u64> {
+        if let Err(e) = self.file.flush().await {
+            self.abort().await;
+            return Err(e.into());
+        }
+        fs::rename(&self.part_path, &self.target_path).await?;
+        Ok(self.written)
+    }
+
+    /// Removes the partial file
+    pub async fn abort(&mut self) {
+        if let Err(e) = fs::remove_file(&self.part_path).await {
+            tracing::warn!("Could not remove partial file {:?}: {e}", self.part_path);
         }
     }
 }
@@ -84,6 +133,7 @@ impl Blob {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{media::processors::MediaProcessorError, models::error::ModelError};
     use pubky_app_specs::PubkyAppBlob;
     use tokio::io::AsyncReadExt;
 
@@ -132,4 +182,60 @@ mod tests {
             .unwrap();
         assert_eq!(content, b"second write");
     }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_get_by_id_rejects_content_type_not_allowed() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let file = FileDetails {
+            content_type: "image/x-portable-anymap".to_string(),
+            ..Default::default()
+        };
+
+        let result =
+            Blob::get_by_id(&file, &FileVariant::Small, tmp_dir.path().to_path_buf()).await;
+
+        assert!(matches!(
+            result,
+            Err(ModelError::MediaProcessorError(
+                MediaProcessorError::UnsupportedContentType(content_type)
+            )) if content_type == "image/x-portable-anymap"
+        ));
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_static_file_writer_streams_chunks() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let files_path = tmp_dir.path().join("user1").join("file1");
+
+        let mut writer = StaticFileWriter::create(&files_path, "main", 16)
+            .await
+            .unwrap();
+        writer.write_chunk(b"hello ").await.unwrap();
+        writer.write_chunk(b"world").await.unwrap();
+        // Nothing is visible under the final name until the file is complete
+        assert!(fs::metadata(files_path.join("main")).await.is_err());
+
+        assert_eq!(writer.finish().await.unwrap(), 11);
+        assert_eq!(
+            fs::read(files_path.join("main")).await.unwrap(),
+            b"hello world"
+        );
+        assert!(fs::metadata(files_path.join("main.part")).await.is_err());
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_static_file_writer_rejects_oversized_file() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let files_path = tmp_dir.path().join("user1").join("file1");
+
+        let mut writer = StaticFileWriter::create(&files_path, "main", 8)
+            .await
+            .unwrap();
+        writer.write_chunk(b"hello").await.unwrap();
+        let result = writer.write_chunk(b" world").await;
+
+        assert!(matches!(result, Err(ModelError::Generic(_))));
+        assert!(fs::metadata(files_path.join("main")).await.is_err());
+        assert!(fs::metadata(files_path.join("main.part")).await.is_err());
+    }
 }
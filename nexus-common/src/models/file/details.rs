@@ -17,6 +17,8 @@ pub struct FileUrls {
     pub main: String,
     pub feed: Option<String>,
     pub small: Option<String>,
+    pub feed_avif: Option<String>,
+    pub small_avif: Option<String>,
 }
 
 impl FileUrls {
@@ -41,6 +43,12 @@ impl FileUrls {
             small: variants
                 .contains(&FileVariant::Small)
                 .then(|| build_url(&FileVariant::Small)),
+            feed_avif: variants
+                .contains(&FileVariant::FeedAvif)
+                .then(|| build_url(&FileVariant::FeedAvif)),
+            small_avif: variants
+                .contains(&FileVariant::SmallAvif)
+                .then(|| build_url(&FileVariant::SmallAvif)),
         }
     }
 }
@@ -82,10 +90,15 @@ pub struct FileDetails {
     #[serde(with = "json_string")]
     pub urls: FileUrls,
     pub metadata: Option<HashMap<String, String>>,
+    /// Compact placeholder of the image, shown by clients until the image is loaded.
+    /// `None` for non-image files and for files indexed before blurhashes were computed
+    #[serde(default)]
+    pub blurhash: Option<String>,
 }
 
 pub struct FileMeta {
     pub urls: FileUrls,
+    pub blurhash: Option<String>,
 }
 
 impl RedisOps for FileDetails {}
@@ -125,6 +138,7 @@ impl FileDetails {
             size: pubkyapp_file.size as i64,
             urls: meta.urls,
             metadata: None,
+            blurhash: meta.blurhash,
         }
     }
 
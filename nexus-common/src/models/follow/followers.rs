@@ -1,9 +1,13 @@
 use crate::db::graph::Query;
+use crate::db::kv::key::build_key;
+use crate::db::kv::sets;
 use crate::db::{queries, RedisOps};
+use crate::models::error::ModelResult;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use super::following::Following;
 use super::traits::UserFollows;
 
 #[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
@@ -18,6 +22,59 @@ impl AsRef<[String]> for Followers {
 #[async_trait]
 impl RedisOps for Followers {}
 
+/// Maximum number of connections loaded from the graph when an index of [Followers::mutual_with]
+/// is missing, like the friends
+const MUTUAL_GRAPH_LIMIT: usize = 10000;
+
+/// The followers of a user that a viewer follows
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
+pub struct MutualFollowers {
+    /// Total number of mutual followers
+    pub count: usize,
+    /// IDs of the mutual followers in the requested page, sorted
+    pub user_ids: Vec<String>,
+}
+
+impl Followers {
+    /// Retrieves the followers of `user_id` that `viewer_id` follows, with their total count.
+    ///
+    /// Returns `None` if `user_id` does not exist.
+    pub async fn mutual_with(
+        user_id: &str,
+        viewer_id: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> ModelResult<Option<MutualFollowers>> {
+        // Index miss: load the connections from the graph, which also indexes them
+        if Followers::get_set_size(&[user_id]).await?.is_none()
+            && Followers::get_by_id(user_id, None, Some(MUTUAL_GRAPH_LIMIT))
+                .await?
+                .is_none()
+        {
+            return Ok(None);
+        }
+        if Following::get_set_size(&[viewer_id]).await?.is_none() {
+            Following::get_by_id(viewer_id, None, Some(MUTUAL_GRAPH_LIMIT)).await?;
+        }
+
+        let keys = [
+            format!("{}:{}", Followers::prefix().await, build_key(&[user_id])),
+            format!("{}:{}", Following::prefix().await, build_key(&[viewer_id])),
+        ];
+        let mut mutuals = sets::intersect(&keys).await?;
+        mutuals.sort();
+
+        let count = mutuals.len();
+        let user_ids = mutuals
+            .into_iter()
+            .skip(skip.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+
+        Ok(Some(MutualFollowers { count, user_ids }))
+    }
+}
+
 impl UserFollows for Followers {
     fn from_vec(vec: Vec<String>) -> Self {
         Self(vec)
@@ -27,7 +84,106 @@ impl UserFollows for Followers {
         queries::get::get_user_followers(user_id, skip, limit)
     }
 
+    fn get_page_query(user_id: &str, after: Option<&str>, limit: usize) -> Query {
+        queries::get::get_user_followers_after(user_id, after, limit)
+    }
+
     fn get_ids_field_name() -> &'static str {
         "follower_ids"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::user::test_utils::create_user;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+    use pubky::Keypair;
+    use std::collections::HashSet;
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_followers_cursor_pagination() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let user_id = create_user("FollowersCursor:user").await?;
+        let mut follower_ids = Vec::new();
+        for i in 0..100 {
+            let follower_id = create_user(&format!("FollowersCursor:{i}")).await?;
+            Followers::put_to_graph(&follower_id, &user_id).await?;
+            follower_ids.push(follower_id);
+        }
+        follower_ids.sort();
+
+        // The graph pages cover all followers in order
+        let mut from_graph = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = Followers::get_page_from_graph(&user_id, cursor.as_deref(), 30)
+                .await?
+                .expect("The user should exist in the graph");
+            let last_page = page.len() < 30;
+            cursor = page.last().cloned();
+            from_graph.extend(page);
+            if last_page {
+                break;
+            }
+        }
+        assert_eq!(from_graph, follower_ids);
+
+        Followers::reindex(&user_id).await?;
+
+        // Page through the index, adding a follower midway
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        let mut new_follower_id = None;
+        loop {
+            let page = Followers::get_page(&user_id, cursor.as_deref(), 7)
+                .await?
+                .expect("The user should exist");
+            seen.extend(page.user_ids);
+
+            if new_follower_id.is_none() && seen.len() >= 50 {
+                let follower_id = create_user("FollowersCursor:new").await?;
+                Followers::put_to_graph(&follower_id, &user_id).await?;
+                Followers(vec![follower_id.clone()])
+                    .put_to_index(&user_id)
+                    .await?;
+                new_follower_id = Some(follower_id);
+            }
+
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+
+        let unique: HashSet<&String> = seen.iter().collect();
+        assert_eq!(
+            unique.len(),
+            seen.len(),
+            "Pages should not repeat followers"
+        );
+        assert!(
+            seen.windows(2).all(|pair| pair[0] < pair[1]),
+            "Followers should be sorted by ID"
+        );
+        let existing: Vec<String> = seen
+            .iter()
+            .filter(|id| Some(*id) != new_follower_id.as_ref())
+            .cloned()
+            .collect();
+        assert_eq!(existing, follower_ids, "Pages should not miss followers");
+
+        // Users that don't exist have no followers page
+        let missing_id = Keypair::random().public_key().to_z32();
+        assert!(Followers::get_page(&missing_id, None, 10).await?.is_none());
+
+        // Cursors are opaque, not user IDs
+        assert!(Followers::get_page(&user_id, Some(&user_id), 10)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+}
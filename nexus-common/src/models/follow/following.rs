@@ -27,6 +27,10 @@ impl UserFollows for Following {
         queries::get::get_user_following(user_id, skip, limit)
     }
 
+    fn get_page_query(user_id: &str, after: Option<&str>, limit: usize) -> Query {
+        queries::get::get_user_following_after(user_id, after, limit)
+    }
+
     fn get_ids_field_name() -> &'static str {
         "following_ids"
     }
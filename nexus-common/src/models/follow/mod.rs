@@ -1,9 +1,11 @@
 mod followers;
 mod following;
 mod friends;
+mod stats;
 mod traits;
 
-pub use followers::Followers;
+pub use followers::{Followers, MutualFollowers};
 pub use following::Following;
 pub use friends::Friends;
-pub use traits::UserFollows;
+pub use stats::{FollowStats, USER_FOLLOWED_AT_KEY_PARTS};
+pub use traits::{FollowsPage, UserFollows};
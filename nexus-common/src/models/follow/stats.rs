@@ -0,0 +1,71 @@
+use crate::db::kv::RedisResult;
+use crate::db::{fetch_all_rows_from_graph, queries, RedisOps};
+use crate::models::error::ModelResult;
+use crate::types::Timeframe;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Sorted set of the followers of each user, scored by the time they followed the user
+pub const USER_FOLLOWED_AT_KEY_PARTS: [&str; 2] = ["Users", "FollowedAt"];
+
+/// Follower growth of a user over a timeframe
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug, PartialEq)]
+pub struct FollowStats {
+    /// Number of users that followed the user within the timeframe, and still follow them
+    pub followers_gained: usize,
+}
+
+impl RedisOps for FollowStats {}
+
+impl FollowStats {
+    /// Records that `follower_id` followed `user_id` at `followed_at` (in milliseconds)
+    pub async fn put_to_index(
+        user_id: &str,
+        follower_id: &str,
+        followed_at: i64,
+    ) -> RedisResult<()> {
+        let key_parts = [&USER_FOLLOWED_AT_KEY_PARTS[..], &[user_id]].concat();
+        Self::put_index_sorted_set(&key_parts, &[(followed_at as f64, follower_id)], None, None)
+            .await
+    }
+
+    /// Forgets when `follower_id` followed `user_id`, once they unfollowed
+    pub async fn del_from_index(user_id: &str, follower_id: &str) -> RedisResult<()> {
+        let key_parts = [&USER_FOLLOWED_AT_KEY_PARTS[..], &[user_id]].concat();
+        Self::remove_from_index_sorted_set(None, &key_parts, &[follower_id]).await
+    }
+
+    /// Indexes when each current follower of `user_id` followed them, read from the graph.
+    ///
+    /// The graph only keeps when a follow was indexed, so that time stands for the follow time.
+    pub async fn reindex(user_id: &str) -> ModelResult<()> {
+        let rows = fetch_all_rows_from_graph(queries::get::get_user_followed_at(user_id)).await?;
+
+        let mut followers = Vec::with_capacity(rows.len());
+        for row in rows {
+            let follower_id: String = row.get("follower_id")?;
+            let followed_at: Option<i64> = row.get("followed_at")?;
+            followers.push((followed_at.unwrap_or_default() as f64, follower_id));
+        }
+        if followers.is_empty() {
+            return Ok(());
+        }
+
+        let key_parts = [&USER_FOLLOWED_AT_KEY_PARTS[..], &[user_id]].concat();
+        let elements: Vec<(f64, &str)> = followers
+            .iter()
+            .map(|(followed_at, follower_id)| (*followed_at, follower_id.as_str()))
+            .collect();
+        Self::put_index_sorted_set(&key_parts, &elements, None, None).await?;
+        Ok(())
+    }
+
+    /// Counts the followers `user_id` gained within `timeframe`
+    pub async fn followers_gained_since(user_id: &str, timeframe: &Timeframe) -> RedisResult<Self> {
+        let key_parts = [&USER_FOLLOWED_AT_KEY_PARTS[..], &[user_id]].concat();
+        let (start, end) = timeframe.to_timestamp_range();
+        let followers_gained =
+            Self::count_index_sorted_set(&key_parts, Some(start as f64), Some(end as f64)).await?;
+        Ok(Self { followers_gained })
+    }
+}
@@ -3,9 +3,42 @@ use crate::db::kv::RedisResult;
 use crate::db::{
     execute_graph_operation, fetch_row_from_graph, queries, GraphResult, OperationOutcome, RedisOps,
 };
-use crate::models::error::ModelResult;
+use crate::models::error::{ModelError, ModelResult};
 use async_trait::async_trait;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A page of (followers | following) IDs, sorted by ID
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
+pub struct FollowsPage {
+    /// IDs of the users in the page
+    pub user_ids: Vec<String>,
+    /// Opaque cursor to pass to retrieve the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl FollowsPage {
+    fn new(user_ids: Vec<String>, limit: usize) -> Self {
+        // The cursor encodes the last ID of a full page, the next page starts after it
+        let next_cursor = match user_ids.len() == limit {
+            true => user_ids.last().map(hex::encode),
+            false => None,
+        };
+        Self {
+            user_ids,
+            next_cursor,
+        }
+    }
+
+    /// Decodes the last ID seen from a cursor returned in [Self::next_cursor]
+    fn decode_cursor(cursor: &str) -> ModelResult<String> {
+        hex::decode(cursor)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| ModelError::Generic(format!("Invalid follows cursor: {cursor}")))
+    }
+}
 
 #[async_trait]
 pub trait UserFollows: Sized + RedisOps + AsRef<[String]> + Default {
@@ -61,6 +94,51 @@ pub trait UserFollows: Sized + RedisOps + AsRef<[String]> + Default {
         Ok(user_follows)
     }
 
+    /// Retrieves the page of `limit` connections that follows `cursor`, or the first page if `cursor` is `None`.
+    ///
+    /// Connections are sorted by ID and the opaque cursor marks the last one already seen, so pages
+    /// don't repeat or miss connections when the list changes between two calls, unlike `skip`.
+    /// Returns `None` if the user does not exist.
+    async fn get_page(
+        user_id: &str,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> ModelResult<Option<FollowsPage>> {
+        let after = cursor.map(FollowsPage::decode_cursor).transpose()?;
+        let prefix = Self::prefix().await;
+        let user_ids = match Self::try_from_index_sorted_set_after(
+            &[&prefix, user_id],
+            after.as_deref(),
+            limit,
+        )
+        .await?
+        {
+            Some(user_ids) => Some(user_ids),
+            None => Self::get_page_from_graph(user_id, after.as_deref(), limit).await?,
+        };
+        Ok(user_ids.map(|user_ids| FollowsPage::new(user_ids, limit)))
+    }
+
+    async fn get_page_from_graph(
+        user_id: &str,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> GraphResult<Option<Vec<String>>> {
+        let query = Self::get_page_query(user_id, cursor, limit);
+        let Some(row) = fetch_row_from_graph(query).await? else {
+            return Ok(None);
+        };
+
+        let user_exists: bool = row.get("user_exists").unwrap_or(false);
+        if !user_exists {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            row.get(Self::get_ids_field_name()).unwrap_or_default(),
+        ))
+    }
+
     async fn get_from_index(
         user_id: &str,
         skip: Option<usize>,
@@ -69,9 +147,28 @@ pub trait UserFollows: Sized + RedisOps + AsRef<[String]> + Default {
         Self::try_from_index_set(&[user_id], skip, limit, None).await
     }
 
+    /// Indexes the connections of a user, both in the set of their connections and in the sorted
+    /// set read by [Self::get_page], where all connections have the same score to be sorted by ID
     async fn put_to_index(&self, user_id: &str) -> RedisResult<()> {
         let user_list_ref: Vec<&str> = self.as_ref().iter().map(|id| id.as_str()).collect();
-        Self::put_index_set(&[user_id], &user_list_ref, None, None).await
+        Self::put_index_set(&[user_id], &user_list_ref, None, None).await?;
+        Self::put_to_page_index(user_id, &user_list_ref).await
+    }
+
+    async fn put_to_page_index(user_id: &str, user_ids: &[&str]) -> RedisResult<()> {
+        let prefix = Self::prefix().await;
+        let elements: Vec<(f64, &str)> = user_ids.iter().map(|id| (0.0, *id)).collect();
+        Self::put_index_sorted_set(&[&prefix, user_id], &elements, None, None).await
+    }
+
+    /// Copies the indexed set of connections of a user to the sorted set read by [Self::get_page]
+    async fn reindex_page(user_id: &str) -> RedisResult<()> {
+        let sets = Self::try_from_multiple_sets(&[user_id], None, None, None).await?;
+        if let Some(Some((user_ids, _, _))) = sets.first() {
+            let user_ids: Vec<&str> = user_ids.iter().map(String::as_str).collect();
+            Self::put_to_page_index(user_id, &user_ids).await?;
+        }
+        Ok(())
     }
 
     async fn reindex(user_id: &str) -> ModelResult<()> {
@@ -91,11 +188,16 @@ pub trait UserFollows: Sized + RedisOps + AsRef<[String]> + Default {
     }
 
     async fn del_from_index(&self, user_id: &str) -> RedisResult<()> {
-        self.remove_from_index_set(&[user_id]).await
+        self.remove_from_index_set(&[user_id]).await?;
+        let prefix = Self::prefix().await;
+        let user_list_ref: Vec<&str> = self.as_ref().iter().map(|id| id.as_str()).collect();
+        Self::remove_from_index_sorted_set(None, &[&prefix, user_id], &user_list_ref).await
     }
 
     fn get_query(user_id: &str, skip: Option<usize>, limit: Option<usize>) -> Query;
 
+    fn get_page_query(user_id: &str, after: Option<&str>, limit: usize) -> Query;
+
     fn get_ids_field_name() -> &'static str;
 
     // Checks whether user_a is (following | follower) of user_b
@@ -104,4 +206,23 @@ pub trait UserFollows: Sized + RedisOps + AsRef<[String]> + Default {
         let (_, follow) = Self::check_set_member(user_a_key_parts, user_b_id).await?;
         Ok(follow)
     }
+
+    /// Checks, for each of `user_ids`, whether it is a (following | follower) of `user_id`.
+    ///
+    /// Returns one flag per user, in the same order. If `user_id` does not exist, all flags are `false`.
+    async fn check_batch(user_id: &str, user_ids: &[&str]) -> ModelResult<Vec<bool>> {
+        if let Some(flags) = Self::check_set_members(&[user_id], user_ids).await? {
+            return Ok(flags);
+        }
+
+        // Index miss: load the connections from the graph, which also indexes them
+        let follows = Self::get_by_id(user_id, None, None)
+            .await?
+            .unwrap_or_default();
+        let connections: &[String] = follows.as_ref();
+        Ok(user_ids
+            .iter()
+            .map(|id| connections.iter().any(|connection| connection == id))
+            .collect())
+    }
 }
@@ -82,6 +82,18 @@ impl Homeserver {
         self.put_index_json(&[&self.id], None, None).await
     }
 
+    /// Retrieves the cursors of multiple homeservers from Redis in a single batched read.
+    ///
+    /// The cursors are returned in the order of `ids`, with `None` for the homeservers
+    /// missing from the index.
+    pub async fn get_cursors(ids: &[String]) -> RedisResult<Vec<Option<String>>> {
+        let homeservers = Self::mget(ids).await?;
+        Ok(homeservers
+            .into_iter()
+            .map(|maybe_hs| maybe_hs.map(|hs| hs.cursor))
+            .collect())
+    }
+
     pub async fn get_by_id(homeserver_id: PubkyId) -> ModelResult<Option<Homeserver>> {
         match Homeserver::get_from_index(&homeserver_id).await? {
             Some(hs) => Ok(Some(hs)),
@@ -200,6 +212,41 @@ mod tests {
         Ok(())
     }
 
+    #[tokio_shared_rt::test(shared)]
+    async fn test_get_cursors() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let first_id = PubkyId::try_from(&Keypair::random().public_key().to_z32())?;
+        let unknown_id = PubkyId::try_from(&Keypair::random().public_key().to_z32())?;
+        let second_id = PubkyId::try_from(&Keypair::random().public_key().to_z32())?;
+
+        Homeserver::try_from_cursor(first_id.clone(), "0000000000001")?
+            .put_to_index()
+            .await?;
+        Homeserver::try_from_cursor(second_id.clone(), "0000000000002")?
+            .put_to_index()
+            .await?;
+
+        let ids = vec![
+            second_id.to_string(),
+            unknown_id.to_string(),
+            first_id.to_string(),
+        ];
+        let cursors = Homeserver::get_cursors(&ids).await?;
+        assert_eq!(
+            cursors,
+            vec![
+                Some("0000000000002".to_string()),
+                None,
+                Some("0000000000001".to_string())
+            ]
+        );
+
+        assert!(Homeserver::get_cursors(&[]).await?.is_empty());
+
+        Ok(())
+    }
+
     #[tokio_shared_rt::test(shared)]
     async fn test_put_to_get_from_index() -> Result<(), DynError> {
         StackManager::setup(&StackConfig::default()).await?;
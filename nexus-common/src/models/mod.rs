@@ -1,12 +1,15 @@
+pub mod audit;
 pub mod bootstrap;
 pub mod error;
 pub mod event;
 pub mod file;
 pub mod follow;
 pub mod homeserver;
+pub mod moderation;
 pub mod notification;
 pub mod post;
 pub mod tag;
+pub mod tombstone;
 pub mod traits;
 pub mod user;
 
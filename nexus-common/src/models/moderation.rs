@@ -0,0 +1,254 @@
+use crate::db::kv::{RedisError, RedisResult, ScoreAction, SortOrder};
+use crate::db::RedisOps;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Why a post or user was removed from the index by the trusted moderator.
+///
+/// Kept so that moderation review UIs can show a placeholder for the removed content. Indexed
+/// by `[author_id, post_id]` for posts and `[user_id]` for users.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct ModerationInfo {
+    /// The moderator that removed the content
+    pub moderator_id: String,
+    /// Label of the moderation tag the moderator put on the content
+    pub reason: String,
+    /// Timestamp (ms) of the moderation
+    pub moderated_at: i64,
+}
+
+impl RedisOps for ModerationInfo {}
+
+impl ModerationInfo {
+    /// Records the moderation of the content identified by `key_parts`
+    pub async fn put_to_index(&self, key_parts: &[&str]) -> RedisResult<()> {
+        self.put_index_json(key_parts, None, None).await
+    }
+
+    /// Retrieves the moderation of the content identified by `key_parts`, if it was moderated
+    pub async fn get_from_index(key_parts: &[&str]) -> RedisResult<Option<Self>> {
+        Self::try_from_index_json(key_parts, None).await
+    }
+
+    /// Retrieves the moderation of the content identified by `key_parts`, only if the viewer is
+    /// the moderator that removed it. Other viewers don't learn that the content was moderated.
+    pub async fn get_for_viewer(
+        key_parts: &[&str],
+        viewer_id: Option<&str>,
+    ) -> RedisResult<Option<Self>> {
+        let Some(viewer_id) = viewer_id else {
+            return Ok(None);
+        };
+        Ok(Self::get_from_index(key_parts)
+            .await?
+            .filter(|moderation| moderation.moderator_id == viewer_id))
+    }
+}
+
+pub const MODERATION_ACTIONS: [&str; 2] = ["Moderation", "Actions"];
+pub const MODERATION_TAG_COUNTS: [&str; 2] = ["Moderation", "Tags"];
+
+/// Audit entry of a moderation action of the trusted moderator, kept so that operators can see
+/// what is being filtered and why.
+///
+/// The entries are indexed in a sorted set by `moderated_at`, and the number of actions per
+/// moderation tag in another one.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct ModerationAction {
+    /// URI of the moderated content (post, user, tag or file)
+    pub uri: String,
+    /// Label of the moderation tag that matched
+    pub tag: String,
+    /// The moderator that tagged the content
+    pub moderator_id: String,
+    /// Timestamp (ms) of the moderation
+    pub moderated_at: i64,
+}
+
+impl RedisOps for ModerationAction {}
+
+impl ModerationAction {
+    /// Records the action in the audit log and counts it for its moderation tag. The log then
+    /// keeps the `max_entries` most recent actions, moderated in the last `max_age_secs`. A bound
+    /// of 0 disables it
+    pub async fn put_to_index(&self, max_entries: usize, max_age_secs: u64) -> RedisResult<()> {
+        self.put_to_log(&MODERATION_ACTIONS).await?;
+        Self::trim_log(&MODERATION_ACTIONS, max_entries, max_age_secs).await?;
+        Self::put_score_index_sorted_set(
+            &MODERATION_TAG_COUNTS,
+            &[&self.tag],
+            ScoreAction::Increment(1.0),
+        )
+        .await
+    }
+
+    /// Adds the action to the log sorted set of `key_parts`
+    async fn put_to_log(&self, key_parts: &[&str]) -> RedisResult<()> {
+        let member = serde_json::to_string(self)
+            .map_err(|e| RedisError::SerializationFailed(Box::new(e)))?;
+        Self::put_index_sorted_set(
+            key_parts,
+            &[(self.moderated_at as f64, &member)],
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Keeps the `max_entries` most recent actions of a log, moderated in the last `max_age_secs`.
+    /// A bound of 0 disables it
+    async fn trim_log(
+        key_parts: &[&str],
+        max_entries: usize,
+        max_age_secs: u64,
+    ) -> RedisResult<()> {
+        let max_len = (max_entries > 0).then_some(max_entries);
+        let min_score = (max_age_secs > 0).then(|| {
+            let max_age_ms = i64::try_from(max_age_secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+            Utc::now().timestamp_millis().saturating_sub(max_age_ms) as f64
+        });
+        if max_len.is_none() && min_score.is_none() {
+            return Ok(());
+        }
+        Self::trim_index_sorted_set(key_parts, max_len, min_score).await
+    }
+
+    /// Retrieves the recorded actions, most recent first
+    pub async fn get_recent(skip: usize, limit: usize) -> RedisResult<Vec<Self>> {
+        let members = Self::try_from_index_sorted_set(
+            &MODERATION_ACTIONS,
+            None,
+            None,
+            Some(skip),
+            Some(limit),
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+
+        members
+            .into_iter()
+            .map(|(member, _)| {
+                serde_json::from_str(&member)
+                    .map_err(|e| RedisError::DeserializationFailed(Box::new(e)))
+            })
+            .collect()
+    }
+
+    /// Retrieves the number of recorded actions per moderation tag, most used first
+    pub async fn get_tag_counts() -> RedisResult<Vec<(String, u64)>> {
+        let counts = Self::try_from_index_sorted_set(
+            &MODERATION_TAG_COUNTS,
+            None,
+            None,
+            None,
+            None,
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+        Ok(counts
+            .into_iter()
+            .map(|(tag, count)| (tag, count as u64))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::DynError, StackConfig, StackManager};
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_moderation_only_visible_to_moderator() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let key_parts = ["moderation_test_author", "0033RCZXVEPNG"];
+        let moderation = ModerationInfo {
+            moderator_id: "moderation_test_moderator".to_string(),
+            reason: "hatespeech".to_string(),
+            moderated_at: Utc::now().timestamp_millis(),
+        };
+        moderation.put_to_index(&key_parts).await?;
+
+        let found =
+            ModerationInfo::get_for_viewer(&key_parts, Some("moderation_test_moderator")).await?;
+        assert_eq!(found, Some(moderation));
+
+        let found = ModerationInfo::get_for_viewer(&key_parts, Some("someone_else")).await?;
+        assert!(found.is_none());
+
+        let found = ModerationInfo::get_for_viewer(&key_parts, None).await?;
+        assert!(found.is_none());
+
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_moderation_log_retention() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let key_parts = ["Moderation", "ActionsRetentionTest"];
+        // Start from an empty log
+        ModerationAction::trim_index_sorted_set(&key_parts, Some(0), None).await?;
+
+        let now = Utc::now().timestamp_millis();
+        let action = |uri: &str, age_secs: i64| ModerationAction {
+            uri: uri.to_string(),
+            tag: "hatespeech".to_string(),
+            moderator_id: "moderation_test_moderator".to_string(),
+            moderated_at: now - age_secs * 1000,
+        };
+        let actions = [
+            action("expired", 120),
+            action("oldest", 50),
+            action("older", 40),
+            action("recent", 30),
+            action("newest", 20),
+        ];
+        for action in &actions {
+            action.put_to_log(&key_parts).await?;
+        }
+
+        // Disabled bounds keep every action
+        ModerationAction::trim_log(&key_parts, 0, 0).await?;
+        assert_eq!(logged_uris(&key_parts).await?.len(), 5);
+
+        // The actions older than the max age are removed
+        ModerationAction::trim_log(&key_parts, 0, 60).await?;
+        assert_eq!(
+            logged_uris(&key_parts).await?,
+            vec!["newest", "recent", "older", "oldest"]
+        );
+
+        // Then only the most recent ones are kept
+        ModerationAction::trim_log(&key_parts, 2, 60).await?;
+        assert_eq!(logged_uris(&key_parts).await?, vec!["newest", "recent"]);
+
+        Ok(())
+    }
+
+    /// URIs of the actions of a log, most recent first
+    async fn logged_uris(key_parts: &[&str]) -> Result<Vec<String>, DynError> {
+        let logged = ModerationAction::try_from_index_sorted_set(
+            key_parts,
+            None,
+            None,
+            None,
+            None,
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+        let mut uris = Vec::with_capacity(logged.len());
+        for (member, _) in logged {
+            uris.push(serde_json::from_str::<ModerationAction>(&member)?.uri);
+        }
+        Ok(uris)
+    }
+}
@@ -6,8 +6,15 @@ use chrono::Utc;
 use neo4rs::Row;
 use pubky_app_specs::{bookmark_uri_builder, post_uri_builder, tag_uri_builder, PubkyId};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use utoipa::ToSchema;
 
+mod preferences;
+mod webhook;
+pub use preferences::{DoNotDisturb, NotificationPreferences, NotificationType, MINUTES_PER_DAY};
+pub use webhook::{check_webhook_url, NotificationWebhook, RECIPIENT_HEADER, SIGNATURE_HEADER};
+
 #[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum PostChangedSource {
@@ -24,10 +31,17 @@ pub enum PostChangedType {
     Deleted,
 }
 
+/// A notification of a user
 #[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
 pub struct Notification {
+    /// Identifies the notification among those of the user: its timestamp and a digest of its body
+    #[serde(default)]
+    pub id: String,
     pub timestamp: i64,
     pub body: NotificationBody,
+    /// Whether the user marked the notification as read
+    #[serde(default)]
+    pub read: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, ToSchema, Debug)]
@@ -101,63 +115,296 @@ impl Default for NotificationBody {
 
 impl RedisOps for Notification {}
 
+const NOTIFICATION_KEY_PART: &str = "Notification";
+/// Notifications of a user of one type, with the same members and scores as their notifications
+const NOTIFICATION_TYPE_KEY_PART: &str = "Type";
+/// Number of notifications read at once while indexing them by type
+const NOTIFICATION_REINDEX_BATCH_SIZE: usize = 1000;
+/// Notifications a user has read, with the same members and scores as their notifications
+const NOTIFICATION_READ_KEY_PARTS: [&str; 2] = ["Notification", "Read"];
+
+/// Identifies a notification by its timestamp and a digest of its sorted set member, so that two
+/// notifications created in the same millisecond are told apart
+fn notification_id(timestamp: i64, member: &str) -> String {
+    let digest = Sha256::digest(member.as_bytes());
+    format!("{timestamp}:{}", hex::encode(&digest[..8]))
+}
+
+/// Key parts of the notifications of a user of one type
+fn type_key_parts(user_id: &str, notification_type: NotificationType) -> [&str; 4] {
+    [
+        NOTIFICATION_KEY_PART,
+        NOTIFICATION_TYPE_KEY_PART,
+        user_id,
+        notification_type.as_str(),
+    ]
+}
+
+/// Timestamp of a notification, read from its [notification_id]
+fn notification_id_timestamp(id: &str) -> Option<i64> {
+    id.split_once(':')?.0.parse().ok()
+}
+
 impl Notification {
     pub fn new(body: NotificationBody) -> Self {
         Self {
+            id: String::new(),
             body,
             timestamp: Utc::now().timestamp_millis(), //milliseconds to avoid sub second collision
+            read: false,
         }
     }
 
     /// Stores the `NotificationBody` in the sorted set for the user using the timestamp as the score.
+    ///
+    /// Nothing is stored if the [NotificationPreferences] of the user do not accept it. A stored
+    /// notification is then queued for delivery to the [NotificationWebhook], if enabled.
     async fn put_to_index(&self, user_id: &str) -> RedisResult<()> {
+        let preferences = NotificationPreferences::get_by_id(user_id).await?;
+        if !preferences.accepts(&self.body, self.timestamp) {
+            return Ok(());
+        }
+
         let notification_body_json = serde_json::to_string(&self.body)
             .map_err(|e| RedisError::SerializationFailed(Box::new(e)))?;
         let score = self.timestamp as f64;
 
+        // A repeated notification replaces the previous one, which the user may have read
+        let key_parts = [NOTIFICATION_KEY_PART, user_id];
+        let read_key_parts = [&NOTIFICATION_READ_KEY_PARTS[..], &[user_id]].concat();
+        Self::remove_from_index_sorted_set(None, &read_key_parts, &[&notification_body_json])
+            .await?;
+
+        let elements = [(score, notification_body_json.as_str())];
+        Notification::put_index_sorted_set(&key_parts, &elements, None, None).await?;
         Notification::put_index_sorted_set(
-            &["Notification", user_id],
-            &[(score, notification_body_json.as_str())],
+            &type_key_parts(user_id, self.body.notification_type()),
+            &elements,
             None,
             None,
         )
-        .await
+        .await?;
+
+        // The webhook receives the id the user marks the notification as read with
+        let notification = Notification {
+            id: notification_id(self.timestamp, &notification_body_json),
+            body: self.body.clone(),
+            timestamp: self.timestamp,
+            read: false,
+        };
+        NotificationWebhook::dispatch(user_id, &notification, preferences.webhook_url.as_deref());
+        Ok(())
     }
 
-    /// Lists notifications from the sorted set for the user, based on skip and limit, or timestamp range.
+    /// Marks the notifications of a user with the given ids as read. Unknown ids are ignored.
+    ///
+    /// Returns the number of notifications marked as read.
+    pub async fn mark_read(user_id: &str, ids: &[String]) -> RedisResult<usize> {
+        let key_parts = [NOTIFICATION_KEY_PART, user_id];
+        let mut read: Vec<(f64, String)> = Vec::with_capacity(ids.len());
+        for id in ids {
+            let Some(timestamp) = notification_id_timestamp(id) else {
+                continue;
+            };
+            // Only the notifications of that millisecond are read to find the one with the id
+            let score = timestamp as f64;
+            let notifications = Self::try_from_index_sorted_set(
+                &key_parts,
+                Some(score),
+                Some(score),
+                None,
+                None,
+                SortOrder::Ascending,
+                None,
+            )
+            .await?
+            .unwrap_or_default();
+            let member = notifications
+                .into_iter()
+                .map(|(member, _)| member)
+                .find(|member| notification_id(timestamp, member) == *id);
+            if let Some(member) = member {
+                if !read.iter().any(|(_, read_member)| *read_member == member) {
+                    read.push((score, member));
+                }
+            }
+        }
+        Self::put_read(user_id, &read).await?;
+        Ok(read.len())
+    }
+
+    /// Marks all the notifications of a user as read, copying their sorted set into the read one
+    /// without reading the notifications.
+    ///
+    /// Returns the number of notifications of the user.
+    pub async fn mark_all_read(user_id: &str) -> RedisResult<usize> {
+        let read_key_parts = [&NOTIFICATION_READ_KEY_PARTS[..], &[user_id]].concat();
+        Self::union_index_sorted_sets(&read_key_parts, &[&[NOTIFICATION_KEY_PART, user_id]]).await
+    }
+
+    /// Counts the notifications of a user that are not marked as read.
+    ///
+    /// A notification is read when its member is in the read set, as for the `read` flag of the
+    /// listed notifications, so read entries left by notifications that no longer exist are not
+    /// counted.
+    pub async fn unread_count(user_id: &str) -> RedisResult<usize> {
+        let read_key_parts = [&NOTIFICATION_READ_KEY_PARTS[..], &[user_id]].concat();
+        Self::count_index_sorted_set_difference(&[NOTIFICATION_KEY_PART, user_id], &read_key_parts)
+            .await
+    }
+
+    /// Adds the `(score, member)` pairs of notifications to the read notifications of a user
+    async fn put_read(user_id: &str, notifications: &[(f64, String)]) -> RedisResult<()> {
+        if notifications.is_empty() {
+            return Ok(());
+        }
+        let elements: Vec<(f64, &str)> = notifications
+            .iter()
+            .map(|(score, member)| (*score, member.as_str()))
+            .collect();
+        let read_key_parts = [&NOTIFICATION_READ_KEY_PARTS[..], &[user_id]].concat();
+        Self::put_index_sorted_set(&read_key_parts, &elements, None, None).await
+    }
+
+    /// Retrieves the ids of the read notifications with a timestamp between `min` and `max`
+    async fn get_read_ids(user_id: &str, min: f64, max: f64) -> RedisResult<HashSet<String>> {
+        let read_key_parts = [&NOTIFICATION_READ_KEY_PARTS[..], &[user_id]].concat();
+        let read = Self::try_from_index_sorted_set(
+            &read_key_parts,
+            Some(max),
+            Some(min),
+            None,
+            None,
+            SortOrder::Ascending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+        Ok(read
+            .into_iter()
+            .map(|(member, score)| notification_id(score as i64, &member))
+            .collect())
+    }
+
+    /// Lists notifications from the sorted set for the user, newest first, based on skip and limit, or timestamp range.
     pub async fn get_by_id(user_id: &str, pagination: Pagination) -> RedisResult<Vec<Self>> {
+        Self::get_by_id_in_order(user_id, pagination, SortOrder::Descending, None).await
+    }
+
+    /// Lists notifications from the sorted set for the user in the given order of timestamp.
+    ///
+    /// The timeframe follows the reading direction: newest first, `start` is the latest timestamp
+    /// and `end` the earliest. Oldest first, `start` is the earliest timestamp and `end` the latest.
+    ///
+    /// When `types` is set, only the notifications of those types are listed, and skip and limit
+    /// apply to them.
+    pub async fn get_by_id_in_order(
+        user_id: &str,
+        pagination: Pagination,
+        order: SortOrder,
+        types: Option<&[NotificationType]>,
+    ) -> RedisResult<Vec<Self>> {
         // Set the default params for pagination
         let skip = pagination.skip.unwrap_or(0);
         let limit = pagination.limit.unwrap_or(20);
 
+        // The sorted set range takes the upper bound first, then the lower bound
+        let (upper, lower) = match order {
+            SortOrder::Descending => (pagination.start, pagination.end),
+            SortOrder::Ascending => (pagination.end, pagination.start),
+        };
+
+        let mut result = match types {
+            None => {
+                Self::get_range(
+                    &[NOTIFICATION_KEY_PART, user_id],
+                    upper,
+                    lower,
+                    skip,
+                    limit,
+                    order,
+                )
+                .await?
+            }
+            // Each type has its own sorted set, so the page is found among the first `skip + limit`
+            // notifications of every requested type
+            Some(types) => {
+                let mut types = types.to_vec();
+                types.sort();
+                types.dedup();
+                let mut result = Vec::new();
+                for notification_type in types {
+                    let notifications = Self::get_range(
+                        &type_key_parts(user_id, notification_type),
+                        upper,
+                        lower,
+                        0,
+                        skip + limit,
+                        order.clone(),
+                    )
+                    .await?;
+                    result.extend(notifications);
+                }
+                match order {
+                    SortOrder::Descending => result.sort_by(|a, b| b.timestamp.cmp(&a.timestamp)),
+                    SortOrder::Ascending => {
+                        result.sort_by_key(|notification| notification.timestamp)
+                    }
+                }
+                result.into_iter().skip(skip).take(limit).collect()
+            }
+        };
+
+        // Flag the read notifications of the page
+        let timestamps = result.iter().map(|notification| notification.timestamp);
+        if let (Some(min), Some(max)) = (timestamps.clone().min(), timestamps.max()) {
+            let read = Self::get_read_ids(user_id, min as f64, max as f64).await?;
+            for notification in result.iter_mut() {
+                notification.read = read.contains(&notification.id);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Reads a range of the notifications sorted set at `key_parts`, skipping the ones that cannot
+    /// be deserialized
+    async fn get_range(
+        key_parts: &[&str],
+        upper: Option<f64>,
+        lower: Option<f64>,
+        skip: usize,
+        limit: usize,
+        order: SortOrder,
+    ) -> RedisResult<Vec<Self>> {
         let notifications = Notification::try_from_index_sorted_set(
-            &["Notification", user_id],
-            pagination.start,
-            pagination.end,
+            key_parts,
+            upper,
+            lower,
             Some(skip),
             Some(limit),
-            SortOrder::Descending, // StreamSorting in descending order by score (timestamp)
+            order,
             None,
         )
-        .await?;
+        .await?
+        .unwrap_or_default();
 
         let mut result = Vec::new();
-
-        if let Some(notifications) = notifications {
-            for (notification_body_str, score) in notifications {
-                match serde_json::from_str::<NotificationBody>(&notification_body_str) {
-                    Ok(body) => {
-                        let notification = Notification {
-                            timestamp: score as i64,
-                            body,
-                        };
-                        result.push(notification);
-                    }
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to deserialize notification, body: {notification_body_str}, reason: {e}"
-                        );
-                    }
+        for (notification_body_str, score) in notifications {
+            match serde_json::from_str::<NotificationBody>(&notification_body_str) {
+                Ok(body) => {
+                    let notification = Notification {
+                        id: notification_id(score as i64, &notification_body_str),
+                        timestamp: score as i64,
+                        body,
+                        read: false,
+                    };
+                    result.push(notification);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to deserialize notification, body: {notification_body_str}, reason: {e}"
+                    );
                 }
             }
         }
@@ -165,6 +412,52 @@ impl Notification {
         Ok(result)
     }
 
+    /// Indexes the notifications of a user by type, so that they can be listed by type without
+    /// reading the others
+    pub async fn reindex_types(user_id: &str) -> RedisResult<()> {
+        let mut offset = 0;
+        loop {
+            let notifications = Self::try_from_index_sorted_set(
+                &[NOTIFICATION_KEY_PART, user_id],
+                None,
+                None,
+                Some(offset),
+                Some(NOTIFICATION_REINDEX_BATCH_SIZE),
+                SortOrder::Ascending,
+                None,
+            )
+            .await?
+            .unwrap_or_default();
+
+            let mut by_type: HashMap<NotificationType, Vec<(f64, &str)>> = HashMap::new();
+            for (member, score) in &notifications {
+                match serde_json::from_str::<NotificationBody>(member) {
+                    Ok(body) => by_type
+                        .entry(body.notification_type())
+                        .or_default()
+                        .push((*score, member.as_str())),
+                    Err(e) => tracing::warn!(
+                        "Failed to deserialize notification, body: {member}, reason: {e}"
+                    ),
+                }
+            }
+            for (notification_type, elements) in by_type {
+                Self::put_index_sorted_set(
+                    &type_key_parts(user_id, notification_type),
+                    &elements,
+                    None,
+                    None,
+                )
+                .await?;
+            }
+
+            if notifications.len() < NOTIFICATION_REINDEX_BATCH_SIZE {
+                return Ok(());
+            }
+            offset += NOTIFICATION_REINDEX_BATCH_SIZE;
+        }
+    }
+
     pub async fn new_follow(user_id: &str, followee_id: &str, new_friend: bool) -> RedisResult<()> {
         let body = match new_friend {
             true => NotificationBody::NewFriend {
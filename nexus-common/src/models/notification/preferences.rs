@@ -0,0 +1,242 @@
+use crate::db::kv::RedisResult;
+use crate::db::RedisOps;
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+use super::NotificationBody;
+
+/// Number of minutes in a day, the exclusive upper bound of [DoNotDisturb] minutes
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Kind of a [NotificationBody], used to enable or disable notifications per type
+#[derive(
+    Serialize, Deserialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    Follow,
+    NewFriend,
+    LostFriend,
+    TagPost,
+    TagProfile,
+    UntagPost,
+    UntagProfile,
+    Reply,
+    Repost,
+    Mention,
+    PostDeleted,
+    PostEdited,
+}
+
+impl NotificationType {
+    /// Name of the type, as serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationType::Follow => "follow",
+            NotificationType::NewFriend => "new_friend",
+            NotificationType::LostFriend => "lost_friend",
+            NotificationType::TagPost => "tag_post",
+            NotificationType::TagProfile => "tag_profile",
+            NotificationType::UntagPost => "untag_post",
+            NotificationType::UntagProfile => "untag_profile",
+            NotificationType::Reply => "reply",
+            NotificationType::Repost => "repost",
+            NotificationType::Mention => "mention",
+            NotificationType::PostDeleted => "post_deleted",
+            NotificationType::PostEdited => "post_edited",
+        }
+    }
+}
+
+impl NotificationBody {
+    pub fn notification_type(&self) -> NotificationType {
+        match self {
+            NotificationBody::Follow { .. } => NotificationType::Follow,
+            NotificationBody::NewFriend { .. } => NotificationType::NewFriend,
+            NotificationBody::LostFriend { .. } => NotificationType::LostFriend,
+            NotificationBody::TagPost { .. } => NotificationType::TagPost,
+            NotificationBody::TagProfile { .. } => NotificationType::TagProfile,
+            NotificationBody::UntagPost { .. } => NotificationType::UntagPost,
+            NotificationBody::UntagProfile { .. } => NotificationType::UntagProfile,
+            NotificationBody::Reply { .. } => NotificationType::Reply,
+            NotificationBody::Repost { .. } => NotificationType::Repost,
+            NotificationBody::Mention { .. } => NotificationType::Mention,
+            NotificationBody::PostDeleted { .. } => NotificationType::PostDeleted,
+            NotificationBody::PostEdited { .. } => NotificationType::PostEdited,
+        }
+    }
+}
+
+/// Daily window, in UTC minutes since midnight, during which no notification is stored.
+///
+/// The window can wrap around midnight, e.g. `start_minute = 1320` (22:00) to
+/// `end_minute = 420` (07:00). `start_minute` is included and `end_minute` is excluded.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DoNotDisturb {
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+impl DoNotDisturb {
+    /// Whether both bounds are valid minutes of a day
+    pub fn is_valid(&self) -> bool {
+        self.start_minute < MINUTES_PER_DAY && self.end_minute < MINUTES_PER_DAY
+    }
+
+    /// Whether `minute` (UTC minutes since midnight) falls into the window
+    pub fn contains(&self, minute: u16) -> bool {
+        match self.start_minute <= self.end_minute {
+            true => self.start_minute <= minute && minute < self.end_minute,
+            // The window wraps around midnight
+            false => minute >= self.start_minute || minute < self.end_minute,
+        }
+    }
+}
+
+/// Per-user notification settings, consulted before a notification is stored for the user.
+///
+/// Types missing from `types` are enabled, so users without preferences get every notification.
+#[derive(Serialize, Deserialize, ToSchema, Default, Clone, Debug, PartialEq)]
+pub struct NotificationPreferences {
+    /// Notification type to whether it is enabled
+    #[serde(default)]
+    pub types: BTreeMap<NotificationType, bool>,
+    #[serde(default)]
+    pub do_not_disturb: Option<DoNotDisturb>,
+    /// HTTPS URL the new notifications of the user are POSTed to, when the notification webhook is
+    /// enabled, see [NotificationWebhook](super::NotificationWebhook). Local and private hosts are
+    /// rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+impl RedisOps for NotificationPreferences {}
+
+impl NotificationPreferences {
+    /// Retrieves the preferences of a user, or the defaults if the user has not set any
+    pub async fn get_by_id(user_id: &str) -> RedisResult<Self> {
+        Ok(Self::try_from_index_json(&[user_id], None)
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Stores the preferences of a user
+    pub async fn put_to_index(&self, user_id: &str) -> RedisResult<()> {
+        self.put_index_json(&[user_id], None, None).await
+    }
+
+    pub fn is_enabled(&self, notification_type: NotificationType) -> bool {
+        self.types.get(&notification_type).copied().unwrap_or(true)
+    }
+
+    /// Whether a notification created at `timestamp` (ms) should be stored for the user
+    pub fn accepts(&self, body: &NotificationBody, timestamp: i64) -> bool {
+        if !self.is_enabled(body.notification_type()) {
+            return false;
+        }
+        let Some(dnd) = self.do_not_disturb else {
+            return true;
+        };
+        let Some(time) = DateTime::<Utc>::from_timestamp_millis(timestamp) else {
+            return true;
+        };
+        let minute = (time.hour() * 60 + time.minute()) as u16;
+        !dnd.contains(minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::notification::Notification;
+    use crate::types::{DynError, Pagination};
+    use crate::{StackConfig, StackManager};
+    use pubky::Keypair;
+
+    #[test]
+    fn test_do_not_disturb_contains() {
+        let day = DoNotDisturb {
+            start_minute: 540,
+            end_minute: 1020,
+        };
+        assert!(!day.contains(539));
+        assert!(day.contains(540));
+        assert!(day.contains(1019));
+        assert!(!day.contains(1020));
+
+        let night = DoNotDisturb {
+            start_minute: 1320,
+            end_minute: 420,
+        };
+        assert!(night.contains(1320));
+        assert!(night.contains(0));
+        assert!(night.contains(419));
+        assert!(!night.contains(420));
+        assert!(!night.contains(1319));
+    }
+
+    #[test]
+    fn test_preferences_accepts() {
+        let follow = NotificationBody::Follow {
+            followed_by: "user".to_string(),
+        };
+        let mention = NotificationBody::Mention {
+            mentioned_by: "user".to_string(),
+            post_uri: "pubky://user/pub/pubky.app/posts/0000000000000".to_string(),
+        };
+        // 1970-01-01 23:00 UTC
+        let at_night = 23 * 60 * 60 * 1000;
+        // 1970-01-01 12:00 UTC
+        let at_noon = 12 * 60 * 60 * 1000;
+
+        let defaults = NotificationPreferences::default();
+        assert!(defaults.accepts(&follow, at_night));
+        assert!(defaults.accepts(&mention, at_night));
+
+        let preferences = NotificationPreferences {
+            types: BTreeMap::from([(NotificationType::Follow, false)]),
+            do_not_disturb: Some(DoNotDisturb {
+                start_minute: 1320,
+                end_minute: 420,
+            }),
+            webhook_url: None,
+        };
+        assert!(!preferences.accepts(&follow, at_noon));
+        assert!(preferences.accepts(&mention, at_noon));
+        assert!(!preferences.accepts(&mention, at_night));
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_disabled_type_is_not_stored() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let user_id = Keypair::random().public_key().to_z32();
+        let follower_id = Keypair::random().public_key().to_z32();
+
+        let preferences = NotificationPreferences {
+            types: BTreeMap::from([(NotificationType::Follow, false)]),
+            do_not_disturb: None,
+            webhook_url: None,
+        };
+        preferences.put_to_index(&user_id).await?;
+        assert_eq!(
+            NotificationPreferences::get_by_id(&user_id).await?,
+            preferences
+        );
+
+        // Follows are disabled, new friends are not
+        Notification::new_follow(&follower_id, &user_id, false).await?;
+        Notification::new_follow(&follower_id, &user_id, true).await?;
+
+        let notifications = Notification::get_by_id(&user_id, Pagination::default()).await?;
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(
+            notifications[0].body.notification_type(),
+            NotificationType::NewFriend
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,481 @@
+use super::Notification;
+use crate::config::NotificationWebhookConfig;
+use hmac::{Hmac, Mac};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, Semaphore};
+use tracing::{debug, warn};
+
+/// Header carrying the signature of the body, `sha256=` followed by the hex encoded HMAC-SHA256
+/// of the body keyed with the configured secret
+pub const SIGNATURE_HEADER: &str = "X-Nexus-Signature";
+/// Header carrying the ID of the user the notification is for
+pub const RECIPIENT_HEADER: &str = "X-Nexus-Recipient";
+
+/// Upper bound on the time taken by a single delivery attempt
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum number of delivery attempts in flight at once, across all destinations
+const MAX_CONCURRENT_DELIVERIES: usize = 64;
+/// Time after which the queue of a destination without new notifications is dropped
+const DESTINATION_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+static WEBHOOK: OnceLock<NotificationWebhook> = OnceLock::new();
+
+struct WebhookDelivery {
+    url: String,
+    /// Whether the URL was registered by the user, rather than configured by the operator
+    user_url: bool,
+    recipient: String,
+    body: String,
+}
+
+/// Best-effort delivery of the new notifications to an outbound webhook.
+///
+/// Notifications are queued without waiting, so that event processing is never blocked. Each
+/// destination host then has its own queue, delivered in order with retries and backoff, so that
+/// a slow or failing URL only delays its own notifications.
+///
+/// The URLs registered by users must pass [check_webhook_url] and resolve to public addresses
+/// only, so that they cannot reach the internal network of the operator.
+pub struct NotificationWebhook {
+    global_url: Option<String>,
+    sender: mpsc::Sender<WebhookDelivery>,
+}
+
+impl NotificationWebhook {
+    /// Starts delivering the new notifications. Only the first call takes effect
+    pub fn start(config: NotificationWebhookConfig) {
+        if WEBHOOK.get().is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel(config.queue_size.max(1));
+        let webhook = NotificationWebhook {
+            global_url: config.url.clone(),
+            sender,
+        };
+        if WEBHOOK.set(webhook).is_ok() {
+            tokio::spawn(deliver_all(receiver, config));
+        }
+    }
+
+    /// Queues the delivery of a notification to the URL registered by the recipient, or else to
+    /// the global URL. The notification is dropped if the queue is full
+    pub(crate) fn dispatch(recipient: &str, notification: &Notification, user_url: Option<&str>) {
+        let Some(webhook) = WEBHOOK.get() else {
+            return;
+        };
+        let (url, user_url) = match (user_url, webhook.global_url.as_deref()) {
+            (Some(url), _) => (url, true),
+            (None, Some(url)) => (url, false),
+            (None, None) => return,
+        };
+        let body = match serde_json::to_string(notification) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Failed to serialize the webhook notification for {recipient}: {e}");
+                return;
+            }
+        };
+
+        let delivery = WebhookDelivery {
+            url: url.to_string(),
+            user_url,
+            recipient: recipient.to_string(),
+            body,
+        };
+        if let Err(e) = webhook.sender.try_send(delivery) {
+            warn!("Dropped the webhook notification for {recipient}: {e}");
+        }
+    }
+}
+
+/// Checks that a webhook URL registered by a user is an HTTPS URL whose host is not local.
+///
+/// A domain is only known to be public once resolved, which happens on each delivery.
+pub fn check_webhook_url(url: &str) -> Result<Url, String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid webhook URL: {e}"))?;
+    if url.scheme() != "https" {
+        return Err("The webhook URL must be an HTTPS URL".to_string());
+    }
+    let Some(host) = url.host_str() else {
+        return Err("The webhook URL must have a host".to_string());
+    };
+    // IPv6 hosts are written within brackets
+    let is_public = match host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        Ok(ip) => is_public_ip(ip),
+        Err(_) => {
+            let domain = host.trim_end_matches('.').to_ascii_lowercase();
+            domain != "localhost" && !domain.ends_with(".localhost")
+        }
+    };
+    match is_public {
+        true => Ok(url),
+        false => Err("The webhook URL must not target a local or private host".to_string()),
+    }
+}
+
+/// Whether an address is reachable on the public internet, as opposed to loopback, private,
+/// link-local and other reserved ranges
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // 100.64.0.0/10 is the shared address space of carrier-grade NATs
+    let is_shared = a == 100 && (b & 0xc0) == 64;
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || is_shared
+        || a == 0)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    // fc00::/7 holds the unique local addresses and fe80::/10 the link-local ones
+    let is_unique_local = (first & 0xfe00) == 0xfc00;
+    let is_link_local = (first & 0xffc0) == 0xfe80;
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || is_unique_local
+        || is_link_local)
+}
+
+/// Resolves the hosts of the user registered URLs to their public addresses only.
+///
+/// The client connects to the addresses returned here, so a domain cannot resolve to a public
+/// address when checked and to a private one when connected to.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{host} does not resolve to a public address").into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Signature of a body, as sent in the [SIGNATURE_HEADER]
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// What the delivery tasks of all destinations share
+struct Deliverer {
+    config: NotificationWebhookConfig,
+    /// Client of the global URL, configured by the operator
+    client: reqwest::Client,
+    /// Client of the URLs registered by users, restricted to public addresses
+    user_client: reqwest::Client,
+    in_flight: Semaphore,
+}
+
+/// Routes each queued notification to the queue of its destination host
+async fn deliver_all(
+    mut receiver: mpsc::Receiver<WebhookDelivery>,
+    config: NotificationWebhookConfig,
+) {
+    // Redirects are not followed, they could lead a user registered URL to a private host
+    let builder = || {
+        reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(Policy::none())
+    };
+    let clients = builder().build().and_then(|client| {
+        let user_client = builder().dns_resolver(Arc::new(PublicResolver)).build()?;
+        Ok((client, user_client))
+    });
+    let (client, user_client) = match clients {
+        Ok(clients) => clients,
+        Err(e) => {
+            warn!("Failed to build the notification webhook client, webhook disabled: {e}");
+            return;
+        }
+    };
+    let deliverer = Arc::new(Deliverer {
+        config,
+        client,
+        user_client,
+        in_flight: Semaphore::new(MAX_CONCURRENT_DELIVERIES),
+    });
+
+    let mut destinations: HashMap<String, mpsc::Sender<WebhookDelivery>> = HashMap::new();
+    while let Some(delivery) = receiver.recv().await {
+        destinations.retain(|_, sender| !sender.is_closed());
+        let destination = Url::parse(&delivery.url)
+            .ok()
+            .and_then(|url| {
+                Some(format!(
+                    "{}:{}",
+                    url.host_str()?,
+                    url.port_or_known_default()?
+                ))
+            })
+            .unwrap_or_else(|| delivery.url.clone());
+
+        let sender = destinations
+            .entry(destination.clone())
+            .or_insert_with(|| spawn_destination(&deliverer));
+        let delivery = match sender.try_send(delivery) {
+            // The queue of an idle destination closed meanwhile, it is started again
+            Err(TrySendError::Closed(delivery)) => {
+                let sender = spawn_destination(&deliverer);
+                let result = sender.try_send(delivery);
+                destinations.insert(destination.clone(), sender);
+                result
+            }
+            result => result,
+        };
+        if let Err(e) = delivery {
+            warn!("Dropped a webhook notification for {destination}: {e}");
+        }
+    }
+}
+
+/// Starts delivering the queue of a destination, which closes once idle
+fn spawn_destination(deliverer: &Arc<Deliverer>) -> mpsc::Sender<WebhookDelivery> {
+    let (sender, mut receiver) = mpsc::channel(deliverer.config.queue_size.max(1));
+    let deliverer = deliverer.clone();
+    tokio::spawn(async move {
+        while let Ok(Some(delivery)) =
+            tokio::time::timeout(DESTINATION_IDLE_TIMEOUT, receiver.recv()).await
+        {
+            deliver(&deliverer, delivery).await;
+        }
+    });
+    sender
+}
+
+async fn deliver(deliverer: &Deliverer, delivery: WebhookDelivery) {
+    let config = &deliverer.config;
+    let client = match delivery.user_url {
+        true => {
+            // The URL may have been registered before the current checks
+            if let Err(e) = check_webhook_url(&delivery.url) {
+                warn!(
+                    "Dropped the webhook notification for {}: {e}",
+                    delivery.recipient
+                );
+                return;
+            }
+            &deliverer.user_client
+        }
+        false => &deliverer.client,
+    };
+    let signature = sign(&config.secret, delivery.body.as_bytes());
+    let max_attempts = config.max_attempts.max(1);
+    let mut backoff = Duration::from_millis(config.initial_backoff_ms);
+
+    for attempt in 1..=max_attempts {
+        let result = {
+            // The semaphore is never closed
+            let _permit = deliverer.in_flight.acquire().await;
+            client
+                .post(&delivery.url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, &signature)
+                .header(RECIPIENT_HEADER, &delivery.recipient)
+                .body(delivery.body.clone())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+        };
+
+        match result {
+            Ok(_) => {
+                debug!(
+                    "Delivered a webhook notification for {}",
+                    delivery.recipient
+                );
+                return;
+            }
+            Err(e) if attempt < max_attempts => {
+                debug!(
+                    "Webhook notification for {} failed (attempt {attempt}): {e}, retrying in {backoff:?}",
+                    delivery.recipient
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            Err(e) => warn!(
+                "Dropped the webhook notification for {} after {attempt} attempts: {e}",
+                delivery.recipient
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+    use pubky::Keypair;
+    use std::collections::HashMap;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::time::timeout;
+
+    /// Reads a single HTTP request, returning its lowercased headers and its body
+    async fn read_request(stream: &mut TcpStream) -> (HashMap<String, String>, String) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 1024];
+        let headers_end = loop {
+            let read = stream.read(&mut chunk).await.unwrap();
+            assert!(
+                read > 0,
+                "The connection closed before the request was read"
+            );
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(i) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break i + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..headers_end]).to_string();
+        let headers: HashMap<String, String> = head
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+
+        let content_length: usize = headers["content-length"].parse().unwrap();
+        while buffer.len() < headers_end + content_length {
+            let read = stream.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        let body = String::from_utf8_lossy(&buffer[headers_end..]).to_string();
+        (headers, body)
+    }
+
+    async fn respond(stream: &mut TcpStream, status: &str) {
+        let response =
+            format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+        stream.write_all(response.as_bytes()).await.unwrap();
+    }
+
+    /// Accepts connections until one carries a notification for `recipient`, answering the others
+    async fn accept_for(
+        listener: &TcpListener,
+        recipient: &str,
+    ) -> (TcpStream, HashMap<String, String>, String) {
+        loop {
+            let (mut stream, _) = timeout(Duration::from_secs(5), listener.accept())
+                .await
+                .expect("The notification should be delivered")
+                .unwrap();
+            let (headers, body) = read_request(&mut stream).await;
+            if headers["x-nexus-recipient"] == recipient {
+                return (stream, headers, body);
+            }
+            respond(&mut stream, "200 OK").await;
+        }
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_notification_is_delivered_to_the_webhook() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        // The global URL is set by the operator, so it may be a local one
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let webhook_url = format!("http://{}/notifications", listener.local_addr()?);
+        let secret = "webhook-test-secret";
+
+        NotificationWebhook::start(NotificationWebhookConfig {
+            url: Some(webhook_url),
+            secret: secret.to_string(),
+            queue_size: 10,
+            max_attempts: 3,
+            initial_backoff_ms: 10,
+        });
+
+        let user_id = Keypair::random().public_key().to_z32();
+        let follower_id = Keypair::random().public_key().to_z32();
+        Notification::new_follow(&follower_id, &user_id, false).await?;
+
+        // The first attempt fails, the notification is delivered again
+        let (mut stream, _, first_body) = accept_for(&listener, &user_id).await;
+        respond(&mut stream, "500 Internal Server Error").await;
+
+        let (mut stream, headers, body) = accept_for(&listener, &user_id).await;
+        respond(&mut stream, "200 OK").await;
+
+        assert_eq!(body, first_body);
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(headers["x-nexus-recipient"], user_id);
+        assert_eq!(headers["x-nexus-signature"], sign(secret, body.as_bytes()));
+
+        let notification: serde_json::Value = serde_json::from_str(&body)?;
+        assert_eq!(notification["body"]["type"], "follow");
+        assert_eq!(notification["body"]["followed_by"], follower_id);
+        assert_eq!(notification["read"], false);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_webhook_url() {
+        assert!(check_webhook_url("https://example.com/notifications").is_ok());
+        assert!(check_webhook_url("https://93.184.216.34/notifications").is_ok());
+        assert!(check_webhook_url("https://[2606:2800:220:1::]/notifications").is_ok());
+
+        for url in [
+            "http://example.com/notifications",
+            "ftp://example.com/notifications",
+            "not a url",
+            "https://localhost/notifications",
+            "https://api.localhost./notifications",
+            "https://127.0.0.1/notifications",
+            "https://10.0.0.1/notifications",
+            "https://172.16.0.1/notifications",
+            "https://192.168.1.1/notifications",
+            "https://169.254.169.254/latest/meta-data",
+            "https://100.64.0.1/notifications",
+            "https://0.0.0.0/notifications",
+            "https://[::1]/notifications",
+            "https://[fe80::1]/notifications",
+            "https://[fd00::1]/notifications",
+            "https://[::ffff:127.0.0.1]/notifications",
+        ] {
+            assert!(check_webhook_url(url).is_err(), "{url} should be rejected");
+        }
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_public_resolver_rejects_local_hosts() {
+        let name: Name = "localhost".parse().unwrap();
+        assert!(PublicResolver.resolve(name).await.is_err());
+    }
+}
@@ -61,6 +61,20 @@ impl Bookmark {
         }
     }
 
+    /// Checks, for each of `post_keys` in the `author_id:post_id` format, whether the viewer
+    /// bookmarked the post, with a single read of the index.
+    ///
+    /// Returns one flag per post, in the same order.
+    pub async fn check_batch(post_keys: &[String], viewer_id: &str) -> RedisResult<Vec<bool>> {
+        let bookmark_keys: Vec<String> = post_keys
+            .iter()
+            .map(|post_key| format!("{post_key}:{viewer_id}"))
+            .collect();
+        let bookmarks = Self::mget(&bookmark_keys).await?;
+
+        Ok(bookmarks.iter().map(Option::is_some).collect())
+    }
+
     pub async fn get_from_index(
         author_id: &str,
         post_id: &str,
@@ -1,11 +1,26 @@
 use crate::db::kv::{JsonAction, RedisResult};
 use crate::db::{fetch_row_from_graph, queries, GraphResult, RedisOps};
-use crate::models::error::ModelResult;
+use crate::models::error::{ModelError, ModelResult};
 use crate::models::tag::post::POST_TAGS_KEY_PARTS;
+use crate::StackManager;
+use futures::future::try_join_all;
+use pubky_app_specs::Resource;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::PostStream;
+use super::{PostRelationships, PostStream, POST_TOTAL_ENGAGEMENT_KEY_PARTS};
+
+/// Maximum depth of the reply threads counted in [PostCounts::descendants]
+pub const DESCENDANTS_MAX_DEPTH: usize = 20;
+
+/// Returns the expiry of the cached descendant counts, `None` if they do not expire.
+/// See [StackConfig::descendants_cache_ttl_secs](crate::StackConfig::descendants_cache_ttl_secs)
+fn descendants_cache_ttl() -> Option<i64> {
+    match StackManager::config().descendants_cache_ttl_secs {
+        0 => None,
+        ttl_secs => Some(i64::try_from(ttl_secs).unwrap_or(i64::MAX)),
+    }
+}
 
 /// Represents total counts of relationships of a user.
 #[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
@@ -16,26 +31,113 @@ pub struct PostCounts {
     pub unique_tags: u32,
     pub replies: u32,
     pub reposts: u32,
+    /// Posts anywhere in the reply thread below the post, only set when the descendant counts are enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descendants: Option<u32>,
 }
 
 impl RedisOps for PostCounts {}
 
+/// Cached count of the posts in the reply thread below a post, see [PostCounts::descendants]
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct PostDescendants {
+    pub descendants: u32,
+}
+
+impl RedisOps for PostDescendants {}
+
+impl PostDescendants {
+    /// Retrieves the count of the descendants of a post, first trying the cache, then the graph.
+    /// Returns `None` if the post does not exist
+    pub async fn get_by_id(author_id: &str, post_id: &str) -> ModelResult<Option<u32>> {
+        if let Some(cached) = Self::try_from_index_json(&[author_id, post_id], None).await? {
+            return Ok(Some(cached.descendants));
+        }
+        let query = queries::get::post_descendants(author_id, post_id, DESCENDANTS_MAX_DEPTH);
+        let Some(row) = fetch_row_from_graph(query).await? else {
+            return Ok(None);
+        };
+        let descendants = row.get::<i64>("descendants").unwrap_or(0).max(0) as u32;
+        Self { descendants }
+            .put_index_json(&[author_id, post_id], None, descendants_cache_ttl())
+            .await?;
+        Ok(Some(descendants))
+    }
+
+    /// Updates the cached descendant counts of a post and of its ancestors, after a reply to the
+    /// post was created or deleted. The counts that are not cached are computed on the next read
+    pub async fn update_ancestors(
+        author_id: &str,
+        post_id: &str,
+        action: JsonAction,
+    ) -> ModelResult<()> {
+        let mut ancestor = Some((author_id.to_string(), post_id.to_string()));
+        for _ in 0..DESCENDANTS_MAX_DEPTH {
+            let Some((author_id, post_id)) = ancestor.take() else {
+                break;
+            };
+            let key_parts = [author_id.as_str(), post_id.as_str()];
+            if Self::try_from_index_json(&key_parts, None).await?.is_some() {
+                Self::modify_json_field(&key_parts, "descendants", action.clone()).await?;
+            }
+            ancestor = PostRelationships::get_by_id(&author_id, &post_id)
+                .await?
+                .and_then(|relationships| relationships.replied)
+                .and_then(|replied| match replied.resource {
+                    Resource::Post(parent_id) => Some((replied.user_id.to_string(), parent_id)),
+                    _ => None,
+                });
+        }
+        Ok(())
+    }
+}
+
 impl PostCounts {
     /// Retrieves counts by user ID, first trying to get from Redis, then from Neo4j if not found.
     pub async fn get_by_id(author_id: &str, post_id: &str) -> ModelResult<Option<PostCounts>> {
-        match Self::get_from_index(author_id, post_id).await? {
-            Some(counts) => Ok(Some(counts)),
-            None => {
-                let graph_response = Self::get_from_graph(author_id, post_id).await?;
-                if let Some((post_counts, is_reply)) = graph_response {
+        let mut counts = match Self::get_from_index(author_id, post_id).await? {
+            Some(counts) => counts,
+            None => match Self::get_from_graph(author_id, post_id).await? {
+                Some((post_counts, is_reply)) => {
                     post_counts
                         .put_to_index(author_id, post_id, !is_reply)
                         .await?;
-                    return Ok(Some(post_counts));
+                    post_counts
                 }
-                Ok(None)
-            }
+                None => return Ok(None),
+            },
+        };
+        if StackManager::config().descendant_counts {
+            counts.descendants = PostDescendants::get_by_id(author_id, post_id).await?;
         }
+        Ok(Some(counts))
+    }
+
+    /// Retrieves the counts of several posts, identified by `author_id:post_id` keys, in the same order.
+    ///
+    /// Counts missing from the index are read from the graph, and `None` is returned for the
+    /// posts that don't exist or whose key is malformed.
+    pub async fn get_by_ids(post_keys: &[String]) -> ModelResult<Vec<Option<PostCounts>>> {
+        let mut counts = Self::mget(post_keys).await?;
+
+        let with_descendants = StackManager::config().descendant_counts;
+        let misses = counts
+            .iter_mut()
+            .zip(post_keys)
+            .filter(|(counts, _)| counts.is_none() || with_descendants)
+            .filter_map(|(counts, post_key)| Some((counts, post_key.split_once(':')?)));
+        try_join_all(misses.map(|(counts, (author_id, post_id))| async move {
+            match counts {
+                Some(counts) => {
+                    counts.descendants = PostDescendants::get_by_id(author_id, post_id).await?
+                }
+                None => *counts = Self::get_by_id(author_id, post_id).await?,
+            }
+            Ok::<(), ModelError>(())
+        }))
+        .await?;
+
+        Ok(counts)
     }
 
     pub async fn get_from_index(author_id: &str, post_id: &str) -> RedisResult<Option<PostCounts>> {
@@ -130,6 +232,7 @@ impl PostCounts {
     ) -> RedisResult<()> {
         // Delete user_details on Redis
         Self::remove_from_index_multiple_json(&[&[author_id, post_id]]).await?;
+        PostDescendants::remove_from_index_multiple_json(&[&[author_id, post_id]]).await?;
         // Delete the posts that does not have any relationship as might be replies and reposts. Just root posts
         if remove_from_feeds {
             PostStream::delete_from_engagement_sorted_set(author_id, post_id).await?;
@@ -137,6 +240,28 @@ impl PostCounts {
         Ok(())
     }
 
+    /// Updates a count field of a post and, if `update_engagement`, its score in the total
+    /// engagement sorted set by the same amount, in a single transaction. The score only changes
+    /// when the count does, so that they never diverge under concurrent updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_key` - The `[author_id, post_id]` key parts of the post.
+    /// * `field` - The name of the count field to be updated.
+    /// * `action` - The action to perform on the count (increment or decrement).
+    /// * `update_engagement` - Whether the post is ranked in the total engagement sorted set.
+    ///   Replies are not
+    pub async fn update_index_field_with_engagement(
+        index_key: &[&str],
+        field: &str,
+        action: JsonAction,
+        update_engagement: bool,
+    ) -> RedisResult<()> {
+        let engagement =
+            update_engagement.then_some((&POST_TOTAL_ENGAGEMENT_KEY_PARTS[..], index_key));
+        Self::modify_json_field_with_score(index_key, field, action, engagement).await
+    }
+
     /// Increments a specified JSON field in a post's index by 1.
     pub async fn increment_index_field(
         index_key: &[&str],
@@ -155,3 +280,74 @@ impl PostCounts {
         Self::update_index_field(index_key, field, JsonAction::Decrement(1), tag_label).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+    use pubky::Keypair;
+
+    async fn engagement_score(author_id: &str, post_id: &str) -> RedisResult<Option<isize>> {
+        PostStream::check_sorted_set_member(
+            None,
+            &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+            &[author_id, post_id],
+        )
+        .await
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_concurrent_count_and_engagement_updates() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let author_id = Keypair::random().public_key().to_z32();
+        let post_id = "0032TESTCOUNTS";
+        let key_parts = [author_id.as_str(), post_id];
+        PostCounts::default()
+            .put_to_index(&author_id, post_id, false)
+            .await?;
+
+        let updates = (0..20).map(|_| {
+            let author_id = author_id.clone();
+            tokio::spawn(async move {
+                let key_parts = [author_id.as_str(), post_id];
+                PostCounts::update_index_field_with_engagement(
+                    &key_parts,
+                    "replies",
+                    JsonAction::Increment(1),
+                    true,
+                )
+                .await
+            })
+        });
+        for update in futures::future::join_all(updates).await {
+            update??;
+        }
+
+        let counts = PostCounts::get_from_index(&author_id, post_id)
+            .await?
+            .unwrap();
+        assert_eq!(counts.replies, 20);
+        assert_eq!(engagement_score(&author_id, post_id).await?, Some(20));
+
+        // The count does not go below zero, and neither does the score
+        for _ in 0..25 {
+            PostCounts::update_index_field_with_engagement(
+                &key_parts,
+                "replies",
+                JsonAction::Decrement(1),
+                true,
+            )
+            .await?;
+        }
+        let counts = PostCounts::get_from_index(&author_id, post_id)
+            .await?
+            .unwrap();
+        assert_eq!(counts.replies, 0);
+        assert_eq!(engagement_score(&author_id, post_id).await?, Some(0));
+
+        PostCounts::delete(&author_id, post_id, true).await?;
+        Ok(())
+    }
+}
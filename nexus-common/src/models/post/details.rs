@@ -1,3 +1,4 @@
+use super::search::PostsByContentSearch;
 use super::{PostRelationships, PostStream};
 use crate::db::kv::RedisResult;
 use crate::db::{
@@ -120,7 +121,16 @@ impl PostDetails {
 
     pub async fn reindex(author_id: &str, post_id: &str) -> ModelResult<()> {
         match Self::get_from_graph(author_id, post_id).await? {
-            Some((details, reply)) => details.put_to_index(author_id, reply, false).await?,
+            Some((details, reply)) => {
+                details.put_to_index(author_id, reply, false).await?;
+                PostsByContentSearch::put_to_index(
+                    author_id,
+                    post_id,
+                    &details.content,
+                    details.indexed_at,
+                )
+                .await?;
+            }
             None => {
                 tracing::error!("{author_id}:{post_id} Could not find post counts in the graph")
             }
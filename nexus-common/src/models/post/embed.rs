@@ -0,0 +1,109 @@
+use crate::db::kv::{RedisResult, SortOrder};
+use crate::db::RedisOps;
+use pubky_app_specs::ParsedUri;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const EMBED_GLOBAL_POST_TIMELINE: [&str; 4] = ["Embed", "Global", "Post", "Timeline"];
+
+/// The URI embedded by a post, kept to remove the post from [PostsByEmbed] once it is edited or deleted
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PostEmbed {
+    pub uri: String,
+}
+
+impl RedisOps for PostEmbed {}
+
+/// Represents a single result of a "posts by embed" search, returning the post keys (`author_id:post_id`) and score
+#[derive(Serialize, Deserialize, ToSchema, Default)]
+pub struct PostsByEmbed {
+    pub post_key: String,
+    pub score: usize,
+}
+
+impl From<(String, f64)> for PostsByEmbed {
+    fn from(tuple: (String, f64)) -> Self {
+        PostsByEmbed {
+            post_key: tuple.0,
+            score: tuple.1 as usize,
+        }
+    }
+}
+
+impl RedisOps for PostsByEmbed {}
+
+impl PostsByEmbed {
+    /// Brings an embedded URI to the form it is indexed with. Pubky URIs are parsed and rebuilt,
+    /// so that equivalent spellings of the same resource match. Other URIs are only trimmed
+    pub fn normalize_uri(uri: &str) -> String {
+        let uri = uri.trim();
+        ParsedUri::try_from(uri)
+            .ok()
+            .and_then(|parsed| parsed.try_to_uri_str().ok())
+            .unwrap_or_else(|| uri.to_string())
+    }
+
+    /// Retrieves the posts embedding `uri`, most recent first
+    pub async fn get(
+        uri: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> RedisResult<Option<Vec<PostsByEmbed>>> {
+        let uri = Self::normalize_uri(uri);
+        let post_score_list = Self::try_from_index_sorted_set(
+            &Self::get_index_key_parts(&uri),
+            None,
+            None,
+            skip,
+            limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
+
+        Ok(post_score_list.map(|list| list.into_iter().map(PostsByEmbed::from).collect()))
+    }
+
+    fn get_index_key_parts(uri: &str) -> Vec<&str> {
+        [&EMBED_GLOBAL_POST_TIMELINE[..], &[uri]].concat()
+    }
+
+    /// Indexes the post under the URI it embeds, scored by the post `indexed_at`
+    pub async fn put_to_index(
+        author_id: &str,
+        post_id: &str,
+        embed_uri: &str,
+        indexed_at: i64,
+    ) -> RedisResult<()> {
+        let uri = Self::normalize_uri(embed_uri);
+        if uri.is_empty() {
+            return Ok(());
+        }
+        let member_key = format!("{author_id}:{post_id}");
+        Self::put_index_sorted_set(
+            &Self::get_index_key_parts(&uri),
+            &[(indexed_at as f64, &member_key)],
+            None,
+            None,
+        )
+        .await?;
+        PostEmbed { uri }
+            .put_index_json(&[author_id, post_id], None, None)
+            .await
+    }
+
+    /// Removes the post from the index of the URI it embeds, if it was indexed
+    pub async fn del_from_index(author_id: &str, post_id: &str) -> RedisResult<()> {
+        let Some(embed) = PostEmbed::try_from_index_json(&[author_id, post_id], None).await? else {
+            return Ok(());
+        };
+        let member_key = format!("{author_id}:{post_id}");
+        Self::remove_from_index_sorted_set(
+            None,
+            &Self::get_index_key_parts(&embed.uri),
+            &[&member_key],
+        )
+        .await?;
+        PostEmbed::remove_from_index_multiple_json(&[&[author_id, post_id]]).await
+    }
+}
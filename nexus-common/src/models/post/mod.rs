@@ -1,18 +1,23 @@
 mod bookmark;
 mod counts;
 mod details;
+pub mod embed;
 mod relationships;
 pub mod search;
 mod stream;
+mod thread;
 mod view;
 
 pub use bookmark::Bookmark;
-pub use counts::PostCounts;
+pub use counts::{PostCounts, PostDescendants, DESCENDANTS_MAX_DEPTH};
 pub use details::PostDetails;
-pub use relationships::PostRelationships;
+pub use embed::PostsByEmbed;
+pub use relationships::{PostKind, PostRelationships, QuotedPost};
 pub use stream::{
-    PostKeyStream, PostStream, StreamSource, POST_PER_USER_KEY_PARTS,
+    PostEngagementCounts, PostKeyStream, PostStream, PostStreamParams, StreamSource,
+    MAX_THREAD_REPLIES, MAX_THREAD_REPLY_DEPTH, POST_PER_USER_KEY_PARTS,
     POST_REPLIES_PER_POST_KEY_PARTS, POST_REPLIES_PER_USER_KEY_PARTS, POST_TIMELINE_KEY_PARTS,
     POST_TOTAL_ENGAGEMENT_KEY_PARTS,
 };
-pub use view::PostView;
+pub use thread::{PostThreadNode, ThreadOptions, POST_DELETED_CONTENT};
+pub use view::{PostEngagementBreakdown, PostTaggerEngagement, PostView};
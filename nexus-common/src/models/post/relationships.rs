@@ -1,7 +1,9 @@
 use crate::db::kv::RedisResult;
 use crate::db::{fetch_row_from_graph, queries, GraphResult, RedisOps};
 use crate::models::error::ModelResult;
-use pubky_app_specs::{post_uri_builder, ParsedUri, PubkyAppPost, PubkyAppPostKind, PubkyId};
+use pubky_app_specs::{
+    post_uri_builder, ParsedUri, PubkyAppPost, PubkyAppPostKind, PubkyId, Resource,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use utoipa::ToSchema;
 
@@ -28,6 +30,25 @@ mod parsed_uri_option {
     }
 }
 
+/// Position of a post in the post graph, as derived from its relationships
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostKind {
+    /// Top-level post, neither a reply nor a repost
+    Root,
+    /// Reply to another post
+    Reply,
+    /// Repost of another post, which is not a reply
+    Repost,
+}
+
+/// Reference to the post quoted by a quote-post
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct QuotedPost {
+    pub author_id: String,
+    pub post_id: String,
+}
+
 #[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
 pub struct PostRelationships {
     /// If set, URI of the post this is a reply to
@@ -40,6 +61,10 @@ pub struct PostRelationships {
     #[serde(with = "parsed_uri_option")]
     pub reposted: Option<ParsedUri>,
 
+    /// If set, the post this post is quoting: a repost with its own commentary
+    #[serde(default)]
+    pub quoted: Option<QuotedPost>,
+
     /// List of user IDs mentioned in this post
     pub mentioned: Vec<PubkyId>,
 }
@@ -89,23 +114,67 @@ impl PostRelationships {
         let reposted_post_id: Option<String> = row.get("reposted_post_id").unwrap_or(None);
         let reposted_author_id: Option<String> = row.get("reposted_author_id").unwrap_or(None);
         let mentioned: Vec<PubkyId> = row.get("mentioned_user_ids").unwrap_or(Vec::new());
+        let is_quote: bool = row.get("is_quote").unwrap_or(false);
 
         let replied = replied_author_id
             .zip(replied_post_id)
             .map(|(author_id, post_id)| post_uri_builder(author_id, post_id))
             .and_then(|uri| ParsedUri::try_from(uri).ok());
-        let reposted = reposted_author_id
-            .zip(reposted_post_id)
+        let reposted_key = reposted_author_id.zip(reposted_post_id);
+        let quoted = match is_quote {
+            true => reposted_key
+                .clone()
+                .map(|(author_id, post_id)| QuotedPost { author_id, post_id }),
+            false => None,
+        };
+        let reposted = reposted_key
             .map(|(author_id, post_id)| post_uri_builder(author_id, post_id))
             .and_then(|uri| ParsedUri::try_from(uri).ok());
 
         Ok(Some(Self {
             replied,
             reposted,
+            quoted,
             mentioned,
         }))
     }
 
+    /// Classifies the relationships of a post. A post that is both a reply and a repost is a reply.
+    pub fn kind(&self) -> PostKind {
+        match (&self.replied, &self.reposted) {
+            (Some(_), _) => PostKind::Reply,
+            (None, Some(_)) => PostKind::Repost,
+            (None, None) => PostKind::Root,
+        }
+    }
+
+    /// Classifies a list of posts as root posts, replies or reposts.
+    ///
+    /// The relationships are read from the index in a single batch. Posts missing from the index
+    /// fall back to the graph, and are returned as `None` if they do not exist there either.
+    ///
+    /// # Arguments
+    ///
+    /// * `post_keys` - Post keys in the `author_id:post_id` format
+    pub async fn classify_batch(post_keys: &[String]) -> ModelResult<Vec<Option<PostKind>>> {
+        let relationships = Self::mget(post_keys).await?;
+
+        let mut kinds = Vec::with_capacity(post_keys.len());
+        for (post_key, relationships) in post_keys.iter().zip(relationships) {
+            let kind = match relationships {
+                Some(relationships) => Some(relationships.kind()),
+                None => match post_key.split_once(':') {
+                    Some((author_id, post_id)) => Self::get_by_id(author_id, post_id)
+                        .await?
+                        .map(|relationships| relationships.kind()),
+                    None => None,
+                },
+            };
+            kinds.push(kind);
+        }
+        Ok(kinds)
+    }
+
     /// Constructs a `Self` instance by extracting relationships from a `PubkyAppPost` object
     pub fn from_homeserver(post: &PubkyAppPost) -> Self {
         let mut relationship = Self::default();
@@ -119,6 +188,21 @@ impl PostRelationships {
                 relationship.reposted = ParsedUri::try_from(embed.uri.as_str()).ok()
             }
         }
+
+        // A repost with its own content quotes the reposted post
+        if !post.content.trim().is_empty() {
+            relationship.quoted =
+                relationship
+                    .reposted
+                    .as_ref()
+                    .and_then(|reposted| match &reposted.resource {
+                        Resource::Post(post_id) => Some(QuotedPost {
+                            author_id: reposted.user_id.to_string(),
+                            post_id: post_id.clone(),
+                        }),
+                        _ => None,
+                    });
+        }
         relationship
     }
 
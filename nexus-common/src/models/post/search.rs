@@ -1,17 +1,34 @@
+use std::collections::HashSet;
+
 use crate::db::graph::Query;
-use crate::db::kv::{RedisResult, ScoreAction, SortOrder};
-use crate::db::queries::get::{global_tags_by_post, global_tags_by_post_engagement};
-use crate::db::{fetch_all_rows_from_graph, RedisOps};
+use crate::db::kv::{sets, RedisError, RedisResult, ScoreAction, SortOrder};
+use crate::db::queries::get::{
+    get_user_ids_by_reach, global_tags_by_post, global_tags_by_post_engagement,
+};
+use crate::db::{fetch_all_rows_from_graph, fetch_key_from_graph, RedisOps};
 use crate::models::error::ModelResult;
-use crate::models::post::PostDetails;
+use crate::models::follow::{Followers, Following, UserFollows};
+use crate::models::post::{PostDetails, PostStream, POST_TOTAL_ENGAGEMENT_KEY_PARTS};
+use crate::models::tag::alias::TagAlias;
 use crate::models::tag::post::TagPost;
 use crate::models::tag::traits::TaggersCollection;
-use crate::types::{Pagination, StreamSorting};
+use crate::types::{Pagination, StreamReach, StreamSorting};
+use futures::future::try_join_all;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 pub const TAG_GLOBAL_POST_TIMELINE: [&str; 4] = ["Tags", "Global", "Post", "Timeline"];
 pub const TAG_GLOBAL_POST_ENGAGEMENT: [&str; 4] = ["Tags", "Global", "Post", "TotalEngagement"];
+pub const CONTENT_GLOBAL_POST_TIMELINE: [&str; 4] = ["Content", "Global", "Post", "Timeline"];
+pub const CACHE_USER_REACH_WOT_KEY_PARTS: [&str; 4] = ["Cache", "Users", "Reach", "Wot"];
+// TTL, 1HR
+pub const CACHE_USER_REACH_WOT_TTL: i64 = 60 * 60;
+/// Number of results of a reach search when no limit is given, as for the search endpoints
+pub const DEFAULT_REACH_SEARCH_LIMIT: usize = 20;
+/// Number of tagged posts read at once while filtering them by the reach of a user
+pub const REACH_SEARCH_BATCH_SIZE: usize = 1000;
+/// Content tokens shorter than this are not indexed, as they would match almost every post
+pub const MIN_CONTENT_TOKEN_LEN: usize = 3;
 
 /// Represents a single search result of a "posts by tag" search, returning the post keys (`author_id:post_id`) and score
 #[derive(Serialize, Deserialize, ToSchema, Default)]
@@ -60,38 +77,197 @@ impl PostsByTagSearch {
         sort_by: Option<StreamSorting>,
         pagination: Pagination,
     ) -> RedisResult<Option<Vec<PostsByTagSearch>>> {
-        let post_score_list = match sort_by {
+        let label = TagAlias::canonical(label).await?;
+        let post_score_list = Self::try_from_index_sorted_set(
+            &Self::get_index_key_parts(&label, sort_by),
+            pagination.start,
+            pagination.end,
+            pagination.skip,
+            pagination.limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
+
+        match post_score_list {
+            Some(list) => Ok(Some(list.into_iter().map(|t| t.into()).collect())),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the posts tagged with `label` whose authors are within the `reach` of `user_id`
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The tag label to search posts for
+    /// * `user_id` - The ID of the user whose reach is used for filtering post authors
+    /// * `reach` - The reach context that determines which authors are included (e.g., following, friends)
+    /// * `sort_by` - The sorting of the results, by timeline if `None`
+    /// * `pagination` - Score range and skip/limit applied to the filtered results, [DEFAULT_REACH_SEARCH_LIMIT] results by default
+    pub async fn get_by_label_with_reach(
+        label: &str,
+        user_id: &str,
+        reach: StreamReach,
+        sort_by: Option<StreamSorting>,
+        pagination: Pagination,
+    ) -> ModelResult<Option<Vec<PostsByTagSearch>>> {
+        let label = TagAlias::canonical(label).await?;
+        if !Self::index_reach(user_id, &reach).await? {
+            return Ok(None);
+        }
+
+        let key_parts = Self::get_index_key_parts(&label, sort_by);
+        let skip = pagination.skip.unwrap_or(0);
+        let limit = pagination.limit.unwrap_or(DEFAULT_REACH_SEARCH_LIMIT);
+
+        // Skip and limit cannot be applied before filtering by author, so the tagged posts are
+        // read in batches until the page is filled or the range is exhausted
+        let mut posts = Vec::new();
+        let mut offset = 0;
+        loop {
+            let Some(batch) = Self::try_from_index_sorted_set(
+                &key_parts,
+                pagination.start,
+                pagination.end,
+                Some(offset),
+                Some(REACH_SEARCH_BATCH_SIZE),
+                SortOrder::Descending,
+                None,
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+
+            let author_ids: Vec<&str> = batch
+                .iter()
+                .map(|(post_key, _)| {
+                    post_key
+                        .split_once(':')
+                        .map_or("", |(author_id, _)| author_id)
+                })
+                .collect();
+            let in_reach = Self::check_reach(user_id, &reach, &author_ids).await?;
+            let batch_len = batch.len();
+            posts.extend(
+                batch
+                    .into_iter()
+                    .zip(in_reach)
+                    .filter_map(|(post, in_reach)| in_reach.then_some(post)),
+            );
+
+            if posts.len() >= skip + limit || batch_len < REACH_SEARCH_BATCH_SIZE {
+                break;
+            }
+            offset += REACH_SEARCH_BATCH_SIZE;
+        }
+
+        let posts = posts
+            .into_iter()
+            .skip(skip)
+            .take(limit)
+            .map(PostsByTagSearch::from)
+            .collect();
+
+        Ok(Some(posts))
+    }
+
+    fn get_index_key_parts(label: &str, sort_by: Option<StreamSorting>) -> Vec<&str> {
+        match sort_by {
             Some(StreamSorting::TotalEngagement) => {
-                Self::try_from_index_sorted_set(
-                    &[&TAG_GLOBAL_POST_ENGAGEMENT[..], &[label]].concat(),
-                    pagination.start,
-                    pagination.end,
-                    pagination.skip,
-                    pagination.limit,
-                    SortOrder::Descending,
-                    None,
-                )
-                .await?
+                [&TAG_GLOBAL_POST_ENGAGEMENT[..], &[label]].concat()
             }
             // Default case always: SortBy::Timeline
-            _ => {
-                Self::try_from_index_sorted_set(
-                    &[&TAG_GLOBAL_POST_TIMELINE[..], &[label]].concat(),
-                    pagination.start,
-                    pagination.end,
-                    pagination.skip,
-                    pagination.limit,
-                    SortOrder::Descending,
-                    None,
-                )
-                .await?
+            _ => [&TAG_GLOBAL_POST_TIMELINE[..], &[label]].concat(),
+        }
+    }
+
+    /// Makes sure the users within the `reach` of `user_id` are indexed, so that
+    /// [Self::check_reach] can test authors against them. Returns `false` if the reach is empty.
+    ///
+    /// Follow based reaches are loaded from the graph into the follow indexes on a miss. The web of
+    /// trust is computed in the graph and cached with a TTL, as it is too expensive to resolve on
+    /// every request.
+    async fn index_reach(user_id: &str, reach: &StreamReach) -> ModelResult<bool> {
+        // A single ID is enough to know whether the index holds any user
+        let has_following = || async {
+            ModelResult::Ok(
+                Following::get_by_id(user_id, None, Some(1))
+                    .await?
+                    .is_some_and(|following| !following.0.is_empty()),
+            )
+        };
+        let has_followers = || async {
+            ModelResult::Ok(
+                Followers::get_by_id(user_id, None, Some(1))
+                    .await?
+                    .is_some_and(|followers| !followers.0.is_empty()),
+            )
+        };
+
+        match reach {
+            StreamReach::Following => has_following().await,
+            StreamReach::Followers => has_followers().await,
+            StreamReach::Friends => Ok(has_following().await? && has_followers().await?),
+            StreamReach::Wot(depth) => Self::index_wot_user_ids(user_id, *depth).await,
+        }
+    }
+
+    /// Flags which of `author_ids` are within the `reach` of `user_id`, one flag per author, from
+    /// the indexes prepared by [Self::index_reach]
+    async fn check_reach(
+        user_id: &str,
+        reach: &StreamReach,
+        author_ids: &[&str],
+    ) -> ModelResult<Vec<bool>> {
+        let or_none =
+            |flags: Option<Vec<bool>>| flags.unwrap_or_else(|| vec![false; author_ids.len()]);
+
+        let flags = match reach {
+            StreamReach::Following => {
+                or_none(Following::check_set_members(&[user_id], author_ids).await?)
+            }
+            StreamReach::Followers => {
+                or_none(Followers::check_set_members(&[user_id], author_ids).await?)
+            }
+            StreamReach::Friends => {
+                let following =
+                    or_none(Following::check_set_members(&[user_id], author_ids).await?);
+                let followers =
+                    or_none(Followers::check_set_members(&[user_id], author_ids).await?);
+                following
+                    .into_iter()
+                    .zip(followers)
+                    .map(|(following, follower)| following && follower)
+                    .collect()
+            }
+            StreamReach::Wot(depth) => {
+                let prefix = CACHE_USER_REACH_WOT_KEY_PARTS.join(":");
+                let key = format!("{user_id}:{depth}");
+                or_none(sets::check_members(&prefix, &key, author_ids).await?)
             }
         };
+        Ok(flags)
+    }
 
-        match post_score_list {
-            Some(list) => Ok(Some(list.into_iter().map(|t| t.into()).collect())),
-            None => Ok(None),
+    /// Caches the web of trust of `user_id` on a miss. Returns `false` if it is empty.
+    async fn index_wot_user_ids(user_id: &str, depth: u8) -> ModelResult<bool> {
+        let prefix = CACHE_USER_REACH_WOT_KEY_PARTS.join(":");
+        let key = format!("{user_id}:{depth}");
+
+        if let Some(size) = sets::get_size(&prefix, &key).await? {
+            return Ok(size > 0);
         }
+
+        let query = get_user_ids_by_reach(user_id, &StreamReach::Wot(depth));
+        let user_ids: Vec<String> = fetch_key_from_graph(query, "user_ids")
+            .await?
+            .unwrap_or_default();
+
+        let values: Vec<&str> = user_ids.iter().map(|s| s.as_str()).collect();
+        sets::put(&prefix, &key, &values, Some(CACHE_USER_REACH_WOT_TTL)).await?;
+
+        Ok(!user_ids.is_empty())
     }
 
     pub async fn update_index_score(
@@ -146,3 +322,168 @@ impl PostsByTagSearch {
         Ok(())
     }
 }
+
+/// Represents a single search result of a "posts by content" search, returning the post keys (`author_id:post_id`) and score
+#[derive(Serialize, Deserialize, ToSchema, Default)]
+pub struct PostsByContentSearch {
+    pub post_key: String,
+    pub score: usize,
+}
+
+impl From<(String, f64)> for PostsByContentSearch {
+    fn from(tuple: (String, f64)) -> Self {
+        PostsByContentSearch {
+            post_key: tuple.0,
+            score: tuple.1 as usize,
+        }
+    }
+}
+
+impl RedisOps for PostsByContentSearch {}
+
+impl PostsByContentSearch {
+    /// Splits `content` into its distinct search tokens. The content is lowercased and split on
+    /// whitespace and punctuation, dropping the tokens shorter than [MIN_CONTENT_TOKEN_LEN].
+    pub fn tokenize(content: &str) -> Vec<String> {
+        let mut seen = HashSet::new();
+        content
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| token.chars().count() >= MIN_CONTENT_TOKEN_LEN)
+            .map(str::to_lowercase)
+            .filter(|token| seen.insert(token.clone()))
+            .collect()
+    }
+
+    /// Retrieves the posts whose content contains all the tokens of `terms`
+    ///
+    /// # Arguments
+    ///
+    /// * `terms` - The search terms, tokenized the same way as the post content
+    /// * `sort_by` - The sorting of the results, by timeline if `None`
+    /// * `pagination` - Timeframe of the posts `indexed_at` and skip/limit applied to the matching posts
+    pub async fn get_by_terms(
+        terms: &str,
+        sort_by: Option<StreamSorting>,
+        pagination: Pagination,
+    ) -> RedisResult<Option<Vec<PostsByContentSearch>>> {
+        let tokens = Self::tokenize(terms);
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+
+        // Skip and limit cannot be applied before intersecting the tokens, so read the whole ranges
+        let mut post_score_list: Option<Vec<(String, f64)>> = None;
+        for token in &tokens {
+            let Some(token_posts) = Self::try_from_index_sorted_set(
+                &Self::get_index_key_parts(token),
+                pagination.start,
+                pagination.end,
+                None,
+                None,
+                SortOrder::Descending,
+                None,
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+
+            post_score_list = Some(match post_score_list {
+                None => token_posts,
+                Some(list) => {
+                    let token_post_keys: HashSet<String> = token_posts
+                        .into_iter()
+                        .map(|(post_key, _)| post_key)
+                        .collect();
+                    list.into_iter()
+                        .filter(|(post_key, _)| token_post_keys.contains(post_key))
+                        .collect()
+                }
+            });
+        }
+
+        let mut post_score_list = post_score_list.unwrap_or_default();
+        if let Some(StreamSorting::TotalEngagement) = sort_by {
+            post_score_list = Self::get_engagement_scores(post_score_list).await?;
+            post_score_list.sort_by(|a, b| b.1.total_cmp(&a.1));
+        }
+
+        let posts = post_score_list
+            .into_iter()
+            .skip(pagination.skip.unwrap_or(0))
+            .take(pagination.limit.unwrap_or(usize::MAX))
+            .map(PostsByContentSearch::from)
+            .collect();
+
+        Ok(Some(posts))
+    }
+
+    /// Replaces the timeline scores of the posts with their total engagement.
+    /// Posts missing from the engagement index (e.g. replies) score 0.
+    async fn get_engagement_scores(
+        post_score_list: Vec<(String, f64)>,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        try_join_all(post_score_list.into_iter().map(|(post_key, _)| async move {
+            let score = PostStream::check_sorted_set_member(
+                None,
+                &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+                &[post_key.as_str()],
+            )
+            .await?;
+            Ok::<_, RedisError>((post_key, score.unwrap_or(0) as f64))
+        }))
+        .await
+    }
+
+    fn get_index_key_parts(token: &str) -> Vec<&str> {
+        [&CONTENT_GLOBAL_POST_TIMELINE[..], &[token]].concat()
+    }
+
+    /// Indexes every token of the post `content`, scored by the post `indexed_at`
+    pub async fn put_to_index(
+        author_id: &str,
+        post_id: &str,
+        content: &str,
+        indexed_at: i64,
+    ) -> RedisResult<()> {
+        let member_key = format!("{author_id}:{post_id}");
+        for token in Self::tokenize(content) {
+            Self::put_index_sorted_set(
+                &Self::get_index_key_parts(&token),
+                &[(indexed_at as f64, &member_key)],
+                None,
+                None,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the post from the index of every token of its `content`
+    pub async fn del_from_index(author_id: &str, post_id: &str, content: &str) -> RedisResult<()> {
+        let member_key = format!("{author_id}:{post_id}");
+        for token in Self::tokenize(content) {
+            Self::remove_from_index_sorted_set(
+                None,
+                &Self::get_index_key_parts(&token),
+                &[&member_key],
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PostsByContentSearch;
+
+    #[test]
+    fn test_tokenize_content() {
+        assert_eq!(
+            PostsByContentSearch::tokenize("Open-source, is FUN! Open source on pubky.app"),
+            vec!["open", "source", "fun", "pubky", "app"]
+        );
+        assert!(PostsByContentSearch::tokenize("a be to, ok?").is_empty());
+    }
+}
@@ -1,18 +1,18 @@
 use super::{Bookmark, PostCounts, PostDetails, PostView};
-use crate::db::kv::{RedisResult, ScoreAction, SortOrder};
-use crate::db::{get_neo4j_graph, queries, GraphError, GraphResult, RedisOps};
-use crate::models::error::ModelError;
+use crate::db::kv::{sets, RedisResult, ScoreAction, SortOrder};
+use crate::db::{
+    fetch_all_rows_from_graph_with_timeout, fetch_key_from_graph, queries, GraphResult, RedisOps,
+};
+use crate::models::audit::OrphanReport;
 use crate::models::error::ModelResult;
 use crate::models::{
     follow::{Followers, Following, Friends, UserFollows},
     post::search::PostsByTagSearch,
 };
-use crate::types::{Pagination, StreamSorting};
-use futures::TryStreamExt;
+use crate::types::{parse_string_to_bool, Pagination, StreamSorting, Timeframe};
 use pubky_app_specs::PubkyAppPostKind;
 use serde::{Deserialize, Serialize};
-use tokio::task::spawn;
-use tokio::time::{timeout, Duration};
+use tokio::time::Duration;
 use tracing::warn;
 use utoipa::ToSchema;
 
@@ -21,8 +21,17 @@ pub const POST_TOTAL_ENGAGEMENT_KEY_PARTS: [&str; 3] = ["Posts", "Global", "Tota
 pub const POST_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "AuthorParents"];
 pub const POST_REPLIES_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "AuthorReplies"];
 pub const POST_REPLIES_PER_POST_KEY_PARTS: [&str; 2] = ["Posts", "PostReplies"];
+pub const POST_SELF_THREADS_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "SelfThreads"];
 const BOOKMARKS_USER_KEY_PARTS: [&str; 2] = ["Bookmarks", "User"];
 
+/// Upper bound on the time taken by a post stream query on the graph
+const POST_STREAM_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Deepest level of replies [PostStream::walk_thread_replies] descends to
+pub const MAX_THREAD_REPLY_DEPTH: usize = 20;
+/// Upper bound on the number of replies [PostStream::walk_thread_replies] walks through
+pub const MAX_THREAD_REPLIES: usize = 1000;
+
 #[derive(ToSchema, Deserialize, Debug, Clone, PartialEq, Default)]
 #[serde(tag = "source", rename_all = "snake_case")]
 pub enum StreamSource {
@@ -41,9 +50,15 @@ pub enum StreamSource {
     },
     Bookmarks {
         observer_id: String,
+        /// Only include posts bookmarked within this timeframe
+        #[serde(default)]
+        timeframe: Option<Timeframe>,
     },
     Author {
         author_id: String,
+        /// Flag the root posts the author continued as a self-reply thread
+        #[serde(default, deserialize_with = "parse_string_to_bool")]
+        collapse_self_threads: bool,
     },
     AuthorReplies {
         author_id: String,
@@ -58,7 +73,7 @@ impl StreamSource {
             StreamSource::Followers { observer_id }
             | StreamSource::Following { observer_id }
             | StreamSource::Friends { observer_id }
-            | StreamSource::Bookmarks { observer_id } => Some(observer_id),
+            | StreamSource::Bookmarks { observer_id, .. } => Some(observer_id),
             _ => None,
         }
     }
@@ -69,18 +84,39 @@ impl StreamSource {
                 author_id,
                 post_id: _,
             } => Some(author_id),
-            StreamSource::Author { author_id } => Some(author_id),
+            StreamSource::Author { author_id, .. } => Some(author_id),
             StreamSource::AuthorReplies { author_id } => Some(author_id),
             _ => None,
         }
     }
 }
 
+/// Interactions of a post that add up to its total engagement
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default, Clone, PartialEq)]
+pub struct PostEngagementCounts {
+    pub tags: u32,
+    pub replies: u32,
+    pub reposts: u32,
+}
+
+impl From<PostCounts> for PostEngagementCounts {
+    fn from(counts: PostCounts) -> Self {
+        Self {
+            tags: counts.tags,
+            replies: counts.replies,
+            reposts: counts.reposts,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, ToSchema, Debug, Default, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct PostKeyStream {
     pub post_keys: Vec<String>,
     pub last_post_score: Option<u64>,
+    /// Engagement components of each post, in the same order as `post_keys`. Only set on request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engagement: Option<Vec<PostEngagementCounts>>,
 }
 
 impl PostKeyStream {
@@ -88,7 +124,23 @@ impl PostKeyStream {
         Self {
             post_keys,
             last_post_score,
+            engagement: None,
+        }
+    }
+
+    /// Sets the engagement components of each post. The graph already returns them with the
+    /// [StreamSorting::TotalEngagement] sorting, otherwise they are read from the post counts.
+    pub async fn with_engagement(mut self) -> ModelResult<Self> {
+        if self.engagement.is_none() {
+            let counts = PostCounts::get_by_ids(&self.post_keys).await?;
+            self.engagement = Some(
+                counts
+                    .into_iter()
+                    .map(|counts| counts.map(Into::into).unwrap_or_default())
+                    .collect(),
+            );
         }
+        Ok(self)
     }
 
     // Iterate over tuples of (post_key, score) to extract the post keys and capture the last score
@@ -103,6 +155,24 @@ impl PostKeyStream {
     }
 }
 
+/// Source, pagination, ordering and filters of a post stream
+#[derive(Debug, Clone, Default)]
+pub struct PostStreamParams {
+    pub source: StreamSource,
+    pub pagination: Pagination,
+    pub order: SortOrder,
+    pub sorting: StreamSorting,
+    /// Only stream the posts tagged with any of these labels
+    pub tags: Option<Vec<String>>,
+    /// Only stream the posts of this kind
+    pub kind: Option<PubkyAppPostKind>,
+    /// Only stream the quote-posts, i.e. reposts with their own content
+    pub quotes_only: bool,
+    /// With [StreamSorting::TotalEngagement], only stream the posts with at least this many
+    /// interactions. No minimum applies when `None`
+    pub min_engagement: Option<u64>,
+}
+
 #[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
 pub struct PostStream(pub Vec<PostView>);
 
@@ -112,35 +182,71 @@ impl PostStream {
     pub fn extend(&mut self, post_stream: PostStream) {
         self.0.extend(post_stream.0);
     }
+
+    /// Retrieves a stream of post views, see [PostStreamParams]
     pub async fn get_posts(
-        source: StreamSource,
-        pagination: Pagination,
-        order: SortOrder,
-        sorting: StreamSorting,
+        params: PostStreamParams,
         viewer_id: Option<String>,
-        tags: Option<Vec<String>>,
-        kind: Option<PubkyAppPostKind>,
     ) -> ModelResult<Option<Self>> {
-        let post_key_stream =
-            Self::collect_post_keys(source, pagination, order, sorting, tags, kind).await?;
+        let self_thread_author = match &params.source {
+            StreamSource::Author {
+                author_id,
+                collapse_self_threads: true,
+            } => Some(author_id.clone()),
+            _ => None,
+        };
+
+        let post_key_stream = Self::collect_post_keys(params).await?;
 
         if post_key_stream.is_empty() {
             return Ok(None);
         }
 
-        Self::from_listed_post_ids(viewer_id, &post_key_stream.post_keys).await
+        let mut stream = Self::from_listed_post_ids(viewer_id, &post_key_stream.post_keys).await?;
+
+        // Author streams only contain root posts, so each self-reply thread is already
+        // collapsed into its root and just needs to be flagged
+        if let (Some(author_id), Some(stream)) = (self_thread_author, stream.as_mut()) {
+            let post_ids: Vec<&str> = stream.0.iter().map(|p| p.details.id.as_str()).collect();
+            let key_parts = [
+                &POST_SELF_THREADS_PER_USER_KEY_PARTS[..],
+                &[author_id.as_str()],
+            ]
+            .concat();
+            if let Some(flags) = Self::check_set_members(&key_parts, &post_ids).await? {
+                for (post_view, has_self_thread) in stream.0.iter_mut().zip(flags) {
+                    post_view.has_self_thread = has_self_thread;
+                }
+            }
+        }
+
+        Ok(stream)
     }
 
-    pub async fn get_post_keys(
-        source: StreamSource,
-        pagination: Pagination,
-        order: SortOrder,
-        sorting: StreamSorting,
-        tags: Option<Vec<String>>,
-        kind: Option<PubkyAppPostKind>,
-    ) -> ModelResult<Option<PostKeyStream>> {
-        let post_key_stream =
-            Self::collect_post_keys(source, pagination, order, sorting, tags, kind).await?;
+    /// Checks whether the author of a post replied to it themselves, starting a self-reply thread
+    async fn has_self_reply(author_id: &str, post_id: &str) -> RedisResult<bool> {
+        let key_parts = [&POST_REPLIES_PER_POST_KEY_PARTS[..], &[author_id, post_id]].concat();
+        let replies = Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            None,
+            None,
+            None,
+            SortOrder::Ascending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+
+        let author_prefix = format!("{author_id}:");
+        Ok(replies
+            .iter()
+            .any(|(reply_key, _)| reply_key.starts_with(&author_prefix)))
+    }
+
+    /// Retrieves a stream of post keys, see [PostStreamParams]
+    pub async fn get_post_keys(params: PostStreamParams) -> ModelResult<Option<PostKeyStream>> {
+        let post_key_stream = Self::collect_post_keys(params).await?;
 
         if post_key_stream.is_empty() {
             return Ok(None);
@@ -149,20 +255,40 @@ impl PostStream {
         Ok(Some(post_key_stream))
     }
 
-    async fn collect_post_keys(
-        source: StreamSource,
-        pagination: Pagination,
-        order: SortOrder,
-        sorting: StreamSorting,
-        tags: Option<Vec<String>>,
-        kind: Option<PubkyAppPostKind>,
-    ) -> ModelResult<PostKeyStream> {
+    async fn collect_post_keys(params: PostStreamParams) -> ModelResult<PostKeyStream> {
+        let PostStreamParams {
+            source,
+            pagination,
+            order,
+            sorting,
+            tags,
+            kind,
+            quotes_only,
+            min_engagement,
+        } = params;
+        let min_engagement = min_engagement.unwrap_or_default();
+
         // Decide whether to use index or fallback to graph query
-        let use_index = Self::can_use_index(&sorting, &source, &tags, &kind);
+        // Quote-posts are only told apart in the graph
+        let use_index = !quotes_only && Self::can_use_index(&sorting, &source, &tags, &kind);
 
         let post_keys = match use_index {
-            true => Self::get_from_index(source, sorting, order, &tags, pagination).await?,
-            false => Self::get_from_graph(source, sorting, &tags, pagination, kind).await?,
+            true => {
+                Self::get_from_index(source, sorting, order, &tags, pagination, min_engagement)
+                    .await?
+            }
+            false => {
+                Self::get_from_graph(
+                    source,
+                    sorting,
+                    &tags,
+                    pagination,
+                    kind,
+                    quotes_only,
+                    min_engagement,
+                )
+                .await?
+            }
         };
 
         Ok(post_keys)
@@ -207,36 +333,68 @@ impl PostStream {
         order: SortOrder,
         tags: &Option<Vec<String>>,
         pagination: Pagination,
+        min_engagement: u64,
     ) -> ModelResult<PostKeyStream> {
         let start = pagination.start;
         let end = pagination.end;
         let skip = pagination.skip;
         let limit = pagination.limit;
 
+        // The engagement sorted sets are scored by the number of interactions of the posts
+        let engagement_end = match sorting {
+            StreamSorting::TotalEngagement => {
+                Some(end.unwrap_or(f64::MIN).max(min_engagement as f64))
+            }
+            StreamSorting::Timeline => end,
+        };
+
         let result = match (source, tags) {
             // Global post streams
             (StreamSource::All, None) => {
-                Self::get_global_posts_keys(sorting, order, start, end, skip, limit).await?
+                Self::get_global_posts_keys(sorting, order, start, engagement_end, skip, limit)
+                    .await?
             }
             // Streams by tags
             (StreamSource::All, Some(tags)) if tags.len() == 1 => {
-                Self::get_posts_keys_by_tag(&tags[0], sorting, start, end, skip, limit).await?
+                Self::get_posts_keys_by_tag(&tags[0], sorting, start, engagement_end, skip, limit)
+                    .await?
             }
             // Bookmark streams
-            (StreamSource::Bookmarks { observer_id }, None) => {
-                Self::get_bookmarked_posts(&observer_id, order, start, end, skip, limit).await?
+            (
+                StreamSource::Bookmarks {
+                    observer_id,
+                    timeframe,
+                },
+                None,
+            ) => {
+                Self::get_bookmarked_posts(
+                    &observer_id,
+                    timeframe.as_ref(),
+                    order,
+                    start,
+                    end,
+                    skip,
+                    limit,
+                )
+                .await?
             }
             // Stream of replies to specific a post
             (StreamSource::PostReplies { author_id, post_id }, None) => {
-                Self::get_post_replies(&author_id, &post_id, order, start, end, skip, limit).await?
+                let replies =
+                    Self::get_post_replies(&author_id, &post_id, order, start, end, skip, limit)
+                        .await?;
+                Self::retain_min_engagement(replies, &sorting, min_engagement).await?
             }
             // Stream of parent post from a given author
-            (StreamSource::Author { author_id }, None) => {
+            (StreamSource::Author { author_id, .. }, None) => {
                 Self::get_author_posts(&author_id, order, start, end, skip, limit, false).await?
             }
             // Streams of replies from a given author
             (StreamSource::AuthorReplies { author_id }, None) => {
-                Self::get_author_posts(&author_id, order, start, end, skip, limit, true).await?
+                let replies =
+                    Self::get_author_posts(&author_id, order, start, end, skip, limit, true)
+                        .await?;
+                Self::retain_min_engagement(replies, &sorting, min_engagement).await?
             }
             // Streams by simple source/reach: Following, Followers, Friends
             (source, None) => {
@@ -247,6 +405,33 @@ impl PostStream {
         Ok(result)
     }
 
+    /// Leaves out of a page of replies the posts with fewer than `min_engagement` interactions,
+    /// when ranked by engagement. The reply sorted sets are scored by time, so unlike the
+    /// engagement sorted sets they cannot be bounded by score
+    async fn retain_min_engagement(
+        stream: PostKeyStream,
+        sorting: &StreamSorting,
+        min_engagement: u64,
+    ) -> ModelResult<PostKeyStream> {
+        if *sorting != StreamSorting::TotalEngagement || min_engagement == 0 || stream.is_empty() {
+            return Ok(stream);
+        }
+        let counts = PostCounts::get_by_ids(&stream.post_keys).await?;
+        let post_keys = stream
+            .post_keys
+            .into_iter()
+            .zip(counts)
+            .filter(|(_, counts)| {
+                counts.as_ref().is_some_and(|counts| {
+                    u64::from(counts.tags + counts.replies + counts.reposts) >= min_engagement
+                })
+            })
+            .map(|(post_key, _)| post_key)
+            .collect();
+        // The cursor stays the score of the last reply of the page, so that no reply is skipped
+        Ok(PostKeyStream::new(post_keys, stream.last_post_score))
+    }
+
     // Fetch posts from index
     async fn get_from_graph(
         source: StreamSource,
@@ -254,25 +439,27 @@ impl PostStream {
         tags: &Option<Vec<String>>,
         pagination: Pagination,
         kind: Option<PubkyAppPostKind>,
+        quotes_only: bool,
+        min_engagement: u64,
     ) -> GraphResult<PostKeyStream> {
-        let mut result;
-        {
-            let graph = get_neo4j_graph()?;
-            let query = queries::get::post_stream(source, sorting, tags, pagination, kind);
-
-            // Set a 10-second timeout for the query execution
-            result = match timeout(Duration::from_secs(10), graph.execute(query)).await {
-                Ok(Ok(res)) => res, // Successfully executed within the timeout
-                Ok(Err(e)) => return Err(GraphError::QueryFailed(e)), // Query failed
-                Err(_) => return Err(GraphError::QueryTimeout), // Timeout error
-            };
-        }
+        let with_engagement = sorting == StreamSorting::TotalEngagement;
+        let query = queries::get::post_stream(
+            source,
+            sorting,
+            tags,
+            pagination,
+            kind,
+            quotes_only,
+            min_engagement,
+        );
+        let rows = fetch_all_rows_from_graph_with_timeout(query, POST_STREAM_QUERY_TIMEOUT).await?;
 
         let mut post_keys = Vec::new();
+        let mut engagement = Vec::new();
         // Track the last post's indexed_at value
         let mut last_post_indexed_at: Option<i64> = None;
 
-        while let Some(row) = result.try_next().await? {
+        for row in rows {
             let author_id: String = row.get("author_id")?;
             let post_id: String = row.get("post_id")?;
             let indexed_at: i64 = row.get("indexed_at")?;
@@ -280,12 +467,54 @@ impl PostStream {
             // Track the last post's indexed_at by overwriting on each iteration
             last_post_indexed_at = Some(indexed_at);
             post_keys.push(post_key);
+            if with_engagement {
+                engagement.push(PostEngagementCounts {
+                    tags: row.get::<i64>("tags_count")? as u32,
+                    replies: row.get::<i64>("replies_count")? as u32,
+                    reposts: row.get::<i64>("reposts_count")? as u32,
+                });
+            }
         }
 
         // Convert the last indexed_at to u64 for the score
         let last_post_score = last_post_indexed_at.map(|indexed_at| indexed_at as u64);
 
-        Ok(PostKeyStream::new(post_keys, last_post_score))
+        let mut post_key_stream = PostKeyStream::new(post_keys, last_post_score);
+        if with_engagement {
+            post_key_stream.engagement = Some(engagement);
+        }
+        Ok(post_key_stream)
+    }
+
+    /// Audits one batch of the global post timeline, reporting the `author_id:post_id` keys that
+    /// have no post node in the graph.
+    ///
+    /// Start with `cursor = 0` and pass the returned [OrphanReport::next_cursor] to continue the
+    /// audit, until it is `None`. `batch_size` is a hint of the number of keys checked per call.
+    pub async fn find_orphans(cursor: u64, batch_size: usize) -> ModelResult<OrphanReport> {
+        let (next_cursor, post_keys) =
+            Self::scan_index_sorted_set(&POST_TIMELINE_KEY_PARTS, cursor, batch_size).await?;
+
+        let mut orphans = Vec::new();
+        let mut key_pairs = Vec::with_capacity(post_keys.len());
+        for post_key in &post_keys {
+            match post_key.split_once(':') {
+                Some((author_id, post_id)) => key_pairs.push([author_id, post_id]),
+                // A malformed key cannot have a backing post
+                None => orphans.push(post_key.clone()),
+            }
+        }
+
+        if !key_pairs.is_empty() {
+            let key_pairs: Vec<&[&str]> = key_pairs.iter().map(|pair| pair.as_slice()).collect();
+            let query = queries::get::get_missing_post_keys(&key_pairs);
+            let missing: Vec<String> = fetch_key_from_graph(query, "missing")
+                .await?
+                .unwrap_or_default();
+            orphans.extend(missing);
+        }
+
+        Ok(OrphanReport::new(post_keys.len(), orphans, next_cursor))
     }
 
     pub async fn get_global_posts_keys(
@@ -446,14 +675,25 @@ impl PostStream {
         }
     }
 
+    /// Retrieves the keys of the posts bookmarked by a user, scored by the time they were bookmarked.
+    /// When a `timeframe` is given, bookmarks created before it are excluded.
     pub async fn get_bookmarked_posts(
         user_id: &str,
+        timeframe: Option<&Timeframe>,
         order: SortOrder,
         start: Option<f64>,
         end: Option<f64>,
         skip: Option<usize>,
         limit: Option<usize>,
     ) -> RedisResult<PostKeyStream> {
+        // The timeframe narrows the lower score bound, keeping a stricter `end` if one was given
+        let end = match timeframe {
+            Some(timeframe) => {
+                let (since, _) = timeframe.to_timestamp_range();
+                Some(end.map_or(since as f64, |end| end.max(since as f64)))
+            }
+            None => end,
+        };
         let key_parts = [&BOOKMARKS_USER_KEY_PARTS[..], &[user_id]].concat();
         let post_keys =
             Self::try_from_index_sorted_set(&key_parts, start, end, skip, limit, order, None)
@@ -482,6 +722,115 @@ impl PostStream {
         ))
     }
 
+    /// Retrieves the replies of a post in thread order, see [Self::walk_thread_replies]. Pages past
+    /// [MAX_THREAD_REPLIES] replies are empty.
+    pub async fn get_thread_reply_keys(
+        author_id: &str,
+        post_id: &str,
+        max_depth: usize,
+        skip: usize,
+        limit: usize,
+    ) -> RedisResult<PostKeyStream> {
+        let wanted = skip.saturating_add(limit);
+        let replies =
+            Self::walk_thread_replies(author_id, post_id, max_depth, None, wanted).await?;
+
+        let post_keys = replies
+            .into_iter()
+            .map(|(reply_key, _)| reply_key)
+            .skip(skip)
+            .take(limit)
+            .collect();
+        Ok(PostKeyStream::new(post_keys, None))
+    }
+
+    /// Walks the replies of a post in thread order: each reply is followed by its own replies
+    /// before its next sibling, and siblings are in chronological order.
+    ///
+    /// Returns the reply keys paired with their depth, the direct replies of the post being at
+    /// depth 1. Replies nested deeper than `max_depth` (bounded by [MAX_THREAD_REPLY_DEPTH]) are
+    /// left out, only the oldest `limit_replies` replies of each post are walked, and the walk
+    /// stops after `max_replies` replies (bounded by [MAX_THREAD_REPLIES]).
+    pub async fn walk_thread_replies(
+        author_id: &str,
+        post_id: &str,
+        max_depth: usize,
+        limit_replies: Option<usize>,
+        max_replies: usize,
+    ) -> RedisResult<Vec<(String, usize)>> {
+        let max_depth = max_depth.min(MAX_THREAD_REPLY_DEPTH);
+        let max_replies = max_replies.min(MAX_THREAD_REPLIES);
+        let limit_replies = limit_replies.unwrap_or(usize::MAX);
+        let mut thread_replies = Vec::new();
+
+        // Depth-first walk: replies are pushed newest first, so the oldest one is visited next
+        let mut pending: Vec<(String, usize)> = Vec::new();
+        if max_depth > 0 && max_replies > 0 {
+            let limit = limit_replies.min(max_replies);
+            pending.extend(Self::get_oldest_reply_keys(author_id, post_id, limit, 1).await?);
+        }
+
+        while let Some((reply_key, depth)) = pending.pop() {
+            // A reply is followed by its own replies, so only the first `remaining` of them can fit
+            let remaining = max_replies - thread_replies.len() - 1;
+            if depth < max_depth && remaining > 0 {
+                if let Some((reply_author_id, reply_id)) = reply_key.split_once(':') {
+                    let limit = limit_replies.min(remaining);
+                    pending.extend(
+                        Self::get_oldest_reply_keys(reply_author_id, reply_id, limit, depth + 1)
+                            .await?,
+                    );
+                }
+            }
+            thread_replies.push((reply_key, depth));
+            if thread_replies.len() >= max_replies {
+                break;
+            }
+        }
+
+        Ok(thread_replies)
+    }
+
+    /// Retrieves the keys of the `limit` oldest replies of a post, newest first and paired with
+    /// their `depth`, ready to be pushed on the stack of a depth-first walk
+    async fn get_oldest_reply_keys(
+        author_id: &str,
+        post_id: &str,
+        limit: usize,
+        depth: usize,
+    ) -> RedisResult<Vec<(String, usize)>> {
+        let replies = Self::get_post_replies(
+            author_id,
+            post_id,
+            SortOrder::Ascending,
+            None,
+            None,
+            None,
+            Some(limit),
+        )
+        .await?;
+        Ok(replies
+            .post_keys
+            .into_iter()
+            .rev()
+            .map(|key| (key, depth))
+            .collect())
+    }
+
+    /// Retrieves the views of the replies of a post in thread order, see [Self::get_thread_reply_keys]
+    pub async fn get_thread_replies(
+        author_id: &str,
+        post_id: &str,
+        viewer_id: Option<String>,
+        max_depth: usize,
+        skip: usize,
+        limit: usize,
+    ) -> ModelResult<Option<Self>> {
+        let stream =
+            Self::get_thread_reply_keys(author_id, post_id, max_depth, skip, limit).await?;
+        Self::from_listed_post_ids(viewer_id, &stream.post_keys).await
+    }
+
     // Streams for followers / followings / friends are expensive.
     // We are truncating to the first 200 user_ids. We could also random draw 200.
     // TODO rethink, we could also fallback to graph
@@ -547,36 +896,41 @@ impl PostStream {
         Ok(selected_post_keys)
     }
 
+    /// Retrieves the listed posts, leaving out the missing ones. A post that fails to load is
+    /// logged and left out too, the stream only fails when none of the posts could be loaded
     pub async fn from_listed_post_ids(
         viewer_id: Option<String>,
         post_keys: &[String],
     ) -> ModelResult<Option<Self>> {
-        let viewer_id = viewer_id.map(|id| id.to_string());
-        let mut handles = Vec::with_capacity(post_keys.len());
-
-        for post_key in post_keys {
-            let Some((author_id, post_id)) = post_key.split_once(':') else {
-                warn!("Invalid post_key format (missing ':'): {post_key}");
-                continue;
-            };
-            let author_id = author_id.to_string();
-            let viewer_id = viewer_id.clone();
-            let post_id = post_id.to_string();
-            let handle = spawn(async move {
-                PostView::get_by_id(&author_id, &post_id, viewer_id.as_deref(), None, None).await
-            });
-            handles.push(handle);
-        }
-
-        let mut post_views = Vec::with_capacity(post_keys.len());
+        let post_keys: Vec<(String, String)> = post_keys
+            .iter()
+            .filter_map(|post_key| match post_key.split_once(':') {
+                Some((author_id, post_id)) => Some((author_id.to_string(), post_id.to_string())),
+                None => {
+                    warn!("Invalid post_key format (missing ':'): {post_key}");
+                    None
+                }
+            })
+            .collect();
 
-        for handle in handles {
-            if let Some(post_view) = handle.await.map_err(ModelError::from_generic)?? {
-                post_views.push(post_view);
+        let results = PostView::get_each_by_ids(&post_keys, viewer_id.as_deref()).await?;
+
+        let mut post_views = Vec::with_capacity(results.len());
+        let mut last_error = None;
+        for ((author_id, post_id), result) in post_keys.iter().zip(results) {
+            match result {
+                Ok(post_view) => post_views.extend(post_view),
+                Err(e) => {
+                    warn!("Failed to get the listed post {author_id}:{post_id}: {e}");
+                    last_error = Some(e);
+                }
             }
         }
 
-        Ok(Some(Self(post_views)))
+        match last_error {
+            Some(e) if post_views.is_empty() => Err(e),
+            _ => Ok(Some(Self(post_views))),
+        }
     }
 
     /// Adds the post to a Redis sorted set using the `indexed_at` timestamp as the score.
@@ -630,7 +984,19 @@ impl PostStream {
         let key_parts = [&POST_REPLIES_PER_POST_KEY_PARTS[..], parent_post_key_parts].concat();
         let score = indexed_at as f64;
         let element = format!("{author_id}:{reply_id}");
-        Self::put_index_sorted_set(&key_parts, &[(score, element.as_str())], None, None).await
+        Self::put_index_sorted_set(&key_parts, &[(score, element.as_str())], None, None).await?;
+
+        let [parent_author_id, parent_post_id] = parent_post_key_parts;
+        if *parent_author_id == author_id {
+            Self::add_to_self_threads_set(author_id, parent_post_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Adds the post to the Redis set of the posts its author continued with replies to themselves.
+    pub async fn add_to_self_threads_set(author_id: &str, post_id: &str) -> RedisResult<()> {
+        let key_parts = [&POST_SELF_THREADS_PER_USER_KEY_PARTS[..], &[author_id]].concat();
+        Self::put_index_set(&key_parts, &[post_id], None, None).await
     }
 
     /// Adds the post response to a Redis sorted set using the `indexed_at` timestamp as the score.
@@ -641,7 +1007,19 @@ impl PostStream {
     ) -> RedisResult<()> {
         let key_parts = [&POST_REPLIES_PER_POST_KEY_PARTS[..], parent_post_key_parts].concat();
         let element = format!("{author_id}:{reply_id}");
-        Self::remove_from_index_sorted_set(None, &key_parts, &[element.as_str()]).await
+        Self::remove_from_index_sorted_set(None, &key_parts, &[element.as_str()]).await?;
+
+        // The post only leaves the self-threads of its author with the last of their own replies
+        let [parent_author_id, parent_post_id] = parent_post_key_parts;
+        if *parent_author_id == author_id
+            && !Self::has_self_reply(author_id, parent_post_id).await?
+        {
+            let key = [&POST_SELF_THREADS_PER_USER_KEY_PARTS[..], &[author_id]]
+                .concat()
+                .join(":");
+            sets::del(&Self::prefix().await, &key, &[*parent_post_id]).await?;
+        }
+        Ok(())
     }
 
     /// Adds the post to a Redis sorted set of replies per author using the `indexed_at` timestamp as the score.
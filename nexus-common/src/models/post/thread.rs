@@ -0,0 +1,131 @@
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{PostStream, PostView, MAX_THREAD_REPLIES};
+use crate::models::error::ModelResult;
+
+/// Content of a post that was deleted while other posts still reference it (e.g. its replies)
+pub const POST_DELETED_CONTENT: &str = "[DELETED]";
+
+/// A post of a reply thread, with its replies in chronological order
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct PostThreadNode {
+    pub author_id: String,
+    pub post_id: String,
+    /// Whether the post was deleted. Deleted posts are tombstones: they keep their place in the
+    /// thread so that their replies are not orphaned, but have no view
+    pub deleted: bool,
+    pub view: Option<PostView>,
+    #[schema(no_recursion)]
+    pub replies: Vec<PostThreadNode>,
+}
+
+/// Options to reconstruct a reply thread with [PostView::get_thread]
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadOptions {
+    /// Levels of replies below the root post
+    pub depth: usize,
+    /// Upper limit on the number of replies per post
+    pub limit_replies: usize,
+    /// Represent deleted posts as tombstones. Otherwise they are dropped with all their replies
+    pub include_tombstones: bool,
+}
+
+impl PostView {
+    /// Reconstructs the reply thread of a post, walking its replies with
+    /// [PostStream::walk_thread_replies]. The thread holds at most [MAX_THREAD_REPLIES] replies.
+    ///
+    /// Returns `None` if the post does not exist, or if it was deleted and tombstones are not included.
+    ///
+    /// # Arguments
+    ///
+    /// * `author_id` - The ID of the root post author
+    /// * `post_id` - The ID of the root post
+    /// * `viewer_id` - The ID of the viewer, used for the views of the posts
+    /// * `options` - Depth, replies limit and handling of deleted posts
+    pub async fn get_thread(
+        author_id: &str,
+        post_id: &str,
+        viewer_id: Option<&str>,
+        options: ThreadOptions,
+    ) -> ModelResult<Option<PostThreadNode>> {
+        let replies = PostStream::walk_thread_replies(
+            author_id,
+            post_id,
+            options.depth,
+            Some(options.limit_replies),
+            MAX_THREAD_REPLIES,
+        )
+        .await?;
+
+        // The root post comes first in thread order, at depth 0
+        let mut posts = vec![(author_id.to_string(), post_id.to_string(), 0)];
+        posts.extend(replies.into_iter().filter_map(|(reply_key, depth)| {
+            let (reply_author_id, reply_id) = reply_key.split_once(':')?;
+            Some((reply_author_id.to_string(), reply_id.to_string(), depth))
+        }));
+
+        let mut views = try_join_all(posts.iter().map(|(author_id, post_id, _)| {
+            PostView::get_by_id(author_id, post_id, viewer_id, None, None)
+        }))
+        .await?;
+
+        // In thread order, the parent of a reply is the last post seen one level above it
+        let mut replies: Vec<Vec<usize>> = vec![Vec::new(); posts.len()];
+        let mut ancestors: Vec<usize> = Vec::new();
+        for (index, (_, _, depth)) in posts.iter().enumerate() {
+            ancestors.truncate(*depth);
+            if let Some(&parent) = ancestors.last() {
+                replies[parent].push(index);
+            }
+            ancestors.push(index);
+        }
+
+        Ok(build_thread_node(
+            0,
+            &posts,
+            &mut views,
+            &replies,
+            options.include_tombstones,
+        ))
+    }
+}
+
+/// Builds the node of the post at `index` of the thread `posts`, with the nodes of its `replies`
+fn build_thread_node(
+    index: usize,
+    posts: &[(String, String, usize)],
+    views: &mut [Option<PostView>],
+    replies: &[Vec<usize>],
+    include_tombstones: bool,
+) -> Option<PostThreadNode> {
+    let view = views[index].take();
+    let deleted = match &view {
+        Some(view) => view.details.content == POST_DELETED_CONTENT,
+        // A post without a view can still have replies if the thread is being indexed
+        None => true,
+    };
+    if deleted && !include_tombstones {
+        return None;
+    }
+
+    let node_replies: Vec<PostThreadNode> = replies[index]
+        .iter()
+        .filter_map(|&reply| build_thread_node(reply, posts, views, replies, include_tombstones))
+        .collect();
+
+    // A post missing from the index without replies is not part of the thread anymore
+    if view.is_none() && node_replies.is_empty() {
+        return None;
+    }
+
+    let (author_id, post_id, _) = &posts[index];
+    Some(PostThreadNode {
+        author_id: author_id.clone(),
+        post_id: post_id.clone(),
+        deleted,
+        view: view.filter(|_| !deleted),
+        replies: node_replies,
+    })
+}
@@ -1,11 +1,19 @@
+use pubky_app_specs::Resource;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tokio::task::spawn;
 use utoipa::ToSchema;
 
-use super::{Bookmark, PostCounts, PostDetails, PostRelationships};
-use crate::models::error::ModelResult;
+use super::{Bookmark, PostCounts, PostDetails, PostRelationships, POST_DELETED_CONTENT};
+use crate::config::DeletedRepostMode;
+use crate::db::kv::single_flight;
+use crate::db::{fetch_row_from_graph, queries, RedisOps};
+use crate::models::error::{ModelError, ModelResult};
+use crate::models::moderation::ModerationInfo;
 use crate::models::tag::post::TagPost;
 use crate::models::tag::traits::TagCollection;
 use crate::models::tag::TagDetails;
+use crate::StackManager;
 
 /// Represents a Pubky user with relational data including tags, counts, and relationship with a viewer.
 #[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
@@ -15,6 +23,39 @@ pub struct PostView {
     pub tags: Vec<TagDetails>,
     pub relationships: PostRelationships,
     pub bookmark: Option<Bookmark>,
+    /// Whether the author continued this post with a chain of replies to themselves.
+    /// Only set on author streams that collapse self-reply threads into their root post.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub has_self_thread: bool,
+    /// Why the post was removed by the moderator. Only set on the placeholder of a moderated
+    /// post, returned to its moderator instead of omitting the post
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderated: Option<ModerationInfo>,
+    /// Whether the post is a repost of a post that was deleted since. Only set on the views
+    /// passed through [PostView::apply_deleted_repost_mode], when reposts of deleted posts are
+    /// not hidden
+    #[serde(default)]
+    pub original_deleted: bool,
+}
+
+/// A user that tagged a post, with the number of tags they put on it
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug, PartialEq)]
+pub struct PostTaggerEngagement {
+    pub user_id: String,
+    pub tags: u32,
+}
+
+/// Breakdown of the engagement a post received, beyond its raw [PostCounts]
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
+pub struct PostEngagementBreakdown {
+    pub counts: PostCounts,
+    /// Taggers with the most tags on the post, in descending order
+    pub top_taggers: Vec<PostTaggerEngagement>,
+    /// Number of distinct users that replied to the post
+    pub unique_repliers: u32,
+    /// Distinct users that reposted the post
+    pub repost_authors: Vec<String>,
+    pub bookmarks: u32,
 }
 
 impl PostView {
@@ -26,6 +67,17 @@ impl PostView {
         limit_tags: Option<usize>,
         limit_taggers: Option<usize>,
     ) -> ModelResult<Option<Self>> {
+        // On a cache miss, let a single caller rebuild the post from the graph while
+        // concurrent callers wait and then read the freshly warmed cache
+        let _guard = match StackManager::config().single_flight_on_read_miss
+            && PostDetails::get_from_index(author_id, post_id)
+                .await?
+                .is_none()
+        {
+            true => Some(single_flight::acquire(format!("PostView:{author_id}:{post_id}")).await),
+            false => None,
+        };
+
         // Perform all operations concurrently
         let (details, counts, bookmark, relationships) = tokio::try_join!(
             PostDetails::get_by_id(author_id, post_id),
@@ -68,6 +120,175 @@ impl PostView {
             bookmark,
             relationships,
             tags,
+            has_self_thread: false,
+            moderated: None,
+            original_deleted: false,
+        }))
+    }
+
+    /// Retrieves the views of several posts, identified by their `(author_id, post_id)`.
+    ///
+    /// Returns one view per post in the same order, `None` for the posts that don't exist.
+    /// The reposts of deleted posts are flagged or left out, depending on
+    /// [StackConfig::deleted_repost_mode](crate::StackConfig::deleted_repost_mode).
+    pub async fn get_by_ids(
+        post_keys: &[(String, String)],
+        viewer_id: Option<&str>,
+    ) -> ModelResult<Vec<Option<Self>>> {
+        Self::get_each_by_ids(post_keys, viewer_id)
+            .await?
+            .into_iter()
+            .collect()
+    }
+
+    /// Retrieves the views of several posts like [Self::get_by_ids], a post that fails to load
+    /// yielding its own error instead of failing the others
+    pub async fn get_each_by_ids(
+        post_keys: &[(String, String)],
+        viewer_id: Option<&str>,
+    ) -> ModelResult<Vec<ModelResult<Option<Self>>>> {
+        let mut handles = Vec::with_capacity(post_keys.len());
+        for (author_id, post_id) in post_keys {
+            let author_id = author_id.clone();
+            let post_id = post_id.clone();
+            let viewer_id = viewer_id.map(String::from);
+            handles.push(spawn(async move {
+                Self::get_by_id(&author_id, &post_id, viewer_id.as_deref(), None, None).await
+            }));
+        }
+
+        let mut results = Vec::with_capacity(post_keys.len());
+        for handle in handles {
+            results.push(
+                handle
+                    .await
+                    .unwrap_or_else(|e| Err(ModelError::from_generic(e))),
+            );
+        }
+
+        let mut views: Vec<Option<Self>> = results
+            .iter_mut()
+            .map(|result| result.as_mut().ok().and_then(Option::take))
+            .collect();
+        let mode = StackManager::config().deleted_repost_mode;
+        Self::apply_deleted_repost_mode(&mut views, mode).await?;
+        for (result, view) in results.iter_mut().zip(views) {
+            if let Ok(slot) = result {
+                *slot = view;
+            }
+        }
+        Ok(results)
+    }
+
+    /// Flags the views that repost a post which was deleted since, or removes them with
+    /// [DeletedRepostMode::Hide]
+    pub async fn apply_deleted_repost_mode(
+        views: &mut [Option<Self>],
+        mode: DeletedRepostMode,
+    ) -> ModelResult<()> {
+        let reposted: Vec<(String, String)> = views
+            .iter()
+            .flatten()
+            .filter_map(|view| view.reposted_key())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if reposted.is_empty() {
+            return Ok(());
+        }
+
+        // The reposted posts are read from the index at once, only the misses go to the graph
+        let key_parts: Vec<[&str; 2]> = reposted
+            .iter()
+            .map(|(author_id, post_id)| [author_id.as_str(), post_id.as_str()])
+            .collect();
+        let key_parts: Vec<&[&str]> = key_parts.iter().map(|parts| &parts[..]).collect();
+        let indexed = PostDetails::try_from_index_multiple_json(&key_parts).await?;
+
+        let mut deleted = HashSet::new();
+        for ((author_id, post_id), details) in reposted.into_iter().zip(indexed) {
+            let details = match details {
+                Some(details) => Some(details),
+                None => PostDetails::get_by_id(&author_id, &post_id).await?,
+            };
+            let is_deleted = match details {
+                Some(details) => details.content == POST_DELETED_CONTENT,
+                None => true,
+            };
+            if is_deleted {
+                deleted.insert((author_id, post_id));
+            }
+        }
+        if deleted.is_empty() {
+            return Ok(());
+        }
+
+        for slot in views.iter_mut() {
+            let Some(view) = slot else { continue };
+            if !view
+                .reposted_key()
+                .is_some_and(|key| deleted.contains(&key))
+            {
+                continue;
+            }
+            match mode {
+                DeletedRepostMode::Hide => *slot = None,
+                DeletedRepostMode::Tombstone => view.original_deleted = true,
+            }
+        }
+        Ok(())
+    }
+
+    /// The `(author_id, post_id)` of the post this view reposts, if any
+    fn reposted_key(&self) -> Option<(String, String)> {
+        let reposted = self.relationships.reposted.as_ref()?;
+        match &reposted.resource {
+            Resource::Post(post_id) => Some((reposted.user_id.to_string(), post_id.clone())),
+            _ => None,
+        }
+    }
+
+    /// Placeholder of a post removed by the moderator, carrying only its ID and the moderation
+    pub fn moderated(author_id: &str, post_id: &str, moderation: ModerationInfo) -> Self {
+        Self {
+            details: PostDetails {
+                id: post_id.to_string(),
+                author: author_id.to_string(),
+                ..Default::default()
+            },
+            moderated: Some(moderation),
+            ..Default::default()
+        }
+    }
+
+    /// Retrieves the engagement breakdown of a post, reading its counts from the index
+    /// and the per user engagement from the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `author_id` - The ID of the post author
+    /// * `post_id` - The ID of the post
+    /// * `limit_taggers` - Upper limit on the number of top taggers
+    pub async fn engagement_breakdown(
+        author_id: &str,
+        post_id: &str,
+        limit_taggers: usize,
+    ) -> ModelResult<Option<PostEngagementBreakdown>> {
+        let query = queries::get::post_engagement_breakdown(author_id, post_id, limit_taggers);
+        let (counts, row) = tokio::try_join!(PostCounts::get_by_id(author_id, post_id), async {
+            fetch_row_from_graph(query).await.map_err(ModelError::from)
+        })?;
+
+        let (Some(counts), Some(row)) = (counts, row) else {
+            return Ok(None);
+        };
+
+        Ok(Some(PostEngagementBreakdown {
+            counts,
+            top_taggers: row.get("top_taggers")?,
+            unique_repliers: row.get("unique_repliers")?,
+            repost_authors: row.get("repost_authors")?,
+            bookmarks: row.get("bookmarks")?,
         }))
     }
 }
@@ -0,0 +1,156 @@
+use crate::db::kv::{hashes, RedisResult};
+use crate::db::{fetch_all_rows_from_graph, queries, RedisOps};
+use crate::models::error::{ModelError, ModelResult};
+use crate::models::post::search::{
+    PostsByTagSearch, TAG_GLOBAL_POST_ENGAGEMENT, TAG_GLOBAL_POST_TIMELINE,
+};
+use crate::models::post::PostCounts;
+use crate::models::tag::label;
+use crate::models::tag::post::{TagPost, POST_TAGS_KEY_PARTS};
+use crate::models::tag::search::TagSearch;
+use crate::models::tag::stream::HotTags;
+use crate::models::tag::traits::{TagCollection, TaggersCollection};
+use crate::models::tag::user::{TagUser, USER_TAGS_KEY_PARTS};
+use crate::models::user::UserCounts;
+use std::collections::{HashMap, HashSet};
+
+/// Redis hash mapping each alias label to its canonical label
+pub const TAG_ALIAS: [&str; 2] = ["Tag", "Alias"];
+
+/// Tag aliases, so that synonyms (e.g. `js` and `javascript`) are indexed and queried as one label
+pub struct TagAlias;
+
+impl TagAlias {
+    /// Returns the label under which `label` is indexed: its normalized form, replaced by the
+    /// canonical label if it is an alias
+    pub async fn canonical(label: &str) -> RedisResult<String> {
+        let label = label::normalize(label);
+        let canonical = hashes::get(TAG_ALIAS[0], TAG_ALIAS[1], &label).await?;
+        Ok(canonical.unwrap_or(label))
+    }
+
+    /// Returns all the aliases, mapped to their canonical label
+    pub async fn get_all() -> RedisResult<HashMap<String, String>> {
+        hashes::get_all(TAG_ALIAS[0], TAG_ALIAS[1]).await
+    }
+
+    /// Makes `alias` an alias of `target`. The tags already using `alias` are folded into the
+    /// canonical label of `target`, and the aliases of `alias` are redirected to it.
+    ///
+    /// Returns the canonical label `alias` now resolves to
+    pub async fn put(alias: &str, target: &str) -> ModelResult<String> {
+        let alias = label::normalize(alias);
+        let canonical = Self::canonical(target).await?;
+        if alias.is_empty() || canonical.is_empty() {
+            return Err(ModelError::from_generic("Tag alias labels cannot be empty"));
+        }
+        if alias == canonical {
+            return Err(ModelError::from_generic(format!(
+                "Tag alias {alias} cannot resolve to itself"
+            )));
+        }
+
+        // Keep the mapping flat, chained aliases resolve in a single lookup
+        for (other, other_canonical) in Self::get_all().await? {
+            if other_canonical == alias {
+                hashes::put(TAG_ALIAS[0], TAG_ALIAS[1], &other, &canonical).await?;
+            }
+        }
+        hashes::put(TAG_ALIAS[0], TAG_ALIAS[1], &alias, &canonical).await?;
+
+        Self::fold(&alias, &canonical).await?;
+        Ok(canonical)
+    }
+
+    /// Removes `alias`. The tags folded when it was added keep the canonical label.
+    ///
+    /// Returns `false` if `alias` was not an alias
+    pub async fn del(alias: &str) -> RedisResult<bool> {
+        hashes::del(TAG_ALIAS[0], TAG_ALIAS[1], &label::normalize(alias)).await
+    }
+
+    /// Moves the existing `alias` tags to `canonical`, in the graph and in the indexes
+    async fn fold(alias: &str, canonical: &str) -> ModelResult<()> {
+        let rows =
+            fetch_all_rows_from_graph(queries::put::fold_tag_label(alias, canonical)).await?;
+
+        let mut taggers_ids = HashSet::new();
+        for row in rows {
+            let user_id: Option<String> = row.get("user_id").unwrap_or(None);
+            let post_id: Option<String> = row.get("post_id").unwrap_or(None);
+            let author_id: Option<String> = row.get("author_id").unwrap_or(None);
+            let taggers: Vec<String> = row.get("taggers").unwrap_or_default();
+
+            match (user_id, post_id, author_id) {
+                (Some(user_id), None, None) => {
+                    Self::fold_user(&user_id, alias, taggers.clone()).await?
+                }
+                (None, Some(post_id), Some(author_id)) => {
+                    Self::fold_post(&author_id, &post_id, alias, canonical, taggers.clone()).await?
+                }
+                _ => continue,
+            }
+            taggers_ids.extend(taggers);
+        }
+
+        // Taggers that used both labels on a target lost their alias tag, and the labels they
+        // applied moved to the canonical one
+        for tagger_id in taggers_ids {
+            UserCounts::reindex(&tagger_id).await?;
+            TagUser::reindex_tagger_labels(&tagger_id).await?;
+        }
+
+        TagSearch::put_to_index(&[canonical.to_string()]).await?;
+        TagSearch::del_from_index(alias).await?;
+        HotTags::clear_global_cache().await?;
+        Ok(())
+    }
+
+    async fn fold_user(user_id: &str, alias: &str, taggers: Vec<String>) -> ModelResult<()> {
+        TagUser(taggers)
+            .del_from_index(user_id, None, alias)
+            .await?;
+        let key_parts = [&USER_TAGS_KEY_PARTS[..], &[user_id]].concat();
+        TagUser::remove_from_index_sorted_set(None, &key_parts, &[alias]).await?;
+
+        TagUser::reindex(user_id, None).await?;
+        UserCounts::reindex(user_id).await
+    }
+
+    async fn fold_post(
+        author_id: &str,
+        post_id: &str,
+        alias: &str,
+        canonical: &str,
+        taggers: Vec<String>,
+    ) -> ModelResult<()> {
+        TagPost(taggers)
+            .del_from_index(author_id, Some(post_id), alias)
+            .await?;
+        let key_parts = [&POST_TAGS_KEY_PARTS[..], &[author_id, post_id]].concat();
+        TagPost::remove_from_index_sorted_set(None, &key_parts, &[alias]).await?;
+
+        TagPost::reindex(author_id, Some(post_id)).await?;
+        PostCounts::reindex(author_id, post_id).await?;
+
+        // Move the post from the alias to the canonical label search indexes
+        let post_key = format!("{author_id}:{post_id}");
+        for index_key in [TAG_GLOBAL_POST_TIMELINE, TAG_GLOBAL_POST_ENGAGEMENT] {
+            let alias_key_parts = [&index_key[..], &[alias]].concat();
+            PostsByTagSearch::remove_from_index_sorted_set(None, &alias_key_parts, &[&post_key])
+                .await?;
+        }
+        PostsByTagSearch::put_to_index(author_id, post_id, canonical).await?;
+        let taggers_count =
+            TagPost::check_sorted_set_member(None, &key_parts, &[canonical]).await?;
+        let engagement_key_parts = [&TAG_GLOBAL_POST_ENGAGEMENT[..], &[canonical]].concat();
+        PostsByTagSearch::put_index_sorted_set(
+            &engagement_key_parts,
+            &[(taggers_count.unwrap_or_default() as f64, &post_key)],
+            None,
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+}
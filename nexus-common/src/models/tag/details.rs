@@ -41,6 +41,19 @@ impl TagDetails {
             .collect()
     }
 
+    /// Sets the `relationship` flag of each tag, according to whether the viewer is one of its taggers.
+    /// Tags read from the graph do not carry it, unlike the ones read from the index.
+    /// # Arguments
+    /// * `tag_details` - A mutable slice of `TagDetails` instances.
+    /// * `viewer_id` - The viewer to look for in the taggers. If `None`, the tags are left untouched.
+    pub fn set_viewer_relationship(tag_details: &mut [TagDetails], viewer_id: Option<&str>) {
+        if let Some(viewer_id) = viewer_id {
+            for tag in tag_details.iter_mut() {
+                tag.relationship = tag.taggers.iter().any(|tagger| tagger == viewer_id);
+            }
+        }
+    }
+
     /// Splits fields of `TagDetails` and calculates scores based on the number of taggers.
     /// # Arguments
     /// * `tag_details` - A reference to a slice of `TagDetails` instances.
@@ -0,0 +1,125 @@
+use crate::db::kv::RedisResult;
+use crate::db::RedisOps;
+use crate::models::error::ModelResult;
+use crate::types::routes::HotTagsInputDTO;
+use crate::types::{StreamReach, Timeframe};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::label;
+use super::stream::HotTags;
+use super::TaggedType;
+
+/// Number of hot tags considered by [FollowedTags::suggest], both in the user network and globally
+const SUGGESTION_CANDIDATES: usize = 100;
+
+/// Tag labels followed by a user, the topics of their tag feed
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
+pub struct FollowedTags(pub Vec<String>);
+
+impl AsRef<[String]> for FollowedTags {
+    fn as_ref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl RedisOps for FollowedTags {}
+
+impl FollowedTags {
+    /// Retrieves the tag labels followed by a user
+    pub async fn get_by_id(user_id: &str) -> RedisResult<Self> {
+        let labels = Self::try_from_index_set(&[user_id], None, None, None).await?;
+        Ok(Self(labels.unwrap_or_default()))
+    }
+
+    /// Adds a tag to the followed tags of a user and returns its normalized label
+    pub async fn follow(user_id: &str, label: &str) -> RedisResult<String> {
+        let label = label::normalize(label);
+        Self::put_index_set(&[user_id], &[&label], None, None).await?;
+        Ok(label)
+    }
+
+    /// Removes a tag from the followed tags of a user
+    pub async fn unfollow(user_id: &str, label: &str) -> RedisResult<()> {
+        Self(vec![label::normalize(label)])
+            .remove_from_index_set(&[user_id])
+            .await
+    }
+
+    /// Suggests tags for a user to follow, excluding the tags they already follow.
+    ///
+    /// The hot tags of the user network (the users they follow) come first, followed by the
+    /// global hot tags.
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `limit` - Upper limit on the number of suggested tags
+    pub async fn suggest(user_id: &str, limit: usize) -> ModelResult<Vec<String>> {
+        let followed = Self::get_by_id(user_id).await?;
+        let input = HotTagsInputDTO::new(
+            Timeframe::AllTime,
+            SUGGESTION_CANDIDATES,
+            0,
+            1,
+            Some(TaggedType::Post),
+        );
+
+        let network_tags = HotTags::get_hot_tags(
+            Some(user_id.to_string()),
+            Some(StreamReach::Following),
+            &input,
+        )
+        .await?
+        .unwrap_or_default();
+        let global_tags = HotTags::get_hot_tags(None, None, &input)
+            .await?
+            .unwrap_or_default();
+
+        let mut suggestions: Vec<String> = Vec::with_capacity(limit);
+        for hot_tag in network_tags.iter().chain(global_tags.iter()) {
+            if suggestions.len() >= limit {
+                break;
+            }
+            if !followed.0.contains(&hot_tag.label) && !suggestions.contains(&hot_tag.label) {
+                suggestions.push(hot_tag.label.clone());
+            }
+        }
+        Ok(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+    use pubky::Keypair;
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_follow_and_unfollow_tags() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let user_id = Keypair::random().public_key().to_z32();
+        assert!(FollowedTags::get_by_id(&user_id).await?.0.is_empty());
+
+        assert_eq!(FollowedTags::follow(&user_id, " rust ").await?, "rust");
+        FollowedTags::follow(&user_id, "pubky").await?;
+        // Following a tag twice is a no-op
+        FollowedTags::follow(&user_id, "rust").await?;
+
+        let mut followed = FollowedTags::get_by_id(&user_id).await?.0;
+        followed.sort();
+        assert_eq!(followed, vec!["pubky", "rust"]);
+
+        FollowedTags::unfollow(&user_id, "rust").await?;
+        assert_eq!(FollowedTags::get_by_id(&user_id).await?.0, vec!["pubky"]);
+
+        // Followed tags are not suggested
+        let suggestions = FollowedTags::suggest(&user_id, 10).await?;
+        assert!(suggestions.len() <= 10);
+        assert!(!suggestions.contains(&"pubky".to_string()));
+
+        FollowedTags::unfollow(&user_id, "pubky").await?;
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    alias::TagAlias,
     stream::{HOT_TAGS_CACHE_PREFIX, POST_HOT_TAGS},
     Taggers as TaggersType,
 };
@@ -93,6 +94,7 @@ impl Taggers {
         limit: usize,
         timeframe: Timeframe,
     ) -> ModelResult<Option<TaggersType>> {
+        let label = TagAlias::canonical(&label).await?;
         let result = match user_id {
             None => Self::get_from_global_timeline(&label, skip, limit, timeframe).await?,
             Some(id) => {
@@ -176,7 +178,7 @@ impl Taggers {
     ///
     /// # Arguments
     /// * `timeframe` - A string slice representing the timeframe (e.g., "today", "this_month", "all_time")
-    fn build_key_parts(timeframe: &str) -> Vec<&str> {
+    pub(crate) fn build_key_parts(timeframe: &str) -> Vec<&str> {
         [&POST_HOT_TAGS[..], &[TAGGERS_INDEX], &[timeframe]].concat()
     }
 }
@@ -0,0 +1,57 @@
+use crate::StackManager;
+
+/// Returns `true` if tag labels keep their case, see
+/// [StackConfig::case_sensitive_tags](crate::StackConfig::case_sensitive_tags)
+pub fn is_case_sensitive() -> bool {
+    StackManager::config().case_sensitive_tags
+}
+
+/// Normalizes a tag label (or label prefix) used for indexing or lookups.
+///
+/// Labels are trimmed, and lowercased unless case-sensitive tags are enabled.
+pub fn normalize(label: &str) -> String {
+    normalize_with(label, is_case_sensitive())
+}
+
+fn normalize_with(label: &str, case_sensitive: bool) -> String {
+    let label = label.trim();
+    match case_sensitive {
+        true => label.to_string(),
+        false => label.to_lowercase(),
+    }
+}
+
+/// Restores the original case of a label sanitized by `pubky-app-specs`, which always lowercases.
+///
+/// The original label is only used if it sanitizes to the same value, so any other sanitization
+/// (e.g. trimming or truncation) is kept. Returns `sanitized` when case-sensitive tags are disabled.
+pub fn restore_case(sanitized: &str, original: &str) -> String {
+    restore_case_with(sanitized, original, is_case_sensitive())
+}
+
+fn restore_case_with(sanitized: &str, original: &str, case_sensitive: bool) -> String {
+    let original = original.trim();
+    match case_sensitive && original.to_lowercase() == sanitized {
+        true => original.to_string(),
+        false => sanitized.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_insensitive_labels() {
+        assert_eq!(normalize_with(" React ", false), "react");
+        assert_eq!(restore_case_with("react", "React", false), "react");
+    }
+
+    #[test]
+    fn test_case_sensitive_labels() {
+        assert_eq!(normalize_with(" React ", true), "React");
+        assert_eq!(restore_case_with("react", " React", true), "React");
+        // Sanitization other than lowercasing is kept
+        assert_eq!(restore_case_with("reac", "React", true), "reac");
+    }
+}
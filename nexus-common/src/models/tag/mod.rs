@@ -1,5 +1,8 @@
+pub mod alias;
 pub mod details;
+pub mod followed;
 pub mod global;
+pub mod label;
 pub mod post;
 pub mod search;
 pub mod stream;
@@ -3,6 +3,8 @@ use crate::db::queries::get::get_tags;
 use crate::db::{fetch_key_from_graph, RedisOps};
 use crate::models::create_zero_score_tuples;
 use crate::models::error::ModelResult;
+use crate::models::tag::alias::TagAlias;
+use crate::models::tag::label;
 use crate::types::Pagination;
 
 use serde::{Deserialize, Serialize};
@@ -28,13 +30,13 @@ impl TagSearch {
         label_prefix: &str,
         pagination: &Pagination,
     ) -> RedisResult<Option<Vec<TagSearch>>> {
-        let label_prefix_lowercase = label_prefix.to_lowercase();
-        let min_inclusive = format!("[{label_prefix_lowercase}");
+        let label_prefix = label::normalize(label_prefix);
+        let min_inclusive = format!("[{label_prefix}");
 
         // We mark the end of the label prefix upper bound with the maximum possible Unicode code point
         // Any valid Unicode string will be lexicographically smaller than a string ending with this character
         let max_unicode_char = char::MAX;
-        let max_exclusive = format!("({label_prefix_lowercase}{max_unicode_char}");
+        let max_exclusive = format!("({label_prefix}{max_unicode_char}");
 
         Self::try_from_index_sorted_set_lex(
             &TAGS_LABEL,
@@ -47,6 +49,27 @@ impl TagSearch {
         .map(|opt| opt.map(|list| list.into_iter().map(TagSearch).collect()))
     }
 
+    /// Same as [Self::get_by_label], but only matches the canonical label itself, so that
+    /// e.g. `rust` does not also return `rustlang`
+    pub async fn get_by_label_exact(
+        label: &str,
+        pagination: &Pagination,
+    ) -> RedisResult<Option<Vec<TagSearch>>> {
+        let label = TagAlias::canonical(label).await?;
+        // Inclusive on both ends, the range holds at most the label itself
+        let bound = format!("[{label}");
+
+        Self::try_from_index_sorted_set_lex(
+            &TAGS_LABEL,
+            &bound,
+            &bound,
+            pagination.skip,
+            pagination.limit,
+        )
+        .await
+        .map(|opt| opt.map(|list| list.into_iter().map(TagSearch).collect()))
+    }
+
     pub async fn put_to_index(tag_labels: &[String]) -> RedisResult<()> {
         let elements: Vec<(f64, &str)> = create_zero_score_tuples(tag_labels);
         Self::put_index_sorted_set(&TAGS_LABEL, &elements, None, None).await
@@ -56,3 +79,53 @@ impl TagSearch {
         Self::remove_from_index_sorted_set(None, &TAGS_LABEL, &[tag_label]).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pubky::Keypair;
+
+    use crate::{types::DynError, StackConfig, StackManager};
+
+    use super::*;
+
+    fn labels(result: Option<Vec<TagSearch>>) -> Vec<String> {
+        result
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tag| tag.0)
+            .collect()
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_get_by_label_exact() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        // Unique labels, so that no other test data matches them
+        let unique = &Keypair::random().public_key().to_z32()[..8];
+        let rust = format!("{unique}rust");
+        let rustlang = format!("{unique}rustlang");
+        TagSearch::put_to_index(&[rust.clone(), rustlang.clone()]).await?;
+
+        let pagination = Pagination::default();
+
+        // The prefix search returns both labels, the exact one only the label itself
+        let by_prefix = labels(TagSearch::get_by_label(&rust, &pagination).await?);
+        assert_eq!(by_prefix, vec![rust.clone(), rustlang.clone()]);
+        let exact = labels(TagSearch::get_by_label_exact(&rust, &pagination).await?);
+        assert_eq!(exact, vec![rust.clone()]);
+
+        // Normalized like the prefix search
+        let exact =
+            TagSearch::get_by_label_exact(&format!(" {} ", rust.to_uppercase()), &pagination)
+                .await?;
+        assert_eq!(labels(exact), vec![rust.clone()]);
+
+        let missing = TagSearch::get_by_label_exact(&format!("{unique}rus"), &pagination).await?;
+        assert!(labels(missing).is_empty());
+
+        TagSearch::del_from_index(&rust).await?;
+        TagSearch::del_from_index(&rustlang).await?;
+
+        Ok(())
+    }
+}
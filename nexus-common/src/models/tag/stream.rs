@@ -1,8 +1,10 @@
+use crate::db::kv::key::build_key;
 use crate::db::kv::{RedisResult, SortOrder};
-use crate::db::{fetch_key_from_graph, queries, RedisOps};
+use crate::db::{fetch_key_from_graph, get_redis_conn, queries, RedisOps};
 use crate::models::error::ModelResult;
 use crate::types::routes::HotTagsInputDTO;
 use crate::types::{StreamReach, Timeframe};
+use deadpool_redis::redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -234,6 +236,24 @@ impl HotTags {
         [&POST_HOT_TAGS[..], &[timeframe]].concat()
     }
 
+    /// Drops the cached global hot tags of every timeframe, so that they are recomputed from the
+    /// graph on the next read
+    pub async fn clear_global_cache() -> RedisResult<()> {
+        let mut keys = Vec::new();
+        for timeframe in [Timeframe::Today, Timeframe::ThisMonth, Timeframe::AllTime] {
+            let timeframe = timeframe.to_string();
+            for key_parts in [
+                Self::build_hot_tags_key_parts(&timeframe),
+                Taggers::build_key_parts(&timeframe),
+            ] {
+                keys.push(format!("{HOT_TAGS_CACHE_PREFIX}:{}", build_key(&key_parts)));
+            }
+        }
+        let mut redis_conn = get_redis_conn().await?;
+        let _: () = redis_conn.del(keys).await?;
+        Ok(())
+    }
+
     /// Reindexes global hot tags
     /// Retrieves and updates global hot tags for different timeframes. It fetches the top 100 hot tags
     ///  with a taggers limit of 20 for both "all-time" and "this month" timeframes
@@ -1,9 +1,12 @@
 use crate::db::graph::Query;
+use crate::db::kv::key::build_key;
 use crate::db::kv::{RedisResult, ScoreAction, SortOrder};
 use crate::db::{
-    execute_graph_operation, fetch_row_from_graph, queries, GraphResult, OperationOutcome, RedisOps,
+    execute_graph_operation, fetch_all_rows_from_graph, fetch_row_from_graph, queries, GraphResult,
+    OperationOutcome, RedisOps,
 };
 use crate::models::error::ModelResult;
+use crate::StackManager;
 use async_trait::async_trait;
 use tracing::error;
 
@@ -12,9 +15,18 @@ use crate::models::tag::{post::POST_TAGS_KEY_PARTS, user::USER_TAGS_KEY_PARTS};
 use crate::models::tag::TagDetails;
 
 const CACHE_SORTED_SET_PREFIX: &str = "Cache:Sorted";
+/// Labels applied by a tagger across posts and users, scored by the number of tags with them
+pub const TAGGER_LABELS_KEY_PARTS: [&str; 2] = ["Tags", "Tagger"];
 pub const CACHE_SET_PREFIX: &str = "Cache";
-// TTL, 3HR
-const CACHE_TTL: i64 = 3 * 60 * 60;
+
+/// Returns the TTL of the cached (WoT) tag indexes, `None` if they do not expire.
+/// See [StackConfig::tags_cache_ttl_secs](crate::StackConfig::tags_cache_ttl_secs)
+fn cache_ttl() -> Option<i64> {
+    match StackManager::config().tags_cache_ttl_secs {
+        0 => None,
+        ttl_secs => Some(i64::try_from(ttl_secs).unwrap_or(i64::MAX)),
+    }
+}
 
 /// Trait for managing a collection of tags
 ///
@@ -42,6 +54,7 @@ where
     /// - If `viewer_id` is provided and `depth` is within the range 1-3, it will retrieve the WoT tags
     /// - If `viewer_id` is not provided or `depth` is out of range, the function retrieves global tags for the user
     /// - The function ensures results from the graph database are cached in the index for faster future retrievals.
+    /// - Tags are ordered by score and then by label, so `skip_tags`/`limit_tags` give stable pages, also when the index is cold.
     async fn get_by_id(
         user_id: &str,
         extra_param: Option<&str>,
@@ -70,8 +83,9 @@ where
                     let depth = depth.unwrap_or(1);
                     let graph_response =
                         Self::get_from_graph(user_id, viewer_id, Some(depth)).await?;
-                    if let Some(tag_details) = graph_response {
+                    if let Some(mut tag_details) = graph_response {
                         Self::put_to_index(user_id, viewer_id, &tag_details, true).await?;
+                        TagDetails::set_viewer_relationship(&mut tag_details, viewer_id);
                         return Ok(Some(tag_details));
                     }
                     return Ok(None);
@@ -93,8 +107,24 @@ where
             Some(tag_details) => Ok(Some(tag_details)),
             None => {
                 let graph_response = Self::get_from_graph(user_id, extra_param, None).await?;
-                if let Some(tag_details) = graph_response {
+                if let Some(mut tag_details) = graph_response {
                     Self::put_to_index(user_id, extra_param, &tag_details, false).await?;
+                    // Read the page back from the freshly written index, so that skip/limit
+                    // and the ordering match the ones of a warm index
+                    if let Some(page) = Self::get_from_index(
+                        user_id,
+                        extra_param,
+                        viewer_id,
+                        skip_tags,
+                        limit_tags,
+                        limit_taggers,
+                        false,
+                    )
+                    .await?
+                    {
+                        return Ok(Some(page));
+                    }
+                    TagDetails::set_viewer_relationship(&mut tag_details, viewer_id);
                     return Ok(Some(tag_details));
                 }
                 Ok(None)
@@ -235,7 +265,7 @@ where
         let index_params = match is_cache {
             true => (
                 Some(CACHE_SORTED_SET_PREFIX),
-                Some(CACHE_TTL),
+                cache_ttl(),
                 Some(CACHE_SET_PREFIX.to_string()),
             ),
             false => (None, None, None),
@@ -283,6 +313,36 @@ where
         Self::put_score_index_sorted_set(&key, &[label], score_action).await
     }
 
+    /// Checks whether `label` can be added to the tags of a post or user without exceeding `max_tags`
+    /// distinct labels. Labels already placed on the target are always accepted, so they keep
+    /// accepting more taggers once the cap is reached.
+    ///
+    /// # Arguments
+    ///
+    /// * `author_id` - A string slice representing the ID of the tagged user or post author.
+    /// * `extra_param` - An optional parameter for specifying additional context, such as a post ID.
+    /// * `label` - A string slice representing the label of the new tag.
+    /// * `max_tags` - The maximum number of distinct labels of the target. 0 disables the cap
+    async fn accepts_label(
+        author_id: &str,
+        extra_param: Option<&str>,
+        label: &str,
+        max_tags: usize,
+    ) -> RedisResult<bool> {
+        if max_tags == 0 {
+            return Ok(true);
+        }
+        let key_parts = Self::create_sorted_set_key_parts(author_id, extra_param, false);
+        // Labels whose taggers were all removed keep a score of 0 and do not count
+        if let Some(score) = Self::check_sorted_set_member(None, &key_parts, &[label]).await? {
+            if score > 0 {
+                return Ok(true);
+            }
+        }
+        let labels = Self::count_index_sorted_set(&key_parts, Some(1.0), None).await?;
+        Ok(labels < max_tags)
+    }
+
     /// Adds a tagger (user) to the appropriate Redis index for a specified tag label.
     /// # Arguments
     ///
@@ -304,6 +364,99 @@ where
         Self::put_index_set(&key, &[tagger_user_id], None, None).await
     }
 
+    /// Updates the number of tags with `label` applied by a tagger. The labels of a tagger are
+    /// shared by [TagPost](crate::models::tag::post::TagPost) and [TagUser](crate::models::tag::user::TagUser)
+    ///
+    /// # Arguments
+    ///
+    /// * `tagger_user_id` - A string slice representing the ID of the user (tagger) applying the tag.
+    /// * `label` - A string slice representing the label of the tag.
+    /// * `score_action` - The action to perform on the label's score (increment on a new tag,
+    ///   decrement on a deleted one).
+    async fn update_tagger_label_score(
+        tagger_user_id: &str,
+        label: &str,
+        score_action: ScoreAction,
+    ) -> RedisResult<()> {
+        let key_parts = [&TAGGER_LABELS_KEY_PARTS[..], &[tagger_user_id]].concat();
+        Self::put_score_index_sorted_set(&key_parts, &[label], score_action).await
+    }
+
+    /// Indexes the labels a tagger has applied to posts and users, counted from the graph. Labels
+    /// the tagger no longer uses in the graph are removed
+    ///
+    /// # Arguments
+    ///
+    /// * `tagger_user_id` - A string slice representing the ID of the user (tagger).
+    async fn reindex_tagger_labels(tagger_user_id: &str) -> ModelResult<()> {
+        let rows = fetch_all_rows_from_graph(queries::get::get_tagger_label_counts(tagger_user_id))
+            .await?;
+        let mut labels = Vec::with_capacity(rows.len());
+        for row in rows {
+            let label: String = row.get("label")?;
+            let tags: i64 = row.get("tags")?;
+            labels.push((tags as f64, label));
+        }
+
+        let key_parts = [&TAGGER_LABELS_KEY_PARTS[..], &[tagger_user_id]].concat();
+        let indexed = Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            None,
+            None,
+            None,
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+        let stale: Vec<&str> = indexed
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .filter(|indexed| !labels.iter().any(|(_, label)| label == indexed))
+            .collect();
+        if !stale.is_empty() {
+            Self::remove_from_index_sorted_set(None, &key_parts, &stale).await?;
+        }
+
+        if !labels.is_empty() {
+            let elements: Vec<(f64, &str)> = labels
+                .iter()
+                .map(|(tags, label)| (*tags, label.as_str()))
+                .collect();
+            Self::put_index_sorted_set(&key_parts, &elements, None, None).await?;
+        }
+        Ok(())
+    }
+
+    /// Retrieves the distinct labels a tagger has applied to posts and users, most used first.
+    /// Labels whose tags were all deleted keep a score of 0 and are left out
+    ///
+    /// # Arguments
+    ///
+    /// * `tagger_user_id` - A string slice representing the ID of the user (tagger).
+    /// * `skip` - An optional number of labels to skip.
+    /// * `limit` - An optional number of labels to return.
+    async fn get_tagger_labels(
+        tagger_user_id: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> RedisResult<Vec<String>> {
+        let key_parts = [&TAGGER_LABELS_KEY_PARTS[..], &[tagger_user_id]].concat();
+        let labels = Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            Some(1.0),
+            skip,
+            limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+        Ok(labels.into_iter().map(|(label, _)| label).collect())
+    }
+
     /// Inserts a tag relationship into the graph database.
     ///
     /// # Arguments
@@ -478,10 +631,54 @@ where
     ) -> String {
         match extra_param {
             Some(extra_id) => match is_cache {
-                true => format!("{extra_id}:{user_id}:{label}"),
-                false => format!("{user_id}:{extra_id}:{label}"),
+                true => build_key(&[extra_id, user_id, label]),
+                false => build_key(&[user_id, extra_id, label]),
             },
-            None => format!("{user_id}:{label}"),
+            None => build_key(&[user_id, label]),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::get_redis_conn;
+    use crate::models::tag::user::TagUser;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+    use deadpool_redis::redis::AsyncCommands;
+    use pubky::Keypair;
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_put_to_index_cache_ttl() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let user_id = Keypair::random().public_key().to_z32();
+        let viewer_id = Keypair::random().public_key().to_z32();
+        let tags = vec![TagDetails {
+            label: "ttl".to_string(),
+            taggers: vec![viewer_id.clone()],
+            taggers_count: 1,
+            relationship: false,
+        }];
+
+        TagUser::put_to_index(&user_id, Some(&viewer_id), &tags, true).await?;
+        TagUser::put_to_index(&user_id, None, &tags, false).await?;
+
+        let cache_key_parts =
+            TagUser::create_sorted_set_key_parts(&user_id, Some(&viewer_id), true);
+        let cache_key = format!("{CACHE_SORTED_SET_PREFIX}:{}", build_key(&cache_key_parts));
+        let key_parts = TagUser::create_sorted_set_key_parts(&user_id, None, false);
+        let key = format!("Sorted:{}", build_key(&key_parts));
+
+        let mut redis_conn = get_redis_conn().await?;
+        let cache_ttl: i64 = redis_conn.ttl(&cache_key).await?;
+        assert!(cache_ttl > 0, "Cached tags should expire, TTL: {cache_ttl}");
+        // -1 means that the key exists without an expiry
+        let ttl: i64 = redis_conn.ttl(&key).await?;
+        assert_eq!(ttl, -1, "Indexed tags should not expire");
+
+        let _: () = redis_conn.del(&[cache_key, key]).await?;
+        Ok(())
+    }
+}
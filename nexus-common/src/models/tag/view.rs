@@ -1,9 +1,10 @@
+use neo4rs::Row;
 use pubky_app_specs::{post_uri_builder, user_uri_builder};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::db::fetch_row_from_graph;
 use crate::db::queries;
+use crate::db::{fetch_all_rows_from_graph, fetch_row_from_graph};
 use crate::models::error::ModelError;
 use crate::models::error::ModelResult;
 
@@ -25,6 +26,21 @@ impl TagView {
             return Ok(None);
         };
 
+        Self::from_row(&row).map(Some)
+    }
+
+    /// Retrieves a page of the tags created by `tagger_id`, oldest first
+    pub async fn get_by_tagger(
+        tagger_id: &str,
+        skip: usize,
+        limit: usize,
+    ) -> ModelResult<Vec<Self>> {
+        let query = queries::get::get_tags_by_tagger(tagger_id, skip, limit);
+        let rows = fetch_all_rows_from_graph(query).await?;
+        rows.iter().map(Self::from_row).collect()
+    }
+
+    fn from_row(row: &Row) -> ModelResult<Self> {
         let tagged_labels: Vec<String> = row.get("tagged_labels")?;
         let tagged_id = row.get("tagged_id")?;
         let uri = if tagged_labels.iter().any(|label| label == "Post") {
@@ -41,10 +57,10 @@ impl TagView {
             )));
         };
 
-        Ok(Some(Self {
+        Ok(Self {
             uri,
             label: row.get("label")?,
             indexed_at: row.get("indexed_at")?,
-        }))
+        })
     }
 }
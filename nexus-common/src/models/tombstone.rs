@@ -0,0 +1,58 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::db::kv::RedisResult;
+use crate::db::RedisOps;
+
+/// Trace of a post or user that existed and was erased from the index, so that it can be told
+/// apart from an ID that was never seen
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Default)]
+pub struct Tombstone {
+    /// Time of the deletion, in milliseconds since the epoch
+    pub deleted_at: i64,
+}
+
+impl RedisOps for Tombstone {}
+
+impl Tombstone {
+    /// Records the deletion of a post, kept for `ttl_secs` seconds (forever if 0)
+    pub async fn put_post(author_id: &str, post_id: &str, ttl_secs: u64) -> RedisResult<()> {
+        Self::put(&["Post", author_id, post_id], ttl_secs).await
+    }
+
+    /// Records the deletion of a user, kept for `ttl_secs` seconds (forever if 0)
+    pub async fn put_user(user_id: &str, ttl_secs: u64) -> RedisResult<()> {
+        Self::put(&["User", user_id], ttl_secs).await
+    }
+
+    /// Once the TTL expires, the ID is no longer told apart from one that was never seen
+    async fn put(key_parts: &[&str], ttl_secs: u64) -> RedisResult<()> {
+        let tombstone = Tombstone {
+            deleted_at: Utc::now().timestamp_millis(),
+        };
+        let expiration = match ttl_secs {
+            0 => None,
+            secs => Some(i64::try_from(secs).unwrap_or(i64::MAX)),
+        };
+        tombstone.put_index_json(key_parts, None, expiration).await
+    }
+
+    pub async fn get_post(author_id: &str, post_id: &str) -> RedisResult<Option<Self>> {
+        Self::try_from_index_json(&["Post", author_id, post_id], None).await
+    }
+
+    pub async fn get_user(user_id: &str) -> RedisResult<Option<Self>> {
+        Self::try_from_index_json(&["User", user_id], None).await
+    }
+
+    /// Removes the tombstone of a post that is indexed again
+    pub async fn del_post(author_id: &str, post_id: &str) -> RedisResult<()> {
+        Self::remove_from_index_multiple_json(&[&["Post", author_id, post_id]]).await
+    }
+
+    /// Removes the tombstone of a user that is indexed again
+    pub async fn del_user(user_id: &str) -> RedisResult<()> {
+        Self::remove_from_index_multiple_json(&[&["User", user_id]]).await
+    }
+}
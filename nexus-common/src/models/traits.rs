@@ -7,18 +7,19 @@ use core::fmt;
 use std::fmt::Debug;
 
 pub trait CollectionId {
-    fn to_string_id(self) -> String;
+    /// Parts of the index key of the record, each of them escaped separately when the key is built
+    fn to_key_parts(self) -> Vec<String>;
 }
 
 impl CollectionId for &str {
-    fn to_string_id(self) -> String {
-        String::from(self)
+    fn to_key_parts(self) -> Vec<String> {
+        vec![String::from(self)]
     }
 }
 
 impl CollectionId for &[&str] {
-    fn to_string_id(self) -> String {
-        self.join(":")
+    fn to_key_parts(self) -> Vec<String> {
+        self.iter().map(|part| part.to_string()).collect()
     }
 }
 
@@ -41,9 +42,12 @@ where
     /// a queried ID, containing `Some(record)` if the record was found in either the cache or the graph database,
     /// or `None` if it was not found in either.
     async fn get_by_ids(ids: &[T]) -> ModelResult<Vec<Option<Self>>> {
-        let key_parts_list: Vec<String> = ids.iter().map(|id| id.to_string_id()).collect();
+        let key_parts_list: Vec<Vec<String>> = ids.iter().map(|id| id.to_key_parts()).collect();
 
-        let keys_refs: Vec<Vec<&str>> = key_parts_list.iter().map(|id| vec![id.as_str()]).collect();
+        let keys_refs: Vec<Vec<&str>> = key_parts_list
+            .iter()
+            .map(|parts| parts.iter().map(String::as_str).collect())
+            .collect();
 
         let keys: Vec<&[&str]> = keys_refs.iter().map(|arr| &arr[..]).collect();
 
@@ -120,12 +124,15 @@ where
                 found_record_ids.push(*id);
             }
         }
-        let key_parts_list: Vec<String> = found_record_ids
+        let key_parts_list: Vec<Vec<String>> = found_record_ids
             .iter()
-            .map(|id| id.to_string_id())
+            .map(|id| id.to_key_parts())
             .collect();
 
-        let keys_refs: Vec<Vec<&str>> = key_parts_list.iter().map(|id| vec![id.as_str()]).collect();
+        let keys_refs: Vec<Vec<&str>> = key_parts_list
+            .iter()
+            .map(|parts| parts.iter().map(String::as_str).collect())
+            .collect();
 
         let keys: Vec<&[&str]> = keys_refs.iter().map(|arr| &arr[..]).collect();
 
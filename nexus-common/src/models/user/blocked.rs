@@ -0,0 +1,65 @@
+use crate::db::kv::RedisResult;
+use crate::db::RedisOps;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// IDs of the users blocked by a user.
+///
+/// Unlike the follows, a block hides both users from each other: the blocked user neither sees
+/// nor interacts with the content of the user that blocked them.
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
+pub struct Blocked(pub Vec<String>);
+
+impl AsRef<[String]> for Blocked {
+    fn as_ref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl RedisOps for Blocked {}
+
+impl Blocked {
+    /// Retrieves the IDs of the users blocked by `user_id`
+    pub async fn get_by_id(
+        user_id: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> RedisResult<Self> {
+        let blocked = Self::try_from_index_set(&[user_id], skip, limit, None).await?;
+        Ok(Self(blocked.unwrap_or_default()))
+    }
+
+    /// Adds `blocked_id` to the users blocked by `user_id`
+    pub async fn put_to_index(user_id: &str, blocked_id: &str) -> RedisResult<()> {
+        Self::put_index_set(&[user_id], &[blocked_id], None, None).await
+    }
+
+    /// Removes `blocked_id` from the users blocked by `user_id`
+    pub async fn del_from_index(user_id: &str, blocked_id: &str) -> RedisResult<()> {
+        Self(vec![blocked_id.to_string()])
+            .remove_from_index_set(&[user_id])
+            .await
+    }
+
+    /// Checks whether `user_id` blocked `blocked_id`
+    pub async fn check(user_id: &str, blocked_id: &str) -> RedisResult<bool> {
+        let (_, blocked) = Self::check_set_member(&[user_id], blocked_id).await?;
+        Ok(blocked)
+    }
+
+    /// Checks, for each of `user_ids`, whether `user_id` blocked it.
+    ///
+    /// Returns one flag per user, in the same order. All flags are `false` if `user_id` blocked
+    /// no one.
+    pub async fn check_batch(user_id: &str, user_ids: &[&str]) -> RedisResult<Vec<bool>> {
+        let flags = Self::check_set_members(&[user_id], user_ids).await?;
+        Ok(flags.unwrap_or_else(|| vec![false; user_ids.len()]))
+    }
+
+    /// Checks, for each of `user_ids`, whether it blocked `blocked_id`.
+    ///
+    /// Returns one flag per user, in the same order, read in a single round trip.
+    pub async fn check_blocked_by(user_ids: &[&str], blocked_id: &str) -> RedisResult<Vec<bool>> {
+        Self::check_member_in_sets(user_ids, blocked_id).await
+    }
+}
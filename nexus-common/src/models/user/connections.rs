@@ -0,0 +1,66 @@
+use crate::db::kv::RedisResult;
+use crate::db::{fetch_key_from_graph, queries, RedisOps};
+use crate::models::error::ModelResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+const CACHE_TOP_CONNECTIONS_PREFIX: &str = "Cache:TopConnections";
+/// TTL of the cached top connections of a user, 1HR
+pub const CACHE_TOP_CONNECTIONS_TTL: i64 = 60 * 60;
+/// Number of top connections computed and cached per user
+pub const MAX_TOP_CONNECTIONS: usize = 100;
+
+/// A user interacted with, and the number of interactions directed at them
+#[derive(Serialize, Deserialize, Debug, ToSchema, Default, Clone, PartialEq)]
+pub struct TopConnection {
+    pub user_id: String,
+    /// Replies to posts of the user
+    pub replies: u64,
+    /// Reposts of posts of the user
+    pub reposts: u64,
+    /// Tags on the user or on posts of the user
+    pub tags: u64,
+    pub total: u64,
+}
+
+/// The users a user has interacted with the most, most interactions first
+#[derive(Serialize, Deserialize, Debug, ToSchema, Default, Clone)]
+pub struct TopConnections(pub Vec<TopConnection>);
+
+impl RedisOps for TopConnections {}
+
+impl TopConnections {
+    /// Retrieves the `limit` users `user_id` has interacted with the most.
+    ///
+    /// The ranking aggregates all outgoing interactions of the user in the graph, so the top
+    /// [MAX_TOP_CONNECTIONS] are cached for [CACHE_TOP_CONNECTIONS_TTL] seconds.
+    pub async fn get_by_id(user_id: &str, limit: usize) -> ModelResult<TopConnections> {
+        let mut connections = match Self::get_from_cache(user_id).await? {
+            Some(cached) => cached,
+            None => {
+                let query = queries::get::user_top_connections(user_id, MAX_TOP_CONNECTIONS);
+                let connections = fetch_key_from_graph::<Vec<TopConnection>>(query, "connections")
+                    .await?
+                    .map(TopConnections)
+                    .unwrap_or_default();
+                connections.put_to_cache(user_id).await?;
+                connections
+            }
+        };
+        connections.0.truncate(limit);
+        Ok(connections)
+    }
+
+    async fn get_from_cache(user_id: &str) -> RedisResult<Option<TopConnections>> {
+        Self::try_from_index_json(&[user_id], Some(CACHE_TOP_CONNECTIONS_PREFIX.to_string())).await
+    }
+
+    async fn put_to_cache(&self, user_id: &str) -> RedisResult<()> {
+        self.put_index_json(
+            &[user_id],
+            Some(CACHE_TOP_CONNECTIONS_PREFIX.to_string()),
+            Some(CACHE_TOP_CONNECTIONS_TTL),
+        )
+        .await
+    }
+}
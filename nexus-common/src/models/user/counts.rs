@@ -1,12 +1,28 @@
-use crate::db::kv::{JsonAction, RedisResult};
+use crate::db::kv::{JsonAction, RedisResult, SortOrder};
 use crate::db::{fetch_row_from_graph, queries, GraphResult, RedisOps};
-use crate::models::error::ModelResult;
+use crate::models::error::{ModelError, ModelResult};
 use crate::models::tag::user::USER_TAGS_KEY_PARTS;
+use chrono::Utc;
+use futures::future::try_join_all;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use super::UserStream;
 
+/// Sorted set of follower count snapshots per user, scored by the snapshot timestamp
+pub const USER_FOLLOWER_HISTORY_KEY_PARTS: [&str; 2] = ["Users", "FollowerHistory"];
+
+/// Maximum number of follower count snapshots returned by [UserCounts::follower_history]
+const FOLLOWER_HISTORY_LIMIT: usize = 10_000;
+
+/// Follower count of a user at a given point in time
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct FollowerSnapshot {
+    /// Start of the snapshot bucket, in milliseconds since the epoch
+    pub timestamp: i64,
+    pub followers: u32,
+}
+
 /// Represents total counts of relationships of a user.
 #[derive(Serialize, Deserialize, ToSchema, Debug, Default, Clone)]
 pub struct UserCounts {
@@ -27,6 +43,13 @@ pub struct UserCounts {
 impl RedisOps for UserCounts {}
 
 impl UserCounts {
+    /// Ratio of followed users to followers, a signal for bot and spam detection.
+    ///
+    /// Users without followers are treated as having one, so the ratio is their following count.
+    pub fn following_follower_ratio(&self) -> f32 {
+        self.following as f32 / self.followers.max(1) as f32
+    }
+
     /// Retrieves counts by user ID, first trying to get from Redis, then from Neo4j if not found.
     pub async fn get_by_id(user_id: &str) -> ModelResult<Option<UserCounts>> {
         match Self::get_from_index(user_id).await? {
@@ -42,6 +65,26 @@ impl UserCounts {
         }
     }
 
+    /// Retrieves the counts of several users, in the same order as `user_ids`.
+    ///
+    /// Counts missing from the index are read from the graph, and `None` is returned for the
+    /// users that don't exist.
+    pub async fn get_by_ids(user_ids: &[String]) -> ModelResult<Vec<Option<UserCounts>>> {
+        let mut counts = Self::mget(user_ids).await?;
+
+        let misses = counts
+            .iter_mut()
+            .zip(user_ids)
+            .filter(|(counts, _)| counts.is_none());
+        try_join_all(misses.map(|(counts, user_id)| async move {
+            *counts = Self::get_by_id(user_id).await?;
+            Ok::<(), ModelError>(())
+        }))
+        .await?;
+
+        Ok(counts)
+    }
+
     /// Retrieves the counts from Neo4j.
     pub async fn get_from_graph(user_id: &str) -> GraphResult<Option<UserCounts>> {
         let query = queries::get::user_counts(user_id);
@@ -125,13 +168,102 @@ impl UserCounts {
                 UserStream::add_to_influencers_sorted_set(user_id, &user_counts).await?;
                 // Increment followers
                 if field == "followers" {
-                    UserStream::add_to_most_followed_sorted_set(user_id, &user_counts).await?
+                    UserStream::add_to_most_followed_sorted_set(user_id, &user_counts).await?;
                 }
             }
         }
         Ok(())
     }
 
+    /// Records the current follower count of a user in its follower history.
+    ///
+    /// Timestamps are floored to `interval_secs`, so a later change within the same interval
+    /// replaces the snapshot instead of adding a new one. An interval of 0 disables snapshots
+    pub async fn snapshot_followers(user_id: &str, interval_secs: u64) -> ModelResult<()> {
+        let interval_ms = i64::try_from(interval_secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+        if interval_ms == 0 {
+            return Ok(());
+        }
+        let Some(UserCounts { followers, .. }) = Self::get_by_id(user_id).await? else {
+            return Ok(());
+        };
+        let bucket = Utc::now().timestamp_millis() / interval_ms * interval_ms;
+        let key_parts = [&USER_FOLLOWER_HISTORY_KEY_PARTS[..], &[user_id]].concat();
+
+        // Drop the snapshot already taken in this interval, if any
+        let score = bucket as f64;
+        if let Some(existing) = Self::try_from_index_sorted_set(
+            &key_parts,
+            Some(score),
+            Some(score),
+            None,
+            None,
+            SortOrder::Ascending,
+            None,
+        )
+        .await?
+        {
+            let members: Vec<&str> = existing.iter().map(|(member, _)| member.as_str()).collect();
+            Self::remove_from_index_sorted_set(None, &key_parts, &members).await?;
+        }
+
+        let member = format!("{bucket}:{followers}");
+        Self::put_index_sorted_set(&key_parts, &[(score, member.as_str())], None, None).await?;
+        Ok(())
+    }
+
+    /// Retrieves the follower count snapshots of a user, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The unique identifier of the user.
+    /// * `from` - Optional lower bound of the snapshot timestamps (inclusive), in milliseconds.
+    /// * `to` - Optional upper bound of the snapshot timestamps (inclusive), in milliseconds.
+    /// * `bucket` - Optional bucket size, in milliseconds. When set, only the latest snapshot of
+    ///   every bucket is returned, timestamped with the start of the bucket.
+    pub async fn follower_history(
+        user_id: &str,
+        from: Option<i64>,
+        to: Option<i64>,
+        bucket: Option<i64>,
+    ) -> RedisResult<Vec<FollowerSnapshot>> {
+        let key_parts = [&USER_FOLLOWER_HISTORY_KEY_PARTS[..], &[user_id]].concat();
+        let members = Self::try_from_index_sorted_set(
+            &key_parts,
+            to.map(|to| to as f64),
+            from.map(|from| from as f64),
+            None,
+            Some(FOLLOWER_HISTORY_LIMIT),
+            SortOrder::Ascending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+
+        let mut history: Vec<FollowerSnapshot> = Vec::with_capacity(members.len());
+        for (member, score) in members {
+            let Some(followers) = member
+                .rsplit_once(':')
+                .and_then(|(_, count)| count.parse().ok())
+            else {
+                continue;
+            };
+            let timestamp = match bucket {
+                Some(bucket) if bucket > 0 => score as i64 / bucket * bucket,
+                _ => score as i64,
+            };
+            // Snapshots are sorted, so the latest one of a bucket overwrites the previous ones
+            match history.last_mut() {
+                Some(last) if last.timestamp == timestamp => last.followers = followers,
+                _ => history.push(FollowerSnapshot {
+                    timestamp,
+                    followers,
+                }),
+            }
+        }
+        Ok(history)
+    }
+
     pub async fn reindex(author_id: &str) -> ModelResult<()> {
         match Self::get_from_graph(author_id).await? {
             Some(counts) => counts.put_to_index(author_id).await?,
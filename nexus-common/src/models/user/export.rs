@@ -0,0 +1,167 @@
+use super::UserDetails;
+use crate::db::kv::SortOrder;
+use crate::db::RedisOps;
+use crate::models::error::ModelResult;
+use crate::models::follow::{Followers, Following, UserFollows};
+use crate::models::post::{PostDetails, PostStream};
+use crate::models::tag::view::TagView;
+use futures::stream::{self, Stream};
+use serde::Serialize;
+
+/// Number of records read at a time by [UserDetails::export]
+pub const EXPORT_CHUNK_SIZE: usize = 100;
+
+/// A record of the data export of a user, serialized as `{"type": ..., "data": ...}`
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum UserExportRecord {
+    Profile(UserDetails),
+    Post(PostDetails),
+    /// A tag the user applied to a post or user
+    Tag(TagView),
+    /// ID of a user the user follows
+    Following(String),
+    /// ID of a user following the user
+    Follower(String),
+    /// Key (`author_id:post_id`) of a post the user bookmarked
+    Bookmark(String),
+}
+
+/// Part of the export being read, with the number of its records already read
+#[derive(Clone, Copy)]
+enum ExportSection {
+    Profile,
+    Posts(usize),
+    Replies(usize),
+    Tags(usize),
+    Following(usize),
+    Followers(usize),
+    Bookmarks(usize),
+    Done,
+}
+
+impl ExportSection {
+    /// The section read after this one
+    fn next(self) -> Self {
+        match self {
+            Self::Profile => Self::Posts(0),
+            Self::Posts(_) => Self::Replies(0),
+            Self::Replies(_) => Self::Tags(0),
+            Self::Tags(_) => Self::Following(0),
+            Self::Following(_) => Self::Followers(0),
+            Self::Followers(_) => Self::Bookmarks(0),
+            Self::Bookmarks(_) | Self::Done => Self::Done,
+        }
+    }
+
+    /// The same section, after `read` more records
+    fn advance(self, read: usize) -> Self {
+        match self {
+            Self::Posts(skip) => Self::Posts(skip + read),
+            Self::Replies(skip) => Self::Replies(skip + read),
+            Self::Tags(skip) => Self::Tags(skip + read),
+            Self::Following(skip) => Self::Following(skip + read),
+            Self::Followers(skip) => Self::Followers(skip + read),
+            Self::Bookmarks(skip) => Self::Bookmarks(skip + read),
+            other => other,
+        }
+    }
+}
+
+impl UserDetails {
+    /// Streams the data of a user: profile, authored posts and replies, applied tags, follows,
+    /// followers and bookmarks.
+    ///
+    /// Each item is a chunk of at most [EXPORT_CHUNK_SIZE] records, so the export of a prolific
+    /// user is never held in memory at once.
+    pub fn export(user_id: String) -> impl Stream<Item = ModelResult<Vec<UserExportRecord>>> {
+        stream::try_unfold(ExportSection::Profile, move |section| {
+            let user_id = user_id.clone();
+            async move {
+                if let ExportSection::Done = section {
+                    return Ok(None);
+                }
+                let (records, read) = Self::export_chunk(&user_id, section).await?;
+                // A partial chunk is the last one of its section
+                let next = match read {
+                    EXPORT_CHUNK_SIZE => section.advance(EXPORT_CHUNK_SIZE),
+                    _ => section.next(),
+                };
+                Ok(Some((records, next)))
+            }
+        })
+    }
+
+    /// Reads the records of `section`, starting after the ones already read. Also returns the
+    /// number of entries read, which is larger than the records if some of them are missing
+    async fn export_chunk(
+        user_id: &str,
+        section: ExportSection,
+    ) -> ModelResult<(Vec<UserExportRecord>, usize)> {
+        let limit = Some(EXPORT_CHUNK_SIZE);
+        let records = match section {
+            ExportSection::Profile => Self::get_by_id(user_id)
+                .await?
+                .map(UserExportRecord::Profile)
+                .into_iter()
+                .collect(),
+            ExportSection::Posts(skip) | ExportSection::Replies(skip) => {
+                let replies = matches!(section, ExportSection::Replies(_));
+                let post_keys = PostStream::get_author_posts(
+                    user_id,
+                    SortOrder::Ascending,
+                    None,
+                    None,
+                    Some(skip),
+                    limit,
+                    replies,
+                )
+                .await?
+                .post_keys;
+                let records = PostDetails::mget(&post_keys)
+                    .await?
+                    .into_iter()
+                    .flatten()
+                    .map(UserExportRecord::Post)
+                    .collect();
+                return Ok((records, post_keys.len()));
+            }
+            ExportSection::Tags(skip) => TagView::get_by_tagger(user_id, skip, EXPORT_CHUNK_SIZE)
+                .await?
+                .into_iter()
+                .map(UserExportRecord::Tag)
+                .collect(),
+            ExportSection::Following(skip) => Following::get_by_id(user_id, Some(skip), limit)
+                .await?
+                .map(|following| following.0)
+                .unwrap_or_default()
+                .into_iter()
+                .map(UserExportRecord::Following)
+                .collect(),
+            ExportSection::Followers(skip) => Followers::get_by_id(user_id, Some(skip), limit)
+                .await?
+                .map(|followers| followers.0)
+                .unwrap_or_default()
+                .into_iter()
+                .map(UserExportRecord::Follower)
+                .collect(),
+            ExportSection::Bookmarks(skip) => PostStream::get_bookmarked_posts(
+                user_id,
+                None,
+                SortOrder::Ascending,
+                None,
+                None,
+                Some(skip),
+                limit,
+            )
+            .await?
+            .post_keys
+            .into_iter()
+            .map(UserExportRecord::Bookmark)
+            .collect(),
+            ExportSection::Done => Vec::new(),
+        };
+        let read = records.len();
+        Ok((records, read))
+    }
+}
@@ -1,18 +1,30 @@
+mod blocked;
+mod connections;
 mod counts;
 mod details;
+mod export;
 //mod id;
 mod influencers;
+mod recommendations;
 mod relationship;
 mod search;
 mod stream;
 mod tags;
+#[cfg(test)]
+pub(crate) mod test_utils;
 mod view;
 
-pub use counts::UserCounts;
+pub use blocked::Blocked;
+pub use connections::{
+    TopConnection, TopConnections, CACHE_TOP_CONNECTIONS_TTL, MAX_TOP_CONNECTIONS,
+};
+pub use counts::{FollowerSnapshot, UserCounts, USER_FOLLOWER_HISTORY_KEY_PARTS};
 pub use details::UserDetails;
+pub use export::{UserExportRecord, EXPORT_CHUNK_SIZE};
 pub use influencers::Influencers;
+pub use recommendations::{Recommendation, Recommendations, MAX_RECOMMENDATIONS};
 pub use relationship::Relationship;
-pub use search::{UserSearch, USER_NAME_KEY_PARTS};
+pub use search::{UserSearch, UserSearchPage, USER_NAME_KEY_PARTS};
 pub use stream::{
     UserIdStream, UserStream, UserStreamInput, UserStreamSource, USER_INFLUENCERS_KEY_PARTS,
     USER_MOSTFOLLOWED_KEY_PARTS,
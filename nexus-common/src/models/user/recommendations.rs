@@ -0,0 +1,111 @@
+use crate::db::{fetch_key_from_graph, queries};
+use crate::models::error::ModelResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::Blocked;
+
+/// Maximum number of users recommended at once
+pub const MAX_RECOMMENDATIONS: usize = 100;
+
+/// A user to follow, and the number of the followed users that follow them
+#[derive(Serialize, Deserialize, Debug, ToSchema, Default, Clone, PartialEq)]
+pub struct Recommendation {
+    pub user_id: String,
+    pub shared_follows: u64,
+}
+
+/// Users to follow, most shared follows first
+#[derive(Serialize, Deserialize, Debug, ToSchema, Default, Clone)]
+pub struct Recommendations(pub Vec<Recommendation>);
+
+impl Recommendations {
+    /// Retrieves the `limit` users followed by the most of the users `user_id` follows.
+    ///
+    /// Only second-degree connections are considered. The users already followed or blocked
+    /// by `user_id` are left out.
+    pub async fn who_to_follow(user_id: &str, limit: usize) -> ModelResult<Recommendations> {
+        let blocked = Blocked::get_by_id(user_id, None, None).await?;
+        let query = queries::get::who_to_follow(user_id, &blocked.0, limit);
+        let recommendations =
+            fetch_key_from_graph::<Vec<Recommendation>>(query, "recommendations").await?;
+        Ok(recommendations.map(Recommendations).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::follow::{Following, UserFollows};
+    use crate::models::user::test_utils::create_user;
+    use crate::models::user::UserDetails;
+    use crate::types::DynError;
+    use crate::{StackConfig, StackManager};
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_who_to_follow_ranked_by_shared_follows() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let mut users = Vec::new();
+        for name in [
+            "me", "f1", "f2", "f3", "one", "two", "three", "followed", "blocked",
+        ] {
+            users.push(create_user(&format!("Recommendations:{name}")).await?);
+        }
+        let [me, f1, f2, f3, one, two, three, followed, blocked] = &users[..] else {
+            unreachable!()
+        };
+
+        let follows = [
+            (me, f1),
+            (me, f2),
+            (me, f3),
+            (me, followed),
+            (f1, three),
+            (f2, three),
+            (f3, three),
+            (f1, two),
+            (f2, two),
+            (f3, one),
+            (f1, followed),
+            (f1, blocked),
+            (f2, blocked),
+            (f3, blocked),
+            // The user is not recommended to themselves
+            (f1, me),
+        ];
+        for (follower, followee) in follows {
+            Following::put_to_graph(follower, followee).await?;
+        }
+        Blocked::put_to_index(me, blocked).await?;
+
+        let recommendations = Recommendations::who_to_follow(me, 10).await?;
+        assert_eq!(
+            recommendations.0,
+            vec![
+                Recommendation {
+                    user_id: three.clone(),
+                    shared_follows: 3
+                },
+                Recommendation {
+                    user_id: two.clone(),
+                    shared_follows: 2
+                },
+                Recommendation {
+                    user_id: one.clone(),
+                    shared_follows: 1
+                },
+            ]
+        );
+
+        let recommendations = Recommendations::who_to_follow(me, 1).await?;
+        assert_eq!(recommendations.0.len(), 1);
+        assert_eq!(&recommendations.0[0].user_id, three);
+
+        Blocked::del_from_index(me, blocked).await?;
+        for user_id in &users {
+            UserDetails::delete(user_id).await?;
+        }
+        Ok(())
+    }
+}
@@ -1,16 +1,23 @@
 use crate::db::kv::RedisResult;
-use crate::models::error::ModelResult;
-use crate::models::follow::{Followers, UserFollows};
+use crate::db::RedisOps;
+use crate::models::error::{ModelError, ModelResult};
+use crate::models::follow::{Followers, Following, UserFollows};
 
-use super::UserCounts;
+use super::{Blocked, UserCounts};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 /// Represents the relationship of the user that views and user being viewed.
-#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default, Clone, PartialEq)]
 pub struct Relationship {
     pub following: bool,
     pub followed_by: bool,
+    /// The viewer blocked the user
+    #[serde(default)]
+    pub blocked: bool,
+    /// The user blocked the viewer
+    #[serde(default)]
+    pub blocked_by: bool,
 }
 
 impl Relationship {
@@ -24,7 +31,54 @@ impl Relationship {
         }
     }
 
-    /// Retrieves relationship from Followers/Following Redis index sets.
+    /// Retrieves the relationships of the viewer with each of `user_ids`, in the same order.
+    ///
+    /// The flags of all users are checked at once against the follow and block sets of the
+    /// viewer. An entry is `None` if the user does not exist, and all are `None` without a viewer
+    /// or if the viewer does not exist.
+    pub async fn get_by_ids(
+        user_ids: &[String],
+        viewer_id: Option<&str>,
+    ) -> ModelResult<Vec<Option<Self>>> {
+        let Some(viewer_id) = viewer_id else {
+            return Ok(vec![None; user_ids.len()]);
+        };
+        if user_ids.is_empty() || UserCounts::get_from_index(viewer_id).await?.is_none() {
+            return Ok(vec![None; user_ids.len()]);
+        }
+
+        let ids: Vec<&str> = user_ids.iter().map(String::as_str).collect();
+        let (counts, following, followed_by, blocked, blocked_by) = tokio::try_join!(
+            async { UserCounts::mget(user_ids).await.map_err(ModelError::from) },
+            Following::check_batch(viewer_id, &ids),
+            Followers::check_batch(viewer_id, &ids),
+            async {
+                Blocked::check_batch(viewer_id, &ids)
+                    .await
+                    .map_err(ModelError::from)
+            },
+            async {
+                Blocked::check_blocked_by(&ids, viewer_id)
+                    .await
+                    .map_err(ModelError::from)
+            },
+        )?;
+
+        Ok(counts
+            .iter()
+            .enumerate()
+            .map(|(i, user_counts)| {
+                user_counts.as_ref().map(|_| Self {
+                    following: following[i],
+                    followed_by: followed_by[i],
+                    blocked: blocked[i],
+                    blocked_by: blocked_by[i],
+                })
+            })
+            .collect())
+    }
+
+    /// Retrieves relationship from Followers/Following and Blocked Redis index sets.
     pub async fn get_from_index(
         user_id: &str,
         viewer_id: &str,
@@ -37,14 +91,18 @@ impl Relationship {
             return Ok(None);
         }
 
-        let (following, followed_by) = tokio::try_join!(
+        let (following, followed_by, blocked, blocked_by) = tokio::try_join!(
             Followers::check_in_index(user_id, viewer_id),
             Followers::check_in_index(viewer_id, user_id),
+            Blocked::check(viewer_id, user_id),
+            Blocked::check(user_id, viewer_id),
         )?;
 
         Ok(Some(Self {
             followed_by,
             following,
+            blocked,
+            blocked_by,
         }))
     }
 }
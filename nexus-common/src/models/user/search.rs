@@ -1,9 +1,12 @@
 use super::{UserDetails, USER_DELETED_SENTINEL};
 use crate::db::kv::RedisResult;
-use crate::db::RedisOps;
+use crate::db::{fetch_key_from_graph, queries, RedisOps};
+use crate::models::audit::OrphanReport;
 use crate::models::create_zero_score_tuples;
+use crate::models::error::ModelResult;
 use crate::models::traits::Collection;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use utoipa::ToSchema;
 
 pub const USER_NAME_KEY_PARTS: [&str; 2] = ["Users", "Name"];
@@ -15,6 +18,13 @@ pub struct UserSearch(pub Vec<String>);
 
 impl RedisOps for UserSearch {}
 
+/// Page of user IDs along with the total number of matches
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
+pub struct UserSearchPage {
+    pub ids: Vec<String>,
+    pub total: usize,
+}
+
 impl UserSearch {
     pub async fn get_by_name(
         name_prefix: &str,
@@ -51,19 +61,87 @@ impl UserSearch {
         Ok(elements.map(UserSearch))
     }
 
+    /// Same as [Self::get_by_name], also returning the total number of usernames matching the prefix.
+    ///
+    /// The total is counted in Redis over the same range as the page, without retrieving the matches.
+    pub async fn get_page_by_name(
+        name_prefix: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> RedisResult<UserSearchPage> {
+        let (min, max) = Self::name_range(name_prefix);
+        let (page, total) = tokio::try_join!(
+            Self::get_by_name(name_prefix, skip, limit),
+            Self::count_index_sorted_set_lex(&USER_NAME_KEY_PARTS, &min, &max),
+        )?;
+
+        Ok(UserSearchPage {
+            ids: page.map(|users| users.0).unwrap_or_default(),
+            total,
+        })
+    }
+
+    /// Searches users whose username or ID starts with `query`, running both lexicographic
+    /// scans concurrently.
+    ///
+    /// Results are de-duplicated and ranked: an exact ID match comes first, followed by the
+    /// username matches and then the remaining ID prefix matches.
+    pub async fn search_combined(
+        query: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> RedisResult<Option<Self>> {
+        let skip = skip.unwrap_or(0);
+        let limit = limit.unwrap_or(1000);
+        // Each branch must return enough results to fill the requested page once merged
+        let window = Some(skip + limit);
+
+        let (by_name, by_id) = tokio::try_join!(
+            Self::get_by_name(query, Some(0), window),
+            Self::get_by_id(query, Some(0), window),
+        )?;
+        if by_name.is_none() && by_id.is_none() {
+            return Ok(None);
+        }
+        let by_name = by_name.map(|users| users.0).unwrap_or_default();
+        let by_id = by_id.map(|users| users.0).unwrap_or_default();
+
+        let query = query.to_lowercase();
+        let (exact_id, prefix_ids): (Vec<String>, Vec<String>) =
+            by_id.into_iter().partition(|user_id| *user_id == query);
+
+        let mut seen = HashSet::new();
+        let user_ids = exact_id
+            .into_iter()
+            .chain(by_name)
+            .chain(prefix_ids)
+            .filter(|user_id| seen.insert(user_id.clone()))
+            .skip(skip)
+            .take(limit)
+            .collect();
+
+        Ok(Some(UserSearch(user_ids)))
+    }
+
     pub async fn get_from_index_name(
         name_prefix: &str,
         skip: Option<usize>,
         limit: Option<usize>,
     ) -> RedisResult<Option<Vec<String>>> {
+        let (min, max) = Self::name_range(name_prefix);
+
+        // Perform the lexicographical range search
+        Self::try_from_index_sorted_set_lex(&USER_NAME_KEY_PARTS, &min, &max, skip, limit).await
+    }
+
+    /// Lexicographical bounds of the usernames starting with `name_prefix`
+    fn name_range(name_prefix: &str) -> (String, String) {
         // Convert the username to lowercase to ensure case-insensitive search
         let name_prefix = name_prefix.to_lowercase();
 
         let min = format!("[{name_prefix}"); // Inclusive range starting with "name_prefix"
         let max = format!("({name_prefix}~"); // Exclusive range ending just after "name_prefix"
-
-        // Perform the lexicographical range search
-        Self::try_from_index_sorted_set_lex(&USER_NAME_KEY_PARTS, &min, &max, skip, limit).await
+        (min, max)
     }
 
     pub async fn get_from_index_id(
@@ -117,6 +195,28 @@ impl UserSearch {
         Self::put_index_sorted_set(&USER_ID_KEY_PARTS, &ids_zscore_tuples, None, None).await
     }
 
+    /// Audits one batch of the user ID index, reporting the IDs that have no user node in the graph.
+    ///
+    /// Start with `cursor = 0` and pass the returned [OrphanReport::next_cursor] to continue the
+    /// audit, until it is `None`. `batch_size` is a hint of the number of IDs checked per call.
+    pub async fn find_orphans(cursor: u64, batch_size: usize) -> ModelResult<OrphanReport> {
+        let (next_cursor, user_ids) =
+            Self::scan_index_sorted_set(&USER_ID_KEY_PARTS, cursor, batch_size).await?;
+
+        let orphans = match user_ids.is_empty() {
+            true => Vec::new(),
+            false => {
+                let ids: Vec<&str> = user_ids.iter().map(|id| id.as_str()).collect();
+                let query = queries::get::get_missing_user_ids(&ids);
+                fetch_key_from_graph(query, "missing")
+                    .await?
+                    .unwrap_or_default()
+            }
+        };
+
+        Ok(OrphanReport::new(user_ids.len(), orphans, next_cursor))
+    }
+
     pub async fn delete(user_id: &str) -> RedisResult<()> {
         Self::delete_existing_records(&[user_id]).await
     }
@@ -155,3 +255,142 @@ impl UserSearch {
         Self::remove_from_index_sorted_set(None, &USER_ID_KEY_PARTS, user_ids).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pubky::Keypair;
+    use pubky_app_specs::PubkyId;
+
+    use crate::{types::DynError, StackConfig, StackManager};
+
+    use super::*;
+
+    fn random_user(name: impl Into<String>) -> Result<UserDetails, DynError> {
+        let id = PubkyId::try_from(&Keypair::random().public_key().to_z32())?;
+        Ok(UserDetails {
+            name: name.into(),
+            id,
+            ..Default::default()
+        })
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_get_page_by_name_total() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        // Five users sharing a username prefix that no other user has
+        let prefix = Keypair::random().public_key().to_z32()[..12].to_string();
+        let users = (0..5)
+            .map(|i| random_user(format!("{prefix} user {i}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        UserSearch::put_to_index(&users.iter().collect::<Vec<_>>()).await?;
+
+        let page = UserSearch::get_page_by_name(&prefix, Some(0), Some(2)).await?;
+        assert_eq!(page.ids.len(), 2);
+        assert_eq!(page.total, 5);
+
+        // The total does not depend on the requested page
+        let page = UserSearch::get_page_by_name(&prefix, Some(4), Some(2)).await?;
+        assert_eq!(page.ids.len(), 1);
+        assert_eq!(page.total, 5);
+
+        let records: Vec<String> = users
+            .iter()
+            .map(|user| format!("{}:{}", user.name, user.id))
+            .collect();
+        UserSearch::remove_from_index_sorted_set(
+            None,
+            &USER_NAME_KEY_PARTS,
+            &records.iter().map(String::as_str).collect::<Vec<_>>(),
+        )
+        .await?;
+        let ids: Vec<String> = users.iter().map(|user| user.id.to_string()).collect();
+        UserSearch::remove_from_index_sorted_set(
+            None,
+            &USER_ID_KEY_PARTS,
+            &ids.iter().map(String::as_str).collect::<Vec<_>>(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_search_combined() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        // One user matched by its ID, another one by a username built from that ID
+        let id_match = random_user("Combined Search")?;
+        let id = id_match.id.to_string();
+        let name_match = random_user(format!("{id} fan"))?;
+        UserSearch::put_to_index(&[&id_match, &name_match]).await?;
+
+        // Exact ID match first, then the name matches
+        let users = UserSearch::search_combined(&id, None, None).await?.unwrap();
+        assert_eq!(users.0, vec![id.clone(), name_match.id.to_string()]);
+
+        // Without an exact ID match, name matches rank above ID prefix matches
+        let users = UserSearch::search_combined(&id[..10], None, None)
+            .await?
+            .unwrap();
+        assert_eq!(users.0, vec![name_match.id.to_string(), id.clone()]);
+
+        // Pagination applies to the merged results
+        let users = UserSearch::search_combined(&id[..10], Some(1), Some(1))
+            .await?
+            .unwrap();
+        assert_eq!(users.0, vec![id.clone()]);
+
+        let name_match_id = name_match.id.to_string();
+        let name_records = [
+            format!("combined search:{id}"),
+            format!("{id} fan:{name_match_id}"),
+        ];
+        UserSearch::remove_from_index_sorted_set(
+            None,
+            &USER_NAME_KEY_PARTS,
+            &[&name_records[0], &name_records[1]],
+        )
+        .await?;
+        UserSearch::remove_from_index_sorted_set(None, &USER_ID_KEY_PARTS, &[&id, &name_match_id])
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_find_orphans() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        // Indexed for search, but never created in the graph
+        let orphan = random_user("Orphan User")?;
+        let orphan_id = orphan.id.to_string();
+        UserSearch::put_to_index(&[&orphan]).await?;
+        // From the mock data, both indexed and in the graph
+        let indexed_id = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+
+        let mut orphans = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let report = UserSearch::find_orphans(cursor, 10).await?;
+            orphans.extend(report.orphans);
+            match report.next_cursor {
+                Some(next_cursor) => cursor = next_cursor,
+                None => break,
+            }
+        }
+        assert!(orphans.contains(&orphan_id));
+        assert!(!orphans.iter().any(|id| id == indexed_id));
+
+        // The audit does not remove anything
+        let users = UserSearch::get_by_id(&orphan_id, None, None)
+            .await?
+            .unwrap();
+        assert_eq!(users.0, vec![orphan_id.clone()]);
+
+        let record = format!("{}:{orphan_id}", orphan.name.to_lowercase());
+        UserSearch::remove_from_index_sorted_set(None, &USER_NAME_KEY_PARTS, &[&record]).await?;
+        UserSearch::remove_from_index_sorted_set(None, &USER_ID_KEY_PARTS, &[&orphan_id]).await?;
+        Ok(())
+    }
+}
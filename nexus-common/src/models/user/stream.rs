@@ -11,6 +11,7 @@ use crate::models::post::{PostStream, POST_REPLIES_PER_POST_KEY_PARTS};
 use crate::types::{StreamReach, Timeframe};
 
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 use utoipa::ToSchema;
 
 pub const USER_MOSTFOLLOWED_KEY_PARTS: [&str; 2] = ["Users", "MostFollowed"];
@@ -94,23 +95,32 @@ impl UserStream {
         }
     }
 
+    /// Retrieves the listed users, leaving out the missing ones. A user that fails to load is
+    /// logged and left out too, the stream only fails when none of the users could be loaded
     pub async fn from_listed_user_ids(
         user_ids: &[String],
         viewer_id: Option<&str>,
         depth: Option<u8>,
     ) -> ModelResult<Option<Self>> {
         // Use the new mget batch operation to retrieve all user views efficiently
-        let user_views_result = UserView::get_by_ids(user_ids, viewer_id, depth).await?;
+        let results = UserView::get_each_by_ids(user_ids, viewer_id, depth).await;
 
         let mut user_views = Vec::with_capacity(user_ids.len());
-
-        for view in user_views_result.into_iter().flatten() {
-            user_views.push(view);
+        let mut last_error = None;
+        for (user_id, result) in user_ids.iter().zip(results) {
+            match result {
+                Ok(view) => user_views.extend(view),
+                Err(e) => {
+                    warn!("Failed to get the listed user {user_id}: {e}");
+                    last_error = Some(e);
+                }
+            }
         }
 
-        match user_views.is_empty() {
-            true => Ok(None),
-            false => Ok(Some(Self(user_views))),
+        match (user_views.is_empty(), last_error) {
+            (true, Some(e)) => Err(e),
+            (true, None) => Ok(None),
+            (false, _) => Ok(Some(Self(user_views))),
         }
     }
 
@@ -0,0 +1,22 @@
+use crate::models::traits::Collection;
+use crate::types::DynError;
+use pubky::Keypair;
+use pubky_app_specs::PubkyId;
+
+use super::UserDetails;
+
+/// Creates a user with a random key and the given name in the graph, returning their ID
+pub(crate) async fn create_user(name: &str) -> Result<String, DynError> {
+    let user_id = Keypair::random().public_key().to_z32();
+    let user = UserDetails {
+        name: name.to_string(),
+        bio: None,
+        id: PubkyId::try_from(user_id.as_str())?,
+        links: None,
+        status: None,
+        image: None,
+        indexed_at: chrono::Utc::now().timestamp_millis(),
+    };
+    user.put_to_graph().await?;
+    Ok(user_id)
+}
@@ -1,12 +1,18 @@
+use futures::future::join_all;
+use pubky_app_specs::PubkyId;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 use utoipa::ToSchema;
 
 use super::{Relationship, UserCounts, UserDetails};
+use crate::db::kv::single_flight;
 use crate::db::RedisOps;
 use crate::models::error::ModelResult;
+use crate::models::moderation::ModerationInfo;
 use crate::models::tag::traits::TagCollection;
 use crate::models::tag::user::TagUser;
 use crate::models::tag::TagDetails;
+use crate::StackManager;
 
 /// Represents a Pubky user with relational data including tags, counts, bookmark and relationship with other posts.
 #[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
@@ -15,6 +21,13 @@ pub struct UserView {
     pub counts: UserCounts,
     pub tags: Vec<TagDetails>,
     pub relationship: Relationship,
+    /// See [UserCounts::following_follower_ratio]
+    #[serde(default)]
+    pub following_follower_ratio: f32,
+    /// Why the user was removed by the moderator. Only set on the placeholder of a moderated
+    /// user, returned to its moderator instead of omitting the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderated: Option<ModerationInfo>,
 }
 
 impl UserView {
@@ -24,6 +37,17 @@ impl UserView {
         viewer_id: Option<&str>,
         depth: Option<u8>,
     ) -> ModelResult<Option<Self>> {
+        // On a cache miss, let a single caller rebuild the user from the graph while
+        // concurrent callers wait and then read the freshly warmed cache
+        let _guard = match StackManager::config().single_flight_on_read_miss
+            && UserDetails::try_from_index_json(&[user_id], None)
+                .await?
+                .is_none()
+        {
+            true => Some(single_flight::acquire(format!("UserView:{user_id}")).await),
+            false => None,
+        };
+
         // Perform all operations concurrently
         let (details, counts, relationship) = tokio::try_join!(
             UserDetails::get_by_id(user_id),
@@ -49,12 +73,26 @@ impl UserView {
 
         Ok(Some(Self {
             details,
+            following_follower_ratio: counts.following_follower_ratio(),
             counts,
             relationship,
             tags,
+            moderated: None,
         }))
     }
 
+    /// Placeholder of a user removed by the moderator, carrying only its ID and the moderation
+    pub fn moderated(user_id: PubkyId, moderation: ModerationInfo) -> Self {
+        Self {
+            details: UserDetails {
+                id: user_id,
+                ..Default::default()
+            },
+            moderated: Some(moderation),
+            ..Default::default()
+        }
+    }
+
     /// Retrieves multiple users by their IDs using batch Redis operations for better performance.
     ///
     /// This method uses the new `mget` operation to fetch user details and counts in bulk,
@@ -67,6 +105,7 @@ impl UserView {
         // Use mget to fetch all user details and counts in bulk
         let (details_list, counts_list): (Vec<Option<UserDetails>>, Vec<Option<UserCounts>>) =
             tokio::try_join!(UserDetails::mget(user_ids), UserCounts::mget(user_ids))?;
+        let relationships = Relationship::get_by_ids(user_ids, viewer_id).await?;
 
         let mut user_views = Vec::with_capacity(user_ids.len());
 
@@ -80,9 +119,7 @@ impl UserView {
             };
 
             let counts = counts.clone().unwrap_or_default();
-            let relationship = Relationship::get_by_id(user_id, viewer_id)
-                .await?
-                .unwrap_or_default();
+            let relationship = relationships[i].clone().unwrap_or_default();
 
             // Before fetching post tags, check if the post has any tags
             let tags = match counts.tags {
@@ -94,12 +131,40 @@ impl UserView {
 
             user_views.push(Some(Self {
                 details: details.clone(),
+                following_follower_ratio: counts.following_follower_ratio(),
                 counts,
                 relationship,
                 tags,
+                moderated: None,
             }));
         }
 
         Ok(user_views)
     }
+
+    /// Retrieves multiple users like [Self::get_by_ids], a user that fails to load yielding its
+    /// own error instead of failing the others.
+    ///
+    /// The users are only retrieved one by one when the batch fails.
+    pub async fn get_each_by_ids(
+        user_ids: &[String],
+        viewer_id: Option<&str>,
+        depth: Option<u8>,
+    ) -> Vec<ModelResult<Option<Self>>> {
+        match Self::get_by_ids(user_ids, viewer_id, depth).await {
+            Ok(views) => views.into_iter().map(Ok).collect(),
+            Err(e) => {
+                warn!(
+                    "Failed to get a batch of {} users, getting them one by one: {e}",
+                    user_ids.len()
+                );
+                join_all(
+                    user_ids
+                        .iter()
+                        .map(|user_id| Self::get_by_id(user_id, viewer_id, depth)),
+                )
+                .await
+            }
+        }
+    }
 }
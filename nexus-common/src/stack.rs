@@ -1,4 +1,5 @@
 use crate::db::{Neo4jConnector, RedisConnector};
+use crate::media::processors::ImageProcessor;
 use crate::types::DynError;
 use crate::{Level, StackConfig};
 use opentelemetry::trace::TracerProvider;
@@ -9,6 +10,7 @@ use opentelemetry_sdk::logs::SdkLoggerProvider;
 use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
 use opentelemetry_sdk::trace::SdkTracerProvider;
 use opentelemetry_sdk::Resource;
+use std::sync::OnceLock;
 use std::time::Duration;
 use tokio::sync::OnceCell;
 use tracing::{error, info};
@@ -36,6 +38,7 @@ impl StackManager {
 
                 RedisConnector::init(&config.db.redis).await?;
                 Neo4jConnector::init(&config.db.neo4j).await?;
+                ImageProcessor::detect_avif_support().await;
                 Ok::<_, DynError>(config.clone())
             })
             .await?;
@@ -47,6 +50,16 @@ impl StackManager {
         Ok(())
     }
 
+    /// Returns the [`StackConfig`] the stack was set up with, read by the shared models for the
+    /// settings the watcher and the API must agree on. Until [`StackManager::setup`] runs, e.g. in
+    /// unit tests, the default config is returned
+    pub fn config() -> &'static StackConfig {
+        static DEFAULT_CONFIG: OnceLock<StackConfig> = OnceLock::new();
+        STACK_CONFIG
+            .get()
+            .unwrap_or_else(|| DEFAULT_CONFIG.get_or_init(StackConfig::default))
+    }
+
     async fn setup_logging(service_name: &str, otel_endpoint: &Option<String>, log_level: Level) {
         match otel_endpoint {
             None => Self::setup_local_logging(log_level),
@@ -1,9 +1,11 @@
 mod pagination;
 pub mod routes;
 mod timeframe;
+mod variants;
 
-pub use pagination::Pagination;
+pub use pagination::{parse_string_to_bool, Pagination};
 pub use timeframe::Timeframe;
+pub use variants::variant_names;
 
 use serde::de::{self, Deserializer};
 use serde::{Deserialize, Serialize};
@@ -37,3 +37,17 @@ where
         None => Ok(None),
     }
 }
+
+/// Parses a query param string into a bool, `false` when absent.
+///
+/// Query params reach the structs with flattened fields as strings, so their booleans need it
+pub fn parse_string_to_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        Some(s) => s.parse::<bool>().map_err(de::Error::custom),
+        None => Ok(false),
+    }
+}
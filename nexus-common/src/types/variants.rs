@@ -0,0 +1,96 @@
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use std::fmt::{self, Display};
+
+/// Returns the string values a unit-like enum deserializes from, e.g. the values accepted for it
+/// in a query parameter.
+///
+/// The values are read from the `Deserialize` impl of `T`, as listed by it when rejecting an
+/// unknown variant, so they follow its `serde` renames and new variants. Returns an empty slice
+/// if `T` does not list the expected variants.
+pub fn variant_names<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    match T::deserialize(VariantsDeserializer) {
+        Err(VariantsError(Some(variants))) => variants,
+        _ => &[],
+    }
+}
+
+/// Error capturing the variants listed by [de::Error::unknown_variant]
+#[derive(Debug)]
+struct VariantsError(Option<&'static [&'static str]>);
+
+impl Display for VariantsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected one of {:?}", self.0.unwrap_or_default())
+    }
+}
+
+impl std::error::Error for VariantsError {}
+
+impl de::Error for VariantsError {
+    fn custom<T: Display>(_msg: T) -> Self {
+        VariantsError(None)
+    }
+
+    fn unknown_variant(_variant: &str, expected: &'static [&'static str]) -> Self {
+        VariantsError(Some(expected))
+    }
+}
+
+/// Deserializer that rejects every value, to collect the variants an enum expects instead
+struct VariantsDeserializer;
+
+impl<'de> Deserializer<'de> for VariantsDeserializer {
+    type Error = VariantsError;
+
+    /// Provides an empty string, which custom `Deserialize` impls reject as an unknown variant
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str("")
+    }
+
+    /// Derived `Deserialize` impls of enums pass their variants here
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(VariantsError(Some(variants)))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::variant_names;
+    use crate::types::{StreamReach, StreamSorting, Timeframe};
+
+    #[test]
+    fn test_variant_names() {
+        assert_eq!(
+            variant_names::<StreamSorting>(),
+            ["timeline", "total_engagement"]
+        );
+        assert_eq!(
+            variant_names::<Timeframe>(),
+            ["today", "this_month", "all_time"]
+        );
+        assert_eq!(
+            variant_names::<StreamReach>(),
+            [
+                "followers",
+                "following",
+                "friends",
+                "wot",
+                "wot_1",
+                "wot_2",
+                "wot_3"
+            ]
+        );
+        assert!(variant_names::<String>().is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::service::NexusWatcher;
 use nexus_common::db::{DatabaseConfig, PubkyConnector};
+use nexus_common::models::notification::NotificationWebhook;
 use nexus_common::types::DynError;
 use nexus_common::utils::create_shutdown_rx;
 use nexus_common::WatcherConfig;
@@ -68,6 +69,9 @@ impl NexusWatcherBuilder {
     /// - `shutdown_rx`: optional shutdown signal. If none is provided, a default one will be created, listening for Ctrl-C.
     pub async fn start(self, shutdown_rx: Option<Receiver<bool>>) -> Result<(), DynError> {
         StackManager::setup(&self.0.stack).await?;
+        if let Some(webhook) = self.0.notification_webhook.clone() {
+            NotificationWebhook::start(webhook);
+        }
         let shutdown_rx = shutdown_rx.unwrap_or_else(create_shutdown_rx);
 
         let testnet_host = self.0.testnet.then_some(self.0.testnet_host.as_str());
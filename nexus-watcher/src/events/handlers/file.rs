@@ -1,17 +1,16 @@
 use crate::events::EventProcessorError;
 
 use nexus_common::db::PubkyConnector;
-use nexus_common::media::FileVariant;
-use nexus_common::media::VariantController;
-use nexus_common::models::file::Blob;
+use nexus_common::media::{max_file_size_bytes, FileVariant, VariantController};
+use nexus_common::models::file::StaticFileWriter;
 use nexus_common::models::{
     file::{FileDetails, FileMeta},
     traits::Collection,
 };
-use pubky_app_specs::{PubkyAppFile, PubkyAppObject, PubkyId};
+use pubky_app_specs::{ParsedUri, PubkyAppFile, PubkyId, Resource};
 use std::path::{Path, PathBuf};
 use tokio::fs::remove_dir_all;
-use tracing::debug;
+use tracing::{debug, warn};
 
 #[tracing::instrument(name = "file.put", skip_all, fields(user_id = %user_id, file_id = %file_id))]
 pub async fn sync_put(
@@ -45,6 +44,10 @@ pub async fn sync_put(
 }
 
 // TODO: Move it into its own process, server, etc
+/// Downloads the blob of a file into the static files directory.
+///
+/// The response body is streamed to disk chunk by chunk and capped at the configured maximum
+/// file size, so large videos are never buffered in memory.
 #[tracing::instrument(name = "file.ingest", skip_all, fields(user_id = %user_id, file_id = %file_id))]
 async fn ingest(
     user_id: &PubkyId,
@@ -52,36 +55,67 @@ async fn ingest(
     pubkyapp_file: &PubkyAppFile,
     files_path: PathBuf,
 ) -> Result<FileMeta, EventProcessorError> {
+    let is_blob = ParsedUri::try_from(pubkyapp_file.src.as_str())
+        .is_ok_and(|parsed_uri| matches!(parsed_uri.resource, Resource::Blob(_)));
+    if !is_blob {
+        return Err(EventProcessorError::InvalidEventLine(format!(
+            "The file has a source uri that is not a blob path: {}",
+            pubkyapp_file.src
+        )));
+    }
+
     let pubky = PubkyConnector::get()?;
-    let response = pubky.public_storage().get(&pubkyapp_file.src).await?;
+    let mut response = pubky.public_storage().get(&pubkyapp_file.src).await?;
 
     let path = Path::new(&user_id.to_string()).join(file_id);
     let full_path = files_path.join(path.clone());
 
-    let blob = response
-        .bytes()
+    let mut writer = StaticFileWriter::create(
+        &full_path,
+        &FileVariant::Main.to_string(),
+        max_file_size_bytes(),
+    )
+    .await
+    .map_err(EventProcessorError::static_save_failed)?;
+
+    loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break,
+            Err(e) => {
+                writer.abort().await;
+                return Err(EventProcessorError::client_error(e.to_string()));
+            }
+        };
+        writer
+            .write_chunk(&chunk)
+            .await
+            .map_err(EventProcessorError::static_save_failed)?;
+    }
+    writer
+        .finish()
         .await
-        .map_err(|e| EventProcessorError::client_error(e.to_string()))?;
-    let pubky_app_object = PubkyAppObject::from_uri(&pubkyapp_file.src, &blob)
-        .map_err(EventProcessorError::generic)?;
-
-    match pubky_app_object {
-        PubkyAppObject::Blob(blob) => {
-            Blob::put_to_static(FileVariant::Main.to_string(), full_path, &blob)
-                .await
-                .map_err(EventProcessorError::static_save_failed)?;
-
-            let urls = VariantController::get_file_urls_by_content_type(
-                pubkyapp_file.content_type.as_str(),
-                &path,
-            );
-            Ok(FileMeta { urls })
+        .map_err(EventProcessorError::static_save_failed)?;
+
+    // The original is served as is, so its metadata has to go before it is exposed. An original
+    // that cannot be stripped is removed rather than served with its metadata
+    let main_file = full_path.join(FileVariant::Main.to_string());
+    if let Err(e) =
+        VariantController::strip_metadata(pubkyapp_file.content_type.as_str(), &main_file).await
+    {
+        if let Err(remove_err) = remove_dir_all(&full_path).await {
+            warn!("Could not remove the unstripped file at {full_path:?}: {remove_err}");
         }
-        _ => Err(EventProcessorError::InvalidEventLine(format!(
-            "The file has a source uri that is not a blob path: {}",
-            pubkyapp_file.src
-        ))),
+        return Err(EventProcessorError::MediaProcessorError(e.to_string()));
     }
+
+    let urls = VariantController::get_file_urls_by_content_type(
+        pubkyapp_file.content_type.as_str(),
+        &path,
+    );
+    let blurhash =
+        VariantController::compute_blurhash(pubkyapp_file.content_type.as_str(), &main_file).await;
+    Ok(FileMeta { urls, blurhash })
 }
 
 #[tracing::instrument(name = "file.del", skip_all, fields(user_id = %user_id, file_id = %file_id))]
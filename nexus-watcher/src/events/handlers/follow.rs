@@ -1,21 +1,29 @@
 use crate::events::retry::event::RetryEvent;
-use crate::events::EventProcessorError;
+use crate::events::{EventProcessorError, EventSettings};
 
+use chrono::Utc;
 use nexus_common::db::kv::JsonAction;
 use nexus_common::db::OperationOutcome;
-use nexus_common::models::follow::{Followers, Following, Friends, UserFollows};
+use nexus_common::models::follow::{FollowStats, Followers, Following, Friends, UserFollows};
 use nexus_common::models::homeserver::Homeserver;
 use nexus_common::models::notification::Notification;
 use nexus_common::models::user::UserCounts;
 use pubky_app_specs::PubkyId;
-use tracing::debug;
+use tracing::{debug, warn};
 
 #[tracing::instrument(name = "follow.put", skip_all, fields(follower_id = %follower_id, followee_id = %followee_id))]
 pub async fn sync_put(
     follower_id: PubkyId,
     followee_id: PubkyId,
+    created_at: i64,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     debug!("Indexing new follow: {} -> {}", follower_id, followee_id);
+    // A follow of the user to themselves would count as a follower and as reach
+    if follower_id == followee_id && !settings.allow_self_follows {
+        warn!("Ignoring the follow of user {follower_id} to themselves");
+        return Ok(());
+    }
     // SAVE TO GRAPH
     // (follower_id)-[:FOLLOWS]->(followee_id)
     match Followers::put_to_graph(&follower_id, &followee_id).await? {
@@ -50,16 +58,24 @@ pub async fn sync_put(
                     &follower_id,
                     &followee_id,
                     JsonAction::Increment(1),
-                    will_be_friends
+                    will_be_friends,
+                    settings
                 ),
                 // Notify the followee
-                Notification::new_follow(&follower_id, &followee_id, will_be_friends)
+                Notification::new_follow(&follower_id, &followee_id, will_be_friends),
+                // Record when the followee gained the follower, a follow cannot be created in the future
+                FollowStats::put_to_index(
+                    &followee_id,
+                    &follower_id,
+                    created_at.min(Utc::now().timestamp_millis())
+                )
             );
 
             indexing_results.0?;
             indexing_results.1?;
             indexing_results.2?;
             indexing_results.3?;
+            indexing_results.4?;
         }
     };
 
@@ -67,15 +83,20 @@ pub async fn sync_put(
 }
 
 #[tracing::instrument(name = "follow.del", skip_all, fields(follower_id = %follower_id, followee_id = %followee_id))]
-pub async fn del(follower_id: PubkyId, followee_id: PubkyId) -> Result<(), EventProcessorError> {
+pub async fn del(
+    follower_id: PubkyId,
+    followee_id: PubkyId,
+    settings: &EventSettings,
+) -> Result<(), EventProcessorError> {
     debug!("Deleting follow: {} -> {}", follower_id, followee_id);
     // Maybe we could do it here but lets follow the naming convention
-    sync_del(follower_id, followee_id).await
+    sync_del(follower_id, followee_id, settings).await
 }
 
 pub async fn sync_del(
     follower_id: PubkyId,
     followee_id: PubkyId,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     match Followers::del_from_graph(&follower_id, &followee_id).await? {
         // Both users exists but they do not have that relationship
@@ -100,14 +121,17 @@ pub async fn sync_del(
                     &followee_id,
                     JsonAction::Decrement(1),
                     were_friends,
+                    settings,
                 ),
                 // Notify the followee
-                Notification::lost_follow(&follower_id, &followee_id, were_friends)
+                Notification::lost_follow(&follower_id, &followee_id, were_friends),
+                FollowStats::del_from_index(&followee_id, &follower_id)
             );
             indexing_results.0?;
             indexing_results.1?;
             indexing_results.2?;
             indexing_results.3?;
+            indexing_results.4?;
 
             Ok(())
         }
@@ -119,10 +143,12 @@ async fn update_follow_counts(
     followee_id: &str,
     counter: JsonAction,
     update_friend_relationship: bool,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     // Update UserCount related indexes
     UserCounts::update_index_field(follower_id, "following", counter.clone()).await?;
     UserCounts::update(followee_id, "followers", counter.clone(), None).await?;
+    UserCounts::snapshot_followers(followee_id, settings.follower_snapshot_interval_secs).await?;
 
     if update_friend_relationship {
         UserCounts::update_index_field(follower_id, "friends", counter.clone()).await?;
@@ -1,14 +1,17 @@
 use crate::events::retry::event::RetryEvent;
-use crate::events::EventProcessorError;
+use crate::events::{EventProcessorError, EventSettings};
 
+use nexus_common::db::kv::JsonAction;
 use nexus_common::db::queries::get::post_is_safe_to_delete;
 use nexus_common::db::{exec_single_row, execute_graph_operation, OperationOutcome};
 use nexus_common::db::{queries, RedisOps};
 use nexus_common::models::homeserver::Homeserver;
 use nexus_common::models::notification::{Notification, PostChangedSource, PostChangedType};
+use nexus_common::models::post::search::PostsByContentSearch;
 use nexus_common::models::post::{
-    PostCounts, PostDetails, PostRelationships, PostStream, POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+    PostCounts, PostDescendants, PostDetails, PostRelationships, PostStream, PostsByEmbed,
 };
+use nexus_common::models::tombstone::Tombstone;
 use nexus_common::models::user::UserCounts;
 use pubky_app_specs::{
     post_uri_builder, ParsedUri, PubkyAppPost, PubkyAppPostKind, PubkyId, Resource,
@@ -22,6 +25,7 @@ pub async fn sync_put(
     post: PubkyAppPost,
     author_id: PubkyId,
     post_id: String,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     debug!("Indexing new post: {}/{}", author_id, post_id);
     // Create PostDetails object
@@ -69,11 +73,22 @@ pub async fn sync_put(
             .ok_or("An existing post in graph, could not be retrieved from index")
             .map_err(EventProcessorError::generic)?;
         if existing_details.content != post_details.content {
-            sync_edit(post, author_id, post_id, post_details).await?;
+            sync_edit(
+                post,
+                author_id,
+                post_id,
+                post_details,
+                existing_details,
+                settings,
+            )
+            .await?;
         }
         return Ok(());
     }
 
+    // A post indexed again under the ID of an erased one is no longer gone
+    Tombstone::del_post(&author_id, &post_id).await?;
+
     // IMPORTANT: Handle the mentions before traverse the graph (reindex_post) for that post
     // Handle "MENTIONED" relationships
     put_mentioned_relationships(
@@ -150,17 +165,17 @@ pub async fn sync_put(
 
         let indexing_results = nexus_common::traced_join!(
             tracing::info_span!("index.write", phase = "reply_parent");
-            PostCounts::increment_index_field(parent_post_key_parts, "replies", None),
             async {
-                if !post_relationships_is_reply(&parent_author_id, &parent_post_id).await? {
-                    PostStream::increment_score_index_sorted_set(
-                        &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                        parent_post_key_parts,
-                    )
-                    .await
-                    .map_err(EventProcessorError::index_operation_failed)?;
-                }
-                Ok::<(), EventProcessorError>(())
+                // The reply count and the engagement score are updated together, replies are not ranked
+                let is_reply = post_relationships_is_reply(&parent_author_id, &parent_post_id).await?;
+                PostCounts::update_index_field_with_engagement(
+                    parent_post_key_parts,
+                    "replies",
+                    JsonAction::Increment(1),
+                    !is_reply,
+                )
+                .await
+                .map_err(EventProcessorError::index_operation_failed)
             },
             PostStream::add_to_post_reply_sorted_set(
                 parent_post_key_parts,
@@ -173,7 +188,14 @@ pub async fn sync_put(
                 &replied_uri_str,
                 &post_details.uri,
                 &parent_author_id,
-            )
+            ),
+            async {
+                // The new reply is a descendant of its parent and of all the parent ancestors
+                if settings.descendant_counts {
+                    PostDescendants::update_ancestors(&parent_author_id, &parent_post_id, JsonAction::Increment(1)).await?;
+                }
+                Ok::<(), EventProcessorError>(())
+            }
         );
 
         indexing_results.0?;
@@ -200,18 +222,17 @@ pub async fn sync_put(
         let parent_post_key_parts: &[&str; 2] = &[&parent_author_id, &parent_post_id];
         let indexing_results = nexus_common::traced_join!(
             tracing::info_span!("index.write", phase = "repost_parent");
-            PostCounts::increment_index_field(parent_post_key_parts, "reposts", None),
             async {
-                // Post replies cannot be included in the total engagement index after they receive a reply
-                if !post_relationships_is_reply(&parent_author_id, &parent_post_id).await? {
-                    PostStream::increment_score_index_sorted_set(
-                        &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                        parent_post_key_parts,
-                    )
-                    .await
-                    .map_err(EventProcessorError::index_operation_failed)?;
-                }
-                Ok::<(), EventProcessorError>(())
+                // Post replies cannot be included in the total engagement index after they receive a repost
+                let is_reply = post_relationships_is_reply(&parent_author_id, &parent_post_id).await?;
+                PostCounts::update_index_field_with_engagement(
+                    parent_post_key_parts,
+                    "reposts",
+                    JsonAction::Increment(1),
+                    !is_reply,
+                )
+                .await
+                .map_err(EventProcessorError::index_operation_failed)
             },
             Notification::new_repost(
                 &author_id,
@@ -223,18 +244,31 @@ pub async fn sync_put(
 
         indexing_results.0?;
         indexing_results.1?;
-        indexing_results.2?;
     }
 
     // PHASE 4: Add post related content
     let indexing_results = nexus_common::traced_join!(
         tracing::info_span!("index.write", phase = "post_details");
         post_relationships.put_to_index(&author_id, &post_id),
-        post_details.put_to_index(&author_id, reply_parent_post_key_wrapper, false)
+        post_details.put_to_index(&author_id, reply_parent_post_key_wrapper, false),
+        PostsByContentSearch::put_to_index(
+            &author_id,
+            &post_id,
+            &post_details.content,
+            post_details.indexed_at
+        ),
+        async {
+            if let Some(embed) = post.embed.as_ref().filter(|_| settings.index_embeds) {
+                PostsByEmbed::put_to_index(&author_id, &post_id, &embed.uri, post_details.indexed_at).await?;
+            }
+            Ok::<(), EventProcessorError>(())
+        }
     );
 
     indexing_results.0?;
     indexing_results.1?;
+    indexing_results.2?;
+    indexing_results.3?;
 
     Ok(())
 }
@@ -244,6 +278,8 @@ async fn sync_edit(
     author_id: PubkyId,
     post_id: String,
     post_details: PostDetails,
+    existing_details: PostDetails,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     // Construct the URI of the post that changed
     let changed_uri = post_uri_builder(author_id.to_string(), post_id.clone());
@@ -259,6 +295,32 @@ async fn sync_edit(
         PostChangedType::Edited
     };
 
+    // Replace the content search tokens, a deleted post is not searchable anymore
+    PostsByContentSearch::del_from_index(&author_id, &post_id, &existing_details.content).await?;
+    if let PostChangedType::Edited = change_type {
+        PostsByContentSearch::put_to_index(
+            &author_id,
+            &post_id,
+            &post_details.content,
+            existing_details.indexed_at,
+        )
+        .await?;
+    }
+
+    // Likewise for the embed index, which the post leaves once deleted
+    PostsByEmbed::del_from_index(&author_id, &post_id).await?;
+    if let (PostChangedType::Edited, Some(embed)) = (&change_type, &post.embed) {
+        if settings.index_embeds {
+            PostsByEmbed::put_to_index(
+                &author_id,
+                &post_id,
+                &embed.uri,
+                existing_details.indexed_at,
+            )
+            .await?;
+        }
+    }
+
     // Send notifications to users who interacted with the post
     Notification::changed_post(&author_id, &post_id, &changed_uri, &change_type).await?;
 
@@ -332,7 +394,11 @@ async fn put_mentioned_relationships_for_prefix(
 }
 
 #[tracing::instrument(name = "post.del", skip_all, fields(user_id = %author_id, post_id = %post_id))]
-pub async fn del(author_id: PubkyId, post_id: String) -> Result<(), EventProcessorError> {
+pub async fn del(
+    author_id: PubkyId,
+    post_id: String,
+    settings: &EventSettings,
+) -> Result<(), EventProcessorError> {
     debug!("Deleting post: {}/{}", author_id, post_id);
 
     // Graph query to check if there is any edge at all to this post other than AUTHORED, is a reply or is a repost.
@@ -345,7 +411,7 @@ pub async fn del(author_id: PubkyId, post_id: String) -> Result<(), EventProcess
         .await
         .map_err(EventProcessorError::graph_query_failed)?
     {
-        OperationOutcome::CreatedOrDeleted => sync_del(author_id, post_id).await?,
+        OperationOutcome::CreatedOrDeleted => sync_del(author_id, post_id, settings).await?,
         OperationOutcome::Updated => {
             let existing_relationships = PostRelationships::get_by_id(&author_id, &post_id).await?;
             let parent = existing_relationships
@@ -361,7 +427,7 @@ pub async fn del(author_id: PubkyId, post_id: String) -> Result<(), EventProcess
                 attachments: None,
             };
 
-            sync_put(dummy_deleted_post, author_id, post_id).await?;
+            sync_put(dummy_deleted_post, author_id, post_id, settings).await?;
         }
         OperationOutcome::MissingDependency => return Err(EventProcessorError::SkipIndexing),
     };
@@ -369,7 +435,11 @@ pub async fn del(author_id: PubkyId, post_id: String) -> Result<(), EventProcess
     Ok(())
 }
 
-pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), EventProcessorError> {
+pub async fn sync_del(
+    author_id: PubkyId,
+    post_id: String,
+    settings: &EventSettings,
+) -> Result<(), EventProcessorError> {
     let deleted_uri = post_uri_builder(author_id.to_string(), post_id.clone());
 
     let post_relationships = PostRelationships::get_by_id(&author_id, &post_id).await?;
@@ -422,18 +492,17 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), EventPr
 
             let indexing_results = nexus_common::traced_join!(
                 tracing::info_span!("index.delete", phase = "reply_parent");
-                PostCounts::decrement_index_field(&parent_post_key_parts, "replies", None),
                 async {
                     // Post replies cannot be included in the total engagement index after the reply is deleted
-                    if !post_relationships_is_reply(&parent_user_id, &parent_post_id).await? {
-                        PostStream::decrement_score_index_sorted_set(
-                            &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                            &parent_post_key_parts,
-                        )
-                        .await
-                        .map_err(EventProcessorError::index_operation_failed)?;
-                    }
-                    Ok::<(), EventProcessorError>(())
+                    let is_reply = post_relationships_is_reply(&parent_user_id, &parent_post_id).await?;
+                    PostCounts::update_index_field_with_engagement(
+                        &parent_post_key_parts,
+                        "replies",
+                        JsonAction::Decrement(1),
+                        !is_reply,
+                    )
+                    .await
+                    .map_err(EventProcessorError::index_operation_failed)
                 },
                 // Notification: "A reply to your post was deleted"
                 Notification::post_children_changed(
@@ -443,7 +512,13 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), EventPr
                     &deleted_uri,
                     PostChangedSource::Reply,
                     &PostChangedType::Deleted,
-                )
+                ),
+                async {
+                    if settings.descendant_counts {
+                        PostDescendants::update_ancestors(&parent_user_id, &parent_post_id, JsonAction::Decrement(1)).await?;
+                    }
+                    Ok::<(), EventProcessorError>(())
+                }
             );
 
             indexing_results.0?;
@@ -469,18 +544,17 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), EventPr
 
             let indexing_results = nexus_common::traced_join!(
                 tracing::info_span!("index.delete", phase = "repost_parent");
-                PostCounts::decrement_index_field(parent_post_key_parts, "reposts", None),
                 async {
                     // Post replies cannot be included in the total engagement index after the repost is deleted
-                    if !post_relationships_is_reply(&reposted_uri.user_id, &parent_post_id).await? {
-                        PostStream::decrement_score_index_sorted_set(
-                            &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                            parent_post_key_parts,
-                        )
-                        .await
-                        .map_err(EventProcessorError::index_operation_failed)?;
-                    }
-                    Ok::<(), EventProcessorError>(())
+                    let is_reply = post_relationships_is_reply(&reposted_uri.user_id, &parent_post_id).await?;
+                    PostCounts::update_index_field_with_engagement(
+                        parent_post_key_parts,
+                        "reposts",
+                        JsonAction::Decrement(1),
+                        !is_reply,
+                    )
+                    .await
+                    .map_err(EventProcessorError::index_operation_failed)
                 },
                 // Notification: "A repost of your post was deleted"
                 Notification::post_children_changed(
@@ -495,9 +569,14 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), EventPr
 
             indexing_results.0?;
             indexing_results.1?;
-            indexing_results.2?;
         }
     }
+    // The content is needed to find the content search tokens of the post
+    if let Some(post_details) = PostDetails::get_from_index(&author_id, &post_id).await? {
+        PostsByContentSearch::del_from_index(&author_id, &post_id, &post_details.content).await?;
+    }
+    PostsByEmbed::del_from_index(&author_id, &post_id).await?;
+
     let indexing_results = nexus_common::traced_join!(
         tracing::info_span!("index.delete", phase = "post_details");
         PostDetails::delete(&author_id, &post_id, reply_parent_post_key_wrapper),
@@ -507,5 +586,9 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), EventPr
     indexing_results.0?;
     indexing_results.1?;
 
+    if settings.record_tombstones {
+        Tombstone::put_post(&author_id, &post_id, settings.tombstone_ttl_secs).await?;
+    }
+
     Ok(())
 }
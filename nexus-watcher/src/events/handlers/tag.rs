@@ -1,13 +1,14 @@
 use crate::events::retry::event::RetryEvent;
-use crate::events::EventProcessorError;
+use crate::events::{EventProcessorError, EventSettings};
 
 use chrono::Utc;
-use nexus_common::db::kv::ScoreAction;
+use nexus_common::db::kv::{JsonAction, ScoreAction};
 use nexus_common::db::OperationOutcome;
 use nexus_common::models::homeserver::Homeserver;
 use nexus_common::models::notification::Notification;
 use nexus_common::models::post::search::PostsByTagSearch;
-use nexus_common::models::post::{PostCounts, PostStream};
+use nexus_common::models::post::PostCounts;
+use nexus_common::models::tag::alias::TagAlias;
 use nexus_common::models::tag::post::TagPost;
 use nexus_common::models::tag::search::TagSearch;
 use nexus_common::models::tag::traits::{TagCollection, TaggersCollection};
@@ -15,7 +16,7 @@ use nexus_common::models::tag::user::TagUser;
 use nexus_common::models::user::UserCounts;
 use nexus_common::types::Pagination;
 use pubky_app_specs::{post_uri_builder, ParsedUri, PubkyAppTag, PubkyId, Resource};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use super::utils::post_relationships_is_reply;
 
@@ -24,6 +25,7 @@ pub async fn sync_put(
     tag: PubkyAppTag,
     tagger_id: PubkyId,
     tag_id: String,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     debug!("Indexing new tag: {} -> {}", tagger_id, tag_id);
 
@@ -31,18 +33,33 @@ pub async fn sync_put(
     let parsed_uri = ParsedUri::try_from(tag.uri.as_str()).map_err(EventProcessorError::generic)?;
     let user_id = parsed_uri.user_id;
     let indexed_at = Utc::now().timestamp_millis();
+    // Aliased labels are indexed under their canonical label
+    let tag_label = TagAlias::canonical(&tag.label).await?;
+    let max_tags = settings.max_tags_per_target;
 
     match parsed_uri.resource {
         // If post_id is in the tagged URI, we place tag to a post.
         Resource::Post(post_id) => {
+            // Bound the distinct labels of a post, new labels beyond the cap are not indexed
+            if !TagPost::accepts_label(&user_id, Some(&post_id), &tag_label, max_tags).await? {
+                warn!("Skipping tag {tag_id} on post {user_id}:{post_id}: label {tag_label} exceeds the cap of {max_tags} distinct labels");
+                return Ok(());
+            }
             // Place the tag on post
             put_sync_post(
-                tagger_id, user_id, &post_id, &tag_id, &tag.label, &tag.uri, indexed_at,
+                tagger_id, user_id, &post_id, &tag_id, &tag_label, &tag.uri, indexed_at,
             )
             .await
         }
         // If no post_id in the tagged URI, we place tag to a user.
-        Resource::User => put_sync_user(tagger_id, user_id, &tag_id, &tag.label, indexed_at).await,
+        Resource::User => {
+            // Bound the distinct labels of a user, new labels beyond the cap are not indexed
+            if !TagUser::accepts_label(&user_id, None, &tag_label, max_tags).await? {
+                warn!("Skipping tag {tag_id} on user {user_id}: label {tag_label} exceeds the cap of {max_tags} distinct labels");
+                return Ok(());
+            }
+            put_sync_user(tagger_id, user_id, &tag_id, &tag_label, indexed_at).await
+        }
         other => Err(EventProcessorError::generic(format!(
             "The tagged resource is not Post or User, instead is: {other:?}"
         ))),
@@ -98,8 +115,6 @@ async fn put_sync_post(
                 tracing::info_span!("index.write", phase = "tag_post");
                 // Update user counts for tagger
                 UserCounts::increment(&tagger_user_id, "tagged", None),
-                // Increment in one the post tags
-                PostCounts::increment_index_field(post_key_slice, "tags", None),
                 async {
                     // Increase unique_tags if the tag does not exist already
                     // NOTE: To update that field, it cannot exist in TagPost SORTED SET the tag. Thats why it has to be executed
@@ -125,25 +140,26 @@ async fn put_sync_post(
                 // Add post to label total engagement
                 PostsByTagSearch::update_index_score(&author_id, post_id, tag_label, ScoreAction::Increment(1.0)),
                 async {
+                    // Increment in one the post tags and the post global engagement together
                     // Post replies cannot be included in the total engagement index once they have been tagged
-                    if !post_relationships_is_reply(&author_id, post_id).await? {
-                        // Increment in one post global engagement
-                        PostStream::update_index_score(
-                            &author_id,
-                            post_id,
-                            ScoreAction::Increment(1.0),
-                        )
-                        .await
-                        .map_err(EventProcessorError::index_operation_failed)?;
-                    }
-                    Ok::<(), EventProcessorError>(())
+                    let is_reply = post_relationships_is_reply(&author_id, post_id).await?;
+                    PostCounts::update_index_field_with_engagement(
+                        post_key_slice,
+                        "tags",
+                        JsonAction::Increment(1),
+                        !is_reply,
+                    )
+                    .await
+                    .map_err(EventProcessorError::index_operation_failed)
                 },
                 // Add post to global label timeline
                 PostsByTagSearch::put_to_index(&author_id, post_id, tag_label),
                 // Save new notification
                 Notification::new_post_tag(&tagger_user_id, &author_id, tag_label, post_uri),
                 // Add tag to search index
-                TagSearch::put_to_index(tag_label_slice)
+                TagSearch::put_to_index(tag_label_slice),
+                // Add label to the labels applied by the tagger
+                TagPost::update_tagger_label_score(&tagger_user_id, tag_label, ScoreAction::Increment(1.0))
             );
 
             indexing_results.0?;
@@ -220,7 +236,9 @@ async fn put_sync_user(
                 // Save new notification
                 Notification::new_user_tag(&tagger_user_id, &tagged_user_id, tag_label),
                 // Add tag to search index
-                TagSearch::put_to_index(tag_label_slice)
+                TagSearch::put_to_index(tag_label_slice),
+                // Add label to the labels applied by the tagger
+                TagUser::update_tagger_label_score(&tagger_user_id, tag_label, ScoreAction::Increment(1.0))
             );
 
             indexing_results.0?;
@@ -229,6 +247,7 @@ async fn put_sync_user(
             indexing_results.3?;
             indexing_results.4?;
             indexing_results.5?;
+            indexing_results.6?;
 
             Ok(())
         }
@@ -236,7 +255,11 @@ async fn put_sync_user(
 }
 
 #[tracing::instrument(name = "tag.del", skip_all, fields(user_id = %user_id, tag_id = %tag_id))]
-pub async fn del(user_id: PubkyId, tag_id: String) -> Result<(), EventProcessorError> {
+pub async fn del(
+    user_id: PubkyId,
+    tag_id: String,
+    settings: &EventSettings,
+) -> Result<(), EventProcessorError> {
     debug!("Deleting tag: {} -> {}", user_id, tag_id);
     let tag_details = TagUser::del_from_graph(&user_id, &tag_id).await?;
     // CHOOSE THE EVENT TYPE
@@ -248,7 +271,7 @@ pub async fn del(user_id: PubkyId, tag_id: String) -> Result<(), EventProcessorE
             }
             // Delete post related indexes
             (None, Some(post_id), Some(author_id)) => {
-                del_sync_post(user_id, &post_id, &author_id, &label).await?;
+                del_sync_post(user_id, &post_id, &author_id, &label, settings).await?;
             }
             // Handle other unexpected cases
             _ => {
@@ -288,7 +311,9 @@ async fn del_sync_user(
             Ok::<(), EventProcessorError>(())
         },
         // Save new notification
-        Notification::new_user_untag(&tagger_id, tagged_id, tag_label)
+        Notification::new_user_untag(&tagger_id, tagged_id, tag_label),
+        // Decrement label in the labels applied by the tagger
+        TagUser::update_tagger_label_score(&tagger_id, tag_label, ScoreAction::Decrement(1.0))
     );
 
     indexing_results.0?;
@@ -296,6 +321,7 @@ async fn del_sync_user(
     indexing_results.2?;
     indexing_results.3?;
     indexing_results.4?;
+    indexing_results.5?;
 
     Ok(())
 }
@@ -305,6 +331,7 @@ async fn del_sync_post(
     post_id: &str,
     author_id: &str,
     tag_label: &str,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     // SAVE TO INDEXES
     let post_key_slice: &[&str] = &[author_id, post_id];
@@ -315,8 +342,6 @@ async fn del_sync_post(
         tracing::info_span!("index.delete", phase = "tag_post");
         // Update user counts for tagger
         UserCounts::decrement(&tagger_id, "tagged", None),
-        // Decrement in one the post tags
-        PostCounts::decrement_index_field(post_key_slice, "tags", None),
         async {
             // Decrement label score in the post
             TagPost::update_index_score(
@@ -340,14 +365,17 @@ async fn del_sync_post(
             ScoreAction::Decrement(1.0),
         ),
         async {
+            // Decrement in one the post tags and the post global engagement together
             // Post replies cannot be included in the total engagement index once the tag have been deleted
-            if !post_relationships_is_reply(author_id, post_id).await? {
-                // Decrement in one post global engagement
-                PostStream::update_index_score(author_id, post_id, ScoreAction::Decrement(1.0))
-                    .await
-                    .map_err(EventProcessorError::index_operation_failed)?;
-            }
-            Ok::<(), EventProcessorError>(())
+            let is_reply = post_relationships_is_reply(author_id, post_id).await?;
+            PostCounts::update_index_field_with_engagement(
+                post_key_slice,
+                "tags",
+                JsonAction::Decrement(1),
+                !is_reply,
+            )
+            .await
+            .map_err(EventProcessorError::index_operation_failed)
         },
         async {
             // Delete the tagger from the tag list
@@ -358,18 +386,22 @@ async fn del_sync_post(
             // Delete post from global label timeline
             PostsByTagSearch::del_from_index(author_id, post_id, tag_label).await?;
 
-            let posts_by_tag =
-                PostsByTagSearch::get_by_label(tag_label, None, Pagination::default()).await?;
-            let posts_by_tag_found = posts_by_tag.is_some_and(|x| !x.is_empty());
-            if !posts_by_tag_found {
-                // If we just removed the last post using this tag, remove tag from autocomplete suggestion list
-                TagSearch::del_from_index(tag_label).await?;
+            if settings.tag_autosuggest_cleanup {
+                let posts_by_tag =
+                    PostsByTagSearch::get_by_label(tag_label, None, Pagination::default()).await?;
+                let posts_by_tag_found = posts_by_tag.is_some_and(|x| !x.is_empty());
+                if !posts_by_tag_found {
+                    // If we just removed the last post using this tag, remove tag from autocomplete suggestion list
+                    TagSearch::del_from_index(tag_label).await?;
+                }
             }
 
             Ok::<(), EventProcessorError>(())
         },
         // Save new notification
-        Notification::new_post_untag(&tagger_id, author_id, tag_label, &post_uri)
+        Notification::new_post_untag(&tagger_id, author_id, tag_label, &post_uri),
+        // Decrement label in the labels applied by the tagger
+        TagPost::update_tagger_label_score(&tagger_id, tag_label, ScoreAction::Decrement(1.0))
     );
 
     indexing_results.0?;
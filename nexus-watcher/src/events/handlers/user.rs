@@ -1,8 +1,9 @@
-use crate::events::EventProcessorError;
+use crate::events::{EventProcessorError, EventSettings};
 
 use nexus_common::db::queries::get::user_is_safe_to_delete;
 use nexus_common::db::{execute_graph_operation, OperationOutcome};
 use nexus_common::models::{
+    tombstone::Tombstone,
     traits::Collection,
     user::{UserCounts, UserDetails, UserSearch, USER_DELETED_SENTINEL},
 };
@@ -44,11 +45,14 @@ pub async fn sync_put(user: PubkyAppUser, user_id: PubkyId) -> Result<(), EventP
     indexing_results.0?;
     indexing_results.1?;
     indexing_results.2?;
+
+    // A user indexed again after being erased is no longer gone
+    Tombstone::del_user(&user_id).await?;
     Ok(())
 }
 
 #[tracing::instrument(name = "user.del", skip_all, fields(user_id = %user_id))]
-pub async fn del(user_id: PubkyId) -> Result<(), EventProcessorError> {
+pub async fn del(user_id: PubkyId, settings: &EventSettings) -> Result<(), EventProcessorError> {
     debug!("Deleting user profile:  {}", user_id);
 
     // 1. Graph query to check if there is any edge at all to this user.
@@ -73,6 +77,9 @@ pub async fn del(user_id: PubkyId) -> Result<(), EventProcessorError> {
             );
             indexing_results.0?;
             indexing_results.1?;
+            if settings.record_tombstones {
+                Tombstone::put_user(&user_id, settings.tombstone_ttl_secs).await?;
+            }
         }
         OperationOutcome::Updated => {
             let deleted_user = PubkyAppUser {
@@ -1,5 +1,6 @@
 use nexus_common::db::PubkyConnector;
 use nexus_common::models::event::{Event, EventProcessorError, EventType};
+use nexus_common::models::tag::label;
 use pubky_app_specs::{PubkyAppObject, Resource};
 use std::sync::Arc;
 use tracing::debug;
@@ -7,13 +8,19 @@ use tracing::debug;
 pub mod handlers;
 mod moderation;
 pub mod retry;
+mod settings;
 
 pub use moderation::Moderation;
+pub use settings::EventSettings;
 
-pub async fn handle(event: &Event, moderation: Arc<Moderation>) -> Result<(), EventProcessorError> {
+pub async fn handle(
+    event: &Event,
+    moderation: Arc<Moderation>,
+    settings: Arc<EventSettings>,
+) -> Result<(), EventProcessorError> {
     match event.event_type {
-        EventType::Put => handle_put_event(event, moderation).await,
-        EventType::Del => handle_del_event(event).await,
+        EventType::Put => handle_put_event(event, moderation, &settings).await,
+        EventType::Del => handle_del_event(event, &settings).await,
     }?;
 
     event.store_event().await?;
@@ -23,6 +30,7 @@ pub async fn handle(event: &Event, moderation: Arc<Moderation>) -> Result<(), Ev
 pub async fn handle_put_event(
     event: &Event,
     moderation: Arc<Moderation>,
+    settings: &EventSettings,
 ) -> Result<(), EventProcessorError> {
     debug!("Handling PUT event for URI: {}", event.uri);
 
@@ -59,10 +67,10 @@ pub async fn handle_put_event(
             handlers::user::sync_put(user, user_id).await?
         }
         (PubkyAppObject::Post(post), Resource::Post(post_id)) => {
-            handlers::post::sync_put(post, user_id, post_id).await?
+            handlers::post::sync_put(post, user_id, post_id, settings).await?
         }
-        (PubkyAppObject::Follow(_follow), Resource::Follow(followee_id)) => {
-            handlers::follow::sync_put(user_id, followee_id).await?
+        (PubkyAppObject::Follow(follow), Resource::Follow(followee_id)) => {
+            handlers::follow::sync_put(user_id, followee_id, follow.created_at, settings).await?
         }
         (PubkyAppObject::Mute(_), Resource::Mute(_)) => {
             debug!("Mute events are no longer handled by nexus");
@@ -70,11 +78,15 @@ pub async fn handle_put_event(
         (PubkyAppObject::Bookmark(bookmark), Resource::Bookmark(bookmark_id)) => {
             handlers::bookmark::sync_put(user_id, bookmark, bookmark_id).await?
         }
-        (PubkyAppObject::Tag(tag), Resource::Tag(tag_id)) => {
+        (PubkyAppObject::Tag(mut tag), Resource::Tag(tag_id)) => {
             if moderation.should_delete(&tag, user_id.clone()).await {
-                Moderation::apply_moderation(tag, event.files_path.clone()).await?
+                Moderation::apply_moderation(tag, user_id, event.files_path.clone(), settings)
+                    .await?
             } else {
-                handlers::tag::sync_put(tag, user_id, tag_id).await?
+                if label::is_case_sensitive() {
+                    tag.label = restore_label_case(&tag.label, &blob);
+                }
+                handlers::tag::sync_put(tag, user_id, tag_id, settings).await?
             }
         }
         (PubkyAppObject::File(file), Resource::File(file_id)) => {
@@ -92,22 +104,38 @@ pub async fn handle_put_event(
     Ok(())
 }
 
+/// Restores the case of a tag label, which `pubky-app-specs` lowercases while sanitizing,
+/// from the raw tag JSON
+fn restore_label_case(sanitized: &str, blob: &[u8]) -> String {
+    serde_json::from_slice::<serde_json::Value>(blob)
+        .ok()
+        .and_then(|tag| {
+            tag["label"]
+                .as_str()
+                .map(|raw| label::restore_case(sanitized, raw))
+        })
+        .unwrap_or_else(|| sanitized.to_string())
+}
+
 /// Handles a DEL event by dispatching to the appropriate handler.
-pub async fn handle_del_event(event: &Event) -> Result<(), EventProcessorError> {
+pub async fn handle_del_event(
+    event: &Event,
+    settings: &EventSettings,
+) -> Result<(), EventProcessorError> {
     debug!("Handling DEL event for URI: {}", event.uri);
 
     let user_id = event.parsed_uri.user_id.clone();
     match &event.parsed_uri.resource {
-        Resource::User => handlers::user::del(user_id).await?,
-        Resource::Post(post_id) => handlers::post::del(user_id, post_id.clone()).await?,
+        Resource::User => handlers::user::del(user_id, settings).await?,
+        Resource::Post(post_id) => handlers::post::del(user_id, post_id.clone(), settings).await?,
         Resource::Follow(followee_id) => {
-            handlers::follow::del(user_id, followee_id.clone()).await?
+            handlers::follow::del(user_id, followee_id.clone(), settings).await?
         }
         Resource::Mute(_) => debug!("Mute events are no longer handled by nexus"),
         Resource::Bookmark(bookmark_id) => {
             handlers::bookmark::del(user_id, bookmark_id.clone()).await?
         }
-        Resource::Tag(tag_id) => handlers::tag::del(user_id, tag_id.clone()).await?,
+        Resource::Tag(tag_id) => handlers::tag::del(user_id, tag_id.clone(), settings).await?,
         Resource::File(file_id) => {
             handlers::file::del(&user_id, file_id.clone(), event.files_path.clone()).await?
         }
@@ -1,40 +1,104 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use crate::events::handlers;
+use crate::events::{handlers, EventSettings};
+use chrono::Utc;
+use nexus_common::db::kv::RedisResult;
+use nexus_common::db::PubkyConnector;
 use nexus_common::models::event::EventProcessorError;
+use nexus_common::models::moderation::{ModerationAction, ModerationInfo};
+use nexus_common::WatcherConfig;
+use pubky::PublicKey;
 use pubky_app_specs::{ParsedUri, PubkyAppTag, PubkyId, Resource};
-use tracing::info;
+use tracing::{error, info, warn};
 
 pub struct Moderation {
     /// Moderator trusted user id
     pub id: PubkyId,
     /// Tags to be moderated (tagged content is deleted)
     pub tags: Vec<String>,
+    /// Tags moderated instead of [Self::tags] on the content hosted by specific homeservers, by
+    /// homeserver ID
+    pub homeserver_tags: BTreeMap<String, Vec<String>>,
 }
 
 impl Moderation {
+    /// Creates the moderation policy of the trusted moderator, with the moderated tags of the
+    /// [HomeserverOverride](nexus_common::HomeserverOverride)s
+    pub fn from_config(config: &WatcherConfig) -> Self {
+        let homeserver_tags = config
+            .homeserver_overrides
+            .iter()
+            .filter_map(|(hs_id, hs_override)| {
+                hs_override
+                    .moderated_tags
+                    .clone()
+                    .map(|tags| (hs_id.clone(), tags))
+            })
+            .collect();
+        Self {
+            id: config.moderation_id.clone(),
+            tags: config.moderated_tags.clone(),
+            homeserver_tags,
+        }
+    }
+
     pub async fn should_delete(&self, tag: &PubkyAppTag, tagger_id: PubkyId) -> bool {
-        tagger_id == self.id && self.tags.contains(&tag.label)
+        tagger_id == self.id && self.moderated_tags(&tag.uri).await.contains(&tag.label)
+    }
+
+    /// Tags moderated on the content of `uri`: those of the homeserver hosting it, if it has its
+    /// own, or the global ones otherwise. The moderator tags from their own homeserver, so the
+    /// homeserver whose events carry the tag says nothing about the tagged content
+    async fn moderated_tags(&self, uri: &str) -> &[String] {
+        if self.homeserver_tags.is_empty() {
+            return &self.tags;
+        }
+        match content_homeserver(uri).await {
+            Some(hs_id) => self.homeserver_tags.get(&hs_id).unwrap_or(&self.tags),
+            None => &self.tags,
+        }
+    }
+
+    /// Retrieves the audit log of the moderation actions, most recent first
+    pub async fn recent_actions(skip: usize, limit: usize) -> RedisResult<Vec<ModerationAction>> {
+        ModerationAction::get_recent(skip, limit).await
+    }
+
+    /// Retrieves the number of moderation actions per moderation tag, most used first
+    pub async fn tag_counts() -> RedisResult<Vec<(String, u64)>> {
+        ModerationAction::get_tag_counts().await
     }
 
+    /// Deletes the content tagged by the moderator. The reason of the deletion of posts and users
+    /// is recorded as their [ModerationInfo]
     #[tracing::instrument(name = "moderation.apply", skip_all)]
     pub async fn apply_moderation(
         moderator_tag: PubkyAppTag,
+        moderator_id: PubkyId,
         files_path: PathBuf,
+        settings: &EventSettings,
     ) -> Result<(), EventProcessorError> {
         // Parse the embeded URI to extract author_id and post_id using parse_tagged_post_uri
         let parsed_uri = ParsedUri::try_from(moderator_tag.uri.as_str())
             .map_err(EventProcessorError::generic)?;
         let user_id = parsed_uri.user_id;
 
-        match parsed_uri.resource {
+        let result = match parsed_uri.resource {
             Resource::Post(post_id) => {
-                // Delete the post and return the result
+                // Delete the post and record why
                 info!(
                     "Moderation tag '{}' detected. Deleting post {}:{}",
                     moderator_tag.label, user_id, post_id
                 );
-                handlers::post::sync_del(user_id, post_id).await
+                handlers::post::sync_del(user_id.clone(), post_id.clone(), settings).await?;
+                record_moderation(
+                    &[&user_id.to_string(), &post_id],
+                    &moderator_tag,
+                    moderator_id.clone(),
+                )
+                .await;
+                Ok(())
             }
             Resource::Tag(tag_id) => {
                 // Delete the tag and return the result
@@ -42,15 +106,22 @@ impl Moderation {
                     "Moderation tag '{}' detected. Deleting tag {}:{}",
                     moderator_tag.label, user_id, tag_id
                 );
-                handlers::tag::del(user_id, tag_id).await
+                handlers::tag::del(user_id, tag_id, settings).await
             }
             Resource::User => {
-                // Delete the user profile and return the result
+                // Delete the user profile and record why
                 info!(
                     "Moderation tag '{}' detected. Deleting user profile {}",
                     moderator_tag.label, user_id
                 );
-                handlers::user::del(user_id).await
+                handlers::user::del(user_id.clone(), settings).await?;
+                record_moderation(
+                    &[&user_id.to_string()],
+                    &moderator_tag,
+                    moderator_id.clone(),
+                )
+                .await;
+                Ok(())
             }
             Resource::File(file_id) => {
                 // Delete the file and return the result
@@ -60,7 +131,61 @@ impl Moderation {
                 );
                 handlers::file::del(&user_id, file_id, files_path).await
             }
-            _ => Ok(()),
+            _ => return Ok(()),
+        };
+
+        if result.is_ok() {
+            record_action(&moderator_tag, moderator_id, settings).await;
         }
+        result
+    }
+}
+
+/// Records why the content identified by `key_parts` was deleted. A failure is only logged, as the
+/// content is already deleted.
+async fn record_moderation(key_parts: &[&str], moderator_tag: &PubkyAppTag, moderator_id: PubkyId) {
+    let moderation = ModerationInfo {
+        moderator_id: moderator_id.to_string(),
+        reason: moderator_tag.label.clone(),
+        moderated_at: Utc::now().timestamp_millis(),
+    };
+    if let Err(e) = moderation.put_to_index(key_parts).await {
+        error!("Failed to record the moderation of {key_parts:?}: {e}");
+    }
+}
+
+/// Resolves the homeserver hosting the content of `uri`, the one published by its owner
+async fn content_homeserver(uri: &str) -> Option<String> {
+    let owner_id = ParsedUri::try_from(uri).ok()?.user_id;
+    let owner_pk = owner_id.as_str().parse::<PublicKey>().ok()?;
+    let pubky = PubkyConnector::get().ok()?;
+    match pubky.get_homeserver_of(&owner_pk).await {
+        Some(hs_pk) => Some(PubkyId::from(hs_pk).to_string()),
+        None => {
+            warn!("No published homeserver for {owner_id}, moderating {uri} with the global tags");
+            None
+        }
+    }
+}
+
+/// Records the moderation action in the audit log, trimmed to the retention of `settings`. A
+/// failure is only logged, as the content is already deleted.
+async fn record_action(
+    moderator_tag: &PubkyAppTag,
+    moderator_id: PubkyId,
+    settings: &EventSettings,
+) {
+    let action = ModerationAction {
+        uri: moderator_tag.uri.clone(),
+        tag: moderator_tag.label.clone(),
+        moderator_id: moderator_id.to_string(),
+        moderated_at: Utc::now().timestamp_millis(),
+    };
+    let (max_entries, max_age_secs) = (
+        settings.moderation_log_max_entries,
+        settings.moderation_log_max_age_secs,
+    );
+    if let Err(e) = action.put_to_index(max_entries, max_age_secs).await {
+        error!("Failed to record the moderation of {}: {e}", action.uri);
     }
 }
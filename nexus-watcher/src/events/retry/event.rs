@@ -1,16 +1,19 @@
 use async_trait::async_trait;
 use chrono::Utc;
+use nexus_common::db::kv::key::KEY_DELIMITER;
 use nexus_common::db::kv::RedisResult;
 use pubky_app_specs::ParsedUri;
 use serde::{Deserialize, Serialize};
 
 use nexus_common::db::RedisOps;
 
+use super::policy::RetryPolicy;
 use crate::events::EventProcessorError;
 
 pub const RETRY_MANAGER_PREFIX: &str = "RetryManager";
 pub const RETRY_MANAGER_EVENTS_INDEX: [&str; 1] = ["events"];
 pub const RETRY_MANAGER_STATE_INDEX: [&str; 1] = ["state"];
+pub const RETRY_MANAGER_DEAD_LETTER_INDEX: [&str; 1] = ["dead_letter"];
 
 /// Represents an event in the retry queue and it is used to manage events that have failed
 /// to process and need to be retried
@@ -18,6 +21,9 @@ pub const RETRY_MANAGER_STATE_INDEX: [&str; 1] = ["state"];
 pub struct RetryEvent {
     /// Retry attempts made for this event
     pub retry_count: u32,
+    /// Timestamp (ms) before which the event is not processed again
+    #[serde(default)]
+    pub next_retry_at: i64,
     /// The type of error that caused the event to fail
     /// This determines how the event should be processed during the retry process
     pub error_type: EventProcessorError,
@@ -34,10 +40,40 @@ impl RetryEvent {
     pub fn new(error_type: EventProcessorError) -> Self {
         Self {
             retry_count: 0,
+            next_retry_at: 0,
             error_type,
         }
     }
 
+    /// Builds the retry state of an event after a failed processing attempt at `now` (ms).
+    ///
+    /// The retry count continues from the `previous` state, if the event failed before, and the
+    /// next retry is delayed by the backoff of the [RetryPolicy].
+    pub fn after_failure(
+        previous: Option<&RetryEvent>,
+        error_type: EventProcessorError,
+        policy: &RetryPolicy,
+        now: i64,
+    ) -> Self {
+        let retry_count = previous.map_or(0, |previous| previous.retry_count + 1);
+        let backoff_ms = policy.backoff_secs(retry_count).saturating_mul(1000);
+        Self {
+            retry_count,
+            next_retry_at: now.saturating_add(i64::try_from(backoff_ms).unwrap_or(i64::MAX)),
+            error_type,
+        }
+    }
+
+    /// Number of failed processing attempts of the event
+    pub fn attempts(&self) -> u32 {
+        self.retry_count + 1
+    }
+
+    /// Whether the event can be processed again at `now` (ms)
+    pub fn is_due(&self, now: i64) -> bool {
+        now >= self.next_retry_at
+    }
+
     /// It processes a homeserver URI and extracts specific components to form a index key
     /// in the format `"{pubkyId}:{repository_model}:{event_id}"`
     /// # Parameters
@@ -74,21 +110,67 @@ impl RetryEvent {
     /// * `event_line` - A `String` representing the event line to be indexed.
     #[tracing::instrument(name = "retry.index.write", skip_all)]
     pub async fn put_to_index(&self, event_line: String) -> RedisResult<()> {
+        // Scored by the time of the next retry, so that the due events come first
         Self::put_index_sorted_set(
             &RETRY_MANAGER_EVENTS_INDEX,
-            // NOTE: Don't know if we should use now timestamp or the event timestamp
-            &[(Utc::now().timestamp_millis() as f64, &event_line)],
+            &[(self.next_retry_at as f64, &event_line)],
             Some(RETRY_MANAGER_PREFIX),
             None,
         )
         .await?;
 
-        let index = &[RETRY_MANAGER_STATE_INDEX, [&event_line]].concat();
-        self.put_index_json(index, None, None).await?;
+        let index = Self::state_index_key_parts(&event_line);
+        self.put_index_json(&index, None, None).await?;
 
         Ok(())
     }
 
+    /// Moves an event that failed too many times from the retry index to the dead-letter index.
+    /// Its state is kept in the dead-letter index, so that the last error can be inspected.
+    /// # Arguments
+    /// * `event_line` - A `String` representing the event line to be moved.
+    #[tracing::instrument(name = "retry.dead_letter.write", skip_all)]
+    pub async fn put_to_dead_letter(&self, event_line: String) -> RedisResult<()> {
+        Self::put_index_sorted_set(
+            &RETRY_MANAGER_DEAD_LETTER_INDEX,
+            &[(Utc::now().timestamp_millis() as f64, &event_line)],
+            Some(RETRY_MANAGER_PREFIX),
+            None,
+        )
+        .await?;
+        let index = Self::dead_letter_index_key_parts(&event_line);
+        self.put_index_json(&index, None, None).await?;
+
+        Self::remove_from_index(&event_line).await
+    }
+
+    /// Removes an event from the retry index, e.g. once it was processed successfully
+    /// # Arguments
+    /// * `event_index` - A `&str` representing the event index to remove
+    pub async fn remove_from_index(event_index: &str) -> RedisResult<()> {
+        Self::remove_from_index_sorted_set(
+            Some(RETRY_MANAGER_PREFIX),
+            &RETRY_MANAGER_EVENTS_INDEX,
+            &[event_index],
+        )
+        .await?;
+
+        let index = Self::state_index_key_parts(event_index);
+        Self::remove_from_index_multiple_json(&[index.as_slice()]).await
+    }
+
+    /// Checks if a specific event exists in the dead-letter index
+    /// # Arguments
+    /// * `event_index` - A `&str` representing the event index to check
+    pub async fn check_dead_letter(event_index: &str) -> RedisResult<Option<isize>> {
+        Self::check_sorted_set_member(
+            Some(RETRY_MANAGER_PREFIX),
+            &RETRY_MANAGER_DEAD_LETTER_INDEX,
+            &[event_index],
+        )
+        .await
+    }
+
     /// Checks if a specific event exists in the Redis sorted set
     /// # Arguments
     /// * `event_index` - A `&str` representing the event index to check
@@ -110,7 +192,32 @@ impl RetryEvent {
     /// # Arguments
     /// * `event_index` - A `&str` representing the event index to retrieve
     pub async fn get_from_index(event_index: &str) -> RedisResult<Option<Self>> {
-        let index: &Vec<&str> = &[RETRY_MANAGER_STATE_INDEX, [event_index]].concat();
-        Self::try_from_index_json(index, None).await
+        let index = Self::state_index_key_parts(event_index);
+        Self::try_from_index_json(&index, None).await
+    }
+
+    /// Retrieves the state of an event from the dead-letter index
+    /// # Arguments
+    /// * `event_index` - A `&str` representing the event index to retrieve
+    pub async fn get_from_dead_letter(event_index: &str) -> RedisResult<Option<Self>> {
+        let index = Self::dead_letter_index_key_parts(event_index);
+        Self::try_from_index_json(&index, None).await
+    }
+
+    /// Splits an event index into the key parts of its JSON state, so that its segments are
+    /// stored as separate key parts instead of being escaped as a single one
+    fn state_index_key_parts(event_index: &str) -> Vec<&str> {
+        RETRY_MANAGER_STATE_INDEX
+            .into_iter()
+            .chain(event_index.split(KEY_DELIMITER))
+            .collect()
+    }
+
+    /// Key parts of the JSON state of a dead-lettered event, see [Self::state_index_key_parts]
+    fn dead_letter_index_key_parts(event_index: &str) -> Vec<&str> {
+        RETRY_MANAGER_DEAD_LETTER_INDEX
+            .into_iter()
+            .chain(event_index.split(KEY_DELIMITER))
+            .collect()
     }
 }
@@ -0,0 +1,62 @@
+use nexus_common::{
+    WatcherConfig, DEFAULT_RETRY_INITIAL_BACKOFF_SECS, DEFAULT_RETRY_MAX_ATTEMPTS,
+    DEFAULT_RETRY_MAX_BACKOFF_SECS,
+};
+
+/// How often and how late failed events are processed again, see [RetryEvent](super::event::RetryEvent)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// See [WatcherConfig::retry_max_attempts]
+    pub max_attempts: u32,
+    /// See [WatcherConfig::retry_initial_backoff_secs]
+    pub initial_backoff_secs: u64,
+    /// See [WatcherConfig::retry_max_backoff_secs]
+    pub max_backoff_secs: u64,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &WatcherConfig) -> Self {
+        Self {
+            max_attempts: config.retry_max_attempts,
+            initial_backoff_secs: config.retry_initial_backoff_secs,
+            max_backoff_secs: config.retry_max_backoff_secs,
+        }
+    }
+
+    /// Backoff (in seconds) after the failure of an event retried `retry_count` times:
+    /// `min(INITIAL * 2^retry_count, MAX)`
+    pub fn backoff_secs(&self, retry_count: u32) -> u64 {
+        self.initial_backoff_secs
+            .saturating_mul(1 << retry_count.min(32))
+            .min(self.max_backoff_secs)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RETRY_MAX_ATTEMPTS,
+            initial_backoff_secs: DEFAULT_RETRY_INITIAL_BACKOFF_SECS,
+            max_backoff_secs: DEFAULT_RETRY_MAX_BACKOFF_SECS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff_secs: 60,
+            max_backoff_secs: 300,
+        };
+        assert_eq!(policy.backoff_secs(0), 60);
+        assert_eq!(policy.backoff_secs(1), 120);
+        assert_eq!(policy.backoff_secs(2), 240);
+        assert_eq!(policy.backoff_secs(3), 300);
+        assert_eq!(policy.backoff_secs(u32::MAX), 300);
+    }
+}
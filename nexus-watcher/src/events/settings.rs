@@ -0,0 +1,49 @@
+use nexus_common::WatcherConfig;
+
+/// Indexing settings applied by the event handlers, see [handle](super::handle)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSettings {
+    /// See [WatcherConfig::allow_self_follows]
+    pub allow_self_follows: bool,
+    /// See [WatcherConfig::follower_snapshot_interval_secs]
+    pub follower_snapshot_interval_secs: u64,
+    /// See [WatcherConfig::max_tags_per_target]
+    pub max_tags_per_target: usize,
+    /// See [WatcherConfig::tag_autosuggest_cleanup]
+    pub tag_autosuggest_cleanup: bool,
+    /// See [WatcherConfig::record_tombstones]
+    pub record_tombstones: bool,
+    /// See [WatcherConfig::tombstone_ttl_secs]
+    pub tombstone_ttl_secs: u64,
+    /// See [WatcherConfig::moderation_log_max_entries]
+    pub moderation_log_max_entries: usize,
+    /// See [WatcherConfig::moderation_log_max_age_secs]
+    pub moderation_log_max_age_secs: u64,
+    /// See [StackConfig::descendant_counts](nexus_common::StackConfig::descendant_counts)
+    pub descendant_counts: bool,
+    /// See [StackConfig::index_embeds](nexus_common::StackConfig::index_embeds)
+    pub index_embeds: bool,
+}
+
+impl EventSettings {
+    pub fn from_config(config: &WatcherConfig) -> Self {
+        Self {
+            allow_self_follows: config.allow_self_follows,
+            follower_snapshot_interval_secs: config.follower_snapshot_interval_secs,
+            max_tags_per_target: config.max_tags_per_target,
+            tag_autosuggest_cleanup: config.tag_autosuggest_cleanup,
+            record_tombstones: config.record_tombstones,
+            tombstone_ttl_secs: config.tombstone_ttl_secs,
+            moderation_log_max_entries: config.moderation_log_max_entries,
+            moderation_log_max_age_secs: config.moderation_log_max_age_secs,
+            descendant_counts: config.stack.descendant_counts,
+            index_embeds: config.stack.index_embeds,
+        }
+    }
+}
+
+impl Default for EventSettings {
+    fn default() -> Self {
+        Self::from_config(&WatcherConfig::default())
+    }
+}
@@ -1,5 +1,9 @@
 /// Name of the watcher config file
 pub const WATCHER_CONFIG_FILE_NAME: &str = "watcher-config.toml";
-///  Per-homeserver hard timeout (seconds)
+///  Per-homeserver hard timeout (seconds), unless overridden by
+/// [HomeserverOverride::timeout_secs](nexus_common::HomeserverOverride::timeout_secs)
 // TODO: Set timeout maybe from the config file
 pub const PROCESSING_TIMEOUT_SECS: u64 = 3_600;
+/// Time (seconds) an in-flight event poll or event is given to finish after a shutdown is
+/// signalled, before it is abandoned
+pub const SHUTDOWN_GRACE_PERIOD_SECS: u64 = 10;
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::{global, KeyValue};
+
+use crate::service::stats::ProcessorRunStatus;
+
+/// The OpenTelemetry meter name used by all event processing metrics
+const METER_NAME: &str = "nexus.watcher";
+
+/// OpenTelemetry metric instruments of the event processing.
+///
+/// Recording is a no-op when the metrics are disabled, see [WatcherConfig::event_metrics].
+/// All instruments are safe to clone (internally Arc'd).
+///
+/// [WatcherConfig::event_metrics]: nexus_common::WatcherConfig::event_metrics
+#[derive(Clone, Default)]
+pub struct EventMetrics(Option<Instruments>);
+
+#[derive(Clone)]
+struct Instruments {
+    /// Incremented for every event handled by an event processor
+    processed: Counter<u64>,
+    /// Incremented for every event whose processing failed
+    failed: Counter<u64>,
+    /// Incremented for every failed event put into the retry index
+    retry_queued: Counter<u64>,
+    /// Duration of every event processor run
+    run_duration: Histogram<f64>,
+}
+
+/// Attributes of the event metrics
+fn event_attrs(homeserver_id: &str, event_type: &str) -> [KeyValue; 2] {
+    [
+        KeyValue::new("homeserver", homeserver_id.to_string()),
+        KeyValue::new("event_type", event_type.to_string()),
+    ]
+}
+
+impl EventMetrics {
+    /// Creates the instruments from the given meter
+    pub fn new(meter: &Meter) -> Self {
+        Self(Some(Instruments {
+            processed: meter
+                .u64_counter("watcher.events.processed")
+                .with_description("Total number of events handled by the event processors")
+                .with_unit("{event}")
+                .build(),
+            failed: meter
+                .u64_counter("watcher.events.failed")
+                .with_description("Total number of events whose processing failed")
+                .with_unit("{event}")
+                .build(),
+            retry_queued: meter
+                .u64_counter("watcher.events.retry_queued")
+                .with_description("Total number of failed events put into the retry index")
+                .with_unit("{event}")
+                .build(),
+            run_duration: meter
+                .f64_histogram("watcher.run.duration")
+                .with_description(
+                    "Duration of an event processor run of a homeserver, in milliseconds",
+                )
+                .with_unit("ms")
+                .build(),
+        }))
+    }
+
+    /// Creates the instruments from the global OpenTelemetry meter provider, if `enabled`.
+    ///
+    /// The instruments are no-ops if no meter provider has been registered (i.e. when OTLP is
+    /// not configured).
+    pub fn from_global(enabled: bool) -> Self {
+        match enabled {
+            true => Self::new(&global::meter(METER_NAME)),
+            false => Self::default(),
+        }
+    }
+
+    /// Records an event handled by the processor of a homeserver
+    pub fn record_processed(&self, homeserver_id: &str, event_type: &str) {
+        if let Some(instruments) = &self.0 {
+            instruments
+                .processed
+                .add(1, &event_attrs(homeserver_id, event_type));
+        }
+    }
+
+    /// Records an event whose processing failed
+    pub fn record_failed(&self, homeserver_id: &str, event_type: &str) {
+        if let Some(instruments) = &self.0 {
+            instruments
+                .failed
+                .add(1, &event_attrs(homeserver_id, event_type));
+        }
+    }
+
+    /// Records a failed event put into the retry index
+    pub fn record_retry_queued(&self, homeserver_id: &str, event_type: &str) {
+        if let Some(instruments) = &self.0 {
+            instruments
+                .retry_queued
+                .add(1, &event_attrs(homeserver_id, event_type));
+        }
+    }
+
+    /// Records the duration of an event processor run, by homeserver and run status
+    pub fn record_run(&self, homeserver_id: &str, duration: Duration, status: &ProcessorRunStatus) {
+        if let Some(instruments) = &self.0 {
+            instruments.run_duration.record(
+                duration.as_secs_f64() * 1000.0,
+                &[
+                    KeyValue::new("homeserver", homeserver_id.to_string()),
+                    KeyValue::new("status", format!("{status:?}")),
+                ],
+            );
+        }
+    }
+}
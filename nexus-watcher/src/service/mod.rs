@@ -1,12 +1,17 @@
 pub mod backoff;
 mod constants;
+mod metrics;
 mod processor;
 mod processor_runner;
+mod shutdown;
 mod stats;
 mod traits;
 
 /// Module exports
-pub use constants::{PROCESSING_TIMEOUT_SECS, WATCHER_CONFIG_FILE_NAME};
+pub use constants::{
+    PROCESSING_TIMEOUT_SECS, SHUTDOWN_GRACE_PERIOD_SECS, WATCHER_CONFIG_FILE_NAME,
+};
+pub use metrics::EventMetrics;
 use nexus_common::types::DynError;
 pub use processor::EventProcessor;
 pub use processor_runner::EventProcessorRunner;
@@ -83,6 +88,11 @@ impl NexusWatcher {
             config.initial_backoff_secs,
             config.max_backoff_secs,
         );
+        // Tracked separately, as the default homeserver may run concurrently with the others
+        let mut default_backoff = crate::service::backoff::HomeserverBackoff::new(
+            config.initial_backoff_secs,
+            config.max_backoff_secs,
+        );
 
         loop {
             tokio::select! {
@@ -92,9 +102,15 @@ impl NexusWatcher {
                 }
                 _ = interval.tick() => {
                     debug!("Indexing homeservers…");
-                    _ = ev_processor_runner
-                        .run_all(&mut backoff)
-                        .await
+                    let run_result = match config.parallel_default_homeserver {
+                        true => {
+                            ev_processor_runner
+                                .run_all_concurrently(&mut default_backoff, &mut backoff)
+                                .await
+                        }
+                        false => ev_processor_runner.run_all(&mut backoff).await,
+                    };
+                    _ = run_result
                         .inspect_err(|e| error!("Failed to start event processors run: {e}"));
                 }
             }
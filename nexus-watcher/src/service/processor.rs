@@ -2,14 +2,22 @@ use nexus_common::models::event::{Event, EventProcessorError};
 
 use crate::events::handle;
 use crate::events::retry::event::RetryEvent;
-use crate::events::Moderation;
+use crate::events::retry::policy::RetryPolicy;
+use crate::events::{EventSettings, Moderation};
+use crate::service::metrics::EventMetrics;
+use crate::service::shutdown::run_with_shutdown_grace;
 use crate::service::traits::TEventProcessor;
+use crate::service::SHUTDOWN_GRACE_PERIOD_SECS;
+use chrono::Utc;
 use nexus_common::db::PubkyConnector;
 use nexus_common::models::homeserver::Homeserver;
+use nexus_common::CursorMode;
 use pubky::Method;
 use pubky_app_specs::PubkyId;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::watch::Receiver;
 use tracing::{debug, error, info, warn};
 
@@ -17,9 +25,32 @@ pub struct EventProcessor {
     pub homeserver: Homeserver,
     /// See [WatcherConfig::events_limit]
     pub limit: u32,
+    /// See [HomeserverOverride::timeout_secs](nexus_common::HomeserverOverride::timeout_secs)
+    pub timeout: Option<Duration>,
     pub files_path: PathBuf,
     pub moderation: Arc<Moderation>,
+    /// See [EventSettings::from_config]
+    pub settings: Arc<EventSettings>,
     pub shutdown_rx: Receiver<bool>,
+    /// Backoff and attempt cap of failed events
+    pub retry_policy: RetryPolicy,
+    /// See [WatcherConfig::cursor_mode]
+    pub cursor_mode: CursorMode,
+    /// See [WatcherConfig::event_metrics]
+    pub metrics: EventMetrics,
+    /// Number of events handled by this processor, see [TEventProcessor::events_processed]
+    pub events_processed: AtomicU64,
+}
+
+/// Outcome of the processing of a single event, see [EventProcessor::handle_event]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventOutcome {
+    /// The event was indexed
+    Handled,
+    /// The event failed or is in retry backoff, it is left to be processed again later
+    Deferred,
+    /// The event will not be processed again: it is invalid, or was moved to the dead-letter index
+    Dropped,
 }
 
 #[async_trait::async_trait]
@@ -28,11 +59,20 @@ impl TEventProcessor for EventProcessor {
         self.homeserver.id.clone()
     }
 
+    fn custom_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn events_processed(&self) -> u64 {
+        self.events_processed.load(Ordering::Relaxed)
+    }
+
     async fn run_internal(self: Arc<Self>) -> Result<(), EventProcessorError> {
-        let maybe_event_lines = self
-            .poll_events()
-            .await
-            .inspect_err(|e| error!("Error polling events: {e:?}"))?;
+        let Some(polled) = self.with_shutdown_grace(self.poll_events()).await else {
+            warn!("Shutdown grace period elapsed while polling events, exiting event processor");
+            return Ok(());
+        };
+        let maybe_event_lines = polled.inspect_err(|e| error!("Error polling events: {e:?}"))?;
 
         match maybe_event_lines {
             None => debug!("No new events"),
@@ -47,6 +87,19 @@ impl TEventProcessor for EventProcessor {
 }
 
 impl EventProcessor {
+    /// Runs `work` until completion, or until [SHUTDOWN_GRACE_PERIOD_SECS] after a shutdown signal,
+    /// so that a stuck fetch or event cannot hang the shutdown.
+    ///
+    /// Returns `None` if the work was abandoned.
+    async fn with_shutdown_grace<F: std::future::Future>(&self, work: F) -> Option<F::Output> {
+        run_with_shutdown_grace(
+            work,
+            self.shutdown_rx.clone(),
+            Duration::from_secs(SHUTDOWN_GRACE_PERIOD_SECS),
+        )
+        .await
+    }
+
     /// Polls new events from the homeserver.
     ///
     /// It sends a GET request to the homeserver's events endpoint
@@ -55,13 +108,21 @@ impl EventProcessor {
     /// and returns the result.
     #[tracing::instrument(name = "events.poll", skip_all, fields(homeserver = %self.homeserver.id))]
     async fn poll_events(&self) -> Result<Option<Vec<String>>, EventProcessorError> {
+        self.poll_events_with_limit(self.limit).await
+    }
+
+    /// Polls at most `limit` new events from the homeserver, see [Self::poll_events]
+    async fn poll_events_with_limit(
+        &self,
+        limit: u32,
+    ) -> Result<Option<Vec<String>>, EventProcessorError> {
         debug!("Polling new events from homeserver");
 
         let response_text = {
             let pubky = PubkyConnector::get()?;
             let url = format!(
                 "https://{}/events/?cursor={}&limit={}",
-                self.homeserver.id, self.homeserver.cursor, self.limit
+                self.homeserver.id, self.homeserver.cursor, limit
             );
 
             let response = pubky
@@ -93,11 +154,14 @@ impl EventProcessor {
     /// - Lines starting with `cursor:` update the cursor for the homeserver and save it to the index.
     /// - Other lines are parsed into events and processed accordingly. If parsing fails, an error is logged.
     ///
+    /// With [CursorMode::Strict], the processing stops at the first deferred event, and the cursor
+    /// only advances to the event before it, see [Self::advance_cursor_to].
+    ///
     /// # Parameters
     /// - `lines`: A vector of strings representing event lines retrieved from the homeserver.
     #[tracing::instrument(name = "event_batch.process", skip_all, fields(batch.size = lines.len()))]
     pub async fn process_event_lines(&self, lines: Vec<String>) -> Result<(), EventProcessorError> {
-        for line in &lines {
+        for (position, line) in lines.iter().enumerate() {
             let id = self.homeserver.id.clone();
 
             if *self.shutdown_rx.borrow() {
@@ -118,7 +182,24 @@ impl EventProcessor {
 
                 if let Some(event) = maybe_event {
                     debug!("Processing event: {:?}", event);
-                    self.handle_event(&event).await?;
+                    let Some(handled) = self.with_shutdown_grace(self.handle_event(&event)).await
+                    else {
+                        warn!(
+                            "Shutdown grace period elapsed while processing event {}, exiting event processing loop",
+                            event.uri
+                        );
+                        return Ok(());
+                    };
+                    let outcome = handled?;
+                    self.events_processed.fetch_add(1, Ordering::Relaxed);
+                    self.metrics
+                        .record_processed(&id, &event.event_type.to_string());
+
+                    if self.cursor_mode == CursorMode::Strict && outcome == EventOutcome::Deferred {
+                        info!("Event {} deferred, stopping the batch before it", event.uri);
+                        // The cursor line is the last one, so all the lines before are events
+                        return self.advance_cursor_to(position as u32).await;
+                    }
                 }
             }
         }
@@ -126,7 +207,44 @@ impl EventProcessor {
         Ok(())
     }
 
+    /// Advances the cursor of the homeserver past its next `events_count` events only.
+    ///
+    /// The homeserver only returns the cursor of the last event of a batch, so the cursor is read
+    /// from a batch of `events_count` events polled again from the current cursor.
+    async fn advance_cursor_to(&self, events_count: u32) -> Result<(), EventProcessorError> {
+        if events_count == 0 {
+            return Ok(());
+        }
+
+        let lines = self
+            .poll_events_with_limit(events_count)
+            .await?
+            .unwrap_or_default();
+        let cursor = lines.iter().rev().find_map(|l| l.strip_prefix("cursor: "));
+        match cursor {
+            Some(cursor) => {
+                info!("Advancing the cursor past {events_count} events: {cursor}");
+                match Homeserver::try_from_cursor(self.homeserver.id.clone(), cursor) {
+                    Ok(hs) => hs.put_to_index().await?,
+                    Err(e) => warn!("{e}"),
+                }
+            }
+            None => {
+                warn!("No cursor returned for the first {events_count} events, keeping the cursor")
+            }
+        }
+        Ok(())
+    }
+
     /// Processes an event and track the fail event it if necessary
+    ///
+    /// The failures of an event URI are counted across attempts, and the event is moved to the
+    /// dead-letter index once it failed [RetryPolicy::max_attempts] times, in both cursor modes.
+    ///
+    /// With [CursorMode::Strict], a failed event holds the cursor and is polled again: it is not
+    /// processed again before its `next_retry_at`. Otherwise the cursor moved past the failed
+    /// event, so skipping a later event of the same URI would lose it: it is always processed, and
+    /// `next_retry_at` only orders the retry index.
     /// # Parameters:
     /// - `event`: The event to be processed
     #[tracing::instrument(
@@ -143,48 +261,111 @@ impl EventProcessor {
             otel.status_message = tracing::field::Empty,
         )
     )]
-    async fn handle_event(&self, event: &Event) -> Result<(), EventProcessorError> {
+    async fn handle_event(&self, event: &Event) -> Result<EventOutcome, EventProcessorError> {
         let span = tracing::Span::current();
-        if let Err(e) = handle(event, self.moderation.clone()).await {
+        let now = Utc::now().timestamp_millis();
+
+        let retry_index = retry_index_key(event);
+        let stored = match &retry_index {
+            Some(index_key) => RetryEvent::get_from_index(index_key)
+                .await
+                .inspect_err(|e| error!("Failed to read event from retry index: {e}"))
+                .unwrap_or(None),
+            None => None,
+        };
+        let previous = stored.as_ref();
+        // Only an event polled again from a held cursor waits for its backoff
+        let in_backoff =
+            previous.filter(|p| self.cursor_mode == CursorMode::Strict && !p.is_due(now));
+        if let Some(previous) = in_backoff {
+            debug!(
+                "Event in retry backoff until {}, skipping: {}",
+                previous.next_retry_at, event.uri
+            );
+            span.record("otel.status_code", "OK");
+            return Ok(EventOutcome::Deferred);
+        }
+
+        if let Err(e) = handle(event, self.moderation.clone(), self.settings.clone()).await {
             span.record("otel.status_code", "ERROR");
             span.record("otel.status_message", tracing::field::display(&e));
 
-            if let Some((index_key, retry_event)) = extract_retry_event_info(event, e) {
-                error!("{}, {}", retry_event.error_type, index_key);
-                if let Err(err) = retry_event.put_to_index(index_key).await {
-                    error!("Failed to put event to retry index: {}", err);
+            let homeserver_id = self.homeserver.id.to_string();
+            let event_type = event.event_type.to_string();
+            self.metrics.record_failed(&homeserver_id, &event_type);
+
+            let Some((index_key, retry_event)) =
+                extract_retry_event_info(event, e, previous, &self.retry_policy, now)
+            else {
+                return Ok(EventOutcome::Dropped);
+            };
+
+            error!("{}, {}", retry_event.error_type, index_key);
+            let (outcome, result) = match retry_event.attempts() >= self.retry_policy.max_attempts {
+                true => {
+                    warn!(
+                        "Event failed {} times, moving it to the dead-letter index: {index_key}",
+                        retry_event.attempts()
+                    );
+                    let result = retry_event.put_to_dead_letter(index_key).await;
+                    (EventOutcome::Dropped, result)
                 }
+                false => {
+                    let result = retry_event.put_to_index(index_key).await.inspect(|_| {
+                        self.metrics
+                            .record_retry_queued(&homeserver_id, &event_type)
+                    });
+                    (EventOutcome::Deferred, result)
+                }
+            };
+            if let Err(err) = result {
+                error!("Failed to put event to retry index: {}", err);
             }
-        } else {
-            span.record("otel.status_code", "OK");
+            return Ok(outcome);
         }
-        Ok(())
+
+        span.record("otel.status_code", "OK");
+
+        // The event does not need to be retried anymore
+        if let (Some(index_key), Some(_)) = (&retry_index, &stored) {
+            if let Err(err) = RetryEvent::remove_from_index(index_key).await {
+                error!("Failed to remove event from retry index: {}", err);
+            }
+        }
+        Ok(EventOutcome::Handled)
     }
 }
 
+/// Builds the retry index key of an event, in the format `"{event_type}:{pubkyId}:{repository_model}:{event_id}"`
+fn retry_index_key(event: &Event) -> Option<String> {
+    let index = RetryEvent::generate_index_key(&event.uri)?;
+    Some(format!("{}:{}", event.event_type, index))
+}
+
 /// Extracts retry-related information from an event and its associated error
 ///
 /// # Parameters
 /// - `event`: Reference to the event for which retry information is being extracted
 /// - `error`: Determines whether the event is eligible for a retry or should be discarded
+/// - `previous`: Retry state of the earlier failures of the event, if any
+/// - `policy`: Backoff applied before the next retry
+/// - `now`: Timestamp (ms) of the failed attempt
 fn extract_retry_event_info(
     event: &Event,
     error: EventProcessorError,
+    previous: Option<&RetryEvent>,
+    policy: &RetryPolicy,
+    now: i64,
 ) -> Option<(String, RetryEvent)> {
     let retry_event = match error {
         EventProcessorError::InvalidEventLine(ref message) => {
             error!("{}", message);
             return None;
         }
-        _ => RetryEvent::new(error),
+        _ => RetryEvent::after_failure(previous, error, policy, now),
     };
 
     // Generate a compress index to save in the cache
-    let index = match RetryEvent::generate_index_key(&event.uri) {
-        Some(retry_index) => retry_index,
-        None => {
-            return None;
-        }
-    };
-    Some((format!("{}:{}", event.event_type, index), retry_event))
+    let index_key = retry_index_key(event)?;
+    Some((index_key, retry_event))
 }
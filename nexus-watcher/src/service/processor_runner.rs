@@ -1,12 +1,17 @@
-use crate::events::Moderation;
+use crate::events::retry::policy::RetryPolicy;
+use crate::events::{EventSettings, Moderation};
+use crate::service::metrics::EventMetrics;
 use crate::service::processor::EventProcessor;
 use crate::service::traits::{TEventProcessor, TEventProcessorRunner};
 use nexus_common::models::homeserver::Homeserver;
 use nexus_common::types::DynError;
-use nexus_common::WatcherConfig;
+use nexus_common::{CursorMode, HomeserverOverride, WatcherConfig};
 use pubky_app_specs::PubkyId;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::watch::Receiver;
 
 pub struct EventProcessorRunner {
@@ -15,10 +20,25 @@ pub struct EventProcessorRunner {
     /// See [WatcherConfig::monitored_homeservers_limit]
     pub monitored_homeservers_limit: usize,
     pub files_path: PathBuf,
+    /// Moderation policy, with the moderated tags of the [HomeserverOverride]s
     pub moderation: Arc<Moderation>,
+    /// See [EventSettings::from_config]
+    pub settings: Arc<EventSettings>,
     pub shutdown_rx: Receiver<bool>,
     /// See [WatcherConfig::homeserver]
     pub default_homeserver: PubkyId,
+    /// See [WatcherConfig::log_run_durations]
+    pub log_run_durations: bool,
+    /// See [RetryPolicy::from_config]
+    pub retry_policy: RetryPolicy,
+    /// See [WatcherConfig::cursor_mode]
+    pub cursor_mode: CursorMode,
+    /// See [WatcherConfig::homeserver_overrides]
+    pub homeserver_overrides: BTreeMap<String, HomeserverOverride>,
+    /// Time of the last poll of each homeserver, used for their [HomeserverOverride::poll_interval]
+    pub last_polls: Mutex<HashMap<String, Instant>>,
+    /// See [WatcherConfig::event_metrics]
+    pub metrics: EventMetrics,
 }
 
 impl EventProcessorRunner {
@@ -28,14 +48,82 @@ impl EventProcessorRunner {
             limit: config.events_limit,
             monitored_homeservers_limit: config.monitored_homeservers_limit,
             files_path: config.stack.files_path.clone(),
-            moderation: Arc::new(Moderation {
-                id: config.moderation_id.clone(),
-                tags: config.moderated_tags.clone(),
-            }),
+            moderation: Arc::new(Moderation::from_config(config)),
+            settings: Arc::new(EventSettings::from_config(config)),
             shutdown_rx,
             default_homeserver: config.homeserver.clone(),
+            log_run_durations: config.log_run_durations,
+            retry_policy: RetryPolicy::from_config(config),
+            cursor_mode: config.cursor_mode,
+            homeserver_overrides: config.homeserver_overrides.clone(),
+            last_polls: Mutex::new(HashMap::new()),
+            metrics: EventMetrics::from_global(config.event_metrics),
         }
     }
+
+    /// Maximum number of events fetched per run from a homeserver, see [HomeserverOverride::events_limit]
+    pub fn events_limit(&self, homeserver_id: &str) -> u32 {
+        self.homeserver_overrides
+            .get(homeserver_id)
+            .and_then(|hs_override| hs_override.events_limit)
+            .unwrap_or(self.limit)
+    }
+
+    /// Timeout of the runs of a homeserver's event processor, see [HomeserverOverride::timeout_secs].
+    /// `None` applies the global [PROCESSING_TIMEOUT_SECS](crate::service::PROCESSING_TIMEOUT_SECS)
+    pub fn processing_timeout(&self, homeserver_id: &str) -> Option<Duration> {
+        self.homeserver_overrides
+            .get(homeserver_id)
+            .and_then(|hs_override| hs_override.timeout_secs)
+            .map(Duration::from_secs)
+    }
+
+    /// Whether a homeserver can be polled in this run, see [HomeserverOverride::poll_interval]
+    fn is_poll_due(&self, homeserver_id: &str) -> bool {
+        let Some(poll_interval) = self
+            .homeserver_overrides
+            .get(homeserver_id)
+            .and_then(|hs_override| hs_override.poll_interval)
+        else {
+            return true;
+        };
+        let last_polls = self.last_polls.lock().unwrap_or_else(|e| e.into_inner());
+        match last_polls.get(homeserver_id) {
+            Some(last_poll) => last_poll.elapsed() >= Duration::from_millis(poll_interval),
+            None => true,
+        }
+    }
+
+    /// Creates a new event processor for the specified homeserver, applying its [HomeserverOverride]
+    pub async fn build_event_processor(
+        &self,
+        homeserver_id: &str,
+    ) -> Result<EventProcessor, DynError> {
+        let homeserver_id = PubkyId::try_from(homeserver_id)?;
+        let homeserver = Homeserver::get_by_id(homeserver_id)
+            .await?
+            .ok_or("Homeserver not found")?;
+
+        let hs_id = homeserver.id.to_string();
+        if self.homeserver_overrides.contains_key(&hs_id) {
+            let mut last_polls = self.last_polls.lock().unwrap_or_else(|e| e.into_inner());
+            last_polls.insert(hs_id.clone(), Instant::now());
+        }
+
+        Ok(EventProcessor {
+            limit: self.events_limit(&hs_id),
+            timeout: self.processing_timeout(&hs_id),
+            homeserver,
+            files_path: self.files_path.clone(),
+            moderation: self.moderation.clone(),
+            settings: self.settings.clone(),
+            shutdown_rx: self.shutdown_rx.clone(),
+            retry_policy: self.retry_policy,
+            cursor_mode: self.cursor_mode,
+            metrics: self.metrics.clone(),
+            events_processed: AtomicU64::new(0),
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -52,9 +140,20 @@ impl TEventProcessorRunner for EventProcessorRunner {
         self.monitored_homeservers_limit
     }
 
+    fn log_run_durations(&self) -> bool {
+        self.log_run_durations
+    }
+
+    fn metrics(&self) -> EventMetrics {
+        self.metrics.clone()
+    }
+
     async fn homeservers_by_priority(&self) -> Result<Vec<String>, DynError> {
         let mut hs_ids = Homeserver::get_all_from_graph().await?;
 
+        // Skip the homeservers polled more recently than their poll interval
+        hs_ids.retain(|hs_id| self.is_poll_due(hs_id));
+
         // Move default homeserver to index 0 if it exists in the array to prioritize its processing
         if let Some(default_pos) = hs_ids
             .iter()
@@ -69,18 +168,6 @@ impl TEventProcessorRunner for EventProcessorRunner {
 
     /// Creates and returns a new event processor instance for the specified homeserver
     async fn build(&self, homeserver_id: String) -> Result<Arc<dyn TEventProcessor>, DynError> {
-        let homeserver_id = PubkyId::try_from(&homeserver_id)?;
-        let homeserver = Homeserver::get_by_id(homeserver_id)
-            .await?
-            .ok_or("Homeserver not found")?;
-
-        // Create a new event processor instance with the specified homeserver
-        Ok(Arc::new(EventProcessor {
-            homeserver,
-            limit: self.limit,
-            files_path: self.files_path.clone(),
-            moderation: self.moderation.clone(),
-            shutdown_rx: self.shutdown_rx.clone(),
-        }))
+        Ok(Arc::new(self.build_event_processor(&homeserver_id).await?))
     }
 }
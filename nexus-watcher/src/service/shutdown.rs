@@ -0,0 +1,99 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::watch::Receiver;
+
+/// Resolves once a shutdown is signalled. Never resolves if the shutdown sender was dropped.
+async fn wait_for_shutdown(shutdown_rx: &mut Receiver<bool>) {
+    if shutdown_rx.wait_for(|shutdown| *shutdown).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Runs `work` to completion, unless a shutdown is signalled while it is in flight. The work is
+/// then given `grace` to finish, after which it is abandoned.
+///
+/// Returns `None` if the work was abandoned.
+pub(crate) async fn run_with_shutdown_grace<F: Future>(
+    work: F,
+    mut shutdown_rx: Receiver<bool>,
+    grace: Duration,
+) -> Option<F::Output> {
+    tokio::pin!(work);
+
+    tokio::select! {
+        output = &mut work => return Some(output),
+        _ = wait_for_shutdown(&mut shutdown_rx) => {}
+    }
+
+    tokio::time::timeout(grace, work).await.ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Instant;
+
+    #[tokio::test]
+    async fn work_completes_without_shutdown() {
+        let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let work = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            42
+        };
+
+        let output = run_with_shutdown_grace(work, shutdown_rx, Duration::ZERO).await;
+        assert_eq!(output, Some(42));
+    }
+
+    #[tokio::test]
+    async fn work_completes_within_grace_period() {
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let work = async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            42
+        };
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            shutdown_tx.send(true).unwrap();
+        });
+
+        let output = run_with_shutdown_grace(work, shutdown_rx, Duration::from_secs(5)).await;
+        assert_eq!(output, Some(42));
+    }
+
+    #[tokio::test]
+    async fn unresponsive_work_is_abandoned_after_grace_period() {
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        // Simulates an event stuck in a fetch that ignores the shutdown signal
+        let work = tokio::time::sleep(Duration::from_secs(60));
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            shutdown_tx.send(true).unwrap();
+        });
+
+        let t0 = Instant::now();
+        let output = run_with_shutdown_grace(work, shutdown_rx, Duration::from_millis(200)).await;
+        assert!(output.is_none());
+
+        let elapsed = t0.elapsed();
+        assert!(elapsed >= Duration::from_millis(300));
+        assert!(elapsed < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn dropped_sender_does_not_interrupt_work() {
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        drop(shutdown_tx);
+
+        let work = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            42
+        };
+
+        let output = run_with_shutdown_grace(work, shutdown_rx, Duration::ZERO).await;
+        assert_eq!(output, Some(42));
+    }
+}
@@ -14,11 +14,15 @@ pub struct ProcessorRunStats {
     pub hs_id: String,
     pub duration: Duration,
     pub status: ProcessorRunStatus,
+    /// Number of events processed during the run
+    pub events: u64,
 }
 
 #[derive(Default)]
 pub struct RunAllProcessorsStats {
     pub stats: Vec<ProcessorRunStats>,
+    /// Wall-clock duration of the full run over all homeservers
+    pub total_duration: Duration,
 }
 
 impl RunAllProcessorsStats {
@@ -27,15 +31,22 @@ impl RunAllProcessorsStats {
         hs_id: String,
         duration: Duration,
         status: ProcessorRunStatus,
+        events: u64,
     ) {
         let individual_run_stats = ProcessorRunStats {
             hs_id,
             duration,
             status,
+            events,
         };
         self.stats.push(individual_run_stats);
     }
 
+    /// Appends the run results of `other`, e.g. of a run executed concurrently with this one
+    pub(crate) fn merge(&mut self, other: RunAllProcessorsStats) {
+        self.stats.extend(other.stats);
+    }
+
     fn count(&self, status: ProcessorRunStatus) -> usize {
         self.stats.iter().filter(|ps| ps.status == status).count()
     }
@@ -69,6 +80,11 @@ impl RunAllProcessorsStats {
     pub fn count_skipped(&self) -> usize {
         self.count(ProcessorRunStatus::Skipped)
     }
+
+    /// Number of events processed across all homeservers
+    pub fn count_events(&self) -> u64 {
+        self.stats.iter().map(|ps| ps.events).sum()
+    }
 }
 
 /// Wrapper around `RunAllProcessorsStats` which indicates they've been processed
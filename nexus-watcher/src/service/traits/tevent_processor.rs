@@ -89,4 +89,11 @@ pub trait TEventProcessor: Send + Sync + 'static {
     fn custom_timeout(&self) -> Option<Duration> {
         None
     }
+
+    /// Number of events processed by the last run of this event processor.
+    ///
+    /// Only used for run statistics. Defaults to 0 for processors that do not track it.
+    fn events_processed(&self) -> u64 {
+        0
+    }
 }
@@ -1,11 +1,17 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use nexus_common::types::DynError;
 use tokio::sync::watch::Receiver;
+use tokio::task::{self, JoinSet};
 use tracing::{debug, error, info, warn};
 
 use crate::service::{
     backoff::HomeserverBackoff,
+    metrics::EventMetrics,
     stats::{ProcessedStats, ProcessorRunStatus, RunAllProcessorsStats},
     traits::{tevent_processor::RunError, TEventProcessor},
 };
@@ -28,6 +34,18 @@ pub trait TEventProcessorRunner {
 
     fn monitored_homeservers_limit(&self) -> usize;
 
+    /// Whether the duration of each homeserver run and of the full run are logged at info level.
+    /// Defaults to `false`, in which case they are only logged at debug level.
+    fn log_run_durations(&self) -> bool {
+        false
+    }
+
+    /// Instruments recording the duration of every homeserver run in [TEventProcessorRunner::post_run_all].
+    /// Defaults to disabled metrics.
+    fn metrics(&self) -> EventMetrics {
+        EventMetrics::default()
+    }
+
     /// Returns the homeserver IDs relevant for this run, ordered by their priority.
     ///
     /// Contains all homeserver IDs from the graph, with the default homeserver prioritized at index 0.
@@ -59,11 +77,35 @@ pub trait TEventProcessorRunner {
 
     /// Post-processing of the run results
     async fn post_run_all(&self, stats: RunAllProcessorsStats) -> ProcessedStats {
+        let log_run_durations = self.log_run_durations();
+        let metrics = self.metrics();
+
         for individual_run_stat in &stats.stats {
             let hs_id = &individual_run_stat.hs_id;
             let duration = individual_run_stat.duration;
             let status = &individual_run_stat.status;
-            debug!("Event processor run for HS {hs_id}: duration {duration:?}, status {status:?}");
+            let events = individual_run_stat.events;
+            metrics.record_run(hs_id, duration, status);
+            if log_run_durations {
+                info!(
+                    homeserver = %hs_id,
+                    duration_ms = duration.as_millis() as u64,
+                    status = ?status,
+                    events,
+                    "Event processor run finished"
+                );
+            } else {
+                debug!("Event processor run for HS {hs_id}: duration {duration:?}, status {status:?}, events {events}");
+            }
+        }
+
+        if log_run_durations {
+            info!(
+                total_duration_ms = stats.total_duration.as_millis() as u64,
+                homeservers = stats.stats.len(),
+                events = stats.count_events(),
+                "Event processors run finished"
+            );
         }
 
         let count_ok = stats.count_ok();
@@ -96,50 +138,153 @@ pub trait TEventProcessorRunner {
     async fn run_all(&self, backoff: &mut HomeserverBackoff) -> Result<ProcessedStats, DynError> {
         let hs_ids = self.pre_run_all().await?;
 
+        let run_t0 = Instant::now();
+        let mut run_stats = self.run_homeservers(hs_ids, backoff).await;
+        run_stats.total_duration = run_t0.elapsed();
+
+        let processed_stats = self.post_run_all(run_stats).await;
+        Ok(processed_stats)
+    }
+
+    /// Runs the event processor of the default homeserver only, with timeout protection.
+    ///
+    /// # Parameters
+    /// * `backoff` - Backoff state of the default homeserver, see [TEventProcessorRunner::run_all]
+    async fn run_default(
+        &self,
+        backoff: &mut HomeserverBackoff,
+    ) -> Result<ProcessedStats, DynError> {
+        let run_t0 = Instant::now();
+        let mut run_stats = self
+            .run_homeservers(vec![self.default_homeserver().to_string()], backoff)
+            .await;
+        run_stats.total_duration = run_t0.elapsed();
+
+        Ok(self.post_run_all(run_stats).await)
+    }
+
+    /// Same as [TEventProcessorRunner::run_all], except that the default homeserver is processed
+    /// concurrently with the other ones, so that slow homeservers don't delay it.
+    ///
+    /// The stats of both runs are combined into a single [`RunAllProcessorsStats`].
+    ///
+    /// # Parameters
+    /// * `default_backoff` - Backoff state of the default homeserver
+    /// * `backoff` - Backoff state of the other homeservers
+    async fn run_all_concurrently(
+        &self,
+        default_backoff: &mut HomeserverBackoff,
+        backoff: &mut HomeserverBackoff,
+    ) -> Result<ProcessedStats, DynError> {
+        let (default_ids, other_ids): (Vec<String>, Vec<String>) = self
+            .pre_run_all()
+            .await?
+            .into_iter()
+            .partition(|hs_id| hs_id == self.default_homeserver());
+
+        let run_t0 = Instant::now();
+        let (mut run_stats, other_stats) = tokio::join!(
+            self.run_homeservers(default_ids, default_backoff),
+            self.run_homeservers(other_ids, backoff),
+        );
+        run_stats.merge(other_stats);
+        run_stats.total_duration = run_t0.elapsed();
+
+        Ok(self.post_run_all(run_stats).await)
+    }
+
+    /// Runs the event processors of the given homeservers concurrently, at most
+    /// [TEventProcessorRunner::monitored_homeservers_limit] at a time.
+    ///
+    /// Homeservers in a backoff window are skipped, and no new processor is started after shutdown.
+    /// A failing or panicking processor only affects the stats of its own homeserver.
+    /// The `total_duration` of the returned stats is left for the caller to set.
+    async fn run_homeservers(
+        &self,
+        hs_ids: Vec<String>,
+        backoff: &mut HomeserverBackoff,
+    ) -> RunAllProcessorsStats {
+        let max_running = self.monitored_homeservers_limit().max(1);
         let mut run_stats = RunAllProcessorsStats::default();
+        let mut running: JoinSet<(ProcessorRunStatus, u64)> = JoinSet::new();
+        let mut running_hs_ids: HashMap<task::Id, (String, Instant)> = HashMap::new();
+        let mut pending = hs_ids.into_iter();
 
-        for hs_id in hs_ids {
-            if *self.shutdown_rx().borrow() {
-                info!("Shutdown detected in homeserver {hs_id}, exiting run_all loop");
-                break; // Exit loop
-            }
+        loop {
+            // Start processors until the limit is reached
+            while running.len() < max_running {
+                let Some(hs_id) = pending.next() else {
+                    break;
+                };
 
-            // Skip homeservers that are in a backoff window
-            if backoff.should_skip(&hs_id) {
-                debug!("Skipping homeserver {hs_id} (in backoff)");
-                run_stats.add_run_result(
-                    hs_id,
-                    std::time::Duration::ZERO,
-                    ProcessorRunStatus::Skipped,
-                );
-                continue;
+                if *self.shutdown_rx().borrow() {
+                    info!("Shutdown detected in homeserver {hs_id}, not starting further event processors");
+                    pending = Vec::new().into_iter();
+                    break;
+                }
+
+                // Skip homeservers that are in a backoff window
+                if backoff.should_skip(&hs_id) {
+                    debug!("Skipping homeserver {hs_id} (in backoff)");
+                    run_stats.add_run_result(hs_id, Duration::ZERO, ProcessorRunStatus::Skipped, 0);
+                    continue;
+                }
+
+                let t0 = Instant::now();
+                match self.build(hs_id.clone()).await {
+                    Ok(event_processor) => {
+                        let handle = running.spawn(async move {
+                            let status = match event_processor.clone().run().await {
+                                Ok(_) => ProcessorRunStatus::Ok,
+                                Err(RunError::Internal(_)) => ProcessorRunStatus::Error,
+                                Err(RunError::Panicked) => ProcessorRunStatus::Panic,
+                                Err(RunError::TimedOut) => ProcessorRunStatus::Timeout,
+                            };
+                            (status, event_processor.events_processed())
+                        });
+                        running_hs_ids.insert(handle.id(), (hs_id, t0));
+                    }
+                    Err(e) => {
+                        error!("Failed to build event processor for homeserver: {hs_id}: {e}");
+                        let status = ProcessorRunStatus::FailedToBuild;
+                        record_run_result(&mut run_stats, backoff, hs_id, t0.elapsed(), status, 0);
+                    }
+                }
             }
 
-            let t0 = Instant::now();
-            let status = match self.build(hs_id.clone()).await {
-                Ok(event_processor) => match event_processor.run().await {
-                    Ok(_) => ProcessorRunStatus::Ok,
-                    Err(RunError::Internal(_)) => ProcessorRunStatus::Error,
-                    Err(RunError::Panicked) => ProcessorRunStatus::Panic,
-                    Err(RunError::TimedOut) => ProcessorRunStatus::Timeout,
-                },
+            let Some(joined) = running.join_next_with_id().await else {
+                break; // Nothing running and nothing left to start
+            };
+            let (id, status, events) = match joined {
+                Ok((id, (status, events))) => (id, status, events),
                 Err(e) => {
-                    error!("Failed to build event processor for homeserver: {hs_id}: {e}");
-                    ProcessorRunStatus::FailedToBuild
+                    error!("Event processor task failed: {e}");
+                    (e.id(), ProcessorRunStatus::Panic, 0)
                 }
             };
-            let duration = t0.elapsed();
-
-            if status == ProcessorRunStatus::Ok {
-                backoff.record_success(&hs_id);
-            } else {
-                backoff.record_failure(&hs_id);
+            if let Some((hs_id, t0)) = running_hs_ids.remove(&id) {
+                record_run_result(&mut run_stats, backoff, hs_id, t0.elapsed(), status, events);
             }
-
-            run_stats.add_run_result(hs_id, duration, status);
         }
 
-        let processed_stats = self.post_run_all(run_stats).await;
-        Ok(processed_stats)
+        run_stats
     }
 }
+
+/// Updates the backoff state of a homeserver according to its run status, and adds the run to the stats
+fn record_run_result(
+    run_stats: &mut RunAllProcessorsStats,
+    backoff: &mut HomeserverBackoff,
+    hs_id: String,
+    duration: Duration,
+    status: ProcessorRunStatus,
+    events: u64,
+) {
+    if status == ProcessorRunStatus::Ok {
+        backoff.record_success(&hs_id);
+    } else {
+        backoff.record_failure(&hs_id);
+    }
+
+    run_stats.add_run_result(hs_id, duration, status, events);
+}
@@ -0,0 +1,91 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::models::homeserver::Homeserver;
+use nexus_common::models::post::PostDetails;
+use nexus_common::models::user::UserDetails;
+use nexus_common::CursorMode;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser, PubkyId};
+
+fn post(content: &str) -> PubkyAppPost {
+    PubkyAppPost {
+        content: content.to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    }
+}
+
+async fn stored_cursor(test: &WatcherTest) -> Result<String> {
+    let hs_id = PubkyId::try_from(test.homeserver_id.as_str()).map_err(anyhow::Error::msg)?;
+    let homeserver = Homeserver::get_by_id(hs_id)
+        .await?
+        .ok_or(anyhow::anyhow!("Homeserver not found"))?;
+    Ok(homeserver.cursor)
+}
+
+async fn run_with_cursor_mode(test: &mut WatcherTest, cursor_mode: CursorMode) -> Result<()> {
+    test.event_processor_runner.cursor_mode = cursor_mode;
+    test.ensure_event_processing = true;
+    test.ensure_event_processing_complete().await?;
+    test.ensure_event_processing = false;
+    Ok(())
+}
+
+/// The events are written to the homeserver first, then processed in a single batch:
+/// a user profile, a post of a user without profile (failing) and a post of the first user
+#[tokio_shared_rt::test(shared)]
+async fn test_cursor_modes_on_failed_event() -> Result<()> {
+    let mut test = WatcherTest::setup().await?.remove_event_processing().await;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: None,
+        image: None,
+        links: None,
+        name: "Watcher:CursorMode:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    let no_profile_kp = Keypair::random();
+    test.register_user(&no_profile_kp).await?;
+    test.create_post(&no_profile_kp, &post("Watcher:CursorMode:Failing"))
+        .await?;
+
+    let (post_id, post_path) = test
+        .create_post(&user_kp, &post("Watcher:CursorMode:After"))
+        .await?;
+
+    let initial_cursor = stored_cursor(&test).await?;
+
+    // Strict: the batch stops at the failed post, the cursor only moves past the events before it
+    run_with_cursor_mode(&mut test, CursorMode::Strict).await?;
+    assert!(UserDetails::get_by_id(&user_id).await?.is_some());
+    assert!(PostDetails::get_from_index(&user_id, &post_id)
+        .await?
+        .is_none());
+    let strict_cursor = stored_cursor(&test).await?;
+    assert_ne!(strict_cursor, initial_cursor);
+
+    // The failed post is in retry backoff, so the cursor stays before it
+    run_with_cursor_mode(&mut test, CursorMode::Strict).await?;
+    assert!(PostDetails::get_from_index(&user_id, &post_id)
+        .await?
+        .is_none());
+    assert_eq!(stored_cursor(&test).await?, strict_cursor);
+
+    // Retry queue: the failed post is left to the retry index, and the batch goes on
+    run_with_cursor_mode(&mut test, CursorMode::RetryQueue).await?;
+    assert!(PostDetails::get_from_index(&user_id, &post_id)
+        .await?
+        .is_some());
+    assert_ne!(stored_cursor(&test).await?, strict_cursor);
+
+    test.ensure_event_processing = true;
+    test.cleanup_post(&user_kp, &post_path).await?;
+    test.cleanup_user(&user_kp).await?;
+
+    Ok(())
+}
@@ -58,3 +58,49 @@ async fn test_put_pubkyapp_file() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_put_unstrippable_image_is_removed() -> Result<()> {
+    // Arrange
+    let mut test = WatcherTest::setup().await?;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: None,
+        image: None,
+        links: None,
+        name: "Test User".to_string(),
+        status: None,
+    };
+
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    // Not an image, so its metadata cannot be stripped
+    let blob = PubkyAppBlob::new(b"Not a PNG".to_vec());
+    let blob_id = blob.create_id();
+    let blob_relative_url = PubkyAppBlob::create_path(&blob_id);
+    let blob_absolute_url = blob_uri_builder(user_id.clone(), blob_id);
+
+    test.create_file_from_body(&user_kp, blob_relative_url.as_str(), blob.0.clone())
+        .await?;
+
+    // Act
+    let file = PubkyAppFile {
+        name: "image.png".to_string(),
+        content_type: "image/png".to_string(),
+        src: blob_absolute_url,
+        size: blob.0.len(),
+        created_at: Utc::now().timestamp_millis(),
+    };
+
+    let (file_id, _) = test.create_file(&user_kp, &file).await?;
+
+    // Assert: The original is not left on disk
+    let file_static_path = format!("./static/files/{user_id}/{file_id}");
+    assert!(
+        !Path::new(&file_static_path).exists(),
+        "An image that could not be stripped should not be kept"
+    );
+
+    Ok(())
+}
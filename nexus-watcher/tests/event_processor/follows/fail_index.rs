@@ -43,8 +43,7 @@ async fn test_homeserver_follow_cannot_complete() -> Result<()> {
     // Simulate the event processor to handle the event.
     // If the event processor were activated, the test would not catch the missing dependency
     // error, and it would pass successfully
-    let moderation_ref = test.event_processor_runner.moderation.clone();
-    let sync_fail = retrieve_and_handle_event_line(&follow_event, moderation_ref)
+    let sync_fail = retrieve_and_handle_event_line(&follow_event, &test.event_processor_runner)
         .await
         .map_err(|e| error!("SYNC ERROR: {:?}", e))
         .is_err();
@@ -63,11 +62,11 @@ async fn test_homeserver_follow_cannot_complete() -> Result<()> {
     // Simulate the event processor to handle the event.
     // If the event processor were activated, the test would not catch the missing dependency
     // error, and it would pass successfully
-    let moderation_ref = test.event_processor_runner.moderation.clone();
-    let sync_fail = retrieve_and_handle_event_line(&opposite_follow_event, moderation_ref)
-        .await
-        .map_err(|e| error!("SYNC ERROR: {:?}", e))
-        .is_err();
+    let sync_fail =
+        retrieve_and_handle_event_line(&opposite_follow_event, &test.event_processor_runner)
+            .await
+            .map_err(|e| error!("SYNC ERROR: {:?}", e))
+            .is_err();
 
     assert!(
         sync_fail,
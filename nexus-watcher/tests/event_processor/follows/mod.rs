@@ -8,4 +8,6 @@ mod put_friends;
 mod put_notification;
 mod put_sequential;
 mod retry_follow;
+mod self_follow;
+mod stats;
 mod utils;
@@ -0,0 +1,61 @@
+use super::utils::find_follow_relationship;
+use crate::event_processor::users::utils::find_user_counts;
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::{
+    db::RedisOps,
+    models::follow::{Followers, Following},
+};
+use nexus_watcher::events::EventSettings;
+use pubky::Keypair;
+use pubky_app_specs::PubkyAppUser;
+use std::sync::Arc;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_self_follow() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_homeserver_self_follow".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:SelfFollow:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    // Self-follows are ignored by default
+    let follow_path = test.create_follow(&user_kp, &user_id).await?;
+
+    assert!(!find_follow_relationship(&user_id, &user_id).await?);
+    let (_exist, member) = Followers::check_set_member(&[&user_id], &user_id).await?;
+    assert!(!member);
+    let (_exist, member) = Following::check_set_member(&[&user_id], &user_id).await?;
+    assert!(!member);
+    let counts = find_user_counts(&user_id).await;
+    assert_eq!(counts.followers, 0);
+    assert_eq!(counts.following, 0);
+
+    // Deleting the ignored follow is a no-op
+    test.del(&user_kp, &follow_path).await?;
+    let counts = find_user_counts(&user_id).await;
+    assert_eq!(counts.followers, 0);
+    assert_eq!(counts.following, 0);
+
+    // Once allowed, they are indexed like any other follow
+    test.event_processor_runner.settings = Arc::new(EventSettings {
+        allow_self_follows: true,
+        ..Default::default()
+    });
+    let follow_path = test.create_follow(&user_kp, &user_id).await?;
+    let exist = find_follow_relationship(&user_id, &user_id).await?;
+    let counts = find_user_counts(&user_id).await;
+    test.del(&user_kp, &follow_path).await?;
+
+    assert!(exist);
+    assert_eq!(counts.followers, 1);
+    assert_eq!(counts.following, 1);
+
+    Ok(())
+}
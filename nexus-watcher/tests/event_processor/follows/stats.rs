@@ -0,0 +1,115 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use nexus_common::models::follow::FollowStats;
+use nexus_common::types::Timeframe;
+use pubky::Keypair;
+use pubky_app_specs::traits::HasIdPath;
+use pubky_app_specs::{PubkyAppFollow, PubkyAppUser};
+
+async fn followers_gained(user_id: &str, timeframe: Timeframe) -> Result<usize> {
+    let stats = FollowStats::followers_gained_since(user_id, &timeframe).await?;
+    Ok(stats.followers_gained)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_follower_stats_by_timeframe() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let followee_kp = Keypair::random();
+    let followee = PubkyAppUser {
+        bio: Some("test_follower_stats_by_timeframe".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:FollowStats:Followee".to_string(),
+        status: None,
+    };
+    let followee_id = test.create_user(&followee_kp, &followee).await?;
+
+    // Followers that followed an hour, 10 days and 60 days ago
+    let now = Utc::now();
+    let mut follow_paths = Vec::new();
+    for (i, age) in [Duration::hours(1), Duration::days(10), Duration::days(60)]
+        .into_iter()
+        .enumerate()
+    {
+        let follower_kp = Keypair::random();
+        let follower = PubkyAppUser {
+            bio: Some("test_follower_stats_by_timeframe".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:FollowStats:Follower{i}"),
+            status: None,
+        };
+        test.create_user(&follower_kp, &follower).await?;
+
+        let follow = PubkyAppFollow {
+            created_at: (now - age).timestamp_millis(),
+        };
+        let follow_path = follow.hs_path(&followee_id);
+        test.put(&follower_kp, &follow_path, follow).await?;
+        follow_paths.push((follower_kp, follow_path));
+    }
+
+    assert_eq!(followers_gained(&followee_id, Timeframe::Today).await?, 1);
+    assert_eq!(
+        followers_gained(&followee_id, Timeframe::ThisMonth).await?,
+        2
+    );
+    assert_eq!(followers_gained(&followee_id, Timeframe::AllTime).await?, 3);
+
+    // An unfollow removes the follower from the gained ones
+    let (follower_kp, follow_path) = &follow_paths[0];
+    test.del(follower_kp, follow_path).await?;
+
+    assert_eq!(followers_gained(&followee_id, Timeframe::Today).await?, 0);
+    assert_eq!(
+        followers_gained(&followee_id, Timeframe::ThisMonth).await?,
+        1
+    );
+    assert_eq!(followers_gained(&followee_id, Timeframe::AllTime).await?, 2);
+
+    Ok(())
+}
+
+/// Follows indexed before the follow times were recorded are backfilled from the graph
+#[tokio_shared_rt::test(shared)]
+async fn test_follower_stats_reindex() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::new();
+    let mut keypairs = Vec::new();
+    for name in ["Followee", "Follower"] {
+        let user_kp = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_follower_stats_reindex".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:FollowStatsReindex:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&user_kp, &user).await?);
+        keypairs.push(user_kp);
+    }
+    let (followee_id, follower_id) = (&user_ids[0], &user_ids[1]);
+
+    let follow = PubkyAppFollow {
+        created_at: (Utc::now() - Duration::days(60)).timestamp_millis(),
+    };
+    let follow_path = follow.hs_path(followee_id);
+    test.put(&keypairs[1], &follow_path, follow).await?;
+    assert_eq!(followers_gained(followee_id, Timeframe::AllTime).await?, 1);
+
+    // Without a recorded follow time, the follower is not counted
+    FollowStats::del_from_index(followee_id, follower_id).await?;
+    assert_eq!(followers_gained(followee_id, Timeframe::AllTime).await?, 0);
+
+    // The graph keeps when the follow was indexed, just now
+    FollowStats::reindex(followee_id).await?;
+    assert_eq!(followers_gained(followee_id, Timeframe::Today).await?, 1);
+
+    test.del(&keypairs[1], &follow_path).await?;
+    assert_eq!(followers_gained(followee_id, Timeframe::AllTime).await?, 0);
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
 mod ingest_homeservers_from_follow_events;
 mod ingest_homeservers_from_post_events;
 mod ingest_homeservers_from_tag_events;
-mod utils;
+pub mod utils;
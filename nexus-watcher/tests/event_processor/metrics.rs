@@ -0,0 +1,69 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::{anyhow, Result};
+use nexus_watcher::service::EventMetrics;
+use opentelemetry::metrics::MeterProvider;
+use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+use opentelemetry_sdk::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+use pubky::Keypair;
+
+/// Sum of all data points of a u64 counter, over all attributes
+fn counter_value(exporter: &InMemoryMetricExporter, name: &str) -> Result<u64> {
+    let resource_metrics = exporter.get_finished_metrics()?;
+    let latest = resource_metrics
+        .last()
+        .ok_or(anyhow!("No metrics were exported"))?;
+
+    let value = latest
+        .scope_metrics()
+        .flat_map(|scope| scope.metrics())
+        .filter(|metric| metric.name() == name)
+        .map(|metric| match metric.data() {
+            AggregatedMetrics::U64(MetricData::Sum(sum)) => {
+                sum.data_points().map(|point| point.value()).sum()
+            }
+            _ => 0,
+        })
+        .sum();
+    Ok(value)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_processed_events_counter() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let exporter = InMemoryMetricExporter::default();
+    let provider = SdkMeterProvider::builder()
+        .with_periodic_exporter(exporter.clone())
+        .build();
+    test.event_processor_runner.metrics = EventMetrics::new(&provider.meter("test"));
+
+    let processor = test
+        .event_processor_runner
+        .build_event_processor(&test.homeserver_id)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    let follower_id = Keypair::random().public_key().to_z32();
+    let event_lines: Vec<String> = (0..3)
+        .map(|_| {
+            let followee_id = Keypair::random().public_key().to_z32();
+            format!("DEL pubky://{follower_id}/pub/pubky.app/follows/{followee_id}")
+        })
+        .collect();
+
+    processor.process_event_lines(event_lines).await?;
+    provider.force_flush()?;
+    assert_eq!(counter_value(&exporter, "watcher.events.processed")?, 3);
+
+    // Cursor lines are not events
+    processor
+        .process_event_lines(vec![
+            format!("DEL pubky://{follower_id}/pub/pubky.app/follows/{follower_id}"),
+            "cursor: 0000000000000".to_string(),
+        ])
+        .await?;
+    provider.force_flush()?;
+    assert_eq!(counter_value(&exporter, "watcher.events.processed")?, 4);
+
+    Ok(())
+}
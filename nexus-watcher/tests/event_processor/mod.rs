@@ -1,8 +1,10 @@
 mod bookmarks;
+mod cursor_mode;
 mod files;
 mod follows;
 mod homeserver;
 mod mentions;
+mod metrics;
 mod network;
 mod posts;
 mod tags;
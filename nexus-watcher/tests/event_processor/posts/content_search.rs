@@ -0,0 +1,67 @@
+use crate::event_processor::utils::{
+    index::{assert_indexed, assert_not_indexed},
+    watcher::WatcherTest,
+};
+use anyhow::Result;
+use nexus_common::models::post::search::{PostsByContentSearch, CONTENT_GLOBAL_POST_TIMELINE};
+use nexus_common::types::Pagination;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_content_search_put_and_delete() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_post_content_search_put_and_delete".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:PostContentSearch:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    // Use a word unlikely to be in any other post, so the search results are predictable
+    let post = PubkyAppPost {
+        content: "Testing the Zyxwvutsrq content search, ok?".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, post_path) = test.create_post(&user_kp, &post).await?;
+    let post_key = format!("{user_id}:{post_id}");
+
+    // Sorted:Content:Global:Post:Timeline:zyxwvutsrq
+    let token_key_parts = [&CONTENT_GLOBAL_POST_TIMELINE[..], &["zyxwvutsrq"]].concat();
+    assert_indexed::<PostsByContentSearch>(&token_key_parts, &[&user_id, &post_id]).await;
+
+    // Tokens shorter than the minimum length are not indexed
+    let short_key_parts = [&CONTENT_GLOBAL_POST_TIMELINE[..], &["ok"]].concat();
+    assert_not_indexed::<PostsByContentSearch>(&short_key_parts, &[&user_id, &post_id]).await;
+
+    let results =
+        PostsByContentSearch::get_by_terms("ZYXWVUTSRQ search", None, Pagination::default())
+            .await
+            .unwrap()
+            .expect("The post should be found by its content");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].post_key, post_key);
+
+    // Delete the post using the event handler
+    test.cleanup_post(&user_kp, &post_path).await?;
+
+    assert_not_indexed::<PostsByContentSearch>(&token_key_parts, &[&user_id, &post_id]).await;
+
+    let results = PostsByContentSearch::get_by_terms("zyxwvutsrq", None, Pagination::default())
+        .await
+        .unwrap()
+        .unwrap_or_default();
+    assert!(
+        results.iter().all(|result| result.post_key != post_key),
+        "Deleted post should not be found by its content"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::models::post::{PostView, POST_DELETED_CONTENT};
+use nexus_common::DeletedRepostMode;
+use pubky::Keypair;
+use pubky_app_specs::{
+    post_uri_builder, PubkyAppPost, PubkyAppPostEmbed, PubkyAppPostKind, PubkyAppUser,
+};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_repost_of_deleted_post_view() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let author_kp = Keypair::random();
+    let author = PubkyAppUser {
+        bio: Some("test_repost_of_deleted_post_view".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:RepostOfDeletedView:Author".to_string(),
+        status: None,
+    };
+    let author_id = test.create_user(&author_kp, &author).await?;
+
+    let reposter_kp = Keypair::random();
+    let reposter = PubkyAppUser {
+        bio: Some("test_repost_of_deleted_post_view".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:RepostOfDeletedView:Reposter".to_string(),
+        status: None,
+    };
+    let reposter_id = test.create_user(&reposter_kp, &reposter).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:RepostOfDeletedView:Original".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, post_path) = test.create_post(&author_kp, &post).await?;
+
+    let repost = PubkyAppPost {
+        content: "".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: Some(PubkyAppPostEmbed {
+            kind: PubkyAppPostKind::Short,
+            uri: post_uri_builder(author_id.clone(), post_id.clone()),
+        }),
+        attachments: None,
+    };
+    let (repost_id, _repost_path) = test.create_post(&reposter_kp, &repost).await?;
+
+    let post_keys = [
+        (reposter_id.clone(), repost_id.clone()),
+        (author_id.clone(), post_id.clone()),
+    ];
+
+    // While the original exists, the repost is returned as is
+    let views = PostView::get_by_ids(&post_keys, None).await?;
+    assert!(!views[0].as_ref().unwrap().original_deleted);
+
+    // The original is kept as deleted, since it has a repost
+    test.cleanup_post(&author_kp, &post_path).await?;
+
+    // By default, the repost is flagged to render a tombstone for the original
+    let views = PostView::get_by_ids(&post_keys, None).await?;
+    let repost_view = views[0].as_ref().expect("The repost should be returned");
+    assert_eq!(repost_view.details.id, repost_id);
+    assert!(repost_view.original_deleted);
+    let original_view = views[1].as_ref().expect("The deleted original is kept");
+    assert_eq!(original_view.details.content, POST_DELETED_CONTENT);
+    assert!(!original_view.original_deleted);
+
+    // When hidden, the repost is left out
+    let mut views = vec![
+        PostView::get_by_id(&reposter_id, &repost_id, None, None, None).await?,
+        PostView::get_by_id(&author_id, &post_id, None, None, None).await?,
+    ];
+    PostView::apply_deleted_repost_mode(&mut views, DeletedRepostMode::Hide).await?;
+    assert_eq!(views.len(), 2);
+    assert!(views[0].is_none(), "The repost should be hidden");
+    assert!(views[1].is_some());
+
+    Ok(())
+}
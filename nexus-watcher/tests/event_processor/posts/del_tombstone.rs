@@ -0,0 +1,61 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::models::tombstone::Tombstone;
+use nexus_watcher::events::EventSettings;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+use std::sync::Arc;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_delete_post_and_user_record_tombstones() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+    test.event_processor_runner.settings = Arc::new(EventSettings {
+        record_tombstones: true,
+        ..Default::default()
+    });
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_delete_post_and_user_record_tombstones".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:DelTombstone:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:DelTombstone:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, post_path) = test.create_post(&user_kp, &post).await?;
+
+    // Indexed resources have no tombstone
+    assert!(Tombstone::get_post(&user_id, &post_id).await?.is_none());
+    assert!(Tombstone::get_user(&user_id).await?.is_none());
+
+    // CACHE_OP: an erased post leaves a tombstone
+    test.cleanup_post(&user_kp, &post_path).await?;
+    let tombstone = Tombstone::get_post(&user_id, &post_id)
+        .await?
+        .expect("The deleted post should leave a tombstone");
+    assert!(tombstone.deleted_at > 0);
+
+    // CACHE_OP: so does an erased user
+    test.cleanup_user(&user_kp).await?;
+    assert!(Tombstone::get_user(&user_id).await?.is_some());
+
+    // A user indexed again is no longer gone
+    test.create_user(&user_kp, &user).await?;
+    assert!(Tombstone::get_user(&user_id).await?.is_none());
+
+    // Cleanup
+    test.cleanup_user(&user_kp).await?;
+    Tombstone::del_user(&user_id).await?;
+    Tombstone::del_post(&user_id, &post_id).await?;
+
+    Ok(())
+}
@@ -1,11 +1,14 @@
 use super::utils::check_member_post_replies;
 use crate::event_processor::users::utils::find_user_counts;
-use crate::event_processor::utils::watcher::WatcherTest;
+use crate::event_processor::utils::{index::assert_not_indexed, watcher::WatcherTest};
 use anyhow::Result;
 use nexus_common::{
     db::RedisOps,
     models::{
-        post::{PostCounts, PostDetails, PostRelationships, PostView},
+        post::{
+            PostCounts, PostDetails, PostRelationships, PostStream, PostView,
+            POST_PER_USER_KEY_PARTS, POST_TIMELINE_KEY_PARTS, POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+        },
         user::UserCounts,
     },
 };
@@ -93,33 +96,16 @@ async fn test_delete_post_without_relationships() -> Result<()> {
 
     // Assert the post does not belong to the global timeline
     // Sorted:Post:Global:Timeline
-    let post_timeline = check_member_global_timeline_user_post(&user_id, &post_id)
-        .await
-        .unwrap();
-    assert!(
-        post_timeline.is_none(),
-        "Post cannot exist in the global timeline, should be deleted"
-    );
+    assert_not_indexed::<PostStream>(&POST_TIMELINE_KEY_PARTS, &post_key).await;
 
     // Assert the post does not belong to the global popularity
     // Sorted:Post:Global:TotalEngagement
-    let post_engagement = check_member_total_engagement_user_posts(&post_key)
-        .await
-        .unwrap();
-    assert!(
-        post_engagement.is_none(),
-        "Post cannot exist in the global total engagement, should be deleted"
-    );
+    assert_not_indexed::<PostStream>(&POST_TOTAL_ENGAGEMENT_KEY_PARTS, &post_key).await;
 
     // Assert that post does not belong to the user
     // Posts:User:user_id
-    let user_post = check_member_user_post_timeline(&user_id, &post_id)
-        .await
-        .unwrap();
-    assert!(
-        user_post.is_none(),
-        "Post cannot be linked to the user because it should not exist"
-    );
+    let user_posts_key_parts = [&POST_PER_USER_KEY_PARTS[..], &[user_id.as_str()]].concat();
+    assert_not_indexed::<PostStream>(&user_posts_key_parts, &[&post_id]).await;
 
     Ok(())
 }
@@ -0,0 +1,76 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::models::post::PostsByEmbed;
+use nexus_watcher::events::EventSettings;
+use pubky::Keypair;
+use pubky_app_specs::{
+    post_uri_builder, PubkyAppPost, PubkyAppPostEmbed, PubkyAppPostKind, PubkyAppUser,
+};
+use std::sync::Arc;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_post_embed_index() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+    test.event_processor_runner.settings = Arc::new(EventSettings {
+        index_embeds: true,
+        ..Default::default()
+    });
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_homeserver_post_embed_index".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:EmbedIndex:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    let embedded_post = PubkyAppPost {
+        content: "Watcher:EmbedIndex:Embedded".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (embedded_id, embedded_path) = test.create_post(&user_kp, &embedded_post).await?;
+    let embedded_uri = post_uri_builder(user_id.clone(), embedded_id.clone());
+
+    let embedding_post = PubkyAppPost {
+        content: "Watcher:EmbedIndex:Embedding".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: Some(PubkyAppPostEmbed {
+            kind: PubkyAppPostKind::Short,
+            uri: embedded_uri.clone(),
+        }),
+        attachments: None,
+    };
+    let (embedding_id, embedding_path) = test.create_post(&user_kp, &embedding_post).await?;
+
+    // CACHE_OP: the embedding post is listed under the embedded URI
+    let posts = PostsByEmbed::get(&embedded_uri, None, None)
+        .await
+        .unwrap()
+        .expect("The embedded URI should be indexed");
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].post_key, format!("{user_id}:{embedding_id}"));
+
+    // A post that embeds nothing is not indexed
+    let embedding_uri = post_uri_builder(user_id.clone(), embedding_id.clone());
+    assert!(PostsByEmbed::get(&embedding_uri, None, None)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Once deleted, the post leaves the index
+    test.cleanup_post(&user_kp, &embedding_path).await?;
+    let posts = PostsByEmbed::get(&embedded_uri, None, None).await.unwrap();
+    assert!(posts.unwrap_or_default().is_empty());
+
+    // Cleanup
+    test.cleanup_post(&user_kp, &embedded_path).await?;
+    test.cleanup_user(&user_kp).await?;
+
+    Ok(())
+}
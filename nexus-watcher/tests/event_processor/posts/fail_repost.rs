@@ -66,11 +66,11 @@ async fn test_homeserver_post_repost_without_post_parent() -> Result<()> {
     // Simulate the event processor to handle the event.
     // If the event processor were activated, the test would not catch the missing dependency
     // error, and it would pass successfully
-    let moderation_ref = test.event_processor_runner.moderation.clone();
-    let sync_fail = retrieve_and_handle_event_line(&post_homeserver_uri, moderation_ref)
-        .await
-        .map_err(|e| error!("SYNC ERROR: {:?}", e))
-        .is_err();
+    let sync_fail =
+        retrieve_and_handle_event_line(&post_homeserver_uri, &test.event_processor_runner)
+            .await
+            .map_err(|e| error!("SYNC ERROR: {:?}", e))
+            .is_err();
 
     assert!(
         sync_fail,
@@ -1,11 +1,14 @@
 mod attachments;
+mod content_search;
 mod del_reply_notification;
 mod del_reply_parent_notification;
 
 mod del_bookmarked_notification;
 mod del_repost_notification;
 mod del_reposted_notification;
+mod del_reposted_view;
 mod del_tagged_notification;
+mod del_tombstone;
 mod del_with_attachments;
 mod del_with_relations;
 mod del_without_relations;
@@ -13,21 +16,27 @@ mod edit_bookmarked_notification;
 mod edit_reply_parent_notification;
 mod edit_reposted_notification;
 mod edit_tagged_notification;
+mod embed_index;
 mod engagement;
 mod fail_reply;
 mod fail_repost;
 mod fail_user;
 mod influencer;
 mod moderated;
+mod quote;
 mod raw;
 mod reply;
+mod reply_descendants;
 mod reply_engagement;
 mod reply_notification;
 mod reply_repost;
 mod repost;
 mod repost_notification;
 mod retry_all;
+mod retry_dead_letter;
 mod retry_post;
 mod retry_reply;
 mod retry_repost;
+mod thread_order;
+mod thread_tombstone;
 pub mod utils;
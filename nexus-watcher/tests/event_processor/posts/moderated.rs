@@ -1,13 +1,21 @@
 use crate::event_processor::{
+    homeserver::utils::create_external_test_homeserver,
     posts::utils::find_post_details,
-    utils::watcher::{HomeserverHashIdPath, WatcherTest},
+    utils::{
+        default_moderation_tests,
+        watcher::{HomeserverHashIdPath, WatcherTest},
+    },
 };
 use anyhow::Result;
 use chrono::Utc;
+use nexus_common::models::homeserver::Homeserver;
+use nexus_watcher::events::Moderation;
 use pubky::{recovery_file, Keypair};
 use pubky_app_specs::{
-    post_uri_builder, PubkyAppPost, PubkyAppPostKind, PubkyAppTag, PubkyAppUser,
+    post_uri_builder, PubkyAppPost, PubkyAppPostKind, PubkyAppTag, PubkyAppUser, PubkyId,
 };
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use tokio::fs;
 
 #[tokio_shared_rt::test(shared)]
@@ -63,3 +71,141 @@ async fn test_moderated_post_lifecycle() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_moderated_post_audit_log() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_moderated_post_audit_log".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:PostModerateAudit:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:PostModerateAudit:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, _post_path) = test.create_post(&user_kp, &post).await?;
+
+    let moderator_recovery_file = fs::read("./tests/event_processor/utils/moderator_key.pkarr")
+        .await
+        .unwrap();
+    let moderator_key =
+        recovery_file::decrypt_recovery_file(&moderator_recovery_file, "password").unwrap();
+    let moderator_id = test.create_user(&moderator_key, &user).await?;
+
+    let count_before = moderated_tag_count("label_to_moderate").await?;
+
+    let post_uri = post_uri_builder(user_id.clone(), post_id.clone());
+    let tag = PubkyAppTag {
+        uri: post_uri.clone(),
+        label: "label_to_moderate".to_string(),
+        created_at: Utc::now().timestamp_millis(),
+    };
+    let tag_path = tag.hs_path();
+    test.put(&moderator_key, &tag_path, tag).await?;
+
+    // The moderation is recorded in the audit log with the matching label
+    let actions = Moderation::recent_actions(0, 100).await?;
+    let action = actions
+        .iter()
+        .find(|action| action.uri == post_uri)
+        .expect("The moderation of the post should be recorded");
+    assert_eq!(action.tag, "label_to_moderate");
+    assert_eq!(action.moderator_id, moderator_id);
+
+    assert!(moderated_tag_count("label_to_moderate").await? > count_before);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_moderated_post_homeserver_override() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    // The author hosts their content on a homeserver other than the moderator's
+    let author_hs_pk = create_external_test_homeserver(&mut test).await?;
+    let author_hs_id = author_hs_pk.to_z32();
+    Homeserver::persist_if_unknown(PubkyId::try_from(author_hs_id.as_str()).unwrap()).await?;
+
+    let author_kp = Keypair::random();
+    test.register_user_in_hs(&author_kp, &author_hs_pk).await?;
+
+    // Index the author's content by processing the events of their homeserver
+    let main_hs_id = std::mem::replace(&mut test.homeserver_id, author_hs_id.clone());
+    let author = PubkyAppUser {
+        bio: Some("test_moderated_post_homeserver_override".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:PostModerateOverride:User".to_string(),
+        status: None,
+    };
+    let author_id = test.create_profile(&author_kp, &author).await?;
+    let post = PubkyAppPost {
+        content: "Watcher:PostModerateOverride:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (global_label_post_id, _) = test.create_post(&author_kp, &post).await?;
+    let (override_label_post_id, _) = test.create_post(&author_kp, &post).await?;
+    test.homeserver_id = main_hs_id;
+
+    // The author's homeserver moderates its own label instead of the global ones
+    let moderation = default_moderation_tests();
+    test.event_processor_runner.moderation = Arc::new(Moderation {
+        homeserver_tags: BTreeMap::from([(
+            author_hs_id,
+            vec!["label_to_moderate_on_hs".to_string()],
+        )]),
+        ..moderation
+    });
+
+    // The moderator tags both posts from the main homeserver
+    let moderator_recovery_file = fs::read("./tests/event_processor/utils/moderator_key.pkarr")
+        .await
+        .unwrap();
+    let moderator_key =
+        recovery_file::decrypt_recovery_file(&moderator_recovery_file, "password").unwrap();
+    test.create_user(&moderator_key, &author).await?;
+
+    for (post_id, label) in [
+        (&global_label_post_id, "label_to_moderate"),
+        (&override_label_post_id, "label_to_moderate_on_hs"),
+    ] {
+        let tag = PubkyAppTag {
+            uri: post_uri_builder(author_id.clone(), post_id.clone()),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        test.put(&moderator_key, &tag.hs_path(), tag).await?;
+    }
+
+    // Only the label moderated on the author's homeserver deletes the post
+    assert!(find_post_details(&author_id, &global_label_post_id)
+        .await
+        .is_ok());
+    assert!(find_post_details(&author_id, &override_label_post_id)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+async fn moderated_tag_count(label: &str) -> Result<u64> {
+    let counts = Moderation::tag_counts().await?;
+    Ok(counts
+        .into_iter()
+        .find(|(tag, _)| tag == label)
+        .map(|(_, count)| count)
+        .unwrap_or_default())
+}
@@ -0,0 +1,110 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::db::kv::SortOrder;
+use nexus_common::models::post::{
+    PostRelationships, PostStream, PostStreamParams, PostView, QuotedPost, StreamSource,
+};
+use nexus_common::types::{Pagination, StreamSorting};
+use pubky::Keypair;
+use pubky_app_specs::{
+    post_uri_builder, PubkyAppPost, PubkyAppPostEmbed, PubkyAppPostKind, PubkyAppUser,
+};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_post_quote() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_homeserver_post_quote".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:PostQuote:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:PostQuote:User:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, _post_path) = test.create_post(&user_kp, &post).await?;
+
+    let embed = PubkyAppPostEmbed {
+        kind: PubkyAppPostKind::Short,
+        uri: post_uri_builder(user_id.clone(), post_id.clone()),
+    };
+    let quote = PubkyAppPost {
+        content: "Watcher:PostQuote:User:Quote".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: Some(embed.clone()),
+        attachments: None,
+    };
+    let (quote_id, _quote_path) = test.create_post(&user_kp, &quote).await?;
+
+    // A repost without commentary does not quote the post
+    let repost = PubkyAppPost {
+        content: "".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: Some(embed),
+        attachments: None,
+    };
+    let (repost_id, _repost_path) = test.create_post(&user_kp, &repost).await?;
+
+    let expected = QuotedPost {
+        author_id: user_id.clone(),
+        post_id: post_id.clone(),
+    };
+
+    // The quote-post view references the quoted post
+    let quote_view = PostView::get_by_id(&user_id, &quote_id, None, None, None)
+        .await?
+        .expect("The quote-post should exist");
+    let quoted = quote_view
+        .relationships
+        .quoted
+        .expect("The quoted post should be set");
+    assert_eq!(quoted, expected);
+
+    // The same reference is derived from the graph
+    let from_graph = PostRelationships::get_from_graph(&user_id, &quote_id)
+        .await?
+        .expect("The quote-post should exist in the graph");
+    assert_eq!(from_graph.quoted, Some(expected));
+
+    // The quoted post can be resolved to render the embedded card
+    let quoted_view = PostView::get_by_id(&quoted.author_id, &quoted.post_id, None, None, None)
+        .await?
+        .expect("The quoted post should be resolvable");
+    assert_eq!(quoted_view.details.content, post.content);
+
+    let repost_view = PostView::get_by_id(&user_id, &repost_id, None, None, None)
+        .await?
+        .expect("The repost should exist");
+    assert!(repost_view.relationships.reposted.is_some());
+    assert!(repost_view.relationships.quoted.is_none());
+
+    // The stream can be filtered to the quote-posts
+    let source = StreamSource::Author {
+        author_id: user_id.clone(),
+        collapse_self_threads: false,
+    };
+    let post_keys = PostStream::get_post_keys(PostStreamParams {
+        source,
+        pagination: Pagination::default(),
+        order: SortOrder::Descending,
+        sorting: StreamSorting::Timeline,
+        quotes_only: true,
+        ..Default::default()
+    })
+    .await?
+    .expect("The author should have quote-posts");
+    assert_eq!(post_keys.post_keys, vec![format!("{user_id}:{quote_id}")]);
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use super::utils::find_post_counts;
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::db::RedisOps;
+use nexus_common::models::post::PostDescendants;
+use nexus_watcher::events::EventSettings;
+use pubky::Keypair;
+use pubky_app_specs::{post_uri_builder, PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+use std::sync::Arc;
+
+fn reply_to(content: &str, author_id: &str, post_id: &str) -> PubkyAppPost {
+    PubkyAppPost {
+        content: content.to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(post_uri_builder(author_id.to_string(), post_id.to_string())),
+        embed: None,
+        attachments: None,
+    }
+}
+
+async fn descendants(author_id: &str, post_id: &str) -> Result<Option<u32>> {
+    Ok(PostDescendants::get_by_id(author_id, post_id).await?)
+}
+
+/// Returns the descendant count of a post computed from the graph, bypassing the cache
+async fn graph_descendants(author_id: &str, post_id: &str) -> Result<Option<u32>> {
+    PostDescendants::remove_from_index_multiple_json(&[&[author_id, post_id]]).await?;
+    Ok(PostDescendants::get_by_id(author_id, post_id).await?)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_reply_descendants() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+    test.event_processor_runner.settings = Arc::new(EventSettings {
+        descendant_counts: true,
+        ..Default::default()
+    });
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_homeserver_reply_descendants".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:ReplyDescendants:User".to_string(),
+        status: None,
+    };
+    let author_id = test.create_user(&user_kp, &user).await?;
+
+    let root = PubkyAppPost {
+        content: "Watcher:ReplyDescendants:Root".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (root_id, _root_path) = test.create_post(&user_kp, &root).await?;
+    // Cache the count of the root, so that it is updated by the replies below
+    assert_eq!(descendants(&author_id, &root_id).await?, Some(0));
+
+    // root <- first <- second <- third, and first <- sibling
+    let first = reply_to("Watcher:ReplyDescendants:First", &author_id, &root_id);
+    let (first_id, first_path) = test.create_post(&user_kp, &first).await?;
+    assert_eq!(descendants(&author_id, &first_id).await?, Some(0));
+
+    let second = reply_to("Watcher:ReplyDescendants:Second", &author_id, &first_id);
+    let (second_id, _second_path) = test.create_post(&user_kp, &second).await?;
+    let third = reply_to("Watcher:ReplyDescendants:Third", &author_id, &second_id);
+    let (third_id, _third_path) = test.create_post(&user_kp, &third).await?;
+    let sibling = reply_to("Watcher:ReplyDescendants:Sibling", &author_id, &first_id);
+    let (_sibling_id, sibling_path) = test.create_post(&user_kp, &sibling).await?;
+
+    let root_counts = find_post_counts(&author_id, &root_id).await;
+    assert_eq!(root_counts.replies, 1);
+    assert_eq!(descendants(&author_id, &root_id).await?, Some(4));
+    assert_eq!(descendants(&author_id, &first_id).await?, Some(3));
+    assert_eq!(descendants(&author_id, &third_id).await?, Some(0));
+
+    // Deleting a reply in the middle of the tree decrements all its ancestors
+    test.cleanup_post(&user_kp, &sibling_path).await?;
+    assert_eq!(descendants(&author_id, &root_id).await?, Some(3));
+    assert_eq!(descendants(&author_id, &first_id).await?, Some(2));
+
+    // A deleted reply that has replies is kept in the thread as a tombstone, so it still counts
+    test.cleanup_post(&user_kp, &first_path).await?;
+    assert_eq!(descendants(&author_id, &root_id).await?, Some(3));
+
+    // The cached counts match the ones computed from the graph
+    assert_eq!(graph_descendants(&author_id, &root_id).await?, Some(3));
+    assert_eq!(graph_descendants(&author_id, &first_id).await?, Some(2));
+
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::models::event::EventType;
+use nexus_common::models::post::PostDetails;
+use nexus_common::CursorMode;
+use nexus_watcher::events::retry::event::RetryEvent;
+use nexus_watcher::events::retry::policy::RetryPolicy;
+use pubky::Keypair;
+use pubky_app_specs::{post_uri_builder, PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+
+fn post(content: &str) -> PubkyAppPost {
+    PubkyAppPost {
+        content: content.to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    }
+}
+
+/// Polls the homeserver once more, with the failed event still holding the strict cursor
+async fn poll_again(test: &mut WatcherTest) -> Result<()> {
+    test.ensure_event_processing = true;
+    test.ensure_event_processing_complete().await?;
+    test.ensure_event_processing = false;
+    Ok(())
+}
+
+/// The author has no profile, so every attempt to index the post fails with a missing dependency
+#[tokio_shared_rt::test(shared)]
+async fn test_post_moved_to_dead_letter_after_max_attempts() -> Result<()> {
+    let mut test = WatcherTest::setup().await?.remove_event_processing().await;
+    test.event_processor_runner.cursor_mode = CursorMode::Strict;
+    // Failed events are due again immediately, and dead-lettered after 3 failures
+    test.event_processor_runner.retry_policy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff_secs: 0,
+        max_backoff_secs: 0,
+    };
+
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    test.register_user(&user_kp).await?;
+
+    let (post_id, _) = test
+        .create_post(&user_kp, &post("Watcher:IndexFail:PostEvent:DeadLetter"))
+        .await?;
+
+    let index_key = format!(
+        "{}:{}",
+        EventType::Put,
+        RetryEvent::generate_index_key(&post_uri_builder(user_id, post_id)).unwrap()
+    );
+
+    // 1st failure
+    poll_again(&mut test).await?;
+    let event_state = RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event_state.retry_count, 0);
+
+    // 2nd failure, the same event is polled and retried once more
+    poll_again(&mut test).await?;
+    let event_state = RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event_state.retry_count, 1);
+    assert!(RetryEvent::check_dead_letter(&index_key)
+        .await
+        .unwrap()
+        .is_none());
+
+    // 3rd failure, the event is not retried anymore
+    poll_again(&mut test).await?;
+    assert!(RetryEvent::check_uri(&index_key).await.unwrap().is_none());
+    assert!(RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .is_none());
+
+    assert!(RetryEvent::check_dead_letter(&index_key)
+        .await
+        .unwrap()
+        .is_some());
+    let dead_letter_state = RetryEvent::get_from_dead_letter(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(dead_letter_state.retry_count, 2);
+    assert_eq!(dead_letter_state.attempts(), 3);
+
+    Ok(())
+}
+
+/// A failed event polled again from the strict cursor is not processed before its backoff elapsed
+#[tokio_shared_rt::test(shared)]
+async fn test_post_retry_respects_backoff() -> Result<()> {
+    let mut test = WatcherTest::setup().await?.remove_event_processing().await;
+    test.event_processor_runner.cursor_mode = CursorMode::Strict;
+    test.event_processor_runner.retry_policy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff_secs: 3_600,
+        max_backoff_secs: 3_600,
+    };
+
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    test.register_user(&user_kp).await?;
+
+    let (post_id, _) = test
+        .create_post(&user_kp, &post("Watcher:IndexFail:PostEvent:Backoff"))
+        .await?;
+
+    let index_key = format!(
+        "{}:{}",
+        EventType::Put,
+        RetryEvent::generate_index_key(&post_uri_builder(user_id, post_id)).unwrap()
+    );
+
+    poll_again(&mut test).await?;
+    let event_state = RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event_state.retry_count, 0);
+    assert!(event_state.next_retry_at > chrono::Utc::now().timestamp_millis());
+
+    // The event is skipped while in backoff, so its retry state is unchanged
+    poll_again(&mut test).await?;
+    let skipped_state = RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(skipped_state.retry_count, 0);
+    assert_eq!(skipped_state.next_retry_at, event_state.next_retry_at);
+
+    Ok(())
+}
+
+/// Once the cursor moved past a failed event, a new event of the same URI is processed even while
+/// the old retry entry is in backoff, and continues its attempts up to the dead-letter index
+#[tokio_shared_rt::test(shared)]
+async fn test_post_new_events_continue_attempts() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+    test.event_processor_runner.retry_policy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff_secs: 3_600,
+        max_backoff_secs: 3_600,
+    };
+
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    test.register_user(&user_kp).await?;
+
+    let post = post("Watcher:IndexFail:PostEvent:RetryQueue");
+    let (post_id, post_path) = test.create_post(&user_kp, &post).await?;
+
+    let index_key = format!(
+        "{}:{}",
+        EventType::Put,
+        RetryEvent::generate_index_key(&post_uri_builder(user_id.clone(), post_id.clone()))
+            .unwrap()
+    );
+    let event_state = RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event_state.retry_count, 0);
+
+    // A new failing event is processed despite the backoff, and counts as a further attempt
+    test.put(&user_kp, &post_path, &post).await?;
+    let retried_state = RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(retried_state.retry_count, 1);
+    assert!(retried_state.next_retry_at >= event_state.next_retry_at);
+
+    // The 3rd failure moves the URI to the dead-letter index
+    test.put(&user_kp, &post_path, &post).await?;
+    assert!(RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .is_none());
+    let dead_letter_state = RetryEvent::get_from_dead_letter(&index_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(dead_letter_state.attempts(), 3);
+
+    // Once the dependency exists, the next event is indexed
+    let user = PubkyAppUser {
+        bio: None,
+        image: None,
+        links: None,
+        name: "Watcher:IndexFail:PostEvent:RetryQueue".to_string(),
+        status: None,
+    };
+    test.create_profile(&user_kp, &user).await?;
+    test.put(&user_kp, &post_path, &post).await?;
+
+    assert!(RetryEvent::get_from_index(&index_key)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(PostDetails::get_by_id(&user_id, &post_id)
+        .await
+        .unwrap()
+        .is_some());
+
+    test.cleanup_post(&user_kp, &post_path).await?;
+    test.cleanup_user(&user_kp).await?;
+
+    Ok(())
+}
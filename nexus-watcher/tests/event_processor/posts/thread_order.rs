@@ -0,0 +1,102 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::models::post::PostStream;
+use pubky::Keypair;
+use pubky_app_specs::{post_uri_builder, PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_replies_in_thread_order() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_replies_in_thread_order".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:ThreadOrder:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    let root_post = PubkyAppPost {
+        content: "Watcher:ThreadOrder:Root".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (root_id, root_path) = test.create_post(&user_kp, &root_post).await?;
+
+    let first_reply = PubkyAppPost {
+        content: "Watcher:ThreadOrder:FirstReply".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(post_uri_builder(user_id.clone(), root_id.clone())),
+        embed: None,
+        attachments: None,
+    };
+    let (first_id, first_path) = test.create_post(&user_kp, &first_reply).await?;
+
+    let second_reply = PubkyAppPost {
+        content: "Watcher:ThreadOrder:SecondReply".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(post_uri_builder(user_id.clone(), root_id.clone())),
+        embed: None,
+        attachments: None,
+    };
+    let (second_id, second_path) = test.create_post(&user_kp, &second_reply).await?;
+
+    // Created last, but it belongs right after the reply it answers
+    let nested_reply = PubkyAppPost {
+        content: "Watcher:ThreadOrder:NestedReply".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(post_uri_builder(user_id.clone(), first_id.clone())),
+        embed: None,
+        attachments: None,
+    };
+    let (nested_id, nested_path) = test.create_post(&user_kp, &nested_reply).await?;
+
+    let key = |post_id: &str| format!("{user_id}:{post_id}");
+
+    let stream = PostStream::get_thread_reply_keys(&user_id, &root_id, 5, 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        stream.post_keys,
+        vec![key(&first_id), key(&nested_id), key(&second_id)]
+    );
+
+    // Only the direct replies are within a depth of 1
+    let stream = PostStream::get_thread_reply_keys(&user_id, &root_id, 1, 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(stream.post_keys, vec![key(&first_id), key(&second_id)]);
+
+    // Pages follow the thread order
+    let stream = PostStream::get_thread_reply_keys(&user_id, &root_id, 5, 1, 1)
+        .await
+        .unwrap();
+    assert_eq!(stream.post_keys, vec![key(&nested_id)]);
+
+    let stream = PostStream::get_thread_replies(&user_id, &root_id, None, 5, 0, 10)
+        .await
+        .unwrap()
+        .expect("The thread replies should exist");
+    let ids: Vec<&str> = stream
+        .0
+        .iter()
+        .map(|post| post.details.id.as_str())
+        .collect();
+    assert_eq!(
+        ids,
+        vec![first_id.as_str(), nested_id.as_str(), second_id.as_str()]
+    );
+
+    // Cleanup
+    test.cleanup_post(&user_kp, &nested_path).await?;
+    test.cleanup_post(&user_kp, &second_path).await?;
+    test.cleanup_post(&user_kp, &first_path).await?;
+    test.cleanup_post(&user_kp, &root_path).await?;
+    test.cleanup_user(&user_kp).await?;
+
+    Ok(())
+}
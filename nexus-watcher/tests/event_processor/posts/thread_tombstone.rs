@@ -0,0 +1,93 @@
+use crate::event_processor::utils::watcher::WatcherTest;
+use anyhow::Result;
+use nexus_common::models::post::{PostView, ThreadOptions};
+use pubky::Keypair;
+use pubky_app_specs::{post_uri_builder, PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_thread_with_deleted_parent() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let user_kp = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_thread_with_deleted_parent".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:ThreadTombstone:User".to_string(),
+        status: None,
+    };
+    let user_id = test.create_user(&user_kp, &user).await?;
+
+    let root_post = PubkyAppPost {
+        content: "Watcher:ThreadTombstone:Root".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (root_id, root_path) = test.create_post(&user_kp, &root_post).await?;
+
+    let middle_post = PubkyAppPost {
+        content: "Watcher:ThreadTombstone:Middle".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(post_uri_builder(user_id.clone(), root_id.clone())),
+        embed: None,
+        attachments: None,
+    };
+    let (middle_id, middle_path) = test.create_post(&user_kp, &middle_post).await?;
+
+    let leaf_post = PubkyAppPost {
+        content: "Watcher:ThreadTombstone:Leaf".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(post_uri_builder(user_id.clone(), middle_id.clone())),
+        embed: None,
+        attachments: None,
+    };
+    let (leaf_id, leaf_path) = test.create_post(&user_kp, &leaf_post).await?;
+
+    // The middle post has a reply, so its deletion leaves it as [DELETED]
+    test.cleanup_post(&user_kp, &middle_path).await?;
+
+    let options = ThreadOptions {
+        depth: 5,
+        limit_replies: 10,
+        include_tombstones: true,
+    };
+    let thread = PostView::get_thread(&user_id, &root_id, None, options)
+        .await
+        .unwrap()
+        .expect("The thread should exist");
+    assert!(!thread.deleted);
+    assert_eq!(thread.replies.len(), 1);
+
+    let tombstone = &thread.replies[0];
+    assert_eq!(tombstone.post_id, middle_id);
+    assert!(tombstone.deleted, "Deleted parent should be a tombstone");
+    assert!(tombstone.view.is_none());
+    assert_eq!(tombstone.replies.len(), 1);
+
+    // The reply of the deleted post is still part of the thread
+    let leaf = &tombstone.replies[0];
+    assert_eq!(leaf.post_id, leaf_id);
+    assert!(!leaf.deleted);
+    assert!(leaf.view.is_some());
+
+    // Without tombstones, the deleted post is dropped with its replies
+    let options = ThreadOptions {
+        include_tombstones: false,
+        ..options
+    };
+    let thread = PostView::get_thread(&user_id, &root_id, None, options)
+        .await
+        .unwrap()
+        .expect("The thread should exist");
+    assert!(thread.replies.is_empty());
+
+    // Cleanup
+    test.cleanup_post(&user_kp, &leaf_path).await?;
+    test.cleanup_post(&user_kp, &middle_path).await?;
+    test.cleanup_post(&user_kp, &root_path).await?;
+    test.cleanup_user(&user_kp).await?;
+
+    Ok(())
+}
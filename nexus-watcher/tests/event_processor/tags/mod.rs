@@ -1,13 +1,16 @@
 mod fail_index;
 mod multi_user;
+mod post_alias;
 mod post_del;
 mod post_del_notification;
 mod post_del_self_notification;
+mod post_max_labels;
 mod post_multi_user;
 mod post_notification;
 mod post_put;
 mod retry_post_tag;
 mod retry_user_tag;
+mod tagger_labels;
 mod user_del_notification;
 mod user_del_self_notification;
 mod user_notification;
@@ -0,0 +1,208 @@
+use super::utils::{check_member_post_tag_global_timeline, find_post_tag};
+use crate::event_processor::posts::utils::find_post_counts;
+use crate::event_processor::utils::watcher::{HomeserverHashIdPath, WatcherTest};
+use anyhow::Result;
+use chrono::Utc;
+use nexus_common::models::post::search::PostsByTagSearch;
+use nexus_common::models::tag::alias::TagAlias;
+use nexus_common::models::tag::search::TagSearch;
+use nexus_common::models::tag::stream::HotTags;
+use nexus_common::models::tag::traits::TagCollection;
+use nexus_common::models::tag::user::TagUser;
+use nexus_common::models::tag::TaggedType;
+use nexus_common::types::routes::HotTagsInputDTO;
+use nexus_common::types::{Pagination, Timeframe};
+use pubky::Keypair;
+use pubky_app_specs::post_uri_builder;
+use pubky_app_specs::{PubkyAppPost, PubkyAppTag, PubkyAppUser};
+
+/// Labels of the global hot tags of today
+async fn hot_tag_labels() -> Result<Vec<String>> {
+    // The cache may hold hot tags computed before the tags of the test
+    HotTags::clear_global_cache().await?;
+    let input = HotTagsInputDTO::new(Timeframe::Today, 100, 0, 20, Some(TaggedType::Post));
+    let hot_tags = HotTags::get_hot_tags(None, None, &input).await?;
+    Ok(hot_tags
+        .map(|tags| tags.iter().map(|tag| tag.label.clone()).collect())
+        .unwrap_or_default())
+}
+
+/// Keys of the posts found by a search of `label`
+async fn posts_by_tag(label: &str) -> Result<Vec<String>> {
+    let posts = PostsByTagSearch::get_by_label(label, None, Pagination::default()).await?;
+    Ok(posts
+        .map(|posts| posts.into_iter().map(|post| post.post_key).collect())
+        .unwrap_or_default())
+}
+
+async fn tag_search_labels(label: &str) -> Result<Vec<String>> {
+    let tags = TagSearch::get_by_label_exact(label, &Pagination::default()).await?;
+    Ok(tags
+        .map(|tags| {
+            tags.into_iter()
+                .map(|tag| {
+                    serde_json::to_value(tag)
+                        .unwrap()
+                        .as_str()
+                        .unwrap()
+                        .to_string()
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tag_post_alias() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let author_kp = Keypair::random();
+    let author = PubkyAppUser {
+        bio: Some("test_homeserver_tag_post_alias".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:TagAlias:User".to_string(),
+        status: None,
+    };
+    let author_id = test.create_user(&author_kp, &author).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:TagAlias:User:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, post_path) = test.create_post(&author_kp, &post).await?;
+    let post_key = format!("{author_id}:{post_id}");
+
+    TagAlias::put("js", "javascript").await?;
+
+    // Tag the post with the alias
+    let tag = PubkyAppTag {
+        uri: post_uri_builder(author_id.clone(), post_id.clone()),
+        label: "js".to_string(),
+        created_at: Utc::now().timestamp_millis(),
+    };
+    let tag_path = tag.hs_path();
+    test.put(&author_kp, &tag_path, tag).await?;
+
+    // The tag is indexed under the canonical label
+    assert!(find_post_tag(&author_id, &post_id, "javascript")
+        .await?
+        .is_some());
+    assert!(find_post_tag(&author_id, &post_id, "js").await?.is_none());
+
+    let hot_tags = hot_tag_labels().await?;
+    assert!(hot_tags.contains(&"javascript".to_string()));
+    assert!(!hot_tags.contains(&"js".to_string()));
+
+    // Searching either label finds the post
+    assert!(posts_by_tag("javascript").await?.contains(&post_key));
+    assert!(posts_by_tag("js").await?.contains(&post_key));
+    assert_eq!(tag_search_labels("js").await?, vec!["javascript"]);
+
+    // Deleting the tag cleans up the canonical label
+    test.del(&author_kp, &tag_path).await?;
+    let post_key_parts: [&str; 2] = [&author_id, &post_id];
+    let tag_timeline = check_member_post_tag_global_timeline(&post_key_parts, "javascript").await?;
+    assert!(tag_timeline.is_none());
+    assert!(!posts_by_tag("js").await?.contains(&post_key));
+
+    assert!(TagAlias::del("js").await?);
+    test.cleanup_post(&author_kp, &post_path).await?;
+    test.cleanup_user(&author_kp).await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tag_post_alias_fold() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let author_kp = Keypair::random();
+    let author = PubkyAppUser {
+        bio: Some("test_homeserver_tag_post_alias_fold".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:TagAliasFold:User".to_string(),
+        status: None,
+    };
+    let author_id = test.create_user(&author_kp, &author).await?;
+
+    let tagger_kp = Keypair::random();
+    let tagger = PubkyAppUser {
+        bio: Some("test_homeserver_tag_post_alias_fold".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:TagAliasFold:Tagger".to_string(),
+        status: None,
+    };
+    let tagger_id = test.create_user(&tagger_kp, &tagger).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:TagAliasFold:User:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, post_path) = test.create_post(&author_kp, &post).await?;
+    let post_key = format!("{author_id}:{post_id}");
+    let post_uri = post_uri_builder(author_id.clone(), post_id.clone());
+
+    // The author uses both labels, the tagger only the future alias
+    let mut tag_paths = Vec::new();
+    for (kp, label) in [
+        (&author_kp, "es6"),
+        (&author_kp, "ecmascript"),
+        (&tagger_kp, "es6"),
+    ] {
+        let tag = PubkyAppTag {
+            uri: post_uri.clone(),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_path = tag.hs_path();
+        test.put(kp, &tag_path, tag).await?;
+        tag_paths.push((kp, tag_path));
+    }
+    assert_eq!(find_post_counts(&author_id, &post_id).await.unique_tags, 2);
+
+    // Adding the alias folds the existing tags
+    TagAlias::put("es6", "ecmascript").await?;
+
+    assert!(find_post_tag(&author_id, &post_id, "es6").await?.is_none());
+    let folded = find_post_tag(&author_id, &post_id, "ecmascript")
+        .await?
+        .expect("The alias tags should be folded into the canonical label");
+    assert_eq!(folded.taggers_count, 2);
+    assert!(folded.taggers.contains(&tagger_id));
+
+    // The author duplicate tag is dropped
+    let post_counts = find_post_counts(&author_id, &post_id).await;
+    assert_eq!(post_counts.tags, 2);
+    assert_eq!(post_counts.unique_tags, 1);
+
+    // The labels applied by the taggers move to the canonical label
+    for user_id in [&author_id, &tagger_id] {
+        let labels = TagUser::get_tagger_labels(user_id, None, None).await?;
+        assert_eq!(labels, vec!["ecmascript"]);
+    }
+
+    let hot_tags = hot_tag_labels().await?;
+    assert!(hot_tags.contains(&"ecmascript".to_string()));
+    assert!(!hot_tags.contains(&"es6".to_string()));
+    assert!(posts_by_tag("es6").await?.contains(&post_key));
+    assert_eq!(tag_search_labels("es6").await?, vec!["ecmascript"]);
+
+    assert!(TagAlias::del("es6").await?);
+    for (kp, tag_path) in tag_paths {
+        test.del(kp, &tag_path).await?;
+    }
+    test.cleanup_post(&author_kp, &post_path).await?;
+    test.cleanup_user(&author_kp).await?;
+    test.cleanup_user(&tagger_kp).await?;
+
+    Ok(())
+}
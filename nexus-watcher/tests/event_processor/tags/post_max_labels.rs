@@ -0,0 +1,70 @@
+use crate::event_processor::utils::watcher::{HomeserverHashIdPath, WatcherTest};
+use anyhow::Result;
+use chrono::Utc;
+use nexus_common::models::tag::post::TagPost;
+use nexus_common::models::tag::traits::TagCollection;
+use pubky::Keypair;
+use pubky_app_specs::post_uri_builder;
+use pubky_app_specs::{PubkyAppPost, PubkyAppTag, PubkyAppUser};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tag_post_max_labels() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let tagger_kp = Keypair::random();
+    let tagger = PubkyAppUser {
+        bio: Some("test_homeserver_tag_post_max_labels".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:MaxLabels:User".to_string(),
+        status: None,
+    };
+    let tagger_id = test.create_user(&tagger_kp, &tagger).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:MaxLabels:User:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, post_path) = test.create_post(&tagger_kp, &post).await?;
+
+    let mut tag_paths = Vec::new();
+    for label in ["cap-a", "cap-b"] {
+        let tag = PubkyAppTag {
+            uri: post_uri_builder(tagger_id.clone(), post_id.clone()),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_path = tag.hs_path();
+        test.put(&tagger_kp, &tag_path, tag).await?;
+        tag_paths.push(tag_path);
+    }
+
+    let accepts = |label: &'static str, max_tags: usize| {
+        let (tagger_id, post_id) = (tagger_id.clone(), post_id.clone());
+        async move {
+            TagPost::accepts_label(&tagger_id, Some(&post_id), label, max_tags)
+                .await
+                .unwrap()
+        }
+    };
+
+    // A new label is rejected once the post has reached the cap
+    assert!(!accepts("cap-c", 2).await);
+    // Existing labels keep accepting more taggers
+    assert!(accepts("cap-a", 2).await);
+    assert!(accepts("cap-c", 3).await);
+    // 0 disables the cap
+    assert!(accepts("cap-c", 0).await);
+
+    // A label without taggers left does not count towards the cap
+    test.del(&tagger_kp, &tag_paths[1]).await?;
+    assert!(accepts("cap-c", 2).await);
+
+    test.cleanup_post(&tagger_kp, &post_path).await?;
+    test.cleanup_user(&tagger_kp).await?;
+
+    Ok(())
+}
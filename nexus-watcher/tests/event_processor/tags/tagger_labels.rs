@@ -0,0 +1,96 @@
+use crate::event_processor::utils::watcher::{HomeserverHashIdPath, WatcherTest};
+use anyhow::Result;
+use chrono::Utc;
+use nexus_common::db::kv::ScoreAction;
+use nexus_common::models::tag::post::TagPost;
+use nexus_common::models::tag::traits::TagCollection;
+use nexus_common::models::tag::user::TagUser;
+use pubky::Keypair;
+use pubky_app_specs::{post_uri_builder, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tagger_labels() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let author_kp = Keypair::random();
+    let author = PubkyAppUser {
+        bio: Some("test_homeserver_tagger_labels".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:TaggerLabels:Author".to_string(),
+        status: None,
+    };
+    let author_id = test.create_user(&author_kp, &author).await?;
+
+    let tagger_kp = Keypair::random();
+    let tagger = PubkyAppUser {
+        bio: Some("test_homeserver_tagger_labels".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:TaggerLabels:Tagger".to_string(),
+        status: None,
+    };
+    let tagger_id = test.create_user(&tagger_kp, &tagger).await?;
+
+    let post = PubkyAppPost {
+        content: "Watcher:TaggerLabels:Author:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let (post_id, post_path) = test.create_post(&author_kp, &post).await?;
+
+    // The tagger uses "rust" on the post and the author, and "dev" on the author only
+    let post_uri = post_uri_builder(author_id.clone(), post_id.clone());
+    let user_uri = format!("pubky://{author_id}/pub/pubky.app/profile.json");
+    let mut tag_paths = Vec::new();
+    for (uri, label) in [(&post_uri, "rust"), (&user_uri, "rust"), (&user_uri, "dev")] {
+        let tag = PubkyAppTag {
+            uri: uri.clone(),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_path = tag.hs_path();
+        test.put(&tagger_kp, &tag_path, tag).await?;
+        tag_paths.push(tag_path);
+    }
+
+    // The labels are shared by posts and users, the most used first
+    let labels = TagPost::get_tagger_labels(&tagger_id, None, None).await?;
+    assert_eq!(labels, vec!["rust", "dev"]);
+    let labels = TagUser::get_tagger_labels(&tagger_id, Some(1), Some(1)).await?;
+    assert_eq!(labels, vec!["dev"]);
+    // The author has not tagged anything
+    assert!(TagUser::get_tagger_labels(&author_id, None, None)
+        .await?
+        .is_empty());
+
+    // Labels of the tags indexed before their scores were kept are counted from the graph
+    TagUser::update_tagger_label_score(&tagger_id, "rust", ScoreAction::Decrement(2.0)).await?;
+    TagUser::update_tagger_label_score(&tagger_id, "dev", ScoreAction::Decrement(1.0)).await?;
+    assert!(TagUser::get_tagger_labels(&tagger_id, None, None)
+        .await?
+        .is_empty());
+    TagUser::reindex_tagger_labels(&tagger_id).await?;
+    let labels = TagPost::get_tagger_labels(&tagger_id, None, None).await?;
+    assert_eq!(labels, vec!["rust", "dev"]);
+
+    // A label is left out once all its tags are deleted
+    test.del(&tagger_kp, &tag_paths[2]).await?;
+    let labels = TagUser::get_tagger_labels(&tagger_id, None, None).await?;
+    assert_eq!(labels, vec!["rust"]);
+
+    for tag_path in &tag_paths[..2] {
+        test.del(&tagger_kp, tag_path).await?;
+    }
+    assert!(TagUser::get_tagger_labels(&tagger_id, None, None)
+        .await?
+        .is_empty());
+
+    test.cleanup_post(&author_kp, &post_path).await?;
+    test.cleanup_user(&author_kp).await?;
+    test.cleanup_user(&tagger_kp).await?;
+
+    Ok(())
+}
@@ -2,14 +2,11 @@ use crate::{
     event_processor::users::utils::{
         check_member_most_followed, check_member_user_influencer, find_user_details,
     },
-    event_processor::utils::watcher::WatcherTest,
+    event_processor::utils::{index::assert_indexed, watcher::WatcherTest},
 };
 use anyhow::Result;
 use nexus_common::models::event::Event;
-use nexus_common::{
-    db::RedisOps,
-    models::user::{UserCounts, UserSearch, USER_NAME_KEY_PARTS},
-};
+use nexus_common::models::user::{UserCounts, UserSearch, USER_NAME_KEY_PARTS};
 use pubky::Keypair;
 use pubky_app_specs::{file_uri_builder, PubkyAppUser, PubkyAppUserLink};
 
@@ -74,16 +71,10 @@ async fn test_homeserver_user_put_event() -> Result<()> {
     assert_eq!(user_counts.posts, 0);
 
     // Sorted:Users:Name
-    let is_member = UserSearch::check_sorted_set_member(
-        None,
-        &USER_NAME_KEY_PARTS,
-        &[&user.name.to_lowercase(), &user_id],
-    )
-    .await
-    .unwrap();
-
-    assert!(is_member.is_some());
-    assert_eq!(is_member.unwrap(), 0);
+    let score =
+        assert_indexed::<UserSearch>(&USER_NAME_KEY_PARTS, &[&user.name.to_lowercase(), &user_id])
+            .await;
+    assert_eq!(score, 0);
 
     // influencers score: Sorted:Users:Influencers
     let influencer_score = check_member_user_influencer(&user_id).await.unwrap();
@@ -0,0 +1,34 @@
+use nexus_common::db::kv::key::build_key;
+use nexus_common::db::RedisOps;
+
+/// Asserts that `member` is part of the sorted set of `T` under `key_parts`, returning its score.
+///
+/// `member` is given in parts, like the `[author_id, post_id]` members of the post streams.
+pub async fn assert_indexed<T: RedisOps>(key_parts: &[&str], member: &[&str]) -> isize {
+    let score = T::check_sorted_set_member(None, key_parts, member)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to read sorted set {}: {e}", build_key(key_parts)));
+
+    score.unwrap_or_else(|| {
+        panic!(
+            "Expected {} to be indexed in sorted set {}",
+            member.join(":"),
+            build_key(key_parts)
+        )
+    })
+}
+
+/// Asserts that `member` is not part of the sorted set of `T` under `key_parts`
+pub async fn assert_not_indexed<T: RedisOps>(key_parts: &[&str], member: &[&str]) {
+    let score = T::check_sorted_set_member(None, key_parts, member)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to read sorted set {}: {e}", build_key(key_parts)));
+
+    if let Some(score) = score {
+        panic!(
+            "Expected {} not to be indexed in sorted set {}, found it with score {score}",
+            member.join(":"),
+            build_key(key_parts)
+        );
+    }
+}
@@ -9,11 +9,12 @@ use nexus_common::models::event::EventProcessorError;
 use nexus_common::models::file::FileDetails;
 use nexus_common::models::homeserver::Homeserver;
 use nexus_common::models::traits::Collection;
-use nexus_common::{StackConfig, StackManager};
+use nexus_common::{CursorMode, StackConfig, StackManager};
 use nexus_watcher::events::retry::event::RetryEvent;
-use nexus_watcher::events::{handle, Moderation};
-use nexus_watcher::service::EventProcessorRunner;
+use nexus_watcher::events::retry::policy::RetryPolicy;
+use nexus_watcher::events::{handle, EventSettings};
 use nexus_watcher::service::TEventProcessorRunner;
+use nexus_watcher::service::{EventMetrics, EventProcessorRunner};
 use pubky::Keypair;
 use pubky::PublicKey;
 use pubky::ResourcePath;
@@ -24,8 +25,9 @@ use pubky_app_specs::{
     PubkyAppFile, PubkyAppFollow, PubkyAppPost, PubkyAppUser, PubkyId,
 };
 use pubky_testnet::Testnet;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tracing::debug;
 
@@ -86,8 +88,15 @@ impl WatcherTest {
             monitored_homeservers_limit: 100,
             files_path: get_files_dir_test_pathbuf(),
             moderation,
+            settings: Arc::new(EventSettings::default()),
             shutdown_rx,
             default_homeserver,
+            log_run_durations: false,
+            retry_policy: RetryPolicy::default(),
+            cursor_mode: CursorMode::default(),
+            homeserver_overrides: BTreeMap::new(),
+            last_polls: Mutex::new(HashMap::new()),
+            metrics: EventMetrics::default(),
         }
     }
 
@@ -340,15 +349,16 @@ impl WatcherTest {
 /// # Arguments
 /// * `event_line` - A string slice that represents the URI of the event to be retrieved
 ///   from the homeserver. It contains the event type and the homeserver uri
+/// * `runner` - The runner whose moderation and settings the event is handled with
 ///
 /// # Errors
 /// Throws an error if event parsing fails
 pub async fn retrieve_and_handle_event_line(
     event_line: &str,
-    moderation: Arc<Moderation>,
+    runner: &EventProcessorRunner,
 ) -> Result<(), EventProcessorError> {
     match Event::parse_event(event_line, get_files_dir_pathbuf())? {
-        Some(event) => handle(&event, moderation).await,
+        Some(event) => handle(&event, runner.moderation.clone(), runner.settings.clone()).await,
         None => Ok(()),
     }
 }
@@ -5,7 +5,7 @@ use crate::service::utils::{
 use anyhow::Result;
 use nexus_watcher::service::backoff::HomeserverBackoff;
 use nexus_watcher::service::TEventProcessorRunner;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[tokio_shared_rt::test(shared)]
 async fn test_multiple_homeserver_event_processing() -> Result<()> {
@@ -209,3 +209,98 @@ async fn test_multi_hs_event_processing_with_panic() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_multi_hs_event_processing_default_runs_concurrently() -> Result<()> {
+    // Initialize the test
+    let mut event_processor_list = setup().await?;
+    let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+    // The first homeserver is the default one. Every homeserver takes 2s to process
+    for _ in 0..3 {
+        create_random_homeservers_and_persist(
+            &mut event_processor_list,
+            Some(Duration::from_secs(2)),
+            MockEventProcessorResult::Success,
+            None,
+            shutdown_rx.clone(),
+        )
+        .await;
+    }
+
+    let runner = MockEventProcessorRunner::new(event_processor_list, 3, shutdown_rx);
+    let default_hs = runner.default_homeserver().to_string();
+
+    let stats = runner
+        .run_all_concurrently(
+            &mut HomeserverBackoff::default(),
+            &mut HomeserverBackoff::default(),
+        )
+        .await
+        .unwrap()
+        .0;
+    assert_eq!(stats.count_ok(), 3);
+
+    // All homeservers run alongside each other, so the full run is not longer than a single one
+    assert!(stats.total_duration >= Duration::from_secs(2));
+    assert!(stats.total_duration < Duration::from_secs(4));
+    let default_stats = stats.stats.iter().find(|s| s.hs_id == default_hs).unwrap();
+    assert!(default_stats.duration < Duration::from_secs(3));
+
+    // Only the default homeserver is processed by run_default
+    let stats = runner
+        .run_default(&mut HomeserverBackoff::default())
+        .await
+        .unwrap()
+        .0;
+    assert_eq!(stats.stats.len(), 1);
+    assert_eq!(stats.stats[0].hs_id, default_hs);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_multi_hs_event_processing_bounded_concurrency() -> Result<()> {
+    // Initialize the test
+    let mut event_processor_list = setup().await?;
+    let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+    // Create 4 random homeservers taking 1s to process, one of them failing
+    for index in 0..4 {
+        let processor_status = match index {
+            0 => MockEventProcessorResult::Error("PubkyClient: timeout from HS".into()),
+            _ => MockEventProcessorResult::Success,
+        };
+        create_random_homeservers_and_persist(
+            &mut event_processor_list,
+            Some(Duration::from_secs(1)),
+            processor_status,
+            None,
+            shutdown_rx.clone(),
+        )
+        .await;
+    }
+    let hs_ids: Vec<String> = event_processor_list
+        .iter()
+        .map(|p| p.homeserver_id.to_string())
+        .collect();
+
+    // At most 2 processors run at the same time
+    let runner = MockEventProcessorRunner::new(event_processor_list, 2, shutdown_rx);
+    let t0 = Instant::now();
+    let stats = runner
+        .run_homeservers(hs_ids, &mut HomeserverBackoff::default())
+        .await;
+    let elapsed = t0.elapsed();
+
+    // The failing homeserver doesn't prevent the other ones from running
+    assert_eq!(stats.stats.len(), 4);
+    assert_eq!(stats.count_ok(), 3);
+    assert_eq!(stats.count_error(), 1);
+
+    // Two rounds of 2 concurrent processors (~2s), instead of 4 sequential runs (~4s)
+    assert!(elapsed >= Duration::from_secs(2));
+    assert!(elapsed < Duration::from_secs(3));
+
+    Ok(())
+}
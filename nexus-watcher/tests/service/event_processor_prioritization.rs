@@ -4,11 +4,15 @@ use crate::service::utils::{create_mock_event_processors, setup, MockEventProces
 use anyhow::Result;
 use nexus_common::models::homeserver::Homeserver;
 use nexus_common::types::DynError;
-use nexus_watcher::service::EventProcessorRunner;
+use nexus_common::CursorMode;
+use nexus_watcher::events::retry::policy::RetryPolicy;
+use nexus_watcher::events::EventSettings;
 use nexus_watcher::service::TEventProcessorRunner;
+use nexus_watcher::service::{EventMetrics, EventProcessorRunner};
 use pubky_app_specs::PubkyId;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[tokio_shared_rt::test(shared)]
 async fn test_event_processor_runner_default_homeserver_prioritization() -> Result<(), DynError> {
@@ -22,6 +26,13 @@ async fn test_event_processor_runner_default_homeserver_prioritization() -> Resu
         monitored_homeservers_limit: HS_IDS.len(),
         files_path: PathBuf::from("/tmp/nexus-watcher-test"),
         moderation: Arc::new(default_moderation_tests()),
+        settings: Arc::new(EventSettings::default()),
+        log_run_durations: false,
+        retry_policy: RetryPolicy::default(),
+        cursor_mode: CursorMode::default(),
+        homeserver_overrides: BTreeMap::new(),
+        last_polls: Mutex::new(HashMap::new()),
+        metrics: EventMetrics::default(),
     };
 
     // Persist the homeservers
@@ -0,0 +1,101 @@
+use crate::service::utils::setup;
+use anyhow::Result;
+use nexus_common::models::homeserver::Homeserver;
+use nexus_common::{HomeserverOverride, WatcherConfig};
+use nexus_watcher::service::{
+    EventProcessorRunner, TEventProcessor, TEventProcessorRunner, PROCESSING_TIMEOUT_SECS,
+};
+use pubky::Keypair;
+use pubky_app_specs::PubkyId;
+use std::time::Duration;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_override() -> Result<()> {
+    // Initialize the test
+    setup().await?;
+
+    // Persist a homeserver with an override and one without
+    let overridden_hs_id = Keypair::random().public_key().to_z32();
+    let other_hs_id = Keypair::random().public_key().to_z32();
+    for hs_id in [&overridden_hs_id, &other_hs_id] {
+        let hs = Homeserver::new(PubkyId::try_from(hs_id.as_str()).unwrap());
+        hs.put_to_graph().await.unwrap();
+    }
+
+    let mut config = WatcherConfig {
+        events_limit: 100,
+        ..Default::default()
+    };
+    config.homeserver_overrides.insert(
+        overridden_hs_id.clone(),
+        HomeserverOverride {
+            events_limit: Some(5),
+            poll_interval: Some(3_600_000),
+            timeout_secs: Some(2 * PROCESSING_TIMEOUT_SECS),
+            moderated_tags: None,
+        },
+    );
+    let runner = EventProcessorRunner::from_config(&config, tokio::sync::watch::channel(false).1);
+
+    // Not polled yet, so both homeservers are part of the run
+    let hs_ids = runner.homeservers_by_priority().await.unwrap();
+    assert!(hs_ids.contains(&overridden_hs_id));
+    assert!(hs_ids.contains(&other_hs_id));
+
+    // The override replaces the global events limit
+    let event_processor = runner
+        .build_event_processor(&overridden_hs_id)
+        .await
+        .unwrap();
+    assert_eq!(event_processor.limit, 5);
+    // The override replaces the global processing timeout
+    assert_eq!(
+        event_processor.custom_timeout(),
+        Some(Duration::from_secs(2 * PROCESSING_TIMEOUT_SECS))
+    );
+    let event_processor = runner.build_event_processor(&other_hs_id).await.unwrap();
+    assert_eq!(event_processor.limit, 100);
+    assert_eq!(event_processor.custom_timeout(), None);
+
+    // Polled less than an hour ago, so the overridden homeserver is skipped until then
+    let hs_ids = runner.homeservers_by_priority().await.unwrap();
+    assert!(!hs_ids.contains(&overridden_hs_id));
+    assert!(hs_ids.contains(&other_hs_id));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_moderation_override_config() -> Result<()> {
+    let mut config = WatcherConfig {
+        moderated_tags: vec!["global_label".to_string()],
+        ..Default::default()
+    };
+    let overridden_hs_id = Keypair::random().public_key().to_z32();
+    let other_hs_id = Keypair::random().public_key().to_z32();
+    config.homeserver_overrides.insert(
+        overridden_hs_id.clone(),
+        HomeserverOverride {
+            moderated_tags: Some(vec!["label_a".to_string()]),
+            ..Default::default()
+        },
+    );
+    // An override of the polling settings only keeps the global moderated tags
+    config.homeserver_overrides.insert(
+        other_hs_id.clone(),
+        HomeserverOverride {
+            events_limit: Some(5),
+            ..Default::default()
+        },
+    );
+    let runner = EventProcessorRunner::from_config(&config, tokio::sync::watch::channel(false).1);
+
+    assert_eq!(runner.moderation.tags, vec!["global_label".to_string()]);
+    assert_eq!(
+        runner.moderation.homeserver_tags.get(&overridden_hs_id),
+        Some(&vec!["label_a".to_string()])
+    );
+    assert!(!runner.moderation.homeserver_tags.contains_key(&other_hs_id));
+
+    Ok(())
+}
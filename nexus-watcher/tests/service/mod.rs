@@ -1,5 +1,6 @@
 pub mod event_processing_multiple_homeservers;
 pub mod event_processor_prioritization;
+pub mod homeserver_overrides;
 pub mod mock_event_processor;
 pub mod signal;
 pub mod utils;
@@ -44,9 +44,10 @@ async fn test_shutdown_signal() -> Result<()> {
         .unwrap()
         .0;
 
-    // We created 3 HSs, each with different execution durations (0s, 2s, 4s)
+    // We created 3 HSs, each with different execution durations (0s, 2s, 4s), all started at once
     // We triggered the shutdown signal 1s after start
-    assert_eq!(stats.count_ok(), 2); // 2 processors run without errors (of the 3, the 3rd one didn't even start)
+    assert_eq!(stats.count_ok(), 3); // the 1st processor finished, the 2 others stopped early on shutdown, without errors
+    assert!(stats.total_duration < Duration::from_secs(2));
     assert_eq!(stats.count_error(), 0); // no processors fail, because no erratic or unexpected behavior was triggered
     assert_eq!(stats.count_panic(), 0);
     assert_eq!(stats.count_timeout(), 0);
@@ -2,7 +2,7 @@ use crate::{run_setup, streams_benches::LIMIT_20};
 use criterion::Criterion;
 use nexus_common::{
     db::kv::SortOrder,
-    models::post::{PostStream, StreamSource},
+    models::post::{PostStream, PostStreamParams, StreamSource},
     types::StreamSorting,
 };
 use tokio::runtime::Runtime;
@@ -24,16 +24,18 @@ pub fn bench_stream_author_timeline(c: &mut Criterion) {
             // Define all the arguments of the post stream
             let source = StreamSource::Author {
                 author_id: AUTHOR_ID.to_string(),
+                collapse_self_threads: false,
             };
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -57,16 +59,18 @@ pub fn bench_stream_author_total_engagement(c: &mut Criterion) {
             // Define all the arguments of the post stream
             let source = StreamSource::Author {
                 author_id: AUTHOR_ID.to_string(),
+                collapse_self_threads: false,
             };
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::TotalEngagement,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::TotalEngagement,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -94,12 +98,13 @@ pub fn bench_stream_author_replies_timeline(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    ..Default::default()
+                },
                 None,
             )
             .await
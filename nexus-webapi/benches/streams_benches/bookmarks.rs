@@ -2,7 +2,7 @@ use crate::run_setup;
 use crate::streams_benches::LIMIT_20;
 use criterion::Criterion;
 use nexus_common::db::kv::SortOrder;
-use nexus_common::models::post::{PostStream, StreamSource};
+use nexus_common::models::post::{PostStream, PostStreamParams, StreamSource};
 use nexus_common::types::StreamSorting;
 use tokio::runtime::Runtime;
 
@@ -23,16 +23,18 @@ pub fn bench_stream_bookmarks_timeline(c: &mut Criterion) {
             // Define all the arguments of the post stream
             let source = StreamSource::Bookmarks {
                 observer_id: OBSERVER_ID.to_string(),
+                timeframe: None,
             };
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -55,16 +57,18 @@ pub fn bench_stream_bookmarks_total_engagement(c: &mut Criterion) {
             // Define all the arguments of the post stream
             let source = StreamSource::Bookmarks {
                 observer_id: OBSERVER_ID.to_string(),
+                timeframe: None,
             };
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::TotalEngagement,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::TotalEngagement,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -2,7 +2,7 @@ use crate::run_setup;
 use crate::streams_benches::LIMIT_20;
 use criterion::Criterion;
 use nexus_common::db::kv::SortOrder;
-use nexus_common::models::post::{PostStream, StreamSource};
+use nexus_common::models::post::{PostStream, PostStreamParams, StreamSource};
 use nexus_common::types::StreamSorting;
 use pubky_app_specs::PubkyAppPostKind;
 use tokio::runtime::Runtime;
@@ -24,13 +24,15 @@ pub fn bench_stream_post_kind_short(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    kind: Some(PubkyAppPostKind::Short),
+                    ..Default::default()
+                },
                 None,
-                None,
-                Some(PubkyAppPostKind::Short),
             )
             .await
             .unwrap();
@@ -55,13 +57,15 @@ pub fn bench_stream_post_kind_long(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    kind: Some(PubkyAppPostKind::Long),
+                    ..Default::default()
+                },
                 None,
-                Some(PubkyAppPostKind::Long),
             )
             .await
             .unwrap();
@@ -86,13 +90,15 @@ pub fn bench_stream_post_kind_image(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    kind: Some(PubkyAppPostKind::Image),
+                    ..Default::default()
+                },
                 None,
-                Some(PubkyAppPostKind::Image),
             )
             .await
             .unwrap();
@@ -117,13 +123,15 @@ pub fn bench_stream_post_kind_video(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    kind: Some(PubkyAppPostKind::Video),
+                    ..Default::default()
+                },
                 None,
-                None,
-                Some(PubkyAppPostKind::Video),
             )
             .await
             .unwrap();
@@ -148,13 +156,15 @@ pub fn bench_stream_post_kind_link(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    kind: Some(PubkyAppPostKind::Link),
+                    ..Default::default()
+                },
                 None,
-                Some(PubkyAppPostKind::Link),
             )
             .await
             .unwrap();
@@ -179,13 +189,15 @@ pub fn bench_stream_post_kind_file(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    kind: Some(PubkyAppPostKind::File),
+                    ..Default::default()
+                },
                 None,
-                Some(PubkyAppPostKind::File),
             )
             .await
             .unwrap();
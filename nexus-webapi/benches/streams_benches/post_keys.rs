@@ -1,7 +1,7 @@
 use crate::{run_setup, streams_benches::LIMIT_20};
 use criterion::Criterion;
 use nexus_common::db::kv::SortOrder;
-use nexus_common::models::post::{PostStream, StreamSource};
+use nexus_common::models::post::{PostStream, PostStreamParams, StreamSource};
 use nexus_common::types::StreamSorting;
 use tokio::runtime::Runtime;
 
@@ -19,14 +19,13 @@ pub fn bench_stream_post_keys_all_timeline(c: &mut Criterion) {
         b.to_async(&rt).iter(|| async {
             let source = StreamSource::All;
 
-            let post_key_stream = PostStream::get_post_keys(
+            let post_key_stream = PostStream::get_post_keys(PostStreamParams {
                 source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
-            )
+                pagination: LIMIT_20,
+                order: SortOrder::Descending,
+                sorting: StreamSorting::Timeline,
+                ..Default::default()
+            })
             .await
             .unwrap()
             .expect("expected post keys in benchmark");
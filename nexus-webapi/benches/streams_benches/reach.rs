@@ -2,7 +2,7 @@ use crate::run_setup;
 use crate::streams_benches::LIMIT_20;
 use criterion::Criterion;
 use nexus_common::db::kv::SortOrder;
-use nexus_common::models::post::{PostStream, StreamSource};
+use nexus_common::models::post::{PostStream, PostStreamParams, StreamSource};
 use nexus_common::types::StreamSorting;
 use tokio::runtime::Runtime;
 
@@ -27,12 +27,13 @@ pub fn bench_stream_followers_timeline(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -60,12 +61,13 @@ pub fn bench_stream_following_timeline(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -93,12 +95,13 @@ pub fn bench_stream_friends_timeline(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -126,12 +129,13 @@ pub fn bench_stream_followers_total_engagement(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::TotalEngagement,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::TotalEngagement,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -159,12 +163,13 @@ pub fn bench_stream_following_total_engagement(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::TotalEngagement,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::TotalEngagement,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -192,12 +197,13 @@ pub fn bench_stream_friends_total_engagement(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::TotalEngagement,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::TotalEngagement,
+                    ..Default::default()
+                },
                 None,
             )
             .await
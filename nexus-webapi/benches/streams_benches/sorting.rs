@@ -1,7 +1,7 @@
 use crate::{run_setup, streams_benches::LIMIT_20};
 use criterion::Criterion;
 use nexus_common::db::kv::SortOrder;
-use nexus_common::models::post::{PostStream, StreamSource};
+use nexus_common::models::post::{PostStream, PostStreamParams, StreamSource};
 use nexus_common::types::StreamSorting;
 use tokio::runtime::Runtime;
 
@@ -22,12 +22,13 @@ pub fn bench_stream_all_timeline(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -53,12 +54,13 @@ pub fn bench_stream_all_total_engagement(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::TotalEngagement,
-                None,
-                None,
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::TotalEngagement,
+                    ..Default::default()
+                },
                 None,
             )
             .await
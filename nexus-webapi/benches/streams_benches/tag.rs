@@ -2,7 +2,7 @@ use crate::run_setup;
 use crate::streams_benches::LIMIT_20;
 use criterion::Criterion;
 use nexus_common::db::kv::SortOrder;
-use nexus_common::models::post::{PostStream, StreamSource};
+use nexus_common::models::post::{PostStream, PostStreamParams, StreamSource};
 use nexus_common::types::StreamSorting;
 use tokio::runtime::Runtime;
 
@@ -25,12 +25,14 @@ pub fn bench_stream_tag_timeline(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::Timeline,
-                None,
-                Some(vec![TAG.to_string()]),
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::Timeline,
+                    tags: Some(vec![TAG.to_string()]),
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -56,12 +58,14 @@ pub fn bench_stream_tag_total_engagement(c: &mut Criterion) {
 
             // Run the benchmark
             let post_stream = PostStream::get_posts(
-                source,
-                LIMIT_20,
-                SortOrder::Descending,
-                StreamSorting::TotalEngagement,
-                None,
-                Some(vec![TAG.to_string()]),
+                PostStreamParams {
+                    source,
+                    pagination: LIMIT_20,
+                    order: SortOrder::Descending,
+                    sorting: StreamSorting::TotalEngagement,
+                    tags: Some(vec![TAG.to_string()]),
+                    ..Default::default()
+                },
                 None,
             )
             .await
@@ -176,7 +176,15 @@ impl NexusApi {
         enable_key_republisher: bool,
     ) -> Result<Self, DynError> {
         // Create all the routes of the API
-        let router = routes::routes(ctx.api_config.stack.files_path.clone());
+        let router = routes::routes(
+            ctx.api_config.stack.files_path.clone(),
+            ctx.api_config.read_only,
+            ctx.api_config.export_token.clone(),
+            &ctx.api_config.cors,
+            &ctx.api_config.rate_limit,
+            ctx.api_config.min_engagement,
+            ctx.api_config.max_stream_tags,
+        );
         debug!(?ctx.api_config, "Running NexusAPI with config");
 
         let (icann_http_handle, icann_http_socket) =
@@ -224,7 +232,7 @@ impl NexusApi {
         tokio::spawn(
             icann_server
                 .handle(handle.clone())
-                .serve(router.into_make_service())
+                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
                 .inspect_err(|e| error!("Nexus API ICANN DNS endpoint error: {e}")),
         );
 
@@ -254,7 +262,7 @@ impl NexusApi {
             tls_server
                 .acceptor(Self::create_pubky_tls_acceptor(&ctx.keypair))
                 .handle(pubky_handle.clone())
-                .serve(router.into_make_service())
+                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
                 .inspect_err(|e| error!("Nexus API pubky TLS endpoint error: {e}")),
         );
 
@@ -1,22 +1,31 @@
-use axum::http::header::InvalidHeaderValue;
+use axum::http::header::{InvalidHeaderValue, RETRY_AFTER};
 use axum::http::uri::InvalidUri;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use nexus_common::db::kv::RedisError;
+use nexus_common::media::processors::MediaProcessorError;
 use nexus_common::models::error::ModelError;
+use nexus_common::models::tombstone::Tombstone;
 use nexus_common::types::DynError;
 use std::io;
 use thiserror::Error;
-use tracing::error;
+use tracing::{error, warn};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// `Retry-After` sent with [Error::ServiceUnavailable], which has no more precise estimate
+pub const SERVICE_UNAVAILABLE_RETRY_AFTER_SECS: u64 = 30;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("User not found: {user_id}")]
     UserNotFound { user_id: String },
     #[error("Post not found: {author_id} {post_id}")]
     PostNotFound { author_id: String, post_id: String },
+    #[error("User deleted: {user_id}")]
+    UserGone { user_id: String },
+    #[error("Post deleted: {author_id} {post_id}")]
+    PostGone { author_id: String, post_id: String },
     #[error("Internal server error: {source}")]
     InternalServerError { source: DynError },
     #[error("Bookmarks not found: {user_id}")]
@@ -29,6 +38,14 @@ pub enum Error {
     FileNotFound {},
     #[error("Tag {tag_id} of {tagger_id} not found")]
     TagNotFound { tag_id: String, tagger_id: String },
+    #[error("Too many requests, retry after {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+    #[error("Service unavailable: {reason}")]
+    ServiceUnavailable { reason: String },
+    #[error("Payload too large: {message}")]
+    PayloadTooLarge { message: String },
+    #[error("Unauthorized: {message}")]
+    Unauthorized { message: String },
     // Add other custom errors here
 }
 
@@ -38,12 +55,45 @@ impl Error {
             message: message.to_string(),
         }
     }
+
+    /// Error for a post missing from the index: [Error::PostGone] if it was deleted and left a
+    /// [Tombstone], [Error::PostNotFound] otherwise
+    pub async fn missing_post(author_id: String, post_id: String) -> Self {
+        match Tombstone::get_post(&author_id, &post_id).await {
+            Ok(Some(_)) => Error::PostGone { author_id, post_id },
+            Ok(None) => Error::PostNotFound { author_id, post_id },
+            Err(e) => {
+                warn!("Failed to get the tombstone of post {author_id}/{post_id}: {e}");
+                Error::PostNotFound { author_id, post_id }
+            }
+        }
+    }
+
+    /// Error for a user missing from the index: [Error::UserGone] if it was deleted and left a
+    /// [Tombstone], [Error::UserNotFound] otherwise
+    pub async fn missing_user(user_id: String) -> Self {
+        match Tombstone::get_user(&user_id).await {
+            Ok(Some(_)) => Error::UserGone { user_id },
+            Ok(None) => Error::UserNotFound { user_id },
+            Err(e) => {
+                warn!("Failed to get the tombstone of user {user_id}: {e}");
+                Error::UserNotFound { user_id }
+            }
+        }
+    }
 }
 
 impl From<ModelError> for Error {
     fn from(source: ModelError) -> Self {
-        Error::InternalServerError {
-            source: source.into(),
+        match source {
+            ModelError::MediaProcessorError(
+                source @ MediaProcessorError::DimensionsExceeded { .. },
+            ) => Error::PayloadTooLarge {
+                message: source.to_string(),
+            },
+            source => Error::InternalServerError {
+                source: source.into(),
+            },
         }
     }
 }
@@ -78,6 +128,14 @@ impl From<InvalidUri> for Error {
     }
 }
 
+impl From<prometheus::Error> for Error {
+    fn from(source: prometheus::Error) -> Self {
+        Error::InternalServerError {
+            source: Box::new(source),
+        }
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(source: io::Error) -> Self {
         Error::InternalServerError {
@@ -92,12 +150,18 @@ impl IntoResponse for Error {
         let status_code = match self {
             Error::UserNotFound { .. } => StatusCode::NOT_FOUND,
             Error::PostNotFound { .. } => StatusCode::NOT_FOUND,
+            Error::UserGone { .. } => StatusCode::GONE,
+            Error::PostGone { .. } => StatusCode::GONE,
             Error::FileNotFound { .. } => StatusCode::NOT_FOUND,
             Error::BookmarksNotFound { .. } => StatusCode::NOT_FOUND,
             Error::TagsNotFound { .. } => StatusCode::NOT_FOUND,
             Error::InvalidInput { .. } => StatusCode::BAD_REQUEST,
             Error::InternalServerError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             Error::TagNotFound { .. } => StatusCode::NOT_FOUND,
+            Error::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Error::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            Error::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Error::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
             // Map other errors to appropriate status codes
         };
 
@@ -107,6 +171,10 @@ impl IntoResponse for Error {
             Error::PostNotFound { author_id, post_id } => {
                 error!("Post not found: {} {}", author_id, post_id)
             }
+            Error::UserGone { user_id } => warn!("User deleted: {}", user_id),
+            Error::PostGone { author_id, post_id } => {
+                warn!("Post deleted: {} {}", author_id, post_id)
+            }
             Error::FileNotFound {} => {
                 error!("File not found.")
             }
@@ -123,12 +191,102 @@ impl IntoResponse for Error {
                 error!("Tag not found: {} of {}", tag_id, tagger_id)
             }
             Error::InternalServerError { source } => error!("Internal server error: {:?}", source),
+            Error::RateLimited { retry_after_secs } => {
+                warn!("Too many requests, retry after {}s", retry_after_secs)
+            }
+            Error::ServiceUnavailable { reason } => warn!("Service unavailable: {}", reason),
+            Error::PayloadTooLarge { message } => warn!("Payload too large: {}", message),
+            Error::Unauthorized { message } => warn!("Unauthorized: {}", message),
         };
 
-        let body = serde_json::json!({
-            "error": self.to_string()
-        });
+        // Clients are told when to retry, along with a machine-readable code
+        let retry = match &self {
+            Error::RateLimited { retry_after_secs } => Some(("rate_limited", *retry_after_secs)),
+            Error::ServiceUnavailable { .. } => {
+                Some(("service_unavailable", SERVICE_UNAVAILABLE_RETRY_AFTER_SECS))
+            }
+            _ => None,
+        };
+
+        match retry {
+            Some((code, retry_after_secs)) => {
+                let body = serde_json::json!({
+                    "error": self.to_string(),
+                    "code": code,
+                    "retry_after_secs": retry_after_secs,
+                });
+                (
+                    status_code,
+                    [(RETRY_AFTER, retry_after_secs.to_string())],
+                    axum::Json(body),
+                )
+                    .into_response()
+            }
+            None => {
+                let body = serde_json::json!({
+                    "error": self.to_string()
+                });
+                (status_code, axum::Json(body)).into_response()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Returns the status, the `Retry-After` header and the JSON body of the response of an error
+    async fn into_parts(error: Error) -> (StatusCode, Option<String>, Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .map(|value| value.to_str().unwrap().to_string());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, retry_after, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn rate_limited_tells_when_to_retry() {
+        let error = Error::RateLimited {
+            retry_after_secs: 12,
+        };
+        let (status, retry_after, body) = into_parts(error).await;
+
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(retry_after.as_deref(), Some("12"));
+        assert_eq!(body["code"], "rate_limited");
+        assert_eq!(body["retry_after_secs"], 12);
+    }
+
+    #[tokio::test]
+    async fn service_unavailable_tells_when_to_retry() {
+        let error = Error::ServiceUnavailable {
+            reason: "read-only".to_string(),
+        };
+        let (status, retry_after, body) = into_parts(error).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        let expected_secs = SERVICE_UNAVAILABLE_RETRY_AFTER_SECS.to_string();
+        assert_eq!(retry_after, Some(expected_secs));
+        assert_eq!(body["code"], "service_unavailable");
+        assert_eq!(
+            body["retry_after_secs"],
+            SERVICE_UNAVAILABLE_RETRY_AFTER_SECS
+        );
+    }
+
+    #[tokio::test]
+    async fn other_errors_have_no_retry_hint() {
+        let (status, retry_after, body) = into_parts(Error::invalid_input("bad")).await;
 
-        (status_code, axum::Json(body)).into_response()
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(retry_after.is_none());
+        assert!(body.get("code").is_none());
     }
 }
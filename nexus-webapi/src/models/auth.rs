@@ -0,0 +1,176 @@
+use axum::body::{to_bytes, Bytes};
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use axum::http::Method;
+use chrono::Utc;
+use nexus_common::db::kv::nonce;
+use pubky::pkarr::{Keypair, PublicKey, Signature};
+use serde::de::DeserializeOwned;
+
+use crate::Error;
+
+/// Scheme of the `Authorization` header of a signed request: `PubkySig <timestamp>:<signature>`,
+/// with the timestamp in milliseconds and the hex encoded signature of [signed_message]
+pub const SIGNATURE_SCHEME: &str = "PubkySig";
+
+/// Largest difference between the timestamp of a signed request and the server clock, so that a
+/// captured request cannot be replayed later on. Within it, [SignedRequest::authorize] accepts each
+/// signature only once
+pub const MAX_SIGNATURE_SKEW_MS: i64 = 5 * 60 * 1000;
+
+/// Largest body read by [SignedJson]
+const MAX_SIGNED_BODY_BYTES: usize = 1024 * 1024;
+
+/// Message a user signs for a request: its method, path with query, timestamp and body
+pub fn signed_message(
+    method: &Method,
+    path_and_query: &str,
+    timestamp: i64,
+    body: &[u8],
+) -> Vec<u8> {
+    let mut message = format!("{method} {path_and_query}\n{timestamp}\n").into_bytes();
+    message.extend_from_slice(body);
+    message
+}
+
+/// Builds the `Authorization` header value of a request signed with `keypair` now
+pub fn authorization(
+    keypair: &Keypair,
+    method: &Method,
+    path_and_query: &str,
+    body: &[u8],
+) -> String {
+    let timestamp = Utc::now().timestamp_millis();
+    let signature = keypair.sign(&signed_message(method, path_and_query, timestamp, body));
+    format!(
+        "{SIGNATURE_SCHEME} {timestamp}:{}",
+        hex::encode(signature.to_bytes())
+    )
+}
+
+/// Signature of a request, checked against the user the request acts for with
+/// [SignedRequest::authorize].
+///
+/// Extracting it never fails: a missing or malformed signature is only reported once the request
+/// needs to be authorized, so that endpoints can also serve anonymous requests.
+pub struct SignedRequest {
+    proof: Result<RequestProof, &'static str>,
+}
+
+struct RequestProof {
+    timestamp: i64,
+    signature: Signature,
+    message: Vec<u8>,
+}
+
+impl SignedRequest {
+    fn from_parts(parts: &Parts, body: &[u8]) -> Self {
+        let proof = parse_authorization(parts).map(|(timestamp, signature)| {
+            let path_and_query = parts
+                .uri
+                .path_and_query()
+                .map(|path| path.as_str())
+                .unwrap_or_else(|| parts.uri.path());
+            RequestProof {
+                timestamp,
+                signature,
+                message: signed_message(&parts.method, path_and_query, timestamp, body),
+            }
+        });
+        Self { proof }
+    }
+
+    /// Checks the request was signed by the key of `user_id`, recently enough, and that its
+    /// signature was not used before, so that a captured request cannot be replayed
+    pub async fn authorize(&self, user_id: &str) -> crate::Result<()> {
+        let proof = self.verify(user_id)?;
+
+        // A signature is accepted until its timestamp is older than the skew, which is at most
+        // twice the skew from now, when the timestamp is ahead of the server clock
+        let nonce = format!("{user_id}:{}", hex::encode(proof.signature.to_bytes()));
+        if !nonce::record(&nonce, 2 * MAX_SIGNATURE_SKEW_MS as u64).await? {
+            return Err(unauthorized("The request signature was already used"));
+        }
+        Ok(())
+    }
+
+    /// Whether the request was signed by the key of `user_id`, see [Self::authorize]. The signature
+    /// is not recorded, so this only fits requests that change nothing
+    pub fn is_signed_by(&self, user_id: &str) -> bool {
+        self.verify(user_id).is_ok()
+    }
+
+    /// Checks the request was signed by the key of `user_id`, recently enough
+    fn verify(&self, user_id: &str) -> crate::Result<&RequestProof> {
+        let proof = self
+            .proof
+            .as_ref()
+            .map_err(|&message| unauthorized(message))?;
+
+        if (Utc::now().timestamp_millis() - proof.timestamp).abs() > MAX_SIGNATURE_SKEW_MS {
+            return Err(unauthorized("The request signature expired"));
+        }
+        let public_key = PublicKey::try_from(user_id)
+            .map_err(|_| unauthorized("The request is not made for a valid user ID"))?;
+        public_key
+            .verify(&proof.message, &proof.signature)
+            .map_err(|_| unauthorized("The request is not signed by the user"))?;
+        Ok(proof)
+    }
+}
+
+fn unauthorized(message: &str) -> Error {
+    Error::Unauthorized {
+        message: message.to_string(),
+    }
+}
+
+/// Reads the timestamp and signature of the `Authorization` header
+fn parse_authorization(parts: &Parts) -> Result<(i64, Signature), &'static str> {
+    let value = parts
+        .headers
+        .get(AUTHORIZATION)
+        .ok_or("The request is not signed")?
+        .to_str()
+        .map_err(|_| "Malformed request signature")?;
+    let (timestamp, signature) = value
+        .strip_prefix(SIGNATURE_SCHEME)
+        .and_then(|value| value.strip_prefix(' '))
+        .and_then(|value| value.split_once(':'))
+        .ok_or("Malformed request signature")?;
+
+    let timestamp = timestamp
+        .parse()
+        .map_err(|_| "Malformed request signature timestamp")?;
+    let signature: [u8; 64] = hex::decode(signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Malformed request signature")?;
+    Ok((timestamp, Signature::from_bytes(&signature)))
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for SignedRequest {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_parts(parts, &[]))
+    }
+}
+
+/// JSON body of a request along with its [SignedRequest], whose signature covers the body
+pub struct SignedJson<T>(pub T, pub SignedRequest);
+
+impl<T: DeserializeOwned, S: Send + Sync> FromRequest<S> for SignedJson<T> {
+    type Rejection = Error;
+
+    async fn from_request(request: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let (parts, body) = request.into_parts();
+        let body: Bytes = to_bytes(body, MAX_SIGNED_BODY_BYTES)
+            .await
+            .map_err(|e| Error::invalid_input(&format!("Invalid request body: {e}")))?;
+        let value = serde_json::from_slice(&body)
+            .map_err(|e| Error::invalid_input(&format!("Invalid JSON body: {e}")))?;
+        Ok(Self(value, SignedRequest::from_parts(&parts, &body)))
+    }
+}
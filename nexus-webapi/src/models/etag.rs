@@ -0,0 +1,79 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use axum::http::header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+use crate::Error;
+
+/// JSON response carrying a weak `ETag` of its body, answered with `304 Not Modified` and no body
+/// when the request's `If-None-Match` already holds that tag.
+///
+/// The tag hashes the whole serialized body, so it changes with any detail, count, tag or
+/// relationship of the resource, and a client is never told that a stale copy is fresh.
+pub struct ETagJson<T> {
+    value: T,
+    if_none_match: Option<HeaderValue>,
+}
+
+impl<T: Serialize> ETagJson<T> {
+    /// Wraps `value`, to be compared with the `If-None-Match` of the request `headers`
+    pub fn new(value: T, headers: &HeaderMap) -> Self {
+        Self {
+            value,
+            if_none_match: headers.get(IF_NONE_MATCH).cloned(),
+        }
+    }
+}
+
+/// Weak `ETag` of a serialized body
+fn weak_etag(body: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    format!("W/\"{:016x}\"", hasher.finish())
+}
+
+/// Whether an `If-None-Match` header matches `etag`, using the weak comparison of RFC 9110
+fn if_none_match_matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(if_none_match) = if_none_match.to_str() else {
+        return false;
+    };
+    let opaque_tag = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque_tag(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque_tag(candidate) == etag)
+}
+
+impl<T: Serialize> IntoResponse for ETagJson<T> {
+    fn into_response(self) -> Response {
+        let body = match serde_json::to_vec(&self.value) {
+            Ok(body) => body,
+            Err(e) => {
+                return Error::InternalServerError {
+                    source: Box::new(e),
+                }
+                .into_response()
+            }
+        };
+        let etag = weak_etag(&body);
+        // The tag is hex within quotes, always a valid header value
+        let etag_header = HeaderValue::from_str(&etag).expect("ETag should be a valid header");
+
+        match self.if_none_match {
+            Some(if_none_match) if if_none_match_matches(&if_none_match, &etag) => {
+                (StatusCode::NOT_MODIFIED, [(ETAG, etag_header)]).into_response()
+            }
+            _ => (
+                [
+                    (CONTENT_TYPE, HeaderValue::from_static("application/json")),
+                    (ETAG, etag_header),
+                ],
+                body,
+            )
+                .into_response(),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::future::Future;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use nexus_common::db::{ping_neo4j, ping_redis};
+use nexus_common::types::DynError;
+use serde::Serialize;
+use tokio::time::timeout;
+use tracing::warn;
+use utoipa::ToSchema;
+
+/// Time each dependency has to answer a readiness check, so that a hung database fails the
+/// probe instead of hanging it
+pub const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A dependency the API needs to serve requests
+#[derive(Serialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Dependency {
+    Redis,
+    Neo4j,
+}
+
+/// Outcome of a readiness check, listing the unreachable dependencies
+#[derive(Serialize, ToSchema, Debug, Default)]
+pub struct Readiness {
+    pub ready: bool,
+    pub failing: Vec<Dependency>,
+}
+
+impl Readiness {
+    /// Checks that Redis answers a `PING` and Neo4j a `RETURN 1`
+    pub async fn check() -> Self {
+        Self::from_checks(async { ping_redis().await.map_err(Into::into) }, async {
+            ping_neo4j().await.map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Runs the checks of each dependency concurrently, each bounded by [READINESS_TIMEOUT]
+    pub async fn from_checks<R, N>(redis: R, neo4j: N) -> Self
+    where
+        R: Future<Output = Result<(), DynError>>,
+        N: Future<Output = Result<(), DynError>>,
+    {
+        let (redis, neo4j) = tokio::join!(
+            timeout(READINESS_TIMEOUT, redis),
+            timeout(READINESS_TIMEOUT, neo4j)
+        );
+
+        let mut failing = Vec::new();
+        for (dependency, outcome) in [(Dependency::Redis, redis), (Dependency::Neo4j, neo4j)] {
+            match outcome {
+                Ok(Ok(())) => (),
+                Ok(Err(e)) => {
+                    warn!("Readiness check of {dependency:?} failed: {e}");
+                    failing.push(dependency);
+                }
+                Err(_) => {
+                    warn!("Readiness check of {dependency:?} timed out");
+                    failing.push(dependency);
+                }
+            }
+        }
+
+        Self {
+            ready: failing.is_empty(),
+            failing,
+        }
+    }
+}
+
+impl IntoResponse for Readiness {
+    fn into_response(self) -> Response {
+        let status_code = if self.ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        (status_code, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,43 @@
+use nexus_common::types::{variant_names, StreamReach, StreamSorting, Timeframe};
+use pubky_app_specs::PubkyAppPostKind;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// The string values accepted by the API for its enum parameters
+#[derive(Serialize, ToSchema, Debug)]
+pub struct ApiEnums {
+    /// Values of the `reach` parameters
+    pub stream_reach: Vec<String>,
+    /// Values of the `timeframe` parameters
+    pub timeframe: Vec<String>,
+    /// Values of the post `kind` parameters
+    pub post_kind: Vec<String>,
+    /// Values of the `sorting` parameters
+    pub stream_sorting: Vec<String>,
+}
+
+impl ApiEnums {
+    /// Collects the accepted values from the `Deserialize` impls of the enums, so that they
+    /// follow their variants
+    pub fn new() -> Self {
+        Self {
+            stream_reach: Self::values::<StreamReach>(),
+            timeframe: Self::values::<Timeframe>(),
+            post_kind: Self::values::<PubkyAppPostKind>(),
+            stream_sorting: Self::values::<StreamSorting>(),
+        }
+    }
+
+    fn values<T: for<'de> serde::Deserialize<'de>>() -> Vec<String> {
+        variant_names::<T>()
+            .iter()
+            .map(|value| value.to_string())
+            .collect()
+    }
+}
+
+impl Default for ApiEnums {
+    fn default() -> Self {
+        Self::new()
+    }
+}
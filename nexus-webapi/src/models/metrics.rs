@@ -0,0 +1,102 @@
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+/// Route label of the requests that matched no route, so that random paths don't each create
+/// their own series
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Prometheus metrics of the HTTP requests served by the API, labelled by method and route
+/// template (e.g. `/v0/post/{author_id}/{post_id}`) rather than concrete path
+#[derive(Clone)]
+pub struct HttpMetrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    duration: HistogramVec,
+    in_flight: IntGaugeVec,
+}
+
+impl Default for HttpMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpMetrics {
+    /// Creates the metrics in a registry of their own
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "Number of HTTP requests served"),
+            &["method", "route", "status"],
+        )
+        .expect("Hardcoded counter options should be valid");
+        let duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Duration of the HTTP requests, in seconds",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("Hardcoded histogram options should be valid");
+        let in_flight = IntGaugeVec::new(
+            Opts::new(
+                "http_requests_in_flight",
+                "Number of HTTP requests being served",
+            ),
+            &["method", "route"],
+        )
+        .expect("Hardcoded gauge options should be valid");
+
+        for collector in [
+            Box::new(requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(duration.clone()),
+            Box::new(in_flight.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("Metrics should have distinct names");
+        }
+
+        Self {
+            registry,
+            requests,
+            duration,
+            in_flight,
+        }
+    }
+
+    /// Counts a request as in flight until the returned guard is dropped
+    pub fn start_request(&self, method: &str, route: &str) -> InFlightGuard {
+        let gauge = self.in_flight.with_label_values(&[method, route]);
+        gauge.inc();
+        InFlightGuard(gauge)
+    }
+
+    /// Records a served request, with its duration in seconds
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, duration_secs: f64) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.requests.with_label_values(&labels).inc();
+        self.duration
+            .with_label_values(&labels)
+            .observe(duration_secs);
+    }
+
+    /// Renders all the metrics in the Prometheus text exposition format
+    pub fn render(&self) -> prometheus::Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        String::from_utf8(buffer).map_err(|e| prometheus::Error::Msg(e.to_string()))
+    }
+}
+
+/// Decrements the in-flight gauge of a request when dropped, also if the request is cancelled
+pub struct InFlightGuard(prometheus::IntGauge);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
@@ -1,5 +1,17 @@
+pub mod auth;
+pub mod etag;
+pub mod health;
 pub mod info;
+pub mod meta;
+pub mod metrics;
 pub mod post;
+pub mod resolve;
 
+pub use auth::{SignedJson, SignedRequest};
+pub use etag::ETagJson;
+pub use health::{Dependency, Readiness};
 pub use info::ServerInfo;
+pub use meta::ApiEnums;
+pub use metrics::HttpMetrics;
 pub use post::{PostStreamDetailed, PostViewDetailed};
+pub use resolve::ResolvedUri;
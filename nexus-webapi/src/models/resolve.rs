@@ -0,0 +1,146 @@
+use crate::Result;
+use nexus_common::models::post::PostView;
+use nexus_common::models::user::UserView;
+use pubky_app_specs::{ParsedUri, Resource};
+use serde::Serialize;
+use tracing::warn;
+use utoipa::ToSchema;
+
+/// A `pubky://` URI resolved to the resource it references
+#[derive(Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResolvedUri {
+    User {
+        uri: String,
+        user: Box<UserView>,
+    },
+    Post {
+        uri: String,
+        post: Box<PostView>,
+    },
+    /// The URI references a user or a post that is not indexed
+    NotFound {
+        uri: String,
+    },
+    /// The URI references a resource that cannot be resolved, e.g. a tag or a bookmark
+    Unsupported {
+        uri: String,
+    },
+    /// The URI cannot be parsed
+    Invalid {
+        uri: String,
+        error: String,
+    },
+    /// The referenced user or post failed to load. The other URIs are still resolved
+    Error {
+        uri: String,
+        error: String,
+    },
+}
+
+/// The kind of resource a URI of the request references
+enum UriTarget {
+    User(String),
+    Post(String, String),
+    Unsupported,
+    Invalid(String),
+}
+
+impl UriTarget {
+    fn parse(uri: &str) -> Self {
+        let parsed = match ParsedUri::try_from(uri) {
+            Ok(parsed) => parsed,
+            Err(e) => return UriTarget::Invalid(e.to_string()),
+        };
+        match parsed.resource {
+            Resource::User => UriTarget::User(parsed.user_id.to_string()),
+            Resource::Post(post_id) => UriTarget::Post(parsed.user_id.to_string(), post_id),
+            _ => UriTarget::Unsupported,
+        }
+    }
+}
+
+impl ResolvedUri {
+    /// Resolves each of `uris` to the user or post it references, in the same order.
+    ///
+    /// The URIs are grouped by resource type, so that all users are retrieved in a single batch.
+    /// A resource that fails to load is resolved to [ResolvedUri::Error], the request only fails
+    /// when none of the referenced resources could be loaded.
+    pub async fn resolve_all(uris: Vec<String>, viewer_id: Option<&str>) -> Result<Vec<Self>> {
+        let targets: Vec<UriTarget> = uris.iter().map(|uri| UriTarget::parse(uri)).collect();
+
+        let user_ids: Vec<String> = targets
+            .iter()
+            .filter_map(|target| match target {
+                UriTarget::User(user_id) => Some(user_id.clone()),
+                _ => None,
+            })
+            .collect();
+        let post_keys: Vec<(String, String)> = targets
+            .iter()
+            .filter_map(|target| match target {
+                UriTarget::Post(author_id, post_id) => Some((author_id.clone(), post_id.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let (users, posts) = tokio::join!(
+            UserView::get_each_by_ids(&user_ids, viewer_id, None),
+            PostView::get_each_by_ids(&post_keys, viewer_id),
+        );
+        let mut users = users.into_iter();
+        let mut posts = posts?.into_iter();
+
+        let mut last_error = None;
+        let mut resolved = Vec::with_capacity(uris.len());
+        for (uri, target) in uris.into_iter().zip(targets) {
+            let resolved_uri = match target {
+                UriTarget::User(_) => match users.next() {
+                    Some(Ok(Some(user))) => ResolvedUri::User {
+                        uri,
+                        user: Box::new(user),
+                    },
+                    Some(Err(e)) => {
+                        warn!("Failed to resolve the user of {uri}: {e}");
+                        last_error = Some(e);
+                        ResolvedUri::Error {
+                            uri,
+                            error: "Failed to load the user".to_string(),
+                        }
+                    }
+                    _ => ResolvedUri::NotFound { uri },
+                },
+                UriTarget::Post(..) => match posts.next() {
+                    Some(Ok(Some(post))) => ResolvedUri::Post {
+                        uri,
+                        post: Box::new(post),
+                    },
+                    Some(Err(e)) => {
+                        warn!("Failed to resolve the post of {uri}: {e}");
+                        last_error = Some(e);
+                        ResolvedUri::Error {
+                            uri,
+                            error: "Failed to load the post".to_string(),
+                        }
+                    }
+                    _ => ResolvedUri::NotFound { uri },
+                },
+                UriTarget::Unsupported => ResolvedUri::Unsupported { uri },
+                UriTarget::Invalid(error) => ResolvedUri::Invalid { uri, error },
+            };
+            resolved.push(resolved_uri);
+        }
+
+        // Only fail the whole request when no referenced resource could be loaded
+        match last_error {
+            Some(e)
+                if resolved
+                    .iter()
+                    .all(|r| matches!(r, ResolvedUri::Error { .. })) =>
+            {
+                Err(e.into())
+            }
+            _ => Ok(resolved),
+        }
+    }
+}
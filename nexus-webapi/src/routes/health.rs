@@ -0,0 +1,50 @@
+use crate::models::{Dependency, Readiness};
+use crate::routes::AppState;
+
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use utoipa::OpenApi;
+
+pub const HEALTH_LIVE_ROUTE: &str = "/health/live";
+pub const HEALTH_READY_ROUTE: &str = "/health/ready";
+
+#[utoipa::path(
+    get,
+    path = HEALTH_LIVE_ROUTE,
+    tag = "Health",
+    description = "Liveness probe: answers as long as the process is up",
+    responses(
+        (status = 200, description = "The process is up")
+    )
+)]
+pub async fn live_handler() -> StatusCode {
+    StatusCode::OK
+}
+
+#[utoipa::path(
+    get,
+    path = HEALTH_READY_ROUTE,
+    tag = "Health",
+    description = "Readiness probe: checks that Redis and Neo4j are reachable, each within a short timeout",
+    responses(
+        (status = 200, description = "Ready to serve requests", body = Readiness),
+        (status = 503, description = "Some dependencies are unreachable, listed in `failing`", body = Readiness)
+    )
+)]
+pub async fn ready_handler() -> Readiness {
+    Readiness::check().await
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(HEALTH_LIVE_ROUTE, get(live_handler))
+        .route(HEALTH_READY_ROUTE, get(ready_handler))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(live_handler, ready_handler),
+    components(schemas(Readiness, Dependency))
+)]
+pub struct HealthApiDoc;
@@ -0,0 +1,35 @@
+use crate::routes::AppState;
+use crate::Result;
+
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use prometheus::TEXT_FORMAT;
+use utoipa::OpenApi;
+
+pub const METRICS_ROUTE: &str = "/metrics";
+
+#[utoipa::path(
+    get,
+    path = METRICS_ROUTE,
+    tag = "Metrics",
+    description = "Request counts, latency histograms and in-flight requests by method and route, in the Prometheus text exposition format",
+    responses(
+        (status = 200, description = "Metrics", content_type = "text/plain", body = String),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn metrics_handler(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    let metrics = state.metrics.render()?;
+    Ok(([(CONTENT_TYPE, TEXT_FORMAT)], metrics))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route(METRICS_ROUTE, get(metrics_handler))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(metrics_handler))]
+pub struct MetricsApiDoc;
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use nexus_common::CorsConfig;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+use tracing::warn;
+
+/// Builds the CORS layer of the API, see [CorsConfig]. An empty list allows any value, which is
+/// mirrored from the request when credentials are allowed, as wildcards cannot be used with them.
+/// Origins are never mirrored: credentials with no listed origin allow none
+pub fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let credentials = config.allow_credentials;
+
+    let origin = match (config.allowed_origins.is_empty(), credentials) {
+        (true, true) => AllowOrigin::list([]),
+        (true, false) => AllowOrigin::any(),
+        (false, _) => AllowOrigin::list(parse_all(&config.allowed_origins, |origin| {
+            HeaderValue::from_str(origin)
+        })),
+    };
+    let methods = match (config.allowed_methods.is_empty(), credentials) {
+        (true, true) => AllowMethods::mirror_request(),
+        (true, false) => AllowMethods::any(),
+        (false, _) => AllowMethods::list(parse_all(&config.allowed_methods, |method| {
+            Method::from_bytes(method.to_uppercase().as_bytes())
+        })),
+    };
+    let headers = match (config.allowed_headers.is_empty(), credentials) {
+        (true, true) => AllowHeaders::mirror_request(),
+        (true, false) => AllowHeaders::any(),
+        (false, _) => AllowHeaders::list(parse_all(&config.allowed_headers, |header| {
+            HeaderName::from_bytes(header.as_bytes())
+        })),
+    };
+
+    CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(credentials)
+}
+
+/// Parses the configured values, leaving out the invalid ones
+fn parse_all<T, E: Display>(values: &[String], parse: impl Fn(&str) -> Result<T, E>) -> Vec<T> {
+    values
+        .iter()
+        .filter_map(|value| {
+            parse(value)
+                .inspect_err(|e| warn!("Ignoring the invalid CORS value `{value}`: {e}"))
+                .ok()
+        })
+        .collect()
+}
@@ -0,0 +1,39 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::models::metrics::UNMATCHED_ROUTE;
+use crate::routes::AppState;
+
+/// Records the method, route template, status and latency of every request, see
+/// [crate::models::HttpMetrics]
+pub async fn metrics_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // The template keeps the number of series bounded, unlike the concrete path
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    let method = request.method().to_string();
+
+    let _in_flight = state.metrics.start_request(&method, &route);
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    state.metrics.observe_request(
+        &method,
+        &route,
+        response.status().as_u16(),
+        start.elapsed().as_secs_f64(),
+    );
+
+    response
+}
@@ -1 +1,5 @@
+pub mod cors;
+pub mod metrics;
+pub mod rate_limit;
+pub mod read_only;
 pub mod tracing;
@@ -0,0 +1,56 @@
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use nexus_common::db::kv::rate_limit::take_token;
+use tracing::error;
+
+use crate::{routes::AppState, Error};
+
+/// Address of the client, read from a trusted `X-Forwarded-For` header or else from the connection
+fn client_ip(request: &Request, trust_forwarded_for: bool) -> Option<String> {
+    let forwarded = trust_forwarded_for
+        .then(|| request.headers().get("x-forwarded-for"))
+        .flatten()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| !ip.is_empty());
+
+    forwarded.or_else(|| {
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+    })
+}
+
+// middleware limiting the requests of each client, see [nexus_common::RateLimitConfig]
+pub async fn rate_limit_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let config = &state.rate_limit;
+    let Some(rule) = config.rule_for(request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let Some(ip) = client_ip(&request, config.trust_forwarded_for) else {
+        return next.run(request).await;
+    };
+    let key = format!("{}:ip:{ip}", rule.path_prefix);
+    match take_token(&key, rule.requests, rule.window_secs).await {
+        Ok(None) => (),
+        Ok(Some(retry_after_secs)) => {
+            return Error::RateLimited { retry_after_secs }.into_response()
+        }
+        // Let the request through, an unavailable limiter should not take the API down
+        Err(e) => error!("Failed to check the rate limit of {key}: {e}"),
+    }
+
+    next.run(request).await
+}
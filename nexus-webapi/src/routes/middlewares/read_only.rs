@@ -0,0 +1,42 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::routes::v0::endpoints::{NOTIFICATION_READ_ALL_ROUTE, NOTIFICATION_READ_ROUTE};
+use crate::{routes::AppState, Error};
+
+/// Routes whose POST mutates state. POST is otherwise only used by bulk read endpoints.
+const MUTATING_POST_ROUTES: [&str; 2] = [NOTIFICATION_READ_ROUTE, NOTIFICATION_READ_ALL_ROUTE];
+
+/// Whether a request to the `route` template may mutate state
+fn is_mutation(method: &Method, route: Option<&str>) -> bool {
+    match *method {
+        Method::PUT | Method::DELETE | Method::PATCH => true,
+        Method::POST => route.is_some_and(|route| MUTATING_POST_ROUTES.contains(&route)),
+        _ => false,
+    }
+}
+
+// middleware rejecting mutations while the API is in read-only mode, see [ApiConfig::read_only]
+pub async fn read_only_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str);
+    if state.read_only && is_mutation(request.method(), route) {
+        return Error::ServiceUnavailable {
+            reason: "The API is in read-only mode, writes are disabled during maintenance"
+                .to_string(),
+        }
+        .into_response();
+    }
+
+    next.run(request).await
+}
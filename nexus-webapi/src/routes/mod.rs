@@ -1,9 +1,13 @@
+use crate::models::HttpMetrics;
 use axum::Router;
+use nexus_common::{CorsConfig, RateLimitConfig};
 use std::{path::PathBuf, sync::Arc};
 use tower_http::compression::CompressionLayer;
-use tower_http::cors::{Any, CorsLayer};
+use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+pub mod health;
+pub mod metrics;
 pub mod r#static;
 pub mod v0;
 
@@ -12,19 +16,53 @@ mod middlewares;
 #[derive(Clone)]
 pub struct AppState {
     pub files_path: Arc<PathBuf>,
+    /// See [nexus_common::ApiConfig::read_only]
+    pub read_only: bool,
+    /// See [nexus_common::ApiConfig::export_token]
+    pub export_token: Option<Arc<str>>,
+    /// See [nexus_common::ApiConfig::rate_limit]
+    pub rate_limit: Arc<RateLimitConfig>,
+    /// See [nexus_common::ApiConfig::min_engagement]
+    pub min_engagement: u64,
+    /// See [nexus_common::ApiConfig::max_stream_tags]
+    pub max_stream_tags: usize,
+    /// Metrics of the served requests, exposed on [metrics::METRICS_ROUTE]
+    pub metrics: HttpMetrics,
 }
 
-pub fn routes(files_path: PathBuf) -> Router {
+pub fn routes(
+    files_path: PathBuf,
+    read_only: bool,
+    export_token: Option<String>,
+    cors: &CorsConfig,
+    rate_limit: &RateLimitConfig,
+    min_engagement: u64,
+    max_stream_tags: usize,
+) -> Router {
     let state = AppState {
         files_path: Arc::new(files_path),
+        read_only,
+        export_token: export_token.map(Arc::from),
+        rate_limit: Arc::new(rate_limit.clone()),
+        min_engagement,
+        max_stream_tags,
+        metrics: HttpMetrics::new(),
     };
 
     let route_static = r#static::routes(state.clone());
 
     let routes_v0 = v0::routes(state.clone());
 
+    let route_health = health::routes();
+
+    let route_metrics = metrics::routes();
+
+    let mut v0_docs = v0::ApiDoc::merge_docs();
+    v0_docs.merge(health::HealthApiDoc::openapi());
+    v0_docs.merge(metrics::MetricsApiDoc::openapi());
+
     let route_openapi = SwaggerUi::new("/swagger-ui")
-        .url("/api-docs/v0/openapi.json", v0::ApiDoc::merge_docs())
+        .url("/api-docs/v0/openapi.json", v0_docs)
         .url(
             "/api-docs/static/openapi.json",
             r#static::ApiDoc::merge_docs(),
@@ -33,19 +71,30 @@ pub fn routes(files_path: PathBuf) -> Router {
     // Combine routes
     let app = routes_v0
         .merge(route_static)
+        .merge(route_health)
+        .merge(route_metrics)
         .merge(route_openapi)
         // IMPORTANT: It also swaps the type from Route<AppState> to Route
         // don't know the reason of swap but I guess the return signature forcing that swap...
-        .with_state(state);
+        .with_state(state.clone());
 
-    // Create a CORS layer that allows all origins, methods, and headers
-    let cors = CorsLayer::new()
-        .allow_origin(Any) // Allow all origins
-        .allow_methods(Any) // Allow all HTTP methods
-        .allow_headers(Any); // Allow all headers
+    // Create a CORS layer, that allows all origins, methods, and headers unless restricted
+    let cors = middlewares::cors::cors_layer(cors);
 
-    // Layer the CORS, tracing middleware, and compression on top of the routes
-    app.layer(axum::middleware::from_fn(
+    // Layer the read-only guard, rate limiter, metrics, CORS, tracing middleware, and compression on top of the routes
+    app.layer(axum::middleware::from_fn_with_state(
+        state.clone(),
+        middlewares::read_only::read_only_middleware,
+    ))
+    .layer(axum::middleware::from_fn_with_state(
+        state.clone(),
+        middlewares::rate_limit::rate_limit_middleware,
+    ))
+    .layer(axum::middleware::from_fn_with_state(
+        state,
+        middlewares::metrics::metrics_middleware,
+    ))
+    .layer(axum::middleware::from_fn(
         middlewares::tracing::tracing_middleware,
     ))
     .layer(cors)
@@ -46,7 +46,8 @@ pub async fn bootstrap_handler(
     ),
     responses(
         (status = 200, description = "Successfully added new homeserver"),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "The API is in read-only mode")
     )
 )]
 pub async fn put_homeserver_handler(Path(user_id): Path<String>) -> Result<()> {
@@ -6,6 +6,9 @@ const VERSION_ROUTE: &str = "/v0";
 // Info routes
 pub const INFO_ROUTE: &str = concatcp!(VERSION_ROUTE, "/info");
 
+// Meta routes
+pub const META_ENUMS_ROUTE: &str = concatcp!(VERSION_ROUTE, "/meta/enums");
+
 // -- USER endpoints --
 const USER_PREFIX: &str = concatcp!(VERSION_ROUTE, "/user");
 pub const USER_ROUTE: &str = concatcp!(USER_PREFIX, "/{user_id}");
@@ -14,9 +17,23 @@ pub const USER_COUNTS_ROUTE: &str = concatcp!(USER_ROUTE, "/counts");
 pub const USER_DETAILS_ROUTE: &str = concatcp!(USER_ROUTE, "/details");
 pub const USER_TAGS_ROUTE: &str = concatcp!(USER_ROUTE, "/tags");
 pub const USER_TAGGERS_ROUTE: &str = concatcp!(USER_ROUTE, "/taggers/{label}");
+pub const USER_APPLIED_LABELS_ROUTE: &str = concatcp!(USER_ROUTE, "/applied-labels");
 pub const USER_FOLLOWERS_ROUTE: &str = concatcp!(USER_ROUTE, "/followers");
+pub const USER_FOLLOWER_HISTORY_ROUTE: &str = concatcp!(USER_ROUTE, "/followers/history");
+pub const USER_FOLLOWER_STATS_ROUTE: &str = concatcp!(USER_ROUTE, "/follower-stats");
 pub const USER_FOLLOWING_ROUTE: &str = concatcp!(USER_ROUTE, "/following");
 pub const USER_FRIENDS_ROUTE: &str = concatcp!(USER_ROUTE, "/friends");
+pub const USER_MUTUAL_ROUTE: &str = concatcp!(USER_ROUTE, "/mutual");
+pub const USER_BLOCKED_ROUTE: &str = concatcp!(USER_ROUTE, "/blocked");
+pub const USER_TOP_CONNECTIONS_ROUTE: &str = concatcp!(USER_ROUTE, "/connections");
+pub const USER_RECOMMENDATIONS_ROUTE: &str = concatcp!(USER_ROUTE, "/recommendations");
+pub const USER_FOLLOWED_TAGS_ROUTE: &str = concatcp!(USER_ROUTE, "/followed-tags");
+pub const USER_FOLLOWED_TAG_ROUTE: &str = concatcp!(USER_FOLLOWED_TAGS_ROUTE, "/{label}");
+pub const USER_SUGGESTED_TAGS_ROUTE: &str = concatcp!(USER_ROUTE, "/suggested-tags");
+pub const USER_EXPORT_ROUTE: &str = concatcp!(USER_ROUTE, "/export");
+const USERS_PREFIX: &str = concatcp!(VERSION_ROUTE, "/users");
+pub const USERS_FOLLOWING_STATUS_ROUTE: &str = concatcp!(USERS_PREFIX, "/following-status");
+pub const USERS_COUNTS_ROUTE: &str = concatcp!(USERS_PREFIX, "/counts");
 
 // -- POST endpoints --
 pub const POST_PREFIX: &str = concatcp!(VERSION_ROUTE, "/post");
@@ -24,9 +41,16 @@ pub const POST_ROUTE: &str = concatcp!(POST_PREFIX, "/{author_id}/{post_id}");
 pub const POST_RELATIONSHIPS_ROUTE: &str = concatcp!(POST_ROUTE, "/relationships");
 pub const POST_BOOKMARK_ROUTE: &str = concatcp!(POST_ROUTE, "/bookmark");
 pub const POST_COUNTS_ROUTE: &str = concatcp!(POST_ROUTE, "/counts");
+pub const POST_ENGAGEMENT_ROUTE: &str = concatcp!(POST_ROUTE, "/engagement");
+pub const POST_THREAD_ROUTE: &str = concatcp!(POST_ROUTE, "/thread");
 pub const POST_DETAILS_ROUTE: &str = concatcp!(POST_ROUTE, "/details");
 pub const POST_TAGS_ROUTE: &str = concatcp!(POST_ROUTE, "/tags");
+pub const POST_ALL_TAGS_ROUTE: &str = concatcp!(POST_ROUTE, "/all-tags");
 pub const POST_TAGGERS_ROUTE: &str = concatcp!(POST_ROUTE, "/taggers/{label}");
+const POSTS_PREFIX: &str = concatcp!(VERSION_ROUTE, "/posts");
+pub const POSTS_COUNTS_ROUTE: &str = concatcp!(POSTS_PREFIX, "/counts");
+pub const POSTS_BOOKMARK_STATUS_ROUTE: &str = concatcp!(POSTS_PREFIX, "/bookmark-status");
+pub const POSTS_BULK_ROUTE: &str = concatcp!(POSTS_PREFIX, "/bulk");
 
 // -- STREAM endpoints --
 const STREAM_PREFIX: &str = concatcp!(VERSION_ROUTE, "/stream");
@@ -39,6 +63,8 @@ pub const STREAM_USERS_BY_IDS_ROUTE: &str = concatcp!(STREAM_USERS_ROUTE, "/by_i
 pub const STREAM_POSTS_ROUTE: &str = concatcp!(STREAM_PREFIX, "/posts");
 pub const STREAM_POSTS_BY_IDS_ROUTE: &str = concatcp!(STREAM_POSTS_ROUTE, "/by_ids");
 pub const STREAM_POST_KEYS_ROUTE: &str = concatcp!(STREAM_POSTS_ROUTE, "/keys");
+pub const STREAM_POSTS_FOLLOWED_TAGS_ROUTE: &str =
+    concatcp!(STREAM_POSTS_ROUTE, "/followed-tags/{user_id}");
 // STREAM of Tags for posts
 pub const STREAM_TAGS_ROUTE: &str = concatcp!(STREAM_PREFIX, "/tags");
 pub const STREAM_TAGS_GLOBAL_ROUTE: &str = concatcp!(STREAM_TAGS_ROUTE, "/global");
@@ -47,15 +73,19 @@ pub const STREAM_TAGS_REACH_ROUTE: &str =
 
 // -- SEARCH endpoints --
 const SEARCH_PREFIX: &str = concatcp!(VERSION_ROUTE, "/search");
-const SEARCH_USERS_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/users");
+pub const SEARCH_USERS_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/users");
 pub const SEARCH_USERS_BY_NAME_ROUTE: &str = concatcp!(SEARCH_USERS_ROUTE, "/by_name/{prefix}");
 pub const SEARCH_USERS_BY_ID_ROUTE: &str = concatcp!(SEARCH_USERS_ROUTE, "/by_id/{prefix}");
 pub const SEARCH_POSTS_BY_TAG_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/posts/by_tag/{tag}");
+pub const SEARCH_POSTS_BY_CONTENT_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/posts/content");
+pub const SEARCH_POSTS_BY_EMBED_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/posts/by_embed");
 pub const SEARCH_TAGS_BY_PREFIX_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/tags/by_prefix/{prefix}");
+pub const SEARCH_TAGS_BY_LABEL_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/tags/by_label/{label}");
 
 // -- TAG endpoints --
 const TAG_PREFIX: &str = concatcp!(VERSION_ROUTE, "/tags");
 pub const TAGS_HOT_ROUTE: &str = concatcp!(TAG_PREFIX, "/hot");
+pub const TAGS_HOT_REACHES_ROUTE: &str = concatcp!(TAGS_HOT_ROUTE, "/reaches");
 pub const TAG_TAGGERS_ROUTE: &str = concatcp!(TAG_PREFIX, "/taggers/{label}");
 pub const TAG_ROUTE: &str = concatcp!(TAG_PREFIX, "/{tagger_id}/{tag_id}");
 
@@ -66,6 +96,10 @@ pub const FILE_ROUTE: &str = concatcp!(FILE_PREFIX, "/file/{file_id}");
 
 // -- NOTIFICATION endpoints -
 pub const NOTIFICATION_ROUTE: &str = concatcp!(USER_ROUTE, "/notifications");
+pub const NOTIFICATION_PREFERENCES_ROUTE: &str = concatcp!(NOTIFICATION_ROUTE, "/preferences");
+pub const NOTIFICATION_READ_ROUTE: &str = concatcp!(NOTIFICATION_ROUTE, "/read");
+pub const NOTIFICATION_READ_ALL_ROUTE: &str = concatcp!(NOTIFICATION_ROUTE, "/read-all");
+pub const NOTIFICATION_UNREAD_COUNT_ROUTE: &str = concatcp!(NOTIFICATION_ROUTE, "/unread-count");
 
 // -- BOOTSTRAP endpoints -
 pub const BOOTSTRAP_ROUTE: &str = concatcp!(VERSION_ROUTE, "/bootstrap/{user_id}");
@@ -73,3 +107,6 @@ pub const PUT_HOMESERVER_ROUTE: &str = concatcp!(VERSION_ROUTE, "/ingest/{user_i
 
 // -- EVENTS endpoints
 pub const EVENTS_ROUTE: &str = concatcp!(VERSION_ROUTE, "/events");
+
+// -- RESOLVE endpoints
+pub const RESOLVE_BULK_ROUTE: &str = concatcp!(VERSION_ROUTE, "/resolve/bulk");
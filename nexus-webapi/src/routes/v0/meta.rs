@@ -0,0 +1,28 @@
+use super::endpoints::META_ENUMS_ROUTE;
+use crate::models::ApiEnums;
+use crate::routes::AppState;
+
+use axum::routing::get;
+use axum::{Json, Router};
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    path = META_ENUMS_ROUTE,
+    description = "Values accepted by the enum parameters of the API",
+    tag = "Info",
+    responses(
+        (status = 200, description = "Accepted enum values", body = ApiEnums)
+    )
+)]
+pub async fn enums_handler() -> Json<ApiEnums> {
+    Json(ApiEnums::new())
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route(META_ENUMS_ROUTE, get(enums_handler))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(enums_handler), components(schemas(ApiEnums)))]
+pub struct MetaApiDoc;
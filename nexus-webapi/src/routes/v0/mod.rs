@@ -6,8 +6,10 @@ pub mod endpoints;
 pub mod events;
 pub mod file;
 pub mod info;
+pub mod meta;
 pub mod notification;
 pub mod post;
+pub mod resolve;
 pub mod search;
 pub mod stream;
 pub mod tag;
@@ -20,6 +22,7 @@ use super::AppState;
 
 pub fn routes(app_state: AppState) -> Router<AppState> {
     let routes_info = info::routes(app_state);
+    let routes_meta = meta::routes();
     let routes_post = post::routes();
     let route_user = user::routes();
     let route_stream = stream::routes();
@@ -29,9 +32,11 @@ pub fn routes(app_state: AppState) -> Router<AppState> {
     let route_notification = notification::routes();
     let route_bootstrap = bootstrap::routes();
     let route_events = events::routes();
+    let route_resolve = resolve::routes();
 
     routes_post
         .merge(routes_info)
+        .merge(routes_meta)
         .merge(route_user)
         .merge(route_stream)
         .merge(route_search)
@@ -40,6 +45,7 @@ pub fn routes(app_state: AppState) -> Router<AppState> {
         .merge(route_notification)
         .merge(route_bootstrap)
         .merge(route_events)
+        .merge(route_resolve)
 }
 
 #[derive(OpenApi)]
@@ -51,6 +57,7 @@ impl ApiDoc {
         let mut combined = post::PostApiDoc::merge_docs();
         combined.merge(bootstrap::BootstrapApiDoc::openapi());
         combined.merge(info::InfoApiDoc::openapi());
+        combined.merge(meta::MetaApiDoc::openapi());
         combined.merge(user::UserApiDoc::merge_docs());
         combined.merge(stream::StreamApiDoc::merge_docs());
         combined.merge(search::SearchApiDoc::merge_docs());
@@ -59,6 +66,7 @@ impl ApiDoc {
         combined.merge(tag::TagApiDoc::merge_docs());
         combined.merge(notification::NotificationApiDoc::merge_docs());
         combined.merge(events::EventsApiDoc::openapi());
+        combined.merge(resolve::ResolveApiDoc::openapi());
 
         combined
     }
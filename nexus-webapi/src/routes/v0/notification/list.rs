@@ -1,12 +1,46 @@
 use crate::routes::v0::endpoints::NOTIFICATION_ROUTE;
-use crate::Result;
+use crate::{Error, Result};
 use axum::extract::{Path, Query};
 use axum::Json;
-use nexus_common::models::notification::{Notification, NotificationBody, PostChangedSource};
+use nexus_common::db::kv::SortOrder;
+use nexus_common::models::notification::{
+    Notification, NotificationBody, NotificationType, PostChangedSource,
+};
 use nexus_common::types::Pagination;
+use serde::de::{value, IntoDeserializer};
+use serde::Deserialize;
 use tracing::debug;
 use utoipa::OpenApi;
 
+#[derive(Deserialize, Debug)]
+pub struct NotificationsQuery {
+    #[serde(flatten)]
+    pub pagination: Pagination,
+    pub order: Option<SortOrder>,
+    pub types: Option<String>,
+}
+
+impl NotificationsQuery {
+    /// Parses the comma-separated notification types, rejecting the unknown ones
+    fn parse_types(&self) -> Result<Option<Vec<NotificationType>>> {
+        let Some(types) = &self.types else {
+            return Ok(None);
+        };
+        types
+            .split(',')
+            .map(|name| {
+                let name = name.trim();
+                NotificationType::deserialize(name.into_deserializer()).map_err(
+                    |_: value::Error| {
+                        Error::invalid_input(&format!("Unknown notification type: {name}"))
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+}
+
 #[utoipa::path(
     get,
     path = NOTIFICATION_ROUTE,
@@ -16,26 +50,34 @@ use utoipa::OpenApi;
         ("user_id" = String, Path, description = "User Pubky ID"),
         ("skip" = Option<usize>, Query, description = "Skip N notifications"),
         ("limit" = Option<usize>, Query, description = "Retrieve N notifications"),
-        ("start" = Option<String>, Query, description = "The start of the notifications timeframe. Notifications with a timestamp greater than this value will be excluded from the results"),
-        ("end" = Option<String>, Query, description = "The end of the notifications timeframe. Notifications with a timestamp less than this value will be excluded from the results")
+        ("start" = Option<String>, Query, description = "The start of the notifications timeframe. Notifications with a timestamp greater than this value (less than it, in ascending order) will be excluded from the results"),
+        ("end" = Option<String>, Query, description = "The end of the notifications timeframe. Notifications with a timestamp less than this value (greater than it, in ascending order) will be excluded from the results"),
+        ("order" = Option<SortOrder>, Query, description = "Ordering of the notifications by timestamp. Either 'ascending' (oldest first) or 'descending'. Defaults to descending."),
+        ("types" = Option<String>, Query, description = "Only list the notifications of these comma-separated types, e.g. `follow,reply,mention`")
     ),
     responses(
         (status = 200, description = "List of notifications", body = Vec<Notification>),
+        (status = 400, description = "Unknown notification type"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn list_notifications_handler(
     Path(user_id): axum::extract::Path<String>,
-    Query(pagination): Query<Pagination>,
+    Query(query): Query<NotificationsQuery>,
 ) -> Result<Json<Vec<Notification>>> {
     debug!("GET {NOTIFICATION_ROUTE} for user_id: {}", user_id);
 
-    Ok(Json(Notification::get_by_id(&user_id, pagination).await?))
+    let types = query.parse_types()?;
+    let order = query.order.unwrap_or_default();
+    Ok(Json(
+        Notification::get_by_id_in_order(&user_id, query.pagination, order, types.as_deref())
+            .await?,
+    ))
 }
 
 #[derive(OpenApi)]
 #[openapi(
     paths(list_notifications_handler,),
-    components(schemas(Notification, NotificationBody, PostChangedSource))
+    components(schemas(Notification, NotificationBody, NotificationType, PostChangedSource))
 )]
 pub struct NotificationsApiDocs;
@@ -1,14 +1,37 @@
-use crate::routes::v0::endpoints::NOTIFICATION_ROUTE;
+use crate::routes::v0::endpoints::{
+    NOTIFICATION_PREFERENCES_ROUTE, NOTIFICATION_READ_ALL_ROUTE, NOTIFICATION_READ_ROUTE,
+    NOTIFICATION_ROUTE, NOTIFICATION_UNREAD_COUNT_ROUTE,
+};
 use crate::routes::AppState;
 
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::Router;
 use utoipa::OpenApi;
 
 mod list;
+mod preferences;
+mod read;
 
 pub fn routes() -> Router<AppState> {
-    Router::new().route(NOTIFICATION_ROUTE, get(list::list_notifications_handler))
+    Router::new()
+        .route(NOTIFICATION_ROUTE, get(list::list_notifications_handler))
+        .route(
+            NOTIFICATION_PREFERENCES_ROUTE,
+            get(preferences::get_notification_preferences_handler)
+                .put(preferences::put_notification_preferences_handler),
+        )
+        .route(
+            NOTIFICATION_READ_ROUTE,
+            post(read::mark_notifications_read_handler),
+        )
+        .route(
+            NOTIFICATION_READ_ALL_ROUTE,
+            post(read::mark_all_notifications_read_handler),
+        )
+        .route(
+            NOTIFICATION_UNREAD_COUNT_ROUTE,
+            get(read::unread_notifications_count_handler),
+        )
 }
 
 #[derive(OpenApi)]
@@ -17,6 +40,9 @@ pub struct NotificationApiDoc;
 
 impl NotificationApiDoc {
     pub fn merge_docs() -> utoipa::openapi::OpenApi {
-        list::NotificationsApiDocs::openapi()
+        let mut combined = list::NotificationsApiDocs::openapi();
+        combined.merge(preferences::NotificationPreferencesApiDocs::openapi());
+        combined.merge(read::NotificationsReadApiDocs::openapi());
+        combined
     }
 }
@@ -0,0 +1,86 @@
+use crate::models::SignedJson;
+use crate::routes::v0::endpoints::NOTIFICATION_PREFERENCES_ROUTE;
+use crate::{Error, Result};
+use axum::extract::Path;
+use axum::Json;
+use nexus_common::models::notification::{
+    check_webhook_url, DoNotDisturb, NotificationPreferences, NotificationType, MINUTES_PER_DAY,
+};
+use pubky_app_specs::PubkyId;
+use tracing::debug;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    path = NOTIFICATION_PREFERENCES_ROUTE,
+    tag = "User",
+    description = "Notification preferences of a user. Notification types that are not listed are enabled",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID")
+    ),
+    responses(
+        (status = 200, description = "Notification preferences", body = NotificationPreferences),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_notification_preferences_handler(
+    Path(user_id): Path<String>,
+) -> Result<Json<NotificationPreferences>> {
+    debug!("GET {NOTIFICATION_PREFERENCES_ROUTE} for user_id: {user_id}");
+
+    Ok(Json(NotificationPreferences::get_by_id(&user_id).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = NOTIFICATION_PREFERENCES_ROUTE,
+    tag = "User",
+    description = "Replace the notification preferences of a user. Disabled notification types, and notifications created during the do-not-disturb window (UTC minutes since midnight), are not stored for the user. The new notifications are POSTed to the `webhook_url`, if the notification webhook is enabled. It must be an HTTPS URL of a public host. The request must be signed with the key of the user (`Authorization: PubkySig <timestamp>:<signature>`)",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("Authorization" = String, Header, description = "Signature of the request by the user key")
+    ),
+    request_body = NotificationPreferences,
+    responses(
+        (status = 200, description = "Updated notification preferences", body = NotificationPreferences),
+        (status = 400, description = "Invalid user ID, do-not-disturb window or webhook URL, which must be HTTPS and not target a local or private host"),
+        (status = 401, description = "The request is not signed by the user"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "The API is in read-only mode")
+    )
+)]
+pub async fn put_notification_preferences_handler(
+    Path(user_id): Path<String>,
+    SignedJson(preferences, signed): SignedJson<NotificationPreferences>,
+) -> Result<Json<NotificationPreferences>> {
+    debug!("PUT {NOTIFICATION_PREFERENCES_ROUTE} for user_id: {user_id}");
+
+    PubkyId::try_from(&user_id)
+        .map_err(|e| Error::invalid_input(&format!("Invalid user PK: {e}")))?;
+    signed.authorize(&user_id).await?;
+
+    if let Some(dnd) = preferences.do_not_disturb {
+        if !dnd.is_valid() {
+            return Err(Error::invalid_input(&format!(
+                "Do-not-disturb minutes must be lower than {MINUTES_PER_DAY}"
+            )));
+        }
+    }
+
+    if let Some(url) = &preferences.webhook_url {
+        check_webhook_url(url).map_err(|e| Error::invalid_input(&e))?;
+    }
+
+    preferences.put_to_index(&user_id).await?;
+    Ok(Json(preferences))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_notification_preferences_handler,
+        put_notification_preferences_handler
+    ),
+    components(schemas(NotificationPreferences, NotificationType, DoNotDisturb))
+)]
+pub struct NotificationPreferencesApiDocs;
@@ -0,0 +1,134 @@
+use crate::models::{SignedJson, SignedRequest};
+use crate::routes::v0::endpoints::{
+    NOTIFICATION_READ_ALL_ROUTE, NOTIFICATION_READ_ROUTE, NOTIFICATION_UNREAD_COUNT_ROUTE,
+};
+use crate::{Error, Result};
+use axum::extract::Path;
+use axum::Json;
+use nexus_common::models::notification::Notification;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+/// Maximum number of notifications that can be marked as read in a single request
+const MAX_READ_NOTIFICATIONS: usize = 100;
+
+#[derive(ToSchema, Deserialize)]
+pub struct NotificationsReadRequest {
+    /// Ids of the notifications of the user, as listed in their `id`
+    pub ids: Vec<String>,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct NotificationsReadResponse {
+    /// Number of notifications marked as read
+    pub read: usize,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct UnreadNotificationsCountResponse {
+    /// Number of notifications not marked as read
+    pub unread: usize,
+}
+
+#[utoipa::path(
+    post,
+    path = NOTIFICATION_READ_ROUTE,
+    tag = "User",
+    description = "Mark notifications of a user as read. Notifications are identified by their `id`, unknown ones are ignored. The request must be signed with the key of the user (`Authorization: PubkySig <timestamp>:<signature>`)",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("Authorization" = String, Header, description = "Signature of the request by the user key")
+    ),
+    request_body = NotificationsReadRequest,
+    responses(
+        (status = 200, description = "Number of notifications marked as read", body = NotificationsReadResponse),
+        (status = 400, description = "Too many notifications"),
+        (status = 401, description = "The request is not signed by the user"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "The API is in read-only mode")
+    )
+)]
+pub async fn mark_notifications_read_handler(
+    Path(user_id): Path<String>,
+    SignedJson(request, signed): SignedJson<NotificationsReadRequest>,
+) -> Result<Json<NotificationsReadResponse>> {
+    debug!(
+        "POST {NOTIFICATION_READ_ROUTE} for user_id: {user_id}, ids size {}",
+        request.ids.len()
+    );
+    signed.authorize(&user_id).await?;
+
+    if request.ids.len() > MAX_READ_NOTIFICATIONS {
+        return Err(Error::invalid_input(&format!(
+            "The maximum number of notifications allowed is {MAX_READ_NOTIFICATIONS}"
+        )));
+    }
+
+    let read = Notification::mark_read(&user_id, &request.ids).await?;
+    Ok(Json(NotificationsReadResponse { read }))
+}
+
+#[utoipa::path(
+    post,
+    path = NOTIFICATION_READ_ALL_ROUTE,
+    tag = "User",
+    description = "Mark all the notifications of a user as read. The request must be signed with the key of the user (`Authorization: PubkySig <timestamp>:<signature>`)",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("Authorization" = String, Header, description = "Signature of the request by the user key")
+    ),
+    responses(
+        (status = 200, description = "Number of notifications marked as read", body = NotificationsReadResponse),
+        (status = 401, description = "The request is not signed by the user"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "The API is in read-only mode")
+    )
+)]
+pub async fn mark_all_notifications_read_handler(
+    Path(user_id): Path<String>,
+    signed: SignedRequest,
+) -> Result<Json<NotificationsReadResponse>> {
+    debug!("POST {NOTIFICATION_READ_ALL_ROUTE} for user_id: {user_id}");
+    signed.authorize(&user_id).await?;
+
+    let read = Notification::mark_all_read(&user_id).await?;
+    Ok(Json(NotificationsReadResponse { read }))
+}
+
+#[utoipa::path(
+    get,
+    path = NOTIFICATION_UNREAD_COUNT_ROUTE,
+    tag = "User",
+    description = "Number of notifications of a user that are not marked as read",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID")
+    ),
+    responses(
+        (status = 200, description = "Unread notifications count", body = UnreadNotificationsCountResponse),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn unread_notifications_count_handler(
+    Path(user_id): Path<String>,
+) -> Result<Json<UnreadNotificationsCountResponse>> {
+    debug!("GET {NOTIFICATION_UNREAD_COUNT_ROUTE} for user_id: {user_id}");
+
+    let unread = Notification::unread_count(&user_id).await?;
+    Ok(Json(UnreadNotificationsCountResponse { unread }))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        mark_notifications_read_handler,
+        mark_all_notifications_read_handler,
+        unread_notifications_count_handler
+    ),
+    components(schemas(
+        NotificationsReadRequest,
+        NotificationsReadResponse,
+        UnreadNotificationsCountResponse
+    ))
+)]
+pub struct NotificationsReadApiDocs;
@@ -1,11 +1,13 @@
-use crate::routes::v0::endpoints::POST_BOOKMARK_ROUTE;
+use crate::routes::v0::endpoints::{POSTS_BOOKMARK_STATUS_ROUTE, POST_BOOKMARK_ROUTE};
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
 use axum::Json;
 use nexus_common::models::post::Bookmark;
 use serde::Deserialize;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
+
+const MAX_BOOKMARK_STATUS_POSTS: usize = 100;
 
 #[derive(Deserialize)]
 pub struct PostQuery {
@@ -45,6 +47,48 @@ pub async fn post_bookmark_handler(
     }
 }
 
+// This is a POST request because the list of post IDs could exceed URL length limits
+#[derive(ToSchema, Deserialize)]
+pub struct BookmarkStatusRequest {
+    pub viewer_id: String,
+    /// Post keys, in the `author_id:post_id` format
+    pub post_ids: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = POSTS_BOOKMARK_STATUS_ROUTE,
+    description = "Check which of the listed posts the viewer bookmarked. Returns one flag per post, in the same order.",
+    tag = "Post",
+    request_body = BookmarkStatusRequest,
+    responses(
+        (status = 200, description = "Whether the viewer bookmarked each listed post", body = Vec<bool>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn posts_bookmark_status_handler(
+    Json(request): Json<BookmarkStatusRequest>,
+) -> Result<Json<Vec<bool>>> {
+    debug!(
+        "POST {POSTS_BOOKMARK_STATUS_ROUTE} viewer_id:{} post_ids size {:?}",
+        request.viewer_id,
+        request.post_ids.len()
+    );
+
+    if request.post_ids.len() > MAX_BOOKMARK_STATUS_POSTS {
+        let err_msg =
+            format!("The maximum number of post IDs allowed is {MAX_BOOKMARK_STATUS_POSTS}");
+        return Err(Error::invalid_input(&err_msg));
+    }
+
+    let statuses = Bookmark::check_batch(&request.post_ids, &request.viewer_id).await?;
+    Ok(Json(statuses))
+}
+
 #[derive(OpenApi)]
-#[openapi(paths(post_bookmark_handler), components(schemas(Bookmark)))]
+#[openapi(
+    paths(post_bookmark_handler, posts_bookmark_status_handler),
+    components(schemas(Bookmark, BookmarkStatusRequest))
+)]
 pub struct BookmarkApiDoc;
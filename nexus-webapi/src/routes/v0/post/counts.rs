@@ -1,10 +1,14 @@
-use crate::routes::v0::endpoints::POST_COUNTS_ROUTE;
+use crate::routes::v0::endpoints::{POSTS_COUNTS_ROUTE, POST_COUNTS_ROUTE};
 use crate::{Error, Result};
 use axum::extract::Path;
 use axum::Json;
 use nexus_common::models::post::PostCounts;
+use serde::Deserialize;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
+
+/// Maximum number of posts whose counts can be retrieved in a single request
+const MAX_COUNTS_POSTS: usize = 100;
 
 #[utoipa::path(
     get,
@@ -32,6 +36,45 @@ pub async fn post_counts_handler(
     }
 }
 
+// This is a POST request because the list of post IDs could exceed URL length limits
+#[derive(ToSchema, Deserialize)]
+pub struct PostsCountsRequest {
+    /// Post keys, in the `author_id:post_id` format
+    pub post_ids: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = POSTS_COUNTS_ROUTE,
+    description = "Counts of the listed posts. Returns the counts of each post in the same order, null for the posts that don't exist.",
+    tag = "Post",
+    request_body = PostsCountsRequest,
+    responses(
+        (status = 200, description = "Counts of each listed post", body = Vec<Option<PostCounts>>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn posts_counts_handler(
+    Json(request): Json<PostsCountsRequest>,
+) -> Result<Json<Vec<Option<PostCounts>>>> {
+    debug!(
+        "POST {POSTS_COUNTS_ROUTE} post_ids size {:?}",
+        request.post_ids.len()
+    );
+
+    if request.post_ids.len() > MAX_COUNTS_POSTS {
+        let err_msg = format!("The maximum number of post IDs allowed is {MAX_COUNTS_POSTS}");
+        return Err(Error::invalid_input(&err_msg));
+    }
+
+    let counts = PostCounts::get_by_ids(&request.post_ids).await?;
+    Ok(Json(counts))
+}
+
 #[derive(OpenApi)]
-#[openapi(paths(post_counts_handler), components(schemas(PostCounts)))]
+#[openapi(
+    paths(post_counts_handler, posts_counts_handler),
+    components(schemas(PostCounts, PostsCountsRequest))
+)]
 pub struct PostCountsApiDoc;
@@ -2,7 +2,7 @@ use crate::routes::v0::endpoints::POST_DETAILS_ROUTE;
 use crate::{Error, Result};
 use axum::extract::Path;
 use axum::Json;
-use nexus_common::models::post::PostDetails;
+use nexus_common::models::post::{PostDetails, POST_DELETED_CONTENT};
 use pubky_app_specs::PubkyAppPostKind;
 use tracing::debug;
 use utoipa::OpenApi;
@@ -19,6 +19,7 @@ use utoipa::OpenApi;
     responses(
         (status = 200, description = "Post Details", body = PostDetails),
         (status = 404, description = "Post not found"),
+        (status = 410, description = "Post deleted"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -28,8 +29,12 @@ pub async fn post_details_handler(
     debug!("GET {POST_DETAILS_ROUTE} author_id:{author_id}, post_id:{post_id}");
 
     match PostDetails::get_by_id(&author_id, &post_id).await? {
+        // A post deleted while it had replies or reposts is only kept as a placeholder
+        Some(post) if post.content == POST_DELETED_CONTENT => {
+            Err(Error::PostGone { author_id, post_id })
+        }
         Some(post) => Ok(Json(post)),
-        None => Err(Error::PostNotFound { author_id, post_id }),
+        None => Err(Error::missing_post(author_id, post_id).await),
     }
 }
 
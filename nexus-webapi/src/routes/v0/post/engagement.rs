@@ -0,0 +1,59 @@
+use crate::routes::v0::endpoints::POST_ENGAGEMENT_ROUTE;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use nexus_common::models::post::{PostEngagementBreakdown, PostTaggerEngagement, PostView};
+use serde::Deserialize;
+use tracing::debug;
+use utoipa::OpenApi;
+
+const DEFAULT_LIMIT_TAGGERS: usize = 10;
+const MAX_LIMIT_TAGGERS: usize = 100;
+
+#[derive(Deserialize)]
+pub struct PostEngagementQuery {
+    pub limit_taggers: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = POST_ENGAGEMENT_ROUTE,
+    description = "Post engagement breakdown: top taggers, unique repliers, repost authors and bookmarks",
+    tag = "Post",
+    params(
+        ("author_id" = String, Path, description = "Author Pubky ID"),
+        ("post_id" = String, Path, description = "Post Crockford32 ID"),
+        ("limit_taggers" = Option<usize>, Query, description = "Upper limit on the number of top taggers (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Post engagement breakdown", body = PostEngagementBreakdown),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn post_engagement_handler(
+    Path((author_id, post_id)): Path<(String, String)>,
+    Query(query): Query<PostEngagementQuery>,
+) -> Result<Json<PostEngagementBreakdown>> {
+    debug!(
+        "GET {POST_ENGAGEMENT_ROUTE} author_id:{author_id}, post_id:{post_id}, limit_taggers:{:?}",
+        query.limit_taggers
+    );
+
+    let limit_taggers = query
+        .limit_taggers
+        .unwrap_or(DEFAULT_LIMIT_TAGGERS)
+        .min(MAX_LIMIT_TAGGERS);
+
+    match PostView::engagement_breakdown(&author_id, &post_id, limit_taggers).await? {
+        Some(breakdown) => Ok(Json(breakdown)),
+        None => Err(Error::PostNotFound { author_id, post_id }),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(post_engagement_handler),
+    components(schemas(PostEngagementBreakdown, PostTaggerEngagement))
+)]
+pub struct PostEngagementApiDoc;
@@ -1,26 +1,41 @@
 use crate::routes::v0::endpoints::{
-    POST_BOOKMARK_ROUTE, POST_COUNTS_ROUTE, POST_DETAILS_ROUTE, POST_ROUTE, POST_TAGGERS_ROUTE,
-    POST_TAGS_ROUTE,
+    POSTS_BOOKMARK_STATUS_ROUTE, POSTS_BULK_ROUTE, POSTS_COUNTS_ROUTE, POST_ALL_TAGS_ROUTE,
+    POST_BOOKMARK_ROUTE, POST_COUNTS_ROUTE, POST_DETAILS_ROUTE, POST_ENGAGEMENT_ROUTE, POST_ROUTE,
+    POST_TAGGERS_ROUTE, POST_TAGS_ROUTE, POST_THREAD_ROUTE,
 };
 use crate::routes::AppState;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::Router;
 use utoipa::OpenApi;
 
 mod bookmark;
 mod counts;
 mod details;
+mod engagement;
 pub mod tags;
+mod thread;
 mod view;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route(POST_ROUTE, get(view::post_view_handler))
+        .route(POSTS_BULK_ROUTE, post(view::posts_bulk_handler))
         .route(POST_DETAILS_ROUTE, get(details::post_details_handler))
         .route(POST_COUNTS_ROUTE, get(counts::post_counts_handler))
+        .route(POSTS_COUNTS_ROUTE, post(counts::posts_counts_handler))
+        .route(
+            POST_ENGAGEMENT_ROUTE,
+            get(engagement::post_engagement_handler),
+        )
         .route(POST_BOOKMARK_ROUTE, get(bookmark::post_bookmark_handler))
+        .route(
+            POSTS_BOOKMARK_STATUS_ROUTE,
+            post(bookmark::posts_bookmark_status_handler),
+        )
         .route(POST_TAGS_ROUTE, get(tags::post_tags_handler))
+        .route(POST_ALL_TAGS_ROUTE, get(tags::post_all_tags_handler))
         .route(POST_TAGGERS_ROUTE, get(tags::post_taggers_handler))
+        .route(POST_THREAD_ROUTE, get(thread::post_thread_handler))
 }
 
 #[derive(OpenApi)]
@@ -33,7 +48,9 @@ impl PostApiDoc {
         combined.merge(counts::PostCountsApiDoc::openapi());
         combined.merge(bookmark::BookmarkApiDoc::openapi());
         combined.merge(details::PostDetailsApiDoc::openapi());
+        combined.merge(engagement::PostEngagementApiDoc::openapi());
         combined.merge(tags::PostTagsApiDoc::openapi());
+        combined.merge(thread::PostThreadApiDoc::openapi());
         combined
     }
 }
@@ -1,4 +1,4 @@
-use crate::routes::v0::endpoints::{POST_TAGGERS_ROUTE, POST_TAGS_ROUTE};
+use crate::routes::v0::endpoints::{POST_ALL_TAGS_ROUTE, POST_TAGGERS_ROUTE, POST_TAGS_ROUTE};
 use crate::routes::v0::user::tags::TaggersQuery;
 use crate::routes::v0::{TaggersInfoResponse, TagsQuery};
 use crate::{Error, Result};
@@ -7,6 +7,8 @@ use axum::Json;
 use nexus_common::models::tag::post::TagPost;
 use nexus_common::models::tag::traits::{TagCollection, TaggersCollection};
 use nexus_common::models::tag::TagDetails;
+use nexus_common::types::Pagination;
+use serde::Deserialize;
 use tracing::debug;
 use utoipa::OpenApi;
 
@@ -53,6 +55,67 @@ pub async fn post_tags_handler(
     }
 }
 
+const DEFAULT_ALL_TAGS_LIMIT: usize = 20;
+const MAX_ALL_TAGS_LIMIT: usize = 100;
+
+#[derive(Deserialize)]
+pub struct AllTagsQuery {
+    #[serde(flatten)]
+    pub pagination: Pagination,
+    pub limit_taggers: Option<usize>,
+    pub viewer_id: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = POST_ALL_TAGS_ROUTE,
+    description = "All post tags, paginated over the full list of labels",
+    tag = "Post",
+    params(
+        ("author_id" = String, Path, description = "Author Pubky ID"),
+        ("post_id" = String, Path, description = "Post ID"),
+        ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
+        ("skip" = Option<usize>, Query, description = "Number of tags to skip for pagination. Defaults to `0`"),
+        ("limit" = Option<usize>, Query, description = "Number of tags to return for pagination. Defaults to `20`, max `100`"),
+        ("limit_taggers" = Option<usize>, Query, description = "Upper limit on the number of taggers per tag. Defaults to `5`"),
+    ),
+    responses(
+        (status = 404, description = "Post not found"),
+        (status = 200, description = "Post tags, ordered by taggers count and then by label", body = Vec<TagDetails>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn post_all_tags_handler(
+    Path((author_id, post_id)): Path<(String, String)>,
+    Query(query): Query<AllTagsQuery>,
+) -> Result<Json<Vec<TagDetails>>> {
+    debug!(
+        "GET {POST_ALL_TAGS_ROUTE} author_id:{}, post_id: {}, skip:{:?}, limit:{:?}, limit_taggers:{:?}",
+        author_id, post_id, query.pagination.skip, query.pagination.limit, query.limit_taggers
+    );
+    let skip = query.pagination.skip.unwrap_or(0);
+    let limit = query
+        .pagination
+        .limit
+        .unwrap_or(DEFAULT_ALL_TAGS_LIMIT)
+        .min(MAX_ALL_TAGS_LIMIT);
+
+    match TagPost::get_by_id(
+        &author_id,
+        Some(&post_id),
+        Some(skip),
+        Some(limit),
+        query.limit_taggers,
+        query.viewer_id.as_deref(),
+        None,
+    )
+    .await?
+    {
+        Some(tags) => Ok(Json(tags)),
+        None => Err(Error::PostNotFound { author_id, post_id }),
+    }
+}
+
 #[utoipa::path(
     get,
     path = POST_TAGGERS_ROUTE,
@@ -93,7 +156,7 @@ pub async fn post_taggers_handler(
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(post_tags_handler, post_taggers_handler),
+    paths(post_tags_handler, post_all_tags_handler, post_taggers_handler),
     components(schemas(TagDetails, TaggersInfoResponse))
 )]
 pub struct PostTagsApiDoc;
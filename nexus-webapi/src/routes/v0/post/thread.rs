@@ -0,0 +1,68 @@
+use crate::routes::v0::endpoints::POST_THREAD_ROUTE;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use nexus_common::models::post::{PostThreadNode, PostView, ThreadOptions};
+use serde::Deserialize;
+use tracing::debug;
+use utoipa::OpenApi;
+
+const DEFAULT_THREAD_DEPTH: usize = 5;
+const MAX_THREAD_DEPTH: usize = 20;
+const DEFAULT_LIMIT_REPLIES: usize = 20;
+const MAX_LIMIT_REPLIES: usize = 100;
+
+#[derive(Deserialize, Debug)]
+pub struct PostThreadQuery {
+    pub viewer_id: Option<String>,
+    pub depth: Option<usize>,
+    pub limit_replies: Option<usize>,
+    pub include_tombstones: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = POST_THREAD_ROUTE,
+    description = "Reply thread of a post",
+    tag = "Post",
+    params(
+        ("author_id" = String, Path, description = "Author Pubky ID"),
+        ("post_id" = String, Path, description = "Post Crockford32 ID"),
+        ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
+        ("depth" = Option<usize>, Query, description = "Levels of replies below the post (default 5, max 20)"),
+        ("limit_replies" = Option<usize>, Query, description = "Upper limit on the number of replies per post (default 20, max 100)"),
+        ("include_tombstones" = Option<bool>, Query, description = "Represent deleted posts as tombstones with `deleted: true` instead of dropping them with their replies (default true)"),
+    ),
+    responses(
+        (status = 200, description = "Post thread", body = PostThreadNode),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn post_thread_handler(
+    Path((author_id, post_id)): Path<(String, String)>,
+    Query(query): Query<PostThreadQuery>,
+) -> Result<Json<PostThreadNode>> {
+    debug!("GET {POST_THREAD_ROUTE} author_id:{author_id}, post_id:{post_id}, query:{query:?}");
+
+    let options = ThreadOptions {
+        depth: query
+            .depth
+            .unwrap_or(DEFAULT_THREAD_DEPTH)
+            .min(MAX_THREAD_DEPTH),
+        limit_replies: query
+            .limit_replies
+            .unwrap_or(DEFAULT_LIMIT_REPLIES)
+            .min(MAX_LIMIT_REPLIES),
+        include_tombstones: query.include_tombstones.unwrap_or(true),
+    };
+
+    match PostView::get_thread(&author_id, &post_id, query.viewer_id.as_deref(), options).await? {
+        Some(thread) => Ok(Json(thread)),
+        None => Err(Error::PostNotFound { author_id, post_id }),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(post_thread_handler), components(schemas(PostThreadNode)))]
+pub struct PostThreadApiDoc;
@@ -1,14 +1,19 @@
-use crate::models::PostViewDetailed;
-use crate::routes::v0::endpoints::POST_ROUTE;
+use crate::models::{ETagJson, PostViewDetailed, SignedRequest};
+use crate::routes::v0::endpoints::{POSTS_BULK_ROUTE, POST_ROUTE};
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
+use axum::http::HeaderMap;
 use axum::Json;
-use nexus_common::models::post::PostRelationships;
+use nexus_common::models::moderation::ModerationInfo;
+use nexus_common::models::post::{PostRelationships, PostView, POST_DELETED_CONTENT};
 use nexus_common::models::tag::post::TagPost;
 use nexus_common::models::tag::TagDetails;
+use nexus_common::StackManager;
 use serde::Deserialize;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
+
+const MAX_BULK_POSTS: usize = 100;
 
 #[derive(Default, Deserialize, Debug)]
 pub struct PostViewQuery {
@@ -17,6 +22,8 @@ pub struct PostViewQuery {
     pub limit_taggers: Option<usize>,
     #[serde(default)]
     pub include_attachment_metadata: bool,
+    #[serde(default)]
+    pub include_moderated: bool,
 }
 
 #[utoipa::path(
@@ -31,17 +38,24 @@ pub struct PostViewQuery {
         ("limit_tags" = Option<usize>, Query, description = "Upper limit on the number of tags for the post"),
         ("limit_taggers" = Option<usize>, Query, description = "Upper limit on the number of taggers per tag"),
         ("include_attachment_metadata" = Option<bool>, Query, description = "Include file metadata for post attachments"),
+        ("include_moderated" = Option<bool>, Query, description = "If the post was removed by the moderator and the viewer is that moderator, return a placeholder with the `moderated` reason instead of 404. The request must be signed by the viewer"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previously fetched view, answered with 304 if the view did not change"),
+        ("Authorization" = Option<String>, Header, description = "Signature of the request by the viewer, `PubkySig <timestamp>:<signature>`, needed for `include_moderated`"),
     ),
     responses(
-        (status = 200, description = "Post", body = PostViewDetailed),
+        (status = 200, description = "Post, with its weak ETag in the `ETag` header. A repost of a deleted post is flagged with `original_deleted`, or not found if such reposts are hidden", body = PostViewDetailed),
+        (status = 304, description = "The post view did not change since the given ETag"),
         (status = 404, description = "Post not found"),
+        (status = 410, description = "Post deleted"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn post_view_handler(
     Path((author_id, post_id)): Path<(String, String)>,
     Query(query): Query<PostViewQuery>,
-) -> Result<Json<PostViewDetailed>> {
+    headers: HeaderMap,
+    signed: SignedRequest,
+) -> Result<ETagJson<PostViewDetailed>> {
     debug!(
         "GET {POST_ROUTE} author_id:{}, post_id:{}, viewer_id:{}, limit_tags:{:?}, limit_taggers:{:?}",
         author_id,
@@ -51,7 +65,7 @@ pub async fn post_view_handler(
         query.limit_taggers
     );
     // Avoid by default WoT tags in a Post. We could add as `depth` argument for that specific use case
-    match PostViewDetailed::get_by_id(
+    let post = PostViewDetailed::get_by_id(
         &author_id,
         &post_id,
         query.viewer_id.as_deref(),
@@ -59,16 +73,118 @@ pub async fn post_view_handler(
         query.limit_taggers,
         query.include_attachment_metadata,
     )
-    .await?
-    {
-        Some(post) => Ok(Json(post)),
-        None => Err(Error::PostNotFound { author_id, post_id }),
+    .await?;
+    // A repost of a deleted post is flagged, or not found when such reposts are hidden
+    let post = match post {
+        Some(PostViewDetailed {
+            view,
+            attachments_metadata,
+        }) => {
+            let mut views = [Some(view)];
+            PostView::apply_deleted_repost_mode(
+                &mut views,
+                StackManager::config().deleted_repost_mode,
+            )
+            .await?;
+            let [view] = views;
+            view.map(|view| PostViewDetailed::new(view, attachments_metadata))
+        }
+        None => None,
+    };
+    match post {
+        // A post deleted while it had replies or reposts is only kept as a placeholder
+        Some(post) if post.view.details.content == POST_DELETED_CONTENT => {
+            Err(Error::PostGone { author_id, post_id })
+        }
+        Some(post) => Ok(ETagJson::new(post, &headers)),
+        None if query.include_moderated => {
+            // Anyone can claim to be the moderator in `viewer_id`, so only a signed viewer counts
+            let moderator_id = query
+                .viewer_id
+                .as_deref()
+                .filter(|viewer_id| signed.is_signed_by(viewer_id));
+            match ModerationInfo::get_for_viewer(&[&author_id, &post_id], moderator_id).await? {
+                Some(moderation) => Ok(ETagJson::new(
+                    PostViewDetailed::new(
+                        PostView::moderated(&author_id, &post_id, moderation),
+                        vec![],
+                    ),
+                    &headers,
+                )),
+                None => Err(Error::missing_post(author_id, post_id).await),
+            }
+        }
+        None => Err(Error::missing_post(author_id, post_id).await),
     }
 }
 
+// This is a POST request because the list of post IDs could exceed URL length limits
+#[derive(ToSchema, Deserialize)]
+pub struct PostsBulkRequest {
+    /// Post keys, in the `author_id:post_id` format
+    pub post_ids: Vec<String>,
+    pub viewer_id: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = POSTS_BULK_ROUTE,
+    description = "Views of the listed posts, to hydrate a page of posts in one request. Returns the view of each post in the same order, null for the posts that don't exist or whose ID is malformed.",
+    tag = "Post",
+    request_body = PostsBulkRequest,
+    responses(
+        (status = 200, description = "View of each listed post", body = Vec<Option<PostView>>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn posts_bulk_handler(
+    Json(request): Json<PostsBulkRequest>,
+) -> Result<Json<Vec<Option<PostView>>>> {
+    debug!(
+        "POST {POSTS_BULK_ROUTE} viewer_id:{:?} post_ids size {:?}",
+        request.viewer_id,
+        request.post_ids.len()
+    );
+
+    if request.post_ids.len() > MAX_BULK_POSTS {
+        let err_msg = format!("The maximum number of post IDs allowed is {MAX_BULK_POSTS}");
+        return Err(Error::invalid_input(&err_msg));
+    }
+
+    // Malformed keys are answered with null, like the posts that don't exist
+    let post_keys: Vec<(String, String)> = request
+        .post_ids
+        .iter()
+        .filter_map(|post_key| post_key.split_once(':'))
+        .map(|(author_id, post_id)| (author_id.to_string(), post_id.to_string()))
+        .collect();
+    let mut found = PostView::get_by_ids(&post_keys, request.viewer_id.as_deref())
+        .await?
+        .into_iter();
+
+    let views = request
+        .post_ids
+        .iter()
+        .map(|post_key| match post_key.contains(':') {
+            true => found.next().flatten(),
+            false => None,
+        })
+        .collect();
+    Ok(Json(views))
+}
+
 #[derive(OpenApi)]
 #[openapi(
-    paths(post_view_handler),
-    components(schemas(PostViewDetailed, PostRelationships, TagPost, TagDetails))
+    paths(post_view_handler, posts_bulk_handler),
+    components(schemas(
+        PostsBulkRequest,
+        PostView,
+        PostViewDetailed,
+        PostRelationships,
+        TagPost,
+        TagDetails,
+        ModerationInfo
+    ))
 )]
 pub struct PostViewApiDoc;
@@ -0,0 +1,62 @@
+use super::endpoints::RESOLVE_BULK_ROUTE;
+use crate::models::ResolvedUri;
+use crate::routes::AppState;
+use crate::{Error, Result};
+
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::Deserialize;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+/// Maximum number of URIs resolved in a single request
+const MAX_RESOLVE_URIS: usize = 100;
+
+#[derive(Deserialize, ToSchema)]
+pub struct ResolveBulkBody {
+    /// The `pubky://` URIs to resolve
+    uris: Vec<String>,
+    /// Viewer Pubky ID
+    viewer_id: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = RESOLVE_BULK_ROUTE,
+    description = "Resolve a list of pubky URIs to the users and posts they reference. The results keep the order of the URIs. A user or post that fails to load is resolved to an `error` entry without failing the others. This is a POST request because we're passing a potentially large list of URIs in the request body.",
+    tag = "Resolve",
+    request_body = ResolveBulkBody,
+    responses(
+        (status = 200, description = "Resolved URIs, one per requested URI", body = Vec<ResolvedUri>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error, none of the referenced users and posts could be loaded")
+    )
+)]
+pub async fn resolve_bulk_handler(
+    Json(body): Json<ResolveBulkBody>,
+) -> Result<Json<Vec<ResolvedUri>>> {
+    debug!("POST {RESOLVE_BULK_ROUTE} uris:{:?}", body.uris);
+
+    if body.uris.is_empty() {
+        return Err(Error::invalid_input("No URIs provided"));
+    }
+    if body.uris.len() > MAX_RESOLVE_URIS {
+        return Err(Error::invalid_input(&format!(
+            "At most {MAX_RESOLVE_URIS} URIs can be resolved at once"
+        )));
+    }
+
+    let resolved = ResolvedUri::resolve_all(body.uris, body.viewer_id.as_deref()).await?;
+    Ok(Json(resolved))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route(RESOLVE_BULK_ROUTE, post(resolve_bulk_handler))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(resolve_bulk_handler),
+    components(schemas(ResolveBulkBody, ResolvedUri))
+)]
+pub struct ResolveApiDoc;
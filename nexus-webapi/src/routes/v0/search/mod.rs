@@ -1,6 +1,7 @@
 use crate::routes::v0::endpoints::{
-    SEARCH_POSTS_BY_TAG_ROUTE, SEARCH_TAGS_BY_PREFIX_ROUTE, SEARCH_USERS_BY_ID_ROUTE,
-    SEARCH_USERS_BY_NAME_ROUTE,
+    SEARCH_POSTS_BY_CONTENT_ROUTE, SEARCH_POSTS_BY_EMBED_ROUTE, SEARCH_POSTS_BY_TAG_ROUTE,
+    SEARCH_TAGS_BY_LABEL_ROUTE, SEARCH_TAGS_BY_PREFIX_ROUTE, SEARCH_USERS_BY_ID_ROUTE,
+    SEARCH_USERS_BY_NAME_ROUTE, SEARCH_USERS_ROUTE,
 };
 use crate::routes::AppState;
 use axum::routing::get;
@@ -15,6 +16,7 @@ pub const USER_ID_SEARCH_MIN_PREFIX_LEN: usize = 3;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
+        .route(SEARCH_USERS_ROUTE, get(users::search_users_handler))
         .route(
             SEARCH_USERS_BY_NAME_ROUTE,
             get(users::search_users_by_name_handler),
@@ -27,10 +29,22 @@ pub fn routes() -> Router<AppState> {
             SEARCH_POSTS_BY_TAG_ROUTE,
             get(posts::search_posts_by_tag_handler),
         )
+        .route(
+            SEARCH_POSTS_BY_CONTENT_ROUTE,
+            get(posts::search_posts_by_content_handler),
+        )
+        .route(
+            SEARCH_POSTS_BY_EMBED_ROUTE,
+            get(posts::search_posts_by_embed_handler),
+        )
         .route(
             SEARCH_TAGS_BY_PREFIX_ROUTE,
             get(tags::search_tags_by_prefix_handler),
         )
+        .route(
+            SEARCH_TAGS_BY_LABEL_ROUTE,
+            get(tags::search_tags_by_label_handler),
+        )
 }
 
 #[derive(OpenApi)]
@@ -41,6 +55,8 @@ impl SearchApiDoc {
     pub fn merge_docs() -> utoipa::openapi::OpenApi {
         let mut combined = users::SearchUsersApiDocs::openapi();
         combined.merge(posts::SearchPostsByTagApiDocs::openapi());
+        combined.merge(posts::SearchPostsByContentApiDocs::openapi());
+        combined.merge(posts::SearchPostsByEmbedApiDocs::openapi());
         combined.merge(tags::SearchTagsByPrefixApiDocs::openapi());
         combined
     }
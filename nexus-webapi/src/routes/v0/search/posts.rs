@@ -1,8 +1,13 @@
-use crate::routes::v0::endpoints::SEARCH_POSTS_BY_TAG_ROUTE;
-use crate::Result;
+use crate::routes::v0::endpoints::{
+    SEARCH_POSTS_BY_CONTENT_ROUTE, SEARCH_POSTS_BY_EMBED_ROUTE, SEARCH_POSTS_BY_TAG_ROUTE,
+};
+use crate::{Error, Result};
 use axum::extract::{Path, Query};
 use axum::Json;
-use nexus_common::models::post::search::PostsByTagSearch;
+use nexus_common::models::post::search::{
+    PostsByContentSearch, PostsByTagSearch, MIN_CONTENT_TOKEN_LEN,
+};
+use nexus_common::models::post::PostsByEmbed;
 use nexus_common::types::Pagination;
 use nexus_common::types::StreamSorting;
 use serde::Deserialize;
@@ -65,3 +70,116 @@ pub async fn search_posts_by_tag_handler(
     components(schemas(PostsByTagSearch))
 )]
 pub struct SearchPostsByTagApiDocs;
+
+#[derive(Deserialize)]
+pub struct SearchPostsByContentQuery {
+    pub q: String,
+    pub sorting: Option<StreamSorting>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[utoipa::path(
+    get,
+    path = SEARCH_POSTS_BY_CONTENT_ROUTE,
+    description = "Search Posts by Content. Returns the posts containing all the words of the query",
+    tag = "Search",
+    params(
+        ("q" = String, Query, description = "Search terms"),
+        ("sorting" = Option<StreamSorting>, Query, description = "StreamSorting method"),
+        ("start" = Option<usize>, Query, description = "The start of the stream timeframe. Posts with a timestamp greater than this value will be excluded from the results"),
+        ("end" = Option<usize>, Query, description = "The end of the stream timeframe. Posts with a timestamp less than this value will be excluded from the results"),
+        ("skip" = Option<usize>, Query, description = "Skip N results"),
+        ("limit" = Option<usize>, Query, description = "Limit the number of results")
+    ),
+    responses(
+        (status = 200, description = "Search results", body = Vec<PostsByContentSearch>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn search_posts_by_content_handler(
+    Query(query): Query<SearchPostsByContentQuery>,
+) -> Result<Json<Vec<PostsByContentSearch>>> {
+    let sorting = query.sorting;
+    let mut pagination = query.pagination;
+
+    debug!(
+        "GET {SEARCH_POSTS_BY_CONTENT_ROUTE} q:{}, sort_by: {:?}, start: {:?}, end: {:?}, skip: {:?}, limit: {:?}",
+        query.q, sorting, pagination.start, pagination.end, pagination.skip, pagination.limit
+    );
+
+    if PostsByContentSearch::tokenize(&query.q).is_empty() {
+        return Err(Error::invalid_input(&format!(
+            "Search query must contain at least one word of {MIN_CONTENT_TOKEN_LEN} or more characters"
+        )));
+    }
+
+    pagination.skip = Some(pagination.skip.unwrap_or(0));
+    pagination.limit = Some(pagination.limit.unwrap_or(20));
+
+    match PostsByContentSearch::get_by_terms(&query.q, sorting, pagination).await? {
+        Some(posts_list) => Ok(Json(posts_list)),
+        None => Ok(Json(vec![])),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(search_posts_by_content_handler),
+    components(schemas(PostsByContentSearch))
+)]
+pub struct SearchPostsByContentApiDocs;
+
+const MAX_EMBED_SEARCH_LIMIT: usize = 100;
+
+#[derive(Deserialize)]
+pub struct SearchPostsByEmbedQuery {
+    pub uri: String,
+    pub skip: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = SEARCH_POSTS_BY_EMBED_ROUTE,
+    description = "Search Posts by Embed. Returns the posts embedding a resource, most recent first. Only available when embed indexing is enabled",
+    tag = "Search",
+    params(
+        ("uri" = String, Query, description = "URI of the embedded resource, e.g. a post URI"),
+        ("skip" = Option<usize>, Query, description = "Skip N results"),
+        ("limit" = Option<usize>, Query, description = "Limit the number of results, at most 100")
+    ),
+    responses(
+        (status = 200, description = "Search results", body = Vec<PostsByEmbed>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn search_posts_by_embed_handler(
+    Query(query): Query<SearchPostsByEmbedQuery>,
+) -> Result<Json<Vec<PostsByEmbed>>> {
+    debug!(
+        "GET {SEARCH_POSTS_BY_EMBED_ROUTE} uri:{}, skip: {:?}, limit: {:?}",
+        query.uri, query.skip, query.limit
+    );
+
+    if query.uri.trim().is_empty() {
+        return Err(Error::invalid_input("The embedded URI cannot be empty"));
+    }
+
+    let skip = query.skip.unwrap_or(0);
+    let limit = query.limit.unwrap_or(20).min(MAX_EMBED_SEARCH_LIMIT);
+
+    match PostsByEmbed::get(&query.uri, Some(skip), Some(limit)).await? {
+        Some(posts_list) => Ok(Json(posts_list)),
+        None => Ok(Json(vec![])),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(search_posts_by_embed_handler),
+    components(schemas(PostsByEmbed))
+)]
+pub struct SearchPostsByEmbedApiDocs;
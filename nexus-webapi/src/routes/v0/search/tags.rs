@@ -1,8 +1,9 @@
-use crate::routes::v0::endpoints::SEARCH_TAGS_BY_PREFIX_ROUTE;
+use crate::routes::v0::endpoints::{SEARCH_TAGS_BY_LABEL_ROUTE, SEARCH_TAGS_BY_PREFIX_ROUTE};
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
 use axum::Json;
 use nexus_common::models::post::search::PostsByTagSearch;
+use nexus_common::models::tag::label;
 use nexus_common::models::tag::search::TagSearch;
 use nexus_common::types::Pagination;
 use pubky_app_specs::traits::Validatable;
@@ -23,12 +24,13 @@ pub struct SearchTagsQuery {
     description = "Search tags by prefix",
     tag = "Search",
     params(
-        ("prefix" = String, Path, description = "Tag name prefix"),
+        ("prefix" = String, Path, description = "Tag name prefix, cannot be empty"),
         ("skip" = Option<usize>, Query, description = "Skip N results"),
         ("limit" = Option<usize>, Query, description = "Limit the number of results")
     ),
     responses(
         (status = 200, description = "Search results", body = Vec<String>),
+        (status = 400, description = "Invalid input"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -36,6 +38,10 @@ pub async fn search_tags_by_prefix_handler(
     Path(prefix): Path<String>,
     Query(query): Query<SearchTagsQuery>,
 ) -> Result<Json<Vec<TagSearch>>> {
+    // An empty prefix would match every tag
+    if prefix.trim().is_empty() {
+        return Err(Error::invalid_input("Tag prefix cannot be empty"));
+    }
     let validated_prefix = sanitize_validate(&prefix)?;
 
     let mut pagination = query.pagination;
@@ -53,6 +59,42 @@ pub async fn search_tags_by_prefix_handler(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = SEARCH_TAGS_BY_LABEL_ROUTE,
+    description = "Search a tag by its exact label. Unlike the prefix search, `rust` does not match `rustlang`",
+    tag = "Search",
+    params(
+        ("label" = String, Path, description = "Tag name"),
+        ("skip" = Option<usize>, Query, description = "Skip N results"),
+        ("limit" = Option<usize>, Query, description = "Limit the number of results")
+    ),
+    responses(
+        (status = 200, description = "Search results", body = Vec<String>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn search_tags_by_label_handler(
+    Path(label): Path<String>,
+    Query(query): Query<SearchTagsQuery>,
+) -> Result<Json<Vec<TagSearch>>> {
+    let validated_label = sanitize_validate(&label)?;
+
+    let mut pagination = query.pagination;
+    pagination.skip.get_or_insert_default();
+    pagination.limit.get_or_insert(20);
+
+    debug!(
+        "GET {SEARCH_TAGS_BY_LABEL_ROUTE} validated_label:{}, skip: {:?}, limit: {:?}",
+        validated_label, pagination.skip, pagination.limit
+    );
+
+    match TagSearch::get_by_label_exact(&validated_label, &pagination).await? {
+        Some(tags_list) => Ok(Json(tags_list)),
+        None => Ok(Json(vec![])),
+    }
+}
+
 fn sanitize_validate(tag_prefix: &str) -> Result<String> {
     // Use a throwaway URI to build the tag instance, as we only need it for validation
     let temp_tag = PubkyAppTag::new(
@@ -64,13 +106,14 @@ fn sanitize_validate(tag_prefix: &str) -> Result<String> {
         .validate(None)
         .map_err(|e| Error::invalid_input(&e.to_string()))?;
 
-    let sanitized = temp_tag.label;
+    // Sanitization lowercases the label, which is undone for case-sensitive tags
+    let sanitized = label::restore_case(&temp_tag.label, tag_prefix);
     Ok(sanitized)
 }
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(search_tags_by_prefix_handler),
+    paths(search_tags_by_prefix_handler, search_tags_by_label_handler),
     components(schemas(PostsByTagSearch))
 )]
 pub struct SearchTagsByPrefixApiDocs;
@@ -1,13 +1,15 @@
-use crate::routes::v0::endpoints::{SEARCH_USERS_BY_ID_ROUTE, SEARCH_USERS_BY_NAME_ROUTE};
+use crate::routes::v0::endpoints::{
+    SEARCH_USERS_BY_ID_ROUTE, SEARCH_USERS_BY_NAME_ROUTE, SEARCH_USERS_ROUTE,
+};
 use crate::routes::v0::search::USER_ID_SEARCH_MIN_PREFIX_LEN;
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
 use axum::Json;
-use nexus_common::models::user::UserSearch;
-use nexus_common::types::Pagination;
-use serde::Deserialize;
+use nexus_common::models::user::{UserSearch, UserSearchPage};
+use nexus_common::types::{parse_string_to_bool, Pagination};
+use serde::{Deserialize, Serialize};
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
 
 #[derive(Deserialize)]
 pub struct SearchQuery {
@@ -15,6 +17,67 @@ pub struct SearchQuery {
     pagination: Pagination,
 }
 
+#[derive(Deserialize)]
+pub struct SearchByNameQuery {
+    #[serde(flatten)]
+    pagination: Pagination,
+    #[serde(default, deserialize_with = "parse_string_to_bool")]
+    with_total: bool,
+}
+
+/// Either the plain list of user IDs or, if `with_total` is set, the page along with the total
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum SearchUsersByNameResponse {
+    Ids(UserSearch),
+    Page(UserSearchPage),
+}
+
+#[derive(Deserialize)]
+pub struct CombinedSearchQuery {
+    q: String,
+    #[serde(flatten)]
+    pagination: Pagination,
+}
+
+#[utoipa::path(
+    get,
+    path = SEARCH_USERS_ROUTE,
+    description = "Search user IDs by username or ID prefix. An exact ID match is ranked first, followed by username matches and ID prefix matches",
+    tag = "Search",
+    params(
+        ("q" = String, Query, description = format!("Username or user ID prefix to search for. IDs are only matched from {USER_ID_SEARCH_MIN_PREFIX_LEN} characters on")),
+        ("skip" = Option<usize>, Query, description = "Skip N results"),
+        ("limit" = Option<usize>, Query, description = "Limit the number of results")
+    ),
+    responses(
+        (status = 200, description = "Search results", body = UserSearch),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn search_users_handler(
+    Query(query): Query<CombinedSearchQuery>,
+) -> Result<Json<UserSearch>> {
+    let search = query.q.trim();
+    if search.is_empty() {
+        return Err(Error::invalid_input("Search query cannot be empty"));
+    }
+
+    debug!("GET {SEARCH_USERS_ROUTE} q:{}", search);
+
+    let skip = query.pagination.skip.unwrap_or(0);
+    let limit = query.pagination.limit.unwrap_or(200);
+
+    // Short queries would match too many IDs, so they only search usernames
+    let result = match search.chars().count() < USER_ID_SEARCH_MIN_PREFIX_LEN {
+        true => UserSearch::get_by_name(search, Some(skip), Some(limit)).await?,
+        false => UserSearch::search_combined(search, Some(skip), Some(limit)).await?,
+    };
+
+    Ok(Json(result.unwrap_or_default()))
+}
+
 #[utoipa::path(
     get,
     path = SEARCH_USERS_BY_NAME_ROUTE,
@@ -23,18 +86,19 @@ pub struct SearchQuery {
     params(
         ("prefix" = String, Path, description = "Username prefix to search for"),
         ("skip" = Option<usize>, Query, description = "Skip N results"),
-        ("limit" = Option<usize>, Query, description = "Limit the number of results")
+        ("limit" = Option<usize>, Query, description = "Limit the number of results"),
+        ("with_total" = Option<bool>, Query, description = "Return the page along with the total number of matches. Defaults to `false`")
     ),
     responses(
-        (status = 200, description = "Search results", body = UserSearch),
+        (status = 200, description = "Search results", body = SearchUsersByNameResponse),
         (status = 400, description = "Invalid input"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn search_users_by_name_handler(
     Path(prefix): Path<String>,
-    Query(query): Query<SearchQuery>,
-) -> Result<Json<UserSearch>> {
+    Query(query): Query<SearchByNameQuery>,
+) -> Result<Json<SearchUsersByNameResponse>> {
     let username = prefix;
     if username.trim().is_empty() {
         return Err(Error::invalid_input("Username cannot be empty"));
@@ -45,10 +109,15 @@ pub async fn search_users_by_name_handler(
     let skip = query.pagination.skip.unwrap_or(0);
     let limit = query.pagination.limit.unwrap_or(200);
 
-    match UserSearch::get_by_name(&username, Some(skip), Some(limit)).await? {
-        Some(user_search) => Ok(Json(user_search)),
-        None => Ok(Json(UserSearch::default())),
+    if query.with_total {
+        let page = UserSearch::get_page_by_name(&username, Some(skip), Some(limit)).await?;
+        return Ok(Json(SearchUsersByNameResponse::Page(page)));
     }
+
+    let user_search = UserSearch::get_by_name(&username, Some(skip), Some(limit)).await?;
+    Ok(Json(SearchUsersByNameResponse::Ids(
+        user_search.unwrap_or_default(),
+    )))
 }
 
 #[utoipa::path(
@@ -91,7 +160,11 @@ pub async fn search_users_by_id_handler(
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(search_users_by_name_handler, search_users_by_id_handler),
-    components(schemas(UserSearch))
+    paths(
+        search_users_handler,
+        search_users_by_name_handler,
+        search_users_by_id_handler
+    ),
+    components(schemas(UserSearch, UserSearchPage, SearchUsersByNameResponse))
 )]
 pub struct SearchUsersApiDocs;
@@ -1,7 +1,7 @@
 use crate::routes::v0::endpoints::{
-    STREAM_POSTS_BY_IDS_ROUTE, STREAM_POSTS_ROUTE, STREAM_POST_KEYS_ROUTE,
-    STREAM_USERS_BY_IDS_ROUTE, STREAM_USERS_ROUTE, STREAM_USERS_USERNAME_SEARCH_ROUTE,
-    STREAM_USER_IDS_ROUTE,
+    STREAM_POSTS_BY_IDS_ROUTE, STREAM_POSTS_FOLLOWED_TAGS_ROUTE, STREAM_POSTS_ROUTE,
+    STREAM_POST_KEYS_ROUTE, STREAM_USERS_BY_IDS_ROUTE, STREAM_USERS_ROUTE,
+    STREAM_USERS_USERNAME_SEARCH_ROUTE, STREAM_USER_IDS_ROUTE,
 };
 use crate::routes::AppState;
 
@@ -22,6 +22,10 @@ pub fn routes() -> Router<AppState> {
         )
         .route(STREAM_POST_KEYS_ROUTE, get(posts::stream_post_keys_handler))
         .route(STREAM_POSTS_ROUTE, get(posts::stream_posts_handler))
+        .route(
+            STREAM_POSTS_FOLLOWED_TAGS_ROUTE,
+            get(posts::stream_followed_tags_posts_handler),
+        )
         .route(
             STREAM_USERS_BY_IDS_ROUTE,
             post(users::stream_users_by_ids_handler),
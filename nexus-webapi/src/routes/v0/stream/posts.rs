@@ -1,13 +1,20 @@
 use crate::models::PostStreamDetailed;
 use crate::routes::v0::endpoints::{
-    STREAM_POSTS_BY_IDS_ROUTE, STREAM_POSTS_ROUTE, STREAM_POST_KEYS_ROUTE,
+    STREAM_POSTS_BY_IDS_ROUTE, STREAM_POSTS_FOLLOWED_TAGS_ROUTE, STREAM_POSTS_ROUTE,
+    STREAM_POST_KEYS_ROUTE,
 };
+use crate::routes::AppState;
 use crate::{Error, Result as AppResult};
-use axum::{extract::Query, Json};
+use axum::extract::{Path, Query, State};
+use axum::Json;
 use nexus_common::db::kv::SortOrder;
-use nexus_common::types::StreamSorting;
+use nexus_common::models::tag::followed::FollowedTags;
+use nexus_common::types::{StreamSorting, Timeframe};
 use nexus_common::{
-    models::post::{PostKeyStream, PostStream, StreamSource},
+    models::post::{
+        PostEngagementCounts, PostKeyStream, PostStream, PostStreamParams, StreamSource,
+        MAX_THREAD_REPLY_DEPTH,
+    },
     types::Pagination,
 };
 use pubky_app_specs::PubkyAppPostKind;
@@ -15,7 +22,10 @@ use serde::{de, Deserialize, Deserializer};
 use tracing::debug;
 use utoipa::{OpenApi, ToSchema};
 
-const MAX_TAGS: usize = 5;
+/// Upper bound of the `min_engagement` a request can ask for
+const MAX_MIN_ENGAGEMENT: u64 = 1000;
+/// Reply depth of a thread ordered stream when `thread_depth` is not given
+const DEFAULT_THREAD_DEPTH: usize = 5;
 
 #[derive(Deserialize, Debug, ToSchema)]
 pub struct PostStreamQuery {
@@ -30,7 +40,15 @@ pub struct PostStreamQuery {
     pub tags: Option<Vec<String>>,
     pub kind: Option<PubkyAppPostKind>,
     #[serde(default)]
+    pub quotes_only: bool,
+    pub min_engagement: Option<u64>,
+    #[serde(default)]
     pub include_attachment_metadata: bool,
+    #[serde(default)]
+    pub include_engagement: bool,
+    #[serde(default)]
+    pub thread_order: bool,
+    pub thread_depth: Option<usize>,
 }
 
 impl PostStreamQuery {
@@ -48,11 +66,77 @@ impl PostStreamQuery {
         )
     }
 
-    pub fn validate_tags(&self) -> AppResult<()> {
+    /// Bounds the number of tags to filter by, see [nexus_common::ApiConfig::max_stream_tags].
+    /// Posts matching any of the tags are streamed
+    pub fn validate_tags(&self, max_tags: usize) -> AppResult<()> {
         if let Some(ref tags) = self.tags {
-            if tags.len() > MAX_TAGS {
+            if tags.len() > max_tags {
+                return Err(Error::invalid_input(&format!(
+                    "Too many tags provided; maximum allowed is {max_tags}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the `(author_id, post_id)` of the post whose replies are streamed in thread order,
+    /// if requested. Thread order only applies to the post replies source, and is neither sorted
+    /// nor filtered otherwise, so it must be checked before [Self::initialize_defaults]
+    pub fn thread_replies_target(&self) -> AppResult<Option<(String, String)>> {
+        if !self.thread_order {
+            return Ok(None);
+        }
+        let unsupported_params = [
+            ("sorting", self.sorting.is_some()),
+            ("order", self.order.is_some()),
+            ("start", self.pagination.start.is_some()),
+            ("end", self.pagination.end.is_some()),
+            ("kind", self.kind.is_some()),
+            ("tags", self.tags.is_some()),
+            ("quotes_only", self.quotes_only),
+        ];
+        if let Some((param, _)) = unsupported_params.iter().find(|(_, is_set)| *is_set) {
+            return Err(Error::invalid_input(&format!(
+                "{param} is not supported with thread_order"
+            )));
+        }
+        match &self.source {
+            Some(StreamSource::PostReplies { author_id, post_id }) => {
+                if let Some(depth) = self.thread_depth {
+                    if depth == 0 || depth > MAX_THREAD_REPLY_DEPTH {
+                        return Err(Error::invalid_input(&format!(
+                            "thread_depth must be between 1 and {MAX_THREAD_REPLY_DEPTH}"
+                        )));
+                    }
+                }
+                Ok(Some((author_id.clone(), post_id.clone())))
+            }
+            _ => Err(Error::invalid_input(
+                "thread_order is only supported with the post_replies source",
+            )),
+        }
+    }
+
+    /// Quote-posts are reposts, which are neither replies to a post nor replies of an author
+    pub fn validate_quotes_only(&self) -> AppResult<()> {
+        if !self.quotes_only {
+            return Ok(());
+        }
+        match self.source {
+            Some(StreamSource::PostReplies { .. }) | Some(StreamSource::AuthorReplies { .. }) => {
+                Err(Error::invalid_input(
+                    "quotes_only is not supported with the post_replies and author_replies sources",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn validate_min_engagement(&self) -> AppResult<()> {
+        if let Some(min_engagement) = self.min_engagement {
+            if min_engagement > MAX_MIN_ENGAGEMENT {
                 return Err(Error::invalid_input(&format!(
-                    "Too many tags provided; maximum allowed is {MAX_TAGS}"
+                    "min_engagement is too high; maximum allowed is {MAX_MIN_ENGAGEMENT}"
                 )));
             }
         }
@@ -85,12 +169,18 @@ where
         ("source" = Option<StreamSource>, Query, description = "Source of posts for streams with viewer (following, followers, friends, bookmarks, post_replies, author, author_replies, all)"),
         ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
         ("observer_id" = Option<String>, Query, description = "Observer Pubky ID. The central point for streams with Reach"),
+        ("timeframe" = Option<Timeframe>, Query, description = "Only for the bookmarks source: keep the posts bookmarked within this timeframe (today, this_month, all_time)"),
         ("author_id" = Option<String>, Query, description = "Filter posts by an specific author User ID"),
+        ("collapse_self_threads" = Option<bool>, Query, description = "Only for the author source: flag root posts the author continued with replies to themselves via `has_self_thread`"),
         ("post_id" = Option<String>, Query, description = "This parameter is needed when we want to retrieve the replies stream for a post"),
         ("sorting" = Option<StreamSorting>, Query, description = "StreamSorting method"),
         ("order" = Option<SortOrder>, Query, description = "Ordering of response list. Either 'ascending' or 'descending'. Defaults to descending."),
-        ("tags" = Option<Vec<String>>, Query, description = "Filter by a list of comma-separated tags (max 5). E.g.,`&tags=dev,free,opensource`. Only posts matching at least one of the tags will be returned."),
+        ("tags" = Option<Vec<String>>, Query, description = "Filter by a list of comma-separated tags (max 5 by default, configurable). E.g.,`&tags=dev,free,opensource`. Only posts matching at least one of the tags will be returned."),
         ("kind" = Option<PubkyAppPostKind>, Query, description = "Specifies the type of posts to retrieve: short, long, image, video, link and file"),
+        ("quotes_only" = Option<bool>, Query, description = "Only retrieve quote-posts, i.e. reposts with their own content. Rejected with the post_replies and author_replies sources, and with thread_order"),
+        ("min_engagement" = Option<u64>, Query, description = "Only for the total_engagement sorting: minimum number of interactions (tags, replies and reposts) of the ranked posts, up to 1000. Defaults to the configured minimum"),
+        ("thread_order" = Option<bool>, Query, description = "Only for the post_replies source: order the replies as they appear in the conversation, each reply followed by its own replies. Paginated with skip and limit only: sorting, order, start, end, kind, tags and quotes_only are rejected"),
+        ("thread_depth" = Option<usize>, Query, description = "Only with thread_order: deepest level of nested replies to include, from 1 to 20. Defaults to 5"),
         ("skip" = Option<usize>, Query, description = "Skip N posts"),
         ("limit" = Option<usize>, Query, description = "Retrieve N posts"),
         ("start" = Option<usize>, Query, description = "The start of the stream timeframe or score. Posts with a timestamp/score greater than this value will be excluded from the results"),
@@ -105,31 +195,57 @@ where
 
 
 The `source` parameter determines the type of stream. Depending on the `source`, certain parameters are required:
-- *following*, *followers*, *friends*, *bookmarks*: Requires **observer_id**.
-- *post_replies*: Requires **author_id** and **post_id** to filter replies to a specific post.
-- *author*:  Requires  **author_id** to filter posts by a specific author.
+- *following*, *followers*, *friends*, *bookmarks*: Requires **observer_id**. *bookmarks* optionally accepts a **timeframe**.
+- *post_replies*: Requires **author_id** and **post_id** to filter replies to a specific post. Optionally accepts **thread_order** and **thread_depth**.
+- *author*:  Requires  **author_id** to filter posts by a specific author. Optionally accepts **collapse_self_threads**.
 - *author_replies*:  Requires  **author_id** to filter replies by a specific author.
 
 Ensure that you provide the necessary parameters based on the selected `source`. If the required parameter is not provided, the provided `source` will be ignored and the stream type will default to *all*"#
 )]
 pub async fn stream_posts_handler(
+    State(state): State<AppState>,
     Query(mut query): Query<PostStreamQuery>,
 ) -> AppResult<Json<PostStreamDetailed>> {
     debug!("GET {STREAM_POSTS_ROUTE}");
 
+    let thread_replies_target = query.thread_replies_target()?;
     query.initialize_defaults();
-    query.validate_tags()?;
-    let (source, sorting, order) = query.extract_stream_params();
+    query.validate_tags(state.max_stream_tags)?;
+    query.validate_min_engagement()?;
+    query.validate_quotes_only()?;
     let include_attachment_metadata = query.include_attachment_metadata;
 
+    if let Some((author_id, post_id)) = thread_replies_target {
+        let stream = PostStream::get_thread_replies(
+            &author_id,
+            &post_id,
+            query.viewer_id,
+            query.thread_depth.unwrap_or(DEFAULT_THREAD_DEPTH),
+            query.pagination.skip.unwrap_or_default(),
+            query.pagination.limit.unwrap_or_default(),
+        )
+        .await?;
+        return match stream {
+            Some(stream) => Ok(Json(
+                PostStreamDetailed::from_post_views(stream.0, include_attachment_metadata).await?,
+            )),
+            None => Ok(Json(PostStreamDetailed::default())),
+        };
+    }
+
+    let (source, sorting, order) = query.extract_stream_params();
     match PostStream::get_posts(
-        source,
-        query.pagination,
-        order,
-        sorting,
+        PostStreamParams {
+            source,
+            pagination: query.pagination,
+            order,
+            sorting,
+            tags: query.tags,
+            kind: query.kind,
+            quotes_only: query.quotes_only,
+            min_engagement: query.min_engagement.or(Some(state.min_engagement)),
+        },
         query.viewer_id,
-        query.tags,
-        query.kind,
     )
     .await?
     {
@@ -147,12 +263,19 @@ pub async fn stream_posts_handler(
     params(
         ("source" = Option<StreamSource>, Query, description = "Source of posts for streams with viewer (following, followers, friends, bookmarks, post_replies, author, author_replies, all)"),
         ("observer_id" = Option<String>, Query, description = "Observer Pubky ID. The central point for streams with Reach"),
+        ("timeframe" = Option<Timeframe>, Query, description = "Only for the bookmarks source: keep the posts bookmarked within this timeframe (today, this_month, all_time)"),
         ("author_id" = Option<String>, Query, description = "Filter posts by an specific author User ID"),
+        ("collapse_self_threads" = Option<bool>, Query, description = "Only for the author source: flag root posts the author continued with replies to themselves via `has_self_thread`"),
         ("post_id" = Option<String>, Query, description = "This parameter is needed when we want to retrieve the replies stream for a post"),
         ("sorting" = Option<StreamSorting>, Query, description = "StreamSorting method"),
         ("order" = Option<SortOrder>, Query, description = "Ordering of response list. Either 'ascending' or 'descending'. Defaults to descending."),
-        ("tags" = Option<Vec<String>>, Query, description = "Filter by a list of comma-separated tags (max 5). E.g.,`&tags=dev,free,opensource`. Only posts matching at least one of the tags will be returned."),
+        ("tags" = Option<Vec<String>>, Query, description = "Filter by a list of comma-separated tags (max 5 by default, configurable). E.g.,`&tags=dev,free,opensource`. Only posts matching at least one of the tags will be returned."),
         ("kind" = Option<PubkyAppPostKind>, Query, description = "Specifies the type of posts to retrieve: short, long, image, video, link and file"),
+        ("quotes_only" = Option<bool>, Query, description = "Only retrieve quote-posts, i.e. reposts with their own content. Rejected with the post_replies and author_replies sources, and with thread_order"),
+        ("min_engagement" = Option<u64>, Query, description = "Only for the total_engagement sorting: minimum number of interactions (tags, replies and reposts) of the ranked posts, up to 1000. Defaults to the configured minimum"),
+        ("include_engagement" = Option<bool>, Query, description = "Include the tags, replies and reposts counts of each post in `engagement`. Defaults to false"),
+        ("thread_order" = Option<bool>, Query, description = "Only for the post_replies source: order the replies as they appear in the conversation, each reply followed by its own replies. Paginated with skip and limit only: sorting, order, start, end, kind, tags and quotes_only are rejected"),
+        ("thread_depth" = Option<usize>, Query, description = "Only with thread_order: deepest level of nested replies to include, from 1 to 20. Defaults to 5"),
         ("skip" = Option<usize>, Query, description = "Skip N posts"),
         ("limit" = Option<usize>, Query, description = "Retrieve N posts"),
         ("start" = Option<usize>, Query, description = "The start of the stream timeframe or score. Posts with a timestamp/score greater than this value will be excluded from the results"),
@@ -165,33 +288,58 @@ pub async fn stream_posts_handler(
     description = r#"Stream Post Keys: Retrieve a stream of post keys
 
 The `source` parameter determines the type of stream. Depending on the `source`, certain parameters are required:
-- *following*, *followers*, *friends*, *bookmarks*: Requires **observer_id**.
-- *post_replies*: Requires **author_id** and **post_id** to filter replies to a specific post.
-- *author*:  Requires  **author_id** to filter posts by a specific author.
+- *following*, *followers*, *friends*, *bookmarks*: Requires **observer_id**. *bookmarks* optionally accepts a **timeframe**.
+- *post_replies*: Requires **author_id** and **post_id** to filter replies to a specific post. Optionally accepts **thread_order** and **thread_depth**.
+- *author*:  Requires  **author_id** to filter posts by a specific author. Optionally accepts **collapse_self_threads**.
 - *author_replies*:  Requires  **author_id** to filter replies by a specific author.
 
 Ensure that you provide the necessary parameters based on the selected `source`. If the required parameter is not provided, the provided `source` will be ignored and the stream type will default to *all*"#
 )]
 pub async fn stream_post_keys_handler(
+    State(state): State<AppState>,
     Query(mut query): Query<PostStreamQuery>,
 ) -> AppResult<Json<PostKeyStream>> {
     debug!("GET {STREAM_POST_KEYS_ROUTE}");
 
+    let thread_replies_target = query.thread_replies_target()?;
     query.initialize_defaults();
-    query.validate_tags()?;
-    let (source, sorting, order) = query.extract_stream_params();
+    query.validate_tags(state.max_stream_tags)?;
+    query.validate_min_engagement()?;
+    query.validate_quotes_only()?;
 
-    match PostStream::get_post_keys(
+    if let Some((author_id, post_id)) = thread_replies_target {
+        let stream = PostStream::get_thread_reply_keys(
+            &author_id,
+            &post_id,
+            query.thread_depth.unwrap_or(DEFAULT_THREAD_DEPTH),
+            query.pagination.skip.unwrap_or_default(),
+            query.pagination.limit.unwrap_or_default(),
+        )
+        .await?;
+        if query.include_engagement {
+            return Ok(Json(stream.with_engagement().await?));
+        }
+        return Ok(Json(stream));
+    }
+
+    let (source, sorting, order) = query.extract_stream_params();
+    match PostStream::get_post_keys(PostStreamParams {
         source,
-        query.pagination,
+        pagination: query.pagination,
         order,
         sorting,
-        query.tags,
-        query.kind,
-    )
+        tags: query.tags,
+        kind: query.kind,
+        quotes_only: query.quotes_only,
+        min_engagement: query.min_engagement.or(Some(state.min_engagement)),
+    })
     .await?
     {
-        Some(stream) => Ok(Json(stream)),
+        Some(stream) if query.include_engagement => Ok(Json(stream.with_engagement().await?)),
+        Some(mut stream) => {
+            stream.engagement = None;
+            Ok(Json(stream))
+        }
         None => Ok(Json(PostKeyStream::default())),
     }
 }
@@ -207,11 +355,11 @@ pub struct PostStreamByIdsRequest {
     post,
     path = STREAM_POSTS_BY_IDS_ROUTE,
     tag = "Stream",
-    description = "Stream post by ID. This is a POST request because we're passing a potentially large list of post IDs in the request body",
+    description = "Stream post by ID. This is a POST request because we're passing a potentially large list of post IDs in the request body. A post that fails to load is left out like a missing one, the request only fails when none of them could be loaded",
     request_body = PostStreamByIdsRequest,
     responses(
         (status = 200, description = "Post stream", body = PostStreamDetailed),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error, none of the posts could be loaded")
     )
 )]
 pub async fn stream_posts_by_ids_handler(
@@ -244,15 +392,93 @@ pub async fn stream_posts_by_ids_handler(
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct FollowedTagsStreamQuery {
+    #[serde(flatten)]
+    pub pagination: Pagination,
+    pub order: Option<SortOrder>,
+    pub sorting: Option<StreamSorting>,
+    pub viewer_id: Option<String>,
+    pub kind: Option<PubkyAppPostKind>,
+    #[serde(default)]
+    pub include_attachment_metadata: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = STREAM_POSTS_FOLLOWED_TAGS_ROUTE,
+    tag = "Stream",
+    description = "Stream of the posts tagged with any of the tags followed by a user, up to the maximum number of tags a post stream can be filtered by",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID, whose followed tags are streamed"),
+        ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
+        ("sorting" = Option<StreamSorting>, Query, description = "StreamSorting method"),
+        ("order" = Option<SortOrder>, Query, description = "Ordering of response list. Either 'ascending' or 'descending'. Defaults to descending."),
+        ("kind" = Option<PubkyAppPostKind>, Query, description = "Specifies the type of posts to retrieve: short, long, image, video, link and file"),
+        ("skip" = Option<usize>, Query, description = "Skip N posts"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N posts"),
+        ("start" = Option<usize>, Query, description = "The start of the stream timeframe or score. Posts with a timestamp/score greater than this value will be excluded from the results"),
+        ("end" = Option<usize>, Query, description = "The end of the stream timeframe or score. Posts with a timestamp/score less than this value will be excluded from the results"),
+        ("include_attachment_metadata" = Option<bool>, Query, description = "Include file metadata for post attachments"),
+    ),
+    responses(
+        (status = 200, description = "Posts stream", body = PostStreamDetailed),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn stream_followed_tags_posts_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Query(query): Query<FollowedTagsStreamQuery>,
+) -> AppResult<Json<PostStreamDetailed>> {
+    debug!("GET {STREAM_POSTS_FOLLOWED_TAGS_ROUTE} user_id:{user_id}");
+
+    let mut followed_tags = FollowedTags::get_by_id(&user_id).await?.0;
+    if followed_tags.is_empty() {
+        return Ok(Json(PostStreamDetailed::default()));
+    }
+    // Tags followed before the cap was lowered are not streamed, the same ones on every request
+    followed_tags.sort();
+    followed_tags.truncate(state.max_stream_tags);
+
+    let mut pagination = query.pagination;
+    pagination.skip.get_or_insert(0);
+    pagination.limit = Some(pagination.limit.unwrap_or(10).min(30));
+
+    match PostStream::get_posts(
+        PostStreamParams {
+            source: StreamSource::All,
+            pagination,
+            order: query.order.unwrap_or_default(),
+            sorting: query.sorting.unwrap_or_default(),
+            tags: Some(followed_tags),
+            kind: query.kind,
+            min_engagement: Some(state.min_engagement),
+            ..Default::default()
+        },
+        query.viewer_id,
+    )
+    .await?
+    {
+        Some(stream) => Ok(Json(
+            PostStreamDetailed::from_post_views(stream.0, query.include_attachment_metadata)
+                .await?,
+        )),
+        None => Ok(Json(PostStreamDetailed::default())),
+    }
+}
+
 #[derive(OpenApi)]
 #[openapi(
     paths(
         stream_posts_handler,
         stream_post_keys_handler,
-        stream_posts_by_ids_handler
+        stream_posts_by_ids_handler,
+        stream_followed_tags_posts_handler
     ),
     components(schemas(
         PostKeyStream,
+        PostEngagementCounts,
         PostStreamDetailed,
         StreamSorting,
         StreamSource,
@@ -185,7 +185,7 @@ pub struct UserStreamByIdsRequest {
     post,
     path = STREAM_USERS_BY_IDS_ROUTE,
     tag = "Stream",
-    description = "Stream users by ID. This is a POST request because we're passing a potentially large list of user IDs in the request body.",
+    description = "Stream users by ID. This is a POST request because we're passing a potentially large list of user IDs in the request body. A user that fails to load is left out like a missing one, the request only fails when none of them could be loaded.",
     request_body = UserStreamByIdsRequest,
     params(
         ("user_ids" = Vec<String>, Path, description = "User Pubky ID array"),
@@ -194,7 +194,7 @@ pub struct UserStreamByIdsRequest {
     ),
     responses(
         (status = 200, description = "Users stream", body = UserStream),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error, none of the users could be loaded")
     )
 )]
 pub async fn stream_users_by_ids_handler(
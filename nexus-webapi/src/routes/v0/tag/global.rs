@@ -1,16 +1,20 @@
-use crate::routes::v0::endpoints::{TAGS_HOT_ROUTE, TAG_TAGGERS_ROUTE};
+use crate::routes::v0::endpoints::{TAGS_HOT_REACHES_ROUTE, TAGS_HOT_ROUTE, TAG_TAGGERS_ROUTE};
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
 use axum::Json;
+use futures_util::future::join_all;
 use nexus_common::models::tag::global::Taggers;
 use nexus_common::models::tag::stream::{HotTag, HotTags};
 use nexus_common::models::tag::TaggedType;
 use nexus_common::models::tag::Taggers as TaggersType;
 use nexus_common::types::routes::HotTagsInputDTO;
 use nexus_common::types::{Pagination, StreamReach, Timeframe};
-use serde::Deserialize;
-use tracing::debug;
-use utoipa::OpenApi;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error};
+use utoipa::{OpenApi, ToSchema};
+
+/// Maximum number of reaches whose hot tags can be retrieved in a single request
+const MAX_HOT_TAGS_REACHES: usize = 8;
 
 #[derive(Deserialize, Debug)]
 pub struct HotTagsQuery {
@@ -128,9 +132,121 @@ pub async fn hot_tags_handler(Query(query): Query<HotTagsQuery>) -> Result<Json<
     }
 }
 
+#[derive(ToSchema, Deserialize)]
+pub struct HotTagsReachesRequest {
+    /// User Pubky ID to base the reaches on
+    pub user_id: String,
+    /// Reach types: `follower` | `following` | `friends` | `wot`
+    pub reaches: Vec<StreamReach>,
+    /// Retrieve N user_id for each tag. Defaults to `20`
+    pub taggers_limit: Option<usize>,
+    /// Retrieve hot tags for this specific timeframe. Defaults to `all_time`
+    pub timeframe: Option<Timeframe>,
+    /// Skip N tags. Defaults to `0`
+    pub skip: Option<usize>,
+    /// Retrieve N tag. Defaults to `40`
+    pub limit: Option<usize>,
+}
+
+#[derive(ToSchema, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotTagsReachStatus {
+    Ok,
+    Error,
+}
+
+/// Outcome of the hot tags of a single reach. Either `hot_tags` or `error` is set, depending on `status`
+#[derive(ToSchema, Serialize)]
+pub struct HotTagsReachResult {
+    pub status: HotTagsReachStatus,
+    pub hot_tags: Option<HotTags>,
+    pub error: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = TAGS_HOT_REACHES_ROUTE,
+    description = "Global Tags of several reaches. Returns the result of each reach in the same order. A reach that fails doesn't fail the others, it is marked with an `error` status",
+    tag = "Tags",
+    request_body = HotTagsReachesRequest,
+    responses(
+        (status = 200, description = "Tags of each reach, at least one of them retrieved", body = Vec<HotTagsReachResult>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error, all the reaches failed")
+    )
+)]
+pub async fn hot_tags_reaches_handler(
+    Json(request): Json<HotTagsReachesRequest>,
+) -> Result<Json<Vec<HotTagsReachResult>>> {
+    debug!(
+        "POST {TAGS_HOT_REACHES_ROUTE} user_id: {}, reaches: {:?}",
+        request.user_id, request.reaches
+    );
+
+    if request.reaches.is_empty() || request.reaches.len() > MAX_HOT_TAGS_REACHES {
+        let err_msg = format!("Between 1 and {MAX_HOT_TAGS_REACHES} reaches are allowed");
+        return Err(Error::invalid_input(&err_msg));
+    }
+
+    let input = HotTagsInputDTO {
+        timeframe: request.timeframe.unwrap_or(Timeframe::AllTime),
+        skip: request.skip.unwrap_or(0),
+        limit: request.limit.unwrap_or(40).min(40),
+        taggers_limit: request.taggers_limit.unwrap_or(20).min(20),
+        tagged_type: Some(TaggedType::Post),
+    };
+
+    let reach_results = join_all(request.reaches.iter().map(|reach| {
+        HotTags::get_hot_tags(Some(request.user_id.clone()), Some(reach.clone()), &input)
+    }))
+    .await;
+
+    let mut last_error = None;
+    let mut results = Vec::with_capacity(reach_results.len());
+    for (reach, reach_result) in request.reaches.iter().zip(reach_results) {
+        match reach_result {
+            Ok(hot_tags) => results.push(HotTagsReachResult {
+                status: HotTagsReachStatus::Ok,
+                hot_tags: Some(hot_tags.unwrap_or_default()),
+                error: None,
+            }),
+            Err(e) => {
+                error!("Failed to get the hot tags of reach {reach:?}: {e}");
+                results.push(HotTagsReachResult {
+                    status: HotTagsReachStatus::Error,
+                    hot_tags: None,
+                    error: Some(format!("Failed to get the hot tags of reach {reach:?}")),
+                });
+                last_error = Some(e);
+            }
+        }
+    }
+
+    // Only fail the whole request when no reach could be retrieved
+    match last_error {
+        Some(e)
+            if results
+                .iter()
+                .all(|r| r.status == HotTagsReachStatus::Error) =>
+        {
+            Err(e.into())
+        }
+        _ => Ok(Json(results)),
+    }
+}
+
 #[derive(OpenApi)]
 #[openapi(
-    paths(hot_tags_handler, tag_taggers_handler),
-    components(schemas(HotTags, HotTag, Taggers, StreamReach, Timeframe))
+    paths(hot_tags_handler, hot_tags_reaches_handler, tag_taggers_handler),
+    components(schemas(
+        HotTags,
+        HotTag,
+        Taggers,
+        StreamReach,
+        Timeframe,
+        HotTagsReachesRequest,
+        HotTagsReachStatus,
+        HotTagsReachResult
+    ))
 )]
 pub struct TagGlobalApiDoc;
@@ -1,6 +1,8 @@
-use crate::routes::v0::endpoints::{TAGS_HOT_ROUTE, TAG_ROUTE, TAG_TAGGERS_ROUTE};
+use crate::routes::v0::endpoints::{
+    TAGS_HOT_REACHES_ROUTE, TAGS_HOT_ROUTE, TAG_ROUTE, TAG_TAGGERS_ROUTE,
+};
 use crate::routes::AppState;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::Router;
 use utoipa::OpenApi;
 
@@ -10,6 +12,10 @@ mod view;
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route(TAGS_HOT_ROUTE, get(global::hot_tags_handler))
+        .route(
+            TAGS_HOT_REACHES_ROUTE,
+            post(global::hot_tags_reaches_handler),
+        )
         .route(TAG_TAGGERS_ROUTE, get(global::tag_taggers_handler))
         .route(TAG_ROUTE, get(view::tag_view_handler))
 }
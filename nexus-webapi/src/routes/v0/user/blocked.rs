@@ -0,0 +1,45 @@
+use crate::routes::v0::endpoints::USER_BLOCKED_ROUTE;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use nexus_common::models::user::{Blocked, UserCounts};
+use nexus_common::types::Pagination;
+use tracing::debug;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    path = USER_BLOCKED_ROUTE,
+    description = "List the IDs of the users blocked by the user",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("skip" = Option<usize>, Query, description = "Skip N blocked users"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N blocked users")
+    ),
+    responses(
+        (status = 200, description = "User blocked list", body = Blocked),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_blocked_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<Blocked>> {
+    debug!("GET {USER_BLOCKED_ROUTE} user_id:{}", user_id);
+
+    if UserCounts::get_by_id(&user_id).await?.is_none() {
+        return Err(Error::UserNotFound { user_id });
+    }
+
+    let skip = query.skip.unwrap_or(0);
+    let limit = query.limit.unwrap_or(200);
+
+    let blocked = Blocked::get_by_id(&user_id, Some(skip), Some(limit)).await?;
+    Ok(Json(blocked))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(user_blocked_handler), components(schemas(Blocked)))]
+pub struct UserBlockedApiDoc;
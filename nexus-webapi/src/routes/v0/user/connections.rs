@@ -0,0 +1,49 @@
+use crate::routes::v0::endpoints::USER_TOP_CONNECTIONS_ROUTE;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use nexus_common::models::user::{TopConnection, TopConnections, UserCounts, MAX_TOP_CONNECTIONS};
+use serde::Deserialize;
+use tracing::debug;
+use utoipa::OpenApi;
+
+#[derive(Deserialize)]
+pub struct TopConnectionsQuery {
+    limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_TOP_CONNECTIONS_ROUTE,
+    tag = "User",
+    description = "Users the user has interacted with the most, by replies, reposts and tags directed at them. Refreshed hourly",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("limit" = Option<usize>, Query, description = "Number of connections, 10 by default and 100 at most")
+    ),
+    responses(
+        (status = 200, description = "Top connections, most interactions first", body = TopConnections),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_top_connections_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<TopConnectionsQuery>,
+) -> Result<Json<TopConnections>> {
+    debug!("GET {USER_TOP_CONNECTIONS_ROUTE} user_id:{}", user_id);
+
+    if UserCounts::get_by_id(&user_id).await?.is_none() {
+        return Err(Error::UserNotFound { user_id });
+    }
+
+    let limit = query.limit.unwrap_or(10).min(MAX_TOP_CONNECTIONS);
+    Ok(Json(TopConnections::get_by_id(&user_id, limit).await?))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(user_top_connections_handler),
+    components(schemas(TopConnections, TopConnection))
+)]
+pub struct TopConnectionsApiDoc;
@@ -1,10 +1,18 @@
-use crate::routes::v0::endpoints::USER_COUNTS_ROUTE;
+use crate::routes::v0::endpoints::{
+    USERS_COUNTS_ROUTE, USER_COUNTS_ROUTE, USER_FOLLOWER_HISTORY_ROUTE, USER_FOLLOWER_STATS_ROUTE,
+};
 use crate::{Error, Result};
-use axum::extract::Path;
+use axum::extract::{Path, Query};
 use axum::Json;
-use nexus_common::models::user::UserCounts;
+use nexus_common::models::follow::FollowStats;
+use nexus_common::models::user::{FollowerSnapshot, UserCounts};
+use nexus_common::types::Timeframe;
+use serde::Deserialize;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
+
+/// Maximum number of users whose counts can be retrieved in a single request
+const MAX_COUNTS_USERS: usize = 100;
 
 #[utoipa::path(
     get,
@@ -29,6 +37,123 @@ pub async fn user_counts_handler(Path(user_id): Path<String>) -> Result<Json<Use
     }
 }
 
+// This is a POST request because the list of user IDs could exceed URL length limits
+#[derive(ToSchema, Deserialize)]
+pub struct UsersCountsRequest {
+    pub user_ids: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = USERS_COUNTS_ROUTE,
+    tag = "User",
+    description = "Counts of the listed users. Returns the counts of each user in the same order, null for the users that don't exist.",
+    request_body = UsersCountsRequest,
+    responses(
+        (status = 200, description = "Counts of each listed user", body = Vec<Option<UserCounts>>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn users_counts_handler(
+    Json(request): Json<UsersCountsRequest>,
+) -> Result<Json<Vec<Option<UserCounts>>>> {
+    debug!(
+        "POST {USERS_COUNTS_ROUTE} user_ids size {:?}",
+        request.user_ids.len()
+    );
+
+    if request.user_ids.len() > MAX_COUNTS_USERS {
+        let err_msg = format!("The maximum number of user IDs allowed is {MAX_COUNTS_USERS}");
+        return Err(Error::invalid_input(&err_msg));
+    }
+
+    let counts = UserCounts::get_by_ids(&request.user_ids).await?;
+    Ok(Json(counts))
+}
+
+#[derive(Deserialize)]
+pub struct FollowerHistoryQuery {
+    from: Option<i64>,
+    to: Option<i64>,
+    bucket: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_FOLLOWER_HISTORY_ROUTE,
+    tag = "User",
+    description = "User follower count over time",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("from" = Option<i64>, Query, description = "Oldest snapshot timestamp to include, in milliseconds"),
+        ("to" = Option<i64>, Query, description = "Newest snapshot timestamp to include, in milliseconds"),
+        ("bucket" = Option<i64>, Query, description = "Keep only the latest snapshot of every bucket of this size, in milliseconds")
+    ),
+    responses(
+        (status = 200, description = "Follower count snapshots, oldest first", body = Vec<FollowerSnapshot>),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_follower_history_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<FollowerHistoryQuery>,
+) -> Result<Json<Vec<FollowerSnapshot>>> {
+    debug!("GET {USER_FOLLOWER_HISTORY_ROUTE} user_id:{}", user_id);
+
+    if UserCounts::get_by_id(&user_id).await?.is_none() {
+        return Err(Error::UserNotFound { user_id });
+    }
+
+    let history =
+        UserCounts::follower_history(&user_id, query.from, query.to, query.bucket).await?;
+    Ok(Json(history))
+}
+
+#[derive(Deserialize)]
+pub struct FollowerStatsQuery {
+    timeframe: Option<Timeframe>,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_FOLLOWER_STATS_ROUTE,
+    tag = "User",
+    description = "Followers the user gained within a timeframe",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("timeframe" = Option<Timeframe>, Query, description = "Timeframe of the follows to count (today, this_month, all_time). Defaults to all_time")
+    ),
+    responses(
+        (status = 200, description = "User follower growth", body = FollowStats),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_follower_stats_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<FollowerStatsQuery>,
+) -> Result<Json<FollowStats>> {
+    debug!("GET {USER_FOLLOWER_STATS_ROUTE} user_id:{}", user_id);
+
+    if UserCounts::get_by_id(&user_id).await?.is_none() {
+        return Err(Error::UserNotFound { user_id });
+    }
+
+    let timeframe = query.timeframe.unwrap_or(Timeframe::AllTime);
+    let stats = FollowStats::followers_gained_since(&user_id, &timeframe).await?;
+    Ok(Json(stats))
+}
+
 #[derive(OpenApi)]
-#[openapi(paths(user_counts_handler), components(schemas(UserCounts)))]
+#[openapi(
+    paths(
+        user_counts_handler,
+        users_counts_handler,
+        user_follower_history_handler,
+        user_follower_stats_handler
+    ),
+    components(schemas(UserCounts, UsersCountsRequest, FollowerSnapshot, FollowStats))
+)]
 pub struct UserCountsApiDoc;
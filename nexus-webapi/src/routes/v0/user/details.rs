@@ -2,7 +2,7 @@ use crate::routes::v0::endpoints::USER_DETAILS_ROUTE;
 use crate::{Error, Result};
 use axum::extract::Path;
 use axum::Json;
-use nexus_common::models::user::UserDetails;
+use nexus_common::models::user::{UserDetails, USER_DELETED_SENTINEL};
 use pubky_app_specs::{PubkyAppUserLink, PubkyId};
 use tracing::debug;
 use utoipa::OpenApi;
@@ -18,6 +18,7 @@ use utoipa::OpenApi;
     responses(
         (status = 200, description = "User details", body = UserDetails),
         (status = 404, description = "User not found"),
+        (status = 410, description = "User deleted"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -25,8 +26,10 @@ pub async fn user_details_handler(Path(user_id): Path<String>) -> Result<Json<Us
     debug!("GET {USER_DETAILS_ROUTE} user_id:{}", user_id);
 
     match UserDetails::get_by_id(&user_id).await? {
+        // A user deleted while they had relationships is only kept as a placeholder
+        Some(details) if details.name == USER_DELETED_SENTINEL => Err(Error::UserGone { user_id }),
         Some(details) => Ok(Json(details)),
-        None => Err(Error::UserNotFound { user_id }),
+        None => Err(Error::missing_user(user_id).await),
     }
 }
 
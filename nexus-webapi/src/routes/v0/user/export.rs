@@ -0,0 +1,94 @@
+use crate::models::SignedRequest;
+use crate::routes::v0::endpoints::USER_EXPORT_ROUTE;
+use crate::routes::AppState;
+use crate::{Error, Result};
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+use futures_util::StreamExt;
+use nexus_common::models::user::{UserDetails, UserExportRecord};
+use nexus_common::types::DynError;
+use subtle::ConstantTimeEq;
+use tracing::debug;
+use utoipa::OpenApi;
+
+/// Content type of the export, one JSON record per line
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Checks the request is signed by the exported user, see [SignedRequest].
+///
+/// An operator holding the `Bearer` token configured for the export can export any user instead.
+async fn authorize(
+    state: &AppState,
+    headers: &HeaderMap,
+    signed: &SignedRequest,
+    user_id: &str,
+) -> Result<()> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let Some(token) = token else {
+        return signed.authorize(user_id).await;
+    };
+    // Compared in constant time, so that the response time doesn't leak how much of it matches
+    match state.export_token.as_deref() {
+        Some(export_token) if bool::from(token.as_bytes().ct_eq(export_token.as_bytes())) => Ok(()),
+        _ => Err(Error::Unauthorized {
+            message: "Invalid export token".to_string(),
+        }),
+    }
+}
+
+/// Serializes a chunk of records as NDJSON lines
+fn to_ndjson(records: Vec<UserExportRecord>) -> core::result::Result<String, DynError> {
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&serde_json::to_string(&record)?);
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+#[utoipa::path(
+    get,
+    path = USER_EXPORT_ROUTE,
+    description = "Export all the data of a user (profile, posts, tags, follows, followers and bookmarks) as NDJSON, one `{\"type\", \"data\"}` record per line. The request must be signed by the user, or carry the operator export token as a `Bearer` token",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID")
+    ),
+    responses(
+        (status = 200, description = "User data export", content_type = "application/x-ndjson", body = String),
+        (status = 401, description = "Missing or invalid signature or export token"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_export_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    signed: SignedRequest,
+) -> Result<Response> {
+    debug!("GET {USER_EXPORT_ROUTE} user_id:{}", user_id);
+
+    authorize(&state, &headers, &signed, &user_id).await?;
+    if UserDetails::get_by_id(&user_id).await?.is_none() {
+        return Err(Error::UserNotFound { user_id });
+    }
+
+    // The export is streamed chunk by chunk, never held in memory at once
+    let lines = UserDetails::export(user_id).map(|chunk| to_ndjson(chunk?));
+    Ok((
+        [(CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(lines),
+    )
+        .into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(user_export_handler))]
+pub struct UserExportApiDoc;
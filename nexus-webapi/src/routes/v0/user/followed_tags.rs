@@ -0,0 +1,152 @@
+use crate::models::SignedRequest;
+use crate::routes::v0::endpoints::{
+    USER_FOLLOWED_TAGS_ROUTE, USER_FOLLOWED_TAG_ROUTE, USER_SUGGESTED_TAGS_ROUTE,
+};
+use crate::routes::AppState;
+use crate::{Error, Result};
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use nexus_common::models::tag::followed::FollowedTags;
+use nexus_common::models::tag::label;
+use pubky_app_specs::PubkyId;
+use serde::Deserialize;
+use tracing::debug;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    path = USER_FOLLOWED_TAGS_ROUTE,
+    tag = "User",
+    description = "Tags followed by a user",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID")
+    ),
+    responses(
+        (status = 200, description = "Followed tags", body = FollowedTags),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_followed_tags_handler(Path(user_id): Path<String>) -> Result<Json<FollowedTags>> {
+    debug!("GET {USER_FOLLOWED_TAGS_ROUTE} user_id:{user_id}");
+
+    Ok(Json(FollowedTags::get_by_id(&user_id).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = USER_FOLLOWED_TAG_ROUTE,
+    tag = "User",
+    description = "Follow a tag. The posts tagged with it are included in the followed tags stream of the user, so a user follows at most as many tags as a post stream can be filtered by. The request must be signed by the user",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("label" = String, Path, description = "Tag label"),
+        ("Authorization" = String, Header, description = "Signature of the request by the user: `PubkySig <timestamp>:<signature>`")
+    ),
+    responses(
+        (status = 200, description = "Updated followed tags", body = FollowedTags),
+        (status = 400, description = "Invalid user ID or label, or too many followed tags"),
+        (status = 401, description = "The request is not signed by the user"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "The API is in read-only mode")
+    )
+)]
+pub async fn follow_tag_handler(
+    State(state): State<AppState>,
+    Path((user_id, label)): Path<(String, String)>,
+    signed: SignedRequest,
+) -> Result<Json<FollowedTags>> {
+    debug!("PUT {USER_FOLLOWED_TAG_ROUTE} user_id:{user_id}, label:{label}");
+
+    PubkyId::try_from(&user_id)
+        .map_err(|e| Error::invalid_input(&format!("Invalid user PK: {e}")))?;
+    signed.authorize(&user_id).await?;
+
+    let label = label::normalize(&label);
+    if label.is_empty() {
+        return Err(Error::invalid_input("Tag label cannot be empty"));
+    }
+
+    let followed = FollowedTags::get_by_id(&user_id).await?;
+    let max_tags = state.max_stream_tags;
+    if followed.0.len() >= max_tags && !followed.0.contains(&label) {
+        return Err(Error::invalid_input(&format!(
+            "A user cannot follow more than {max_tags} tags"
+        )));
+    }
+
+    FollowedTags::follow(&user_id, &label).await?;
+    Ok(Json(FollowedTags::get_by_id(&user_id).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = USER_FOLLOWED_TAG_ROUTE,
+    tag = "User",
+    description = "Unfollow a tag. The request must be signed by the user",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("label" = String, Path, description = "Tag label"),
+        ("Authorization" = String, Header, description = "Signature of the request by the user: `PubkySig <timestamp>:<signature>`")
+    ),
+    responses(
+        (status = 200, description = "Updated followed tags", body = FollowedTags),
+        (status = 400, description = "Invalid user ID"),
+        (status = 401, description = "The request is not signed by the user"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "The API is in read-only mode")
+    )
+)]
+pub async fn unfollow_tag_handler(
+    Path((user_id, label)): Path<(String, String)>,
+    signed: SignedRequest,
+) -> Result<Json<FollowedTags>> {
+    debug!("DELETE {USER_FOLLOWED_TAG_ROUTE} user_id:{user_id}, label:{label}");
+
+    PubkyId::try_from(&user_id)
+        .map_err(|e| Error::invalid_input(&format!("Invalid user PK: {e}")))?;
+    signed.authorize(&user_id).await?;
+
+    FollowedTags::unfollow(&user_id, &label).await?;
+    Ok(Json(FollowedTags::get_by_id(&user_id).await?))
+}
+
+#[derive(Deserialize)]
+pub struct SuggestedTagsQuery {
+    limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_SUGGESTED_TAGS_ROUTE,
+    tag = "User",
+    description = "Tags suggested for a user to follow. Hot tags of the user network come first, then global hot tags. Tags the user already follows are excluded",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("limit" = Option<usize>, Query, description = "Number of suggested tags, 10 by default and 50 at most")
+    ),
+    responses(
+        (status = 200, description = "Suggested tags", body = Vec<String>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_suggested_tags_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<SuggestedTagsQuery>,
+) -> Result<Json<Vec<String>>> {
+    debug!("GET {USER_SUGGESTED_TAGS_ROUTE} user_id:{user_id}");
+
+    let limit = query.limit.unwrap_or(10).min(50);
+    Ok(Json(FollowedTags::suggest(&user_id, limit).await?))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        user_followed_tags_handler,
+        follow_tag_handler,
+        unfollow_tag_handler,
+        user_suggested_tags_handler
+    ),
+    components(schemas(FollowedTags))
+)]
+pub struct FollowedTagsApiDoc;
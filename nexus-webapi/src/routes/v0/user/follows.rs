@@ -1,13 +1,18 @@
 use crate::routes::v0::endpoints::{
-    USER_FOLLOWERS_ROUTE, USER_FOLLOWING_ROUTE, USER_FRIENDS_ROUTE,
+    USERS_FOLLOWING_STATUS_ROUTE, USER_FOLLOWERS_ROUTE, USER_FOLLOWING_ROUTE, USER_FRIENDS_ROUTE,
+    USER_MUTUAL_ROUTE,
 };
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
 use axum::Json;
-use nexus_common::models::follow::{Followers, Following, Friends, UserFollows};
+use nexus_common::models::follow::{Followers, Following, Friends, MutualFollowers, UserFollows};
 use nexus_common::types::Pagination;
+use serde::Deserialize;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
+
+/// Maximum number of users whose following status can be checked in a single request
+const MAX_FOLLOWING_STATUS_USERS: usize = 100;
 
 #[utoipa::path(
     get,
@@ -102,9 +107,95 @@ pub async fn user_friends_handler(
     }
 }
 
+#[derive(Deserialize)]
+pub struct MutualQuery {
+    pub viewer_id: String,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_MUTUAL_ROUTE,
+    description = "List the followers of the user that the viewer follows",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("viewer_id" = String, Query, description = "Viewer Pubky ID"),
+        ("skip" = Option<usize>, Query, description = "Skip N mutual followers"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N mutual followers")
+    ),
+    responses(
+        (status = 200, description = "Mutual followers of the user and the viewer", body = MutualFollowers),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_mutual_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<MutualQuery>,
+) -> Result<Json<MutualFollowers>> {
+    debug!(
+        "GET {USER_MUTUAL_ROUTE} user_id:{}, viewer_id:{}",
+        user_id, query.viewer_id
+    );
+
+    let skip = query.pagination.skip.unwrap_or(0);
+    let limit = query.pagination.limit.unwrap_or(200);
+
+    match Followers::mutual_with(&user_id, &query.viewer_id, Some(skip), Some(limit)).await? {
+        Some(mutuals) => Ok(Json(mutuals)),
+        None => Err(Error::UserNotFound { user_id }),
+    }
+}
+
+// This is a POST request because the list of user IDs could exceed URL length limits
+#[derive(ToSchema, Deserialize)]
+pub struct FollowingStatusRequest {
+    pub viewer_id: String,
+    pub user_ids: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = USERS_FOLLOWING_STATUS_ROUTE,
+    description = "Check which of the listed users the viewer follows. Returns one flag per user, in the same order.",
+    tag = "User",
+    request_body = FollowingStatusRequest,
+    responses(
+        (status = 200, description = "Whether the viewer follows each listed user", body = Vec<bool>),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn users_following_status_handler(
+    Json(request): Json<FollowingStatusRequest>,
+) -> Result<Json<Vec<bool>>> {
+    debug!(
+        "POST {USERS_FOLLOWING_STATUS_ROUTE} viewer_id:{} user_ids:{:?}",
+        request.viewer_id, request.user_ids
+    );
+
+    if request.user_ids.len() > MAX_FOLLOWING_STATUS_USERS {
+        let err_msg =
+            format!("The maximum number of user IDs allowed is {MAX_FOLLOWING_STATUS_USERS}");
+        return Err(Error::invalid_input(&err_msg));
+    }
+
+    let user_ids: Vec<&str> = request.user_ids.iter().map(String::as_str).collect();
+    let statuses = Following::check_batch(&request.viewer_id, &user_ids).await?;
+    Ok(Json(statuses))
+}
+
 #[derive(OpenApi)]
 #[openapi(
-    paths(user_followers_handler, user_following_handler, user_friends_handler),
-    components(schemas(Followers, Following, Friends))
+    paths(
+        user_followers_handler,
+        user_following_handler,
+        user_friends_handler,
+        user_mutual_handler,
+        users_following_status_handler
+    ),
+    components(schemas(Followers, Following, Friends, MutualFollowers, FollowingStatusRequest))
 )]
 pub struct UserFollowsApiDoc;
@@ -1,16 +1,25 @@
 use crate::routes::v0::endpoints::{
-    RELATIONSHIP_ROUTE, USER_COUNTS_ROUTE, USER_DETAILS_ROUTE, USER_FOLLOWERS_ROUTE,
-    USER_FOLLOWING_ROUTE, USER_FRIENDS_ROUTE, USER_ROUTE, USER_TAGGERS_ROUTE, USER_TAGS_ROUTE,
+    RELATIONSHIP_ROUTE, USERS_COUNTS_ROUTE, USERS_FOLLOWING_STATUS_ROUTE,
+    USER_APPLIED_LABELS_ROUTE, USER_BLOCKED_ROUTE, USER_COUNTS_ROUTE, USER_DETAILS_ROUTE,
+    USER_EXPORT_ROUTE, USER_FOLLOWED_TAGS_ROUTE, USER_FOLLOWED_TAG_ROUTE, USER_FOLLOWERS_ROUTE,
+    USER_FOLLOWER_HISTORY_ROUTE, USER_FOLLOWER_STATS_ROUTE, USER_FOLLOWING_ROUTE,
+    USER_FRIENDS_ROUTE, USER_MUTUAL_ROUTE, USER_RECOMMENDATIONS_ROUTE, USER_ROUTE,
+    USER_SUGGESTED_TAGS_ROUTE, USER_TAGGERS_ROUTE, USER_TAGS_ROUTE, USER_TOP_CONNECTIONS_ROUTE,
 };
 use crate::routes::AppState;
 
-use axum::routing::get;
+use axum::routing::{get, post, put};
 use axum::Router;
 use utoipa::OpenApi;
 
+mod blocked;
+mod connections;
 mod counts;
 mod details;
+mod export;
+mod followed_tags;
 mod follows;
+mod recommendations;
 mod relationship;
 pub mod tags;
 mod view;
@@ -19,16 +28,56 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route(USER_ROUTE, get(view::user_view_handler))
         .route(USER_DETAILS_ROUTE, get(details::user_details_handler))
+        .route(USER_EXPORT_ROUTE, get(export::user_export_handler))
         .route(
             RELATIONSHIP_ROUTE,
             get(relationship::user_relationship_handler),
         )
         .route(USER_TAGS_ROUTE, get(tags::user_tags_handler))
         .route(USER_TAGGERS_ROUTE, get(tags::user_taggers_handler))
+        .route(
+            USER_APPLIED_LABELS_ROUTE,
+            get(tags::user_applied_labels_handler),
+        )
         .route(USER_COUNTS_ROUTE, get(counts::user_counts_handler))
+        .route(USERS_COUNTS_ROUTE, post(counts::users_counts_handler))
         .route(USER_FOLLOWERS_ROUTE, get(follows::user_followers_handler))
+        .route(
+            USER_FOLLOWER_HISTORY_ROUTE,
+            get(counts::user_follower_history_handler),
+        )
+        .route(
+            USER_FOLLOWER_STATS_ROUTE,
+            get(counts::user_follower_stats_handler),
+        )
         .route(USER_FOLLOWING_ROUTE, get(follows::user_following_handler))
         .route(USER_FRIENDS_ROUTE, get(follows::user_friends_handler))
+        .route(USER_MUTUAL_ROUTE, get(follows::user_mutual_handler))
+        .route(USER_BLOCKED_ROUTE, get(blocked::user_blocked_handler))
+        .route(
+            USER_TOP_CONNECTIONS_ROUTE,
+            get(connections::user_top_connections_handler),
+        )
+        .route(
+            USER_RECOMMENDATIONS_ROUTE,
+            get(recommendations::user_recommendations_handler),
+        )
+        .route(
+            USERS_FOLLOWING_STATUS_ROUTE,
+            post(follows::users_following_status_handler),
+        )
+        .route(
+            USER_FOLLOWED_TAGS_ROUTE,
+            get(followed_tags::user_followed_tags_handler),
+        )
+        .route(
+            USER_FOLLOWED_TAG_ROUTE,
+            put(followed_tags::follow_tag_handler).delete(followed_tags::unfollow_tag_handler),
+        )
+        .route(
+            USER_SUGGESTED_TAGS_ROUTE,
+            get(followed_tags::user_suggested_tags_handler),
+        )
 }
 
 #[derive(OpenApi)]
@@ -43,6 +92,11 @@ impl UserApiDoc {
         combined.merge(relationship::RelationshipApiDoc::openapi());
         combined.merge(tags::UserTagsApiDoc::openapi());
         combined.merge(follows::UserFollowsApiDoc::openapi());
+        combined.merge(followed_tags::FollowedTagsApiDoc::openapi());
+        combined.merge(connections::TopConnectionsApiDoc::openapi());
+        combined.merge(blocked::UserBlockedApiDoc::openapi());
+        combined.merge(recommendations::RecommendationsApiDoc::openapi());
+        combined.merge(export::UserExportApiDoc::openapi());
         combined
     }
 }
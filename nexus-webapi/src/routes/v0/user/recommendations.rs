@@ -0,0 +1,51 @@
+use crate::routes::v0::endpoints::USER_RECOMMENDATIONS_ROUTE;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use nexus_common::models::user::{
+    Recommendation, Recommendations, UserCounts, MAX_RECOMMENDATIONS,
+};
+use serde::Deserialize;
+use tracing::debug;
+use utoipa::OpenApi;
+
+#[derive(Deserialize)]
+pub struct RecommendationsQuery {
+    limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_RECOMMENDATIONS_ROUTE,
+    tag = "User",
+    description = "Users to follow: the users followed by the users the user follows, ranked by how many of them follow each. Users already followed or blocked are left out",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("limit" = Option<usize>, Query, description = "Number of recommendations, 10 by default and 100 at most")
+    ),
+    responses(
+        (status = 200, description = "Recommended users, most shared follows first", body = Recommendations),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_recommendations_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<RecommendationsQuery>,
+) -> Result<Json<Recommendations>> {
+    debug!("GET {USER_RECOMMENDATIONS_ROUTE} user_id:{}", user_id);
+
+    if UserCounts::get_by_id(&user_id).await?.is_none() {
+        return Err(Error::UserNotFound { user_id });
+    }
+
+    let limit = query.limit.unwrap_or(10).min(MAX_RECOMMENDATIONS);
+    Ok(Json(Recommendations::who_to_follow(&user_id, limit).await?))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(user_recommendations_handler),
+    components(schemas(Recommendations, Recommendation))
+)]
+pub struct RecommendationsApiDoc;
@@ -1,4 +1,6 @@
-use crate::routes::v0::endpoints::{USER_TAGGERS_ROUTE, USER_TAGS_ROUTE};
+use crate::routes::v0::endpoints::{
+    USER_APPLIED_LABELS_ROUTE, USER_TAGGERS_ROUTE, USER_TAGS_ROUTE,
+};
 use crate::routes::v0::{TaggersInfoResponse, TagsQuery};
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
@@ -105,9 +107,42 @@ pub async fn user_taggers_handler(
     Ok(Json(TaggersInfoResponse::from(taggers)))
 }
 
+#[utoipa::path(
+    get,
+    path = USER_APPLIED_LABELS_ROUTE,
+    description = "Distinct labels the user has tagged posts and users with, most used first",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("skip" = Option<usize>, Query, description = "Number of labels to skip for pagination. **Default** value 0"),
+        ("limit" = Option<usize>, Query, description = "Number of labels to return for pagination. **Default** value 20"),
+    ),
+    responses(
+        (status = 200, description = "Labels applied by the user", body = Vec<String>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_applied_labels_handler(
+    Path(user_id): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Vec<String>>> {
+    debug!(
+        "GET {USER_APPLIED_LABELS_ROUTE} user_id:{}, skip:{:?}, limit:{:?}",
+        user_id, pagination.skip, pagination.limit
+    );
+
+    let labels = TagUser::get_tagger_labels(
+        &user_id,
+        Some(pagination.skip.unwrap_or(0)),
+        Some(pagination.limit.unwrap_or(20)),
+    )
+    .await?;
+    Ok(Json(labels))
+}
+
 #[derive(OpenApi)]
 #[openapi(
-    paths(user_tags_handler, user_taggers_handler),
+    paths(user_tags_handler, user_taggers_handler, user_applied_labels_handler),
     components(schemas(TagDetails, TaggersInfoResponse))
 )]
 pub struct UserTagsApiDoc;
@@ -1,9 +1,12 @@
+use crate::models::{ETagJson, SignedRequest};
 use crate::routes::v0::endpoints::USER_ROUTE;
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
-use axum::Json;
+use axum::http::HeaderMap;
+use nexus_common::models::moderation::ModerationInfo;
 use nexus_common::models::tag::TagDetails;
-use nexus_common::models::user::UserView;
+use nexus_common::models::user::{UserView, USER_DELETED_SENTINEL};
+use pubky_app_specs::PubkyId;
 use serde::Deserialize;
 use tracing::debug;
 use utoipa::OpenApi;
@@ -12,6 +15,8 @@ use utoipa::OpenApi;
 pub struct ProfileQuery {
     viewer_id: Option<String>,
     depth: Option<u8>,
+    #[serde(default)]
+    include_moderated: bool,
 }
 
 #[utoipa::path(
@@ -22,29 +27,58 @@ pub struct ProfileQuery {
     params(
         ("user_id" = String, Path, description = "User Pubky ID"),
         ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
-        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored")
+        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored"),
+        ("include_moderated" = Option<bool>, Query, description = "If the user was removed by the moderator and the viewer is that moderator, return a placeholder with the `moderated` reason instead of 404. The request must be signed by the viewer"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previously fetched profile, answered with 304 if the profile did not change"),
+        ("Authorization" = Option<String>, Header, description = "Signature of the request by the viewer, `PubkySig <timestamp>:<signature>`, needed for `include_moderated`")
     ),
     responses(
-        (status = 200, description = "User Profile", body = UserView),
+        (status = 200, description = "User Profile, with its weak ETag in the `ETag` header", body = UserView),
+        (status = 304, description = "The user profile did not change since the given ETag"),
         (status = 404, description = "User not found"),
+        (status = 410, description = "User deleted"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn user_view_handler(
     Path(user_id): Path<String>,
     Query(query): Query<ProfileQuery>,
-) -> Result<Json<UserView>> {
+    headers: HeaderMap,
+    signed: SignedRequest,
+) -> Result<ETagJson<UserView>> {
     debug!(
         "GET {USER_ROUTE} user_id:{}, viewer_id:{:?}, depth: {:?}",
         user_id, query.viewer_id, query.depth
     );
 
     match UserView::get_by_id(&user_id, query.viewer_id.as_deref(), query.depth).await? {
-        Some(user) => Ok(Json(user)),
-        None => Err(Error::UserNotFound { user_id }),
+        // A user deleted while they had relationships is only kept as a placeholder
+        Some(user) if user.details.name == USER_DELETED_SENTINEL => {
+            Err(Error::UserGone { user_id })
+        }
+        Some(user) => Ok(ETagJson::new(user, &headers)),
+        None if query.include_moderated => {
+            // Anyone can claim to be the moderator in `viewer_id`, so only a signed viewer counts
+            let moderator_id = query
+                .viewer_id
+                .as_deref()
+                .filter(|viewer_id| signed.is_signed_by(viewer_id));
+            match ModerationInfo::get_for_viewer(&[&user_id], moderator_id).await? {
+                Some(moderation) => {
+                    let id = PubkyId::try_from(user_id.as_str())
+                        .map_err(|e| Error::invalid_input(&format!("Invalid user PK: {e}")))?;
+                    Ok(ETagJson::new(UserView::moderated(id, moderation), &headers))
+                }
+                None => Err(Error::missing_user(user_id).await),
+            }
+        }
+        None => Err(Error::missing_user(user_id).await),
     }
 }
 
 #[derive(OpenApi)]
-#[openapi(paths(user_view_handler), components(schemas(UserView, TagDetails)))]
+#[openapi(
+    paths(user_view_handler),
+    components(schemas(UserView, TagDetails, ModerationInfo))
+)]
 pub struct UserViewApiDoc;
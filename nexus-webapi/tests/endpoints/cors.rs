@@ -0,0 +1,46 @@
+use crate::utils::server::{TestServiceServer, TEST_CORS_ORIGIN};
+
+use anyhow::Result;
+use axum::http::header::{ACCESS_CONTROL_ALLOW_ORIGIN, ORIGIN};
+use axum::http::Method;
+
+/// Sends a GET request from `origin`, returning the `Access-Control-Allow-Origin` of the response
+async fn allowed_origin(test_server: &TestServiceServer, origin: &str) -> Result<Option<String>> {
+    let client = test_server.testnet.client_builder().build()?;
+    let url = format!("{}/v0/info", test_server.nexus_api.icann_http_url());
+    let response = client
+        .request(Method::GET, &url)
+        .header(ORIGIN, origin)
+        .send()
+        .await?;
+    assert_eq!(response.status(), 200);
+
+    Ok(response
+        .headers()
+        .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+        .map(|value| value.to_str().unwrap().to_string()))
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_cors_permissive_by_default() -> Result<()> {
+    let test_server = TestServiceServer::get_test_server().await;
+
+    let allowed = allowed_origin(test_server, "https://any.example.com").await?;
+    assert_eq!(allowed.as_deref(), Some("*"));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_cors_allowlist() -> Result<()> {
+    let test_server = TestServiceServer::get_cors_test_server().await;
+
+    let allowed = allowed_origin(test_server, TEST_CORS_ORIGIN).await?;
+    assert_eq!(allowed.as_deref(), Some(TEST_CORS_ORIGIN));
+
+    // Other origins are not allowed to read the response
+    let allowed = allowed_origin(test_server, "https://other.example.com").await?;
+    assert_eq!(allowed, None);
+
+    Ok(())
+}
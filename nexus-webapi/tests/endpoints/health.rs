@@ -0,0 +1,57 @@
+use crate::utils::host_url;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use nexus_common::db::ping_neo4j;
+use nexus_webapi::models::{Dependency, Readiness};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_health_live() -> Result<()> {
+    let client = httpc_test::new_client(host_url().await)?;
+
+    let res = client.do_get("/health/live").await?;
+    assert_eq!(res.status(), 200);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_health_ready() -> Result<()> {
+    let client = httpc_test::new_client(host_url().await)?;
+
+    let res = client.do_get("/health/ready").await?;
+    assert_eq!(res.status(), 200);
+    let body = res.json_body()?;
+    assert_eq!(body["ready"], true);
+    assert_eq!(body["failing"], serde_json::json!([]));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_health_not_ready_when_redis_is_unreachable() -> Result<()> {
+    // Make sure the stack is set up, so that only Redis fails
+    host_url().await;
+
+    let readiness = Readiness::from_checks(async { Err("Connection refused".into()) }, async {
+        ping_neo4j().await.map_err(Into::into)
+    })
+    .await;
+    assert!(!readiness.ready);
+    assert_eq!(readiness.failing, vec![Dependency::Redis]);
+
+    let response = readiness.into_response();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_health_not_ready_when_a_dependency_hangs() -> Result<()> {
+    let readiness = Readiness::from_checks(async { Ok(()) }, std::future::pending()).await;
+    assert!(!readiness.ready);
+    assert_eq!(readiness.failing, vec![Dependency::Neo4j]);
+
+    Ok(())
+}
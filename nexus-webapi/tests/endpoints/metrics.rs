@@ -0,0 +1,39 @@
+use crate::utils::host_url;
+
+use anyhow::Result;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_metrics_use_route_templates() -> Result<()> {
+    let client = httpc_test::new_client(host_url().await)?;
+
+    // Two concrete paths of the same route, for posts that don't exist
+    for post_id in ["0000000000001", "0000000000002"] {
+        let res = client
+            .do_get(&format!("/v0/post/metrics_test_author/{post_id}"))
+            .await?;
+        assert_eq!(res.status(), 404);
+    }
+
+    let res = client.do_get("/metrics").await?;
+    assert_eq!(res.status(), 200);
+    let body = res.text_body()?;
+
+    // Both requests are counted under the route template
+    let counter =
+        r#"http_requests_total{method="GET",route="/v0/post/{author_id}/{post_id}",status="404"}"#;
+    let count = body
+        .lines()
+        .find_map(|line| line.strip_prefix(counter))
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .expect("The counter of the post route should be exposed");
+    assert!(count >= 2);
+    assert!(body.contains(
+        r#"http_request_duration_seconds_count{method="GET",route="/v0/post/{author_id}/{post_id}",status="404"}"#
+    ));
+    assert!(body.contains("http_requests_in_flight"));
+
+    // The concrete paths never appear as labels
+    assert!(!body.contains("metrics_test_author"));
+
+    Ok(())
+}
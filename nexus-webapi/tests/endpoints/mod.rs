@@ -3,7 +3,13 @@ use crate::utils::{host_url, server::TestServiceServer};
 use anyhow::Result;
 use axum::http::Method;
 
+mod cors;
+mod health;
+mod metrics;
 mod openapi;
+mod rate_limit;
+mod read_only;
+mod resolve;
 
 #[tokio_shared_rt::test(shared)]
 async fn test_swagger_ui() -> Result<()> {
@@ -48,6 +54,32 @@ async fn test_info_endpoint() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_meta_enums_endpoint() -> Result<()> {
+    let client = httpc_test::new_client(host_url().await)?;
+
+    let res = client.do_get("/v0/meta/enums").await?;
+    assert_eq!(res.status(), 200);
+
+    let body = res.json_body()?;
+    assert_eq!(
+        body["stream_sorting"],
+        serde_json::json!(["timeline", "total_engagement"])
+    );
+    assert_eq!(
+        body["timeframe"],
+        serde_json::json!(["today", "this_month", "all_time"])
+    );
+    let reach = body["stream_reach"].as_array().unwrap();
+    assert!(reach.contains(&"followers".into()));
+    assert!(reach.contains(&"wot_3".into()));
+    let kinds = body["post_kind"].as_array().unwrap();
+    assert!(kinds.contains(&"short".into()));
+    assert!(kinds.contains(&"long".into()));
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_pkarr_endpoint() -> Result<()> {
     let test_server = TestServiceServer::get_test_server_with_key_republisher().await;
@@ -0,0 +1,79 @@
+use crate::utils::server::{
+    TestServiceServer, TEST_RATE_LIMIT_PATH, TEST_RATE_LIMIT_REQUESTS, TEST_RATE_LIMIT_WINDOW_SECS,
+};
+
+use anyhow::Result;
+use axum::http::header::RETRY_AFTER;
+use axum::http::{Method, StatusCode};
+use nexus_common::db::kv::rate_limit;
+use std::time::Duration;
+
+/// Sends a GET request to `path` as the client with address `client_ip`, returning the status
+/// and the `Retry-After` of the response
+async fn request_as(
+    test_server: &TestServiceServer,
+    path: &str,
+    client_ip: &str,
+) -> Result<(StatusCode, Option<String>)> {
+    let client = test_server.testnet.client_builder().build()?;
+    let url = format!("{}{path}", test_server.nexus_api.icann_http_url());
+    let response = client
+        .request(Method::GET, &url)
+        .header("x-forwarded-for", client_ip)
+        .send()
+        .await?;
+
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .map(|value| value.to_str().unwrap().to_string());
+    Ok((response.status(), retry_after))
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_rate_limit_per_ip() -> Result<()> {
+    let test_server = TestServiceServer::get_rate_limited_test_server().await;
+    let client_ip = "10.0.0.1";
+    rate_limit::reset(&format!("{TEST_RATE_LIMIT_PATH}:ip:{client_ip}")).await?;
+
+    for _ in 0..TEST_RATE_LIMIT_REQUESTS {
+        let (status, _) = request_as(test_server, TEST_RATE_LIMIT_PATH, client_ip).await?;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // The next request within the window is rejected, and told when to retry
+    let (status, retry_after) = request_as(test_server, TEST_RATE_LIMIT_PATH, client_ip).await?;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = retry_after.expect("Retry-After should be set").parse()?;
+    assert!((1..=TEST_RATE_LIMIT_WINDOW_SECS).contains(&retry_after));
+
+    // Other clients and other paths are not affected
+    let (status, _) = request_as(test_server, TEST_RATE_LIMIT_PATH, "10.0.0.2").await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = request_as(test_server, "/v0/meta/enums", client_ip).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    // After the window, the bucket is refilled
+    tokio::time::sleep(Duration::from_secs(TEST_RATE_LIMIT_WINDOW_SECS)).await;
+    let (status, _) = request_as(test_server, TEST_RATE_LIMIT_PATH, client_ip).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_rate_limit_ignores_viewer_id() -> Result<()> {
+    let test_server = TestServiceServer::get_rate_limited_test_server().await;
+    let viewer_id = "rate_limit_test_viewer";
+
+    // The viewer_id is not authenticated, so it cannot drain a bucket shared by its requests
+    let path = format!("{TEST_RATE_LIMIT_PATH}?viewer_id={viewer_id}");
+    for i in 0..=TEST_RATE_LIMIT_REQUESTS {
+        let client_ip = format!("10.0.1.{i}");
+        rate_limit::reset(&format!("{TEST_RATE_LIMIT_PATH}:ip:{client_ip}")).await?;
+        let (status, _) = request_as(test_server, &path, &client_ip).await?;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::utils::server::TestServiceServer;
+
+use anyhow::Result;
+use nexus_webapi::routes::v0::endpoints::{
+    NOTIFICATION_PREFERENCES_ROUTE, NOTIFICATION_READ_ALL_ROUTE, NOTIFICATION_READ_ROUTE,
+    STREAM_POSTS_BY_IDS_ROUTE, USER_FOLLOWED_TAG_ROUTE,
+};
+use pubky::Keypair;
+use serde_json::json;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_read_only_mode() -> Result<()> {
+    let test_server = TestServiceServer::get_read_only_test_server().await;
+    let client = httpc_test::new_client(test_server.nexus_api.icann_http_url())?;
+    let user_id = Keypair::random().public_key().to_z32();
+
+    // Reads, including the bulk reads sent as POST, remain available
+    let res = client.do_get("/v0/info").await?;
+    assert_eq!(res.status(), 200);
+
+    let res = client
+        .do_post(STREAM_POSTS_BY_IDS_ROUTE, json!({ "post_ids": [] }))
+        .await?;
+    assert_ne!(res.status(), 503);
+
+    // Writes are rejected
+    let preferences_path = NOTIFICATION_PREFERENCES_ROUTE.replace("{user_id}", &user_id);
+    let res = client
+        .do_put(&preferences_path, json!({ "types": { "follow": false } }))
+        .await?;
+    assert_eq!(res.status(), 503);
+    let body = res.json_body()?;
+    assert_eq!(body["code"], "service_unavailable");
+
+    let followed_tag_path = USER_FOLLOWED_TAG_ROUTE
+        .replace("{user_id}", &user_id)
+        .replace("{label}", "bitcoin");
+    let res = client.do_delete(&followed_tag_path).await?;
+    assert_eq!(res.status(), 503);
+
+    // Marking notifications as read is a write, although sent as POST
+    let read_path = NOTIFICATION_READ_ROUTE.replace("{user_id}", &user_id);
+    let res = client.do_post(&read_path, json!({ "ids": [] })).await?;
+    assert_eq!(res.status(), 503);
+    let read_all_path = NOTIFICATION_READ_ALL_ROUTE.replace("{user_id}", &user_id);
+    let res = client.do_post(&read_all_path, json!({})).await?;
+    assert_eq!(res.status(), 503);
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::utils::{invalid_post_request, post_request, put_corrupt_details};
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use nexus_common::db::RedisOps;
+use nexus_common::models::user::UserDetails;
+use nexus_webapi::routes::v0::endpoints::RESOLVE_BULK_ROUTE;
+use pubky::Keypair;
+use pubky_app_specs::{file_uri_builder, post_uri_builder};
+use serde_json::json;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_resolve_bulk_mixed_uris() -> Result<()> {
+    let author_id = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+    let post_id = "2ZCW1TGR5BKG0";
+    let missing_user_id = Keypair::random().public_key().to_z32();
+
+    let uris = vec![
+        post_uri_builder(author_id.into(), post_id.into()),
+        "not a pubky uri".to_string(),
+        format!("pubky://{author_id}/pub/pubky.app/profile.json"),
+        format!("pubky://{missing_user_id}/pub/pubky.app/profile.json"),
+        file_uri_builder(author_id.into(), "2ZK2H8P2T5NG0".into()),
+    ];
+
+    let body = post_request(RESOLVE_BULK_ROUTE, json!({ "uris": uris })).await?;
+    let results = body.as_array().expect("Results should be an array");
+    assert_eq!(results.len(), uris.len());
+
+    // Results are aligned with the requested URIs
+    for (result, uri) in results.iter().zip(&uris) {
+        assert_eq!(result["uri"], uri.as_str());
+    }
+
+    assert_eq!(results[0]["type"], "post");
+    assert_eq!(results[0]["post"]["details"]["id"], post_id);
+    assert_eq!(results[0]["post"]["details"]["author"], author_id);
+
+    assert_eq!(results[1]["type"], "invalid");
+    assert!(results[1]["error"].is_string());
+
+    assert_eq!(results[2]["type"], "user");
+    assert_eq!(results[2]["user"]["details"]["id"], author_id);
+
+    assert_eq!(results[3]["type"], "not_found");
+    assert_eq!(results[4]["type"], "unsupported");
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_resolve_bulk_partial_failure() -> Result<()> {
+    let author_id = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+    let post_id = "2ZCW1TGR5BKG0";
+    let corrupt_user_id = Keypair::random().public_key().to_z32();
+    put_corrupt_details("User:Details", &[&corrupt_user_id]).await?;
+
+    let uris = vec![
+        format!("pubky://{corrupt_user_id}/pub/pubky.app/profile.json"),
+        format!("pubky://{author_id}/pub/pubky.app/profile.json"),
+        post_uri_builder(author_id.into(), post_id.into()),
+    ];
+    let body = post_request(RESOLVE_BULK_ROUTE, json!({ "uris": uris })).await;
+    UserDetails::remove_from_index_multiple_json(&[&[&corrupt_user_id]]).await?;
+
+    // The user that fails to load doesn't fail the other URIs
+    let body = body?;
+    let results = body.as_array().expect("Results should be an array");
+    assert_eq!(results.len(), uris.len());
+
+    assert_eq!(results[0]["type"], "error");
+    assert_eq!(results[0]["uri"], uris[0].as_str());
+    assert!(results[0]["error"].is_string());
+
+    assert_eq!(results[1]["type"], "user");
+    assert_eq!(results[1]["user"]["details"]["id"], author_id);
+
+    assert_eq!(results[2]["type"], "post");
+    assert_eq!(results[2]["post"]["details"]["id"], post_id);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_resolve_bulk_invalid_input() -> Result<()> {
+    invalid_post_request(
+        RESOLVE_BULK_ROUTE,
+        json!({ "uris": [] }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    let uris: Vec<String> = (0..101).map(|_| "pubky://invalid".to_string()).collect();
+    invalid_post_request(
+        RESOLVE_BULK_ROUTE,
+        json!({ "uris": uris }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use anyhow::Result;
+use nexus_common::db::RedisOps;
+use nexus_common::models::file::FileDetails;
+use nexus_common::models::traits::Collection;
+use pubky::Keypair;
 use pubky_app_specs::file_uri_builder;
 
-use crate::utils::get_request;
+use crate::utils::{get_request, server::TestServiceServer};
 
 #[tokio_shared_rt::test(shared)]
 async fn test_file_details() -> Result<()> {
@@ -24,3 +28,40 @@ async fn test_file_details() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_file_details_index_round_trip() -> Result<()> {
+    TestServiceServer::get_test_server().await;
+
+    let owner_id = Keypair::random().public_key().to_z32();
+    let file_id = "0034BQ0000001";
+    let file = FileDetails {
+        id: file_id.to_string(),
+        owner_id: owner_id.clone(),
+        name: "round-trip".to_string(),
+        ..Default::default()
+    };
+    let key: &[&str] = &[&owner_id, file_id];
+
+    // The collection caches the file under the key it is read and deleted with
+    FileDetails::put_to_index(&[key], vec![Some(file)])
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let cached = FileDetails::get_from_index(vec![key])
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    assert_eq!(
+        cached[0].as_ref().map(|file| file.name.as_str()),
+        Some("round-trip")
+    );
+
+    FileDetails::remove_from_index_multiple_json(&[key])
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let cached = FileDetails::get_from_index(vec![key])
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    assert!(cached[0].is_none());
+
+    Ok(())
+}
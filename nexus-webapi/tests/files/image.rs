@@ -5,11 +5,16 @@ use std::{
 
 use crate::utils::host_url;
 use anyhow::Result;
-use nexus_common::media::FileVariant;
+use nexus_common::media::processors::{ImageProcessor, MediaProcessorError, VariantProcessor};
+use nexus_common::media::{FileVariant, VariantController};
 use nexus_common::models::{file::FileDetails, traits::Collection};
 use tokio::fs::create_dir_all;
 
 const IMAGE_BLOB_NAME: &str = "SynonymLogo.png";
+/// JPEG carrying an EXIF block with a GPS latitude tag
+const GPS_IMAGE_BLOB_NAME: &str = "GpsTagged.jpg";
+/// PNG whose header claims 30000x30000 pixels, with almost no pixel data
+const OVERSIZED_IMAGE_BLOB_NAME: &str = "Oversized.png";
 const BLOB_PATH: &str = "tests/files/blobs";
 
 const FILE_ID: &str = "2ZKH7K7M9G3G0";
@@ -166,3 +171,203 @@ async fn test_static_image_serving_small() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_image_webp_variant_from_png() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let file = FileDetails {
+        id: FILE_ID.to_string(),
+        owner_id: USER_PUBKY.to_string(),
+        content_type: "image/png".to_string(),
+        ..Default::default()
+    };
+
+    let file_dir = tmp_dir.path().join(USER_PUBKY).join(FILE_ID);
+    create_dir_all(&file_dir).await?;
+    let main_path = file_dir.join(FileVariant::Main.to_string());
+    fs::copy(PathBuf::from(BLOB_PATH).join(IMAGE_BLOB_NAME), &main_path)?;
+
+    let content_type =
+        ImageProcessor::create_variant(&file, &FileVariant::Feed, tmp_dir.path().to_path_buf())
+            .await?;
+    assert_eq!(content_type, "image/webp");
+
+    let webp = fs::read(file_dir.join(FileVariant::Feed.to_string()))?;
+    // RIFF container holding a WebP image
+    assert_eq!(&webp[0..4], b"RIFF");
+    assert_eq!(&webp[8..12], b"WEBP");
+    assert!(webp.len() < fs::metadata(&main_path)?.len() as usize);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_image_blurhash() -> Result<()> {
+    let image_path = PathBuf::from(BLOB_PATH).join(IMAGE_BLOB_NAME);
+
+    let blurhash = VariantController::compute_blurhash("image/png", &image_path)
+        .await
+        .expect("Blurhash should be computed for a png image");
+    // 4x3 components: size flag, maximum AC value, DC and 11 AC values
+    assert!(blurhash.starts_with('L'));
+    assert_eq!(blurhash.len(), 28);
+
+    // Computing it again yields the same placeholder
+    assert_eq!(
+        VariantController::compute_blurhash("image/png", &image_path).await,
+        Some(blurhash)
+    );
+
+    // No blurhash for non-image files
+    assert!(
+        VariantController::compute_blurhash("video/mp4", &image_path)
+            .await
+            .is_none()
+    );
+
+    Ok(())
+}
+
+/// Returns `true` if the JPEG bytes contain an EXIF (APP1) block
+fn has_exif(jpeg: &[u8]) -> bool {
+    jpeg.windows(6).any(|window| window == b"Exif\0\0")
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_image_strip_metadata() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let file = FileDetails {
+        id: FILE_ID.to_string(),
+        owner_id: USER_PUBKY.to_string(),
+        content_type: "image/jpeg".to_string(),
+        ..Default::default()
+    };
+
+    let file_dir = tmp_dir.path().join(USER_PUBKY).join(FILE_ID);
+    create_dir_all(&file_dir).await?;
+    let main_path = file_dir.join(FileVariant::Main.to_string());
+    fs::copy(
+        PathBuf::from(BLOB_PATH).join(GPS_IMAGE_BLOB_NAME),
+        &main_path,
+    )?;
+    assert!(has_exif(&fs::read(&main_path)?));
+
+    VariantController::strip_metadata("image/jpeg", &main_path).await?;
+    let main = fs::read(&main_path)?;
+    assert_eq!(
+        &main[0..2],
+        b"\xff\xd8",
+        "Main variant should still be a JPEG"
+    );
+    assert!(!has_exif(&main), "Main variant still holds EXIF metadata");
+
+    ImageProcessor::create_variant(&file, &FileVariant::Small, tmp_dir.path().to_path_buf())
+        .await?;
+    let small = fs::read(file_dir.join(FileVariant::Small.to_string()))?;
+    assert!(!has_exif(&small), "Small variant still holds EXIF metadata");
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_image_process_all_variants() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let file = FileDetails {
+        id: FILE_ID.to_string(),
+        owner_id: USER_PUBKY.to_string(),
+        content_type: "image/png".to_string(),
+        ..Default::default()
+    };
+    let variants = [FileVariant::Small, FileVariant::Main, FileVariant::Feed];
+
+    let file_dir = tmp_dir.path().join(USER_PUBKY).join(FILE_ID);
+    create_dir_all(&file_dir).await?;
+    fs::copy(
+        PathBuf::from(BLOB_PATH).join(IMAGE_BLOB_NAME),
+        file_dir.join(FileVariant::Main.to_string()),
+    )?;
+
+    let processed =
+        ImageProcessor::process_all_variants(&file, &variants, tmp_dir.path().to_path_buf()).await;
+    assert!(processed.errors.is_empty(), "{:?}", processed.errors);
+    let created: Vec<(FileVariant, &str)> = processed
+        .created
+        .iter()
+        .map(|(variant, content_type)| (variant.clone(), content_type.as_str()))
+        .collect();
+    assert_eq!(
+        created,
+        vec![
+            (FileVariant::Small, "image/webp"),
+            (FileVariant::Main, "image/png"),
+            (FileVariant::Feed, "image/webp"),
+        ]
+    );
+    assert!(file_dir.join(FileVariant::Small.to_string()).exists());
+    assert!(file_dir.join(FileVariant::Feed.to_string()).exists());
+
+    // Without an original, resized variants fail but don't prevent the others
+    let missing_file = FileDetails {
+        id: "MISSING".to_string(),
+        ..file
+    };
+    let processed = ImageProcessor::process_all_variants(
+        &missing_file,
+        &variants,
+        tmp_dir.path().to_path_buf(),
+    )
+    .await;
+    assert_eq!(processed.created.len(), 1);
+    assert_eq!(processed.created[0].0, FileVariant::Main);
+    let failed: Vec<FileVariant> = processed
+        .errors
+        .into_iter()
+        .map(|(variant, _)| variant)
+        .collect();
+    assert_eq!(failed, vec![FileVariant::Small, FileVariant::Feed]);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_image_dimensions_guard() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let file = FileDetails {
+        id: FILE_ID.to_string(),
+        owner_id: USER_PUBKY.to_string(),
+        content_type: "image/png".to_string(),
+        ..Default::default()
+    };
+
+    let file_dir = tmp_dir.path().join(USER_PUBKY).join(FILE_ID);
+    create_dir_all(&file_dir).await?;
+    let main_path = file_dir.join(FileVariant::Main.to_string());
+    fs::copy(
+        PathBuf::from(BLOB_PATH).join(OVERSIZED_IMAGE_BLOB_NAME),
+        &main_path,
+    )?;
+
+    // Rejected from the header alone, before ImageMagick tries to decode 900 megapixels
+    let result =
+        ImageProcessor::create_variant(&file, &FileVariant::Feed, tmp_dir.path().to_path_buf())
+            .await;
+    assert!(matches!(
+        result,
+        Err(MediaProcessorError::DimensionsExceeded {
+            width: 30000,
+            height: 30000
+        })
+    ));
+    assert!(!file_dir.join(FileVariant::Feed.to_string()).exists());
+
+    // A regular image passes the guard
+    ImageProcessor::check_dimensions(
+        PathBuf::from(BLOB_PATH)
+            .join(IMAGE_BLOB_NAME)
+            .to_str()
+            .unwrap(),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::utils::{get_request, invalid_get_request};
+use anyhow::Result;
+use axum::http::StatusCode;
+use nexus_webapi::routes::v0::endpoints::POST_ENGAGEMENT_ROUTE;
+
+const AUTHOR_ID: &str = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+const POST_ID: &str = "2ZCW1TGR5BKG0";
+const REPOSTER_ID: &str = "h3fghnb3x59oh7r53x8y6a5x38oatqyjym9b31ybss17zqdnhcoy";
+
+fn engagement_path(author_id: &str, post_id: &str) -> String {
+    POST_ENGAGEMENT_ROUTE
+        .replace("{author_id}", author_id)
+        .replace("{post_id}", post_id)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_engagement_breakdown() -> Result<()> {
+    let body = get_request(&engagement_path(AUTHOR_ID, POST_ID)).await?;
+
+    assert_eq!(body["counts"]["tags"].as_u64(), Some(5));
+    assert_eq!(body["counts"]["replies"].as_u64(), Some(2));
+    assert_eq!(body["counts"]["reposts"].as_u64(), Some(1));
+
+    // The author tagged the post 5 times
+    let top_taggers = body["top_taggers"].as_array().unwrap();
+    assert_eq!(top_taggers.len(), 1);
+    assert_eq!(top_taggers[0]["user_id"], AUTHOR_ID);
+    assert_eq!(top_taggers[0]["tags"].as_u64(), Some(5));
+
+    // Both replies come from different users
+    assert_eq!(body["unique_repliers"].as_u64(), Some(2));
+    assert_eq!(body["repost_authors"], serde_json::json!([REPOSTER_ID]));
+    assert_eq!(body["bookmarks"].as_u64(), Some(1));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_engagement_breakdown_limit_taggers() -> Result<()> {
+    // From posts.cypher: four users tagged this post once each
+    let author_id = "ep441mndnsjeesenwz78r9paepm6e4kqm4ggiyy9uzpoe43eu9ny";
+    let post_id = "A5D6P9V3Q0T";
+
+    let path = format!("{}?limit_taggers=2", engagement_path(author_id, post_id));
+    let body = get_request(&path).await?;
+
+    let top_taggers = body["top_taggers"].as_array().unwrap();
+    assert_eq!(top_taggers.len(), 2);
+    // Ties are ordered by user ID
+    assert_eq!(
+        top_taggers[0]["user_id"],
+        "7w4hmktqa7gia5thmk7zki8px7ttwpwjtgaaaou4tbqx64re8d1o"
+    );
+    assert_eq!(
+        top_taggers[1]["user_id"],
+        "8attbeo9ftu5nztqkcfw3gydksehr7jbspgfi64u4h8eo5e7dbiy"
+    );
+    assert_eq!(body["unique_repliers"].as_u64(), Some(0));
+    assert_eq!(body["bookmarks"].as_u64(), Some(1));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_engagement_breakdown_not_found() -> Result<()> {
+    let path = engagement_path(AUTHOR_ID, "2ZCW1TGR5BKGX");
+    invalid_get_request(&path, StatusCode::NOT_FOUND).await?;
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use nexus_webapi::routes::v0::endpoints;
 
+pub mod engagement;
 pub mod from_post_views;
+pub mod relationships;
 pub mod search;
+pub mod thread;
 pub mod view;
 
 pub const ROOT_PATH: &str = endpoints::POST_PREFIX;
@@ -0,0 +1,29 @@
+use crate::utils::server::TestServiceServer;
+use anyhow::Result;
+use nexus_common::models::post::{PostKind, PostRelationships};
+
+// Amsterdam's root post and Bogota's reply to it, from posts.cypher
+const AMSTERDAM_USER: &str = "emq37ky6fbnaun7q1ris6rx3mqmw3a33so1txfesg9jj3ak9ryoy";
+const BOGOTA_USER: &str = "ep441mndnsjeesenwz78r9paepm6e4kqm4ggiyy9uzpoe43eu9ny";
+const ROOT_POST_ID: &str = "1A1P4D8C9K0F";
+const REPLY_POST_ID: &str = "2B9XKZG3T4L6";
+
+#[tokio_shared_rt::test(shared)]
+async fn test_classify_batch_mixed_post_keys() -> Result<()> {
+    TestServiceServer::get_test_server().await;
+
+    let post_keys = vec![
+        format!("{AMSTERDAM_USER}:{ROOT_POST_ID}"),
+        format!("{BOGOTA_USER}:{REPLY_POST_ID}"),
+        format!("{AMSTERDAM_USER}:NONEXISTENT00"),
+    ];
+
+    let kinds = PostRelationships::classify_batch(&post_keys).await.unwrap();
+
+    assert_eq!(
+        kinds,
+        vec![Some(PostKind::Root), Some(PostKind::Reply), None]
+    );
+
+    Ok(())
+}
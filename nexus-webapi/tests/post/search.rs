@@ -1,13 +1,38 @@
 use anyhow::Result;
-use nexus_webapi::routes::v0::endpoints::SEARCH_POSTS_BY_TAG_ROUTE;
+use axum::http::StatusCode;
+use nexus_common::db::RedisOps;
+use nexus_common::models::post::search::{
+    PostsByTagSearch, REACH_SEARCH_BATCH_SIZE, TAG_GLOBAL_POST_TIMELINE,
+};
+use nexus_common::models::post::PostsByEmbed;
+use nexus_common::types::{Pagination, StreamReach};
+use nexus_webapi::routes::v0::endpoints::{
+    SEARCH_POSTS_BY_CONTENT_ROUTE, SEARCH_POSTS_BY_EMBED_ROUTE, SEARCH_POSTS_BY_TAG_ROUTE,
+};
+use pubky::Keypair;
+use pubky_app_specs::post_uri_builder;
 use serde_json::Value;
 
-use crate::{stream::post::TAG_LABEL_2, utils::get_request};
+use super::ENCRYPTION_TAG;
+use crate::{
+    stream::post::TAG_LABEL_2,
+    utils::{get_request, invalid_get_request, server::TestServiceServer},
+};
 
 const POST_A: &str = "2VDW8YBDZJ02";
 const POST_B: &str = "1TDV7XBCF4M1";
 const POST_C: &str = "HC3T5CEPBPHQ";
 
+// From posts.cypher: Amsterdam follows Bogota and Cairo, who each authored one post tagged "encryption"
+const AMSTERDAM_USER: &str = "emq37ky6fbnaun7q1ris6rx3mqmw3a33so1txfesg9jj3ak9ryoy";
+const BOGOTA_USER: &str = "ep441mndnsjeesenwz78r9paepm6e4kqm4ggiyy9uzpoe43eu9ny";
+const CAIRO_USER: &str = "f5tcy5gtgzshipr6pag6cn9uski3s8tjare7wd3n7enmyokgjk1o";
+const BOGOTA_ENCRYPTION_POST: &str = "A5D6P9V3Q0T";
+const CAIRO_ENCRYPTION_POST: &str = "N7Q2F5W8J0L3";
+// From posts.cypher: the only posts with "transparency" in their content
+const TRANSPARENCY_POST_A: &str = "3NFG9K0L5QH4";
+const TRANSPARENCY_POST_B: &str = "K1P6Q9M2X4J8";
+
 pub fn format_search_posts_by_tag(tag: &str) -> String {
     SEARCH_POSTS_BY_TAG_ROUTE.replace("{tag}", tag)
 }
@@ -136,3 +161,194 @@ async fn test_post_search_skip_beyond_range() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_search_by_tag_within_following_reach() -> Result<()> {
+    TestServiceServer::get_test_server().await;
+
+    let posts = PostsByTagSearch::get_by_label_with_reach(
+        ENCRYPTION_TAG,
+        AMSTERDAM_USER,
+        StreamReach::Following,
+        None,
+        Pagination::default(),
+    )
+    .await
+    .unwrap()
+    .expect("Amsterdam's follows authored posts tagged with encryption");
+
+    let post_keys: Vec<&str> = posts.iter().map(|p| p.post_key.as_str()).collect();
+
+    // Amsterdam's own encryption posts are outside of the reach
+    assert!(post_keys
+        .iter()
+        .all(|key| key.starts_with(BOGOTA_USER) || key.starts_with(CAIRO_USER)));
+    assert!(post_keys.contains(&format!("{BOGOTA_USER}:{BOGOTA_ENCRYPTION_POST}").as_str()));
+    assert!(post_keys.contains(&format!("{CAIRO_USER}:{CAIRO_ENCRYPTION_POST}").as_str()));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_search_by_tag_within_reach_with_limit() -> Result<()> {
+    TestServiceServer::get_test_server().await;
+
+    let pagination = Pagination {
+        limit: Some(1),
+        ..Default::default()
+    };
+    let posts = PostsByTagSearch::get_by_label_with_reach(
+        ENCRYPTION_TAG,
+        AMSTERDAM_USER,
+        StreamReach::Following,
+        None,
+        pagination,
+    )
+    .await
+    .unwrap()
+    .unwrap_or_default();
+
+    assert_eq!(posts.len(), 1);
+
+    Ok(())
+}
+
+/// The posts of the reach are found even behind more than a batch of newer posts from other
+/// authors
+#[tokio_shared_rt::test(shared)]
+async fn test_post_search_by_tag_within_reach_beyond_a_batch() -> Result<()> {
+    TestServiceServer::get_test_server().await;
+
+    let label = format!("reach{}", Keypair::random().public_key().to_z32());
+    let key_parts = [&TAG_GLOBAL_POST_TIMELINE[..], &[label.as_str()]].concat();
+    let outsider_id = Keypair::random().public_key().to_z32();
+    let mut post_keys: Vec<String> = (0..REACH_SEARCH_BATCH_SIZE + 1)
+        .map(|i| format!("{outsider_id}:{i:013}"))
+        .collect();
+    post_keys.push(format!("{BOGOTA_USER}:{BOGOTA_ENCRYPTION_POST}"));
+    // The post of the reach is the oldest one
+    let elements: Vec<(f64, &str)> = post_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| ((post_keys.len() - i) as f64, key.as_str()))
+        .collect();
+    PostsByTagSearch::put_index_sorted_set(&key_parts, &elements, None, None).await?;
+
+    let posts = PostsByTagSearch::get_by_label_with_reach(
+        &label,
+        AMSTERDAM_USER,
+        StreamReach::Following,
+        None,
+        Pagination::default(),
+    )
+    .await;
+
+    let members: Vec<&str> = post_keys.iter().map(String::as_str).collect();
+    PostsByTagSearch::remove_from_index_sorted_set(None, &key_parts, &members).await?;
+
+    let posts = posts?.expect("Amsterdam follows Bogota");
+    let post_keys: Vec<&str> = posts.iter().map(|p| p.post_key.as_str()).collect();
+    assert_eq!(
+        post_keys,
+        vec![format!("{BOGOTA_USER}:{BOGOTA_ENCRYPTION_POST}").as_str()]
+    );
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_search_by_content() -> Result<()> {
+    let path = format!("{SEARCH_POSTS_BY_CONTENT_ROUTE}?q=transparency");
+    let body = get_request(&path).await?;
+
+    let posts = body.as_array().expect("Post list should be an array");
+    assert_eq!(posts.len(), 2);
+    search_posts(posts, vec![TRANSPARENCY_POST_A, TRANSPARENCY_POST_B]);
+
+    // Every term has to match, regardless of the case and punctuation
+    let path = format!("{SEARCH_POSTS_BY_CONTENT_ROUTE}?q=Software,%20TRANSPARENCY!");
+    let body = get_request(&path).await?;
+
+    let posts = body.as_array().expect("Post list should be an array");
+    assert_eq!(posts.len(), 1);
+    search_posts(posts, vec![TRANSPARENCY_POST_A]);
+
+    let path = format!("{SEARCH_POSTS_BY_CONTENT_ROUTE}?q=transparency&skip=1&limit=1");
+    let body = get_request(&path).await?;
+    search_posts(body.as_array().unwrap(), vec![TRANSPARENCY_POST_B]);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_search_by_content_no_result() -> Result<()> {
+    let path = format!("{SEARCH_POSTS_BY_CONTENT_ROUTE}?q=transparency%20randommm");
+    let body = get_request(&path).await?;
+
+    assert!(body.as_array().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_search_by_content_too_short() -> Result<()> {
+    let path = format!("{SEARCH_POSTS_BY_CONTENT_ROUTE}?q=a%20to");
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_search_by_embed() -> Result<()> {
+    let author_id = Keypair::random().public_key().to_z32();
+    let embedded_uri = post_uri_builder(author_id.clone(), "0000000000000".into());
+    let embedding_ids: Vec<String> = (0..101).map(|i| format!("{i:013}")).collect();
+    for (i, post_id) in embedding_ids.iter().enumerate() {
+        PostsByEmbed::put_to_index(&author_id, post_id, &embedded_uri, 1000 + i as i64).await?;
+    }
+
+    let encoded_uri: String =
+        url::form_urlencoded::byte_serialize(embedded_uri.as_bytes()).collect();
+    let path = |query: &str| format!("{SEARCH_POSTS_BY_EMBED_ROUTE}?uri={encoded_uri}{query}");
+    let first = get_request(&path("&limit=2")).await;
+    let skipped = get_request(&path("&skip=100")).await;
+    let uncapped = get_request(&path("&limit=1000")).await;
+
+    for post_id in &embedding_ids {
+        PostsByEmbed::del_from_index(&author_id, post_id).await?;
+    }
+
+    // The posts embedding the URI, most recent first
+    let first = first?;
+    let first = first.as_array().expect("Search results should be an array");
+    assert_eq!(first.len(), 2);
+    assert_eq!(
+        first[0]["post_key"],
+        format!("{author_id}:{}", embedding_ids[100])
+    );
+    assert_eq!(
+        first[1]["post_key"],
+        format!("{author_id}:{}", embedding_ids[99])
+    );
+
+    let skipped = skipped?;
+    let skipped = skipped
+        .as_array()
+        .expect("Search results should be an array");
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(
+        skipped[0]["post_key"],
+        format!("{author_id}:{}", embedding_ids[0])
+    );
+
+    // The limit is capped
+    assert_eq!(uncapped?.as_array().map(Vec::len), Some(100));
+
+    invalid_get_request(
+        &format!("{SEARCH_POSTS_BY_EMBED_ROUTE}?uri=%20"),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::utils::{get_request, invalid_get_request};
+use anyhow::Result;
+use axum::http::StatusCode;
+use nexus_webapi::routes::v0::endpoints::POST_THREAD_ROUTE;
+
+const AUTHOR_ID: &str = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+const POST_ID: &str = "2ZCW1TGR5BKG0";
+
+fn thread_path(author_id: &str, post_id: &str) -> String {
+    POST_THREAD_ROUTE
+        .replace("{author_id}", author_id)
+        .replace("{post_id}", post_id)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_thread() -> Result<()> {
+    let body = get_request(&thread_path(AUTHOR_ID, POST_ID)).await?;
+
+    assert_eq!(body["author_id"], AUTHOR_ID);
+    assert_eq!(body["post_id"], POST_ID);
+    assert_eq!(body["deleted"], false);
+    assert_eq!(body["view"]["details"]["id"], POST_ID);
+
+    let replies = body["replies"].as_array().unwrap();
+    assert_eq!(replies.len(), 2);
+    for reply in replies {
+        assert_eq!(reply["deleted"], false);
+        assert_eq!(reply["view"]["details"]["id"], reply["post_id"]);
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_thread_depth_zero() -> Result<()> {
+    let body = get_request(&format!("{}?depth=0", thread_path(AUTHOR_ID, POST_ID))).await?;
+
+    assert_eq!(body["post_id"], POST_ID);
+    assert_eq!(body["replies"].as_array().unwrap().len(), 0);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_thread_limit_replies() -> Result<()> {
+    let body = get_request(&format!(
+        "{}?limit_replies=1",
+        thread_path(AUTHOR_ID, POST_ID)
+    ))
+    .await?;
+
+    assert_eq!(body["post_id"], POST_ID);
+    assert_eq!(body["replies"].as_array().unwrap().len(), 1);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_thread_not_found() -> Result<()> {
+    invalid_get_request(
+        &thread_path(AUTHOR_ID, "0000000000000"),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,11 +1,21 @@
 use crate::{
     post::{CAIRO_USER, ENCRYPTION_TAG, ROOT_PATH},
     stream::post::{kind::DETROIT, POST_H, TAG_LABEL_2},
-    utils::{get_request, invalid_get_request},
+    utils::{
+        conditional_get_request, get_request, invalid_get_request, invalid_post_request,
+        post_request, signed_request,
+    },
 };
 use anyhow::Result;
-use axum::http::StatusCode;
+use axum::http::{Method, StatusCode};
+use nexus_common::db::RedisOps;
+use nexus_common::models::moderation::ModerationInfo;
+use nexus_common::models::post::{PostDetails, POST_DELETED_CONTENT};
 use nexus_common::models::tag::TagDetails;
+use nexus_webapi::routes::v0::endpoints;
+use pubky::Keypair;
+use pubky_app_specs::post_uri_builder;
+use serde_json::json;
 
 #[tokio_shared_rt::test(shared)]
 async fn test_get_post_view() -> Result<()> {
@@ -69,6 +79,66 @@ async fn test_get_post_counts() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_get_posts_counts() -> Result<()> {
+    let post_ids = [
+        format!("{CAIRO_USER}:{POST_H}"),
+        format!("{CAIRO_USER}:0000000000000"),
+        "malformed".to_string(),
+    ];
+    let body = post_request(
+        endpoints::POSTS_COUNTS_ROUTE,
+        json!({ "post_ids": post_ids }),
+    )
+    .await?;
+
+    // One entry per post, in the same order
+    let counts = body.as_array().expect("Counts should be an array");
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[0]["tags"], 6);
+    assert_eq!(counts[0]["unique_tags"], 2);
+    assert!(counts[1].is_null());
+    assert!(counts[2].is_null());
+
+    let post_ids: Vec<String> = (0..101).map(|_| format!("{CAIRO_USER}:{POST_H}")).collect();
+    invalid_post_request(
+        endpoints::POSTS_COUNTS_ROUTE,
+        json!({ "post_ids": post_ids }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_posts_bookmark_status() -> Result<()> {
+    let viewer_id = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+    let post_ids = [
+        format!("{viewer_id}:2ZCW1TGR5BKG0"),
+        format!("{viewer_id}:0000000000000"),
+        "malformed".to_string(),
+    ];
+    let body = post_request(
+        endpoints::POSTS_BOOKMARK_STATUS_ROUTE,
+        json!({ "viewer_id": viewer_id, "post_ids": post_ids }),
+    )
+    .await?;
+
+    // One flag per post, in the same order
+    assert_eq!(body, json!([true, false, false]));
+
+    let post_ids: Vec<String> = (0..101).map(|_| format!("{CAIRO_USER}:{POST_H}")).collect();
+    invalid_post_request(
+        endpoints::POSTS_BOOKMARK_STATUS_ROUTE,
+        json!({ "viewer_id": viewer_id, "post_ids": post_ids }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_get_post_view_with_limit_tags() -> Result<()> {
     let path = format!("{ROOT_PATH}/{CAIRO_USER}/{POST_H}?limit_tags=1");
@@ -237,3 +307,170 @@ async fn test_get_post_view_with_viewer() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_moderated_post_view() -> Result<()> {
+    crate::utils::server::TestServiceServer::get_test_server().await;
+
+    let author_id = Keypair::random().public_key().to_z32();
+    let moderator_kp = Keypair::random();
+    let moderator_id = moderator_kp.public_key().to_z32();
+    let post_id = "0033RCZXVEPNG";
+
+    ModerationInfo {
+        moderator_id: moderator_id.clone(),
+        reason: "hatespeech".to_string(),
+        moderated_at: 1_700_000_000_000,
+    }
+    .put_to_index(&[&author_id, post_id])
+    .await
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    // The moderator gets a placeholder with the moderation reason
+    let moderated_path = format!(
+        "{ROOT_PATH}/{author_id}/{post_id}?viewer_id={moderator_id}&include_moderated=true"
+    );
+    let (status, body) = signed_request(Method::GET, &moderated_path, &moderator_kp, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["details"]["id"], post_id);
+    assert_eq!(body["details"]["author"], author_id.as_str());
+    assert_eq!(body["moderated"]["reason"], "hatespeech");
+    assert_eq!(body["moderated"]["moderator_id"], moderator_id.as_str());
+
+    // Claiming to be the moderator without signing the request is not enough
+    invalid_get_request(&moderated_path, StatusCode::NOT_FOUND).await?;
+
+    // Other viewers, and requests without the flag, still get a 404
+    invalid_get_request(
+        &format!("{ROOT_PATH}/{author_id}/{post_id}?viewer_id={author_id}&include_moderated=true"),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+    invalid_get_request(
+        &format!("{ROOT_PATH}/{author_id}/{post_id}?viewer_id={moderator_id}"),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_deleted_post_gone() -> Result<()> {
+    crate::utils::server::TestServiceServer::get_test_server().await;
+
+    // A post deleted while it had replies is kept with its content replaced
+    let author_id = Keypair::random().public_key().to_z32();
+    let post_id = "0035DE1E7ED00";
+    PostDetails {
+        content: POST_DELETED_CONTENT.to_string(),
+        id: post_id.to_string(),
+        indexed_at: 1_700_000_000_000,
+        author: author_id.clone(),
+        uri: post_uri_builder(author_id.clone(), post_id.to_string()),
+        ..Default::default()
+    }
+    .put_index_json(&[&author_id, post_id], None, None)
+    .await?;
+
+    let view = invalid_get_request(
+        &format!("{ROOT_PATH}/{author_id}/{post_id}"),
+        StatusCode::GONE,
+    )
+    .await;
+    let details = invalid_get_request(
+        &format!("{ROOT_PATH}/{author_id}/{post_id}/details"),
+        StatusCode::GONE,
+    )
+    .await;
+    PostDetails::remove_from_index_multiple_json(&[&[&author_id, post_id]]).await?;
+
+    view?;
+    details?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_post_view_etag() -> Result<()> {
+    let path = format!("{ROOT_PATH}/{CAIRO_USER}/{POST_H}");
+
+    let (status, etag, _) = conditional_get_request(&path, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let etag = etag.expect("The post view should carry an ETag");
+
+    // A matching tag, also among others, answers 304 without a body
+    let if_none_match = format!("W/\"0000000000000000\", {etag}");
+    let (status, _, body) = conditional_get_request(&path, Some(&if_none_match)).await?;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_empty());
+
+    // A stale tag gets the full view
+    let (status, _, body) = conditional_get_request(&path, Some("W/\"stale\"")).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains(POST_H));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_posts_bulk() -> Result<()> {
+    crate::utils::server::TestServiceServer::get_test_server().await;
+
+    let author_id = Keypair::random().public_key().to_z32();
+    let post_ids = ["0034BQ0000001", "0034BQ0000002", "0034BQ0000003"];
+    for (i, post_id) in post_ids.iter().enumerate() {
+        PostDetails {
+            content: format!("Bulk post {i}"),
+            id: post_id.to_string(),
+            indexed_at: 1_700_000_000_000 + i as i64,
+            author: author_id.clone(),
+            uri: post_uri_builder(author_id.clone(), post_id.to_string()),
+            ..Default::default()
+        }
+        .put_index_json(&[&author_id, post_id], None, None)
+        .await?;
+    }
+
+    // Out of creation order, with a missing post and a malformed ID in between
+    let keys = [
+        format!("{author_id}:{}", post_ids[2]),
+        format!("{author_id}:0034BQ0000004"),
+        format!("{author_id}:{}", post_ids[0]),
+        "malformed".to_string(),
+        format!("{author_id}:{}", post_ids[1]),
+    ];
+    let body = post_request(
+        endpoints::POSTS_BULK_ROUTE,
+        json!({ "post_ids": keys, "viewer_id": DETROIT }),
+    )
+    .await;
+
+    let details_keys: Vec<[&str; 2]> = post_ids
+        .iter()
+        .map(|post_id| [author_id.as_str(), *post_id])
+        .collect();
+    let details_keys: Vec<&[&str]> = details_keys.iter().map(|key| &key[..]).collect();
+    PostDetails::remove_from_index_multiple_json(&details_keys).await?;
+
+    // One entry per post, in the same order
+    let body = body?;
+    let views = body.as_array().expect("Post views should be an array");
+    assert_eq!(views.len(), 5);
+    assert_eq!(views[0]["details"]["id"], post_ids[2]);
+    assert!(views[1].is_null());
+    assert_eq!(views[2]["details"]["id"], post_ids[0]);
+    assert!(views[3].is_null());
+    assert_eq!(views[4]["details"]["id"], post_ids[1]);
+    assert_eq!(views[4]["details"]["content"], "Bulk post 1");
+
+    let keys: Vec<String> = (0..101).map(|_| format!("{CAIRO_USER}:{POST_H}")).collect();
+    invalid_post_request(
+        endpoints::POSTS_BULK_ROUTE,
+        json!({ "post_ids": keys }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -81,3 +81,28 @@ async fn test_stream_user_bookmarks_by_timeline_with_skip_end() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_user_bookmarks_with_all_time_timeframe() -> Result<()> {
+    let path = format!(
+        "{ROOT_PATH}?observer_id={BOOKMARKER_ID}&source=bookmarks&timeframe=all_time&limit=5&end={END_TIMELINE}"
+    );
+
+    let body = get_request(&path).await?;
+    let post_list = vec![POST_TA, POST_TB, POST_TC, POST_TD, POST_TE];
+    verify_post_list(post_list, body);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_user_bookmarks_with_today_timeframe() -> Result<()> {
+    // All the mocked bookmarks are older than a day
+    let path = format!("{ROOT_PATH}?observer_id={BOOKMARKER_ID}&source=bookmarks&timeframe=today");
+
+    let body = get_request(&path).await?;
+    assert!(body.is_array());
+    assert!(body.as_array().unwrap().is_empty());
+
+    Ok(())
+}
@@ -100,3 +100,62 @@ async fn test_stream_post_keys_with_multiple_tags_uses_graph() -> Result<()> {
     let query = format!("tags={tags}&limit=5");
     assert_post_keys_align_with_posts(&query, "when using graph query with multiple tags").await
 }
+
+/// Fetches the post keys with their engagement components, checking they align with the keys
+async fn get_post_keys_engagement(query_params: &str) -> Result<(Vec<Value>, Vec<Value>)> {
+    let keys_body = get_request(&format!(
+        "{KEYS_ROOT_PATH}?{query_params}&include_engagement=true"
+    ))
+    .await?;
+    let keys = keys_body["post_keys"].as_array().unwrap().clone();
+    let engagement = keys_body["engagement"]
+        .as_array()
+        .expect("Post key stream should expose an engagement array on request")
+        .clone();
+    assert_eq!(keys.len(), engagement.len());
+
+    // Not requested, not returned
+    let keys_body = get_request(&format!("{KEYS_ROOT_PATH}?{query_params}")).await?;
+    assert!(keys_body.get("engagement").is_none());
+
+    Ok((keys, engagement))
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_post_keys_include_engagement() -> Result<()> {
+    let query = "sorting=timeline&limit=5";
+    let (keys, engagement) = get_post_keys_engagement(query).await?;
+    assert!(!keys.is_empty());
+
+    // The components match the counts of the posts
+    let posts_body = get_request(&format!("{ROOT_PATH}?{query}")).await?;
+    let posts = posts_body.as_array().unwrap();
+    verify_keys_match_posts(&keys, posts);
+    for (components, post) in engagement.iter().zip(posts) {
+        assert_eq!(components["tags"], post["counts"]["tags"]);
+        assert_eq!(components["replies"], post["counts"]["replies"]);
+        assert_eq!(components["reposts"], post["counts"]["reposts"]);
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_post_keys_include_engagement_from_graph() -> Result<()> {
+    let query = format!("observer_id={USER_ID}&source=following&sorting=total_engagement&limit=5");
+    let (keys, engagement) = get_post_keys_engagement(&query).await?;
+    assert!(!keys.is_empty());
+
+    // The graph ranks the posts by the sum of their components
+    let totals: Vec<u64> = engagement
+        .iter()
+        .map(|components| {
+            components["tags"].as_u64().unwrap()
+                + components["replies"].as_u64().unwrap()
+                + components["reposts"].as_u64().unwrap()
+        })
+        .collect();
+    assert!(totals.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
-use crate::utils::get_request;
+use crate::utils::{get_request, invalid_get_request};
 use anyhow::Result;
+use axum::http::StatusCode;
 use nexus_common::models::post::{PostStream, PostView};
 
 use super::{AMSTERDAM, ROOT_PATH};
@@ -213,6 +214,101 @@ async fn test_stream_posts_replies_with_start_and_end_also_limit_query() -> Resu
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_replies_in_thread_order() -> Result<()> {
+    let path = format!(
+        "{ROOT_PATH}?source=post_replies&author_id={AUTHOR_ID}&post_id={PARENT_POST_ID}&thread_order=true&skip=1&limit=3"
+    );
+    let body = get_request(&path).await?;
+
+    assert!(body.is_array());
+    let post_reply_stream: PostStream = serde_json::from_value(body)?;
+
+    // Without nested replies, the thread order is the chronological one
+    let replies_order = vec![CHILD_2_POST_ID, CHILD_3_POST_ID, CHILD_4_POST_ID];
+    assert_eq!(post_reply_stream.0.len(), 3);
+
+    check_replies_timeline(post_reply_stream.0, replies_order);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_thread_order_invalid() -> Result<()> {
+    // Thread order is only meaningful for the replies of a post
+    let path = format!("{ROOT_PATH}?source=author&author_id={AUTHOR_ID}&thread_order=true");
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    let path = format!(
+        "{ROOT_PATH}?source=post_replies&author_id={AUTHOR_ID}&post_id={PARENT_POST_ID}&thread_order=true&thread_depth=21"
+    );
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    // The thread order is neither sorted nor filtered
+    for param in [
+        "sorting=total_engagement",
+        "order=ascending",
+        "start=0",
+        "end=0",
+        "kind=short",
+        "quotes_only=true",
+    ] {
+        let path = format!(
+            "{ROOT_PATH}?source=post_replies&author_id={AUTHOR_ID}&post_id={PARENT_POST_ID}&thread_order=true&{param}"
+        );
+        invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_replies_quotes_only_invalid() -> Result<()> {
+    // Quote-posts are reposts, never replies
+    let path = format!(
+        "{ROOT_PATH}?source=post_replies&author_id={AUTHOR_ID}&post_id={PARENT_POST_ID}&quotes_only=true"
+    );
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    let path = format!("{ROOT_PATH}?source=author_replies&author_id={AUTHOR_ID}&quotes_only=true");
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_replies_with_min_engagement() -> Result<()> {
+    let path = format!(
+        "{ROOT_PATH}?source=post_replies&author_id={AUTHOR_ID}&post_id={PARENT_POST_ID}&sorting=total_engagement"
+    );
+    let replies: PostStream = serde_json::from_value(get_request(&path).await?)?;
+    // Every reply is ranked by default
+    assert_eq!(replies.0.len(), 6);
+
+    let min_engagement = 1;
+    let engaged_replies = replies
+        .0
+        .iter()
+        .filter(|post| {
+            post.counts.tags + post.counts.replies + post.counts.reposts >= min_engagement
+        })
+        .count();
+
+    let body = get_request(&format!("{path}&min_engagement={min_engagement}")).await?;
+    let filtered: PostStream = serde_json::from_value(body)?;
+    assert_eq!(filtered.0.len(), engaged_replies);
+    for post in filtered.0 {
+        let engagement = post.counts.tags + post.counts.replies + post.counts.reposts;
+        assert!(
+            engagement >= min_engagement,
+            "Reply {} should have at least {min_engagement} interactions",
+            post.details.id
+        );
+    }
+
+    Ok(())
+}
+
 pub fn check_replies_timeline(posts: Vec<PostView>, post_order: Vec<&str>) {
     for (index, post) in posts.iter().enumerate() {
         // Check if the order of the post is the right one
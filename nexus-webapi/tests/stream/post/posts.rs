@@ -1,7 +1,9 @@
-use crate::utils::{get_request, invalid_get_request, post_request};
+use crate::utils::{get_request, invalid_get_request, post_request, put_corrupt_details};
 use anyhow::Result;
 use axum::http::StatusCode;
-use nexus_common::models::post::PostStream;
+use nexus_common::db::RedisOps;
+use nexus_common::models::post::{PostDetails, PostStream};
+use pubky::Keypair;
 use serde_json::json;
 
 use super::utils::{search_tag_in_post, verify_post_list, verify_timeline_post_list};
@@ -10,6 +12,10 @@ use super::{ROOT_PATH, USER_ID};
 use super::{TAG_LABEL_1, TAG_LABEL_2};
 
 const CAIRO_USER: &str = "f5tcy5gtgzshipr6pag6cn9uski3s8tjare7wd3n7enmyokgjk1o";
+const EIXAMPLE_USER: &str = "8attbeo9ftu5nztqkcfw3gydksehr7jbspgfi64u4h8eo5e7dbiy";
+// Eixample's root post that Eixample replied to with SELF_REPLY
+const SELF_THREAD_ROOT: &str = "SIJW1TGL5BKG8";
+const SELF_REPLY: &str = "SIJW1TGL5BKG9";
 
 // Post order by timeline
 pub const POST_TA: &str = "2ZKB76Q194T00";
@@ -270,6 +276,36 @@ async fn test_stream_combined_parameters() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_by_engagement_with_min_engagement() -> Result<()> {
+    let min_engagement = 5;
+    let path =
+        format!("{ROOT_PATH}?sorting=total_engagement&min_engagement={min_engagement}&limit=30");
+    let body = get_request(&path).await?;
+    let post_stream: PostStream = serde_json::from_value(body)?;
+
+    assert!(!post_stream.0.is_empty(), "Post stream should not be empty");
+    // Only the posts with enough interactions are ranked
+    for post in post_stream.0 {
+        let engagement = post.counts.tags + post.counts.replies + post.counts.reposts;
+        assert!(
+            engagement >= min_engagement,
+            "Post {} should have at least {min_engagement} interactions",
+            post.details.id
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_invalid_min_engagement() -> Result<()> {
+    let endpoint = "/v0/stream/posts?sorting=total_engagement&min_engagement=1001";
+    invalid_get_request(endpoint, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_stream_invalid_sorting() -> Result<()> {
     // Invalid sorting option should fail
@@ -362,3 +398,67 @@ async fn test_stream_posts_by_ids_with_attachment_metadata() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_by_ids_partial_failure() -> Result<()> {
+    let corrupt_author_id = Keypair::random().public_key().to_z32();
+    let corrupt_post_id = "2ZK3C0RRP7T00";
+    put_corrupt_details("Post:Details", &[&corrupt_author_id, corrupt_post_id]).await?;
+
+    let request_body = json!({
+        "post_ids": [
+            format!("{CAIRO_USER}:{POST_H}"),
+            format!("{corrupt_author_id}:{corrupt_post_id}"),
+        ]
+    });
+    let body = post_request("/v0/stream/posts/by_ids", request_body).await;
+    PostDetails::remove_from_index_multiple_json(&[&[&corrupt_author_id, corrupt_post_id]]).await?;
+
+    // The post that fails to load is left out, the others are still listed
+    let body = body?;
+    let posts = body.as_array().expect("Post stream should be an array");
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["details"]["id"], POST_H);
+    assert_eq!(posts[0]["details"]["author"], CAIRO_USER);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_by_author_collapse_self_threads() -> Result<()> {
+    let path = format!(
+        "{ROOT_PATH}?author_id={EIXAMPLE_USER}&source=author&collapse_self_threads=true&limit=30"
+    );
+    let body = get_request(&path).await?;
+
+    let posts = body.as_array().expect("Post stream should be an array");
+
+    // The self-reply is collapsed into its root post
+    assert!(posts.iter().all(|p| p["details"]["id"] != SELF_REPLY));
+    for post in posts {
+        let is_thread_root = post["details"]["id"] == SELF_THREAD_ROOT;
+        // The flag is left out of the posts that do not start a self-thread
+        assert_eq!(
+            post["has_self_thread"].as_bool().unwrap_or(false),
+            is_thread_root
+        );
+    }
+    assert!(posts.iter().any(|p| p["details"]["id"] == SELF_THREAD_ROOT));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_by_author_without_collapse_self_threads() -> Result<()> {
+    let path = format!("{ROOT_PATH}?author_id={EIXAMPLE_USER}&source=author&limit=30");
+    let body = get_request(&path).await?;
+
+    let posts = body.as_array().expect("Post stream should be an array");
+    let thread_root = posts
+        .iter()
+        .find(|p| p["details"]["id"] == SELF_THREAD_ROOT)
+        .expect("The thread root should be in the stream");
+    assert!(thread_root.get("has_self_thread").is_none());
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::utils::{get_request, invalid_get_request};
 use anyhow::Result;
 use axum::http::StatusCode;
+use nexus_common::config::DEFAULT_MAX_STREAM_TAGS;
 use nexus_common::models::post::PostStream;
 
 use super::utils::{search_tag_in_post, verify_post_list, verify_timeline_post_list};
@@ -364,3 +365,32 @@ async fn test_post_specific_tag_with_no_result() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_tag_search_too_many_tags() -> Result<()> {
+    // The test server runs with the default cap of tags
+    let tags: Vec<String> = (0..=DEFAULT_MAX_STREAM_TAGS)
+        .map(|i| format!("tag{i}"))
+        .collect();
+    let path = format!("{ROOT_PATH}?tags={}", tags.join(","));
+    let body = invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+    assert!(body["error"]
+        .as_str()
+        .unwrap_or_default()
+        .contains(&format!("maximum allowed is {DEFAULT_MAX_STREAM_TAGS}")));
+
+    // Up to the cap, the posts matching any of the tags are returned
+    let path = format!(
+        "{ROOT_PATH}?tags={}",
+        tags[..DEFAULT_MAX_STREAM_TAGS - 1]
+            .iter()
+            .map(String::as_str)
+            .chain([TAG_LABEL_2])
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let body = get_request(&path).await?;
+    assert!(!body.as_array().unwrap().is_empty());
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
-use crate::utils::{invalid_post_request, post_request};
+use crate::utils::{invalid_post_request, post_request, put_corrupt_details};
 use anyhow::Result;
 use axum::http::StatusCode;
+use nexus_common::db::RedisOps;
+use nexus_common::models::user::UserDetails;
+use pubky::Keypair;
 use serde_json::json;
 
 // ##### LIST OF USERS BY ID ######
@@ -51,6 +54,25 @@ async fn test_stream_users_by_ids_valid_request() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_users_by_ids_partial_failure() -> Result<()> {
+    let user_id = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+    let corrupt_user_id = Keypair::random().public_key().to_z32();
+    put_corrupt_details("User:Details", &[&corrupt_user_id]).await?;
+
+    let request_body = json!({ "user_ids": [user_id, corrupt_user_id] });
+    let res = post_request("/v0/stream/users/by_ids", request_body).await;
+    UserDetails::remove_from_index_multiple_json(&[&[&corrupt_user_id]]).await?;
+
+    // The user that fails to load is left out, the others are still listed
+    let res = res?;
+    let users = res.as_array().expect("User stream should be an array");
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["details"]["id"], user_id);
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_stream_users_by_ids_limit_exceeded() -> Result<()> {
     // Generate a list of 1001 user IDs to exceed the limit
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use axum::http::StatusCode;
-use serde_json::Value;
+use serde_json::{json, Value};
 
-use crate::utils::{get_request, invalid_get_request};
+use crate::utils::{get_request, invalid_get_request, invalid_post_request, post_request};
 
 const PEER_PUBKY: &str = "o1gg96ewuojmopcjbz8895478wdtxtzzuxnfjjz8o8e77csa1ngo";
 // mocks/hot-tags.cypher users
@@ -744,3 +744,46 @@ async fn test_hot_tags_label_taggers_with_reach_friends_skip_and_limit() -> Resu
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_hot_tags_by_several_reaches() -> Result<()> {
+    let body = post_request(
+        "/v0/tags/hot/reaches",
+        json!({ "user_id": PEER_PUBKY, "reaches": ["following", "followers"] }),
+    )
+    .await?;
+
+    let results = body.as_array().expect("Reach results should be an array");
+    assert_eq!(results.len(), 2);
+
+    // Each reach matches the hot tags of its own request, in the requested order
+    for (result, reach) in results.iter().zip(["following", "followers"]) {
+        assert_eq!(result["status"], "ok");
+        assert!(result["error"].is_null());
+        let expected =
+            get_request(&format!("/v0/tags/hot?user_id={PEER_PUBKY}&reach={reach}")).await?;
+        assert_eq!(result["hot_tags"], expected);
+        analyse_hot_tags_structure(result["hot_tags"].as_array().unwrap());
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_hot_tags_by_several_reaches_invalid_input() -> Result<()> {
+    invalid_post_request(
+        "/v0/tags/hot/reaches",
+        json!({ "user_id": PEER_PUBKY, "reaches": [] }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    invalid_post_request(
+        "/v0/tags/hot/reaches",
+        json!({ "user_id": PEER_PUBKY, "reaches": vec!["following"; 9] }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -59,6 +59,54 @@ async fn test_user_tags_limit_tag_filter_active() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_post_all_tags_pagination() -> Result<()> {
+    let labels = |body: serde_json::Value| -> Vec<String> {
+        body.as_array()
+            .expect("Tag list should be an array")
+            .iter()
+            .map(|tag| tag["label"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let all = labels(get_request(&format!("/v0/post/{PEER_PUBKY}/{POST_ID}/tags")).await?);
+    assert_eq!(all.len(), 3);
+
+    let first_page = get_request(&format!(
+        "/v0/post/{PEER_PUBKY}/{POST_ID}/all-tags?skip=0&limit=2"
+    ))
+    .await?;
+    analyse_tag_details_structure(first_page.as_array().unwrap());
+    let first_page = labels(first_page);
+    let second_page = labels(
+        get_request(&format!(
+            "/v0/post/{PEER_PUBKY}/{POST_ID}/all-tags?skip=2&limit=2"
+        ))
+        .await?,
+    );
+
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(second_page.len(), 1);
+    // Pages follow the same ordering as the full list, without gaps or duplicates
+    assert_eq!([first_page, second_page].concat(), all);
+
+    // Paginating past the last tag returns an empty page
+    let empty_page = get_request(&format!(
+        "/v0/post/{PEER_PUBKY}/{POST_ID}/all-tags?skip=3&limit=2"
+    ))
+    .await?;
+    assert_eq!(empty_page.as_array().unwrap().len(), 0);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_post_all_tags_not_found() -> Result<()> {
+    let path = format!("/v0/post/{ANONYMOUS_PUBKY}/{POST_ID}/all-tags");
+    invalid_get_request(&path, StatusCode::NOT_FOUND).await?;
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_user_tags_viewer_filter_active() -> Result<()> {
     let path = format!("/v0/post/{PEER_PUBKY}/{POST_ID}/tags?viewer_id={PUBKY_PEER}");
@@ -1,11 +1,18 @@
-use crate::utils::get_request;
+use crate::utils::{get_request, invalid_get_request};
 use anyhow::Result;
-use nexus_webapi::routes::v0::endpoints::SEARCH_TAGS_BY_PREFIX_ROUTE;
+use axum::http::StatusCode;
+use nexus_webapi::routes::v0::endpoints::{
+    SEARCH_TAGS_BY_LABEL_ROUTE, SEARCH_TAGS_BY_PREFIX_ROUTE,
+};
 
 pub fn format_search_tags_by_prefix(prefix: &str) -> String {
     SEARCH_TAGS_BY_PREFIX_ROUTE.replace("{prefix}", prefix)
 }
 
+pub fn format_search_tags_by_label(label: &str) -> String {
+    SEARCH_TAGS_BY_LABEL_ROUTE.replace("{label}", label)
+}
+
 fn extract_str_vec(res: serde_json::Value) -> Vec<String> {
     assert!(res.is_array());
 
@@ -46,6 +53,15 @@ async fn test_search_tags_by_prefix_sanitized() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_search_tags_by_blank_prefix() -> Result<()> {
+    // Whitespace-only prefixes are rejected instead of matching every tag
+    let url_path = format_search_tags_by_prefix("%20%20%20");
+    invalid_get_request(&url_path, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_search_tags_with_emojis_by_prefix() -> Result<()> {
     let label_prefix = "⭐⭐⭐";
@@ -72,3 +88,20 @@ async fn test_search_non_existing_prefix() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_search_tags_by_exact_label() -> Result<()> {
+    // The prefix search would also return "⭐⭐⭐⭐" and "⭐⭐⭐⭐⭐"
+    let res = get_request(&format_search_tags_by_label("⭐⭐⭐")).await?;
+    assert_eq!(extract_str_vec(res), vec!["⭐⭐⭐"]);
+
+    // Labels are sanitized like in the prefix search
+    let res = get_request(&format_search_tags_by_label("Hello ")).await?;
+    assert_eq!(extract_str_vec(res), vec!["hello"]);
+
+    // A prefix of an existing label is not an exact match
+    let res = get_request(&format_search_tags_by_label("hell")).await?;
+    assert!(extract_str_vec(res).is_empty());
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use anyhow::Result;
 use axum::http::StatusCode;
+use nexus_common::db::kv::ScoreAction;
+use nexus_common::models::tag::traits::TagCollection;
+use nexus_common::models::tag::user::TagUser;
 use nexus_webapi::routes::v0::TaggersInfoResponse;
+use pubky::Keypair;
 
 use crate::{
     tags::PEER_PUBKY,
@@ -320,3 +324,28 @@ async fn test_user_specific_tag_with_no_result() -> Result<()> {
     Ok(())
 }
 // TODO: Check if it is in the cache. Maybe we should add under tests/service: endpoints, db, ...
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_applied_labels() -> Result<()> {
+    let tagger_id = Keypair::random().public_key().to_z32();
+    for (label, tags) in [("rust", 2), ("dev", 1)] {
+        for _ in 0..tags {
+            TagUser::update_tagger_label_score(&tagger_id, label, ScoreAction::Increment(1.0))
+                .await?;
+        }
+    }
+    // Labels whose tags were all deleted are left out
+    TagUser::update_tagger_label_score(&tagger_id, "unused", ScoreAction::Increment(1.0)).await?;
+    TagUser::update_tagger_label_score(&tagger_id, "unused", ScoreAction::Decrement(1.0)).await?;
+
+    let body = get_request(&format!("/v0/user/{tagger_id}/applied-labels")).await?;
+    assert_eq!(body, serde_json::json!(["rust", "dev"]));
+
+    let body = get_request(&format!(
+        "/v0/user/{tagger_id}/applied-labels?skip=1&limit=1"
+    ))
+    .await?;
+    assert_eq!(body, serde_json::json!(["dev"]));
+
+    Ok(())
+}
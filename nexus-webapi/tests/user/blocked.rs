@@ -0,0 +1,72 @@
+use crate::utils::{get_request, invalid_get_request, post_request};
+use anyhow::Result;
+use axum::http::StatusCode;
+use nexus_common::models::user::Blocked;
+use nexus_webapi::routes::v0::endpoints::{STREAM_USERS_BY_IDS_ROUTE, USER_BLOCKED_ROUTE};
+use pubky::Keypair;
+use serde_json::json;
+
+// Aldert
+const USER_A: &str = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+// Flavio
+const USER_B: &str = "5g3fwnue819wfdjwiwm8qr35ww6uxxgbzrigrtdgmbi19ksioeoy";
+const USER_C: &str = "o1gg96ewuojmopcjbz8895478wdtxtzzuxnfjjz8o8e77csa1ngo";
+const VIEWER: &str = "kzq3o8y8w1b7ffogpq73okop4gb3ahm31ytwwk1na8p6gpr4511o";
+
+#[tokio_shared_rt::test(shared)]
+async fn test_blocked_relationships_in_batch() -> Result<()> {
+    // The viewer blocked A, and B blocked the viewer
+    Blocked::put_to_index(VIEWER, USER_A).await?;
+    Blocked::put_to_index(USER_B, VIEWER).await?;
+
+    let body = json!({ "user_ids": [USER_A, USER_B, USER_C], "viewer_id": VIEWER });
+    let users = post_request(STREAM_USERS_BY_IDS_ROUTE, body).await?;
+    let users = users.as_array().expect("User stream should be an array");
+    assert_eq!(users.len(), 3);
+
+    let expected = [
+        (USER_A, true, false),
+        (USER_B, false, true),
+        (USER_C, false, false),
+    ];
+    for (user, (user_id, blocked, blocked_by)) in users.iter().zip(expected) {
+        assert_eq!(user["details"]["id"], user_id);
+        assert_eq!(
+            user["relationship"]["blocked"], blocked,
+            "blocked of {user_id}"
+        );
+        assert_eq!(
+            user["relationship"]["blocked_by"], blocked_by,
+            "blocked_by of {user_id}"
+        );
+
+        // The batched relationship matches the one of the single user endpoint
+        let relationship =
+            get_request(&format!("/v0/user/{user_id}/relationship/{VIEWER}")).await?;
+        assert_eq!(user["relationship"], relationship);
+    }
+
+    let blocked = get_request(&USER_BLOCKED_ROUTE.replace("{user_id}", VIEWER)).await?;
+    assert_eq!(blocked, json!([USER_A]));
+
+    Blocked::del_from_index(VIEWER, USER_A).await?;
+    Blocked::del_from_index(USER_B, VIEWER).await?;
+
+    let relationship = get_request(&format!("/v0/user/{USER_A}/relationship/{VIEWER}")).await?;
+    assert_eq!(relationship["blocked"], false);
+    let blocked = get_request(&USER_BLOCKED_ROUTE.replace("{user_id}", VIEWER)).await?;
+    assert_eq!(blocked, json!([]));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_blocked_unknown_user() -> Result<()> {
+    let user_id = Keypair::random().public_key().to_z32();
+    invalid_get_request(
+        &USER_BLOCKED_ROUTE.replace("{user_id}", &user_id),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+    Ok(())
+}
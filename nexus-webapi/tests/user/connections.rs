@@ -0,0 +1,55 @@
+use crate::utils::{get_request, invalid_get_request};
+use anyhow::Result;
+use axum::http::StatusCode;
+use nexus_webapi::routes::v0::endpoints::USER_TOP_CONNECTIONS_ROUTE;
+use pubky::Keypair;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_top_connections() -> Result<()> {
+    // Aldert
+    let user_id = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+    let path = USER_TOP_CONNECTIONS_ROUTE.replace("{user_id}", user_id);
+
+    let body = get_request(&format!("{path}?limit=5")).await?;
+    let connections = body.as_array().expect("Top connections should be an array");
+    assert!(
+        !connections.is_empty(),
+        "Aldert should have interacted with other users"
+    );
+    assert!(connections.len() <= 5);
+
+    let mut previous_total = u64::MAX;
+    for connection in connections {
+        assert_ne!(
+            connection["user_id"], user_id,
+            "The user is not its own connection"
+        );
+        let total = connection["total"].as_u64().unwrap();
+        assert_eq!(
+            total,
+            connection["replies"].as_u64().unwrap()
+                + connection["reposts"].as_u64().unwrap()
+                + connection["tags"].as_u64().unwrap()
+        );
+        assert!(total > 0);
+        assert!(
+            total <= previous_total,
+            "Connections should be sorted by total"
+        );
+        previous_total = total;
+    }
+
+    // A second request is served from the cache, with the same ranking
+    let cached = get_request(&format!("{path}?limit=5")).await?;
+    assert_eq!(cached, body);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_top_connections_unknown_user() -> Result<()> {
+    let user_id = Keypair::random().public_key().to_z32();
+    let path = USER_TOP_CONNECTIONS_ROUTE.replace("{user_id}", &user_id);
+    invalid_get_request(&path, StatusCode::NOT_FOUND).await?;
+    Ok(())
+}
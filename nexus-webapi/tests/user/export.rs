@@ -0,0 +1,139 @@
+use crate::utils::server::TEST_EXPORT_TOKEN;
+use crate::utils::{host_url, invalid_get_request};
+use anyhow::Result;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{Method, StatusCode};
+use nexus_common::db::RedisOps;
+use nexus_common::models::user::UserDetails;
+use nexus_webapi::models::auth::authorization;
+use nexus_webapi::routes::v0::endpoints::USER_EXPORT_ROUTE;
+use pubky::Keypair;
+use pubky_app_specs::PubkyId;
+use serde_json::Value;
+
+// Aldert
+const USER_ID: &str = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_export() -> Result<()> {
+    let url = format!(
+        "{}{}",
+        host_url().await,
+        USER_EXPORT_ROUTE.replace("{user_id}", USER_ID)
+    );
+    let client = httpc_test::new_client("")?;
+    let res = client
+        .reqwest_client()
+        .get(&url)
+        .bearer_auth(TEST_EXPORT_TOKEN)
+        .send()
+        .await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["content-type"], "application/x-ndjson");
+
+    let records = res
+        .text()
+        .await?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<Value>, _>>()?;
+
+    // The profile comes first, followed by the other sections
+    assert_eq!(records[0]["type"], "profile");
+    assert_eq!(records[0]["data"]["id"], USER_ID);
+    assert_eq!(records[0]["data"]["name"], "Aldert");
+    for record in &records[1..] {
+        assert_ne!(record["type"], "profile");
+    }
+    assert!(records.iter().any(|record| record["type"] == "post"));
+    for post in records.iter().filter(|record| record["type"] == "post") {
+        assert_eq!(post["data"]["author"], USER_ID);
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_export_unauthorized() -> Result<()> {
+    let endpoint = USER_EXPORT_ROUTE.replace("{user_id}", USER_ID);
+    invalid_get_request(&endpoint, StatusCode::UNAUTHORIZED).await?;
+
+    let url = format!("{}{endpoint}", host_url().await);
+    let client = httpc_test::new_client("")?;
+    let res = client
+        .reqwest_client()
+        .get(&url)
+        .bearer_auth("wrong_token")
+        .send()
+        .await?;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+/// Sends an export request of `user_id` signed with `keypair`, returning the status and the body
+async fn signed_export(user_id: &str, keypair: &Keypair) -> Result<(StatusCode, String)> {
+    let endpoint = USER_EXPORT_ROUTE.replace("{user_id}", user_id);
+    let url = format!("{}{endpoint}", host_url().await);
+    let client = httpc_test::new_client("")?;
+    let res = client
+        .reqwest_client()
+        .get(&url)
+        .header(
+            AUTHORIZATION,
+            authorization(keypair, &Method::GET, &endpoint, &[]),
+        )
+        .send()
+        .await?;
+    Ok((res.status(), res.text().await?))
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_export_signed_by_user() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    UserDetails {
+        name: "Export".to_string(),
+        id: PubkyId::try_from(user_id.as_str()).map_err(|e| anyhow::anyhow!("{e}"))?,
+        indexed_at: 1_700_000_000_000,
+        ..Default::default()
+    }
+    .put_index_json(&[&user_id], None, None)
+    .await?;
+
+    let own = signed_export(&user_id, &user_kp).await;
+    let other = signed_export(&user_id, &Keypair::random()).await;
+
+    UserDetails::remove_from_index_multiple_json(&[&[&user_id]]).await?;
+
+    // A user exports their own data without the operator token
+    let (status, body) = own?;
+    assert_eq!(status, StatusCode::OK);
+    let profile: Value = serde_json::from_str(body.lines().next().unwrap_or_default())?;
+    assert_eq!(profile["type"], "profile");
+    assert_eq!(profile["data"]["id"], user_id);
+
+    // But not the data of another user
+    assert_eq!(other?.0, StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_export_not_found() -> Result<()> {
+    let url = format!(
+        "{}{}",
+        host_url().await,
+        USER_EXPORT_ROUTE.replace("{user_id}", "nonexistent_user")
+    );
+    let client = httpc_test::new_client("")?;
+    let res = client
+        .reqwest_client()
+        .get(&url)
+        .bearer_auth(TEST_EXPORT_TOKEN)
+        .send()
+        .await?;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use crate::utils::{get_request, invalid_get_request, invalid_put_request, signed_request};
+use anyhow::Result;
+use axum::http::{Method, StatusCode};
+use nexus_common::config::DEFAULT_MAX_STREAM_TAGS;
+use nexus_webapi::routes::v0::endpoints::{
+    STREAM_POSTS_FOLLOWED_TAGS_ROUTE, USER_FOLLOWED_TAGS_ROUTE, USER_FOLLOWED_TAG_ROUTE,
+    USER_SUGGESTED_TAGS_ROUTE,
+};
+use pubky::Keypair;
+use serde_json::json;
+
+fn followed_tag_path(user_id: &str, label: &str) -> String {
+    USER_FOLLOWED_TAG_ROUTE
+        .replace("{user_id}", user_id)
+        .replace("{label}", label)
+}
+
+/// Follows a tag with a request signed by the user
+async fn follow_tag(user_kp: &Keypair, label: &str) -> Result<serde_json::Value> {
+    let path = followed_tag_path(&user_kp.public_key().to_z32(), label);
+    let (status, body) = signed_request(Method::PUT, &path, user_kp, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    Ok(body)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_follow_and_unfollow_tags() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    let list_path = USER_FOLLOWED_TAGS_ROUTE.replace("{user_id}", &user_id);
+
+    let body = get_request(&list_path).await?;
+    assert_eq!(body, json!([]));
+
+    let body = follow_tag(&user_kp, "bitcoin").await?;
+    assert_eq!(body, json!(["bitcoin"]));
+
+    // Following the same tag twice, with another casing, keeps a single entry
+    let body = follow_tag(&user_kp, "BitCoin").await?;
+    assert_eq!(body, json!(["bitcoin"]));
+
+    let (status, body) = signed_request(
+        Method::DELETE,
+        &followed_tag_path(&user_id, "bitcoin"),
+        &user_kp,
+        None,
+    )
+    .await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!([]));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_follow_tag_unauthorized() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    let path = followed_tag_path(&user_id, "bitcoin");
+
+    // Without a signature
+    invalid_put_request(&path, json!(null), StatusCode::UNAUTHORIZED).await?;
+
+    // Signed by another user
+    let other_kp = Keypair::random();
+    let (status, _) = signed_request(Method::PUT, &path, &other_kp, None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    follow_tag(&user_kp, "bitcoin").await?;
+    let (status, _) = signed_request(Method::DELETE, &path, &other_kp, None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let list_path = USER_FOLLOWED_TAGS_ROUTE.replace("{user_id}", &user_id);
+    assert_eq!(get_request(&list_path).await?, json!(["bitcoin"]));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_follow_tag_limit() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+
+    // A user follows at most as many tags as a post stream can be filtered by
+    for i in 0..DEFAULT_MAX_STREAM_TAGS {
+        follow_tag(&user_kp, &format!("limit{i}")).await?;
+    }
+    let (status, _) = signed_request(
+        Method::PUT,
+        &followed_tag_path(&user_id, "onemore"),
+        &user_kp,
+        None,
+    )
+    .await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_follow_tag_invalid_user() -> Result<()> {
+    invalid_put_request(
+        &followed_tag_path("not_a_pubky", "bitcoin"),
+        json!(null),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_suggested_tags_exclude_followed() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    let path = USER_SUGGESTED_TAGS_ROUTE.replace("{user_id}", &user_id);
+
+    let body = get_request(&format!("{path}?limit=5")).await?;
+    let suggestions = body.as_array().expect("Suggestions should be an array");
+    assert!(!suggestions.is_empty());
+    assert!(suggestions.len() <= 5);
+
+    let followed = suggestions[0].as_str().unwrap().to_string();
+    follow_tag(&user_kp, &followed).await?;
+
+    let body = get_request(&format!("{path}?limit=5")).await?;
+    let suggestions = body.as_array().unwrap();
+    assert!(suggestions
+        .iter()
+        .all(|tag| tag.as_str() != Some(followed.as_str())));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_followed_tags_posts() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    let stream_path = STREAM_POSTS_FOLLOWED_TAGS_ROUTE.replace("{user_id}", &user_id);
+
+    // No followed tags, empty stream
+    let body = get_request(&stream_path).await?;
+    assert_eq!(body, json!([]));
+
+    follow_tag(&user_kp, "bitcoin").await?;
+
+    let body = get_request(&format!("{stream_path}?limit=5")).await?;
+    let posts = body.as_array().expect("Post stream should be an array");
+    assert!(!posts.is_empty());
+    assert!(posts.len() <= 5);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_suggested_tags_invalid_limit() -> Result<()> {
+    let user_id = Keypair::random().public_key().to_z32();
+    let path = USER_SUGGESTED_TAGS_ROUTE.replace("{user_id}", &user_id);
+
+    invalid_get_request(&format!("{path}?limit=-1"), StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
@@ -1,5 +1,12 @@
+pub mod blocked;
 pub mod bootstrap;
+pub mod connections;
+pub mod export;
+pub mod followed_tags;
+pub mod mutual;
+pub mod notification_preferences;
 pub mod notifications;
 pub mod reach;
+pub mod recommendations;
 pub mod search;
 pub mod views;
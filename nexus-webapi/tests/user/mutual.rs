@@ -0,0 +1,58 @@
+use crate::utils::{get_request, invalid_get_request};
+use anyhow::Result;
+use axum::http::StatusCode;
+use nexus_common::models::follow::{Followers, Following, UserFollows};
+use nexus_webapi::routes::v0::endpoints::USER_MUTUAL_ROUTE;
+use pubky::Keypair;
+use serde_json::json;
+
+fn random_user_id() -> String {
+    Keypair::random().public_key().to_z32()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_mutual_followers() -> Result<()> {
+    let [target, viewer, user_a, user_b, user_c, user_d] =
+        std::array::from_fn(|_| random_user_id());
+
+    // viewer -> A, B, C and A, B, D -> target
+    let following = Following(vec![user_a.clone(), user_b.clone(), user_c.clone()]);
+    following.put_to_index(&viewer).await?;
+    let followers = Followers(vec![user_a.clone(), user_b.clone(), user_d.clone()]);
+    followers.put_to_index(&target).await?;
+
+    let mut expected = vec![user_a.clone(), user_b.clone()];
+    expected.sort();
+
+    let path = USER_MUTUAL_ROUTE.replace("{user_id}", &target);
+    let body = get_request(&format!("{path}?viewer_id={viewer}")).await?;
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["user_ids"], json!(expected));
+
+    // The count covers all mutual followers, not only the requested page
+    let body = get_request(&format!("{path}?viewer_id={viewer}&skip=1&limit=1")).await?;
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["user_ids"], json!([expected[1]]));
+
+    // A viewer following none of the followers has no mutuals
+    let body = get_request(&format!("{path}?viewer_id={user_c}")).await?;
+    assert_eq!(body["count"], 0);
+    assert_eq!(body["user_ids"], json!([]));
+
+    following.del_from_index(&viewer).await?;
+    followers.del_from_index(&target).await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_mutual_followers_user_not_found() -> Result<()> {
+    let path = USER_MUTUAL_ROUTE.replace("{user_id}", &random_user_id());
+    invalid_get_request(
+        &format!("{path}?viewer_id={}", random_user_id()),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
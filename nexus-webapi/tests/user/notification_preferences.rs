@@ -0,0 +1,157 @@
+use crate::utils::{get_request, host_url, invalid_put_request, signed_request};
+use anyhow::Result;
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{Method, StatusCode};
+use nexus_webapi::models::auth::authorization;
+use nexus_webapi::routes::v0::endpoints::NOTIFICATION_PREFERENCES_ROUTE;
+use pubky::Keypair;
+use serde_json::json;
+
+fn preferences_path(user_id: &str) -> String {
+    NOTIFICATION_PREFERENCES_ROUTE.replace("{user_id}", user_id)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_notification_preferences_default() -> Result<()> {
+    let user_id = Keypair::random().public_key().to_z32();
+
+    let body = get_request(&preferences_path(&user_id)).await?;
+    assert_eq!(body["types"], json!({}));
+    assert!(body["do_not_disturb"].is_null());
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_notification_preferences_update() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    let preferences = json!({
+        "types": { "follow": false, "mention": true },
+        "do_not_disturb": { "start_minute": 1320, "end_minute": 420 },
+        "webhook_url": "https://example.com/notifications"
+    });
+
+    let (status, body) = signed_request(
+        Method::PUT,
+        &preferences_path(&user_id),
+        &user_kp,
+        Some(preferences.clone()),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, preferences);
+
+    let body = get_request(&preferences_path(&user_id)).await?;
+    assert_eq!(body, preferences);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_notification_preferences_invalid() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+
+    // Minutes past the end of the day
+    let preferences = json!({
+        "do_not_disturb": { "start_minute": 1440, "end_minute": 60 }
+    });
+    let (status, _) = signed_request(
+        Method::PUT,
+        &preferences_path(&user_id),
+        &user_kp,
+        Some(preferences),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Webhook URLs that are not HTTPS or target a local or private host
+    for webhook_url in [
+        "http://example.com/notifications",
+        "https://localhost/notifications",
+        "https://127.0.0.1:8080/notifications",
+        "https://169.254.169.254/latest/meta-data",
+        "https://[::1]/notifications",
+    ] {
+        let (status, _) = signed_request(
+            Method::PUT,
+            &preferences_path(&user_id),
+            &user_kp,
+            Some(json!({ "webhook_url": webhook_url })),
+        )
+        .await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{webhook_url}");
+    }
+
+    // Not a Pubky ID
+    let (status, _) = signed_request(
+        Method::PUT,
+        &preferences_path("not_a_pubky_id"),
+        &user_kp,
+        Some(json!({})),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_notification_preferences_unauthorized() -> Result<()> {
+    let user_id = Keypair::random().public_key().to_z32();
+    let preferences = json!({ "types": { "follow": false } });
+
+    // Signed by another user
+    let (status, _) = signed_request(
+        Method::PUT,
+        &preferences_path(&user_id),
+        &Keypair::random(),
+        Some(preferences.clone()),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Not signed at all
+    invalid_put_request(
+        &preferences_path(&user_id),
+        preferences,
+        StatusCode::UNAUTHORIZED,
+    )
+    .await?;
+
+    // The preferences are left unchanged
+    let body = get_request(&preferences_path(&user_id)).await?;
+    assert_eq!(body["types"], json!({}));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_notification_preferences_replayed_request() -> Result<()> {
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    let path = preferences_path(&user_id);
+    let body = json!({ "webhook_url": "https://example.com/notifications" }).to_string();
+    let signature = authorization(&user_kp, &Method::PUT, &path, body.as_bytes());
+
+    let url = format!("{}{path}", host_url().await);
+    let client = httpc_test::new_client("")?;
+    let mut statuses = Vec::new();
+    for _ in 0..2 {
+        let res = client
+            .reqwest_client()
+            .put(&url)
+            .header(AUTHORIZATION, &signature)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.clone())
+            .send()
+            .await?;
+        statuses.push(res.status());
+    }
+
+    // The same signed request is only accepted once
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::UNAUTHORIZED]);
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
-use crate::utils::get_request;
+use crate::utils::{get_request, invalid_get_request, invalid_post_request, signed_request};
 use anyhow::Result;
+use axum::http::{Method, StatusCode};
 use nexus_common::{
     db::RedisOps,
     models::notification::{Notification, NotificationBody},
 };
+use pubky::Keypair;
+use serde_json::json;
 
 async fn env_init() {
     crate::utils::server::TestServiceServer::get_test_server().await;
@@ -23,7 +26,10 @@ async fn seed_follow(recipient_id: &str, follower_id: &str, timestamp: i64) -> R
         None,
     )
     .await
-    .map_err(|e| anyhow::anyhow!("{e}"))
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+    Notification::reindex_types(recipient_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))
 }
 
 /// Seeds 3 follow notifications (A oldest → C newest) for a dedicated test user, then
@@ -297,3 +303,335 @@ async fn test_get_notifications_with_limit_and_skip() -> Result<()> {
 
     Ok(())
 }
+
+/// Seeds 4 follow notifications (A oldest → D newest) and verifies that order=ascending returns
+/// them oldest-first, with `start` and `end` bounding the timeframe from the oldest side.
+///
+/// Idempotent for the same reason as the limit test.
+#[tokio_shared_rt::test(shared)]
+async fn test_get_notifications_ascending() -> Result<()> {
+    env_init().await;
+    const TEST_USER: &str = "test_notif_asc_recipient_00000000001";
+    const FOLLOWER_A: &str = "test_notif_asc_follower_a_00000000001";
+    const FOLLOWER_B: &str = "test_notif_asc_follower_b_00000000001";
+    const FOLLOWER_C: &str = "test_notif_asc_follower_c_00000000001";
+    const FOLLOWER_D: &str = "test_notif_asc_follower_d_00000000001";
+
+    seed_follow(TEST_USER, FOLLOWER_A, 1000).await?;
+    seed_follow(TEST_USER, FOLLOWER_B, 2000).await?;
+    seed_follow(TEST_USER, FOLLOWER_C, 3000).await?;
+    seed_follow(TEST_USER, FOLLOWER_D, 4000).await?;
+
+    // Oldest first, paginated with skip and limit
+    let first_page = get_request(&format!(
+        "/v0/user/{TEST_USER}/notifications?order=ascending&limit=2"
+    ))
+    .await?;
+    let first_page = first_page.as_array().unwrap();
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(first_page[0]["body"]["followed_by"], FOLLOWER_A);
+    assert_eq!(first_page[1]["body"]["followed_by"], FOLLOWER_B);
+
+    let second_page = get_request(&format!(
+        "/v0/user/{TEST_USER}/notifications?order=ascending&skip=2&limit=2"
+    ))
+    .await?;
+    let second_page = second_page.as_array().unwrap();
+    assert_eq!(second_page.len(), 2);
+    assert_eq!(second_page[0]["timestamp"], 3000_i64);
+    assert_eq!(second_page[1]["timestamp"], 4000_i64);
+
+    // In ascending order, start is the earliest timestamp and end the latest
+    let timeframe = get_request(&format!(
+        "/v0/user/{TEST_USER}/notifications?order=ascending&start=2000&end=3000"
+    ))
+    .await?;
+    let timestamps: Vec<i64> = timeframe
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|notification| notification["timestamp"].as_i64().unwrap())
+        .collect();
+    assert_eq!(timestamps, vec![2000, 3000]);
+
+    // Descending remains the default
+    let default_order = get_request(&format!("/v0/user/{TEST_USER}/notifications?limit=1")).await?;
+    assert_eq!(default_order[0]["body"]["followed_by"], FOLLOWER_D);
+
+    Ok(())
+}
+
+/// Ids of the listed notifications of a user, by the follower they notify about
+async fn notification_ids(user_id: &str) -> Result<Vec<(String, String)>> {
+    let all = get_request(&format!("/v0/user/{user_id}/notifications")).await?;
+    Ok(all
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| {
+            let follower = n["body"]["followed_by"].as_str().unwrap().to_string();
+            (follower, n["id"].as_str().unwrap().to_string())
+        })
+        .collect())
+}
+
+/// Marks notifications of a user as read with a request signed by `signer_kp`
+async fn mark_read(
+    user_id: &str,
+    signer_kp: &Keypair,
+    ids: &[&str],
+) -> Result<(StatusCode, serde_json::Value)> {
+    signed_request(
+        Method::POST,
+        &format!("/v0/user/{user_id}/notifications/read"),
+        signer_kp,
+        Some(json!({ "ids": ids })),
+    )
+    .await
+}
+
+/// Marks all the notifications of a user as read with a request signed by `signer_kp`
+async fn mark_all_read(
+    user_id: &str,
+    signer_kp: &Keypair,
+) -> Result<(StatusCode, serde_json::Value)> {
+    signed_request(
+        Method::POST,
+        &format!("/v0/user/{user_id}/notifications/read-all"),
+        signer_kp,
+        None,
+    )
+    .await
+}
+
+/// Seeds 3 notifications for a fresh user, marks the middle one as read and verifies
+/// that only that notification flips its `read` flag, then marks all of them as read.
+#[tokio_shared_rt::test(shared)]
+async fn test_mark_notifications_read() -> Result<()> {
+    env_init().await;
+    // A fresh recipient on every run, the read state is not reset by re-seeding
+    let test_user_kp = Keypair::random();
+    let test_user = test_user_kp.public_key().to_z32();
+    const FOLLOWER_A: &str = "test_notif_read_follower_a_00000000001";
+    const FOLLOWER_B: &str = "test_notif_read_follower_b_00000000001";
+    const FOLLOWER_C: &str = "test_notif_read_follower_c_00000000001";
+
+    seed_follow(&test_user, FOLLOWER_A, 1000).await?;
+    seed_follow(&test_user, FOLLOWER_B, 2000).await?;
+    seed_follow(&test_user, FOLLOWER_C, 3000).await?;
+
+    let all = get_request(&format!("/v0/user/{test_user}/notifications")).await?;
+    let items = all.as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert!(items.iter().all(|n| n["read"] == false));
+    let ids = notification_ids(&test_user).await?;
+    let (_, id_b) = ids
+        .iter()
+        .find(|(follower, _)| follower == FOLLOWER_B)
+        .unwrap();
+
+    // Only the user can mark their notifications as read
+    let (status, _) = mark_read(&test_user, &Keypair::random(), &[id_b.as_str()]).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = mark_all_read(&test_user, &Keypair::random()).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let res = invalid_post_request(
+        &format!("/v0/user/{test_user}/notifications/read"),
+        json!({ "ids": [id_b] }),
+        StatusCode::UNAUTHORIZED,
+    )
+    .await;
+    assert!(res.is_ok());
+
+    // Unknown ids are ignored
+    let (status, res) = mark_read(
+        &test_user,
+        &test_user_kp,
+        &[id_b.as_str(), "9999:0000000000000000"],
+    )
+    .await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(res["read"], 1);
+
+    let all = get_request(&format!("/v0/user/{test_user}/notifications")).await?;
+    let items = all.as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    for item in items {
+        let expected = item["id"] == id_b.as_str();
+        assert_eq!(item["read"], expected, "Unexpected read flag: {item}");
+    }
+
+    let (status, res) = mark_all_read(&test_user, &test_user_kp).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(res["read"], 3);
+
+    let all = get_request(&format!("/v0/user/{test_user}/notifications")).await?;
+    assert!(all.as_array().unwrap().iter().all(|n| n["read"] == true));
+
+    Ok(())
+}
+
+/// Two notifications of the same millisecond are marked as read one at a time
+#[tokio_shared_rt::test(shared)]
+async fn test_mark_notifications_read_same_timestamp() -> Result<()> {
+    env_init().await;
+    let test_user_kp = Keypair::random();
+    let test_user = test_user_kp.public_key().to_z32();
+    const FOLLOWER_A: &str = "test_notif_read_same_ms_follower_a_0001";
+    const FOLLOWER_B: &str = "test_notif_read_same_ms_follower_b_0001";
+
+    seed_follow(&test_user, FOLLOWER_A, 1000).await?;
+    seed_follow(&test_user, FOLLOWER_B, 1000).await?;
+
+    let ids = notification_ids(&test_user).await?;
+    assert_eq!(ids.len(), 2);
+    assert_ne!(ids[0].1, ids[1].1);
+    let (_, id_a) = ids
+        .iter()
+        .find(|(follower, _)| follower == FOLLOWER_A)
+        .unwrap();
+
+    let (_, res) = mark_read(&test_user, &test_user_kp, &[id_a.as_str()]).await?;
+    assert_eq!(res["read"], 1);
+
+    let all = get_request(&format!("/v0/user/{test_user}/notifications")).await?;
+    for item in all.as_array().unwrap() {
+        let expected = item["body"]["followed_by"] == FOLLOWER_A;
+        assert_eq!(item["read"], expected, "Unexpected read flag: {item}");
+    }
+    let res = get_request(&format!("/v0/user/{test_user}/notifications/unread-count")).await?;
+    assert_eq!(res["unread"], 1);
+
+    Ok(())
+}
+
+/// Seeds 3 notifications for a fresh user, reads one and verifies the unread count follows
+/// both the read operations and newly arriving notifications.
+#[tokio_shared_rt::test(shared)]
+async fn test_unread_notifications_count() -> Result<()> {
+    env_init().await;
+    let test_user_kp = Keypair::random();
+    let test_user = test_user_kp.public_key().to_z32();
+    const FOLLOWER_A: &str = "test_notif_unread_follower_a_00000000001";
+    const FOLLOWER_B: &str = "test_notif_unread_follower_b_00000000001";
+    const FOLLOWER_C: &str = "test_notif_unread_follower_c_00000000001";
+    const FOLLOWER_D: &str = "test_notif_unread_follower_d_00000000001";
+    let unread_count_path = format!("/v0/user/{test_user}/notifications/unread-count");
+
+    let res = get_request(&unread_count_path).await?;
+    assert_eq!(res["unread"], 0);
+
+    seed_follow(&test_user, FOLLOWER_A, 1000).await?;
+    seed_follow(&test_user, FOLLOWER_B, 2000).await?;
+    seed_follow(&test_user, FOLLOWER_C, 3000).await?;
+
+    let res = get_request(&unread_count_path).await?;
+    assert_eq!(res["unread"], 3);
+
+    let ids = notification_ids(&test_user).await?;
+    let (_, id_a) = ids
+        .iter()
+        .find(|(follower, _)| follower == FOLLOWER_A)
+        .unwrap();
+    mark_read(&test_user, &test_user_kp, &[id_a.as_str()]).await?;
+    let res = get_request(&unread_count_path).await?;
+    assert_eq!(res["unread"], 2);
+
+    // A new notification arrives
+    seed_follow(&test_user, FOLLOWER_D, 4000).await?;
+    let res = get_request(&unread_count_path).await?;
+    assert_eq!(res["unread"], 3);
+
+    // A read entry without its notification does not hide an unread one
+    let gone = serde_json::to_string(&NotificationBody::Follow {
+        followed_by: "test_notif_unread_follower_gone_000000001".to_string(),
+    })?;
+    Notification::put_index_sorted_set(
+        &["Notification", "Read", &test_user],
+        &[(5000.0, gone.as_str())],
+        None,
+        None,
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let res = get_request(&unread_count_path).await?;
+    assert_eq!(res["unread"], 3);
+
+    mark_all_read(&test_user, &test_user_kp).await?;
+    let res = get_request(&unread_count_path).await?;
+    assert_eq!(res["unread"], 0);
+
+    Ok(())
+}
+
+/// Seeds a follow and a reply notification, then verifies that `types` only lists the
+/// notifications of the requested types.
+#[tokio_shared_rt::test(shared)]
+async fn test_get_notifications_by_types() -> Result<()> {
+    env_init().await;
+    const TEST_USER: &str = "test_notif_types_recipient_00000000001";
+    const FOLLOWER: &str = "test_notif_types_follower_00000000001";
+    const REPLIER: &str = "test_notif_types_replier_00000000001";
+
+    seed_follow(TEST_USER, FOLLOWER, 1000).await?;
+    let reply = NotificationBody::Reply {
+        replied_by: REPLIER.to_string(),
+        parent_post_uri: format!("pubky://{TEST_USER}/pub/pubky.app/posts/0000000000001"),
+        reply_uri: format!("pubky://{REPLIER}/pub/pubky.app/posts/0000000000002"),
+    };
+    let json = serde_json::to_string(&reply).unwrap();
+    Notification::put_index_sorted_set(
+        &["Notification", TEST_USER],
+        &[(2000.0, json.as_str())],
+        None,
+        None,
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+    // The notifications stored without the type index are listed by type once reindexed
+    Notification::reindex_types(TEST_USER)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let all = get_request(&format!("/v0/user/{TEST_USER}/notifications")).await?;
+    assert_eq!(all.as_array().unwrap().len(), 2);
+
+    let follows = get_request(&format!("/v0/user/{TEST_USER}/notifications?types=follow")).await?;
+    let follows = follows.as_array().unwrap();
+    assert_eq!(follows.len(), 1);
+    assert_eq!(follows[0]["body"]["type"], "follow");
+    assert_eq!(follows[0]["body"]["followed_by"], FOLLOWER);
+
+    let both = get_request(&format!(
+        "/v0/user/{TEST_USER}/notifications?types=follow,reply"
+    ))
+    .await?;
+    let both = both.as_array().unwrap();
+    assert_eq!(both.len(), 2);
+    // The notifications of the types are merged newest first
+    assert_eq!(both[0]["body"]["type"], "reply");
+    assert_eq!(both[1]["body"]["type"], "follow");
+
+    let second = get_request(&format!(
+        "/v0/user/{TEST_USER}/notifications?types=follow,reply&skip=1&limit=1"
+    ))
+    .await?;
+    let second = second.as_array().unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0]["body"]["type"], "follow");
+
+    // Skip applies to the notifications of the requested types
+    let skipped = get_request(&format!(
+        "/v0/user/{TEST_USER}/notifications?types=follow&skip=1"
+    ))
+    .await?;
+    assert!(skipped.as_array().unwrap().is_empty());
+
+    invalid_get_request(
+        &format!("/v0/user/{TEST_USER}/notifications?types=follow,unknown"),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
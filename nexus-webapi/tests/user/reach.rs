@@ -1,6 +1,7 @@
-use crate::utils::{get_request, invalid_get_request};
+use crate::utils::{get_request, invalid_get_request, invalid_post_request, post_request};
 use anyhow::Result;
 use axum::http::StatusCode;
+use serde_json::json;
 
 #[tokio_shared_rt::test(shared)]
 async fn test_get_followers() -> Result<()> {
@@ -151,3 +152,28 @@ async fn test_get_friends() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_users_following_status() -> Result<()> {
+    // Amsterdam follows Bogota and Cairo, but not Detroit
+    let amsterdam = "emq37ky6fbnaun7q1ris6rx3mqmw3a33so1txfesg9jj3ak9ryoy";
+    let bogota = "ep441mndnsjeesenwz78r9paepm6e4kqm4ggiyy9uzpoe43eu9ny";
+    let cairo = "f5tcy5gtgzshipr6pag6cn9uski3s8tjare7wd3n7enmyokgjk1o";
+    let detroit = "7w4hmktqa7gia5thmk7zki8px7ttwpwjtgaaaou4tbqx64re8d1o";
+
+    let body = json!({ "viewer_id": amsterdam, "user_ids": [bogota, detroit, cairo] });
+    let res = post_request("/v0/users/following-status", body).await?;
+    assert_eq!(res, json!([true, false, true]));
+
+    // A viewer who does not exist follows nobody
+    let body = json!({ "viewer_id": "bad_user_id", "user_ids": [bogota] });
+    let res = post_request("/v0/users/following-status", body).await?;
+    assert_eq!(res, json!([false]));
+
+    // The list of users is capped
+    let user_ids = vec![bogota; 101];
+    let body = json!({ "viewer_id": amsterdam, "user_ids": user_ids });
+    invalid_post_request("/v0/users/following-status", body, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
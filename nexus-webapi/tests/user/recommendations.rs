@@ -0,0 +1,38 @@
+use crate::utils::{get_request, invalid_get_request};
+use anyhow::Result;
+use axum::http::StatusCode;
+use nexus_webapi::routes::v0::endpoints::USER_RECOMMENDATIONS_ROUTE;
+use pubky::Keypair;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_recommendations() -> Result<()> {
+    // Aldert
+    let user_id = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+    let path = USER_RECOMMENDATIONS_ROUTE.replace("{user_id}", user_id);
+
+    let body = get_request(&format!("{path}?limit=5")).await?;
+    let recommendations = body.as_array().expect("Recommendations should be an array");
+    assert!(recommendations.len() <= 5);
+
+    let mut previous = u64::MAX;
+    for recommendation in recommendations {
+        assert_ne!(recommendation["user_id"], user_id);
+        let shared_follows = recommendation["shared_follows"].as_u64().unwrap();
+        assert!(shared_follows > 0);
+        assert!(
+            shared_follows <= previous,
+            "Recommendations should be sorted by shared follows"
+        );
+        previous = shared_follows;
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_recommendations_unknown_user() -> Result<()> {
+    let user_id = Keypair::random().public_key().to_z32();
+    let path = USER_RECOMMENDATIONS_ROUTE.replace("{user_id}", &user_id);
+    invalid_get_request(&path, StatusCode::NOT_FOUND).await?;
+    Ok(())
+}
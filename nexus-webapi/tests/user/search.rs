@@ -2,7 +2,7 @@ use crate::utils::{get_request, invalid_get_request};
 use anyhow::Result;
 use axum::http::StatusCode;
 use nexus_webapi::routes::v0::{
-    endpoints::{SEARCH_USERS_BY_ID_ROUTE, SEARCH_USERS_BY_NAME_ROUTE},
+    endpoints::{SEARCH_USERS_BY_ID_ROUTE, SEARCH_USERS_BY_NAME_ROUTE, SEARCH_USERS_ROUTE},
     search::USER_ID_SEARCH_MIN_PREFIX_LEN,
 };
 
@@ -48,6 +48,27 @@ async fn test_search_users_by_username() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_search_users_by_username_with_total() -> Result<()> {
+    let url_path = format!(
+        "{}?limit=1&with_total=true",
+        format_search_users_by_name_prefix("Jo")
+    );
+    let res = get_request(&url_path).await?;
+
+    assert_eq!(
+        res["ids"],
+        serde_json::json!(["y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy"])
+    );
+    assert_eq!(res["total"], 2);
+
+    // Without the flag, the response is still the plain list of IDs
+    let res = get_request(&format_search_users_by_name_prefix("Jo")).await?;
+    assert!(res.is_array());
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_search_users_by_id_empty_id() -> Result<()> {
     let url_path = format_search_users_by_id_prefix("");
@@ -150,3 +171,38 @@ async fn test_search_empty_username() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_search_users_combined() -> Result<()> {
+    // "dz" is the username prefix of two users and the ID prefix of another one
+    let name_match = "f8r8pf61kh7cpirthz7e1ztqzr8qg5yf7pbnbeifjx4exdqaekpo";
+    let other_name_match = "gxk8itzrnikrpshfsudgsgtxrz59ojp4iwmp4w9iff3ess6zfr4y";
+    let id_match = "dzz8cshisfst7dthpy7eio9a3byecmrym1ymn75hwqt67a9fs7zo";
+
+    // Below the min ID prefix length, only usernames are searched
+    let res = get_request(&format!("{SEARCH_USERS_ROUTE}?q=dz")).await?;
+    assert_eq!(res, serde_json::json!([name_match, other_name_match]));
+
+    let res = get_request(&format!("{SEARCH_USERS_ROUTE}?q=dzz")).await?;
+    assert_eq!(res, serde_json::json!([id_match]));
+
+    // An exact ID match is returned even if no username matches
+    let res = get_request(&format!("{SEARCH_USERS_ROUTE}?q={id_match}")).await?;
+    assert_eq!(res, serde_json::json!([id_match]));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_search_users_combined_empty_query() -> Result<()> {
+    let res = invalid_get_request(
+        &format!("{SEARCH_USERS_ROUTE}?q=%20"),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+    assert!(res["error"]
+        .as_str()
+        .unwrap_or("")
+        .contains("Search query cannot be empty"));
+    Ok(())
+}
@@ -1,9 +1,23 @@
 use crate::{
     tags::user::PUBKY_PEER,
-    utils::{get_request, invalid_get_request},
+    utils::{
+        conditional_get_request, get_request, invalid_get_request, invalid_post_request,
+        post_request,
+    },
 };
 use anyhow::Result;
 use axum::http::StatusCode;
+use nexus_common::db::RedisOps;
+use nexus_common::models::tag::traits::{TagCollection, TaggersCollection};
+use nexus_common::models::tag::user::{TagUser, USER_TAGS_KEY_PARTS};
+use nexus_common::models::tag::TagDetails;
+use nexus_common::models::user::{
+    UserCounts, UserDetails, USER_DELETED_SENTINEL, USER_FOLLOWER_HISTORY_KEY_PARTS,
+};
+use nexus_webapi::routes::v0::endpoints::USERS_COUNTS_ROUTE;
+use pubky::Keypair;
+use pubky_app_specs::PubkyId;
+use serde_json::json;
 
 #[tokio_shared_rt::test(shared)]
 async fn test_user_endpoint() -> Result<()> {
@@ -25,6 +39,7 @@ async fn test_user_endpoint() -> Result<()> {
     assert_eq!(res["counts"]["followers"], 10);
     assert_eq!(res["counts"]["friends"], 8);
     assert_eq!(res["counts"]["bookmarks"], 0);
+    assert_eq!(res["following_follower_ratio"], 1.5);
 
     // Test tags on Ar's profile
     let ar_id = "pxnu33x7jtpx9ar1ytsi4yxbp6a5o36gwhffs8zoxmbuptici1jy";
@@ -133,6 +148,158 @@ async fn test_get_counts() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_get_users_counts() -> Result<()> {
+    let aldert = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+    let flavio = "5g3fwnue819wfdjwiwm8qr35ww6uxxgbzrigrtdgmbi19ksioeoy";
+    let body = post_request(
+        USERS_COUNTS_ROUTE,
+        json!({ "user_ids": [aldert, "bad_user_id", flavio] }),
+    )
+    .await?;
+
+    // One entry per user, in the same order
+    let counts = body.as_array().expect("Counts should be an array");
+    assert_eq!(counts.len(), 3);
+    for (entry, user_id) in [(&counts[0], aldert), (&counts[2], flavio)] {
+        let expected = get_request(&format!("/v0/user/{user_id}/counts")).await?;
+        assert_eq!(entry, &expected);
+    }
+    assert!(counts[1].is_null());
+
+    let user_ids: Vec<&str> = vec![aldert; 101];
+    invalid_post_request(
+        USERS_COUNTS_ROUTE,
+        json!({ "user_ids": user_ids }),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_follower_history() -> Result<()> {
+    crate::utils::server::TestServiceServer::get_test_server().await;
+
+    let user_id = Keypair::random().public_key().to_z32();
+    UserCounts {
+        followers: 7,
+        ..Default::default()
+    }
+    .put_index_json(&[&user_id], None, None)
+    .await
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    // Snapshots taken on three distinct days, two of them within the first day
+    const DAY: i64 = 86_400_000;
+    const HOUR: i64 = 3_600_000;
+    let day0 = 1_699_920_000_000;
+    let snapshots = [
+        (day0 + HOUR, 3),
+        (day0 + 5 * HOUR, 5),
+        (day0 + DAY + 2 * HOUR, 4),
+        (day0 + 3 * DAY, 7),
+    ];
+    let members: Vec<(f64, String)> = snapshots
+        .iter()
+        .map(|(timestamp, followers)| (*timestamp as f64, format!("{timestamp}:{followers}")))
+        .collect();
+    let members: Vec<(f64, &str)> = members
+        .iter()
+        .map(|(score, member)| (*score, member.as_str()))
+        .collect();
+    let key_parts = [&USER_FOLLOWER_HISTORY_KEY_PARTS[..], &[user_id.as_str()]].concat();
+    UserCounts::put_index_sorted_set(&key_parts, &members, None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    // Without a bucket, every snapshot is returned as recorded
+    let res = get_request(&format!("/v0/user/{user_id}/followers/history")).await?;
+    assert_eq!(
+        res,
+        json!([
+            { "timestamp": day0 + HOUR, "followers": 3 },
+            { "timestamp": day0 + 5 * HOUR, "followers": 5 },
+            { "timestamp": day0 + DAY + 2 * HOUR, "followers": 4 },
+            { "timestamp": day0 + 3 * DAY, "followers": 7 },
+        ])
+    );
+
+    // Daily buckets keep the latest count of each day
+    let res = get_request(&format!(
+        "/v0/user/{user_id}/followers/history?bucket={DAY}"
+    ))
+    .await?;
+    assert_eq!(
+        res,
+        json!([
+            { "timestamp": day0, "followers": 5 },
+            { "timestamp": day0 + DAY, "followers": 4 },
+            { "timestamp": day0 + 3 * DAY, "followers": 7 },
+        ])
+    );
+
+    // The timeframe bounds are inclusive
+    let res = get_request(&format!(
+        "/v0/user/{user_id}/followers/history?from={}&to={}&bucket={DAY}",
+        day0 + DAY + 2 * HOUR,
+        day0 + 3 * DAY
+    ))
+    .await?;
+    assert_eq!(
+        res,
+        json!([
+            { "timestamp": day0 + DAY, "followers": 4 },
+            { "timestamp": day0 + 3 * DAY, "followers": 7 },
+        ])
+    );
+
+    let member_ids: Vec<&str> = members.iter().map(|(_, member)| *member).collect();
+    UserCounts::remove_from_index_sorted_set(None, &key_parts, &member_ids)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    UserCounts::remove_from_index_multiple_json(&[&[user_id.as_str()]])
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    // Test non-existing user
+    let user_id = "bad_user_id";
+    invalid_get_request(
+        &format!("/v0/user/{user_id}/followers/history"),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_follower_stats() -> Result<()> {
+    let user_id = "pxnu33x7jtpx9ar1ytsi4yxbp6a5o36gwhffs8zoxmbuptici1jy";
+    let res = get_request(&format!(
+        "/v0/user/{user_id}/follower-stats?timeframe=this_month"
+    ))
+    .await?;
+    assert!(res["followers_gained"].is_number());
+
+    invalid_get_request(
+        &format!("/v0/user/{user_id}/follower-stats?timeframe=this_week"),
+        StatusCode::BAD_REQUEST,
+    )
+    .await?;
+
+    // Test non-existing user
+    let user_id = "bad_user_id";
+    invalid_get_request(
+        &format!("/v0/user/{user_id}/follower-stats"),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_get_details() -> Result<()> {
     let user_id = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
@@ -155,3 +322,108 @@ async fn test_get_details() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_deleted_user_gone() -> Result<()> {
+    // A user deleted while they had relationships is kept with their name replaced
+    let user_id = Keypair::random().public_key().to_z32();
+    UserDetails {
+        name: USER_DELETED_SENTINEL.to_string(),
+        id: PubkyId::try_from(user_id.as_str()).map_err(|e| anyhow::anyhow!("{e}"))?,
+        indexed_at: 1_700_000_000_000,
+        ..Default::default()
+    }
+    .put_index_json(&[&user_id], None, None)
+    .await?;
+
+    let view = invalid_get_request(&format!("/v0/user/{user_id}"), StatusCode::GONE).await;
+    let details =
+        invalid_get_request(&format!("/v0/user/{user_id}/details"), StatusCode::GONE).await;
+    UserDetails::remove_from_index_multiple_json(&[&[&user_id]]).await?;
+
+    view?;
+    details?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_user_view_etag() -> Result<()> {
+    crate::utils::server::TestServiceServer::get_test_server().await;
+
+    let user_kp = Keypair::random();
+    let user_id = user_kp.public_key().to_z32();
+    UserDetails {
+        name: "ETag".to_string(),
+        id: PubkyId::try_from(user_id.as_str()).map_err(|e| anyhow::anyhow!("{e}"))?,
+        indexed_at: 1_700_000_000_000,
+        ..Default::default()
+    }
+    .put_index_json(&[&user_id], None, None)
+    .await?;
+    UserCounts::default()
+        .put_index_json(&[&user_id], None, None)
+        .await?;
+    let path = format!("/v0/user/{user_id}");
+    let tagger_id = Keypair::random().public_key().to_z32();
+
+    // Every request runs before the cleanup, and the assertions after it
+    let responses = async {
+        let first = conditional_get_request(&path, None).await?;
+        let etag = first.1.clone().unwrap_or_default();
+        let unchanged = conditional_get_request(&path, Some(&etag)).await?;
+
+        let tags = vec![TagDetails {
+            label: "etag".to_string(),
+            taggers: vec![tagger_id.clone()],
+            taggers_count: 1,
+            relationship: false,
+        }];
+        TagUser::put_to_index(&user_id, None, &tags, false).await?;
+        UserCounts {
+            tags: 1,
+            unique_tags: 1,
+            ..Default::default()
+        }
+        .put_index_json(&[&user_id], None, None)
+        .await?;
+        let tagged = conditional_get_request(&path, Some(&etag)).await?;
+
+        anyhow::Ok((first, unchanged, tagged))
+    }
+    .await;
+
+    UserDetails::remove_from_index_multiple_json(&[&[&user_id]]).await?;
+    UserCounts::delete(&user_id).await?;
+    TagUser(vec![tagger_id])
+        .del_from_index(&user_id, None, "etag")
+        .await?;
+    TagUser::remove_from_index_sorted_set(
+        None,
+        &[&USER_TAGS_KEY_PARTS[..], &[&user_id]].concat(),
+        &["etag"],
+    )
+    .await?;
+    let (first, unchanged, tagged) = responses?;
+
+    // The first request gets the profile and its ETag
+    let (status, etag, body) = first;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains(&user_id));
+    let etag = etag.expect("The profile should carry an ETag");
+    assert!(etag.starts_with("W/\""));
+
+    // While nothing changed, the client's copy is fresh
+    let (status, not_modified_etag, body) = unchanged;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(not_modified_etag.as_deref(), Some(etag.as_str()));
+    assert!(body.is_empty());
+
+    // Once the user is tagged, the profile and its ETag change
+    let (status, tagged_etag, body) = tagged;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("\"etag\""));
+    assert_ne!(tagged_etag.as_deref(), Some(etag.as_str()));
+
+    Ok(())
+}
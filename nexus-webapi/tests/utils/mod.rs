@@ -1,4 +1,9 @@
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
 use axum::http::{Method, StatusCode};
+use nexus_common::db::RedisOps;
+use nexus_webapi::models::auth::authorization;
+use pubky::Keypair;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use server::TestServiceServer;
 
@@ -56,6 +61,97 @@ pub async fn invalid_post_request(
     Ok(body)
 }
 
+pub async fn put_request(endpoint: &str, data: Value) -> Result<Value, httpc_test::Error> {
+    let url = host_url().await;
+    let full_endpoint = format!("{url}{endpoint}");
+    let body = inner_make_request(&full_endpoint, Some(Method::PUT), Some(data), None).await?;
+    Ok(body)
+}
+
+pub async fn invalid_put_request(
+    endpoint: &str,
+    data: Value,
+    error_code: StatusCode,
+) -> Result<Value, httpc_test::Error> {
+    let url = host_url().await;
+    let full_endpoint = format!("{url}{endpoint}");
+    let body = inner_make_request(
+        &full_endpoint,
+        Some(Method::PUT),
+        Some(data),
+        Some(error_code),
+    )
+    .await?;
+    Ok(body)
+}
+
+/// Sends a GET request with an optional `If-None-Match`, returning the status, `ETag` and body
+pub async fn conditional_get_request(
+    endpoint: &str,
+    if_none_match: Option<&str>,
+) -> anyhow::Result<(StatusCode, Option<String>, String)> {
+    let test_server = TestServiceServer::get_test_server().await;
+    let client = test_server.testnet.client_builder().build()?;
+    let url = format!("{}{endpoint}", test_server.nexus_api.icann_http_url());
+    let mut request = client.request(Method::GET, &url);
+    if let Some(etag) = if_none_match {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    let response = request.send().await?;
+    let status = StatusCode::from_u16(response.status().as_u16())?;
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .map(|value| value.to_str().unwrap().to_string());
+    Ok((status, etag, response.text().await?))
+}
+
+/// Sends a request signed with the key of a user, returning the status and the JSON body
+pub async fn signed_request(
+    method: Method,
+    endpoint: &str,
+    keypair: &Keypair,
+    data: Option<Value>,
+) -> anyhow::Result<(StatusCode, Value)> {
+    let test_server = TestServiceServer::get_test_server().await;
+    let client = test_server.testnet.client_builder().build()?;
+    let url = format!("{}{endpoint}", test_server.nexus_api.icann_http_url());
+    let body = data.map(|data| data.to_string()).unwrap_or_default();
+    let signature = authorization(keypair, &method, endpoint, body.as_bytes());
+
+    let mut request = client
+        .request(method, &url)
+        .header(AUTHORIZATION, signature);
+    if !body.is_empty() {
+        request = request.header(CONTENT_TYPE, "application/json").body(body);
+    }
+    let response = request.send().await?;
+    let status = StatusCode::from_u16(response.status().as_u16())?;
+    let body = serde_json::from_str(&response.text().await?).unwrap_or(Value::Null);
+    Ok((status, body))
+}
+
+// #######################################
+// ######### Index fixtures ##############
+// #######################################
+
+/// JSON of the wrong shape, so that reading the details stored in its place fails
+#[derive(Serialize, Deserialize)]
+struct CorruptDetails {
+    id: u64,
+}
+
+impl RedisOps for CorruptDetails {}
+
+/// Stores a corrupt JSON under `prefix` (e.g. `User:Details`), to make the resource at
+/// `key_parts` fail to load
+pub async fn put_corrupt_details(prefix: &str, key_parts: &[&str]) -> anyhow::Result<()> {
+    CorruptDetails { id: 0 }
+        .put_index_json(key_parts, Some(prefix.to_string()), None)
+        .await?;
+    Ok(())
+}
+
 // Small helper function to send requests.
 async fn inner_make_request(
     endpoint: &str,
@@ -1,7 +1,9 @@
 use std::net::SocketAddr;
 
 use anyhow::Result;
-use nexus_common::{get_files_dir_test_pathbuf, ApiConfig};
+use nexus_common::{
+    get_files_dir_test_pathbuf, ApiConfig, CorsConfig, RateLimitConfig, RateLimitRule,
+};
 use nexus_webapi::{api_context::ApiContextBuilder, NexusApi, NexusApiBuilder};
 use tokio::sync::OnceCell;
 
@@ -13,10 +15,26 @@ pub struct TestServiceServer {
     pub testnet: pubky_testnet::Testnet,
 }
 
+/// Token of the user data export, see [ApiConfig::export_token]
+pub const TEST_EXPORT_TOKEN: &str = "test_export_token";
+/// Only origin allowed by the [TestServiceServer] with a CORS allowlist
+pub const TEST_CORS_ORIGIN: &str = "https://app.example.com";
+/// Paths limited by the rate limited [TestServiceServer]
+pub const TEST_RATE_LIMIT_PATH: &str = "/v0/info";
+/// Requests allowed per [TEST_RATE_LIMIT_WINDOW_SECS] by the rate limited [TestServiceServer]
+pub const TEST_RATE_LIMIT_REQUESTS: u32 = 3;
+pub const TEST_RATE_LIMIT_WINDOW_SECS: u64 = 2;
+
 /// [TestServiceServer] with no key republisher
 static TEST_SERVER: OnceCell<TestServiceServer> = OnceCell::const_new();
 /// [TestServiceServer] where the [NexusApi] is initialized with a key republisher
 static TEST_SERVER_WITH_KEY_REPUBLISHER: OnceCell<TestServiceServer> = OnceCell::const_new();
+/// [TestServiceServer] where the [NexusApi] is in read-only mode
+static TEST_SERVER_READ_ONLY: OnceCell<TestServiceServer> = OnceCell::const_new();
+/// [TestServiceServer] where the [NexusApi] only allows [TEST_CORS_ORIGIN]
+static TEST_SERVER_CORS: OnceCell<TestServiceServer> = OnceCell::const_new();
+/// [TestServiceServer] where the [NexusApi] rate limits [TEST_RATE_LIMIT_PATH]
+static TEST_SERVER_RATE_LIMITED: OnceCell<TestServiceServer> = OnceCell::const_new();
 
 impl TestServiceServer {
     /// Returns a test server with no [KeyRepublisher]. This is the default setup used in most tests.
@@ -24,7 +42,74 @@ impl TestServiceServer {
         TEST_SERVER
             .get_or_init(|| async {
                 let testnet = pubky_testnet::Testnet::new().await.unwrap();
-                let nexus_api = Self::start_server(&testnet, false).await.unwrap();
+                let nexus_api = Self::start_server(&testnet, false, ApiConfig::default())
+                    .await
+                    .unwrap();
+                TestServiceServer { nexus_api, testnet }
+            })
+            .await
+    }
+
+    /// Returns a test server in read-only mode, where the write endpoints are rejected
+    pub async fn get_read_only_test_server() -> &'static TestServiceServer {
+        TEST_SERVER_READ_ONLY
+            .get_or_init(|| async {
+                let testnet = pubky_testnet::Testnet::new().await.unwrap();
+                let api_config = ApiConfig {
+                    read_only: true,
+                    ..Default::default()
+                };
+                let nexus_api = Self::start_server(&testnet, false, api_config)
+                    .await
+                    .unwrap();
+                TestServiceServer { nexus_api, testnet }
+            })
+            .await
+    }
+
+    /// Returns a test server whose CORS allowlist only contains [TEST_CORS_ORIGIN]
+    pub async fn get_cors_test_server() -> &'static TestServiceServer {
+        TEST_SERVER_CORS
+            .get_or_init(|| async {
+                let testnet = pubky_testnet::Testnet::new().await.unwrap();
+                let cors = CorsConfig {
+                    allowed_origins: vec![TEST_CORS_ORIGIN.to_string()],
+                    allowed_methods: vec!["GET".to_string(), "POST".to_string()],
+                    ..Default::default()
+                };
+                let api_config = ApiConfig {
+                    cors,
+                    ..Default::default()
+                };
+                let nexus_api = Self::start_server(&testnet, false, api_config)
+                    .await
+                    .unwrap();
+                TestServiceServer { nexus_api, testnet }
+            })
+            .await
+    }
+
+    /// Returns a test server that rate limits [TEST_RATE_LIMIT_PATH], per client IP and viewer.
+    /// The client IP is read from `X-Forwarded-For`, so that tests can simulate several clients
+    pub async fn get_rate_limited_test_server() -> &'static TestServiceServer {
+        TEST_SERVER_RATE_LIMITED
+            .get_or_init(|| async {
+                let testnet = pubky_testnet::Testnet::new().await.unwrap();
+                let rate_limit = RateLimitConfig {
+                    rules: vec![RateLimitRule {
+                        path_prefix: TEST_RATE_LIMIT_PATH.to_string(),
+                        requests: TEST_RATE_LIMIT_REQUESTS,
+                        window_secs: TEST_RATE_LIMIT_WINDOW_SECS,
+                    }],
+                    trust_forwarded_for: true,
+                };
+                let api_config = ApiConfig {
+                    rate_limit,
+                    ..Default::default()
+                };
+                let nexus_api = Self::start_server(&testnet, false, api_config)
+                    .await
+                    .unwrap();
                 TestServiceServer { nexus_api, testnet }
             })
             .await
@@ -38,7 +123,9 @@ impl TestServiceServer {
         TEST_SERVER_WITH_KEY_REPUBLISHER
             .get_or_init(|| async {
                 let testnet = pubky_testnet::Testnet::new().await.unwrap();
-                let nexus_api = Self::start_server(&testnet, true).await.unwrap();
+                let nexus_api = Self::start_server(&testnet, true, ApiConfig::default())
+                    .await
+                    .unwrap();
                 TestServiceServer { nexus_api, testnet }
             })
             .await
@@ -47,12 +134,14 @@ impl TestServiceServer {
     async fn start_server(
         testnet: &pubky_testnet::Testnet,
         enable_key_republisher: bool,
+        api_config: ApiConfig,
     ) -> Result<NexusApi> {
         let test_api_config = ApiConfig {
             // When we define the sockets, use local port 0 so OS assigns an available port
             public_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
             pubky_listen_socket: SocketAddr::from(([127, 0, 0, 1], 0)),
-            ..Default::default()
+            export_token: Some(TEST_EXPORT_TOKEN.to_string()),
+            ..api_config
         };
 
         // Every time we start a test server, use a new temp config dir, which is automatically removed after the tests
@@ -0,0 +1,43 @@
+use crate::cli::{AuditArgs, AuditIndex};
+use nexus_common::models::post::PostStream;
+use nexus_common::models::user::UserSearch;
+use nexus_common::types::DynError;
+
+/// Audits an index for entries without a backing graph node, printing them as they are found.
+///
+/// Stops after `max_batches` batches, printing the cursor to pass to resume the audit.
+pub async fn audit_orphans(args: AuditArgs) -> Result<(), DynError> {
+    let mut cursor = args.cursor;
+    let mut scanned = 0;
+    let mut orphans = 0;
+
+    for _ in 0..args.max_batches {
+        let report = match args.index {
+            AuditIndex::Users => UserSearch::find_orphans(cursor, args.batch_size).await?,
+            AuditIndex::Posts => PostStream::find_orphans(cursor, args.batch_size).await?,
+        };
+
+        scanned += report.scanned;
+        orphans += report.orphans.len();
+        for orphan in &report.orphans {
+            println!("orphan {orphan}");
+        }
+
+        match report.next_cursor {
+            Some(next_cursor) => cursor = next_cursor,
+            None => {
+                println!(
+                    "Audit of {:?} complete: {scanned} entries checked, {orphans} orphans",
+                    args.index
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    println!(
+        "Audit of {:?} paused after {} batches: {scanned} entries checked, {orphans} orphans. Resume with --cursor {cursor}",
+        args.index, args.max_batches
+    );
+    Ok(())
+}
@@ -1,4 +1,5 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use nexus_common::db::reindex::DEFAULT_REINDEX_BATCH_SIZE;
 use nexus_common::file::{default_config_dir_path, validate_and_expand_path};
 use nexus_webapi::mock::MockType;
 use std::path::PathBuf;
@@ -75,6 +76,16 @@ pub enum DbCommands {
     /// Mock the database (optional redis/graph). Usually for tests
     Mock(MockArgs),
 
+    /// Rebuild the Redis indexes from the graph
+    Reindex(ReindexArgs),
+
+    /// Report Redis index entries without a backing graph node. Read-only
+    Audit(AuditArgs),
+
+    /// Manage tag aliases, whose labels are indexed under a canonical label
+    #[command(subcommand)]
+    TagAlias(TagAliasCommands),
+
     /// Manage database migrations
     #[command(subcommand)]
     Migration(MigrationCommands),
@@ -87,6 +98,70 @@ pub struct MockArgs {
     pub mock_type: Option<MockType>,
 }
 
+#[derive(Args, Debug)]
+pub struct ReindexArgs {
+    /// Number of users or posts read from the graph and reindexed at a time
+    #[arg(long, default_value_t = DEFAULT_REINDEX_BATCH_SIZE)]
+    pub batch_size: usize,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum AuditIndex {
+    /// User IDs of the user search index
+    Users,
+    /// Post keys of the global post timeline
+    Posts,
+}
+
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    /// Index to audit
+    #[arg(long, value_enum)]
+    pub index: AuditIndex,
+
+    /// Approximate number of index entries checked against the graph per batch
+    #[arg(long, default_value_t = 1000)]
+    pub batch_size: usize,
+
+    /// Cursor printed by a previous audit, to resume it
+    #[arg(long, default_value_t = 0)]
+    pub cursor: u64,
+
+    /// Stop after this many batches, printing the cursor to resume from
+    #[arg(long, default_value_t = 100)]
+    pub max_batches: usize,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TagAliasCommands {
+    /// Make a label an alias of another one, folding the existing tags of the alias
+    Add(TagAliasAddArgs),
+
+    /// Remove an alias. Tags folded when it was added keep the canonical label
+    Remove(TagAliasRemoveArgs),
+
+    /// List all aliases with their canonical label
+    List,
+}
+
+#[derive(Args, Debug)]
+pub struct TagAliasAddArgs {
+    /// The label to alias, e.g. `js`
+    #[arg(required = true)]
+    pub alias: String,
+
+    /// The label the alias resolves to, e.g. `javascript`
+    #[arg(required = true)]
+    pub target: String,
+}
+
+#[derive(Args, Debug)]
+pub struct TagAliasRemoveArgs {
+    /// The alias to remove
+    #[arg(required = true)]
+    pub alias: String,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum MigrationCommands {
     /// Create a new migration with a required migration name
@@ -94,6 +169,12 @@ pub enum MigrationCommands {
 
     /// Run pending migrations
     Run,
+
+    /// Roll back the most recently applied migrations
+    Rollback(MigrationRollbackArgs),
+
+    /// List all known migrations, marking which are applied and which are pending
+    Status,
 }
 
 #[derive(Args, Debug)]
@@ -102,3 +183,10 @@ pub struct MigrationNewArgs {
     #[arg(required = true)]
     pub name: String,
 }
+
+#[derive(Args, Debug)]
+pub struct MigrationRollbackArgs {
+    /// Number of applied migrations to roll back, most recent first
+    #[arg(long, default_value_t = 1)]
+    pub steps: usize,
+}
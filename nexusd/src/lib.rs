@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod cli;
 mod launcher;
 pub mod migrations;
@@ -1,10 +1,18 @@
+use chrono::DateTime;
 use clap::Parser;
+use nexus_common::db::reindex;
+use nexus_common::models::tag::alias::TagAlias;
 use nexus_common::types::DynError;
-use nexus_common::StackManager;
+use nexus_common::{StackConfig, StackManager};
 use nexus_watcher::service::NexusWatcher;
 use nexus_webapi::mock::MockDb;
 use nexus_webapi::NexusApi;
-use nexusd::cli::{ApiArgs, Cli, DbCommands, MigrationCommands, NexusCommands, WatcherArgs};
+use nexusd::audit::audit_orphans;
+use nexusd::cli::{
+    ApiArgs, Cli, DbCommands, MigrationCommands, NexusCommands, ReindexArgs, TagAliasCommands,
+    WatcherArgs,
+};
+use nexusd::migrations::manager::MigrationStatus;
 use nexusd::migrations::{import_migrations, MigrationBuilder, MigrationManager};
 use nexusd::DaemonLauncher;
 
@@ -16,6 +24,34 @@ async fn main() -> Result<(), DynError> {
         NexusCommands::Db(db_command) => match db_command {
             DbCommands::Clear => MockDb::clear_database().await,
             DbCommands::Mock(args) => MockDb::run(args.mock_type).await,
+            DbCommands::Audit(args) => {
+                StackManager::setup(&StackConfig::default()).await?;
+                audit_orphans(args).await?;
+            }
+            DbCommands::Reindex(ReindexArgs { batch_size }) => {
+                StackManager::setup(&StackConfig::default()).await?;
+                reindex::sync_in_batches(batch_size).await?;
+            }
+            DbCommands::TagAlias(tag_alias_command) => {
+                StackManager::setup(&StackConfig::default()).await?;
+                match tag_alias_command {
+                    TagAliasCommands::Add(args) => {
+                        let canonical = TagAlias::put(&args.alias, &args.target).await?;
+                        println!("Tag alias {} -> {canonical}", args.alias);
+                    }
+                    TagAliasCommands::Remove(args) => match TagAlias::del(&args.alias).await? {
+                        true => println!("Removed tag alias {}", args.alias),
+                        false => println!("{} is not a tag alias", args.alias),
+                    },
+                    TagAliasCommands::List => {
+                        let mut aliases: Vec<_> = TagAlias::get_all().await?.into_iter().collect();
+                        aliases.sort();
+                        for (alias, canonical) in aliases {
+                            println!("{alias} -> {canonical}");
+                        }
+                    }
+                }
+            }
             DbCommands::Migration(migration_command) => match migration_command {
                 MigrationCommands::New(args) => MigrationManager::new_migration(args.name).await?,
                 MigrationCommands::Run => {
@@ -25,6 +61,22 @@ async fn main() -> Result<(), DynError> {
                     import_migrations(&mut mm);
                     mm.run(&builder.migrations_backfill_ready()).await?;
                 }
+                MigrationCommands::Rollback(args) => {
+                    let builder = MigrationBuilder::default().await?;
+                    StackManager::setup(builder.stack()).await?;
+                    let mut mm = MigrationManager::default();
+                    import_migrations(&mut mm);
+                    for migration_id in mm.rollback(args.steps).await? {
+                        println!("Rolled back migration {migration_id}");
+                    }
+                }
+                MigrationCommands::Status => {
+                    let builder = MigrationBuilder::default().await?;
+                    StackManager::setup(builder.stack()).await?;
+                    let mut mm = MigrationManager::default();
+                    import_migrations(&mut mm);
+                    print_migration_status(&mm.status().await?);
+                }
             },
         },
         NexusCommands::Api(ApiArgs { config_dir }) => {
@@ -40,3 +92,33 @@ async fn main() -> Result<(), DynError> {
 
     Ok(())
 }
+
+/// Prints the migrations status as a table
+fn print_migration_status(statuses: &[MigrationStatus]) {
+    let name_width = statuses
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("MIGRATION".len());
+    println!(
+        "{:<name_width$}  {:<10}  {:<10}  APPLIED AT",
+        "MIGRATION", "STATUS", "PHASE"
+    );
+    for status in statuses {
+        let applied_at = status
+            .applied_at
+            .and_then(DateTime::from_timestamp_millis)
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_else(|| "-".to_string());
+        let state = match status.applied_at {
+            Some(_) => "applied",
+            None => "pending",
+        };
+        let phase = status.phase.as_ref().map_or("-", |phase| phase.to_string());
+        println!(
+            "{:<name_width$}  {state:<10}  {phase:<10}  {applied_at}",
+            status.name
+        );
+    }
+}
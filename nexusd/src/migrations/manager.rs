@@ -33,7 +33,7 @@ impl MigrationPhase {
         }
     }
 
-    fn to_string(&self) -> &str {
+    pub fn to_string(&self) -> &str {
         match self {
             MigrationPhase::DualWrite => "dual_write",
             MigrationPhase::Backfill => "backfill",
@@ -45,7 +45,7 @@ impl MigrationPhase {
 }
 
 #[async_trait]
-pub trait Migration {
+pub trait Migration: Send + Sync {
     fn id(&self) -> &'static str;
     /*
      * Should be marked as true if the migration is multi-staged.
@@ -77,6 +77,14 @@ pub trait Migration {
      * For redis, this might mean deleting the old keys, if any is left.
      */
     async fn cleanup(&self) -> Result<(), DynError>;
+    /* This method undoes the migration, whatever phase it reached, when it is rolled back with
+     * `db migration rollback`. Once rolled back, the migration is no longer tracked and runs again
+     * from its first phase with the next `db migration run`.
+     * Migrations that cannot be undone keep this default, which fails the rollback.
+     */
+    async fn rollback(&self) -> Result<(), DynError> {
+        Err(format!("Migration {} does not define a rollback step", self.id()).into())
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -87,6 +95,17 @@ pub struct MigrationNode {
     updated_at: i64,
 }
 
+/// Status of a registered migration, as reported by [MigrationManager::status]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MigrationStatus {
+    pub name: String,
+    /// Current phase, `None` if the migration was never run
+    pub phase: Option<MigrationPhase>,
+    /// When the migration reached the [MigrationPhase::Done] phase, as a UNIX timestamp in
+    /// milliseconds. `None` while the migration is pending
+    pub applied_at: Option<i64>,
+}
+
 const MIGRATION_PATH: &str = "nexusd/src/migrations/migrations_list/";
 
 pub struct MigrationManager {
@@ -218,6 +237,63 @@ impl MigrationManager {
         Ok(())
     }
 
+    /// Reverts the last `steps` applied migrations, most recent first.
+    ///
+    /// Applied migrations are the registered migrations tracked in the graph, in their
+    /// registration order, which is the order [MigrationManager::run] applies them in. The
+    /// tracking of every reverted migration is removed, so that a later run applies it again.
+    ///
+    /// Fails on the first migration that does not define a rollback, leaving it and the older
+    /// migrations applied.
+    pub async fn rollback(&self, steps: usize) -> Result<Vec<String>, DynError> {
+        let stored_migrations = self.get_migrations().await?;
+        let applied: Vec<&dyn Migration> = self
+            .migrations
+            .iter()
+            .map(|migration| migration.as_ref())
+            .filter(|migration| stored_migrations.iter().any(|m| m.id == migration.id()))
+            .collect();
+
+        let mut rolled_back = Vec::new();
+        for migration in applied.into_iter().rev().take(steps) {
+            let migration_id = migration.id();
+            info!("Rolling back migration {}...", migration_id);
+            migration
+                .rollback()
+                .await
+                .map_err(|e| format!("Failed to roll back migration {migration_id}: {e}"))?;
+            self.remove_migration(migration_id).await?;
+            info!("Migration {} rolled back successfully!", migration_id);
+            rolled_back.push(migration_id.to_string());
+        }
+        Ok(rolled_back)
+    }
+
+    /// Lists all registered migrations in registration order, marking which are applied and
+    /// which are still pending.
+    ///
+    /// A migration is applied once it has reached the [MigrationPhase::Done] phase. Migrations
+    /// never run, or with a multi-staged run in progress, are pending.
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>, DynError> {
+        let stored_migrations = self.get_migrations().await?;
+        let statuses = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let stored = stored_migrations.iter().find(|m| m.id == migration.id());
+                let applied_at = stored
+                    .filter(|m| m.phase == MigrationPhase::Done)
+                    .map(|m| m.updated_at);
+                MigrationStatus {
+                    name: migration.id().to_string(),
+                    phase: stored.map(|m| m.phase.clone()),
+                    applied_at,
+                }
+            })
+            .collect();
+        Ok(statuses)
+    }
+
     async fn get_migrations(&self) -> Result<Vec<MigrationNode>, DynError> {
         let query = Query::new(
             "get_migrations",
@@ -253,6 +329,14 @@ impl MigrationManager {
         Ok(())
     }
 
+    async fn remove_migration(&self, id: &str) -> Result<(), DynError> {
+        let query = Query::new("remove_migration", "MATCH (m:Migration {id: $id}) DELETE m")
+            .param("id", id);
+
+        self.graph.run(query).await?;
+        Ok(())
+    }
+
     async fn update_migration_phase(
         &self,
         id: &str,
@@ -269,3 +353,192 @@ impl MigrationManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nexus_common::{StackConfig, StackManager};
+
+    struct FirstTestMigration;
+    struct SecondTestMigration;
+
+    #[async_trait]
+    impl Migration for FirstTestMigration {
+        fn id(&self) -> &'static str {
+            "RollbackTestFirst"
+        }
+
+        fn is_multi_staged(&self) -> bool {
+            false
+        }
+
+        async fn dual_write(_data: Box<dyn Any + Send + 'static>) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn backfill(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn cutover(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn cleanup(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Migration for SecondTestMigration {
+        fn id(&self) -> &'static str {
+            "RollbackTestSecond"
+        }
+
+        fn is_multi_staged(&self) -> bool {
+            false
+        }
+
+        async fn dual_write(_data: Box<dyn Any + Send + 'static>) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn backfill(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn cutover(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn cleanup(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn rollback(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+    }
+
+    struct StatusTestMigration(&'static str);
+
+    #[async_trait]
+    impl Migration for StatusTestMigration {
+        fn id(&self) -> &'static str {
+            self.0
+        }
+
+        fn is_multi_staged(&self) -> bool {
+            false
+        }
+
+        async fn dual_write(_data: Box<dyn Any + Send + 'static>) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn backfill(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn cutover(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn cleanup(&self) -> Result<(), DynError> {
+            Ok(())
+        }
+    }
+
+    async fn tracked_test_migrations(mm: &MigrationManager) -> Vec<String> {
+        let mut ids: Vec<String> = mm
+            .get_migrations()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .filter(|id| id.starts_with("RollbackTest"))
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_rollback_last_migration() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let mut mm = MigrationManager::default();
+        mm.register(Box::new(FirstTestMigration));
+        mm.register(Box::new(SecondTestMigration));
+        mm.remove_migration("RollbackTestFirst").await?;
+        mm.remove_migration("RollbackTestSecond").await?;
+
+        mm.run(&[]).await?;
+        assert_eq!(
+            tracked_test_migrations(&mm).await,
+            vec!["RollbackTestFirst", "RollbackTestSecond"]
+        );
+
+        let rolled_back = mm.rollback(1).await?;
+        assert_eq!(rolled_back, vec!["RollbackTestSecond"]);
+        assert_eq!(
+            tracked_test_migrations(&mm).await,
+            vec!["RollbackTestFirst"]
+        );
+
+        // The first migration defines no rollback step
+        let result = mm.rollback(1).await;
+        assert!(result.is_err());
+        assert_eq!(
+            tracked_test_migrations(&mm).await,
+            vec!["RollbackTestFirst"]
+        );
+
+        mm.remove_migration("RollbackTestFirst").await?;
+        Ok(())
+    }
+
+    #[tokio_shared_rt::test(shared)]
+    async fn test_status_applied_and_pending() -> Result<(), DynError> {
+        StackManager::setup(&StackConfig::default()).await?;
+
+        let ids = ["StatusTestFirst", "StatusTestSecond", "StatusTestThird"];
+        let mut mm = MigrationManager::default();
+        for id in ids {
+            mm.remove_migration(id).await?;
+        }
+
+        // Fresh database: every migration is pending
+        for id in ids {
+            mm.register(Box::new(StatusTestMigration(id)));
+        }
+        let status = mm.status().await?;
+        assert_eq!(
+            status.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            ids
+        );
+        assert!(status
+            .iter()
+            .all(|s| s.phase.is_none() && s.applied_at.is_none()));
+
+        // Apply only the second migration
+        let mut partial = MigrationManager::default();
+        partial.register(Box::new(StatusTestMigration(ids[1])));
+        partial.run(&[]).await?;
+
+        let status = mm.status().await?;
+        assert_eq!(
+            status.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            ids
+        );
+        assert!(status[0].applied_at.is_none());
+        assert_eq!(status[1].phase, Some(MigrationPhase::Done));
+        assert!(status[1].applied_at.is_some());
+        assert!(status[2].applied_at.is_none());
+        assert_eq!(status.iter().filter(|s| s.applied_at.is_some()).count(), 1);
+
+        for id in ids {
+            mm.remove_migration(id).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+
+use crate::migrations::{manager::Migration, utils::delete_keys_by_pattern};
+use nexus_common::types::DynError;
+use tracing::info;
+
+/// Removes the file details cached under a single escaped `owner%3Afile` key part.
+///
+/// Files are now cached under separate owner and file ID parts, the key they are deleted with.
+/// The stale entries are dropped rather than renamed: file details are read through from the
+/// graph on a cache miss, so they are cached again under the new key on their next read.
+pub struct FileDetailsKeys1792108800;
+
+#[async_trait]
+impl Migration for FileDetailsKeys1792108800 {
+    fn id(&self) -> &'static str {
+        "FileDetailsKeys1792108800"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let deleted = delete_keys_by_pattern("File:Details:*%3A*", 100).await?;
+        info!(
+            "FileDetailsKeys migration: deleted {} file details keys from Redis",
+            deleted
+        );
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+}
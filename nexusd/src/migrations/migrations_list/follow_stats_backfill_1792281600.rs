@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+
+use crate::migrations::manager::Migration;
+use nexus_common::{db::reindex::get_all_user_ids, models::follow::FollowStats, types::DynError};
+use tracing::{info, warn};
+
+/// Indexes when the existing followers of every user followed them, read from the graph.
+///
+/// The watcher only records the follows it indexes, so without the backfill the follower growth
+/// of a user would ignore everyone that followed them before the upgrade.
+pub struct FollowStatsBackfill1792281600;
+
+#[async_trait]
+impl Migration for FollowStatsBackfill1792281600 {
+    fn id(&self) -> &'static str {
+        "FollowStatsBackfill1792281600"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let user_ids = get_all_user_ids().await?;
+        for user_id in &user_ids {
+            if let Err(e) = FollowStats::reindex(user_id).await {
+                warn!("Failed to index the follow times of the followers of {user_id}: {e}");
+            }
+        }
+        info!(
+            "FollowStatsBackfill migration: indexed the followers of {} users",
+            user_ids.len()
+        );
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+
+use crate::migrations::manager::Migration;
+use nexus_common::{
+    db::reindex::get_all_user_ids,
+    models::follow::{Followers, Following, UserFollows},
+    types::DynError,
+};
+use tracing::{info, warn};
+
+/// Copies the indexed followers and following of every user to the sorted sets their cursor
+/// pages are read from.
+///
+/// New follows are written to both indexes, so without the copy a sorted set would only hold the
+/// follows indexed since the upgrade, and its pages would miss the older ones.
+pub struct FollowsPageIndex1792195200;
+
+#[async_trait]
+impl Migration for FollowsPageIndex1792195200 {
+    fn id(&self) -> &'static str {
+        "FollowsPageIndex1792195200"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let user_ids = get_all_user_ids().await?;
+        for user_id in &user_ids {
+            if let Err(e) = Followers::reindex_page(user_id).await {
+                warn!("Failed to index the followers page of {user_id}: {e}");
+            }
+            if let Err(e) = Following::reindex_page(user_id).await {
+                warn!("Failed to index the following page of {user_id}: {e}");
+            }
+        }
+        info!(
+            "FollowsPageIndex migration: indexed the follows pages of {} users",
+            user_ids.len()
+        );
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+}
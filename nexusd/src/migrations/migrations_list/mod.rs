@@ -1,3 +1,10 @@
 // pub mod tag_counts_reset_1739459180;
+pub mod file_details_keys_1792108800;
+pub mod follow_stats_backfill_1792281600;
+pub mod follows_page_index_1792195200;
+pub mod notification_types_index_1792540800;
+pub mod quoted_posts_reindex_1792368000;
 pub mod remove_muted_1771718400;
+pub mod self_threads_index_1792627200;
+pub mod tagger_labels_backfill_1792454400;
 pub mod users_by_pk_reindex_1751635096;
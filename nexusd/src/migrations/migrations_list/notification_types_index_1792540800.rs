@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+
+use crate::migrations::manager::Migration;
+use nexus_common::{
+    db::reindex::get_all_user_ids, models::notification::Notification, types::DynError,
+};
+use tracing::{info, warn};
+
+/// Indexes the notifications of every user by type.
+///
+/// Listing the notifications of some types reads the per-type sorted sets, which only hold the
+/// notifications stored after the upgrade without the backfill.
+pub struct NotificationTypesIndex1792540800;
+
+#[async_trait]
+impl Migration for NotificationTypesIndex1792540800 {
+    fn id(&self) -> &'static str {
+        "NotificationTypesIndex1792540800"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let user_ids = get_all_user_ids().await?;
+        for user_id in &user_ids {
+            if let Err(e) = Notification::reindex_types(user_id).await {
+                warn!("Failed to index the notifications of {user_id} by type: {e}");
+            }
+        }
+        info!(
+            "NotificationTypesIndex migration: indexed the notifications of {} users",
+            user_ids.len()
+        );
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+
+use crate::migrations::manager::Migration;
+use nexus_common::{
+    db::{fetch_all_rows_from_graph, graph::Query},
+    models::post::PostRelationships,
+    types::DynError,
+};
+use tracing::{info, warn};
+
+/// Reindexes the relationships of the quote-posts, reposts with their own content, so that their
+/// indexed relationships carry the `quoted` post like the ones indexed since.
+pub struct QuotedPostsReindex1792368000;
+
+#[async_trait]
+impl Migration for QuotedPostsReindex1792368000 {
+    fn id(&self) -> &'static str {
+        "QuotedPostsReindex1792368000"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let query = Query::new(
+            "get_quote_post_keys",
+            "MATCH (author:User)-[:AUTHORED]->(p:Post)-[:REPOSTED]->(:Post)
+             WHERE trim(p.content) <> ''
+             RETURN author.id AS author_id, p.id AS post_id",
+        );
+        let rows = fetch_all_rows_from_graph(query).await?;
+
+        let mut reindexed = 0;
+        for row in rows {
+            let author_id: String = row.get("author_id")?;
+            let post_id: String = row.get("post_id")?;
+            match PostRelationships::reindex(&author_id, &post_id).await {
+                Ok(()) => reindexed += 1,
+                Err(e) => {
+                    warn!("Failed to reindex the relationships of {author_id}:{post_id}: {e}")
+                }
+            }
+        }
+        info!(
+            "QuotedPostsReindex migration: reindexed the relationships of {} quote-posts",
+            reindexed
+        );
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+}
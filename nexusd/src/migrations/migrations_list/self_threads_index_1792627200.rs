@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+
+use crate::migrations::manager::Migration;
+use nexus_common::{
+    db::{fetch_all_rows_from_graph, graph::Query},
+    models::post::PostStream,
+    types::DynError,
+};
+use tracing::{info, warn};
+
+/// Indexes the posts their authors continued with replies to themselves, which flag the
+/// self-threads of the author streams.
+///
+/// The watcher only indexes the self-replies it sees, so without the backfill the threads started
+/// before the upgrade would not be flagged.
+pub struct SelfThreadsIndex1792627200;
+
+#[async_trait]
+impl Migration for SelfThreadsIndex1792627200 {
+    fn id(&self) -> &'static str {
+        "SelfThreadsIndex1792627200"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let query = Query::new(
+            "get_self_thread_keys",
+            "MATCH (author:User)-[:AUTHORED]->(:Post)-[:REPLIED]->(p:Post)<-[:AUTHORED]-(author)
+             RETURN DISTINCT author.id AS author_id, p.id AS post_id",
+        );
+        let rows = fetch_all_rows_from_graph(query).await?;
+
+        let mut indexed = 0;
+        for row in rows {
+            let author_id: String = row.get("author_id")?;
+            let post_id: String = row.get("post_id")?;
+            match PostStream::add_to_self_threads_set(&author_id, &post_id).await {
+                Ok(()) => indexed += 1,
+                Err(e) => warn!("Failed to index the self-thread of {author_id}:{post_id}: {e}"),
+            }
+        }
+        info!(
+            "SelfThreadsIndex migration: indexed {} self-threads",
+            indexed
+        );
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+}
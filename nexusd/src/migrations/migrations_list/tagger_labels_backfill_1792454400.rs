@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+
+use crate::migrations::manager::Migration;
+use nexus_common::{
+    db::reindex::get_all_user_ids,
+    models::tag::{traits::TagCollection, user::TagUser},
+    types::DynError,
+};
+use tracing::{info, warn};
+
+/// Indexes the labels every user has applied to posts and users, counted from the graph.
+///
+/// The watcher only counts the tags it indexes, so without the backfill the applied labels of a
+/// user would ignore every tag they placed before the upgrade.
+pub struct TaggerLabelsBackfill1792454400;
+
+#[async_trait]
+impl Migration for TaggerLabelsBackfill1792454400 {
+    fn id(&self) -> &'static str {
+        "TaggerLabelsBackfill1792454400"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let user_ids = get_all_user_ids().await?;
+        for user_id in &user_ids {
+            if let Err(e) = TagUser::reindex_tagger_labels(user_id).await {
+                warn!("Failed to index the labels applied by {user_id}: {e}");
+            }
+        }
+        info!(
+            "TaggerLabelsBackfill migration: indexed the labels of {} users",
+            user_ids.len()
+        );
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        Ok(())
+    }
+}
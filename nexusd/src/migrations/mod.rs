@@ -9,7 +9,14 @@ mod utils;
 pub use builder::MigrationBuilder;
 pub use manager::MigrationManager;
 
+use crate::migrations::migrations_list::file_details_keys_1792108800::FileDetailsKeys1792108800;
+use crate::migrations::migrations_list::follow_stats_backfill_1792281600::FollowStatsBackfill1792281600;
+use crate::migrations::migrations_list::follows_page_index_1792195200::FollowsPageIndex1792195200;
+use crate::migrations::migrations_list::notification_types_index_1792540800::NotificationTypesIndex1792540800;
+use crate::migrations::migrations_list::quoted_posts_reindex_1792368000::QuotedPostsReindex1792368000;
 use crate::migrations::migrations_list::remove_muted_1771718400::RemoveMuted1771718400;
+use crate::migrations::migrations_list::self_threads_index_1792627200::SelfThreadsIndex1792627200;
+use crate::migrations::migrations_list::tagger_labels_backfill_1792454400::TaggerLabelsBackfill1792454400;
 use crate::migrations::migrations_list::users_by_pk_reindex_1751635096::UsersByPkReindex1751635096;
 /// Registers migrations with the `MigrationManager`
 ///
@@ -41,6 +48,13 @@ pub fn import_migrations(migration_manager: &mut MigrationManager) {
         // Note: Add your migrations here to be picked up by the manager
         Box::new(UsersByPkReindex1751635096),
         Box::new(RemoveMuted1771718400),
+        Box::new(FileDetailsKeys1792108800),
+        Box::new(FollowsPageIndex1792195200),
+        Box::new(FollowStatsBackfill1792281600),
+        Box::new(QuotedPostsReindex1792368000),
+        Box::new(TaggerLabelsBackfill1792454400),
+        Box::new(NotificationTypesIndex1792540800),
+        Box::new(SelfThreadsIndex1792627200),
     ];
     for migration in migrations {
         migration_manager.register(migration);